@@ -6,44 +6,199 @@ connection pooling, SSL/TLS encryption, Azure Active Directory authentication, a
 
 # Import from the compiled Rust module
 from .fastmssql import (
+    AuthenticationError,
     Connection as _RustConnection,
+    DatabaseError,
+    DatabaseUnavailableError,
+    FastMssqlError,
+    IntegrityError,
+    InterfaceError,
+    InvalidCredentialsError,
+    LoginDisabledError,
+    OperationalError,
+    PolicyViolation,
+    PoolTimeoutError,
+    ProgrammingError,
+    QueryTimeoutError,
+    ResultLimitError,
 )
 from .fastmssql import (
     AzureCredential,
     AzureCredentialType,
+    Batch,
+    CertificateExpiryWarning,
+    ChunkStream,
+    Composed,
     ConversionError,
+    DataError,
     SqlConnectionError,
     EncryptionLevel,
     FastRow,
+    Identifier,
+    Param,
     Parameter,
     Parameters,
+    PinnedConnection,
     PoolConfig,
     ProtocolError,
     QueryStream,
+    ResultSetStream,
+    RetryPolicy,
+    RowStream,
+    SQL,
     SqlError,
     SslConfig,
+    StatementPolicy,
     TlsError,
     TypedNull,
+    concat,
+    enable_wire_debug,
+    flush_dns_cache,
+    set_dns_cache_ttl,
+    set_certificate_expiry_warning,
+    set_host_aliases,
+    set_proxies,
+    set_resolver,
+    statement_fingerprint,
     version,
 )
 from .fastmssql import (
     Transaction as _RustTransaction,
 )
+from .fastmssql import (
+    diagnose as _diagnose,
+)
 
+import asyncio
+import random
+import threading
 from enum import StrEnum
 
+# SQL Server error numbers after which the whole transaction can safely be re-run:
+# 1205 = chosen as deadlock victim, 3960 = snapshot isolation update conflict.
+_TRANSACTION_RETRY_ERRORS = frozenset({1205, 3960})
+
 
 class ApplicationIntent(StrEnum):
     READ_ONLY = "ReadOnly"
     READ_WRITE = "ReadWrite"
 
 
+class Cursor:
+    """DBAPI 2.0 style cursor over a Connection or Transaction.
+
+    ``execute()`` is a coroutine; the fetch methods are synchronous because the
+    result set is already buffered client-side by the time ``execute()`` returns.
+
+    Example:
+        async with conn.cursor() as cur:
+            await cur.execute("SELECT id, name FROM users WHERE active = @P1", [1])
+            first = cur.fetchone()
+            rest = cur.fetchall()
+    """
+
+    def __init__(self, executor):
+        self._executor = executor
+        self._result = None
+        self._closed = False
+        self.arraysize = 1
+        self.rowcount = -1
+        self.description = None
+
+    def _check_open(self):
+        if self._closed:
+            raise RuntimeError("Cursor is closed")
+
+    def _check_result(self):
+        self._check_open()
+        if self._result is None:
+            raise RuntimeError("No result set: call execute() with a row-returning statement first")
+
+    async def execute(self, sql, params=None):
+        """Run ``sql`` and make its first result set available to the fetch methods.
+
+        ``rowcount`` is the number of rows returned, or -1 when no rows come back
+        (use ``Connection.execute()`` when you need affected counts).
+        """
+        self._check_open()
+        self._result = await self._executor.query(sql, params)
+        if self._result.has_rows():
+            self.rowcount = len(self._result)
+            self.description = tuple(
+                (col["name"], col["type"], None, None, None, None, None)
+                for col in self._result.column_metadata()
+            )
+        else:
+            self.rowcount = -1
+            self.description = None
+        return self
+
+    async def executemany(self, sql, seq_of_params):
+        """Run ``sql`` once per parameter list; ``rowcount`` is the total affected rows."""
+        self._check_open()
+        counts = await self._executor.execute_batch([(sql, params) for params in seq_of_params])
+        self._result = None
+        self.description = None
+        self.rowcount = sum(counts)
+        return self
+
+    def fetchone(self):
+        """Return the next row, or None when the result set is exhausted."""
+        self._check_result()
+        return self._result.fetchone()
+
+    def fetchmany(self, size=None):
+        """Return up to ``size`` (default ``arraysize``) more rows."""
+        self._check_result()
+        return self._result.fetchmany(self.arraysize if size is None else size)
+
+    def fetchall(self):
+        """Return all remaining rows."""
+        self._check_result()
+        return self._result.fetchall()
+
+    def __iter__(self):
+        self._check_result()
+        return self
+
+    def __next__(self):
+        row = self.fetchone()
+        if row is None:
+            raise StopIteration
+        return row
+
+    def setinputsizes(self, sizes):
+        """No-op, for DBAPI compatibility."""
+
+    def setoutputsize(self, size, column=None):
+        """No-op, for DBAPI compatibility."""
+
+    def close(self):
+        """Release the buffered result set; further calls raise RuntimeError."""
+        self._result = None
+        self._closed = True
+
+    async def __aenter__(self):
+        return self
+
+    async def __aexit__(self, exc_type, exc_val, exc_tb):
+        self.close()
+        return False
+
+
 class Connection:
     """Thin wrapper to fix async context manager behavior."""
 
     def __init__(self, *args, **kwargs):
         self._conn = _RustConnection(*args, **kwargs)
 
+    @classmethod
+    def _from_rust(cls, rust_conn):
+        """Wrap an existing Rust connection object (e.g. from with_options())."""
+        self = cls.__new__(cls)
+        self._conn = rust_conn
+        return self
+
     def __getattr__(self, name):
         return getattr(self._conn, name)
 
@@ -54,13 +209,53 @@ class Connection:
     async def __aexit__(self, exc_type, exc_val, exc_tb):
         return await self._conn.__aexit__(exc_type, exc_val, exc_tb)
 
-    async def pool_stats(self):
+    async def pool_stats(self, by_key=False):
         """Get connection pool statistics.
 
         Returns a dict with keys: connected, connections, idle_connections,
-        active_connections, max_size, min_idle
+        active_connections, max_size, min_idle, key (and by_key when requested)
+        """
+        return await self._conn.pool_stats(by_key)
+
+    def cursor(self):
+        """Create a DBAPI-style Cursor that runs statements on this connection's pool."""
+        return Cursor(self)
+
+    def with_options(self, **options):
+        """Create another Connection sharing this one's pool, with different defaults."""
+        return Connection._from_rust(self._conn.with_options(**options))
+
+    def acquire(self):
+        """Hold one pooled connection for several statements (``async with conn.acquire()``)."""
+        return self._conn.acquire()
+
+    def transaction(self):
+        """Create a dedicated Transaction using this connection's server and credentials."""
+        return Transaction._from_rust(self._conn.transaction())
+
+    async def run_in_transaction(self, func, retries=3):
+        """Run ``await func(transaction)`` inside a transaction, retrying on deadlocks.
+
+        The transaction is committed when ``func`` returns and rolled back when it
+        raises. If SQL Server aborts the transaction as a deadlock victim (1205) or
+        with a snapshot update conflict (3960), ``func`` is re-invoked on a fresh
+        transaction up to ``retries`` more times. Returns whatever ``func`` returns.
         """
-        return await self._conn.pool_stats()
+        if retries < 0:
+            raise ValueError("retries must be >= 0")
+
+        attempt = 0
+        while True:
+            try:
+                async with self.transaction() as transaction:
+                    return await func(transaction)
+            except SqlError as e:
+                if getattr(e, "code", None) not in _TRANSACTION_RETRY_ERRORS or attempt >= retries:
+                    raise
+                attempt += 1
+                # Exponential backoff with jitter so the competing transactions
+                # don't immediately collide again.
+                await asyncio.sleep(min(0.05 * 2 ** (attempt - 1), 1.0) * random.uniform(0.5, 1.0))
 
 
 class Transaction:
@@ -80,12 +275,22 @@ class Transaction:
         self._TRANSACTION_BEGUN = False
         self._TRANSACTION_COMMITTED = False
         self._TRANSACTION_ROLLEDBACK = False
+        self._savepoints = []
+
+    @classmethod
+    def _from_rust(cls, rust_conn):
+        """Wrap an existing Rust transaction object (e.g. from Connection.transaction())."""
+        self = cls.__new__(cls)
+        self._rust_conn = rust_conn
+        self._reset_transaction_flags()
+        return self
 
     def _reset_transaction_flags(self):
         """Reset the transaction state flags."""
         self._TRANSACTION_BEGUN = False
         self._TRANSACTION_COMMITTED = False
         self._TRANSACTION_ROLLEDBACK = False
+        self._savepoints = []
     
     def _validate_transaction_flags(self):
         if not self._TRANSACTION_BEGUN:
@@ -111,6 +316,10 @@ class Transaction:
         """Execute multiple SELECT queries in sequence on this connection."""
         return await self._rust_conn.query_batch(queries)
 
+    async def bulk_insert(self, table, columns, data, *, bulk_copy=False, verify_key=None):
+        """Insert rows on this connection, e.g. into a #temp table created in the transaction."""
+        return await self._rust_conn.bulk_insert(table, columns, data, bulk_copy=bulk_copy, verify_key=verify_key)
+
     async def simple_query(self, sql):
         """Execute a raw (non-prepared) SQL query and return a QueryStream."""
         return await self._rust_conn.simple_query(sql)
@@ -119,6 +328,24 @@ class Transaction:
         """Return True if the underlying connection is currently established."""
         return self._rust_conn.is_connected()
 
+    def cursor(self):
+        """Create a DBAPI-style Cursor that runs statements on this transaction's connection."""
+        return Cursor(self)
+
+    async def filestream_context(self, table, column, key_column, key):
+        """Return PathName() and the FILESTREAM transaction context for one row."""
+        # The transaction context is NULL outside of an open transaction.
+        self._validate_transaction_flags()
+        return await self._rust_conn.filestream_context(table, column, key_column, key)
+
+    async def filestream_read(self, table, column, key_column, key, offset=0, length=None):
+        """Read FILESTREAM data (optionally a byte range) for one row."""
+        return await self._rust_conn.filestream_read(table, column, key_column, key, offset, length)
+
+    async def filestream_write(self, table, column, key_column, key, data, offset=None):
+        """Replace FILESTREAM data, or overwrite it at a byte offset, for one row."""
+        return await self._rust_conn.filestream_write(table, column, key_column, key, data, offset)
+
     async def begin(self):
         """Begin a transaction."""
         # If previous transaction completed, reset flags to allow reuse
@@ -144,6 +371,39 @@ class Transaction:
         await self._rust_conn.rollback()
         self._TRANSACTION_ROLLEDBACK = True
 
+    def _savepoint_index(self, name):
+        """Index of the most recent savepoint called ``name`` (SQL Server allows reuse)."""
+        for i in range(len(self._savepoints) - 1, -1, -1):
+            if self._savepoints[i] == name:
+                return i
+        raise ValueError(f"Savepoint '{name}' does not exist in this transaction")
+
+    async def savepoint(self, name):
+        """Mark a savepoint in the current transaction that rollback_to() can return to."""
+        self._validate_transaction_flags()
+        await self._rust_conn.savepoint(name)
+        self._savepoints.append(name)
+
+    async def rollback_to(self, name):
+        """Undo the work done since savepoint ``name``, keeping the transaction open.
+
+        The savepoint itself survives and can be rolled back to again; savepoints set
+        after it are discarded.
+        """
+        self._validate_transaction_flags()
+        index = self._savepoint_index(name)
+        await self._rust_conn.rollback_to(name)
+        del self._savepoints[index + 1:]
+
+    async def release(self, name):
+        """Forget savepoint ``name`` and any set after it, keeping their work.
+
+        SQL Server has no RELEASE SAVEPOINT, so this only updates the savepoints
+        known to rollback_to(); no statement is sent.
+        """
+        self._validate_transaction_flags()
+        del self._savepoints[self._savepoint_index(name):]
+
     async def close(self):
         """Close the connection."""
         result = await self._rust_conn.close()
@@ -155,29 +415,48 @@ class Transaction:
         await self.begin()
         return self
 
+    async def _settle(self, exc_type):
+        """COMMIT on success or ROLLBACK on error, unless the caller already settled."""
+        if self._TRANSACTION_COMMITTED or self._TRANSACTION_ROLLEDBACK:
+            return
+        if exc_type is not None:
+            # An exception occurred - rollback
+            try:
+                await self.rollback()
+            except Exception:
+                pass
+        else:
+            try:
+                await self.commit()
+            except Exception as commit_err:
+                # Commit failed - attempt rollback then re-raise so the
+                # caller knows the transaction was never committed.
+                try:
+                    await self.rollback()
+                except Exception:
+                    pass
+                raise commit_err
+
+    def transaction(self):
+        """Run one transaction on this connection without closing it afterwards.
+
+        Unlike ``async with Transaction(...)``, which closes the dedicated connection
+        on exit, this can be entered repeatedly so several units of work share one
+        session (temp tables, SET options, session context):
+
+            conn = Transaction(conn_str)
+            async with conn.transaction():
+                await conn.execute("INSERT ...")
+            async with conn.transaction():
+                await conn.execute("UPDATE ...")
+            await conn.close()
+        """
+        return _TransactionScope(self)
+
     async def __aexit__(self, exc_type, exc_val, exc_tb):
         """Async context manager exit - automatically COMMIT or ROLLBACK."""
         try:
-            if exc_type is not None:
-                # An exception occurred - rollback (unless already settled)
-                if not self._TRANSACTION_COMMITTED and not self._TRANSACTION_ROLLEDBACK:
-                    try:
-                        await self.rollback()
-                    except Exception:
-                        pass
-            else:
-                # No exception - commit (unless user already committed/rolled back manually)
-                if not self._TRANSACTION_COMMITTED and not self._TRANSACTION_ROLLEDBACK:
-                    try:
-                        await self.commit()
-                    except Exception as commit_err:
-                        # Commit failed - attempt rollback then re-raise so the
-                        # caller knows the transaction was never committed.
-                        try:
-                            await self.rollback()
-                        except Exception:
-                            pass
-                        raise commit_err
+            await self._settle(exc_type)
         finally:
             # Always close the TCP connection so it is not held open until GC.
             # close() issues a best-effort ROLLBACK before dropping the stream,
@@ -188,24 +467,174 @@ class Transaction:
         return False  # Don't suppress exceptions
 
 
+class _TransactionScope:
+    """Context manager returned by ``Transaction.transaction()``."""
+
+    def __init__(self, conn):
+        self._conn = conn
+
+    async def __aenter__(self):
+        await self._conn.begin()
+        return self._conn
+
+    async def __aexit__(self, exc_type, exc_val, exc_tb):
+        await self._conn._settle(exc_type)
+        return False
+
+
+# Process-wide named Connection objects for get_pool(). A threading lock (rather
+# than an asyncio one) keeps creation atomic across event loops and threads;
+# constructing a Connection does no I/O, so the lock is never held across an await.
+_POOL_REGISTRY = {}
+_POOL_REGISTRY_LOCK = threading.Lock()
+
+
+def get_pool(name, *args, **kwargs):
+    """Return the Connection registered as ``name``, creating it on first use.
+
+    The remaining arguments are passed to ``Connection(...)`` when the pool is created
+    and ignored afterwards, so every module can call ``get_pool("main", conn_str)``
+    and share one pool. Raises KeyError if ``name`` is unknown and no arguments are given.
+    """
+    with _POOL_REGISTRY_LOCK:
+        conn = _POOL_REGISTRY.get(name)
+        if conn is None:
+            if not args and not kwargs:
+                raise KeyError(f"No pool named {name!r} and no connection settings given")
+            conn = Connection(*args, **kwargs)
+            _POOL_REGISTRY[name] = conn
+        return conn
+
+
+async def close_pool(name):
+    """Remove ``name`` from the registry and close its pool. Returns False if unknown."""
+    with _POOL_REGISTRY_LOCK:
+        conn = _POOL_REGISTRY.pop(name, None)
+    if conn is None:
+        return False
+    await conn.disconnect()
+    return True
+
+
+async def _next_keyed(stream, keys, previous, name):
+    """Next ``(row, key)`` of ``stream``, or ``(None, None)`` once it is exhausted.
+
+    Raises ValueError for a NULL key or one that does not follow ``previous``, as
+    diff() relies on both results being ordered by their unique key.
+    """
+    try:
+        row = await stream.__anext__()
+    except StopAsyncIteration:
+        return None, None
+    key = tuple(row[k] for k in keys)
+    if None in key:
+        raise ValueError(f"{name} returned a NULL key {key!r}")
+    if previous is not None and key <= previous:
+        raise ValueError(
+            f"{name} must be ordered by {', '.join(keys)} with unique keys: "
+            f"{key!r} follows {previous!r}"
+        )
+    return row, key
+
+
+async def diff(query_a, conn_a, query_b, conn_b, key_columns):
+    """Compare two results ordered by ``key_columns``; see fastmssql.pyi for details.
+
+    Both results are streamed and merged on their keys, so only one row of each is
+    held at a time.
+    """
+    keys = [key_columns] if isinstance(key_columns, str) else list(key_columns)
+    if not keys:
+        raise ValueError("key_columns must name at least one column")
+
+    stream_a = conn_a.query_stream(query_a)
+    stream_b = conn_b.query_stream(query_b)
+    try:
+        row_a, key_a = await _next_keyed(stream_a, keys, None, "query_a")
+        row_b, key_b = await _next_keyed(stream_b, keys, None, "query_b")
+        while row_a is not None or row_b is not None:
+            if row_b is None or (row_a is not None and key_a < key_b):
+                yield "removed", key_a
+                row_a, key_a = await _next_keyed(stream_a, keys, key_a, "query_a")
+            elif row_a is None or key_b < key_a:
+                yield "added", key_b
+                row_b, key_b = await _next_keyed(stream_b, keys, key_b, "query_b")
+            else:
+                if row_a.to_dict() != row_b.to_dict():
+                    yield "changed", key_a
+                row_a, key_a = await _next_keyed(stream_a, keys, key_a, "query_a")
+                row_b, key_b = await _next_keyed(stream_b, keys, key_b, "query_b")
+    finally:
+        await stream_a.aclose()
+        await stream_b.aclose()
+
+
+async def diagnose(target, timeout=10.0):
+    """Check DNS, TCP, TLS, login and ``SELECT 1`` in turn; see fastmssql.pyi for the report."""
+    if isinstance(target, Connection):
+        target = target._conn
+    return await _diagnose(target, timeout)
+
+
 __all__ = [
+    "AuthenticationError",
     "AzureCredential",
     "AzureCredentialType",
+    "Batch",
+    "CertificateExpiryWarning",
+    "ChunkStream",
+    "Composed",
     "Connection",
     "ConversionError",
+    "Cursor",
+    "DataError",
+    "DatabaseError",
+    "DatabaseUnavailableError",
+    "FastMssqlError",
+    "IntegrityError",
+    "InterfaceError",
+    "InvalidCredentialsError",
+    "LoginDisabledError",
+    "OperationalError",
+    "PolicyViolation",
+    "PoolTimeoutError",
+    "QueryTimeoutError",
+    "ProgrammingError",
+    "ResultLimitError",
     "SqlConnectionError",
     "EncryptionLevel",
     "FastRow",
+    "Identifier",
+    "Param",
     "Parameter",
     "Parameters",
+    "PinnedConnection",
     "PoolConfig",
     "ProtocolError",
     "QueryStream",
+    "ResultSetStream",
+    "RetryPolicy",
+    "RowStream",
+    "SQL",
     "SqlError",
     "SslConfig",
+    "StatementPolicy",
     "TlsError",
     "Transaction",
     "ApplicationIntent",
     "TypedNull",
+    "close_pool",
+    "concat",
+    "diagnose",
+    "diff",
+    "enable_wire_debug",
+    "flush_dns_cache",
+    "get_pool",
+    "set_dns_cache_ttl",
+    "set_certificate_expiry_warning",
+    "set_host_aliases",
+    "set_proxies",
+    "set_resolver",
+    "statement_fingerprint",
     "version",
 ]
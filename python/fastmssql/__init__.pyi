@@ -1,30 +1,110 @@
 """Type stubs for FastMSSQL wrapper classes."""
 
-from typing import Any, Coroutine, Dict, List, Optional, Tuple, StrEnum
+import os
+from datetime import datetime
+from typing import Any, AsyncIterator, Awaitable, Callable, Coroutine, Dict, List, Optional, Tuple, TypeVar, StrEnum, Union
 from .fastmssql import (
+    AuthenticationError,
     AzureCredential,
     AzureCredentialType,
+    Batch,
+    CertificateExpiryWarning,
+    ChunkStream,
+    Composed,
     ConversionError,
+    DataError,
+    DatabaseError,
+    DatabaseUnavailableError,
     EncryptionLevel,
+    FastMssqlError,
     FastRow,
+    Identifier,
+    IntegrityError,
+    InterfaceError,
+    InvalidCredentialsError,
+    LoginDisabledError,
+    OperationalError,
+    Param,
     Parameter,
     Parameters,
+    PinnedConnection,
+    PolicyViolation,
     PoolConfig,
+    PoolTimeoutError,
+    QueryTimeoutError,
+    ProgrammingError,
     ProtocolError,
     QueryStream,
+    ResultLimitError,
+    ResultSetStream,
+    RetryPolicy,
+    RowStream,
+    SQL,
     SqlConnectionError,
     SqlError,
     SslConfig,
+    StatementPolicy,
     TlsError,
     TypedNull,
 )
 
+_T = TypeVar("_T")
+
 class ApplicationIntent(StrEnum):
     """SQL Server application intent constants."""
 
     READ_ONLY: str
     READ_WRITE: str
 
+class Cursor:
+    """
+    DBAPI 2.0 style cursor over a Connection or Transaction.
+
+    ``execute()`` and ``executemany()`` are coroutines; the fetch methods are
+    synchronous because the result set is buffered when ``execute()`` returns.
+    """
+
+    arraysize: int
+    rowcount: int
+    description: Optional[Tuple[Tuple[str, str, None, None, None, None, None], ...]]
+
+    def __init__(self, executor: Any) -> None: ...
+    async def execute(self, sql: str, params: Optional[List[Any]] = None) -> Cursor:
+        """
+        Run ``sql`` and make its first result set available to the fetch methods.
+
+        ``rowcount`` is the number of rows returned, or -1 (and ``description`` is
+        None) when no rows come back, since column information is read from the rows.
+        """
+        ...
+
+    async def executemany(self, sql: str, seq_of_params: List[List[Any]]) -> Cursor:
+        """Run ``sql`` once per parameter list; ``rowcount`` is the total affected rows."""
+        ...
+
+    def fetchone(self) -> Optional[FastRow]:
+        """Return the next row, or None when the result set is exhausted."""
+        ...
+
+    def fetchmany(self, size: Optional[int] = None) -> List[FastRow]:
+        """Return up to ``size`` (default ``arraysize``) more rows."""
+        ...
+
+    def fetchall(self) -> List[FastRow]:
+        """Return all remaining rows."""
+        ...
+
+    def __iter__(self) -> Cursor: ...
+    def __next__(self) -> FastRow: ...
+    def setinputsizes(self, sizes: Any) -> None: ...
+    def setoutputsize(self, size: Any, column: Optional[int] = None) -> None: ...
+    def close(self) -> None:
+        """Release the buffered result set; further calls raise RuntimeError."""
+        ...
+
+    async def __aenter__(self) -> Cursor: ...
+    async def __aexit__(self, exc_type: Any, exc_val: Any, exc_tb: Any) -> bool: ...
+
 class Connection:
     """
     High-performance SQL Server connection with async/await support.
@@ -64,6 +144,20 @@ class Connection:
         port: Optional[int] = None,
         instance_name: Optional[str] = None,
         application_name: Optional[str] = None,
+        rstrip_char: bool = False,
+        time_as_timedelta: bool = False,
+        parse_json: Union[bool, List[str]] = False,
+        parse_xml: Union[bool, str] = False,
+        trace_context_info: bool = False,
+        log_queries: bool = False,
+        log_parameters: bool = False,
+        redact_parameters: Optional[List[Union[int, str]]] = None,
+        on_message: Optional[Callable[[str], None]] = None,
+        retry_policy: Optional[RetryPolicy] = None,
+        paramstyle: Optional[str] = None,
+        statement_policy: Optional[Union[StatementPolicy, Callable[[str], Any]]] = None,
+        max_result_columns: Optional[int] = None,
+        max_row_bytes: Optional[int] = None,
     ) -> None:
         """
         Initialize a new SQL Server connection.
@@ -81,6 +175,38 @@ class Connection:
             port: TCP port number (default: 1433)
             instance_name: Named instance of SQL Server
             application_name: Application name for SQL Server connection
+            rstrip_char: Strip trailing padding spaces from CHAR/NCHAR values (default: preserve)
+            time_as_timedelta: Return TIME values as ``datetime.timedelta`` since midnight
+                instead of ``datetime.time`` (default: ``time``)
+            parse_json: Return ``FOR JSON`` results as one row holding the parsed dict or
+                list (``True``), and also parse the named text columns of every row (a
+                list of column names) (default: JSON stays a string)
+            parse_xml: Return XML columns as ``xml.etree.ElementTree.Element`` objects
+                (``True`` or ``"etree"``) or ``lxml.etree`` elements (``"lxml"``)
+                (default: XML stays a string)
+            trace_context_info: Write each call's execution_id to the session's CONTEXT_INFO
+                before running it, for joining with server-side traces (default: off)
+            log_queries: Log each call's statement and execution_id at DEBUG level to the
+                ``fastmssql.query`` logger (default: off)
+            log_parameters: Include parameter values in those log records (default: off)
+            redact_parameters: Parameters logged as ``***``: 1-based positions (1 is @P1)
+                and/or name patterns with ``*`` and ``?`` wildcards, e.g. ``["*password*", 3]``
+            on_message: Called with each informational message (``PRINT``, ``RAISERROR``
+                with severity 10 or lower) as the server sends it (default: none)
+            retry_policy: Re-run ``execute(..., idempotent=True)`` statements that fail
+                with a transient error such as a deadlock (1205) or Azure SQL failover
+                (40613) (default: none)
+            paramstyle: Placeholder syntax of statements run with parameters: "native"
+                (``@P1``, the default), "qmark" (``?``, as in pyodbc) or "format"
+                (``%s``, as in pymssql); placeholders are rewritten to ``@P1..@Pn``
+            statement_policy: A ``StatementPolicy`` (or a callable used as its ``check``)
+                every statement must pass before it is sent; rejected statements raise
+                ``PolicyViolation`` (default: none)
+            max_result_columns: Raise ``ResultLimitError`` for a result with more columns
+                than this, e.g. an accidental ``SELECT *`` of a very wide table
+                (default: unlimited)
+            max_row_bytes: Raise ``ResultLimitError`` for a row whose values take more
+                bytes than this as received (strings counted as UTF-8) (default: unlimited)
 
         Note:
             - Either connection_string OR individual parameters must be provided
@@ -101,6 +227,30 @@ class Connection:
         """Check if the connection pool is active and ready."""
         ...
 
+    def describe(self, sql: str) -> Coroutine[Any, Any, List[Dict[str, Any]]]:
+        """
+        Describe the columns of the first result set ``sql`` would return, without running it.
+
+        Uses sp_describe_first_result_set. Each dict has the keys ``name``, ``type``
+        (base type, e.g. "nvarchar"), ``declared_type`` (e.g. "nvarchar(50)"), ``nullable``,
+        ``is_unicode``, ``max_length`` (bytes, -1 for MAX), ``char_length`` (characters for
+        character types, -1 for MAX/TEXT, otherwise None), ``precision``, ``scale``,
+        ``is_identity`` and ``is_computed``.
+        """
+        ...
+
+    def table_columns(self, table: str) -> Coroutine[Any, Any, List[Dict[str, Any]]]:
+        """
+        Describe the columns of ``table`` from the catalog views, in column order.
+
+        Each dict has the keys ``name``, ``type``, ``nullable``, ``is_identity``,
+        ``is_computed``, ``is_persisted``, ``has_default``, ``is_rowversion``,
+        ``is_generated_always`` and ``is_insertable`` (False for identity, computed,
+        rowversion and GENERATED ALWAYS columns). Raises ValueError if the table does
+        not exist.
+        """
+        ...
+
     def query(
         self,
         sql: str,
@@ -119,6 +269,151 @@ class Connection:
         """
         ...
 
+    def fetch_column(
+        self,
+        sql: str,
+        params: Optional[List[Any]] = None,
+    ) -> Coroutine[Any, Any, List[Any]]:
+        """
+        Run a query and return the values of its first column as a list.
+
+        Skips building FastRow objects, which makes it the fastest way to fetch e.g.
+        a list of IDs. Other columns are ignored; an empty result gives ``[]``.
+        """
+        ...
+
+    def query_arrow(
+        self,
+        sql: str,
+        params: Optional[List[Any]] = None,
+    ) -> Coroutine[Any, Any, Any]:
+        """
+        Run a query and return its first result set as a ``pyarrow.Table``.
+
+        Values are written into Arrow buffers as rows arrive, without creating a
+        Python object per value. Integers keep their width, DECIMAL and MONEY become
+        ``decimal128``, DATETIME/DATETIME2 ``timestamp[us]``, DATETIMEOFFSET
+        ``timestamp[us, tz=UTC]``, and strings, GUIDs and XML ``large_string``.
+        Requires pyarrow (``pip install fastmssql[arrow]``).
+        """
+        ...
+
+    def query_polars(
+        self,
+        sql: str,
+        params: Optional[List[Any]] = None,
+    ) -> Coroutine[Any, Any, Any]:
+        """
+        Run a query and return its first result set as a ``polars.DataFrame``.
+
+        The columns are built as for ``query_arrow()`` and handed to polars through
+        the Arrow C data interface, without copying them or going through pandas.
+        Requires polars and pyarrow (``pip install fastmssql[polars]``).
+        """
+        ...
+
+    def query_numpy(
+        self,
+        sql: str,
+        params: Optional[List[Any]] = None,
+    ) -> Coroutine[Any, Any, Dict[str, Any]]:
+        """
+        Run a query and return its first result set as a dict of column name to 1-D
+        ``numpy.ndarray``.
+
+        Integer and float columns keep their width; with NULLs they become float64
+        with NaN. DATETIME/DATETIME2 become ``datetime64[us]`` (DATETIMEOFFSET in UTC),
+        DATE ``datetime64[D]`` and TIME ``timedelta64[us]``, with NaT for NULL.
+        Booleans with NULLs, decimals, strings and binary are object arrays.
+        Requires numpy but not pyarrow (``pip install fastmssql[numpy]``).
+        """
+        ...
+
+    def query_stream(
+        self,
+        sql: str,
+        params: Optional[List[Any]] = None,
+    ) -> RowStream:
+        """
+        Iterate over the rows of the first result set without buffering them all.
+
+        Rows are read from the server in small batches as the iterator advances, so
+        exports of millions of rows run in constant memory. The pooled connection stays
+        checked out until the rows are exhausted or ``aclose()`` is awaited.
+
+        Example:
+            async for row in conn.query_stream("SELECT * FROM events WHERE day = @P1", [day]):
+                writer.writerow(row.values())
+        """
+        ...
+
+    def fetch_chunks(
+        self,
+        sql: str,
+        params: Optional[List[Any]] = None,
+        chunk_size: int = 10_000,
+    ) -> ChunkStream:
+        """
+        Iterate over the rows of the first result set in lists of up to ``chunk_size`` rows.
+
+        The next chunk is read from the server while the current one is processed, so
+        large results can be pipelined without holding them in memory or paying async
+        overhead per row. The pooled connection stays checked out until the rows are
+        exhausted or ``aclose()`` is awaited. Raises ValueError if ``chunk_size`` < 1.
+
+        Example:
+            async for chunk in conn.fetch_chunks("SELECT * FROM events", chunk_size=5000):
+                await sink.write_many([row.values() for row in chunk])
+        """
+        ...
+
+    def fetch_page(
+        self,
+        sql: str,
+        params: Optional[List[Any]] = None,
+        *,
+        page_size: int,
+        key: Optional[Union[str, List[str]]] = None,
+        token: Optional[str] = None,
+    ) -> Coroutine[Any, Any, QueryStream]:
+        """
+        Fetch one page of up to ``page_size`` rows of the query.
+
+        The result's ``next_token`` is an opaque string to pass back as ``token=`` for
+        the page after it, or None on the last page. With ``key`` (a column, or
+        columns that are unique together) pages follow the key in ascending order and
+        the query must not have its own ORDER BY; without it the query must end with
+        an ORDER BY and pages are read with OFFSET/FETCH. Key columns must not be NULL.
+
+        Raises:
+            ValueError: If ``page_size`` < 1, or the token is invalid or was issued for
+                a different query or key.
+
+        Example:
+            page = await conn.fetch_page("SELECT id, name FROM users", page_size=50, key="id", token=request_token)
+            return {"items": [r.to_dict() for r in page], "next": page.next_token}
+        """
+        ...
+
+    def stream_results(
+        self,
+        sql: str,
+        params: Optional[List[Any]] = None,
+    ) -> ResultSetStream:
+        """
+        Iterate over the result sets of a multi-statement batch or stored procedure.
+
+        Each item is a QueryStream for one result set; result sets are fetched as the
+        iterator advances. Statements that return no rows (e.g. INSERT/UPDATE) do not
+        produce an item - use execute() when you need their affected counts.
+
+        Example:
+            async for result_set in conn.stream_results("EXEC dbo.report @P1", [2024]):
+                for row in result_set:
+                    ...
+        """
+        ...
+
     def simple_query(
         self,
         sql: str,
@@ -141,6 +436,11 @@ class Connection:
         self,
         sql: str,
         params: Optional[List[Any]] = None,
+        *,
+        idempotent: bool = False,
+        timeout: Optional[float] = None,
+        recover_on_timeout: bool = False,
+        label: Optional[str] = None,
     ) -> Coroutine[Any, Any, int]:
         """
         Execute INSERT/UPDATE/DELETE/DDL command.
@@ -148,12 +448,127 @@ class Connection:
         Args:
             sql: SQL command with @P1, @P2, etc. placeholders
             params: List of parameter values in order
+            idempotent: Pass True for a statement that is safe to run twice, so the
+                connection's retry policy may re-run it after a transient error
+                (default: False; a multi-statement batch may have committed part of
+                its work before failing)
+            timeout: Seconds the statement may run; a statement still running is
+                cancelled and raises QueryTimeoutError
+            recover_on_timeout: After a timeout, check the cancelled connection in the
+                background and return it to the pool if it is still healthy, instead
+                of discarding it
+            label: Appended to the statement as ``/* fastmssql:label=<label> */`` so its
+                runs can be found in Query Store, and counted in ``pool_stats()["labels"]``;
+                letters, digits, '-', '_', '.' and ':' only
+
+        Cancelling the returned awaitable (``.cancel()``, a cancelled task or an
+        ``asyncio.wait_for()`` timeout) stops the statement. Only a connection that is
+        unencrypted after login gets a TDS attention signal; with encryption (the
+        default) the connection is closed instead, which makes the server abort the
+        statement. Either way the connection is discarded.
 
         Returns:
             Number of affected rows
         """
         ...
 
+    def execute_counts(
+        self,
+        sql: str,
+        params: Optional[List[Any]] = None,
+    ) -> Coroutine[Any, Any, List[int]]:
+        """
+        Execute a command like ``execute()`` but return each statement's row count.
+
+        For a batch such as ``UPDATE ...; DELETE ...`` the result is one count per
+        statement, in order, where ``execute()`` returns their sum.
+
+        Args:
+            sql: SQL command with @P1, @P2, etc. placeholders
+            params: List of parameter values in order
+
+        Returns:
+            Number of affected rows for each statement
+        """
+        ...
+
+    def execute_returning(
+        self,
+        sql: str,
+        params: Optional[List[Any]] = None,
+    ) -> Coroutine[Any, Any, QueryStream]:
+        """
+        Execute a data-modifying statement and return both its rows and its row count.
+
+        Use for INSERT/UPDATE/DELETE/MERGE with an OUTPUT clause: the returned
+        QueryStream holds the OUTPUT rows and ``rows_affected`` holds the count.
+        Without OUTPUT the stream is empty and only ``rows_affected`` is useful.
+
+        ``rows_affected`` is the count of the last statement. For a batch or an
+        ``EXEC`` of a procedure that modifies rows and then SELECTs, the count of
+        each statement is not available together with the rows; use
+        ``execute_counts()`` for the counts alone, or have the procedure return
+        its count as an OUTPUT parameter or result column.
+
+        Args:
+            sql: SQL command with @P1, @P2, etc. placeholders
+            params: List of parameter values in order
+
+        Returns:
+            QueryStream with ``rows_affected`` set to the count of the last statement
+        """
+        ...
+
+    def touched_tables(
+        self,
+        sql: str,
+        params: Optional[List[Any]] = None,
+    ) -> Coroutine[Any, Any, Dict[str, List[str]]]:
+        """
+        Report which tables a statement would read and write, without running it.
+
+        The statement is compiled with SHOWPLAN_XML on and the tables are taken from
+        its estimated plan, so writes (UPDATE/DELETE/INSERT/MERGE targets) are
+        reported as well as reads. Useful for cache invalidation.
+
+        Args:
+            sql: SQL with @P1, @P2, etc. placeholders
+            params: List of parameter values in order
+
+        Returns:
+            ``{"read": [...], "written": [...]}`` of ``database.schema.table`` names
+        """
+        ...
+
+    def exec_sql(
+        self,
+        sql: str,
+        params: Optional[Dict[str, Any]] = None,
+        returns_rows: Optional[bool] = None,
+        *,
+        outputs: Optional[Dict[str, str]] = None,
+    ) -> Coroutine[Any, Any, Union[QueryStream, int]]:
+        """
+        Run dynamic SQL through ``sp_executesql`` with parameters bound by name.
+
+        Parameter types are declared from the Python values, so repeated calls with
+        the same statement text reuse one cached plan.
+
+        Args:
+            sql: SQL referencing parameters by name, e.g. ``@customer_id``
+            params: Mapping of parameter name (with or without ``@``) to value
+            returns_rows: None returns a QueryStream; True also raises ValueError if
+                the statement produced no result set; False returns the affected
+                row count instead
+            outputs: Mapping of OUTPUT parameter name to SQL type, e.g.
+                ``{"total": "int"}``; a name also in ``params`` is input/output
+
+        Returns:
+            QueryStream over the first result set with ``rows_affected`` and
+            ``output_params`` set, or the affected row count for ``returns_rows=False``
+        """
+        ...
+
     def execute_batch(
         self,
         commands: List[Tuple[str, Optional[List[Any]]]],
@@ -169,12 +584,49 @@ class Connection:
         """
         ...
 
+    def apply_migrations(
+        self,
+        directory: Union[str, os.PathLike[str]],
+        *,
+        table: str = "dbo.__fastmssql_migrations",
+        dry_run: bool = False,
+    ) -> Coroutine[Any, Any, List[Dict[str, Any]]]:
+        """
+        Apply the ``*.sql`` scripts in ``directory`` that have not been applied yet.
+
+        Scripts run in file name order, each once; ``table`` records the name and
+        checksum of every applied script and is created on first use. Scripts are
+        split on ``GO`` lines (``GO 5`` repeats a batch) and each runs in its own
+        transaction together with its version row, unless it contains
+        ``-- fastmssql:no-transaction`` or a statement that cannot run in a
+        transaction (``CREATE/ALTER/DROP DATABASE``, ``BACKUP``, ``RESTORE``, full-text
+        DDL, ``RECONFIGURE``). A session application lock keeps concurrent callers
+        from applying the same scripts twice.
+
+        Raises ``InterfaceError`` if an applied script has changed on disk. A failing
+        script raises the server error with a note naming the script and batch;
+        scripts applied before it stay applied.
+
+        Args:
+            directory: Folder holding the ``.sql`` scripts
+            table: Version table, optionally schema-qualified
+            dry_run: Only report which scripts would be applied
+
+        Returns:
+            One dict per script: ``name``, ``status`` (``"applied"``, ``"skipped"``
+            or ``"pending"`` with ``dry_run``), ``batches``, ``duration_ms`` and
+            ``transactional``
+        """
+        ...
+
     def bulk_insert(
         self,
         table: str,
         columns: List[str],
         data: List[List[Any]],
-    ) -> Coroutine[Any, Any, None]:
+        *,
+        bulk_copy: bool = False,
+    ) -> Coroutine[Any, Any, int]:
         """
         High-performance bulk insert for large datasets.
 
@@ -182,6 +634,52 @@ class Connection:
             table: Target table name (can be schema-qualified)
             columns: List of column names
             data: List of rows, each row is a list of values
+            bulk_copy: Stream the rows with the TDS bulk load protocol instead of
+                chunked INSERT statements. Much faster for large loads; insertable
+                columns not listed in ``columns`` get NULL rather than their default
+
+        Returns:
+            Number of rows inserted
+        """
+        ...
+
+    def bulk_insert_arrow(
+        self,
+        table: str,
+        arrow_table: Any,
+    ) -> Coroutine[Any, Any, int]:
+        """
+        Bulk load a ``pyarrow.Table`` (or ``RecordBatch``) with the TDS bulk load protocol.
+
+        Arrow columns are matched to the table's columns by name and each value is
+        encoded as its column's SQL type, as with ``bulk_insert(..., bulk_copy=True)``;
+        insertable columns missing from the Arrow table get NULL. The Arrow buffers are
+        read directly, without creating a Python object per value. Timezone-aware
+        timestamps are stored as their UTC time. Requires pyarrow.
+
+        Returns:
+            Number of rows inserted
+        """
+        ...
+
+    def bulk_insert_dataframe(
+        self,
+        table: str,
+        frame: Any,
+        *,
+        chunk_size: int = 100_000,
+    ) -> Coroutine[Any, Any, int]:
+        """
+        Bulk load a ``pandas.DataFrame`` with the TDS bulk load protocol.
+
+        The frame is converted with ``pyarrow.Table.from_pandas()`` and loaded as by
+        ``bulk_insert_arrow()``: dtypes map to the matching Arrow types, NaN, NaT and
+        ``pd.NA`` become NULL, and the index is not loaded. Rows are sent
+        ``chunk_size`` at a time on one connection; chunks already loaded stay if a
+        later one fails. Requires pandas and pyarrow (``pip install fastmssql[pandas]``).
+
+        Returns:
+            Number of rows inserted
         """
         ...
 
@@ -200,7 +698,28 @@ class Connection:
         """
         ...
 
-    def pool_stats(self) -> Coroutine[Any, Any, Dict[str, int | bool | None]]:
+    def server_info(self) -> Coroutine[Any, Any, Dict[str, Any]]:
+        """
+        Security details of a pooled connection, for asserting encryption posture.
+
+        Returns a dictionary with the following keys:
+        - tds_version (str | None): Negotiated TDS protocol version, e.g. "7.4"
+        - encrypted (bool | None): Whether the session traffic is encrypted
+        - auth_scheme (str | None): "SQL", "NTLM", "KERBEROS" ...
+        - net_transport (str | None): "TCP", "Shared memory" ...
+        - server_version (str | None): Server product version, e.g. "16.0.4135"
+        - tls_version (str | None): e.g. "TLSv1.2"; None when TLS was not used
+        - cipher_suite (str | None): IANA name, e.g. "TLS_ECDHE_RSA_WITH_AES_256_GCM_SHA384"
+        - certificate_subject (str | None): e.g. "CN=sql01.example.com"
+        - certificate_issuer (str | None)
+        - certificate_not_before (datetime | None): UTC
+        - certificate_not_after (datetime | None): UTC
+
+        The certificate fields are None under TLS 1.3, which encrypts the certificate.
+        """
+        ...
+
+    def pool_stats(self, by_key: bool = False) -> Coroutine[Any, Any, Dict[str, Any]]:
         """
         Get connection pool statistics.
 
@@ -211,6 +730,163 @@ class Connection:
         - active_connections (int): Number of connections currently in use
         - max_size (int): Maximum pool size
         - min_idle (int | None): Minimum idle connections to maintain
+        - key (dict): The server, database and user this pool connects as
+        - by_key (list[dict]): Only with ``by_key=True``; one entry per distinct
+          key across every connected pool in the process, with server, database,
+          user, pools, connections, idle_connections, active_connections and
+          max_size summed over the pools under that key
+        - labels (dict): Per label given to ``execute(..., label=...)``: executions,
+          errors, rows_affected, total_ms, avg_ms and max_ms
+        """
+        ...
+
+    def health_report(self, timeout: float = 5.0) -> Coroutine[Any, Any, Dict[str, Any]]:
+        """
+        Summarise this connection's health for a ``/healthz`` handler.
+
+        Pings the server with ``SELECT 1`` through the pool (giving up after
+        ``timeout`` seconds) and returns a JSON-serialisable dict:
+        - status (str): "down" when the ping failed, "degraded" when the circuit is
+          open or every pooled connection is busy, otherwise "ok"
+        - checked_at (str): ISO 8601 UTC time of the check
+        - ping_ms (float | None): Ping round trip, including the checkout
+        - ping_error (str | None): Why the ping failed
+        - connections, idle_connections, active_connections, max_size (int)
+        - saturation (float): active_connections / max_size
+        - circuit (str): "open" while the pool's latest attempt to open a connection
+          has failed, otherwise "closed"
+        - last_error (str | None): The latest connection failure, even if resolved
+        - last_error_at (str | None): ISO 8601 UTC time of that failure
+        """
+        ...
+
+    def apply(self, batch: Batch) -> Coroutine[Any, Any, int]:
+        """
+        Send every statement queued in ``batch`` as one transaction in a single round trip.
+
+        Each statement's @P1, @P2, ... placeholders refer to its own parameters; nothing
+        is committed if any statement fails.
+
+        Args:
+            batch: Batch of queued statements
+
+        Returns:
+            Total number of affected rows
+        """
+        ...
+
+    def cursor(self) -> Cursor:
+        """Create a DBAPI-style Cursor that runs statements on this connection's pool."""
+        ...
+
+    def with_options(
+        self,
+        *,
+        rstrip_char: Optional[bool] = None,
+        time_as_timedelta: Optional[bool] = None,
+        parse_json: Optional[Union[bool, List[str]]] = None,
+        parse_xml: Optional[Union[bool, str]] = None,
+        trace_context_info: Optional[bool] = None,
+        log_queries: Optional[bool] = None,
+        log_parameters: Optional[bool] = None,
+        redact_parameters: Optional[List[Union[int, str]]] = None,
+        on_message: Optional[Callable[[str], None]] = None,
+        retry_policy: Optional[RetryPolicy] = None,
+        paramstyle: Optional[str] = None,
+        statement_policy: Optional[Union[StatementPolicy, Callable[[str], Any]]] = None,
+        max_result_columns: Optional[int] = None,
+        max_row_bytes: Optional[int] = None,
+    ) -> Connection:
+        """
+        Create another Connection backed by the same pool as this one, with different
+        defaults. Options left as None are inherited from this connection.
+
+        Both objects share physical connections, pool settings and credentials; closing
+        either one closes the shared pool (it is re-created on next use).
+
+        Example:
+            padded = Connection(conn_str)
+            trimmed = padded.with_options(rstrip_char=True)
+        """
+        ...
+
+    async def reload_config(
+        self,
+        *,
+        new_pool_config: Optional[PoolConfig] = None,
+        new_ssl_config: Optional[SslConfig] = None,
+        new_azure_credential: Optional[AzureCredential] = None,
+    ) -> None:
+        """
+        Apply new pool sizing, TLS settings or credential without restarting the
+        service. Options left as None are kept.
+
+        A replacement pool is built from the new settings (warmed up to its
+        ``min_idle``) and swapped in atomically: later statements run on it, while
+        statements already running on the old pool finish there, and the old pool
+        closes its connections once the last of them is returned. If the new pool
+        cannot be built the old one stays in use and the error is raised. A
+        connection that has not connected yet just takes the new settings.
+
+        Args:
+            new_pool_config: Pool sizing, timeouts and health checks
+            new_ssl_config: TLS settings; only for connections created with
+                ``server=...`` (a connection string's ``Encrypt`` and
+                ``TrustServerCertificate`` settings cannot be replaced)
+            new_azure_credential: Credential new connections sign in with
+
+        Example:
+            await conn.reload_config(new_pool_config=PoolConfig(max_size=50))
+        """
+        ...
+
+    def acquire(self) -> PinnedConnection:
+        """
+        Hold one pooled connection for several statements, so temp tables, SET options
+        and other session state carry from one statement to the next.
+
+        The connection is checked out on entry and returned to the pool on exit, after
+        rolling back any transaction left open. If the block raises, the connection is
+        discarded instead. Session state that is not rolled back (temp tables, SET
+        options) stays with the connection when it goes back to the pool.
+
+        Example:
+            async with conn.acquire() as pinned:
+                await pinned.execute("SELECT id INTO #ids FROM orders WHERE status = @P1", ["open"])
+                result = await pinned.query("SELECT COUNT(*) AS n FROM #ids")
+        """
+        ...
+
+    def transaction(self) -> Transaction:
+        """
+        Create a dedicated (non-pooled) Transaction that uses this connection's
+        server, SSL settings and credentials.
+
+        Example:
+            async with conn.transaction() as tx:
+                await tx.execute("UPDATE accounts SET balance = balance - @P1 WHERE id = @P2", [50, 1])
+        """
+        ...
+
+    async def run_in_transaction(
+        self,
+        func: Callable[[Transaction], Awaitable[_T]],
+        retries: int = 3,
+    ) -> _T:
+        """
+        Run ``await func(transaction)`` inside a transaction and return its result.
+
+        Commits when ``func`` returns and rolls back when it raises. If SQL Server
+        aborts the transaction as a deadlock victim (error 1205) or with a snapshot
+        update conflict (error 3960), ``func`` is invoked again on a fresh transaction,
+        up to ``retries`` additional times, with a short jittered backoff in between.
+
+        Args:
+            func: Async callable receiving the transaction-bound executor
+            retries: Maximum number of re-invocations after a retryable failure
+
+        Returns:
+            The value returned by ``func``
         """
         ...
 
@@ -222,6 +898,12 @@ class Connection:
         """Async context manager exit (closes pool)."""
         ...
 
+class _TransactionScope:
+    """Context manager returned by ``Transaction.transaction()``."""
+
+    async def __aenter__(self) -> Transaction: ...
+    async def __aexit__(self, exc_type: Any, exc_val: Any, exc_tb: Any) -> bool: ...
+
 class Transaction:
     """
     Single dedicated connection for SQL Server transactions.
@@ -249,6 +931,8 @@ class Transaction:
         port: Optional[int] = None,
         instance_name: Optional[str] = None,
         application_name: Optional[str] = None,
+        rstrip_char: bool = False,
+        time_as_timedelta: bool = False,
     ) -> None:
         """Initialize a dedicated non-pooled connection for transactions."""
         ...
@@ -276,6 +960,23 @@ class Transaction:
         """Execute multiple commands in sequence on this connection."""
         ...
 
+    def bulk_insert(
+        self,
+        table: str,
+        columns: List[str],
+        data: List[List[Any]],
+        *,
+        bulk_copy: bool = False,
+        verify_key: Optional[str] = None,
+    ) -> Coroutine[Any, Any, Union[int, Dict[str, Any]]]:
+        """
+        Insert rows on this connection, as ``Connection.bulk_insert()`` does.
+
+        The table may be a ``#temp`` table created earlier on this connection, so
+        rows can be staged and merged into their target inside the transaction.
+        """
+        ...
+
     def query_batch(
         self,
         queries: List[Tuple[str, Optional[List[Any]]]],
@@ -294,6 +995,56 @@ class Transaction:
         """
         ...
 
+    def cursor(self) -> Cursor:
+        """Create a DBAPI-style Cursor that runs statements on this transaction's connection."""
+        ...
+
+    def filestream_context(
+        self,
+        table: str,
+        column: str,
+        key_column: str,
+        key: Any,
+    ) -> Coroutine[Any, Any, Optional[Dict[str, Any]]]:
+        """Return ``{"path": str, "transaction_context": bytes}`` for one FILESTREAM value.
+
+        Raises RuntimeError unless a transaction has begun, since the transaction
+        context is NULL outside of one. Returns None if no row matches.
+        """
+        ...
+
+    def filestream_read(
+        self,
+        table: str,
+        column: str,
+        key_column: str,
+        key: Any,
+        offset: int = 0,
+        length: Optional[int] = None,
+    ) -> Coroutine[Any, Any, Optional[bytes]]:
+        """Read FILESTREAM data for the row where ``key_column = key``.
+
+        Reads ``length`` bytes (default: to the end) from the 0-based ``offset``.
+        Returns None if no row matches or the value is NULL.
+        """
+        ...
+
+    def filestream_write(
+        self,
+        table: str,
+        column: str,
+        key_column: str,
+        key: Any,
+        data: bytes,
+        offset: Optional[int] = None,
+    ) -> Coroutine[Any, Any, int]:
+        """Write FILESTREAM data for the row where ``key_column = key``.
+
+        Replaces the whole value, or overwrites ``len(data)`` bytes at the 0-based
+        ``offset``. Returns the number of rows updated.
+        """
+        ...
+
     def is_connected(self) -> bool:
         """Return True if the underlying connection is currently established."""
         ...
@@ -302,6 +1053,16 @@ class Transaction:
         """Begin a transaction."""
         ...
 
+    def transaction(self) -> _TransactionScope:
+        """
+        Run one transaction on this connection without closing it afterwards.
+
+        Entering the returned context manager calls ``begin()``; leaving it commits,
+        or rolls back if the block raised. Unlike ``async with Transaction(...)``,
+        the dedicated connection stays open, so it can be entered repeatedly.
+        """
+        ...
+
     async def commit(self) -> None:
         """Commit the current transaction."""
         ...
@@ -310,6 +1071,42 @@ class Transaction:
         """Rollback the current transaction."""
         ...
 
+    async def savepoint(self, name: str) -> None:
+        """
+        Mark a savepoint (``SAVE TRANSACTION``) in the current transaction.
+
+        Args:
+            name: Savepoint name, 1 to 32 characters; names may be reused
+
+        Raises:
+            RuntimeError: If no transaction is active
+            ValueError: If the name is empty or longer than 32 characters
+        """
+        ...
+
+    async def rollback_to(self, name: str) -> None:
+        """
+        Undo the work done since savepoint ``name``; the transaction stays open.
+
+        The savepoint survives and can be rolled back to again; savepoints set after
+        it are discarded.
+
+        Raises:
+            ValueError: If no savepoint called ``name`` exists in this transaction
+        """
+        ...
+
+    async def release(self, name: str) -> None:
+        """
+        Forget savepoint ``name`` and any set after it, keeping their work.
+
+        SQL Server has no RELEASE SAVEPOINT, so no statement is sent.
+
+        Raises:
+            ValueError: If no savepoint called ``name`` exists in this transaction
+        """
+        ...
+
     async def close(self) -> None:
         """Close the connection."""
         ...
@@ -324,24 +1121,224 @@ class Transaction:
 
 __all__ = [
     "ApplicationIntent",
+    "AuthenticationError",
     "AzureCredential",
     "AzureCredentialType",
+    "Batch",
+    "CertificateExpiryWarning",
+    "ChunkStream",
+    "Composed",
     "ConversionError",
+    "Cursor",
+    "DataError",
     "Connection",
+    "DatabaseError",
+    "DatabaseUnavailableError",
     "EncryptionLevel",
+    "FastMssqlError",
     "FastRow",
+    "Identifier",
+    "IntegrityError",
+    "InterfaceError",
+    "InvalidCredentialsError",
+    "LoginDisabledError",
+    "OperationalError",
+    "Param",
     "Parameter",
     "Parameters",
+    "PinnedConnection",
+    "PolicyViolation",
     "PoolConfig",
+    "PoolTimeoutError",
+    "QueryTimeoutError",
+    "ProgrammingError",
     "ProtocolError",
     "QueryStream",
+    "ResultLimitError",
+    "ResultSetStream",
+    "RetryPolicy",
+    "RowStream",
+    "SQL",
     "SqlConnectionError",
     "SqlError",
     "SslConfig",
+    "StatementPolicy",
     "TlsError",
     "Transaction",
     "TypedNull",
+    "close_pool",
+    "concat",
+    "diagnose",
+    "diff",
+    "enable_wire_debug",
+    "flush_dns_cache",
+    "get_pool",
+    "set_dns_cache_ttl",
+    "set_certificate_expiry_warning",
+    "set_host_aliases",
+    "set_proxies",
+    "set_resolver",
+    "statement_fingerprint",
     "version",
 ]
 
 def version() -> str: ...
+def get_pool(name: str, *args: Any, **kwargs: Any) -> Connection:
+    """
+    Return the Connection registered under ``name``, creating it atomically on first use.
+
+    ``args``/``kwargs`` are the ``Connection(...)`` arguments; they are only used when the
+    pool is created and ignored on later calls. Raises KeyError if ``name`` is not
+    registered and no arguments are given.
+
+    Example:
+        conn = get_pool("main", conn_str, pool_config=PoolConfig.adaptive(20))
+        # elsewhere
+        conn = get_pool("main")
+    """
+    ...
+
+async def close_pool(name: str) -> bool:
+    """Unregister ``name`` and close its pool. Returns False if no such pool exists."""
+    ...
+def concat(results: List[QueryStream]) -> QueryStream:
+    """Concatenate result sets with matching columns into a new QueryStream (inputs are emptied)."""
+    ...
+
+async def diagnose(target: Union[str, Connection], timeout: float = 10.0) -> Dict[str, Any]:
+    """
+    Check connectivity step by step and report where it fails.
+
+    ``target`` is a connection string or a Connection (whose credentials, including
+    Azure ones, are used). The steps run in order and stop at the first failure:
+    ``dns``, ``tcp``, ``token`` (Azure credentials only), ``tls``, ``login`` and
+    ``query`` (``SELECT 1``). Each is limited to ``timeout`` seconds. Connection
+    problems are reported rather than raised. A server routed through
+    ``set_proxies()`` is connected to through its proxy, which resolves the name,
+    so there is no ``dns`` step.
+
+    Returns a dict with ``ok``, ``failed_step`` (None on success), ``address``
+    (host:port), ``proxy`` (e.g. ``"SOCKS5 proxy bastion:1080"``, or None for a
+    direct connection) and ``steps``: a list of dicts with ``step``, ``ok``, ``elapsed_ms``,
+    ``detail`` and ``code`` (the SQL Server error number for login failures).
+
+    Example:
+        report = await diagnose(conn_str)
+        if not report["ok"]:
+            print(report["failed_step"], report["steps"][-1]["detail"])
+    """
+    ...
+
+def diff(
+    query_a: str,
+    conn_a: Connection,
+    query_b: str,
+    conn_b: Connection,
+    key_columns: Union[str, List[str]],
+) -> AsyncIterator[Tuple[str, Tuple[Any, ...]]]:
+    """
+    Compare the results of two queries row by row, e.g. a table before and after a
+    migration or in two environments.
+
+    Both queries must return their rows ordered by ``key_columns``, which must be
+    unique and not NULL. The results are streamed and merged on the key, so memory
+    use does not grow with their size. Yields ``(kind, key)`` pairs in key order,
+    where ``key`` is a tuple of the key column values and ``kind`` is ``"removed"``
+    (only in ``query_a``), ``"added"`` (only in ``query_b``) or ``"changed"`` (in
+    both, with different values in some column).
+
+    Keys are compared in Python, so order string keys with a binary collation
+    (``ORDER BY name COLLATE Latin1_General_BIN2``) to match. Raises ValueError when
+    a result is not ordered by its key. Each query holds a pooled connection while
+    the comparison runs, so a shared pool needs room for both. Stopping early
+    (``break`` followed by ``aclose()``) cancels whichever query still has rows
+    unread and discards its connection.
+
+    Example:
+        async for kind, (order_id,) in diff(
+            "SELECT * FROM orders ORDER BY id", prod,
+            "SELECT * FROM orders ORDER BY id", staging,
+            key_columns="id",
+        ):
+            print(kind, order_id)
+    """
+    ...
+
+def set_dns_cache_ttl(seconds: float) -> None:
+    """
+    Set how long server name resolutions are reused for new connections (default 30).
+
+    0 disables the cache. A cached name is resolved again as soon as none of its
+    addresses accepts a connection, so failovers are picked up without waiting for
+    the TTL. Raises ValueError for negative values.
+    """
+    ...
+
+def flush_dns_cache(host: Optional[str] = None) -> int:
+    """Drop cached resolutions for ``host`` (or all servers); returns how many were removed."""
+    ...
+
+def set_host_aliases(aliases: Optional[Dict[str, str]] = None) -> None:
+    """
+    Replace the host alias table used when opening connections.
+
+    Maps server names (case-insensitive) to the address to dial: an IP address or host
+    name, optionally with a port, e.g. ``{"db.internal": "127.0.0.1:14330"}`` for an
+    SSH tunnel. TLS still validates the certificate against the original name. None
+    removes all aliases.
+    """
+    ...
+
+def set_resolver(resolver: Optional[Callable[[str, int], Union[str, List[str], None]]] = None) -> None:
+    """
+    Set a callable ``resolver(host, port)`` consulted for server names without an alias.
+
+    It returns the address to dial (``"10.0.0.5"``, ``"10.0.0.5:1433"`` or another host
+    name), a list of them, or None to use normal DNS. It is called on a background
+    thread for each uncached lookup, so keep it quick. None removes the resolver.
+    """
+    ...
+
+def set_proxies(routes: Optional[Dict[str, str]] = None) -> None:
+    """
+    Route connections to matching servers through a SOCKS5 or HTTP CONNECT proxy.
+
+    Keys are server names, ``*.suffix`` patterns or ``*`` for every server; values are
+    ``socks5://[user:pass@]host[:1080]`` or ``http://[user:pass@]host[:8080]``. Server
+    names are resolved by the proxy. None or an empty dict connects directly. Raises
+    ValueError for an unsupported or malformed URL.
+    """
+    ...
+
+def enable_wire_debug(path: Optional[Union[str, os.PathLike[str]]], *, include_values: bool = False) -> None:
+    """
+    Log the TDS traffic of connections opened from now on to ``path`` (appended to).
+
+    One line per packet sent and received (type, length, status, packet ID), plus a
+    summary of the tokens of each server response (DONE counts, ENVCHANGE types, INFO
+    and ERROR numbers, LOGINACK), followed up to its first COLMETADATA. Values are left
+    out unless ``include_values=True``, which adds message texts and the first bytes of
+    each packet; LOGIN7 packets are never dumped. With ``Encrypt=true`` the traffic
+    after login is TLS and is logged by record size only. ``path=None`` turns tracing
+    off again.
+    """
+    ...
+
+def set_certificate_expiry_warning(
+    days: Optional[int] = 30,
+    callback: Optional[Callable[[str, datetime, int], None]] = None,
+) -> None:
+    """
+    Report server certificates that expire within ``days`` days.
+
+    New connections check the certificate presented during the TLS handshake. One
+    close to expiry (or expired) raises a ``CertificateExpiryWarning`` through the
+    ``warnings`` module, or calls ``callback(subject, not_after, days_left)`` instead.
+    Each certificate is reported once per process. Certificates are only visible under
+    TLS 1.2. The check is off until this is called; ``days=None`` turns it off again.
+    """
+    ...
+
+def statement_fingerprint(sql: str) -> str:
+    """Stable 16-hex-digit hash of ``sql`` with literals, comments, case and layout normalized."""
+    ...
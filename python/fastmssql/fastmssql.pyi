@@ -9,7 +9,11 @@ High-performance Rust-backed Python driver for SQL Server with:
 - Memory-efficient result handling
 """
 
-from typing import Any, Coroutine, Dict, List, Optional, Tuple
+import os
+from typing import Any, Callable, Coroutine, Dict, Iterable, Iterator, List, Optional, Tuple, Union
+from datetime import date, datetime, time
+from decimal import Decimal
+from uuid import UUID
 from enum import StrEnum
 from .fastmssql import _RustConnection, _RustTransaction
 
@@ -25,6 +29,19 @@ class PoolConfig:
         connection_timeout_secs: Timeout for acquiring a connection in seconds (default: 30)
         test_on_check_out: Whether to test connections when checking out (default: None)
         retry_connection: Whether to retry connection attempts (default: None)
+        health_check_interval_secs: Interval of the background idle connection maintenance
+            task, which pings idle connections, replaces dead or expired ones and restores
+            min_idle (default: None = disabled)
+        login_timeout_secs: Limit on opening one physical connection (DNS lookup, TCP
+            connect, Azure token, TLS handshake and login). When it runs out the error
+            names the slow stage (default: None = bounded only by connection_timeout_secs)
+        max_concurrent_logins: Limit on connections being opened at the same time; further
+            ones wait for a slot, so a cold pool doesn't send max_size logins at once
+            (default: None = unlimited)
+        login_interval_secs: Minimum time between the starts of two logins in this pool,
+            e.g. to stay under Azure SQL's login rate limit (default: None = no spacing)
+        login_jitter_secs: Up to this many seconds are randomly added to each spacing, so
+            pools started together don't log in in lockstep (default: None)
 
     Performance Note:
         Pool size should match your actual concurrency needs, not theoretical maximum.
@@ -39,6 +56,11 @@ class PoolConfig:
     connection_timeout_secs: Optional[int]
     test_on_check_out: Optional[bool]
     retry_connection: Optional[bool]
+    health_check_interval_secs: Optional[int]
+    login_timeout_secs: Optional[int]
+    max_concurrent_logins: Optional[int]
+    login_interval_secs: Optional[float]
+    login_jitter_secs: Optional[float]
 
     def __init__(
         self,
@@ -49,6 +71,11 @@ class PoolConfig:
         connection_timeout_secs: int = 30,
         test_on_check_out: Optional[bool] = None,
         retry_connection: Optional[bool] = None,
+        health_check_interval_secs: Optional[int] = None,
+        login_timeout_secs: Optional[int] = None,
+        max_concurrent_logins: Optional[int] = None,
+        login_interval_secs: Optional[float] = None,
+        login_jitter_secs: Optional[float] = None,
     ) -> None: ...
     @staticmethod
     def one() -> PoolConfig:
@@ -108,29 +135,97 @@ class ApplicationIntent(StrEnum):
     READ_WRITE: str
     """Read-write workload."""
 
-class SqlError(Exception):
+class FastMssqlError(Exception):
+    """Base class of every exception raised by fastmssql."""
+
+    ...
+
+class InterfaceError(FastMssqlError):
+    """Raised when the API is misused, e.g. a Transaction is used before ``begin()``."""
+
+    ...
+
+class PolicyViolation(InterfaceError):
+    """Raised when a statement is rejected by the connection's ``StatementPolicy``; it is never sent."""
+
+    ...
+
+class ResultLimitError(InterfaceError):
+    """
+    Raised when a result is wider than the connection's ``max_result_columns`` or has a
+    row larger than its ``max_row_bytes``.
+
+    The limits are checked as the result arrives, so at most one oversized row is read;
+    a pooled connection is then discarded rather than reading the rest.
+    """
+
+    ...
+
+class DatabaseError(FastMssqlError):
+    """Base class of errors from the database, the connection or data conversion."""
+
+    ...
+
+class SqlError(DatabaseError):
     """
     Raised when the SQL Server returns an error response.
 
+    Constraint violations raise ``IntegrityError``, invalid SQL or object names
+    ``ProgrammingError`` and login failures ``AuthenticationError``.
+
     Attributes:
         code: SQL Server error number (e.g. 208 for object not found).
+        number: Same as ``code``, named after ``ERROR_NUMBER()``.
         message: Human-readable error message from the server.
         state: SQL Server error state byte.
+        class_: Severity (11-16 for user errors, 17 and up for resource or system errors).
+        server: Name of the server instance that raised the error.
+        procedure: Stored procedure or trigger that raised the error, or None.
+        line_number: Line of the batch or procedure the error was raised on.
+        hint: What to check for login failures with a known cause, otherwise None.
 
     Example::
 
         try:
             await conn.execute("INVALID SQL")
         except SqlError as e:
-            print(e.code, e.message, e.state)
+            print(e.number, e.class_, e.procedure, e.line_number, e.message)
     """
 
     code: int
+    number: int
     message: str
     state: int
+    class_: int
+    server: str
+    procedure: Optional[str]
+    line_number: int
+    hint: Optional[str]
+    ...
+
+class IntegrityError(SqlError):
+    """
+    Raised for constraint violations: duplicate keys (2627, 2601), foreign key or CHECK
+    conflicts (547) and NULL in a NOT NULL column (515).
+    """
+
     ...
 
-class SqlConnectionError(Exception):
+class ProgrammingError(SqlError):
+    """
+    Raised for invalid SQL: syntax errors (102, 105, 156), unknown columns (207),
+    objects (208) or procedures (2812), undeclared variables (137) and wrong procedure
+    arguments (201, 8144, 8145).
+    """
+
+    ...
+
+class OperationalError(DatabaseError):
+    """Base class of connection, pool and authentication failures."""
+
+    ...
+
+class SqlConnectionError(OperationalError):
     """
     Raised when a network I/O or routing error occurs connecting to SQL Server.
 
@@ -145,18 +240,98 @@ class SqlConnectionError(Exception):
     port: Optional[int]
     ...
 
-class TlsError(Exception):
+class TlsError(SqlConnectionError):
     """
     Raised when a TLS/SSL handshake error occurs.
 
     Attributes:
         message: Human-readable error description.
+        hint: What to check, e.g. trusting the server's CA for an unknown issuer.
+    """
+
+    message: str
+    hint: str
+    ...
+
+class PoolTimeoutError(SqlConnectionError):
+    """
+    Raised when no pooled connection became available within ``connection_timeout_secs``.
+
+    The message says whether the pool was busy or why new connections could not be opened.
+    """
+
+    message: str
+    ...
+
+class AuthenticationError(OperationalError):
+    """
+    Raised when the server rejects the login (18456 and related errors) or an Azure
+    token cannot be obtained.
+
+    Attributes:
+        message: Human-readable error description.
+        code: SQL Server error number, for login failures reported by the server.
+        state: SQL Server error state, for login failures reported by the server.
+
+    Login failures reported by the server also carry ``number``, ``class_``, ``server``,
+    ``procedure``, ``line_number`` and ``hint`` as on ``SqlError``; the hint is also
+    appended to the exception text.
+
+    A login the server refuses raises this error (or a subclass) even when the pool
+    kept retrying until ``connection_timeout_secs``, rather than ``PoolTimeoutError``.
+    """
+
+    message: str
+    code: Optional[int]
+    state: Optional[int]
+    hint: Optional[str]
+    ...
+
+class InvalidCredentialsError(AuthenticationError, SqlError):
+    """
+    Raised when the login does not exist or the password is wrong (18456 with states
+    2, 5, 6, 8 or 9).
+
+    On-premises SQL Server reports every failed login with state 1, which raises a
+    plain ``AuthenticationError``; its error log records the actual state.
+    """
+
+    ...
+
+class LoginDisabledError(AuthenticationError, SqlError):
+    """
+    Raised when the login exists but cannot be used: disabled (18470, 18456 state 7),
+    locked out (18486), or with an expired password or one that must be changed
+    (18487, 18488, 18456 state 18).
+    """
+
+    ...
+
+class DatabaseUnavailableError(AuthenticationError, SqlError):
+    """
+    Raised when the login cannot open its database (4060, 18456 states 38 and 40): it
+    does not exist, is offline, or the login has no user in it.
+    """
+
+    ...
+
+class QueryTimeoutError(OperationalError):
+    """
+    Raised when a statement run with ``timeout=`` was still running when it expired.
+
+    The statement was cancelled and its connection discarded; the server rolls back
+    the statement's work.
     """
 
     message: str
     ...
 
-class ProtocolError(Exception):
+class CertificateExpiryWarning(UserWarning):
+    """Warned when a server certificate is close to expiry; see ``set_certificate_expiry_warning()``."""
+
+    ...
+
+class ProtocolError(DatabaseError):
     """
     Raised when a protocol-level parsing error occurs during request or response handling.
 
@@ -167,7 +342,7 @@ class ProtocolError(Exception):
     message: str
     ...
 
-class ConversionError(Exception):
+class ConversionError(DatabaseError):
     """
     Raised when a type conversion or encoding error occurs.
 
@@ -178,6 +353,94 @@ class ConversionError(Exception):
     message: str
     ...
 
+class DataError(ConversionError):
+    """
+    Raised by the typed FastRow getters (``get_int``, ``get_str``, ...) when a column
+    holds a value of a different type.
+
+    Attributes:
+        column: The column name or index that was requested.
+        message: Human-readable error description.
+    """
+
+    column: str | int
+    message: str
+    ...
+
+class RetryPolicy:
+    """
+    How ``Connection.execute()`` retries statements that fail with a transient error.
+
+    A statement that fails with one of the ``retry_on`` error numbers is re-run after
+    an exponential backoff (with jitter) until it succeeds or ``max_attempts`` tries
+    have been made; the last error is then raised. Only statements passed to
+    ``execute()`` with ``idempotent=True`` are retried.
+
+    Attributes:
+        max_attempts: Total number of tries, including the first one (default: 3)
+        backoff_secs: Delay before the first retry; it doubles for each further retry
+            (default: 0.1)
+        max_backoff_secs: Upper bound for the delay between retries (default: 5.0)
+        retry_on: SQL Server error numbers that are retried (default: deadlock victim
+            1205 and the transient Azure SQL errors 40197, 40501, 40613, 49918, 49919,
+            49920, 10928, 10929)
+
+    Example::
+
+        conn = Connection(conn_str, retry_policy=RetryPolicy(max_attempts=5))
+    """
+
+    max_attempts: int
+    backoff_secs: float
+    max_backoff_secs: float
+    retry_on: List[int]
+
+    def __init__(
+        self,
+        max_attempts: int = 3,
+        backoff_secs: float = 0.1,
+        max_backoff_secs: float = 5.0,
+        retry_on: Optional[List[int]] = None,
+    ) -> None: ...
+
+class StatementPolicy:
+    """
+    Rules a ``Connection`` checks before sending each statement.
+
+    A statement is rejected with ``PolicyViolation`` when it matches a ``deny``
+    pattern, when ``allow`` patterns are given and it matches none of them, when
+    ``require_where`` is set and it contains a DELETE or UPDATE without a WHERE
+    clause, or when ``check`` returns ``False`` or a string (used as the reason).
+    Patterns are regular expressions searched anywhere in the statement; strings are
+    compiled case-insensitively and ``re.Pattern`` objects are used as given.
+
+    The WHERE check is lexical: comments, string literals and bracketed names are
+    skipped, but a WHERE inside a subquery does not count for the outer statement.
+
+    Attributes:
+        deny: Patterns that block a statement they match
+        allow: Patterns of which a statement must match one, when any are given
+        require_where: Whether DELETE and UPDATE statements need a WHERE clause
+
+    Example::
+
+        policy = StatementPolicy(deny=[r"\\bDROP\\b", r"\\bTRUNCATE\\b"], require_where=True)
+        conn = Connection(conn_str, statement_policy=policy)
+    """
+
+    deny: List[str]
+    allow: List[str]
+    require_where: bool
+
+    def __init__(
+        self,
+        deny: Optional[List[Union[str, Any]]] = None,
+        allow: Optional[List[Union[str, Any]]] = None,
+        *,
+        require_where: bool = False,
+        check: Optional[Callable[[str], Union[bool, str, None]]] = None,
+    ) -> None: ...
+
 class SslConfig:
     """
     Configuration for SSL/TLS encrypted connections.
@@ -234,6 +497,10 @@ class FastRow:
         """Get list of all column names in this row."""
         ...
 
+    def keys(self) -> List[str]:
+        """Get list of all column names, as for a mapping; same as columns()."""
+        ...
+
     def __len__(self) -> int:
         """Get number of columns in this row."""
         ...
@@ -246,6 +513,64 @@ class FastRow:
         """Get column value by index."""
         ...
 
+    def rowversion(self, column: str | int) -> Optional[int]:
+        """
+        Decode a TIMESTAMP/ROWVERSION column (8 big-endian bytes) as an int.
+
+        Returns None for NULL. Raises ValueError if the value is not 8 bytes of binary.
+        """
+        ...
+
+    def get_int(self, column: str | int) -> Optional[int]:
+        """Integer value (INT/BIGINT/SMALLINT/TINYINT/BIT or an integral DECIMAL). None for NULL; raises DataError otherwise."""
+        ...
+
+    def get_float(self, column: str | int) -> Optional[float]:
+        """Float value of any numeric column. None for NULL; raises DataError otherwise."""
+        ...
+
+    def get_decimal(self, column: str | int) -> Optional[Decimal]:
+        """Decimal value; ints and floats are converted (floats via their repr). None for NULL; raises DataError otherwise."""
+        ...
+
+    def get_str(self, column: str | int) -> Optional[str]:
+        """String value. None for NULL; raises DataError otherwise."""
+        ...
+
+    def get_bool(self, column: str | int) -> Optional[bool]:
+        """Boolean value of a BIT column, or of an integer 0/1 (a computed ``CASE ... THEN 1 ELSE 0`` flag). None for NULL; raises DataError otherwise."""
+        ...
+
+    def get_bytes(self, column: str | int) -> Optional[bytes]:
+        """Bytes value of a binary column. None for NULL; raises DataError otherwise."""
+        ...
+
+    def get_datetime(self, column: str | int) -> Optional[datetime]:
+        """datetime value (aware for DATETIMEOFFSET). None for NULL; raises DataError otherwise."""
+        ...
+
+    def get_date(self, column: str | int) -> Optional[date]:
+        """date value; datetimes are truncated to their date. None for NULL; raises DataError otherwise."""
+        ...
+
+    def get_time(self, column: str | int) -> Optional[time]:
+        """time value of a TIME column, also with time_as_timedelta. None for NULL; raises DataError otherwise."""
+        ...
+
+    def get_uuid(self, column: str | int) -> Optional[UUID]:
+        """uuid.UUID parsed from a UNIQUEIDENTIFIER column. None for NULL; raises DataError otherwise."""
+        ...
+
+    def column_set(self, column: str | int) -> Optional[Dict[str, str]]:
+        """
+        Decode a sparse COLUMN_SET value into a dict of the sparse columns it contains.
+
+        NULL sparse columns are absent from the dict; values are their XML text
+        (varbinary values are base64). Returns None for NULL. Raises ValueError if
+        the value is not column-set XML.
+        """
+        ...
+
     def values(self) -> List[Any]:
         """Get all column values as a list in column order."""
         ...
@@ -286,6 +611,78 @@ class QueryStream:
         """Get list of all column names in the result set."""
         ...
 
+    def column_metadata(self) -> List[Dict[str, Any]]:
+        """
+        Get per-column type metadata from the result set.
+
+        Each dict has the keys ``name``, ``type`` (SQL Server type name, e.g. "nvarchar")
+        and ``is_unicode`` (True for NCHAR/NVARCHAR/NTEXT). Declared lengths are not part of
+        row metadata; use Connection.describe() for those.
+        """
+        ...
+
+    def schema_fingerprint(self) -> Optional[str]:
+        """
+        Stable hash of the column names and SQL Server types, in order, as 16 hex digits.
+
+        Suitable as part of a cache key: results with the same fingerprint have the same
+        shape. None when the result carries no column information.
+        """
+        ...
+
+    def row_hashes(self, columns: Optional[List[str]] = None) -> List[str]:
+        """
+        Stable hash of each row's values, as 16 hex digits, computed without converting
+        the rows to Python.
+
+        Compare the hashes of the same query run against two environments to find rows
+        that differ. Hashes depend on the values rather than the declared types: INT,
+        BIGINT and DECIMAL columns holding the same number hash the same, as do VARCHAR
+        and NVARCHAR columns holding the same text.
+
+        Args:
+            columns: Names of the columns to hash, in order; all columns by default.
+
+        Raises ValueError for an unknown column, or if rows were already read from the
+        result (call this before iterating).
+        """
+        ...
+
+    def to_ipc_bytes(self, compression: Optional[str] = None) -> bytes:
+        """
+        Serialize the rows as an Arrow IPC stream, to hand the result to another process
+        or cache it (e.g. in Redis) without pickling row objects.
+
+        The stream is built from the raw rows like ``query_arrow()`` builds its table,
+        and keeps the SQL Server column types for ``from_ipc_bytes()``. Requires pyarrow.
+
+        Args:
+            compression: ``"zstd"`` or ``"lz4"`` to compress the record batches; None
+                (the default) leaves them uncompressed.
+
+        Raises ValueError for another compression, or if rows were already read from
+        the result (call this before iterating).
+        """
+        ...
+
+    @staticmethod
+    def from_ipc_bytes(data: bytes) -> QueryStream:
+        """
+        Rebuild a result from the bytes of ``to_ipc_bytes()``. Rows hold the values as
+        pyarrow converts them, which are the types a query returns; ``column_metadata()``
+        and ``schema_fingerprint()`` match the original. Requires pyarrow.
+        """
+        ...
+
+    def extend(self, other: QueryStream) -> None:
+        """
+        Append the rows of ``other``, which must have the same column names and types.
+
+        Rows are moved rather than copied or converted, so ``other`` is left empty.
+        Raises ValueError if the columns do not match.
+        """
+        ...
+
     def reset(self) -> None:
         """Reset iteration to the beginning of the stream."""
         ...
@@ -326,6 +723,37 @@ class QueryStream:
         """Check if the stream is empty."""
         ...
 
+    @property
+    def rows_affected(self) -> Optional[int]:
+        """Rows affected, for results from ``Connection.execute_returning()`` and ``exec_sql()``; None otherwise."""
+        ...
+
+    @property
+    def messages(self) -> List[str]:
+        """Informational messages (``PRINT``, low-severity ``RAISERROR``) sent while the statement ran."""
+        ...
+
+    @property
+    def output_params(self) -> Optional[Dict[str, Any]]:
+        """OUTPUT parameter values by name, for results from ``Connection.exec_sql()``; None otherwise."""
+        ...
+
+    @property
+    def next_token(self) -> Optional[str]:
+        """Token for the next page, for results from ``Connection.fetch_page()``; None on the last page and otherwise."""
+        ...
+
+    @property
+    def execution_id(self) -> Optional[str]:
+        """
+        ID of the query() / simple_query() call that produced this result.
+
+        The same ID is attached as ``execution_id`` to exceptions raised by a call,
+        and written to CONTEXT_INFO when the connection uses ``trace_context_info``.
+        None for results built by other means (e.g. ``concat()``).
+        """
+        ...
+
     def has_rows(self) -> bool:
         """Check if stream has rows."""
         ...
@@ -362,6 +790,128 @@ class QueryStream:
         """
         ...
 
+class ResultSetStream:
+    """
+    Async iterator over the result sets of a batch, returned by Connection.stream_results().
+
+    Each ``__anext__`` yields a QueryStream holding one complete result set. At most
+    one result set is read ahead of the consumer. Dropping the iterator before the
+    last result set cancels the batch and discards its connection.
+    """
+
+    def __aiter__(self) -> ResultSetStream: ...
+    async def __anext__(self) -> QueryStream: ...
+    @property
+    def execution_id(self) -> str:
+        """ID of the stream_results() call, as attached to exceptions it raises."""
+        ...
+
+class ChunkStream:
+    """
+    Async iterator over a result in chunks, returned by Connection.fetch_chunks().
+
+    Each ``__anext__`` yields a list of FastRow; every list but the last holds exactly
+    ``chunk_size`` rows. Only the first result set is read.
+    """
+
+    def __aiter__(self) -> ChunkStream: ...
+    async def __anext__(self) -> List[FastRow]: ...
+    async def aclose(self) -> None:
+        """
+        Stop reading and release the connection; unread chunks are discarded.
+
+        If rows were still unread the statement is cancelled and the connection
+        discarded, as with ``cancel()``. Dropping the iterator does the same.
+        """
+        ...
+    def cancel(self) -> None:
+        """
+        Cancel the statement on the server; iteration then ends.
+
+        Unlike ``aclose()`` the rest of the result is not read; the connection is
+        discarded instead. Only a connection that is unencrypted after login gets a
+        TDS attention signal; with encryption (the default) the connection is closed,
+        which makes the server abort the statement.
+        """
+        ...
+    @property
+    def execution_id(self) -> str:
+        """ID of the fetch_chunks() call, as attached to exceptions it raises."""
+        ...
+
+class PinnedConnection:
+    """
+    One pooled connection held for a sequence of statements, returned by
+    Connection.acquire().
+
+    The connection is checked out by ``async with`` and returned to the pool when the
+    block exits, after rolling back any transaction left open. If the block raised, the
+    connection is discarded instead. Statements run outside the block raise
+    InterfaceError.
+    """
+
+    async def __aenter__(self) -> PinnedConnection: ...
+    async def __aexit__(self, exc_type: Any, exc_val: Any, exc_tb: Any) -> bool: ...
+    async def query(self, query: str, parameters: Optional[Any] = None) -> QueryStream:
+        """Run a query on the pinned connection and return its first result set."""
+        ...
+    async def execute(self, query: str, parameters: Optional[Any] = None) -> int:
+        """Run a statement on the pinned connection and return the rows affected."""
+        ...
+    async def simple_query(self, query: str) -> QueryStream:
+        """Run an unparameterised batch on the pinned connection."""
+        ...
+    async def bulk_insert(
+        self,
+        table: str,
+        columns: List[str],
+        data: List[List[Any]],
+        *,
+        bulk_copy: bool = False,
+        verify_key: Optional[str] = None,
+    ) -> Union[int, Dict[str, Any]]:
+        """Insert rows on the pinned connection, e.g. into a #temp table created in the block."""
+        ...
+    async def release(self) -> None:
+        """Return the connection to the pool early; later statements raise InterfaceError."""
+        ...
+    def is_acquired(self) -> bool:
+        """Whether a pooled connection is currently held."""
+        ...
+
+class RowStream:
+    """
+    Async iterator over the rows of a result, returned by Connection.query_stream().
+
+    Each ``__anext__`` yields a FastRow. Rows are fetched from the server in small
+    batches, and only the first result set is read.
+    """
+
+    def __aiter__(self) -> RowStream: ...
+    async def __anext__(self) -> FastRow: ...
+    async def aclose(self) -> None:
+        """
+        Stop reading and release the connection; unread rows are discarded.
+
+        If rows were still unread the statement is cancelled and the connection
+        discarded, as with ``cancel()``. Dropping the iterator does the same.
+        """
+        ...
+    def cancel(self) -> None:
+        """
+        Cancel the statement on the server; iteration then ends.
+
+        Unlike ``aclose()`` the rest of the result is not read; the connection is
+        discarded instead. Only a connection that is unencrypted after login gets a
+        TDS attention signal; with encryption (the default) the connection is closed,
+        which makes the server abort the statement.
+        """
+        ...
+    @property
+    def execution_id(self) -> str:
+        """ID of the query_stream() call, as attached to exceptions it raises."""
+        ...
+
 class Parameter:
     """
     Parameter object for SQL queries with optional type hints.
@@ -373,11 +923,13 @@ class Parameter:
         value: The parameter value (any Python type that can be converted to SQL)
         sql_type: Optional SQL Server type name (e.g., 'INT', 'VARCHAR', 'DATETIME2')
         is_expanded: Whether this parameter is an iterable for IN clause expansion
+        is_json_list: Whether this parameter was created by ``Parameter.json_list()``
     """
 
     value: Any
     sql_type: Optional[str]
     is_expanded: bool
+    is_json_list: bool
 
     def __init__(
         self,
@@ -389,7 +941,40 @@ class Parameter:
 
         Args:
             value: The parameter value
-            sql_type: Optional SQL Server type name for explicit type conversion
+            sql_type: Optional SQL Server type name for explicit type conversion.
+                "SMALLDATETIME" binds a datetime/date as SMALLDATETIME, rounded to the
+                minute as SQL Server does; "XML" binds a str (or an ElementTree
+                Element) as XML instead of NVARCHAR; other type names are informational.
+                A value of None is sent as a NULL of this type.
+        """
+        ...
+
+    @staticmethod
+    def null(sql_type: str) -> "Parameter":
+        """
+        Create a NULL parameter of the given SQL Server type.
+
+        Needed where a plain None (sent as a TINYINT NULL) cannot be converted, e.g.
+        inserting NULL into a VARBINARY or DATETIME column. Length, precision and
+        scale in the type name ("VARBINARY(MAX)", "DECIMAL(18, 2)") are ignored.
+
+        Raises:
+            ValueError: If sql_type is not a SQL Server type name.
+        """
+        ...
+
+    @staticmethod
+    def json_list(values: Iterable[Any]) -> "Parameter":
+        """
+        Send a list of any length as one NVARCHAR(MAX) JSON array.
+
+        Bound to ``IN (@Pn)`` (or ``NOT IN (@Pn)``), the predicate is rewritten to
+        ``IN (SELECT value FROM OPENJSON(@Pn))``, so large ID lists need neither a
+        placeholder per value nor the 2,100 parameter limit. Other uses of the
+        placeholder are left alone. The value is the JSON text.
+
+        Raises:
+            ValueError: If values is not an iterable, or holds bytes or non-finite floats.
         """
         ...
 
@@ -470,6 +1055,95 @@ class Parameters:
         """Get string representation of parameters."""
         ...
 
+class SQL:
+    """
+    A trusted SQL fragment, and a template for composing statements safely.
+
+    ``format()`` fills ``{}``, ``{0}`` and ``{name}`` placeholders with
+    ``Identifier`` (bracket-quoted names), ``Param`` (bound as ``@P1..@Pn``), other
+    ``SQL`` fragments or ``Composed`` statements; anything else raises TypeError.
+    Write ``{{`` and ``}}`` for literal braces.
+
+    Example::
+
+        stmt = SQL("SELECT * FROM {table} WHERE id = {id}").format(
+            table=Identifier("dbo.Users"), id=Param(5)
+        )
+        stmt.sql     # 'SELECT * FROM [dbo].[Users] WHERE id = @P1'
+        stmt.params  # [5]
+        result = await conn.query(*stmt)
+    """
+
+    string: str
+
+    def __init__(self, template: str) -> None: ...
+    def format(self, *args: Any, **kwargs: Any) -> Composed:
+        """Fill the placeholders; the same placeholder used twice binds its Param once."""
+        ...
+
+    def join(self, items: Iterable[Any]) -> Composed:
+        """Join ``items`` with this fragment, e.g. ``SQL(", ").join(map(Identifier, cols))``."""
+        ...
+
+    def __add__(self, other: Any) -> Composed: ...
+
+class Identifier:
+    """
+    A table, column or other name, bracket-quoted. One argument is split on dots
+    (``Identifier("dbo.Users")`` is ``[dbo].[Users]``); several arguments are the
+    parts of a multipart name, taken literally (``Identifier("dbo", "odd.name")``).
+    """
+
+    string: str
+
+    def __init__(self, *parts: str) -> None: ...
+    def __add__(self, other: Any) -> Composed: ...
+
+class Param:
+    """A value bound as a statement parameter, optionally with an explicit SQL type as ``Parameter`` takes it."""
+
+    value: Any
+    sql_type: Optional[str]
+
+    def __init__(self, value: Any, sql_type: Optional[str] = None) -> None: ...
+
+class Composed:
+    """A composed statement; unpacks into ``(sql, params)`` for ``conn.query(*stmt)``."""
+
+    sql: str
+    """Statement text with ``@P1..@Pn`` placeholders."""
+    params: List[Any]
+    """Values for the placeholders, in order."""
+
+    def __iter__(self) -> Iterator[Any]: ...
+    def __add__(self, other: Any) -> Composed: ...
+
+class Batch:
+    """
+    Buffered unit of work for Connection.apply().
+
+    Statements queued with add() are sent together as one transaction in a
+    single round trip. Each statement numbers its own placeholders from @P1.
+    """
+
+    param_count: int
+
+    def __init__(self) -> None: ...
+    def add(self, sql: str, params: Optional[List[Any]] = None) -> None:
+        """
+        Queue a statement. Parameters are converted immediately.
+
+        Raises:
+            ValueError: If the batch would exceed SQL Server's 2,100-parameter limit
+        """
+        ...
+
+    def clear(self) -> None:
+        """Remove all queued statements."""
+        ...
+
+    def __len__(self) -> int: ...
+
 class AzureCredentialType(StrEnum):
     """Azure credential type constants for authentication."""
 
@@ -494,7 +1168,12 @@ class AzureCredential:
 
     @staticmethod
     def service_principal(
-        client_id: str, client_secret: str, tenant_id: str
+        client_id: str,
+        client_secret: str,
+        tenant_id: str,
+        *,
+        resource: Optional[str] = None,
+        authority_host: Optional[str] = None,
     ) -> AzureCredential:
         """
         Create Azure credential for Service Principal authentication.
@@ -503,27 +1182,87 @@ class AzureCredential:
             client_id: Azure AD application (client) ID
             client_secret: Azure AD application client secret
             tenant_id: Azure AD tenant ID
+            resource: Token audience for clouds other than Azure public, e.g.
+                      "https://database.usgovcloudapi.net/" (Azure Government) or
+                      "https://database.chinacloudapi.cn/" (Azure China); the
+                      "/.default" scope form is accepted too
+            authority_host: Microsoft Entra ID login host for client-credential tokens,
+                      e.g. "login.microsoftonline.us" (Azure Government) or
+                      "login.partner.microsoftonline.cn" (Azure China); defaults to
+                      AZURE_AUTHORITY_HOST, else "login.microsoftonline.com"
+
+        Returns:
+            AzureCredential configured for Service Principal authentication
+        """
+        ...
+
+    @staticmethod
+    def service_principal_certificate(
+        client_id: str,
+        tenant_id: str,
+        cert_path: Union[str, os.PathLike],
+        *,
+        resource: Optional[str] = None,
+        authority_host: Optional[str] = None,
+    ) -> AzureCredential:
+        """
+        Create Azure credential for a Service Principal that signs in with a certificate.
+
+        Token requests carry a client assertion (a JWT signed with the certificate's
+        private key) instead of a client secret. ``config`` shows ``cert_path`` and
+        ``cert_thumbprint``, never the key.
+
+        Args:
+            client_id: Azure AD application (client) ID
+            tenant_id: Azure AD tenant ID
+            cert_path: PEM file holding the certificate uploaded to the app
+                      registration and its unencrypted RSA private key (PKCS#8 or
+                      PKCS#1); read once, when the credential is created
+            resource: Token audience, as for ``service_principal()``
+            authority_host: Microsoft Entra ID login host, as for ``service_principal()``
 
         Returns:
             AzureCredential configured for Service Principal authentication
+
+        Raises:
+            ValueError: The file cannot be read or lacks a certificate or RSA key
         """
         ...
 
     @staticmethod
-    def managed_identity(client_id: Optional[str] = None) -> AzureCredential:
+    def managed_identity(
+        client_id: Optional[str] = None,
+        *,
+        endpoint: Optional[str] = None,
+        api_version: Optional[str] = None,
+        resource: Optional[str] = None,
+    ) -> AzureCredential:
         """
         Create Azure credential for Managed Identity authentication.
 
         Args:
             client_id: Optional client ID for user-assigned managed identity.
                       If None, uses system-assigned managed identity.
+            endpoint: Token endpoint to use instead of the Azure instance metadata
+                      service. By default Azure Arc's local endpoint is used when the
+                      Arc agent sets IDENTITY_ENDPOINT and IMDS_ENDPOINT.
+            api_version: api-version to request (default "2021-02-01", or
+                      "2020-06-01" for Azure Arc).
+            resource: Token audience for clouds other than Azure public, e.g.
+                      "https://database.usgovcloudapi.net/" (Azure Government) or
+                      "https://database.chinacloudapi.cn/" (Azure China); the
+                      "/.default" scope form is accepted too
 
         Returns:
             AzureCredential configured for Managed Identity authentication
 
+        Raises:
+            ValueError: If endpoint is not an http(s) URL, api_version is empty or
+                      resource is not an https URL
+
         Note:
-            This only works when running on Azure resources (VMs, Functions, App Service, etc.)
-            with managed identity enabled.
+            This only works on Azure resources (VMs, Functions, App Service, etc.) or
+            Azure Arc-enabled servers with managed identity enabled.
         """
         ...
 
@@ -545,10 +1284,22 @@ class AzureCredential:
         ...
 
     @staticmethod
-    def default() -> AzureCredential:
+    def default(
+        *, resource: Optional[str] = None, authority_host: Optional[str] = None
+    ) -> AzureCredential:
         """
         Create Azure credential using default credential chain.
 
+        Args:
+            resource: Token audience for clouds other than Azure public, e.g.
+                      "https://database.usgovcloudapi.net/" (Azure Government) or
+                      "https://database.chinacloudapi.cn/" (Azure China); the
+                      "/.default" scope form is accepted too
+            authority_host: Microsoft Entra ID login host for client-credential tokens,
+                      e.g. "login.microsoftonline.us" (Azure Government) or
+                      "login.partner.microsoftonline.cn" (Azure China); defaults to
+                      AZURE_AUTHORITY_HOST, else "login.microsoftonline.com"
+
         Returns:
             AzureCredential using Azure SDK's default credential chain
 
@@ -595,6 +1346,20 @@ class Connection:
         port: Optional[int] = None,
         instance_name: Optional[str] = None,
         application_name: Optional[str] = None,
+        rstrip_char: bool = False,
+        time_as_timedelta: bool = False,
+        parse_json: Union[bool, List[str]] = False,
+        parse_xml: Union[bool, str] = False,
+        trace_context_info: bool = False,
+        log_queries: bool = False,
+        log_parameters: bool = False,
+        redact_parameters: Optional[List[Union[int, str]]] = None,
+        on_message: Optional[Callable[[str], None]] = None,
+        retry_policy: Optional[RetryPolicy] = None,
+        paramstyle: Optional[str] = None,
+        statement_policy: Optional[Union[StatementPolicy, Callable[[str], Any]]] = None,
+        max_result_columns: Optional[int] = None,
+        max_row_bytes: Optional[int] = None,
     ) -> None:
         """
         Initialize a new SQL Server connection.
@@ -612,41 +1377,242 @@ class Connection:
             port: TCP port number (default: 1433)
             instance_name: Named instance of SQL Server
             application_name: Application name for SQL Server connection
+            rstrip_char: Strip trailing padding spaces from CHAR/NCHAR values (default: preserve)
+            time_as_timedelta: Return TIME values as ``datetime.timedelta`` since midnight
+                instead of ``datetime.time`` (default: ``time``)
+            parse_json: Return ``FOR JSON`` results as one row holding the parsed dict or
+                list (``True``), and also parse the named text columns of every row (a
+                list of column names) (default: JSON stays a string)
+            parse_xml: Return XML columns as ``xml.etree.ElementTree.Element`` objects
+                (``True`` or ``"etree"``) or ``lxml.etree`` elements (``"lxml"``)
+                (default: XML stays a string)
+            trace_context_info: Write each call's execution_id to the session's CONTEXT_INFO
+                before running it, for joining with server-side traces (default: off)
+            log_queries: Log each call's statement and execution_id at DEBUG level to the
+                ``fastmssql.query`` logger (default: off)
+            log_parameters: Include parameter values in those log records (default: off)
+            redact_parameters: Parameters logged as ``***``: 1-based positions (1 is @P1)
+                and/or name patterns with ``*`` and ``?`` wildcards, e.g. ``["*password*", 3]``
+            on_message: Called with each informational message (``PRINT``, ``RAISERROR``
+                with severity 10 or lower) as the server sends it (default: none)
+            retry_policy: Re-run ``execute(..., idempotent=True)`` statements that fail
+                with a transient error such as a deadlock (1205) or Azure SQL failover
+                (40613) (default: none)
+            paramstyle: Placeholder syntax of statements run with parameters: "native"
+                (``@P1``, the default), "qmark" (``?``, as in pyodbc) or "format"
+                (``%s``, as in pymssql); placeholders are rewritten to ``@P1..@Pn``
+            statement_policy: A ``StatementPolicy`` (or a callable used as its ``check``)
+                every statement must pass before it is sent; rejected statements raise
+                ``PolicyViolation`` (default: none)
+            max_result_columns: Raise ``ResultLimitError`` for a result with more columns
+                than this, e.g. an accidental ``SELECT *`` of a very wide table
+                (default: unlimited)
+            max_row_bytes: Raise ``ResultLimitError`` for a row whose values take more
+                bytes than this as received (strings counted as UTF-8) (default: unlimited)
+
+        Note:
+            - Either connection_string OR individual parameters must be provided
+            - When using individual parameters, either username/password OR azure_credential must be provided
+            - azure_credential and username/password are mutually exclusive
+        """
+        ...
+
+    def connect(self) -> Coroutine[Any, Any, bool]:
+        """Explicitly initialize the connection pool."""
+        ...
+
+    def disconnect(self) -> Coroutine[Any, Any, bool]:
+        """Explicitly close the connection pool and all connections."""
+        ...
+
+    def is_connected(self) -> Coroutine[Any, Any, bool]:
+        """Check if the connection pool is active and ready."""
+        ...
+
+    def describe(self, sql: str) -> Coroutine[Any, Any, List[Dict[str, Any]]]:
+        """
+        Describe the columns of the first result set ``sql`` would return, without running it.
+
+        Uses sp_describe_first_result_set. Each dict has the keys ``name``, ``type``
+        (base type, e.g. "nvarchar"), ``declared_type`` (e.g. "nvarchar(50)"), ``nullable``,
+        ``is_unicode``, ``max_length`` (bytes, -1 for MAX), ``char_length`` (characters for
+        character types, -1 for MAX/TEXT, otherwise None), ``precision``, ``scale``,
+        ``is_identity`` and ``is_computed``.
+        """
+        ...
+
+    def table_columns(self, table: str) -> Coroutine[Any, Any, List[Dict[str, Any]]]:
+        """
+        Describe the columns of ``table`` from the catalog views, in column order.
+
+        Each dict has the keys ``name``, ``type``, ``nullable``, ``is_identity``,
+        ``is_computed``, ``is_persisted``, ``has_default``, ``is_rowversion``,
+        ``is_generated_always`` and ``is_insertable`` (False for identity, computed,
+        rowversion and GENERATED ALWAYS columns). Raises ValueError if the table does
+        not exist.
+        """
+        ...
+
+    def query(
+        self,
+        sql: str,
+        params: Optional[List[Any]] = None,
+    ) -> Coroutine[Any, Any, QueryStream]:
+        """
+        Execute SELECT query that returns rows as an async stream.
+
+        Returns a QueryStream for memory-efficient iteration over large result sets.
+
+        Args:
+            sql: SQL query with @P1, @P2, etc. placeholders for parameters
+            params: List of parameter values in order
+        Returns:
+            QueryStream for iterating over result rows
+        """
+        ...
+
+    def fetch_column(
+        self,
+        sql: str,
+        params: Optional[List[Any]] = None,
+    ) -> Coroutine[Any, Any, List[Any]]:
+        """
+        Run a query and return the values of its first column as a list.
+
+        Skips building FastRow objects, which makes it the fastest way to fetch e.g.
+        a list of IDs. Other columns are ignored; an empty result gives ``[]``.
+        """
+        ...
+
+    def query_arrow(
+        self,
+        sql: str,
+        params: Optional[List[Any]] = None,
+    ) -> Coroutine[Any, Any, Any]:
+        """
+        Run a query and return its first result set as a ``pyarrow.Table``.
+
+        Values are written into Arrow buffers as rows arrive, without creating a
+        Python object per value. Integers keep their width, DECIMAL and MONEY become
+        ``decimal128``, DATETIME/DATETIME2 ``timestamp[us]``, DATETIMEOFFSET
+        ``timestamp[us, tz=UTC]``, and strings, GUIDs and XML ``large_string``.
+        Requires pyarrow (``pip install fastmssql[arrow]``).
+        """
+        ...
 
-        Note:
-            - Either connection_string OR individual parameters must be provided
-            - When using individual parameters, either username/password OR azure_credential must be provided
-            - azure_credential and username/password are mutually exclusive
+    def query_polars(
+        self,
+        sql: str,
+        params: Optional[List[Any]] = None,
+    ) -> Coroutine[Any, Any, Any]:
+        """
+        Run a query and return its first result set as a ``polars.DataFrame``.
+
+        The columns are built as for ``query_arrow()`` and handed to polars through
+        the Arrow C data interface, without copying them or going through pandas.
+        Requires polars and pyarrow (``pip install fastmssql[polars]``).
         """
         ...
 
-    def connect(self) -> Coroutine[Any, Any, bool]:
-        """Explicitly initialize the connection pool."""
+    def query_numpy(
+        self,
+        sql: str,
+        params: Optional[List[Any]] = None,
+    ) -> Coroutine[Any, Any, Dict[str, Any]]:
+        """
+        Run a query and return its first result set as a dict of column name to 1-D
+        ``numpy.ndarray``.
+
+        Integer and float columns keep their width; with NULLs they become float64
+        with NaN. DATETIME/DATETIME2 become ``datetime64[us]`` (DATETIMEOFFSET in UTC),
+        DATE ``datetime64[D]`` and TIME ``timedelta64[us]``, with NaT for NULL.
+        Booleans with NULLs, decimals, strings and binary are object arrays.
+        Requires numpy but not pyarrow (``pip install fastmssql[numpy]``).
+        """
         ...
 
-    def disconnect(self) -> Coroutine[Any, Any, bool]:
-        """Explicitly close the connection pool and all connections."""
+    def query_stream(
+        self,
+        sql: str,
+        params: Optional[List[Any]] = None,
+    ) -> RowStream:
+        """
+        Iterate over the rows of the first result set without buffering them all.
+
+        Rows are read from the server in small batches as the iterator advances, so
+        exports of millions of rows run in constant memory. The pooled connection stays
+        checked out until the rows are exhausted or ``aclose()`` is awaited.
+
+        Example:
+            async for row in conn.query_stream("SELECT * FROM events WHERE day = @P1", [day]):
+                writer.writerow(row.values())
+        """
         ...
 
-    def is_connected(self) -> Coroutine[Any, Any, bool]:
-        """Check if the connection pool is active and ready."""
+    def fetch_chunks(
+        self,
+        sql: str,
+        params: Optional[List[Any]] = None,
+        chunk_size: int = 10_000,
+    ) -> ChunkStream:
+        """
+        Iterate over the rows of the first result set in lists of up to ``chunk_size`` rows.
+
+        The next chunk is read from the server while the current one is processed, so
+        large results can be pipelined without holding them in memory or paying async
+        overhead per row. The pooled connection stays checked out until the rows are
+        exhausted or ``aclose()`` is awaited. Raises ValueError if ``chunk_size`` < 1.
+
+        Example:
+            async for chunk in conn.fetch_chunks("SELECT * FROM events", chunk_size=5000):
+                await sink.write_many([row.values() for row in chunk])
+        """
         ...
 
-    def query(
+    def fetch_page(
         self,
         sql: str,
         params: Optional[List[Any]] = None,
+        *,
+        page_size: int,
+        key: Optional[Union[str, List[str]]] = None,
+        token: Optional[str] = None,
     ) -> Coroutine[Any, Any, QueryStream]:
         """
-        Execute SELECT query that returns rows as an async stream.
+        Fetch one page of up to ``page_size`` rows of the query.
 
-        Returns a QueryStream for memory-efficient iteration over large result sets.
+        The result's ``next_token`` is an opaque string to pass back as ``token=`` for
+        the page after it, or None on the last page. With ``key`` (a column, or
+        columns that are unique together) pages follow the key in ascending order and
+        the query must not have its own ORDER BY; without it the query must end with
+        an ORDER BY and pages are read with OFFSET/FETCH. Key columns must not be NULL.
 
-        Args:
-            sql: SQL query with @P1, @P2, etc. placeholders for parameters
-            params: List of parameter values in order
-        Returns:
-            QueryStream for iterating over result rows
+        Raises:
+            ValueError: If ``page_size`` < 1, or the token is invalid or was issued for
+                a different query or key.
+
+        Example:
+            page = await conn.fetch_page("SELECT id, name FROM users", page_size=50, key="id", token=request_token)
+            return {"items": [r.to_dict() for r in page], "next": page.next_token}
+        """
+        ...
+
+    def stream_results(
+        self,
+        sql: str,
+        params: Optional[List[Any]] = None,
+    ) -> ResultSetStream:
+        """
+        Iterate over the result sets of a multi-statement batch or stored procedure.
+
+        Each item is a QueryStream for one result set; result sets are fetched as the
+        iterator advances. Statements that return no rows (e.g. INSERT/UPDATE) do not
+        produce an item - use execute() when you need their affected counts.
+
+        Example:
+            async for result_set in conn.stream_results("EXEC dbo.report @P1", [2024]):
+                for row in result_set:
+                    ...
         """
         ...
 
@@ -672,6 +1638,13 @@ class Connection:
         self,
         sql: str,
         params: Optional[List[Any]] = None,
+        *,
+        idempotent: bool = False,
+        timeout: Optional[float] = None,
+        recover_on_timeout: bool = False,
+        label: Optional[str] = None,
+        on_progress: Optional[Callable[[int], Any]] = None,
+        progress_interval: Optional[float] = None,
     ) -> Coroutine[Any, Any, int]:
         """
         Execute INSERT/UPDATE/DELETE/DDL command.
@@ -679,12 +1652,132 @@ class Connection:
         Args:
             sql: SQL command with @P1, @P2, etc. placeholders
             params: List of parameter values in order
+            idempotent: Pass True for a statement that is safe to run twice, so the
+                connection's retry policy may re-run it after a transient error
+                (default: False; a multi-statement batch may have committed part of
+                its work before failing)
+            timeout: Seconds the statement may run; a statement still running is
+                cancelled and raises QueryTimeoutError
+            recover_on_timeout: After a timeout, check the cancelled connection in the
+                background and return it to the pool if it is still healthy, instead
+                of discarding it
+            label: Appended to the statement as ``/* fastmssql:label=<label> */`` so its
+                runs can be found in Query Store, and counted in ``pool_stats()["labels"]``;
+                letters, digits, '-', '_', '.' and ':' only
+            on_progress: Called with the number of rows the running statement has
+                affected so far, polled from ``sys.dm_exec_query_profiles`` on a
+                second pooled connection (needs ``VIEW SERVER STATE``) and passed
+                on whenever it changes
+            progress_interval: Seconds between progress polls (default 5)
+
+        Cancelling the returned awaitable (``.cancel()``, a cancelled task or an
+        ``asyncio.wait_for()`` timeout) stops the statement. Only a connection that is
+        unencrypted after login gets a TDS attention signal; with encryption (the
+        default) the connection is closed instead, which makes the server abort the
+        statement. Either way the connection is discarded.
 
         Returns:
             Number of affected rows
         """
         ...
 
+    def execute_counts(
+        self,
+        sql: str,
+        params: Optional[List[Any]] = None,
+    ) -> Coroutine[Any, Any, List[int]]:
+        """
+        Execute a command like ``execute()`` but return each statement's row count.
+
+        For a batch such as ``UPDATE ...; DELETE ...`` the result is one count per
+        statement, in order, where ``execute()`` returns their sum.
+
+        Args:
+            sql: SQL command with @P1, @P2, etc. placeholders
+            params: List of parameter values in order
+
+        Returns:
+            Number of affected rows for each statement
+        """
+        ...
+
+    def execute_returning(
+        self,
+        sql: str,
+        params: Optional[List[Any]] = None,
+    ) -> Coroutine[Any, Any, QueryStream]:
+        """
+        Execute a data-modifying statement and return both its rows and its row count.
+
+        Use for INSERT/UPDATE/DELETE/MERGE with an OUTPUT clause: the returned
+        QueryStream holds the OUTPUT rows and ``rows_affected`` holds the count.
+        Without OUTPUT the stream is empty and only ``rows_affected`` is useful.
+
+        ``rows_affected`` is the count of the last statement. For a batch or an
+        ``EXEC`` of a procedure that modifies rows and then SELECTs, the count of
+        each statement is not available together with the rows; use
+        ``execute_counts()`` for the counts alone, or have the procedure return
+        its count as an OUTPUT parameter or result column.
+
+        Args:
+            sql: SQL command with @P1, @P2, etc. placeholders
+            params: List of parameter values in order
+
+        Returns:
+            QueryStream with ``rows_affected`` set to the count of the last statement
+        """
+        ...
+
+    def touched_tables(
+        self,
+        sql: str,
+        params: Optional[List[Any]] = None,
+    ) -> Coroutine[Any, Any, Dict[str, List[str]]]:
+        """
+        Report which tables a statement would read and write, without running it.
+
+        The statement is compiled with SHOWPLAN_XML on and the tables are taken from
+        its estimated plan, so writes (UPDATE/DELETE/INSERT/MERGE targets) are
+        reported as well as reads. Useful for cache invalidation.
+
+        Args:
+            sql: SQL with @P1, @P2, etc. placeholders
+            params: List of parameter values in order
+
+        Returns:
+            ``{"read": [...], "written": [...]}`` of ``database.schema.table`` names
+        """
+        ...
+
+    def exec_sql(
+        self,
+        sql: str,
+        params: Optional[Dict[str, Any]] = None,
+        returns_rows: Optional[bool] = None,
+        *,
+        outputs: Optional[Dict[str, str]] = None,
+    ) -> Coroutine[Any, Any, Union[QueryStream, int]]:
+        """
+        Run dynamic SQL through ``sp_executesql`` with parameters bound by name.
+
+        Parameter types are declared from the Python values, so repeated calls with
+        the same statement text reuse one cached plan.
+
+        Args:
+            sql: SQL referencing parameters by name, e.g. ``@customer_id``
+            params: Mapping of parameter name (with or without ``@``) to value
+            returns_rows: None returns a QueryStream; True also raises ValueError if
+                the statement produced no result set; False returns the affected
+                row count instead
+            outputs: Mapping of OUTPUT parameter name to SQL type, e.g.
+                ``{"total": "int"}``; a name also in ``params`` is input/output
+
+        Returns:
+            QueryStream over the first result set with ``rows_affected`` and
+            ``output_params`` set, or the affected row count for ``returns_rows=False``
+        """
+        ...
+
     def execute_batch(
         self,
         commands: List[Tuple[str, Optional[List[Any]]]],
@@ -700,12 +1793,50 @@ class Connection:
         """
         ...
 
+    def apply_migrations(
+        self,
+        directory: Union[str, os.PathLike[str]],
+        *,
+        table: str = "dbo.__fastmssql_migrations",
+        dry_run: bool = False,
+    ) -> Coroutine[Any, Any, List[Dict[str, Any]]]:
+        """
+        Apply the ``*.sql`` scripts in ``directory`` that have not been applied yet.
+
+        Scripts run in file name order, each once; ``table`` records the name and
+        checksum of every applied script and is created on first use. Scripts are
+        split on ``GO`` lines (``GO 5`` repeats a batch) and each runs in its own
+        transaction together with its version row, unless it contains
+        ``-- fastmssql:no-transaction`` or a statement that cannot run in a
+        transaction (``CREATE/ALTER/DROP DATABASE``, ``BACKUP``, ``RESTORE``, full-text
+        DDL, ``RECONFIGURE``). A session application lock keeps concurrent callers
+        from applying the same scripts twice.
+
+        Raises ``InterfaceError`` if an applied script has changed on disk. A failing
+        script raises the server error with a note naming the script and batch;
+        scripts applied before it stay applied.
+
+        Args:
+            directory: Folder holding the ``.sql`` scripts
+            table: Version table, optionally schema-qualified
+            dry_run: Only report which scripts would be applied
+
+        Returns:
+            One dict per script: ``name``, ``status`` (``"applied"``, ``"skipped"``
+            or ``"pending"`` with ``dry_run``), ``batches``, ``duration_ms`` and
+            ``transactional``
+        """
+        ...
+
     def bulk_insert(
         self,
         table: str,
         columns: List[str],
         data: List[List[Any]],
-    ) -> Coroutine[Any, Any, None]:
+        *,
+        bulk_copy: bool = False,
+        verify_key: Optional[str] = None,
+    ) -> Coroutine[Any, Any, Union[int, Dict[str, Any]]]:
         """
         High-performance bulk insert for large datasets.
 
@@ -713,6 +1844,59 @@ class Connection:
             table: Target table name (can be schema-qualified)
             columns: List of column names
             data: List of rows, each row is a list of values
+            bulk_copy: Stream the rows with the TDS bulk load protocol instead of
+                chunked INSERT statements. Much faster for large loads; insertable
+                columns not listed in ``columns`` get NULL rather than their default
+            verify_key: One of ``columns`` whose values identify the loaded rows. After
+                the load, the rows of ``table`` between the smallest and largest key
+                loaded are counted and checksummed on the same connection
+
+        Returns:
+            Number of rows inserted. With ``verify_key``, a dict with ``rows_inserted``,
+            ``row_count`` (rows now in the key range), ``checksum``
+            (``CHECKSUM_AGG(CHECKSUM(columns))`` over that range, None when it is
+            empty), ``key_min``, ``key_max`` and ``verified`` (``row_count`` equals
+            ``rows_inserted``)
+        """
+        ...
+
+    def bulk_insert_arrow(
+        self,
+        table: str,
+        arrow_table: Any,
+    ) -> Coroutine[Any, Any, int]:
+        """
+        Bulk load a ``pyarrow.Table`` (or ``RecordBatch``) with the TDS bulk load protocol.
+
+        Arrow columns are matched to the table's columns by name and each value is
+        encoded as its column's SQL type, as with ``bulk_insert(..., bulk_copy=True)``;
+        insertable columns missing from the Arrow table get NULL. The Arrow buffers are
+        read directly, without creating a Python object per value. Timezone-aware
+        timestamps are stored as their UTC time. Requires pyarrow.
+
+        Returns:
+            Number of rows inserted
+        """
+        ...
+
+    def bulk_insert_dataframe(
+        self,
+        table: str,
+        frame: Any,
+        *,
+        chunk_size: int = 100_000,
+    ) -> Coroutine[Any, Any, int]:
+        """
+        Bulk load a ``pandas.DataFrame`` with the TDS bulk load protocol.
+
+        The frame is converted with ``pyarrow.Table.from_pandas()`` and loaded as by
+        ``bulk_insert_arrow()``: dtypes map to the matching Arrow types, NaN, NaT and
+        ``pd.NA`` become NULL, and the index is not loaded. Rows are sent
+        ``chunk_size`` at a time on one connection; chunks already loaded stay if a
+        later one fails. Requires pandas and pyarrow (``pip install fastmssql[pandas]``).
+
+        Returns:
+            Number of rows inserted
         """
         ...
 
@@ -731,7 +1915,28 @@ class Connection:
         """
         ...
 
-    def pool_stats(self) -> Coroutine[Any, Any, Dict[str, int | bool | None]]:
+    def server_info(self) -> Coroutine[Any, Any, Dict[str, Any]]:
+        """
+        Security details of a pooled connection, for asserting encryption posture.
+
+        Returns a dictionary with the following keys:
+        - tds_version (str | None): Negotiated TDS protocol version, e.g. "7.4"
+        - encrypted (bool | None): Whether the session traffic is encrypted
+        - auth_scheme (str | None): "SQL", "NTLM", "KERBEROS" ...
+        - net_transport (str | None): "TCP", "Shared memory" ...
+        - server_version (str | None): Server product version, e.g. "16.0.4135"
+        - tls_version (str | None): e.g. "TLSv1.2"; None when TLS was not used
+        - cipher_suite (str | None): IANA name, e.g. "TLS_ECDHE_RSA_WITH_AES_256_GCM_SHA384"
+        - certificate_subject (str | None): e.g. "CN=sql01.example.com"
+        - certificate_issuer (str | None)
+        - certificate_not_before (datetime | None): UTC
+        - certificate_not_after (datetime | None): UTC
+
+        The certificate fields are None under TLS 1.3, which encrypts the certificate.
+        """
+        ...
+
+    def pool_stats(self, by_key: bool = False) -> Coroutine[Any, Any, Dict[str, Any]]:
         """
         Get connection pool statistics.
 
@@ -742,9 +1947,89 @@ class Connection:
         - active_connections (int): Number of connections currently in use
         - max_size (int): Maximum pool size
         - min_idle (int | None): Minimum idle connections to maintain
+        - key (dict): The server, database and user this pool connects as
+        - by_key (list[dict]): Only with ``by_key=True``; one entry per distinct
+          key across every connected pool in the process, with server, database,
+          user, pools, connections, idle_connections, active_connections and
+          max_size summed over the pools under that key
+        - labels (dict): Per label given to ``execute(..., label=...)``: executions,
+          errors, rows_affected, total_ms, avg_ms and max_ms
+        """
+        ...
+
+    def health_report(self, timeout: float = 5.0) -> Coroutine[Any, Any, Dict[str, Any]]:
+        """
+        Summarise this connection's health for a ``/healthz`` handler.
+
+        Pings the server with ``SELECT 1`` through the pool (giving up after
+        ``timeout`` seconds) and returns a JSON-serialisable dict:
+        - status (str): "down" when the ping failed, "degraded" when the circuit is
+          open or every pooled connection is busy, otherwise "ok"
+        - checked_at (str): ISO 8601 UTC time of the check
+        - ping_ms (float | None): Ping round trip, including the checkout
+        - ping_error (str | None): Why the ping failed
+        - connections, idle_connections, active_connections, max_size (int)
+        - saturation (float): active_connections / max_size
+        - circuit (str): "open" while the pool's latest attempt to open a connection
+          has failed, otherwise "closed"
+        - last_error (str | None): The latest connection failure, even if resolved
+        - last_error_at (str | None): ISO 8601 UTC time of that failure
+        """
+        ...
+
+    def apply(self, batch: Batch) -> Coroutine[Any, Any, int]:
+        """Send every statement queued in ``batch`` as one transaction in a single round trip; returns total affected rows."""
+        ...
+
+    def with_options(
+        self,
+        *,
+        rstrip_char: Optional[bool] = None,
+        time_as_timedelta: Optional[bool] = None,
+        parse_json: Optional[Union[bool, List[str]]] = None,
+        parse_xml: Optional[Union[bool, str]] = None,
+        trace_context_info: Optional[bool] = None,
+        log_queries: Optional[bool] = None,
+        log_parameters: Optional[bool] = None,
+        redact_parameters: Optional[List[Union[int, str]]] = None,
+        on_message: Optional[Callable[[str], None]] = None,
+        retry_policy: Optional[RetryPolicy] = None,
+        paramstyle: Optional[str] = None,
+        statement_policy: Optional[Union[StatementPolicy, Callable[[str], Any]]] = None,
+        max_result_columns: Optional[int] = None,
+        max_row_bytes: Optional[int] = None,
+    ) -> Connection:
+        """
+        Create another Connection backed by the same pool, with different defaults.
+
+        Options left as None are inherited. Closing either object closes the shared pool.
+        """
+        ...
+
+    def reload_config(
+        self,
+        *,
+        new_pool_config: Optional[PoolConfig] = None,
+        new_ssl_config: Optional[SslConfig] = None,
+        new_azure_credential: Optional[AzureCredential] = None,
+    ) -> Coroutine[Any, Any, None]:
+        """
+        Apply new pool sizing, TLS settings or credential without restarting.
+
+        A pool is built from the new settings and swapped in; the old pool closes once
+        the requests running on it have finished. Options left as None are kept.
+        Copies made with ``with_options()`` share the settings and see the change.
         """
         ...
 
+    def acquire(self) -> PinnedConnection:
+        """Hold one pooled connection for several statements (``async with conn.acquire() as pinned``)."""
+        ...
+
+    def transaction(self) -> Transaction:
+        """Create a dedicated (non-pooled) Transaction sharing this connection's config and credentials."""
+        ...
+
     async def __aenter__(self) -> _RustConnection:
         """Async context manager entry (initializes pool)."""
         ...
@@ -783,6 +2068,8 @@ class Transaction:
         port: Optional[int] = None,
         instance_name: Optional[str] = None,
         application_name: Optional[str] = None,
+        rstrip_char: bool = False,
+        time_as_timedelta: bool = False,
     ) -> None:
         """Initialize a dedicated non-pooled connection for transactions."""
         ...
@@ -839,6 +2126,23 @@ class Transaction:
         """
         ...
 
+    def bulk_insert(
+        self,
+        table: str,
+        columns: List[str],
+        data: List[List[Any]],
+        *,
+        bulk_copy: bool = False,
+        verify_key: Optional[str] = None,
+    ) -> Coroutine[Any, Any, Union[int, Dict[str, Any]]]:
+        """
+        Insert rows on this connection, as ``Connection.bulk_insert()`` does.
+
+        The table may be a ``#temp`` table created earlier on this connection, so
+        rows can be staged and merged into their target inside the transaction.
+        """
+        ...
+
     def query_batch(
         self,
         queries: List[tuple[str, Optional[List[Any]]]],
@@ -868,6 +2172,14 @@ class Transaction:
         """Rollback the current transaction."""
         ...
 
+    async def savepoint(self, name: str) -> None:
+        """Mark a savepoint (SAVE TRANSACTION) in the current transaction."""
+        ...
+
+    async def rollback_to(self, name: str) -> None:
+        """Undo the work done since savepoint ``name``; the transaction stays open."""
+        ...
+
     async def close(self) -> None:
         """Close the connection."""
         ...
@@ -876,6 +2188,52 @@ class Transaction:
         """Return True if the underlying connection is currently established."""
         ...
 
+    def filestream_context(
+        self,
+        table: str,
+        column: str,
+        key_column: str,
+        key: Any,
+    ) -> Coroutine[Any, Any, Optional[Dict[str, Any]]]:
+        """Return ``{"path": str, "transaction_context": bytes}`` for one FILESTREAM value.
+
+        The transaction context is NULL outside of a transaction. Returns None if
+        no row matches.
+        """
+        ...
+
+    def filestream_read(
+        self,
+        table: str,
+        column: str,
+        key_column: str,
+        key: Any,
+        offset: int = 0,
+        length: Optional[int] = None,
+    ) -> Coroutine[Any, Any, Optional[bytes]]:
+        """Read FILESTREAM data for the row where ``key_column = key``.
+
+        Reads ``length`` bytes (default: to the end) from the 0-based ``offset``.
+        Returns None if no row matches or the value is NULL.
+        """
+        ...
+
+    def filestream_write(
+        self,
+        table: str,
+        column: str,
+        key_column: str,
+        key: Any,
+        data: bytes,
+        offset: Optional[int] = None,
+    ) -> Coroutine[Any, Any, int]:
+        """Write FILESTREAM data for the row where ``key_column = key``.
+
+        Replaces the whole value, or overwrites ``len(data)`` bytes at the 0-based
+        ``offset``. Returns the number of rows updated.
+        """
+        ...
+
     async def __aenter__(self) -> _RustTransaction:
         """Async context manager entry (begins transaction)."""
         ...
@@ -915,3 +2273,122 @@ class TypedNull(StrEnum):
     DATETIMEOFFSET = "DATETIMEOFFSET"
 
 def version() -> str: ...
+def concat(results: List[QueryStream]) -> QueryStream:
+    """
+    Concatenate result sets with matching column names and types into a new QueryStream.
+
+    Rows are moved out of the inputs, which are left empty. All schemas are checked
+    before any rows are moved; raises ValueError if they differ.
+    """
+    ...
+
+def diagnose(target: Union[str, Connection], timeout: float = 10.0) -> Coroutine[Any, Any, Dict[str, Any]]:
+    """
+    Check connectivity step by step and report where it fails.
+
+    ``target`` is a connection string or a Connection (whose credentials, including
+    Azure ones, are used). The steps run in order and stop at the first failure:
+    ``dns``, ``tcp``, ``token`` (Azure credentials only), ``tls``, ``login`` and
+    ``query`` (``SELECT 1``). Each is limited to ``timeout`` seconds. Connection
+    problems are reported rather than raised. A server routed through
+    ``set_proxies()`` is connected to through its proxy, which resolves the name,
+    so there is no ``dns`` step.
+
+    Returns a dict with ``ok``, ``failed_step`` (None on success), ``address``
+    (host:port), ``proxy`` (e.g. ``"SOCKS5 proxy bastion:1080"``, or None for a
+    direct connection) and ``steps``: a list of dicts with ``step``, ``ok``, ``elapsed_ms``,
+    ``detail`` and ``code`` (the SQL Server error number for login failures).
+
+    Example:
+        report = await diagnose(conn_str)
+        if not report["ok"]:
+            print(report["failed_step"], report["steps"][-1]["detail"])
+    """
+    ...
+
+def set_dns_cache_ttl(seconds: float) -> None:
+    """
+    Set how long server name resolutions are reused for new connections (default 30).
+
+    0 disables the cache. A cached name is resolved again as soon as none of its
+    addresses accepts a connection, so failovers are picked up without waiting for
+    the TTL. Raises ValueError for negative values.
+    """
+    ...
+
+def flush_dns_cache(host: Optional[str] = None) -> int:
+    """Drop cached resolutions for ``host`` (or all servers); returns how many were removed."""
+    ...
+
+def set_host_aliases(aliases: Optional[Dict[str, str]] = None) -> None:
+    """
+    Replace the host alias table used when opening connections.
+
+    Maps server names (case-insensitive) to the address to dial: an IP address or host
+    name, optionally with a port, e.g. ``{"db.internal": "127.0.0.1:14330"}`` for an
+    SSH tunnel. TLS still validates the certificate against the original name. None
+    removes all aliases.
+    """
+    ...
+
+def set_resolver(resolver: Optional[Callable[[str, int], Union[str, List[str], None]]] = None) -> None:
+    """
+    Set a callable ``resolver(host, port)`` consulted for server names without an alias.
+
+    It returns the address to dial (``"10.0.0.5"``, ``"10.0.0.5:1433"`` or another host
+    name), a list of them, or None to use normal DNS. It is called on a background
+    thread for each uncached lookup, so keep it quick. None removes the resolver.
+    """
+    ...
+
+def set_proxies(routes: Optional[Dict[str, str]] = None) -> None:
+    """
+    Route connections to matching servers through a SOCKS5 or HTTP CONNECT proxy.
+
+    Keys are server names, ``*.suffix`` patterns or ``*`` for every server; values are
+    ``socks5://[user:pass@]host[:1080]`` or ``http://[user:pass@]host[:8080]``. Server
+    names are resolved by the proxy. None or an empty dict connects directly. Raises
+    ValueError for an unsupported or malformed URL.
+    """
+    ...
+
+def enable_wire_debug(path: Optional[Union[str, os.PathLike[str]]], *, include_values: bool = False) -> None:
+    """
+    Log the TDS traffic of connections opened from now on to ``path`` (appended to).
+
+    One line per packet sent and received (type, length, status, packet ID), plus a
+    summary of the tokens of each server response (DONE counts, ENVCHANGE types, INFO
+    and ERROR numbers, LOGINACK), followed up to its first COLMETADATA. Values are left
+    out unless ``include_values=True``, which adds message texts and the first bytes of
+    each packet; LOGIN7 packets are never dumped. With ``Encrypt=true`` the traffic
+    after login is TLS and is logged by record size only. ``path=None`` turns tracing
+    off again.
+    """
+    ...
+
+def set_certificate_expiry_warning(
+    days: Optional[int] = 30,
+    callback: Optional[Callable[[str, datetime, int], None]] = None,
+) -> None:
+    """
+    Report server certificates that expire within ``days`` days.
+
+    New connections check the certificate presented during the TLS handshake. One
+    close to expiry (or expired) raises a ``CertificateExpiryWarning`` through the
+    ``warnings`` module, or calls ``callback(subject, not_after, days_left)`` instead.
+    Each certificate is reported once per process. Certificates are only visible under
+    TLS 1.2. The check is off until this is called; ``days=None`` turns it off again.
+    """
+    ...
+
+def statement_fingerprint(sql: str) -> str:
+    """
+    Stable hash of a normalized SQL statement, as 16 hex digits, for grouping query
+    telemetry.
+
+    Comments are dropped, whitespace and keyword case are normalized, and literals and
+    ``@Pn`` placeholders are replaced by ``?``, with IN / VALUES lists of any length
+    collapsed to one, so ``SELECT * FROM t WHERE id IN (1, 2)`` and
+    ``select * from T where id in (@P1)`` share a fingerprint.
+    """
+    ...
@@ -0,0 +1,622 @@
+//! Columnar results for `Connection.query_arrow()`, `query_polars()` and
+//! `query_numpy()`.
+//!
+//! Rows are read off the TDS stream and their values appended straight into Arrow
+//! buffers (a validity bitmap plus little-endian values, or offsets and bytes for
+//! strings and binary), one builder per column, without creating a Python object per
+//! value.  Only once the first result set is complete are the buffers handed to
+//! `pyarrow.Array.from_buffers()` and combined into a `pyarrow.Table`, so pyarrow is
+//! needed at runtime but not to build fastmssql.
+//!
+//! `query_numpy()` turns the same buffers into numpy arrays instead, without pyarrow.
+//!
+//! The Arrow type of a column follows its values, so nullable INT/FLOAT columns keep
+//! their exact width.  A column with no non-NULL values falls back to a type derived
+//! from its wire type, or `null` when that is ambiguous (e.g. DECIMAL, whose scale
+//! only the values carry).
+
+use futures_util::TryStreamExt;
+use pyo3::exceptions::PyImportError;
+use pyo3::prelude::*;
+use pyo3::types::{PyByteArray, PyBytes, PyDict, PyList, PySlice, PyString};
+use tiberius::numeric::Numeric;
+use tiberius::{Column, ColumnData, ColumnType, FromSql, QueryItem, QueryStream, Row};
+
+use crate::type_mapping::{DecodeOptions, decimal_class, numeric_to_decimal_string};
+use crate::types::{DataError, create_sql_error};
+
+/// Arrow type of a column, decided by its first non-NULL value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Bool,
+    UInt8,
+    Int16,
+    Int32,
+    Int64,
+    Float32,
+    Float64,
+    Utf8,
+    Binary,
+    Decimal { precision: u8, scale: u8 },
+    Date32,
+    Timestamp { utc: bool },
+    Time64,
+    Duration,
+}
+
+impl Kind {
+    /// Bytes per value of fixed-width types; None for bit-packed and variable-width ones.
+    fn width(self) -> Option<usize> {
+        match self {
+            Kind::Bool | Kind::Utf8 | Kind::Binary => None,
+            Kind::UInt8 => Some(1),
+            Kind::Int16 => Some(2),
+            Kind::Int32 | Kind::Float32 | Kind::Date32 => Some(4),
+            Kind::Int64
+            | Kind::Float64
+            | Kind::Timestamp { .. }
+            | Kind::Time64
+            | Kind::Duration => Some(8),
+            Kind::Decimal { .. } => Some(16),
+        }
+    }
+
+    /// The `pyarrow` data type.
+    fn to_py<'py>(self, pa: &Bound<'py, PyModule>) -> PyResult<Bound<'py, PyAny>> {
+        match self {
+            Kind::Bool => pa.call_method0("bool_"),
+            Kind::UInt8 => pa.call_method0("uint8"),
+            Kind::Int16 => pa.call_method0("int16"),
+            Kind::Int32 => pa.call_method0("int32"),
+            Kind::Int64 => pa.call_method0("int64"),
+            Kind::Float32 => pa.call_method0("float32"),
+            Kind::Float64 => pa.call_method0("float64"),
+            Kind::Utf8 => pa.call_method0("large_string"),
+            Kind::Binary => pa.call_method0("large_binary"),
+            Kind::Decimal { precision, scale } => pa.call_method1("decimal128", (precision, scale)),
+            Kind::Date32 => pa.call_method0("date32"),
+            Kind::Timestamp { utc: false } => pa.call_method1("timestamp", ("us",)),
+            Kind::Timestamp { utc: true } => pa.call_method1("timestamp", ("us", "UTC")),
+            Kind::Time64 => pa.call_method1("time64", ("us",)),
+            Kind::Duration => pa.call_method1("duration", ("us",)),
+        }
+    }
+}
+
+/// The kind of a non-NULL value, or None for NULL.
+fn value_kind(
+    data: &ColumnData<'static>,
+    col_type: ColumnType,
+    options: DecodeOptions,
+) -> Option<Kind> {
+    Some(match data {
+        ColumnData::U8(v) => v.map(|_| Kind::UInt8)?,
+        ColumnData::I16(v) => v.map(|_| Kind::Int16)?,
+        ColumnData::I32(v) => v.map(|_| Kind::Int32)?,
+        ColumnData::I64(v) => v.map(|_| Kind::Int64)?,
+        ColumnData::F32(v) => v.map(|_| Kind::Float32)?,
+        ColumnData::F64(v) => {
+            v.as_ref()?;
+            if matches!(col_type, ColumnType::Money | ColumnType::Money4) {
+                MONEY
+            } else {
+                Kind::Float64
+            }
+        }
+        ColumnData::Bit(v) => v.map(|_| Kind::Bool)?,
+        ColumnData::String(v) => v.as_ref().map(|_| Kind::Utf8)?,
+        ColumnData::Guid(v) => v.map(|_| Kind::Utf8)?,
+        ColumnData::Xml(v) => v.as_ref().map(|_| Kind::Utf8)?,
+        ColumnData::Binary(v) => v.as_ref().map(|_| Kind::Binary)?,
+        ColumnData::Numeric(v) => v.map(|n| Kind::Decimal {
+            precision: 38,
+            scale: n.scale(),
+        })?,
+        ColumnData::DateTime(v) => v.map(|_| Kind::Timestamp { utc: false })?,
+        ColumnData::SmallDateTime(v) => v.map(|_| Kind::Timestamp { utc: false })?,
+        ColumnData::DateTime2(v) => v.map(|_| Kind::Timestamp { utc: false })?,
+        ColumnData::DateTimeOffset(v) => v.map(|_| Kind::Timestamp { utc: true })?,
+        ColumnData::Date(v) => v.map(|_| Kind::Date32)?,
+        ColumnData::Time(v) => v.map(|_| time_kind(options))?,
+    })
+}
+
+/// MONEY and SMALLMONEY have four decimal places and fit DECIMAL(19, 4).
+const MONEY: Kind = Kind::Decimal {
+    precision: 19,
+    scale: 4,
+};
+
+fn time_kind(options: DecodeOptions) -> Kind {
+    if options.time_as_timedelta {
+        Kind::Duration
+    } else {
+        Kind::Time64
+    }
+}
+
+/// Kind of a column that had no non-NULL values, when its wire type settles it.
+fn wire_kind(col_type: ColumnType, options: DecodeOptions) -> Option<Kind> {
+    Some(match col_type {
+        ColumnType::Bit | ColumnType::Bitn => Kind::Bool,
+        ColumnType::Int1 => Kind::UInt8,
+        ColumnType::Int2 => Kind::Int16,
+        ColumnType::Int4 => Kind::Int32,
+        ColumnType::Int8 => Kind::Int64,
+        ColumnType::Float4 => Kind::Float32,
+        ColumnType::Float8 => Kind::Float64,
+        ColumnType::Money | ColumnType::Money4 => MONEY,
+        ColumnType::Guid
+        | ColumnType::BigVarChar
+        | ColumnType::BigChar
+        | ColumnType::NVarchar
+        | ColumnType::NChar
+        | ColumnType::Xml
+        | ColumnType::Text
+        | ColumnType::NText => Kind::Utf8,
+        ColumnType::BigVarBin | ColumnType::BigBinary | ColumnType::Image => Kind::Binary,
+        ColumnType::Datetime
+        | ColumnType::Datetime4
+        | ColumnType::Datetimen
+        | ColumnType::Datetime2 => Kind::Timestamp { utc: false },
+        ColumnType::DatetimeOffsetn => Kind::Timestamp { utc: true },
+        ColumnType::Daten => Kind::Date32,
+        ColumnType::Timen => time_kind(options),
+        _ => return None,
+    })
+}
+
+struct ColumnBuilder {
+    name: String,
+    col_type: ColumnType,
+    rstrip: bool,
+    kind: Option<Kind>,
+    len: usize,
+    null_count: usize,
+    validity: Vec<u8>,
+    values: Vec<u8>,
+    offsets: Vec<i64>,
+}
+
+fn set_bit(bits: &mut Vec<u8>, index: usize, value: bool) {
+    if bits.len() <= index / 8 {
+        bits.resize(index / 8 + 1, 0);
+    }
+    if value {
+        bits[index / 8] |= 1 << (index % 8);
+    }
+}
+
+impl ColumnBuilder {
+    fn new(column: &Column, options: DecodeOptions) -> Self {
+        let col_type = column.column_type();
+        ColumnBuilder {
+            name: column.name().to_string(),
+            col_type,
+            rstrip: options.rstrip_char
+                && matches!(col_type, ColumnType::BigChar | ColumnType::NChar),
+            kind: None,
+            len: 0,
+            null_count: 0,
+            validity: Vec::new(),
+            values: Vec::new(),
+            offsets: vec![0],
+        }
+    }
+
+    /// Fix the column's kind, filling in the NULLs seen before it was known.
+    fn start(&mut self, kind: Kind) {
+        self.kind = Some(kind);
+        for index in 0..self.len {
+            self.push_empty(kind, index);
+        }
+    }
+
+    /// Placeholder value for a NULL slot.
+    fn push_empty(&mut self, kind: Kind, index: usize) {
+        match kind.width() {
+            Some(width) => self.values.resize(self.values.len() + width, 0),
+            None if kind == Kind::Bool => set_bit(&mut self.values, index, false),
+            None => self.offsets.push(self.values.len() as i64),
+        }
+    }
+
+    fn push_bytes(&mut self, bytes: &[u8]) {
+        self.values.extend_from_slice(bytes);
+        self.offsets.push(self.values.len() as i64);
+    }
+
+    fn push(&mut self, data: ColumnData<'static>, options: DecodeOptions) -> PyResult<()> {
+        let index = self.len;
+        let Some(kind) = value_kind(&data, self.col_type, options) else {
+            if let Some(kind) = self.kind {
+                self.push_empty(kind, index);
+            }
+            set_bit(&mut self.validity, index, false);
+            self.null_count += 1;
+            self.len += 1;
+            return Ok(());
+        };
+        match self.kind {
+            None => self.start(kind),
+            Some(current) if current != kind => {
+                return Err(DataError::new_err(format!(
+                    "Column '{}' mixes {:?} and {:?} values",
+                    self.name, current, kind
+                )));
+            }
+            Some(_) => {}
+        }
+
+        match &data {
+            ColumnData::U8(Some(v)) => self.values.push(*v),
+            ColumnData::I16(Some(v)) => self.values.extend_from_slice(&v.to_le_bytes()),
+            ColumnData::I32(Some(v)) => self.values.extend_from_slice(&v.to_le_bytes()),
+            ColumnData::I64(Some(v)) => self.values.extend_from_slice(&v.to_le_bytes()),
+            ColumnData::F32(Some(v)) => self.values.extend_from_slice(&v.to_le_bytes()),
+            ColumnData::F64(Some(v)) if kind == MONEY => {
+                let scaled = (v * 10_000.0).round() as i128;
+                self.values.extend_from_slice(&scaled.to_le_bytes());
+            }
+            ColumnData::F64(Some(v)) => self.values.extend_from_slice(&v.to_le_bytes()),
+            ColumnData::Bit(Some(v)) => set_bit(&mut self.values, index, *v),
+            ColumnData::String(Some(s)) => {
+                let s = if self.rstrip {
+                    s.trim_end_matches(' ')
+                } else {
+                    s
+                };
+                self.push_bytes(s.as_bytes());
+            }
+            ColumnData::Guid(Some(g)) => {
+                let mut buf = uuid::Uuid::encode_buffer();
+                let text = g.hyphenated().encode_lower(&mut buf);
+                self.push_bytes(text.as_bytes());
+            }
+            ColumnData::Xml(Some(xml)) => self.push_bytes(xml.to_string().as_bytes()),
+            ColumnData::Binary(Some(b)) => self.push_bytes(b),
+            ColumnData::Numeric(Some(n)) => self.values.extend_from_slice(&n.value().to_le_bytes()),
+            ColumnData::DateTimeOffset(Some(_)) => {
+                let value = chrono::DateTime::<chrono::Utc>::from_sql(&data)
+                    .map_err(|e| self.conversion_error(e))?
+                    .unwrap_or_default();
+                self.values
+                    .extend_from_slice(&value.timestamp_micros().to_le_bytes());
+            }
+            ColumnData::DateTime(_) | ColumnData::SmallDateTime(_) | ColumnData::DateTime2(_) => {
+                let value = chrono::NaiveDateTime::from_sql(&data)
+                    .map_err(|e| self.conversion_error(e))?
+                    .unwrap_or_default();
+                self.values
+                    .extend_from_slice(&value.and_utc().timestamp_micros().to_le_bytes());
+            }
+            ColumnData::Date(_) => {
+                let value = chrono::NaiveDate::from_sql(&data)
+                    .map_err(|e| self.conversion_error(e))?
+                    .unwrap_or_default();
+                let days = value
+                    .signed_duration_since(chrono::NaiveDate::default())
+                    .num_days();
+                self.values.extend_from_slice(&(days as i32).to_le_bytes());
+            }
+            ColumnData::Time(_) => {
+                let value = chrono::NaiveTime::from_sql(&data)
+                    .map_err(|e| self.conversion_error(e))?
+                    .unwrap_or_default();
+                let micros = (value - chrono::NaiveTime::MIN)
+                    .num_microseconds()
+                    .unwrap_or(0);
+                self.values.extend_from_slice(&micros.to_le_bytes());
+            }
+            _ => unreachable!("NULL values return early"),
+        }
+        set_bit(&mut self.validity, index, true);
+        self.len += 1;
+        Ok(())
+    }
+
+    fn conversion_error(&self, e: tiberius::error::Error) -> PyErr {
+        DataError::new_err(format!("Failed to convert column '{}': {}", self.name, e))
+    }
+
+    /// The column as a `pyarrow.Array`.
+    fn into_array<'py>(
+        mut self,
+        pa: &Bound<'py, PyModule>,
+        options: DecodeOptions,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let py = pa.py();
+        let kind = match self.kind.or_else(|| wire_kind(self.col_type, options)) {
+            Some(kind) => kind,
+            None => return pa.call_method1("nulls", (self.len,)),
+        };
+        if self.kind.is_none() {
+            self.start(kind);
+        }
+
+        let buffer = |bytes: &[u8]| pa.call_method1("py_buffer", (PyBytes::new(py, bytes),));
+        let validity = if self.null_count > 0 {
+            buffer(&self.validity)?
+        } else {
+            py.None().into_bound(py)
+        };
+        let buffers = PyList::new(py, [validity])?;
+        if matches!(kind, Kind::Utf8 | Kind::Binary) {
+            let offsets: Vec<u8> = self.offsets.iter().flat_map(|o| o.to_le_bytes()).collect();
+            buffers.append(buffer(&offsets)?)?;
+        }
+        buffers.append(buffer(&self.values)?)?;
+
+        pa.getattr("Array")?.call_method1(
+            "from_buffers",
+            (kind.to_py(pa)?, self.len, buffers, self.null_count),
+        )
+    }
+
+    fn is_valid(&self, index: usize) -> bool {
+        self.null_count == 0 || self.validity[index / 8] & (1 << (index % 8)) != 0
+    }
+
+    /// The column as a 1-D `numpy.ndarray`.  As in pandas, numeric columns with NULLs
+    /// become float64 with NaN and temporal ones hold NaT; TIME becomes
+    /// `timedelta64[us]` since midnight.  Booleans with NULLs, decimals, strings and
+    /// binary become object arrays.
+    fn into_ndarray<'py>(
+        mut self,
+        np: &Bound<'py, PyModule>,
+        options: DecodeOptions,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let py = np.py();
+        let Some(kind) = self.kind.or_else(|| wire_kind(self.col_type, options)) else {
+            return object_array(np, (0..self.len).map(|_| py.None().into_bound(py)));
+        };
+        if self.kind.is_none() {
+            self.start(kind);
+        }
+        let frombuffer = |bytes: &[u8], dtype: &str| {
+            np.call_method1("frombuffer", (PyByteArray::new(py, bytes), dtype))
+        };
+        let width = kind.width().unwrap_or(0);
+        let slots = || self.values.chunks_exact(width.max(1)).enumerate();
+
+        match kind {
+            Kind::UInt8 | Kind::Int16 | Kind::Int32 | Kind::Int64 | Kind::Float32 | Kind::Float64
+                if self.null_count == 0 =>
+            {
+                let dtype = match kind {
+                    Kind::UInt8 => "u1",
+                    Kind::Int16 => "<i2",
+                    Kind::Int32 => "<i4",
+                    Kind::Int64 => "<i8",
+                    Kind::Float32 => "<f4",
+                    _ => "<f8",
+                };
+                frombuffer(&self.values, dtype)
+            }
+            Kind::UInt8 | Kind::Int16 | Kind::Int32 | Kind::Int64 | Kind::Float32 | Kind::Float64 => {
+                let mut out = Vec::with_capacity(self.len * 8);
+                for (index, slot) in slots() {
+                    let value = if !self.is_valid(index) {
+                        f64::NAN
+                    } else {
+                        match kind {
+                            Kind::UInt8 => f64::from(slot[0]),
+                            Kind::Int16 => f64::from(i16::from_le_bytes([slot[0], slot[1]])),
+                            Kind::Int32 => f64::from(i32::from_le_bytes(slot.try_into()?)),
+                            Kind::Int64 => i64::from_le_bytes(slot.try_into()?) as f64,
+                            Kind::Float32 => f64::from(f32::from_le_bytes(slot.try_into()?)),
+                            _ => f64::from_le_bytes(slot.try_into()?),
+                        }
+                    };
+                    out.extend_from_slice(&value.to_le_bytes());
+                }
+                frombuffer(&out, "<f8")
+            }
+            Kind::Timestamp { .. } | Kind::Time64 | Kind::Duration | Kind::Date32 => {
+                let mut out = Vec::with_capacity(self.len * 8);
+                for (index, slot) in slots() {
+                    let value = if !self.is_valid(index) {
+                        i64::MIN // NaT
+                    } else if kind == Kind::Date32 {
+                        i64::from(i32::from_le_bytes(slot.try_into()?))
+                    } else {
+                        i64::from_le_bytes(slot.try_into()?)
+                    };
+                    out.extend_from_slice(&value.to_le_bytes());
+                }
+                let dtype = match kind {
+                    Kind::Timestamp { .. } => "<M8[us]",
+                    Kind::Date32 => "<M8[D]",
+                    _ => "<m8[us]",
+                };
+                frombuffer(&out, dtype)
+            }
+            Kind::Bool if self.null_count == 0 => {
+                let out: Vec<u8> = (0..self.len)
+                    .map(|i| (self.values[i / 8] >> (i % 8)) & 1)
+                    .collect();
+                frombuffer(&out, "?")
+            }
+            Kind::Bool => object_array(
+                np,
+                (0..self.len).map(|i| {
+                    if self.is_valid(i) {
+                        pyo3::types::PyBool::new(py, (self.values[i / 8] >> (i % 8)) & 1 == 1)
+                            .to_owned()
+                            .into_any()
+                    } else {
+                        py.None().into_bound(py)
+                    }
+                }),
+            ),
+            Kind::Decimal { scale, .. } => {
+                let decimal = decimal_class(py)?;
+                let mut values = Vec::with_capacity(self.len);
+                for (index, slot) in slots() {
+                    values.push(if self.is_valid(index) {
+                        let numeric =
+                            Numeric::new_with_scale(i128::from_le_bytes(slot.try_into()?), scale);
+                        decimal.call1((numeric_to_decimal_string(numeric),))?
+                    } else {
+                        py.None().into_bound(py)
+                    });
+                }
+                object_array(np, values.into_iter())
+            }
+            Kind::Utf8 | Kind::Binary => object_array(
+                np,
+                (0..self.len).map(|i| {
+                    if !self.is_valid(i) {
+                        return py.None().into_bound(py);
+                    }
+                    let bytes = &self.values[self.offsets[i] as usize..self.offsets[i + 1] as usize];
+                    if kind == Kind::Utf8 {
+                        PyString::new(py, &String::from_utf8_lossy(bytes)).into_any()
+                    } else {
+                        PyBytes::new(py, bytes).into_any()
+                    }
+                }),
+            ),
+        }
+    }
+}
+
+/// A 1-D object array holding `values`.
+fn object_array<'py>(
+    np: &Bound<'py, PyModule>,
+    values: impl ExactSizeIterator<Item = Bound<'py, PyAny>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let py = np.py();
+    let kwargs = PyDict::new(py);
+    kwargs.set_item("dtype", "O")?;
+    let array = np.call_method("empty", (values.len(),), Some(&kwargs))?;
+    array.set_item(PySlice::full(py), PyList::new(py, values)?)?;
+    Ok(array)
+}
+
+/// Column builders for the first result set of a query.
+pub struct ArrowColumns {
+    columns: Vec<ColumnBuilder>,
+    options: DecodeOptions,
+}
+
+impl ArrowColumns {
+    /// Read `stream` to the end, appending the rows of its first result set.  Later
+    /// result sets are read and dropped.  A value that cannot be converted fails the
+    /// call only after the stream has been drained, so the connection stays usable;
+    /// a result over `options.limits` fails it at once.
+    pub async fn from_stream(
+        mut stream: QueryStream<'_>,
+        options: DecodeOptions,
+    ) -> PyResult<Self> {
+        let mut result = ArrowColumns {
+            columns: Vec::new(),
+            options,
+        };
+        let mut seen_metadata = false;
+        let mut in_first = true;
+        let mut failure = None;
+        let mut row_count = 0;
+        while let Some(item) = stream
+            .try_next()
+            .await
+            .map_err(|e| create_sql_error(e, "Failed to get results"))?
+        {
+            match item {
+                QueryItem::Metadata(meta) if !seen_metadata => {
+                    options.limits.check_columns(meta.columns())?;
+                    seen_metadata = true;
+                    result.columns = meta
+                        .columns()
+                        .iter()
+                        .map(|column| ColumnBuilder::new(column, options))
+                        .collect();
+                }
+                QueryItem::Metadata(_) => in_first = false,
+                QueryItem::Row(row) if in_first && failure.is_none() => {
+                    options.limits.check_row(&row, row_count)?;
+                    row_count += 1;
+                    for (column, data) in result.columns.iter_mut().zip(row) {
+                        if let Err(e) = column.push(data, options) {
+                            failure = Some(e);
+                            break;
+                        }
+                    }
+                }
+                QueryItem::Row(_) => {}
+            }
+        }
+        match failure {
+            Some(e) => Err(e),
+            None => Ok(result),
+        }
+    }
+
+    /// Append `rows`, all with `columns`, as held by a `QueryStream`.
+    pub fn from_rows<'a>(
+        columns: &[Column],
+        rows: impl Iterator<Item = &'a Row>,
+        options: DecodeOptions,
+    ) -> PyResult<Self> {
+        let mut result = ArrowColumns {
+            columns: columns
+                .iter()
+                .map(|column| ColumnBuilder::new(column, options))
+                .collect(),
+            options,
+        };
+        for row in rows {
+            for (column, (_, data)) in result.columns.iter_mut().zip(row.cells()) {
+                column.push(data.clone(), options)?;
+            }
+        }
+        Ok(result)
+    }
+
+    /// Assemble the `pyarrow.Table`.
+    pub fn into_table(self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        let pa = py.import("pyarrow").map_err(|_| {
+            PyImportError::new_err("query_arrow() requires pyarrow (pip install pyarrow)")
+        })?;
+        let names = PyList::new(py, self.columns.iter().map(|c| c.name.as_str()))?;
+        let arrays = PyList::empty(py);
+        for column in self.columns {
+            arrays.append(column.into_array(&pa, self.options)?)?;
+        }
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("names", names)?;
+        Ok(pa
+            .getattr("Table")?
+            .call_method("from_arrays", (arrays,), Some(&kwargs))?
+            .unbind())
+    }
+
+    /// Assemble a `polars.DataFrame`.  `polars.from_arrow()` imports the table's
+    /// buffers through the Arrow C data interface, so no values are copied and pandas
+    /// is not involved.
+    pub fn into_polars(self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        let pl = py
+            .import("pyarrow")
+            .and_then(|_| py.import("polars"))
+            .map_err(|_| {
+                PyImportError::new_err(
+                    "query_polars() requires polars and pyarrow (pip install fastmssql[polars])",
+                )
+            })?;
+        let table = self.into_table(py)?;
+        Ok(pl.call_method1("from_arrow", (table,))?.unbind())
+    }
+
+    /// Assemble a dict of column name to 1-D `numpy.ndarray`.
+    pub fn into_numpy(self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        let np = py.import("numpy").map_err(|_| {
+            PyImportError::new_err("query_numpy() requires numpy (pip install numpy)")
+        })?;
+        let arrays = PyDict::new(py);
+        for column in self.columns {
+            let name = column.name.clone();
+            arrays.set_item(name, column.into_ndarray(&np, self.options)?)?;
+        }
+        Ok(arrays.into_any().unbind())
+    }
+}
@@ -0,0 +1,305 @@
+//! Rows for `Connection.bulk_insert_arrow()`, read out of a `pyarrow.Table`.
+//!
+//! The buffers of each chunk (validity bitmap, values, offsets) are read directly
+//! rather than through `to_pylist()`, so loading a table does not create a Python
+//! object per value.  Values become [`FastParameter`]s and are then encoded as their
+//! table column's type by the bulk load, exactly like rows passed to `bulk_insert()`.
+//!
+//! Timestamps are stored as their wall-clock time; timezone-aware ones (which Arrow
+//! keeps in UTC) as their UTC time.  Arrow types read here: null, bool, signed and
+//! unsigned integers, float and double, (large) string and binary, fixed-size binary,
+//! date32/64, timestamp, time32/64, decimal128 and dictionary-encoded columns of
+//! these.  Anything else goes through `to_pylist()` and the usual parameter
+//! conversion.
+
+use chrono::{DateTime, NaiveDate, NaiveTime};
+use pyo3::exceptions::{PyImportError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+use tiberius::numeric::Numeric;
+
+use crate::parameter_conversion::{FastParameter, TypedNull, python_to_fast_parameter};
+
+/// How the values of an Arrow type are laid out in its buffers.
+#[derive(Clone, Copy)]
+enum Layout {
+    Null,
+    Bool,
+    Int {
+        width: usize,
+        signed: bool,
+    },
+    Float32,
+    Float64,
+    Utf8 {
+        large: bool,
+    },
+    Binary {
+        large: bool,
+    },
+    FixedBinary(usize),
+    Date32,
+    Date64,
+    /// `per_second` units of the timestamp or time of day in a second.
+    Timestamp {
+        per_second: i64,
+    },
+    Time {
+        width: usize,
+        per_second: i64,
+    },
+    Decimal(u8),
+    Dictionary,
+    /// Read with `to_pylist()`.
+    Other,
+}
+
+fn per_second(unit: &str) -> Option<i64> {
+    match unit {
+        "s" => Some(1),
+        "ms" => Some(1_000),
+        "us" => Some(1_000_000),
+        "ns" => Some(1_000_000_000),
+        _ => None,
+    }
+}
+
+fn layout(data_type: &Bound<PyAny>) -> PyResult<Layout> {
+    let name = data_type.str()?.to_string();
+    let unit = || -> PyResult<Option<i64>> {
+        Ok(per_second(&data_type.getattr("unit")?.extract::<String>()?))
+    };
+    Ok(match name.as_str() {
+        "null" => Layout::Null,
+        "bool" => Layout::Bool,
+        "int8" | "int16" | "int32" | "int64" | "uint8" | "uint16" | "uint32" | "uint64" => {
+            let signed = !name.starts_with('u');
+            let bits: usize = name.trim_start_matches('u')[3..].parse().unwrap_or(64);
+            Layout::Int {
+                width: bits / 8,
+                signed,
+            }
+        }
+        "float" => Layout::Float32,
+        "double" => Layout::Float64,
+        "string" | "utf8" => Layout::Utf8 { large: false },
+        "large_string" | "large_utf8" => Layout::Utf8 { large: true },
+        "binary" => Layout::Binary { large: false },
+        "large_binary" => Layout::Binary { large: true },
+        "date32[day]" => Layout::Date32,
+        "date64[ms]" => Layout::Date64,
+        _ if name.starts_with("fixed_size_binary[") => {
+            Layout::FixedBinary(data_type.getattr("byte_width")?.extract()?)
+        }
+        _ if name.starts_with("timestamp[") => match unit()? {
+            Some(per_second) => Layout::Timestamp { per_second },
+            None => Layout::Other,
+        },
+        _ if name.starts_with("time32[") || name.starts_with("time64[") => match unit()? {
+            Some(per_second) => Layout::Time {
+                width: if name.starts_with("time32") { 4 } else { 8 },
+                per_second,
+            },
+            None => Layout::Other,
+        },
+        _ if name.starts_with("decimal128(") => {
+            match u8::try_from(data_type.getattr("scale")?.extract::<i32>()?) {
+                Ok(scale) => Layout::Decimal(scale),
+                Err(_) => Layout::Other,
+            }
+        }
+        _ if name.starts_with("dictionary<") => Layout::Dictionary,
+        _ => Layout::Other,
+    })
+}
+
+fn bit(bits: &[u8], index: usize) -> bool {
+    bits[index / 8] >> (index % 8) & 1 == 1
+}
+
+/// The `N` little-endian bytes of fixed-width value `index`.
+fn fixed<const N: usize>(values: &[u8], index: usize) -> [u8; N] {
+    values[index * N..][..N]
+        .try_into()
+        .expect("slice has N bytes")
+}
+
+/// Byte range of variable-width value `index`.
+fn span(offsets: &[u8], index: usize, large: bool) -> (usize, usize) {
+    if large {
+        let start = i64::from_le_bytes(fixed(offsets, index));
+        let end = i64::from_le_bytes(fixed(offsets, index + 1));
+        (start as usize, end as usize)
+    } else {
+        let start = i32::from_le_bytes(fixed(offsets, index));
+        let end = i32::from_le_bytes(fixed(offsets, index + 1));
+        (start as usize, end as usize)
+    }
+}
+
+fn timestamp(value: i64, per_second: i64) -> Option<FastParameter> {
+    let nanos = value.rem_euclid(per_second) * (1_000_000_000 / per_second);
+    DateTime::from_timestamp(value.div_euclid(per_second), nanos as u32)
+        .map(|dt| FastParameter::DateTime(dt.naive_utc()))
+}
+
+fn time_of_day(value: i64, per_second: i64) -> Option<FastParameter> {
+    let nanos = value.rem_euclid(per_second) * (1_000_000_000 / per_second);
+    let seconds = u32::try_from(value.div_euclid(per_second)).ok()?;
+    NaiveTime::from_num_seconds_from_midnight_opt(seconds, nanos as u32).map(FastParameter::Time)
+}
+
+/// Append the values of one `pyarrow.Array` to `out`.
+fn read_array(array: &Bound<PyAny>, out: &mut Vec<FastParameter>) -> PyResult<()> {
+    let len: usize = array.len()?;
+    let layout = layout(&array.getattr("type")?)?;
+    match layout {
+        Layout::Dictionary => return read_array(&array.call_method0("dictionary_decode")?, out),
+        Layout::Other => {
+            for value in array.call_method0("to_pylist")?.try_iter()? {
+                out.push(python_to_fast_parameter(&value?)?);
+            }
+            return Ok(());
+        }
+        Layout::Null => {
+            out.extend((0..len).map(|_| FastParameter::Null(TypedNull::U8)));
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    let offset: usize = array.getattr("offset")?.extract()?;
+    let buffers: Vec<Option<Bound<PyBytes>>> = array
+        .call_method0("buffers")?
+        .try_iter()?
+        .map(|buffer| {
+            let buffer = buffer?;
+            if buffer.is_none() {
+                return Ok(None);
+            }
+            Ok(Some(
+                buffer.call_method0("to_pybytes")?.cast_into::<PyBytes>()?,
+            ))
+        })
+        .collect::<PyResult<_>>()?;
+    let bytes = |index: usize| -> &[u8] {
+        buffers
+            .get(index)
+            .and_then(Option::as_ref)
+            .map_or(&[][..], |b| b.as_bytes())
+    };
+    let validity = buffers
+        .first()
+        .and_then(Option::as_ref)
+        .map(|b| b.as_bytes());
+    let values = bytes(1);
+    let invalid = |what: &str| {
+        PyValueError::new_err(format!("Arrow {} value out of range for SQL Server", what))
+    };
+
+    out.reserve(len);
+    for row in 0..len {
+        let index = offset + row;
+        if validity.is_some_and(|bits| !bit(bits, index)) {
+            out.push(FastParameter::Null(TypedNull::U8));
+            continue;
+        }
+        out.push(match layout {
+            Layout::Bool => FastParameter::Bool(bit(values, index)),
+            Layout::Int { width, signed } => {
+                let value = match (width, signed) {
+                    (1, true) => i8::from_le_bytes(fixed(values, index)) as i64,
+                    (2, true) => i16::from_le_bytes(fixed(values, index)) as i64,
+                    (4, true) => i32::from_le_bytes(fixed(values, index)) as i64,
+                    (1, false) => u8::from_le_bytes(fixed(values, index)) as i64,
+                    (2, false) => u16::from_le_bytes(fixed(values, index)) as i64,
+                    (4, false) => u32::from_le_bytes(fixed(values, index)) as i64,
+                    (_, true) => i64::from_le_bytes(fixed(values, index)),
+                    (_, false) => i64::try_from(u64::from_le_bytes(fixed(values, index)))
+                        .map_err(|_| invalid("uint64"))?,
+                };
+                FastParameter::I64(value)
+            }
+            Layout::Float32 => FastParameter::F64(f32::from_le_bytes(fixed(values, index)) as f64),
+            Layout::Float64 => FastParameter::F64(f64::from_le_bytes(fixed(values, index))),
+            Layout::Utf8 { large } => {
+                let (start, end) = span(values, index, large);
+                let text = std::str::from_utf8(&bytes(2)[start..end])
+                    .map_err(|_| PyValueError::new_err("Arrow string column is not UTF-8"))?;
+                FastParameter::String(text.to_string())
+            }
+            Layout::Binary { large } => {
+                let (start, end) = span(values, index, large);
+                FastParameter::Bytes(bytes(2)[start..end].to_vec())
+            }
+            Layout::FixedBinary(width) => {
+                FastParameter::Bytes(values[index * width..][..width].to_vec())
+            }
+            Layout::Date32 => {
+                let days = i32::from_le_bytes(fixed(values, index));
+                NaiveDate::from_ymd_opt(1970, 1, 1)
+                    .and_then(|epoch| epoch.checked_add_signed(chrono::Duration::days(days as i64)))
+                    .map(FastParameter::Date)
+                    .ok_or_else(|| invalid("date32"))?
+            }
+            Layout::Date64 => {
+                let millis = i64::from_le_bytes(fixed(values, index));
+                DateTime::from_timestamp_millis(millis)
+                    .map(|dt| FastParameter::Date(dt.date_naive()))
+                    .ok_or_else(|| invalid("date64"))?
+            }
+            Layout::Timestamp { per_second } => {
+                timestamp(i64::from_le_bytes(fixed(values, index)), per_second)
+                    .ok_or_else(|| invalid("timestamp"))?
+            }
+            Layout::Time { width, per_second } => {
+                let value = match width {
+                    4 => i32::from_le_bytes(fixed(values, index)) as i64,
+                    _ => i64::from_le_bytes(fixed(values, index)),
+                };
+                time_of_day(value, per_second).ok_or_else(|| invalid("time"))?
+            }
+            Layout::Decimal(scale) => FastParameter::Decimal(Numeric::new_with_scale(
+                i128::from_le_bytes(fixed(values, index)),
+                scale,
+            )),
+            Layout::Null | Layout::Dictionary | Layout::Other => unreachable!(),
+        });
+    }
+    Ok(())
+}
+
+/// Column names and rows of `table` (a `pyarrow.Table`, or anything `pyarrow.table()`
+/// accepts, such as a `RecordBatch`).
+pub fn arrow_table_rows(table: &Bound<PyAny>) -> PyResult<(Vec<String>, Vec<Vec<FastParameter>>)> {
+    let py = table.py();
+    let pa = py.import("pyarrow").map_err(|_| {
+        PyImportError::new_err("bulk_insert_arrow() requires pyarrow (pip install pyarrow)")
+    })?;
+    let table = pa.call_method1("table", (table,))?;
+    let names: Vec<String> = table.getattr("column_names")?.extract()?;
+    let num_rows: usize = table.getattr("num_rows")?.extract()?;
+
+    let mut columns = Vec::with_capacity(names.len());
+    for index in 0..names.len() {
+        let mut values = Vec::with_capacity(num_rows);
+        for chunk in table
+            .call_method1("column", (index,))?
+            .getattr("chunks")?
+            .try_iter()?
+        {
+            read_array(&chunk?, &mut values)?;
+        }
+        columns.push(values.into_iter());
+    }
+
+    let rows = (0..num_rows)
+        .map(|_| {
+            columns
+                .iter_mut()
+                .map(|column| column.next().unwrap_or(FastParameter::Null(TypedNull::U8)))
+                .collect()
+        })
+        .collect();
+    Ok((names, rows))
+}
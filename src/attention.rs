@@ -0,0 +1,105 @@
+//! Cancelling the request in flight on a connection.
+//!
+//! SQL Server stops a running request when the client sends an attention signal, an
+//! empty TDS packet of type 6.  tiberius has no API for it, so [`Canceller`] writes it
+//! straight to the socket underneath tiberius, which it shares with the connection's
+//! [`ObservedStream`](crate::tls_info::ObservedStream).  That is only possible while
+//! the socket carries plain TDS packets: with `Encrypt=true` (the default) everything
+//! after login goes through tiberius's TLS session, and the request is instead
+//! cancelled by closing the connection, which makes the server abort and roll it back.
+//!
+//! Either way the connection is discarded afterwards: tiberius was interrupted part-way
+//! through reading the response, so its framing state cannot be trusted.
+
+use std::sync::{Arc, Mutex};
+use tokio::net::TcpStream;
+
+/// Packet type 6 (attention), status EOM, length 8, SPID 0, packet ID 1, window 0.
+const ATTENTION_PACKET: [u8; 8] = [0x06, 0x01, 0x00, 0x08, 0x00, 0x00, 0x01, 0x00];
+
+/// Tracks the frames the client writes, to know whether an attention packet can go on
+/// the wire next.  The outgoing byte stream is a sequence of TDS packets (length at
+/// offset 2) or, once TLS is up, TLS records (content types 20-23, length at offset 3).
+#[derive(Debug, Default)]
+pub struct OutgoingFrames {
+    header: [u8; 5],
+    header_len: usize,
+    remaining: usize,
+    last_type: Option<u8>,
+    lost: bool,
+}
+
+impl OutgoingFrames {
+    /// Account for `data`, just written to the socket.
+    pub fn feed(&mut self, mut data: &[u8]) {
+        while !data.is_empty() && !self.lost {
+            if self.remaining > 0 {
+                let n = self.remaining.min(data.len());
+                self.remaining -= n;
+                data = &data[n..];
+                continue;
+            }
+            let n = (self.header.len() - self.header_len).min(data.len());
+            self.header[self.header_len..self.header_len + n].copy_from_slice(&data[..n]);
+            self.header_len += n;
+            data = &data[n..];
+            if self.header_len < self.header.len() {
+                break;
+            }
+
+            let kind = self.header[0];
+            let total = if (20..=23).contains(&kind) {
+                5 + u16::from_be_bytes([self.header[3], self.header[4]]) as usize
+            } else {
+                u16::from_be_bytes([self.header[2], self.header[3]]) as usize
+            };
+            if total < self.header.len() {
+                self.lost = true;
+                break;
+            }
+            self.remaining = total - self.header.len();
+            self.header_len = 0;
+            self.last_type = Some(kind);
+        }
+    }
+
+    /// True when the last complete frame was a plain TDS request (SQL batch, RPC,
+    /// bulk load or transaction manager request), so the connection is not encrypted
+    /// after login and no packet is half-written.
+    fn at_request_boundary(&self) -> bool {
+        !self.lost
+            && self.header_len == 0
+            && self.remaining == 0
+            && matches!(self.last_type, Some(0x01 | 0x03 | 0x07 | 0x0E))
+    }
+}
+
+/// Sends an attention signal on a connection's socket.
+#[derive(Clone)]
+pub struct Canceller {
+    socket: Arc<TcpStream>,
+    frames: Arc<Mutex<OutgoingFrames>>,
+}
+
+impl Canceller {
+    pub fn new(socket: Arc<TcpStream>, frames: Arc<Mutex<OutgoingFrames>>) -> Self {
+        Canceller { socket, frames }
+    }
+
+    /// Write an attention signal if the connection carries plain TDS and no request
+    /// packet is half-written.  Returns whether it was sent.  Never blocks: eight bytes
+    /// fit in the socket's send buffer unless the connection is already wedged.
+    pub fn send_attention(&self) -> bool {
+        if !self
+            .frames
+            .lock()
+            .is_ok_and(|frames| frames.at_request_boundary())
+        {
+            return false;
+        }
+        matches!(
+            self.socket.try_write(&ATTENTION_PACKET),
+            Ok(n) if n == ATTENTION_PACKET.len()
+        )
+    }
+}
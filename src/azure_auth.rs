@@ -1,18 +1,21 @@
-use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use reqwest::Client;
 use serde_json::Value;
 use std::collections::HashMap;
 use std::fmt;
-use std::path::Path;
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, PoisonError, Weak};
 use std::time::{Duration, Instant};
 use tiberius::AuthMethod;
 use tokio::sync::{Mutex, RwLock};
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
+use crate::client_assertion::ClientCertificate;
+use crate::types::create_auth_error;
+
 /// Secure string wrapper that zeroizes memory when dropped
-#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+#[derive(Clone, PartialEq, Eq, Hash, Zeroize, ZeroizeOnDrop)]
 struct SensitiveString(String);
 
 impl SensitiveString {
@@ -52,8 +55,104 @@ impl Drop for CachedToken {
     }
 }
 
+/// Resource tokens are requested for unless the credential names another, such as
+/// `https://database.usgovcloudapi.net/` in Azure Government.
+const SQL_RESOURCE: &str = "https://database.windows.net/";
+
+/// Microsoft Entra ID login endpoint of the public cloud.
+const AUTHORITY_HOST: &str = "https://login.microsoftonline.com";
+
+/// Normalize an `authority_host=` argument, a host name such as
+/// `login.microsoftonline.us` or a URL, to a URL without a trailing slash.
+fn parse_authority_host(value: &str) -> PyResult<String> {
+    let value = value.trim().trim_end_matches('/');
+    let url = if value.contains("://") {
+        value.to_string()
+    } else {
+        format!("https://{}", value)
+    };
+    match reqwest::Url::parse(&url) {
+        Ok(parsed)
+            if matches!(parsed.scheme(), "http" | "https") && parsed.host_str().is_some() =>
+        {
+            Ok(url)
+        }
+        _ => Err(PyValueError::new_err(format!(
+            "authority_host must be a host name such as 'login.microsoftonline.us' or a URL, got '{}'",
+            value
+        ))),
+    }
+}
+
+/// Normalize a `resource=` argument, given as a resource or as its `/.default` scope,
+/// to the resource form with a trailing slash.
+fn parse_resource(value: &str) -> PyResult<String> {
+    let resource = value.trim().trim_end_matches(".default");
+    match reqwest::Url::parse(resource) {
+        Ok(url) if url.scheme() == "https" && url.host_str().is_some() => {}
+        _ => {
+            return Err(PyValueError::new_err(format!(
+                "resource must be an https URL such as 'https://database.windows.net/', got '{}'",
+                value
+            )));
+        }
+    }
+    Ok(format!("{}/", resource.trim_end_matches('/')))
+}
+
+/// `client_assertion_type` of a token request authenticated with a signed JWT.
+const JWT_BEARER_ASSERTION: &str = "urn:ietf:params:oauth:client-assertion-type:jwt-bearer";
+
+/// How a service principal authenticates its token requests: with a client secret,
+/// or with a client assertion signed by the certificate in a PEM file.
+enum ClientAuth<'a> {
+    Secret(&'a str),
+    Certificate(&'a str),
+}
+
+/// Identifies the token a credential obtains.  Credentials with equal keys share one
+/// cached token, so separate `AzureCredential` objects for the same identity (one per
+/// `Connection`, say) fetch it once between them.  The secret (or client certificate)
+/// is part of the key so a credential with a wrong secret cannot pick up a token
+/// obtained with the right one.
+#[derive(PartialEq, Eq, Hash)]
+struct TokenKey {
+    credential_type: AzureCredentialType,
+    resource: String,
+    client_id: Option<String>,
+    tenant_id: Option<String>,
+    endpoint: Option<String>,
+    authority_host: Option<String>,
+    secret: Option<SensitiveString>,
+}
+
+/// A cached token and the lock that serializes refreshing it.
+#[derive(Default)]
+struct TokenSlot {
+    token: RwLock<Option<CachedToken>>,
+    refresh: Mutex<()>,
+}
+
+/// Process-wide token slots.  Entries are weak, so a token (and the secret in its key)
+/// is kept only while some credential still uses it.
+static TOKEN_SLOTS: LazyLock<std::sync::Mutex<HashMap<TokenKey, Weak<TokenSlot>>>> =
+    LazyLock::new(Default::default);
+
+impl TokenSlot {
+    fn shared(key: TokenKey) -> Arc<TokenSlot> {
+        let mut slots = TOKEN_SLOTS.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(slot) = slots.get(&key).and_then(Weak::upgrade) {
+            return slot;
+        }
+        slots.retain(|_, slot| slot.strong_count() > 0);
+        let slot = Arc::new(TokenSlot::default());
+        slots.insert(key, Arc::downgrade(&slot));
+        slot
+    }
+}
+
 #[pyclass(name = "AzureCredentialType", from_py_object)] // <-- Explicit opt-in
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum AzureCredentialType {
     ServicePrincipal,
     ManagedIdentity,
@@ -69,8 +168,7 @@ pub struct PyAzureCredential {
     pub config: HashMap<String, String>,
     // Sensitive configuration (zeroized on drop; never exposed via .config)
     sensitive_config: Arc<HashMap<String, SensitiveString>>,
-    token_cache: Arc<RwLock<Option<CachedToken>>>,
-    refresh_mutex: Arc<Mutex<()>>,
+    tokens: Arc<TokenSlot>,
     client: Arc<Client>,
 }
 
@@ -80,7 +178,7 @@ impl fmt::Debug for PyAzureCredential {
             .field("credential_type", &self.credential_type)
             .field("config", &self.config)
             .field("sensitive_config", &"***redacted***")
-            .field("token_cache", &"***redacted***")
+            .field("tokens", &"***redacted***")
             .finish()
     }
 }
@@ -132,14 +230,26 @@ fn build_http_client() -> Result<Arc<Client>, reqwest::Error> {
 #[pymethods]
 impl PyAzureCredential {
     #[staticmethod]
+    #[pyo3(signature = (client_id, client_secret, tenant_id, *, resource = None, authority_host = None))]
     pub fn service_principal(
         client_id: String,
         client_secret: String,
         tenant_id: String,
+        resource: Option<&str>,
+        authority_host: Option<&str>,
     ) -> PyResult<Self> {
         let mut config = HashMap::new();
         config.insert("client_id".to_string(), client_id.clone());
         config.insert("tenant_id".to_string(), tenant_id.clone());
+        if let Some(resource) = resource {
+            config.insert("resource".to_string(), parse_resource(resource)?);
+        }
+        if let Some(authority_host) = authority_host {
+            config.insert(
+                "authority_host".to_string(),
+                parse_authority_host(authority_host)?,
+            );
+        }
 
         let mut sensitive_config = HashMap::new();
         sensitive_config.insert("client_id".to_string(), SensitiveString::new(client_id));
@@ -149,40 +259,107 @@ impl PyAzureCredential {
         );
         sensitive_config.insert("tenant_id".to_string(), SensitiveString::new(tenant_id));
 
-        let client = build_http_client()
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to build HTTP client: {}", e)))?;
+        PyAzureCredential::new(
+            AzureCredentialType::ServicePrincipal,
+            config,
+            sensitive_config,
+        )
+    }
 
-        Ok(PyAzureCredential {
-            credential_type: AzureCredentialType::ServicePrincipal,
+    /// A service principal that signs in with a certificate instead of a client
+    /// secret.  `cert_path` is a PEM file holding the certificate and its unencrypted
+    /// RSA private key; it is read once, here.
+    #[staticmethod]
+    #[pyo3(signature = (client_id, tenant_id, cert_path, *, resource = None, authority_host = None))]
+    pub fn service_principal_certificate(
+        client_id: String,
+        tenant_id: String,
+        cert_path: PathBuf,
+        resource: Option<&str>,
+        authority_host: Option<&str>,
+    ) -> PyResult<Self> {
+        let pem = std::fs::read(&cert_path)
+            .ok()
+            .and_then(|pem| String::from_utf8(pem).ok())
+            .ok_or_else(|| {
+                PyValueError::new_err(format!(
+                    "Cannot read certificate file '{}' as PEM",
+                    cert_path.display()
+                ))
+            })?;
+        let pem = SensitiveString::new(pem);
+        let certificate = ClientCertificate::from_pem(pem.as_str().as_bytes())?;
+
+        let mut config = HashMap::new();
+        config.insert("client_id".to_string(), client_id.clone());
+        config.insert("tenant_id".to_string(), tenant_id.clone());
+        config.insert("cert_path".to_string(), cert_path.display().to_string());
+        config.insert("cert_thumbprint".to_string(), certificate.thumbprint());
+        if let Some(resource) = resource {
+            config.insert("resource".to_string(), parse_resource(resource)?);
+        }
+        if let Some(authority_host) = authority_host {
+            config.insert(
+                "authority_host".to_string(),
+                parse_authority_host(authority_host)?,
+            );
+        }
+
+        let mut sensitive_config = HashMap::new();
+        sensitive_config.insert("client_id".to_string(), SensitiveString::new(client_id));
+        sensitive_config.insert("client_certificate".to_string(), pem);
+        sensitive_config.insert("tenant_id".to_string(), SensitiveString::new(tenant_id));
+
+        PyAzureCredential::new(
+            AzureCredentialType::ServicePrincipal,
             config,
-            sensitive_config: Arc::new(sensitive_config),
-            token_cache: Arc::new(RwLock::new(None)),
-            refresh_mutex: Arc::new(Mutex::new(())),
-            client,
-        })
+            sensitive_config,
+        )
     }
 
     #[staticmethod]
-    pub fn managed_identity(client_id: Option<String>) -> PyResult<Self> {
+    #[pyo3(signature = (client_id = None, *, endpoint = None, api_version = None, resource = None))]
+    pub fn managed_identity(
+        client_id: Option<String>,
+        endpoint: Option<String>,
+        api_version: Option<String>,
+        resource: Option<&str>,
+    ) -> PyResult<Self> {
         let mut config = HashMap::new();
         let mut sensitive_config = HashMap::new();
 
+        if let Some(resource) = resource {
+            config.insert("resource".to_string(), parse_resource(resource)?);
+        }
+
         if let Some(id) = client_id {
             config.insert("client_id".to_string(), id.clone());
             sensitive_config.insert("client_id".to_string(), SensitiveString::new(id));
         }
+        if let Some(endpoint) = endpoint {
+            match reqwest::Url::parse(&endpoint) {
+                Ok(url) if matches!(url.scheme(), "http" | "https") => {}
+                _ => {
+                    return Err(PyValueError::new_err(format!(
+                        "endpoint must be an http(s) URL, got '{}'",
+                        endpoint
+                    )));
+                }
+            }
+            config.insert("endpoint".to_string(), endpoint);
+        }
+        if let Some(api_version) = api_version {
+            if api_version.trim().is_empty() {
+                return Err(PyValueError::new_err("api_version must not be empty"));
+            }
+            config.insert("api_version".to_string(), api_version);
+        }
 
-        let client = build_http_client()
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to build HTTP client: {}", e)))?;
-
-        Ok(PyAzureCredential {
-            credential_type: AzureCredentialType::ManagedIdentity,
+        PyAzureCredential::new(
+            AzureCredentialType::ManagedIdentity,
             config,
-            sensitive_config: Arc::new(sensitive_config),
-            token_cache: Arc::new(RwLock::new(None)),
-            refresh_mutex: Arc::new(Mutex::new(())),
-            client,
-        })
+            sensitive_config,
+        )
     }
 
     #[staticmethod]
@@ -190,32 +367,28 @@ impl PyAzureCredential {
         let mut sensitive_config = HashMap::new();
         sensitive_config.insert("access_token".to_string(), SensitiveString::new(token));
 
-        let client = build_http_client()
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to build HTTP client: {}", e)))?;
-
-        Ok(PyAzureCredential {
-            credential_type: AzureCredentialType::AccessToken,
-            config: HashMap::new(),
-            sensitive_config: Arc::new(sensitive_config),
-            token_cache: Arc::new(RwLock::new(None)),
-            refresh_mutex: Arc::new(Mutex::new(())),
-            client,
-        })
+        PyAzureCredential::new(
+            AzureCredentialType::AccessToken,
+            HashMap::new(),
+            sensitive_config,
+        )
     }
 
     #[staticmethod]
-    pub fn default() -> PyResult<Self> {
-        let client = build_http_client()
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to build HTTP client: {}", e)))?;
-
-        Ok(PyAzureCredential {
-            credential_type: AzureCredentialType::DefaultAzure,
-            config: HashMap::new(),
-            sensitive_config: Arc::new(HashMap::new()),
-            token_cache: Arc::new(RwLock::new(None)),
-            refresh_mutex: Arc::new(Mutex::new(())),
-            client,
-        })
+    #[pyo3(signature = (*, resource = None, authority_host = None))]
+    #[allow(clippy::should_implement_trait)]
+    pub fn default(resource: Option<&str>, authority_host: Option<&str>) -> PyResult<Self> {
+        let mut config = HashMap::new();
+        if let Some(resource) = resource {
+            config.insert("resource".to_string(), parse_resource(resource)?);
+        }
+        if let Some(authority_host) = authority_host {
+            config.insert(
+                "authority_host".to_string(),
+                parse_authority_host(authority_host)?,
+            );
+        }
+        PyAzureCredential::new(AzureCredentialType::DefaultAzure, config, HashMap::new())
     }
 
     #[getter]
@@ -231,6 +404,61 @@ impl PyAzureCredential {
 }
 
 impl PyAzureCredential {
+    fn new(
+        credential_type: AzureCredentialType,
+        config: HashMap<String, String>,
+        sensitive_config: HashMap<String, SensitiveString>,
+    ) -> PyResult<Self> {
+        let client = build_http_client()
+            .map_err(|e| create_auth_error(format!("Failed to build HTTP client: {}", e)))?;
+        let tokens = match credential_type {
+            // Never refreshed, so there is nothing to share.
+            AzureCredentialType::AccessToken => Arc::default(),
+            _ => TokenSlot::shared(TokenKey {
+                credential_type: credential_type.clone(),
+                resource: config
+                    .get("resource")
+                    .cloned()
+                    .unwrap_or_else(|| SQL_RESOURCE.to_string()),
+                client_id: config.get("client_id").cloned(),
+                tenant_id: config.get("tenant_id").cloned(),
+                endpoint: config.get("endpoint").cloned(),
+                authority_host: config.get("authority_host").cloned(),
+                secret: sensitive_config
+                    .get("client_secret")
+                    .or_else(|| sensitive_config.get("client_certificate"))
+                    .cloned(),
+            }),
+        };
+
+        Ok(PyAzureCredential {
+            credential_type,
+            config,
+            sensitive_config: Arc::new(sensitive_config),
+            tokens,
+            client,
+        })
+    }
+
+    /// Resource tokens are requested for.
+    fn resource(&self) -> &str {
+        self.config
+            .get("resource")
+            .map_or(SQL_RESOURCE, String::as_str)
+    }
+
+    /// Login endpoint for client-credential tokens: the credential's `authority_host`,
+    /// else `AZURE_AUTHORITY_HOST` as the Azure SDKs read it, else the public cloud.
+    fn authority_host(&self) -> PyResult<String> {
+        if let Some(host) = self.config.get("authority_host") {
+            return Ok(host.clone());
+        }
+        match std::env::var("AZURE_AUTHORITY_HOST") {
+            Ok(host) if !host.trim().is_empty() => parse_authority_host(&host),
+            _ => Ok(AUTHORITY_HOST.to_string()),
+        }
+    }
+
     fn get_sensitive_value(&self, key: &str) -> Option<&SensitiveString> {
         self.sensitive_config.get(key)
     }
@@ -248,10 +476,10 @@ impl PyAzureCredential {
         if let Some(num) = json[key].as_u64() {
             return num;
         }
-        if let Some(s) = json[key].as_str() {
-            if let Ok(parsed) = s.parse::<u64>() {
-                return parsed;
-            }
+        if let Some(s) = json[key].as_str()
+            && let Ok(parsed) = s.parse::<u64>()
+        {
+            return parsed;
         }
         3600 // Safe default: 1 hour
     }
@@ -285,49 +513,62 @@ impl PyAzureCredential {
 
         // 2. Fast Path Read Lock
         {
-            let read_guard = self.token_cache.read().await;
-            if let Some(cached) = read_guard.as_ref() {
-                if Self::is_token_still_valid(cached) {
-                    return Ok(AuthMethod::aad_token(
-                        cached.access_token.as_str().to_string(),
-                    ));
-                }
+            let read_guard = self.tokens.token.read().await;
+            if let Some(cached) = read_guard.as_ref()
+                && Self::is_token_still_valid(cached)
+            {
+                return Ok(AuthMethod::aad_token(
+                    cached.access_token.as_str().to_string(),
+                ));
             }
         }
 
         // 3. Slow Path Serialization Mutex
-        let _refresh_guard = self.refresh_mutex.lock().await;
+        let _refresh_guard = self.tokens.refresh.lock().await;
 
         // Double check cache
         {
-            let read_guard = self.token_cache.read().await;
-            if let Some(cached) = read_guard.as_ref() {
-                if Self::is_token_still_valid(cached) {
-                    return Ok(AuthMethod::aad_token(
-                        cached.access_token.as_str().to_string(),
-                    ));
-                }
+            let read_guard = self.tokens.token.read().await;
+            if let Some(cached) = read_guard.as_ref()
+                && Self::is_token_still_valid(cached)
+            {
+                return Ok(AuthMethod::aad_token(
+                    cached.access_token.as_str().to_string(),
+                ));
             }
         }
 
+        let fresh = self.fetch_and_cache_token().await?;
+
+        // Return token string
+        Ok(AuthMethod::aad_token(
+            fresh.access_token.as_str().to_string(),
+        ))
+    }
+
+    /// Fetch a token over the network and store it in the cache.
+    ///
+    /// Callers must hold `tokens.refresh`.
+    async fn fetch_and_cache_token(&self) -> PyResult<CachedToken> {
         // Fetch new token over network
         let (token, expires_in) = match self.credential_type {
             AzureCredentialType::ServicePrincipal => {
                 let client_id = self
                     .get_sensitive_value("client_id")
                     .ok_or_else(|| PyValueError::new_err("Client ID not found"))?;
-                let client_secret = self
-                    .get_sensitive_value("client_secret")
-                    .ok_or_else(|| PyValueError::new_err("Client secret not found"))?;
+                let auth = match (
+                    self.get_sensitive_value("client_secret"),
+                    self.get_sensitive_value("client_certificate"),
+                ) {
+                    (Some(secret), _) => ClientAuth::Secret(secret.as_str()),
+                    (None, Some(pem)) => ClientAuth::Certificate(pem.as_str()),
+                    (None, None) => return Err(PyValueError::new_err("Client secret not found")),
+                };
                 let tenant_id = self
                     .get_sensitive_value("tenant_id")
                     .ok_or_else(|| PyValueError::new_err("Tenant ID not found"))?;
-                self.acquire_service_principal_token(
-                    client_id.as_str(),
-                    client_secret.as_str(),
-                    tenant_id.as_str(),
-                )
-                .await?
+                self.acquire_service_principal_token(client_id.as_str(), auth, tenant_id.as_str())
+                    .await?
             }
             AzureCredentialType::ManagedIdentity => {
                 let client_id = self.get_sensitive_value("client_id").map(|s| s.as_str());
@@ -339,41 +580,111 @@ impl PyAzureCredential {
 
         // Enforce safety buffers against premature expiration
         let buffer_secs = ((expires_in as f64 * 0.10) as u64)
-            .max(30)
-            .min(600)
+            .clamp(30, 600)
             .min(expires_in);
         let expires_at =
             Instant::now() + Duration::from_secs(expires_in.saturating_sub(buffer_secs));
 
+        let fresh = CachedToken {
+            access_token: SensitiveString::new(token),
+            expires_at,
+        };
+
         // Brief write lock update
         {
-            let mut write_guard = self.token_cache.write().await;
-            *write_guard = Some(CachedToken {
-                access_token: SensitiveString::new(token.clone()),
-                expires_at,
-            });
+            let mut write_guard = self.tokens.token.write().await;
+            *write_guard = Some(fresh.clone());
         }
 
-        // Return token string
-        Ok(AuthMethod::aad_token(token))
+        Ok(fresh)
+    }
+
+    /// Forget the cached token after the server rejected a login made with it (e.g. it
+    /// was revoked, or expired early by the server's clock), so the next connection
+    /// fetches a new one instead of presenting the same token until it expires here.
+    pub(crate) async fn discard_cached_token(&self) {
+        if let AzureCredentialType::AccessToken = self.credential_type {
+            return;
+        }
+        let _refresh_guard = self.tokens.refresh.lock().await;
+        *self.tokens.token.write().await = None;
+    }
+
+    /// [`Self::discard_cached_token`] if `error`, from a dedicated (non-pooled)
+    /// connection's login, is the server refusing the login (18456).
+    pub(crate) async fn discard_refused_token(&self, error: &tiberius::error::Error) {
+        if matches!(error, tiberius::error::Error::Server(e) if e.code() == 18456) {
+            self.discard_cached_token().await;
+        }
+    }
+
+    /// Refresh the cached token if it expires within `lead`, and return how long
+    /// the caller can wait before checking again.
+    ///
+    /// Used by the pool's background refresher so that new connections opened after
+    /// the token's lifetime find a fresh token in the cache instead of paying for the
+    /// token round trip on the request path.  Returns `None` for static access tokens,
+    /// which cannot be refreshed.
+    pub(crate) async fn refresh_ahead_of_expiry(
+        &self,
+        lead: Duration,
+    ) -> PyResult<Option<Duration>> {
+        if let AzureCredentialType::AccessToken = self.credential_type {
+            return Ok(None);
+        }
+
+        let _refresh_guard = self.tokens.refresh.lock().await;
+
+        let remaining = {
+            let read_guard = self.tokens.token.read().await;
+            read_guard
+                .as_ref()
+                .map(|cached| cached.expires_at.saturating_duration_since(Instant::now()))
+        };
+        if let Some(remaining) = remaining
+            && remaining > lead
+        {
+            return Ok(Some(remaining - lead));
+        }
+
+        let remaining = self
+            .fetch_and_cache_token()
+            .await?
+            .expires_at
+            .saturating_duration_since(Instant::now());
+        // Short-lived tokens are refreshed at half their lifetime rather than in a
+        // tight loop once the whole lifetime fits inside `lead`.
+        Ok(Some(
+            remaining
+                .saturating_sub(lead)
+                .max(remaining / 2)
+                .max(Duration::from_secs(30)),
+        ))
     }
 
     async fn acquire_service_principal_token(
         &self,
         client_id: &str,
-        client_secret: &str,
+        auth: ClientAuth<'_>,
         tenant_id: &str,
     ) -> PyResult<(String, u64)> {
-        let token_url = format!(
-            "https://login.microsoftonline.com/{}/oauth2/v2.0/token",
-            tenant_id
-        );
-        let params = [
+        let token_url = format!("{}/{}/oauth2/v2.0/token", self.authority_host()?, tenant_id);
+        let scope = format!("{}.default", self.resource());
+        let mut params = vec![
             ("grant_type", "client_credentials"),
             ("client_id", client_id),
-            ("client_secret", client_secret),
-            ("scope", "https://database.windows.net/.default"),
+            ("scope", scope.as_str()),
         ];
+        let assertion;
+        match auth {
+            ClientAuth::Secret(client_secret) => params.push(("client_secret", client_secret)),
+            ClientAuth::Certificate(pem) => {
+                assertion = ClientCertificate::from_pem(pem.as_bytes())?
+                    .assertion(client_id, &token_url)?;
+                params.push(("client_assertion_type", JWT_BEARER_ASSERTION));
+                params.push(("client_assertion", assertion.as_str()));
+            }
+        }
 
         let response = self
             .client
@@ -381,10 +692,10 @@ impl PyAzureCredential {
             .form(&params)
             .send()
             .await
-            .map_err(|e| PyRuntimeError::new_err(format!("Token request failed: {}", e)))?;
+            .map_err(|e| create_auth_error(format!("Token request failed: {}", e)))?;
 
         if !response.status().is_success() {
-            return Err(PyRuntimeError::new_err(format!(
+            return Err(create_auth_error(format!(
                 "HTTP Error: {}",
                 response.status()
             )));
@@ -393,11 +704,11 @@ impl PyAzureCredential {
         let json: Value = response
             .json()
             .await
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed parsing JSON: {}", e)))?;
+            .map_err(|e| create_auth_error(format!("Failed parsing JSON: {}", e)))?;
 
         let access_token = json["access_token"]
             .as_str()
-            .ok_or_else(|| PyRuntimeError::new_err("Access token missing"))?
+            .ok_or_else(|| create_auth_error("Access token missing"))?
             .to_string();
 
         let expires_in = Self::parse_expires_in(&json, "expires_in");
@@ -405,32 +716,79 @@ impl PyAzureCredential {
         Ok((access_token, expires_in))
     }
 
+    /// Where to request managed identity tokens, and the API version to ask for: the
+    /// endpoint passed to `managed_identity()`, Azure Arc's local identity service when
+    /// the Arc agent announces it through `IDENTITY_ENDPOINT` and `IMDS_ENDPOINT`, or
+    /// the Azure instance metadata service.
+    fn managed_identity_endpoint(&self) -> (String, String) {
+        const IMDS_ENDPOINT: &str = "http://169.254.169.254/metadata/identity/oauth2/token";
+        const IMDS_API_VERSION: &str = "2021-02-01";
+        const ARC_API_VERSION: &str = "2020-06-01";
+
+        let arc_endpoint = std::env::var("IDENTITY_ENDPOINT")
+            .ok()
+            .filter(|s| !s.is_empty() && std::env::var_os("IMDS_ENDPOINT").is_some());
+        let (endpoint, default_api_version) = match (self.config.get("endpoint"), arc_endpoint) {
+            (Some(endpoint), _) => (endpoint.clone(), IMDS_API_VERSION),
+            (None, Some(endpoint)) => (endpoint, ARC_API_VERSION),
+            (None, None) => (IMDS_ENDPOINT.to_string(), IMDS_API_VERSION),
+        };
+        let api_version = self
+            .config
+            .get("api_version")
+            .cloned()
+            .unwrap_or_else(|| default_api_version.to_string());
+        (endpoint, api_version)
+    }
+
     async fn acquire_managed_identity_token(
         &self,
         client_id: Option<&str>,
     ) -> PyResult<(String, u64)> {
-        const IMDS_ENDPOINT: &str = "http://169.254.169.254/metadata/identity/oauth2/token";
-        let mut url = reqwest::Url::parse(IMDS_ENDPOINT)
-            .map_err(|e| PyRuntimeError::new_err(format!("Invalid IMDS endpoint: {}", e)))?;
+        let (endpoint, api_version) = self.managed_identity_endpoint();
+        let mut url = reqwest::Url::parse(&endpoint)
+            .map_err(|e| create_auth_error(format!("Invalid IMDS endpoint: {}", e)))?;
 
         url.query_pairs_mut()
-            .append_pair("api-version", "2021-02-01")
-            .append_pair("resource", "https://database.windows.net/");
+            .append_pair("api-version", &api_version)
+            .append_pair("resource", self.resource());
 
         if let Some(id) = client_id {
             url.query_pairs_mut().append_pair("client_id", id);
         }
 
-        let response = self
-            .client
-            .get(url)
-            .header("Metadata", "true")
+        let request = self.client.get(url).header("Metadata", "true");
+        let challenge = request
+            .try_clone()
+            .ok_or_else(|| create_auth_error("IMDS request cannot be retried"))?;
+        let mut response = request
             .send()
             .await
-            .map_err(|e| PyRuntimeError::new_err(format!("IMDS request failed: {}", e)))?;
+            .map_err(|e| create_auth_error(format!("IMDS request failed: {}", e)))?;
+
+        // Azure Arc answers the first request with 401 and the path of a file that only
+        // administrators can read; reading it proves the caller is allowed to use the
+        // machine's identity.
+        if response.status() == reqwest::StatusCode::UNAUTHORIZED
+            && let Some(key_path) = response
+                .headers()
+                .get(reqwest::header::WWW_AUTHENTICATE)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.strip_prefix("Basic realm="))
+        {
+            let secret = Self::read_arc_key(key_path)?;
+            response = challenge
+                .header(
+                    reqwest::header::AUTHORIZATION,
+                    format!("Basic {}", secret.as_str()),
+                )
+                .send()
+                .await
+                .map_err(|e| create_auth_error(format!("IMDS request failed: {}", e)))?;
+        }
 
         if !response.status().is_success() {
-            return Err(PyRuntimeError::new_err(format!(
+            return Err(create_auth_error(format!(
                 "IMDS error status: {}",
                 response.status()
             )));
@@ -439,10 +797,10 @@ impl PyAzureCredential {
         let json: Value = response
             .json()
             .await
-            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+            .map_err(|e| create_auth_error(e.to_string()))?;
         let access_token = json["access_token"]
             .as_str()
-            .ok_or_else(|| PyRuntimeError::new_err("Access token missing"))?
+            .ok_or_else(|| create_auth_error("Access token missing"))?
             .to_string();
 
         let expires_in = Self::parse_expires_in(&json, "expires_in");
@@ -450,6 +808,63 @@ impl PyAzureCredential {
         Ok((access_token, expires_in))
     }
 
+    /// Directory the Azure Arc agent writes its challenge files to.
+    fn arc_key_directory() -> Option<std::path::PathBuf> {
+        if cfg!(windows) {
+            std::env::var_os("ProgramData").map(|dir| {
+                Path::new(&dir)
+                    .join("AzureConnectedMachineAgent")
+                    .join("Tokens")
+            })
+        } else if cfg!(target_os = "linux") {
+            Some("/var/opt/azcmagent/tokens".into())
+        } else {
+            None
+        }
+    }
+
+    /// Read the secret named by an Azure Arc challenge.  The path comes from the
+    /// endpoint's response, so only small `.key` files in the agent's token directory
+    /// are accepted; anything else would let the endpoint read arbitrary files.
+    fn read_arc_key(path: &str) -> PyResult<SensitiveString> {
+        const MAX_KEY_SIZE: u64 = 4096;
+
+        let path = Path::new(path);
+        let in_key_directory = Self::arc_key_directory().is_some_and(|dir| {
+            path.parent().is_some_and(|parent| parent == dir)
+                && path.extension().is_some_and(|ext| ext == "key")
+        });
+        if !in_key_directory {
+            return Err(create_auth_error(format!(
+                "Managed identity endpoint asked for '{}', which is not an Azure Arc key file",
+                path.display()
+            )));
+        }
+        let metadata = std::fs::metadata(path).map_err(|e| {
+            create_auth_error(format!(
+                "Cannot read Azure Arc key file '{}': {}",
+                path.display(),
+                e
+            ))
+        })?;
+        if metadata.len() > MAX_KEY_SIZE {
+            return Err(create_auth_error(format!(
+                "Azure Arc key file '{}' is larger than {} bytes",
+                path.display(),
+                MAX_KEY_SIZE
+            )));
+        }
+        std::fs::read_to_string(path)
+            .map(|secret| SensitiveString::new(secret.trim().to_string()))
+            .map_err(|e| {
+                create_auth_error(format!(
+                    "Cannot read Azure Arc key file '{}': {}",
+                    path.display(),
+                    e
+                ))
+            })
+    }
+
     /// Get the default Azure CLI path for the current OS
     fn get_default_az_path() -> &'static str {
         // Return bare program name on all platforms to leverage OS PATH resolution.
@@ -474,9 +889,9 @@ impl PyAzureCredential {
         // On Windows, also treat drive-relative paths like `C:az.cmd` as explicit paths
         // (they contain no separators but are still path-like).
         let path = Path::new(&az_path);
-        let is_bare_name = !az_path.contains('/')
-            && !az_path.contains('\\')
-            && !(cfg!(windows) && az_path.contains(':'));
+        let is_bare_name = !(az_path.contains('/')
+            || az_path.contains('\\')
+            || (cfg!(windows) && az_path.contains(':')));
         if is_bare_name {
             // Bare program name - let the OS resolve it via PATH
             return Ok(az_path);
@@ -484,7 +899,7 @@ impl PyAzureCredential {
 
         // For explicit paths, validate that the path exists and is accessible
         if !path.exists() {
-            return Err(PyRuntimeError::new_err(format!(
+            return Err(create_auth_error(format!(
                 "Azure CLI executable not found at '{}'. Set AZURE_CLI_PATH environment variable if installed elsewhere.",
                 az_path
             )));
@@ -492,7 +907,7 @@ impl PyAzureCredential {
 
         // Verify it's a file (not a directory)
         if !path.is_file() {
-            return Err(PyRuntimeError::new_err(format!(
+            return Err(create_auth_error(format!(
                 "Azure CLI path '{}' is not a file",
                 az_path
             )));
@@ -503,14 +918,14 @@ impl PyAzureCredential {
         {
             use std::os::unix::fs::PermissionsExt;
             let metadata = std::fs::metadata(&az_path).map_err(|e| {
-                PyRuntimeError::new_err(format!(
+                create_auth_error(format!(
                     "Cannot access Azure CLI at '{}': {}",
                     az_path, e
                 ))
             })?;
             let permissions = metadata.permissions();
             if permissions.mode() & 0o111 == 0 {
-                return Err(PyRuntimeError::new_err(format!(
+                return Err(create_auth_error(format!(
                     "Azure CLI at '{}' is not executable",
                     az_path
                 )));
@@ -536,7 +951,11 @@ impl PyAzureCredential {
             (client_id, client_secret, tenant_id)
         {
             return self
-                .acquire_service_principal_token(&client_id, &client_secret, &tenant_id)
+                .acquire_service_principal_token(
+                    &client_id,
+                    ClientAuth::Secret(&client_secret),
+                    &tenant_id,
+                )
                 .await;
         }
 
@@ -555,7 +974,7 @@ impl PyAzureCredential {
                     "account",
                     "get-access-token",
                     "--resource",
-                    "https://database.windows.net/",
+                    self.resource(),
                     "--output",
                     "json",
                 ])
@@ -565,13 +984,13 @@ impl PyAzureCredential {
         {
             Ok(Ok(output)) => output,
             Ok(Err(e)) => {
-                return Err(PyRuntimeError::new_err(format!(
+                return Err(create_auth_error(format!(
                     "Failed to execute Azure CLI from '{}': {}",
                     az_path, e
                 )));
             }
             Err(_) => {
-                return Err(PyRuntimeError::new_err(format!(
+                return Err(create_auth_error(format!(
                     "Azure CLI command timed out after 10 seconds (executed from '{}')",
                     az_path
                 )));
@@ -581,11 +1000,11 @@ impl PyAzureCredential {
         match output.status.success() {
             true => {
                 let json: Value = serde_json::from_slice(&output.stdout)
-                    .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+                    .map_err(|e| create_auth_error(e.to_string()))?;
 
                 let access_token = json["accessToken"]
                     .as_str()
-                    .ok_or_else(|| PyRuntimeError::new_err("Missing accessToken"))?
+                    .ok_or_else(|| create_auth_error("Missing accessToken"))?
                     .to_string();
 
                 // Azure CLI returns 'expiresOn' as an ISO 8601 timestamp string.
@@ -596,7 +1015,7 @@ impl PyAzureCredential {
             }
             false => {
                 let stderr = String::from_utf8_lossy(&output.stderr);
-                Err(PyRuntimeError::new_err(format!(
+                Err(create_auth_error(format!(
                     "Azure CLI command failed (executed from '{}'): {}. Exit code: {}",
                     az_path,
                     stderr.trim(),
@@ -1,12 +1,19 @@
 use std::fmt::Write;
 
 use crate::azure_auth::PyAzureCredential;
+use crate::bulk_copy::{BulkLoadRows, bulk_load_on_connection, prepare_bulk_load};
+use crate::bulk_verify::BulkVerify;
+use crate::execution_id::BatchTrace;
 use crate::parameter_conversion::{
     FastParameter, TypedNull, convert_parameters_to_fast, params_as_sql_refs,
-    python_to_fast_parameter,
+    python_to_fast_parameter, rewrite_json_lists,
 };
 use crate::pool_config::PyPoolConfig;
 use crate::pool_manager::{ConnectionPool, ensure_pool_initialized_with_auth};
+use crate::query_log::LoggedParams;
+use crate::sql_placeholders::renumber_placeholders;
+use crate::tls_info::ObservedStream;
+use crate::type_mapping::DecodeOptions;
 use crate::types::{create_connection_error, create_sql_error};
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
@@ -15,7 +22,6 @@ use pyo3_async_runtimes::tokio::future_into_py;
 use smallvec::SmallVec;
 use std::sync::Arc;
 use tiberius::Config;
-use tokio::net::TcpStream;
 use tokio::sync::RwLock;
 use tokio_util::compat::TokioAsyncWriteCompatExt;
 
@@ -59,7 +65,7 @@ pub fn parse_batch_items<'p>(
             )));
         }
 
-        batch_items.push((sql, fast_params));
+        batch_items.push((rewrite_json_lists(sql, &fast_params), fast_params));
     }
 
     Ok(batch_items)
@@ -68,7 +74,7 @@ pub fn parse_batch_items<'p>(
 /// Internal helper: Execute batch commands on an existing connection without transaction management.
 /// Used by both Connection (with automatic transaction) and Transaction (with manual control).
 pub async fn execute_batch_on_connection(
-    conn: &mut tiberius::Client<tokio_util::compat::Compat<tokio::net::TcpStream>>,
+    conn: &mut tiberius::Client<tokio_util::compat::Compat<ObservedStream>>,
     batch_commands: Vec<(String, SmallVec<[FastParameter; 16]>)>,
 ) -> PyResult<Vec<u64>> {
     let mut all_results = Vec::with_capacity(batch_commands.len());
@@ -97,7 +103,7 @@ pub async fn execute_batch_on_connection(
 /// Internal helper: Execute batch queries on an existing connection.
 /// Used by both Connection and Transaction classes.
 pub async fn query_batch_on_connection(
-    conn: &mut tiberius::Client<tokio_util::compat::Compat<tokio::net::TcpStream>>,
+    conn: &mut tiberius::Client<tokio_util::compat::Compat<ObservedStream>>,
     batch_queries: Vec<(String, SmallVec<[FastParameter; 16]>)>,
 ) -> PyResult<Vec<Vec<tiberius::Row>>> {
     let mut all_results = Vec::with_capacity(batch_queries.len());
@@ -130,106 +136,136 @@ pub fn execute_batch<'p>(
     config: Arc<Config>,
     azure_credential: Option<Arc<PyAzureCredential>>,
     py: Python<'p>,
-    commands: &Bound<'p, PyList>,
+    batch_commands: Vec<(String, SmallVec<[FastParameter; 16]>)>,
+    trace: BatchTrace,
 ) -> PyResult<Bound<'p, PyAny>> {
-    let batch_commands = parse_batch_items(commands, py)?;
-
     future_into_py(py, async move {
-        // ── Safety: dedicated connection, not a pooled one ─────────────────────────
-        //
-        // execute_batch wraps all commands in a single BEGIN / COMMIT transaction.
-        // If the caller's coroutine is cancelled (e.g. asyncio.Task.cancel()) while
-        // the transaction is open, the Rust future is dropped.  With a *pooled*
-        // connection the guard would silently return the connection to the pool with
-        // an open BEGIN TRANSACTION, corrupting the state seen by the next caller.
-        //
-        // By using a *dedicated* TCP connection instead:
-        //   • If the future is dropped, the TCP socket is closed by the OS.
-        //   • SQL Server detects the broken connection and automatically rolls back.
-        //   • The shared pool is never touched, so no poisoning is possible.
-        //
-        // The cost (one extra TCP + TDS handshake per batch call) is acceptable
-        // because batch operations are inherently heavy and latency-tolerant.
-        // ───────────────────────────────────────────────────────────────────────────
-
-        let tcp = TcpStream::connect(config.get_addr())
+        let all_results = execute_batch_async(config, azure_credential, &trace, batch_commands)
             .await
-            .map_err(|e| create_connection_error(format!("Failed to connect to server: {}", e)))?;
-
-        // Disable Nagle — same rationale as pool_manager.rs and transaction.rs.
-        tcp.set_nodelay(true)
-            .map_err(|e| create_connection_error(format!("Failed to set TCP_NODELAY: {}", e)))?;
+            .map_err(|e| trace.tag(e))?;
 
-        // Apply Azure token (or leave config auth as-is for SQL / Windows auth).
-        let mut auth_config = (*config).clone();
-        if let Some(ref cred) = azure_credential {
-            let auth_method = cred
-                .to_auth_method()
-                .await
-                .map_err(|e| create_connection_error(format!("Authentication failed: {}", e)))?;
-            auth_config.authentication(auth_method);
-        }
+        Python::attach(|py| {
+            let py_list = PyList::new(py, all_results)?;
+            Ok(py_list.into_any().unbind())
+        })
+    })
+}
 
-        let mut conn = tiberius::Client::connect(auth_config, tcp.compat_write())
+/// Run `batch_commands` in one transaction on a dedicated connection.
+async fn execute_batch_async(
+    config: Arc<Config>,
+    azure_credential: Option<Arc<PyAzureCredential>>,
+    trace: &BatchTrace,
+    batch_commands: Vec<(String, SmallVec<[FastParameter; 16]>)>,
+) -> PyResult<Vec<u64>> {
+    // ── Safety: dedicated connection, not a pooled one ─────────────────────────
+    //
+    // execute_batch wraps all commands in a single BEGIN / COMMIT transaction.
+    // If the caller's coroutine is cancelled (e.g. asyncio.Task.cancel()) while
+    // the transaction is open, the Rust future is dropped.  With a *pooled*
+    // connection the guard would silently return the connection to the pool with
+    // an open BEGIN TRANSACTION, corrupting the state seen by the next caller.
+    //
+    // By using a *dedicated* TCP connection instead:
+    //   • If the future is dropped, the TCP socket is closed by the OS.
+    //   • SQL Server detects the broken connection and automatically rolls back.
+    //   • The shared pool is never touched, so no poisoning is possible.
+    //
+    // The cost (one extra TCP + TDS handshake per batch call) is acceptable
+    // because batch operations are inherently heavy and latency-tolerant.
+    // ───────────────────────────────────────────────────────────────────────────
+
+    let tcp = crate::tcp_connect::connect(&config.get_addr())
+        .await
+        .map_err(|e| create_connection_error(format!("Failed to connect to server: {}", e)))?;
+
+    // Disable Nagle — same rationale as pool_manager.rs and transaction.rs.
+    tcp.set_nodelay(true)
+        .map_err(|e| create_connection_error(format!("Failed to set TCP_NODELAY: {}", e)))?;
+
+    // Apply Azure token (or leave config auth as-is for SQL / Windows auth).
+    let mut auth_config = (*config).clone();
+    if let Some(ref cred) = azure_credential {
+        let auth_method = cred
+            .to_auth_method()
             .await
-            .map_err(|e| create_sql_error(e, "Failed to connect for batch execution"))?;
+            .map_err(|e| create_connection_error(format!("Authentication failed: {}", e)))?;
+        auth_config.authentication(auth_method);
+    }
 
-        conn.simple_query("BEGIN TRANSACTION")
-            .await
-            .map_err(|e| create_sql_error(e, "Failed to start transaction"))?;
-
-        let all_results = match execute_batch_on_connection(&mut conn, batch_commands).await {
-            Ok(results) => results,
-            Err(e) => {
-                // Best-effort rollback; ignore secondary errors.
-                let _ = conn.simple_query("ROLLBACK TRANSACTION").await;
-                return Err(e);
+    let tcp = ObservedStream::new(tcp);
+    let mut conn = match tiberius::Client::connect(auth_config, tcp.compat_write()).await {
+        Ok(conn) => conn,
+        Err(e) => {
+            if let Some(ref cred) = azure_credential {
+                cred.discard_refused_token(&e).await;
             }
-        };
+            return Err(create_sql_error(e, "Failed to connect for batch execution"));
+        }
+    };
+
+    trace.set_context_info(&mut conn).await?;
+    conn.simple_query("BEGIN TRANSACTION")
+        .await
+        .map_err(|e| create_sql_error(e, "Failed to start transaction"))?;
+
+    let all_results = match execute_batch_on_connection(&mut conn, batch_commands).await {
+        Ok(results) => results,
+        Err(e) => {
+            // Best-effort rollback; ignore secondary errors.
+            let _ = conn.simple_query("ROLLBACK TRANSACTION").await;
+            return Err(e);
+        }
+    };
 
-        conn.simple_query("COMMIT TRANSACTION")
-            .await
-            .map_err(|e| create_sql_error(e, "Failed to commit batch transaction"))?;
+    conn.simple_query("COMMIT TRANSACTION")
+        .await
+        .map_err(|e| create_sql_error(e, "Failed to commit batch transaction"))?;
 
-        // conn drops here — TCP connection closed cleanly.
-        // On future cancellation the OS closes the socket; SQL Server rolls back.
+    // conn drops here — TCP connection closed cleanly.
+    // On future cancellation the OS closes the socket; SQL Server rolls back.
 
-        Python::attach(|py| {
-            let py_list = PyList::new(py, all_results)?;
-            Ok(py_list.into_any().unbind())
-        })
-    })
+    Ok(all_results)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn query_batch<'p>(
     pool: Arc<RwLock<Option<ConnectionPool>>>,
     config: Arc<Config>,
     pool_config: PyPoolConfig,
     azure_credential: Option<Arc<PyAzureCredential>>,
+    decode_options: DecodeOptions,
     py: Python<'p>,
-    queries: &Bound<'p, PyList>,
+    batch_queries: Vec<(String, SmallVec<[FastParameter; 16]>)>,
+    trace: BatchTrace,
 ) -> PyResult<Bound<'p, PyAny>> {
-    let batch_queries = parse_batch_items(queries, py)?;
-
     let pool = Arc::clone(&pool);
     let config = Arc::clone(&config);
     let pool_config = pool_config.clone();
 
     future_into_py(py, async move {
-        let pool_ref =
-            ensure_pool_initialized_with_auth(pool, config, &pool_config, azure_credential)
-                .await?;
+        let all_results = async {
+            let pool_ref =
+                ensure_pool_initialized_with_auth(pool, config, &pool_config, azure_credential)
+                    .await?;
 
-        let mut conn = pool_ref.get().await.map_err(|e| {
-            create_connection_error(format!("Failed to get connection from pool: {}", e))
-        })?;
+            let mut conn = pool_ref.checkout().await?;
 
-        let all_results = query_batch_on_connection(&mut conn, batch_queries).await?;
+            conn.request(async |client| {
+                trace.set_context_info(client).await?;
+                query_batch_on_connection(client, batch_queries).await
+            })
+            .await
+        }
+        .await
+        .map_err(|e| trace.tag(e))?;
 
         Python::attach(|py| -> PyResult<Py<PyAny>> {
             let mut py_results = Vec::with_capacity(all_results.len());
             for result in all_results {
-                let query_stream = crate::types::PyQueryStream::from_tiberius_rows(result, py)?;
+                let query_stream =
+                    crate::types::PyQueryStream::from_tiberius_rows(result, decode_options, py)?
+                        .with_execution_id(trace.execution_id.clone());
                 let py_result = Py::new(py, query_stream)?;
                 py_results.push(py_result.into_any());
             }
@@ -239,6 +275,176 @@ pub fn query_batch<'p>(
     })
 }
 
+/// Maximum number of parameters SQL Server accepts in a single request.
+const MAX_REQUEST_PARAMETERS: usize = 2100;
+
+/// A buffered unit of work: statements queued with `add()` are sent together by
+/// `Connection.apply()` as one transaction in a single round trip.
+#[pyclass(name = "Batch")]
+#[derive(Default)]
+pub struct PyBatch {
+    items: Vec<(String, SmallVec<[FastParameter; 16]>)>,
+    param_count: usize,
+}
+
+impl PyBatch {
+    /// SQL of each queued statement, in order.
+    pub(crate) fn statements(&self) -> impl Iterator<Item = &str> {
+        self.items.iter().map(|(sql, _)| sql.as_str())
+    }
+
+    /// Each queued statement with its own parameters, for the query log.
+    pub(crate) fn logged_statements(&self) -> impl Iterator<Item = (&str, LoggedParams<'_>)> {
+        self.items
+            .iter()
+            .map(|(sql, params)| (sql.as_str(), LoggedParams::Positional(params)))
+    }
+}
+
+#[pymethods]
+impl PyBatch {
+    #[new]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a statement. Parameters are converted immediately, so later changes
+    /// to the Python objects do not affect the batch.
+    #[pyo3(signature = (sql, params = None))]
+    pub fn add(
+        &mut self,
+        py: Python<'_>,
+        sql: String,
+        params: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<()> {
+        let fast_params = match params {
+            Some(p) if !p.is_none() => convert_parameters_to_fast(Some(p), py)?,
+            _ => SmallVec::new(),
+        };
+
+        // Placeholders are renumbered when the batch is applied, so every statement
+        // shares the request-wide parameter limit.
+        let total = self.param_count + fast_params.len();
+        if total > MAX_REQUEST_PARAMETERS {
+            return Err(PyValueError::new_err(format!(
+                "Batch exceeds SQL Server parameter limit: {} parameters queued, maximum is 2,100",
+                total
+            )));
+        }
+
+        self.param_count = total;
+        self.items.push((rewrite_json_lists(sql, &fast_params), fast_params));
+        Ok(())
+    }
+
+    /// Remove all queued statements.
+    pub fn clear(&mut self) {
+        self.items.clear();
+        self.param_count = 0;
+    }
+
+    /// Total number of parameters queued across all statements.
+    #[getter]
+    pub fn param_count(&self) -> usize {
+        self.param_count
+    }
+
+    pub fn __len__(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!(
+            "Batch(statements={}, params={})",
+            self.items.len(),
+            self.param_count
+        )
+    }
+}
+
+impl PyBatch {
+    /// Combine the queued statements into a single T-SQL request wrapped in a
+    /// transaction, renumbering each statement's `@Pn` placeholders so that they
+    /// index into the concatenated parameter list.
+    fn build_request(&self) -> PyResult<(String, Vec<FastParameter>)> {
+        let mut sql = String::with_capacity(
+            128 + self.items.iter().map(|(s, _)| s.len() + 2).sum::<usize>(),
+        );
+        let mut params = Vec::with_capacity(self.param_count);
+
+        sql.push_str("SET XACT_ABORT ON;\nBEGIN TRY\nBEGIN TRANSACTION;\n");
+        for (index, (stmt, stmt_params)) in self.items.iter().enumerate() {
+            let renumbered = renumber_placeholders(stmt, params.len(), stmt_params.len())
+                .map_err(|e| PyValueError::new_err(format!("Batch item {}: {}", index, e)))?;
+            // Trailing semicolons are dropped so the separator never produces an empty
+            // statement; the newline also terminates a trailing `--` comment.
+            sql.push_str(renumbered.trim_end_matches(|c: char| c == ';' || c.is_whitespace()));
+            sql.push_str(";\n");
+            params.extend(stmt_params.iter().cloned());
+        }
+        sql.push_str(
+            "COMMIT TRANSACTION;\nEND TRY\nBEGIN CATCH\nIF @@TRANCOUNT > 0 ROLLBACK TRANSACTION;\nTHROW;\nEND CATCH",
+        );
+
+        Ok((sql, params))
+    }
+}
+
+/// Send every statement queued in `batch` as one transaction in one round trip and
+/// return the total number of affected rows.
+pub fn apply_batch<'p>(
+    pool: Arc<RwLock<Option<ConnectionPool>>>,
+    config: Arc<Config>,
+    pool_config: PyPoolConfig,
+    azure_credential: Option<Arc<PyAzureCredential>>,
+    py: Python<'p>,
+    batch: &PyBatch,
+    trace: BatchTrace,
+) -> PyResult<Bound<'p, PyAny>> {
+    if batch.items.is_empty() {
+        return future_into_py(py, async move { Ok(0u64) });
+    }
+
+    let (sql, params) = batch.build_request()?;
+    // Sent in the same request, so the batch stays a single round trip.
+    let sql = match &trace.context_info {
+        Some(context_info) => context_info.clone() + &sql,
+        None => sql,
+    };
+
+    future_into_py(py, async move {
+        // Unlike execute_batch this uses a pooled connection: BEGIN, the statements
+        // and COMMIT/ROLLBACK travel in one request, so there is no await point at
+        // which a cancelled future could leave a transaction open between round
+        // trips.  The CATCH block rolls back runtime errors server-side; the explicit
+        // rollback below covers errors (e.g. deferred name resolution) that abort the
+        // batch before CATCH can run.
+        let pool_ref =
+            ensure_pool_initialized_with_auth(pool, config, &pool_config, azure_credential)
+                .await
+                .map_err(|e| trace.tag(e))?;
+
+        let mut conn = pool_ref.checkout().await.map_err(|e| trace.tag(e))?;
+
+        let tiberius_params = params_as_sql_refs(&params);
+        conn.request(async |client| {
+            let result = match client.execute(sql, &tiberius_params).await {
+                Ok(result) => result,
+                Err(e) => {
+                    // Best-effort rollback; ignore secondary errors.
+                    let _ = client
+                        .simple_query("IF @@TRANCOUNT > 0 ROLLBACK TRANSACTION")
+                        .await;
+                    return Err(create_sql_error(e, "Batch apply failed"));
+                }
+            };
+            Ok(result.rows_affected().iter().sum::<u64>())
+        })
+        .await
+        .map_err(|e| trace.tag(e))
+    })
+}
+
 /// Wraps a single SQL Server identifier part in square brackets and escapes `]` as `]]`.
 ///
 /// Returns `Err` if `part` contains a null byte (`\x00`).  Null bytes are the only
@@ -247,7 +453,7 @@ pub fn query_batch<'p>(
 /// and produce unintended SQL.  All other Unicode characters — including right-to-left
 /// override codepoints (U+202E etc.) — are inert inside `[...]` and require no special
 /// handling because SQL Server parses bracket-quoted names literally at the byte level.
-fn quote_identifier_part(part: &str) -> PyResult<String> {
+pub(crate) fn quote_identifier_part(part: &str) -> PyResult<String> {
     if part.contains('\x00') {
         return Err(PyValueError::new_err(
             "Identifier contains a null byte (\\x00), which is not allowed in SQL Server identifiers",
@@ -275,7 +481,7 @@ fn quote_identifier_part(part: &str) -> PyResult<String> {
 ///
 /// Returns `Err` (propagated from [`quote_identifier_part`]) if any identifier part
 /// contains a null byte.
-fn quote_identifier(name: &str) -> PyResult<String> {
+pub(crate) fn quote_identifier(name: &str) -> PyResult<String> {
     let parts: Vec<&str> = name.split('.').collect();
     let mut result = String::with_capacity(name.len() + parts.len() * 2);
     for (i, part) in parts.iter().enumerate() {
@@ -310,13 +516,21 @@ fn fix_bulk_null_types(flat_data: &mut [FastParameter], col_count: usize) {
         let null_type = (0..row_count)
             .map(|row| &flat_data[row * col_count + col])
             .find_map(|p| match p {
-                FastParameter::String(_) => Some(TypedNull::String),
+                FastParameter::String(_) | FastParameter::JsonList(_) => {
+                    Some(TypedNull::String)
+                }
+                FastParameter::Xml(_) => Some(TypedNull::Xml),
                 FastParameter::I64(_) => Some(TypedNull::I64),
                 FastParameter::F64(_) => Some(TypedNull::F64),
+                FastParameter::Decimal(_) => Some(TypedNull::Numeric),
+                FastParameter::Guid(_) => Some(TypedNull::Guid),
                 FastParameter::Bool(_) => Some(TypedNull::Bit),
-                FastParameter::Bytes(_) => Some(TypedNull::Binary),
+                FastParameter::Bytes(_) | FastParameter::Buffer(_) => Some(TypedNull::Binary),
                 FastParameter::Date(_) => Some(TypedNull::Date),
                 FastParameter::DateTime(_) => Some(TypedNull::DateTime),
+                FastParameter::SmallDateTime(_) => Some(TypedNull::SmallDateTime),
+                FastParameter::Time(_) => Some(TypedNull::Time),
+                FastParameter::DateTimeOffset(_) => Some(TypedNull::DateTimeOffset),
                 FastParameter::Null(_) => None,
             })
             .unwrap_or(TypedNull::String); // all-null column → nvarchar null is safe
@@ -331,16 +545,21 @@ fn fix_bulk_null_types(flat_data: &mut [FastParameter], col_count: usize) {
     }
 }
 
-pub fn bulk_insert<'p>(
-    pool: Arc<RwLock<Option<ConnectionPool>>>,
-    config: Arc<Config>,
-    pool_config: PyPoolConfig,
-    azure_credential: Option<Arc<PyAzureCredential>>,
-    py: Python<'p>,
+/// The rows of a `bulk_insert()` call, converted while the GIL is held and split
+/// into chunks of at most 2,000 parameters, one multi-row INSERT each.
+pub struct BulkInsertRows {
     table_name: String,
     columns: Vec<String>,
-    data_rows: &Bound<'p, PyList>,
-) -> PyResult<Bound<'p, PyAny>> {
+    chunks: Vec<Vec<FastParameter>>,
+    verify: Option<BulkVerify>,
+}
+
+pub fn prepare_bulk_insert(
+    table_name: String,
+    columns: Vec<String>,
+    data_rows: &Bound<'_, PyList>,
+    verify_key: Option<String>,
+) -> PyResult<BulkInsertRows> {
     if columns.is_empty() {
         return Err(PyValueError::new_err(
             "At least one column must be specified",
@@ -395,80 +614,186 @@ pub fn bulk_insert<'p>(
         chunks.push(current_chunk);
     }
 
-    future_into_py(py, async move {
-        let pool_ref =
-            ensure_pool_initialized_with_auth(pool, config, &pool_config, azure_credential)
-                .await?;
+    let verify = verify_key
+        .map(|key| BulkVerify::new(&key, &columns, data_rows))
+        .transpose()?;
 
-        let mut conn = pool_ref
-            .get()
-            .await
-            .map_err(|e| create_connection_error(format!("Pool error: {}", e)))?;
+    Ok(BulkInsertRows {
+        table_name,
+        columns,
+        chunks,
+        verify,
+    })
+}
 
-        let mut total_affected = 0u64;
+impl BulkInsertRows {
+    /// The statement logged for the load, without its generated VALUES lists.
+    pub fn logged_sql(&self) -> PyResult<String> {
+        logged_insert_sql("INSERT INTO", &self.table_name, &self.columns)
+    }
+}
 
-        // Quote identifiers to prevent SQL injection (bracket-quote per SQL Server rules).
-        // Returns Err if any name contains a null byte.
-        let quoted_table = quote_identifier(&table_name)?;
-        let columns_sql = columns
-            .iter()
-            .map(|c| quote_identifier(c))
-            .collect::<PyResult<Vec<_>>>()?
-            .join(", ");
-
-        // Drain chunks via into_iter: each Vec<FastParameter> is moved out and
-        // dropped at the end of its loop body, freeing memory progressively
-        // instead of holding all rows alive until the final query completes.
-        for chunk in chunks {
-            let row_count_in_batch = chunk.len() / col_count;
-
-            // Optimize: Use String with pre-allocated capacity instead of format!
-            let mut sql = String::with_capacity(100 + row_count_in_batch * (col_count * 5));
-            sql.push_str("INSERT INTO ");
-            sql.push_str(&quoted_table);
-            sql.push_str(" (");
-            sql.push_str(&columns_sql);
-            sql.push_str(") VALUES ");
-
-            // Optimize: Build value placeholders more efficiently
-            for r in 0..row_count_in_batch {
-                if r > 0 {
+/// `<verb> <table> (<columns>)`, quoted, for the query log.
+pub(crate) fn logged_insert_sql(
+    verb: &str,
+    table_name: &str,
+    columns: &[String],
+) -> PyResult<String> {
+    let columns = columns
+        .iter()
+        .map(|c| quote_identifier(c))
+        .collect::<PyResult<Vec<_>>>()?;
+    Ok(format!(
+        "{} {} ({})",
+        verb,
+        quote_identifier(table_name)?,
+        columns.join(", ")
+    ))
+}
+
+/// Internal helper: Insert prepared rows on an existing connection.
+/// Used by Connection, PinnedConnection and Transaction; the latter two can load a
+/// #temp table created earlier in the same session.
+pub async fn bulk_insert_on_connection(
+    conn: &mut tiberius::Client<tokio_util::compat::Compat<ObservedStream>>,
+    rows: BulkInsertRows,
+) -> PyResult<Py<PyAny>> {
+    let BulkInsertRows {
+        table_name,
+        columns,
+        chunks,
+        verify,
+    } = rows;
+    let col_count = columns.len();
+    let mut total_affected = 0u64;
+
+    // Quote identifiers to prevent SQL injection (bracket-quote per SQL Server rules).
+    // Returns Err if any name contains a null byte.
+    let quoted_table = quote_identifier(&table_name)?;
+    let columns_sql = columns
+        .iter()
+        .map(|c| quote_identifier(c))
+        .collect::<PyResult<Vec<_>>>()?
+        .join(", ");
+
+    // Drain chunks via into_iter: each Vec<FastParameter> is moved out and
+    // dropped at the end of its loop body, freeing memory progressively
+    // instead of holding all rows alive until the final query completes.
+    for chunk in chunks {
+        let row_count_in_batch = chunk.len() / col_count;
+
+        // Optimize: Use String with pre-allocated capacity instead of format!
+        let mut sql = String::with_capacity(100 + row_count_in_batch * (col_count * 5));
+        sql.push_str("INSERT INTO ");
+        sql.push_str(&quoted_table);
+        sql.push_str(" (");
+        sql.push_str(&columns_sql);
+        sql.push_str(") VALUES ");
+
+        // Optimize: Build value placeholders more efficiently
+        for r in 0..row_count_in_batch {
+            if r > 0 {
+                sql.push(',');
+            }
+            sql.push('(');
+            for c in 1..=col_count {
+                if c > 1 {
                     sql.push(',');
                 }
-                sql.push('(');
-                for c in 1..=col_count {
-                    if c > 1 {
-                        sql.push(',');
-                    }
-                    sql.push('@');
-                    sql.push('P');
-                    // Optimized: write integer directly into pre-allocated buffer
-                    let param_num = (r * col_count) + c;
-                    let _ = write!(sql, "{}", param_num);
-                }
-                sql.push(')');
+                sql.push('@');
+                sql.push('P');
+                // Optimized: write integer directly into pre-allocated buffer
+                let param_num = (r * col_count) + c;
+                let _ = write!(sql, "{}", param_num);
             }
+            sql.push(')');
+        }
 
-            // Use SmallVec to avoid heap allocation for small parameter sets
-            let mut params: SmallVec<[&dyn tiberius::ToSql; 128]> =
-                SmallVec::with_capacity(chunk.len());
-            for p in &chunk {
-                params.push(p as &dyn tiberius::ToSql);
-            }
+        // Use SmallVec to avoid heap allocation for small parameter sets
+        let mut params: SmallVec<[&dyn tiberius::ToSql; 128]> =
+            SmallVec::with_capacity(chunk.len());
+        for p in &chunk {
+            params.push(p as &dyn tiberius::ToSql);
+        }
 
-            let result = conn
-                .execute(sql, &params)
-                .await
-                .map_err(|e| create_sql_error(e, "Batch execution failed"))?;
+        let result = conn
+            .execute(sql, &params)
+            .await
+            .map_err(|e| create_sql_error(e, "Batch execution failed"))?;
 
-            total_affected += result.rows_affected().iter().sum::<u64>();
-            // `chunk` is dropped here — its FastParameter memory is freed before
-            // the next batch is sent.
-        }
+        total_affected += result.rows_affected().iter().sum::<u64>();
+        // `chunk` is dropped here — its FastParameter memory is freed before
+        // the next batch is sent.
+    }
 
-        Python::attach(|py| {
-            let res = total_affected.into_pyobject(py)?;
-            Ok(res.into_any().unbind())
-        })
+    if let Some(verify) = verify {
+        return verify.run(conn, &table_name, &columns, total_affected).await;
+    }
+    Python::attach(|py| {
+        let res = total_affected.into_pyobject(py)?;
+        Ok(res.into_any().unbind())
     })
 }
+
+#[allow(clippy::too_many_arguments)]
+pub fn bulk_insert<'p>(
+    pool: Arc<RwLock<Option<ConnectionPool>>>,
+    config: Arc<Config>,
+    pool_config: PyPoolConfig,
+    azure_credential: Option<Arc<PyAzureCredential>>,
+    py: Python<'p>,
+    rows: BulkInsertRows,
+    trace: BatchTrace,
+) -> PyResult<Bound<'p, PyAny>> {
+    future_into_py(py, async move {
+        async {
+            let pool_ref =
+                ensure_pool_initialized_with_auth(pool, config, &pool_config, azure_credential)
+                    .await?;
+
+            let mut conn = pool_ref.checkout().await?;
+            conn.request(async |client| {
+                trace.set_context_info(client).await?;
+                bulk_insert_on_connection(client, rows).await
+            })
+            .await
+        }
+        .await
+        .map_err(|e| trace.tag(e))
+    })
+}
+
+/// A `bulk_insert()` call on a pinned or transaction connection, prepared for
+/// either multi-row INSERT statements or a native bulk load.
+pub enum BulkRequest {
+    Statements(BulkInsertRows),
+    BulkCopy(BulkLoadRows),
+}
+
+impl BulkRequest {
+    pub fn prepare(
+        table_name: String,
+        columns: Vec<String>,
+        data_rows: &Bound<'_, PyList>,
+        bulk_copy: bool,
+        verify_key: Option<String>,
+    ) -> PyResult<Self> {
+        if bulk_copy {
+            prepare_bulk_load(table_name, columns, data_rows, verify_key).map(Self::BulkCopy)
+        } else {
+            prepare_bulk_insert(table_name, columns, data_rows, verify_key).map(Self::Statements)
+        }
+    }
+
+    /// Run the load on `conn`; see [`bulk_load_on_connection`] for `interrupted`.
+    pub async fn run(
+        self,
+        conn: &mut tiberius::Client<tokio_util::compat::Compat<ObservedStream>>,
+        interrupted: &mut bool,
+    ) -> PyResult<Py<PyAny>> {
+        match self {
+            Self::Statements(rows) => bulk_insert_on_connection(conn, rows).await,
+            Self::BulkCopy(rows) => bulk_load_on_connection(conn, rows, interrupted).await,
+        }
+    }
+}
@@ -0,0 +1,673 @@
+//! Native bulk load (`INSERT BULK`) for `Connection.bulk_insert(..., bulk_copy=True)`
+//! and `Connection.bulk_insert_arrow()` / `bulk_insert_dataframe()`.
+//!
+//! Rows are streamed to the server as TDS row tokens instead of being bound as
+//! parameters of generated INSERT statements, so there is no 2,100-parameter chunking
+//! and no per-statement compile.  The bulk load protocol takes one value for every
+//! insertable column of the table, in table order, encoded exactly as that column's
+//! type; the columns are therefore read from the catalog first and every value is
+//! converted to its column's type before the load starts.  Insertable columns that
+//! are not listed receive NULL, since `INSERT BULK` does not apply column defaults.
+
+use chrono::{NaiveDate, NaiveDateTime, Timelike};
+use pyo3::exceptions::{PyImportError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList, PySlice};
+use pyo3_async_runtimes::tokio::future_into_py;
+use std::borrow::Cow;
+use std::sync::Arc;
+use tiberius::numeric::Numeric;
+use tiberius::xml::XmlData;
+use tiberius::{ColumnData, Config, IntoSql, Row, TokenRow, Uuid};
+use tokio::sync::RwLock;
+
+use crate::arrow_import::arrow_table_rows;
+use crate::azure_auth::PyAzureCredential;
+use crate::batch::{logged_insert_sql, quote_identifier};
+use crate::bulk_verify::BulkVerify;
+use crate::execution_id::BatchTrace;
+use crate::parameter_conversion::{
+    FastParameter, buffer_bytes, python_to_fast_parameter, smalldatetime_epoch, to_smalldatetime,
+};
+use crate::pool_config::PyPoolConfig;
+use crate::pool_manager::{ConnectionPool, ensure_pool_initialized_with_auth};
+use crate::tls_info::ObservedStream;
+use crate::type_mapping::numeric_to_decimal_string;
+use crate::types::create_sql_error;
+
+/// Columns the bulk load expects values for, in table order: everything except
+/// identity, computed, rowversion, GENERATED ALWAYS and hidden columns.  `{catalog}`
+/// is `tempdb.` for temporary tables.
+const BULK_COLUMNS_SQL: &str = "\
+SELECT c.name, TYPE_NAME(c.system_type_id) AS type, c.scale \
+FROM {catalog}sys.columns c \
+WHERE c.object_id = OBJECT_ID(@P1) \
+  AND c.is_identity = 0 AND c.is_computed = 0 AND c.system_type_id <> 189 \
+  AND ISNULL(COLUMNPROPERTY(c.object_id, c.name, 'GeneratedAlwaysType'), 0) = 0 \
+  AND ISNULL(COLUMNPROPERTY(c.object_id, c.name, 'IsHidden'), 0) = 0 \
+ORDER BY c.column_id";
+
+/// Column types the bulk load can encode values for.
+#[derive(Clone, Copy)]
+enum BulkType {
+    Bit,
+    TinyInt,
+    SmallInt,
+    Int,
+    BigInt,
+    Real,
+    Float,
+    Decimal(u8),
+    Text,
+    Binary,
+    Date,
+    DateTime,
+    SmallDateTime,
+    DateTime2,
+    Guid,
+    Xml,
+}
+
+struct BulkColumn {
+    name: String,
+    type_name: String,
+    ty: BulkType,
+}
+
+impl BulkColumn {
+    fn from_row(row: &Row) -> PyResult<Self> {
+        let name = row.get::<&str, _>(0).unwrap_or_default().to_string();
+        let type_name = row
+            .get::<&str, _>(1)
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let scale = row.get::<u8, _>(2).unwrap_or(0);
+        let ty = match type_name.as_str() {
+            "bit" => BulkType::Bit,
+            "tinyint" => BulkType::TinyInt,
+            "smallint" => BulkType::SmallInt,
+            "int" => BulkType::Int,
+            "bigint" => BulkType::BigInt,
+            "real" => BulkType::Real,
+            "float" => BulkType::Float,
+            "decimal" | "numeric" => BulkType::Decimal(scale),
+            "char" | "varchar" | "nchar" | "nvarchar" => BulkType::Text,
+            "binary" | "varbinary" => BulkType::Binary,
+            "date" => BulkType::Date,
+            "datetime" => BulkType::DateTime,
+            "smalldatetime" => BulkType::SmallDateTime,
+            "datetime2" => BulkType::DateTime2,
+            "uniqueidentifier" => BulkType::Guid,
+            "xml" => BulkType::Xml,
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "Column '{}' has type {}, which bulk copy does not support; \
+                     use bulk_insert() without bulk_copy=True for this table",
+                    name, type_name
+                )));
+            }
+        };
+        Ok(BulkColumn {
+            name,
+            type_name,
+            ty,
+        })
+    }
+
+    fn null(&self) -> ColumnData<'static> {
+        match self.ty {
+            BulkType::Bit => ColumnData::Bit(None),
+            BulkType::TinyInt => ColumnData::U8(None),
+            BulkType::SmallInt => ColumnData::I16(None),
+            BulkType::Int => ColumnData::I32(None),
+            BulkType::BigInt => ColumnData::I64(None),
+            BulkType::Real => ColumnData::F32(None),
+            BulkType::Float => ColumnData::F64(None),
+            BulkType::Decimal(_) => ColumnData::Numeric(None),
+            BulkType::Text => ColumnData::String(None),
+            BulkType::Binary => ColumnData::Binary(None),
+            BulkType::Date => ColumnData::Date(None),
+            BulkType::DateTime => ColumnData::DateTime(None),
+            BulkType::SmallDateTime => ColumnData::SmallDateTime(None),
+            BulkType::DateTime2 => ColumnData::DateTime2(None),
+            BulkType::Guid => ColumnData::Guid(None),
+            BulkType::Xml => ColumnData::Xml(None),
+        }
+    }
+
+    /// Encode `value` as this column's type; the error names the value's kind.
+    fn convert(&self, value: FastParameter) -> Result<ColumnData<'static>, String> {
+        let out_of_range = || format!("value out of range for {}", self.type_name);
+        let invalid_text = |s: &str| format!("'{}' is not a valid {}", s, self.type_name);
+        // ISO 8601 text is accepted for temporal columns, as SQL Server would convert it.
+        let value = match (self.ty, value) {
+            (BulkType::Date, FastParameter::String(s)) => {
+                FastParameter::Date(parse_date_text(&s).ok_or_else(|| invalid_text(&s))?)
+            }
+            (
+                BulkType::DateTime | BulkType::SmallDateTime | BulkType::DateTime2,
+                FastParameter::String(s),
+            ) => FastParameter::DateTime(parse_datetime_text(&s).ok_or_else(|| invalid_text(&s))?),
+            (_, value) => value,
+        };
+        Ok(match (self.ty, value) {
+            (_, FastParameter::Null(_)) => self.null(),
+            (BulkType::Bit, FastParameter::Bool(b)) => ColumnData::Bit(Some(b)),
+            (BulkType::Bit, FastParameter::I64(i)) => ColumnData::Bit(Some(i != 0)),
+            (BulkType::TinyInt, FastParameter::I64(i)) => {
+                ColumnData::U8(Some(u8::try_from(i).map_err(|_| out_of_range())?))
+            }
+            (BulkType::SmallInt, FastParameter::I64(i)) => {
+                ColumnData::I16(Some(i16::try_from(i).map_err(|_| out_of_range())?))
+            }
+            (BulkType::Int, FastParameter::I64(i)) => {
+                ColumnData::I32(Some(i32::try_from(i).map_err(|_| out_of_range())?))
+            }
+            (BulkType::BigInt, FastParameter::I64(i)) => ColumnData::I64(Some(i)),
+            (BulkType::Real, FastParameter::F64(f)) => ColumnData::F32(Some(f as f32)),
+            (BulkType::Real, FastParameter::I64(i)) => ColumnData::F32(Some(i as f32)),
+            (BulkType::Float, FastParameter::F64(f)) => ColumnData::F64(Some(f)),
+            (BulkType::Float, FastParameter::I64(i)) => ColumnData::F64(Some(i as f64)),
+            (BulkType::Decimal(scale), FastParameter::I64(i)) => {
+                let value = (i as i128)
+                    .checked_mul(10i128.pow(scale as u32))
+                    .ok_or_else(out_of_range)?;
+                ColumnData::Numeric(Some(Numeric::new_with_scale(value, scale)))
+            }
+            (BulkType::Decimal(scale), FastParameter::F64(f)) => {
+                let scaled = (f * 10f64.powi(scale as i32)).round();
+                if !scaled.is_finite() || scaled.abs() >= 1e38 {
+                    return Err(out_of_range());
+                }
+                ColumnData::Numeric(Some(Numeric::new_with_scale(scaled as i128, scale)))
+            }
+            (BulkType::Decimal(scale), FastParameter::Decimal(n)) => {
+                let value = parse_decimal(&numeric_to_decimal_string(n), scale)
+                    .filter(|v| v.unsigned_abs() < 10u128.pow(38))
+                    .ok_or_else(out_of_range)?;
+                ColumnData::Numeric(Some(Numeric::new_with_scale(value, scale)))
+            }
+            (BulkType::Decimal(scale), FastParameter::String(s)) => {
+                let value = parse_decimal(&s, scale).ok_or_else(|| invalid_text(&s))?;
+                ColumnData::Numeric(Some(Numeric::new_with_scale(value, scale)))
+            }
+            (BulkType::Text, FastParameter::String(s) | FastParameter::Xml(s)) => {
+                ColumnData::String(Some(Cow::Owned(s)))
+            }
+            (BulkType::Binary, FastParameter::Bytes(b)) => ColumnData::Binary(Some(Cow::Owned(b))),
+            (BulkType::Binary, FastParameter::Buffer(b)) => {
+                ColumnData::Binary(Some(Cow::Owned(buffer_bytes(&b).to_vec())))
+            }
+            (BulkType::Date, FastParameter::Date(d)) => d.into_sql(),
+            (BulkType::DateTime, FastParameter::Date(d)) => legacy_datetime(midnight(d)),
+            (
+                BulkType::DateTime,
+                FastParameter::DateTime(dt) | FastParameter::SmallDateTime(dt),
+            ) => legacy_datetime(dt),
+            (BulkType::SmallDateTime, FastParameter::Date(d)) => smalldatetime(midnight(d))?,
+            (
+                BulkType::SmallDateTime,
+                FastParameter::DateTime(dt) | FastParameter::SmallDateTime(dt),
+            ) => smalldatetime(dt)?,
+            (BulkType::DateTime2, FastParameter::Date(d)) => midnight(d).into_sql(),
+            (
+                BulkType::DateTime2,
+                FastParameter::DateTime(dt) | FastParameter::SmallDateTime(dt),
+            ) => dt.into_sql(),
+            (BulkType::Guid, FastParameter::Guid(g)) => ColumnData::Guid(Some(g)),
+            (BulkType::Guid, FastParameter::String(s)) => ColumnData::Guid(Some(
+                Uuid::parse_str(&s).map_err(|_| format!("'{}' is not a valid GUID", s))?,
+            )),
+            (BulkType::Xml, FastParameter::String(s) | FastParameter::Xml(s)) => {
+                ColumnData::Xml(Some(Cow::Owned(XmlData::new(s))))
+            }
+            (_, other) => {
+                return Err(format!(
+                    "cannot store {} in a {} column",
+                    kind_name(&other),
+                    self.type_name
+                ));
+            }
+        })
+    }
+}
+
+fn kind_name(value: &FastParameter) -> &'static str {
+    match value {
+        FastParameter::Null(_) => "NULL",
+        FastParameter::Bool(_) => "bool",
+        FastParameter::I64(_) => "int",
+        FastParameter::F64(_) => "float",
+        FastParameter::Decimal(_) => "Decimal",
+        FastParameter::Guid(_) => "UUID",
+        FastParameter::String(_) => "str",
+        FastParameter::JsonList(_) => "json_list",
+        FastParameter::Xml(_) => "XML",
+        FastParameter::Bytes(_) => "bytes",
+        FastParameter::Buffer(_) => "buffer",
+        FastParameter::Date(_) => "date",
+        FastParameter::DateTime(_) | FastParameter::SmallDateTime(_) => "datetime",
+        FastParameter::Time(_) => "time",
+        FastParameter::DateTimeOffset(_) => "aware datetime",
+    }
+}
+
+fn midnight(date: NaiveDate) -> NaiveDateTime {
+    date.and_hms_opt(0, 0, 0).expect("midnight is a valid time")
+}
+
+/// DATETIME: days since 1900-01-01 and 1/300 s ticks since midnight.
+fn legacy_datetime(dt: NaiveDateTime) -> ColumnData<'static> {
+    const TICKS_PER_DAY: u64 = 300 * 86_400;
+    let mut days = (dt.date() - smalldatetime_epoch()).num_days() as i32;
+    let mut ticks = dt.num_seconds_from_midnight() as u64 * 300
+        + (dt.nanosecond() as u64 * 300 + 500_000_000) / 1_000_000_000;
+    if ticks >= TICKS_PER_DAY {
+        days += 1;
+        ticks -= TICKS_PER_DAY;
+    }
+    ColumnData::DateTime(Some(tiberius::time::DateTime::new(days, ticks as u32)))
+}
+
+fn smalldatetime(dt: NaiveDateTime) -> Result<ColumnData<'static>, String> {
+    let dt = to_smalldatetime(dt).map_err(|e| e.to_string())?;
+    let days = (dt.date() - smalldatetime_epoch()).num_days() as u16;
+    let minutes = (dt.hour() * 60 + dt.minute()) as u16;
+    Ok(ColumnData::SmallDateTime(Some(
+        tiberius::time::SmallDateTime::new(days, minutes),
+    )))
+}
+
+fn parse_date_text(text: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(text.trim(), "%Y-%m-%d").ok()
+}
+
+fn parse_datetime_text(text: &str) -> Option<NaiveDateTime> {
+    let text = text.trim();
+    [
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%Y-%m-%d %H:%M:%S%.f",
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%d %H:%M",
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+    .or_else(|| parse_date_text(text).map(midnight))
+}
+
+/// Parse decimal text into an integer scaled by `10^scale`, rounding half away from
+/// zero when the text has more fractional digits than the column.
+fn parse_decimal(text: &str, scale: u8) -> Option<i128> {
+    let text = text.trim();
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text.strip_prefix('+').unwrap_or(text)),
+    };
+    let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    if whole.is_empty() && fraction.is_empty()
+        || !whole
+            .chars()
+            .chain(fraction.chars())
+            .all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let mut value: i128 = 0;
+    let mut fraction_digits = fraction.chars();
+    for c in whole
+        .chars()
+        .chain((0..scale).map(|_| fraction_digits.next().unwrap_or('0')))
+    {
+        value = value
+            .checked_mul(10)?
+            .checked_add(c.to_digit(10)? as i128)?;
+    }
+    if fraction_digits.next().is_some_and(|c| c >= '5') {
+        value = value.checked_add(1)?;
+    }
+    Some(if negative { -value } else { value })
+}
+
+/// Catalog prefix and `OBJECT_ID` argument for `table`; temporary tables live in tempdb.
+fn catalog_lookup(table: &str) -> (&'static str, String) {
+    let object = table.rsplit('.').next().unwrap_or(table);
+    if object.starts_with('#') {
+        ("tempdb.", format!("tempdb..{}", object))
+    } else {
+        ("", table.to_string())
+    }
+}
+
+/// The rows of a `bulk_insert(..., bulk_copy=True)` call, converted to parameters
+/// while the GIL is held.
+pub struct BulkLoadRows {
+    table_name: String,
+    quoted_table: String,
+    columns: Vec<String>,
+    rows: Vec<Vec<FastParameter>>,
+    verify: Option<BulkVerify>,
+}
+
+pub fn prepare_bulk_load(
+    table_name: String,
+    columns: Vec<String>,
+    data_rows: &Bound<'_, PyList>,
+    verify_key: Option<String>,
+) -> PyResult<BulkLoadRows> {
+    if columns.is_empty() {
+        return Err(PyValueError::new_err(
+            "At least one column must be specified",
+        ));
+    }
+    let col_count = columns.len();
+
+    let mut rows: Vec<Vec<FastParameter>> = Vec::with_capacity(data_rows.len());
+    for row in data_rows.iter() {
+        let row_list = row.cast::<PyList>()?;
+        if row_list.len() != col_count {
+            return Err(PyValueError::new_err(format!(
+                "Row has {} values but {} columns specified",
+                row_list.len(),
+                col_count
+            )));
+        }
+        rows.push(
+            row_list
+                .iter()
+                .map(|value| python_to_fast_parameter(&value))
+                .collect::<PyResult<_>>()?,
+        );
+    }
+
+    let verify = verify_key
+        .map(|key| BulkVerify::new(&key, &columns, data_rows))
+        .transpose()?;
+    let quoted_table = quote_identifier(&table_name)?;
+    Ok(BulkLoadRows {
+        table_name,
+        quoted_table,
+        columns,
+        rows,
+        verify,
+    })
+}
+
+impl BulkLoadRows {
+    /// The statement logged for the load, as the bulk load request names it.
+    pub fn logged_sql(&self) -> PyResult<String> {
+        logged_insert_sql("INSERT BULK", &self.table_name, &self.columns)
+    }
+}
+
+/// Internal helper: Stream prepared rows to `table_name` on an existing connection.
+/// Used by Connection, PinnedConnection and Transaction; the latter two can load a
+/// #temp table created earlier in the same session.  `interrupted` is set when the
+/// load failed part-way through the request, leaving the connection unusable.
+pub async fn bulk_load_on_connection(
+    conn: &mut tiberius::Client<tokio_util::compat::Compat<ObservedStream>>,
+    rows: BulkLoadRows,
+    interrupted: &mut bool,
+) -> PyResult<Py<PyAny>> {
+    let BulkLoadRows {
+        table_name,
+        quoted_table,
+        columns,
+        rows,
+        verify,
+    } = rows;
+
+    let (catalog, object_name) = catalog_lookup(&table_name);
+    let sql = BULK_COLUMNS_SQL.replace("{catalog}", catalog);
+    let object_param = FastParameter::String(object_name);
+    let table_columns = conn
+        .query(sql.as_str(), &[&object_param as &dyn tiberius::ToSql])
+        .await
+        .map_err(|e| create_sql_error(e, "Failed to read table columns"))?
+        .into_first_result()
+        .await
+        .map_err(|e| create_sql_error(e, "Failed to read table columns"))?
+        .iter()
+        .map(BulkColumn::from_row)
+        .collect::<PyResult<Vec<_>>>()?;
+    if table_columns.is_empty() {
+        return Err(PyValueError::new_err(format!(
+            "Table '{}' not found or has no insertable columns",
+            table_name
+        )));
+    }
+
+    // Position of each table column in the caller's column list, if listed.
+    let mut sources: Vec<Option<usize>> = vec![None; table_columns.len()];
+    for (index, name) in columns.iter().enumerate() {
+        let target = table_columns
+            .iter()
+            .position(|c| c.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| {
+                PyValueError::new_err(format!(
+                    "Column '{}' is not an insertable column of '{}'",
+                    name, table_name
+                ))
+            })?;
+        if sources[target].replace(index).is_some() {
+            return Err(PyValueError::new_err(format!(
+                "Column '{}' is listed more than once",
+                name
+            )));
+        }
+    }
+
+    // Convert everything before the load starts, so a bad value does not leave
+    // the connection in the middle of a bulk load.
+    let mut token_rows = Vec::with_capacity(rows.len());
+    for (row_index, row) in rows.into_iter().enumerate() {
+        let mut values: Vec<Option<FastParameter>> = row.into_iter().map(Some).collect();
+        let mut token_row = TokenRow::with_capacity(table_columns.len());
+        for (column, source) in table_columns.iter().zip(&sources) {
+            let value = match source.and_then(|i| values[i].take()) {
+                Some(value) => column.convert(value).map_err(|e| {
+                    PyValueError::new_err(format!(
+                        "Row {}, column '{}': {}",
+                        row_index, column.name, e
+                    ))
+                })?,
+                None => column.null(),
+            };
+            token_row.push(value);
+        }
+        token_rows.push(token_row);
+    }
+
+    let result = async {
+        let mut request = conn.bulk_insert(&quoted_table).await?;
+        for token_row in token_rows {
+            request.send(token_row).await?;
+        }
+        request.finalize().await
+    }
+    .await;
+
+    let total = match result {
+        Ok(result) => result.total(),
+        Err(e) => {
+            // The load may have stopped part-way through the request.
+            *interrupted = true;
+            return Err(create_sql_error(e, "Bulk copy failed"));
+        }
+    };
+    match verify {
+        Some(verify) => verify.run(conn, &table_name, &columns, total).await,
+        None => Python::attach(|py| Ok(total.into_pyobject(py)?.into_any().unbind())),
+    }
+}
+
+/// The rows of a `bulk_insert_arrow()` call, read out of the Arrow table.
+pub fn prepare_arrow_bulk_load(
+    table_name: String,
+    table: &Bound<'_, PyAny>,
+) -> PyResult<BulkLoadRows> {
+    let (columns, rows) = arrow_table_rows(table)?;
+    if columns.is_empty() {
+        return Err(PyValueError::new_err("Arrow table has no columns"));
+    }
+    let quoted_table = quote_identifier(&table_name)?;
+    Ok(BulkLoadRows {
+        table_name,
+        quoted_table,
+        columns,
+        rows,
+        verify: None,
+    })
+}
+
+/// Rows `start..start + chunk_size` of a `pandas.DataFrame`, converted through
+/// `pyarrow.Table.from_pandas()`, which maps NaN, NaT and `pd.NA` to NULL.
+fn dataframe_chunk(
+    table_name: &str,
+    frame: &Bound<'_, PyAny>,
+    start: usize,
+    chunk_size: usize,
+) -> PyResult<BulkLoadRows> {
+    let py = frame.py();
+    let pa = py.import("pyarrow").map_err(|_| {
+        PyImportError::new_err(
+            "bulk_insert_dataframe() requires pandas and pyarrow (pip install fastmssql[pandas])",
+        )
+    })?;
+    let chunk = frame
+        .getattr("iloc")?
+        .get_item(PySlice::new(py, start as isize, (start + chunk_size) as isize, 1))?;
+    let kwargs = PyDict::new(py);
+    kwargs.set_item("preserve_index", false)?;
+    let table = pa
+        .getattr("Table")?
+        .call_method("from_pandas", (chunk,), Some(&kwargs))?;
+    prepare_arrow_bulk_load(table_name.to_string(), &table)
+}
+
+/// The first chunk of a `bulk_insert_dataframe()` call, converted up front so a bad
+/// frame raises before connecting.
+pub fn prepare_dataframe_load(
+    table_name: &str,
+    frame: &Bound<'_, PyAny>,
+    chunk_size: usize,
+) -> PyResult<BulkLoadRows> {
+    if chunk_size == 0 {
+        return Err(PyValueError::new_err("chunk_size must be >= 1"));
+    }
+    dataframe_chunk(table_name, frame, 0, chunk_size)
+}
+
+/// Bulk load a `pandas.DataFrame` on one connection, `chunk_size` rows per load,
+/// starting with `first` from [`prepare_dataframe_load`].  Each later chunk is
+/// converted only when its turn comes, so a large frame is never held as parameters
+/// all at once.
+#[allow(clippy::too_many_arguments)]
+pub fn bulk_load_dataframe<'p>(
+    pool: Arc<RwLock<Option<ConnectionPool>>>,
+    config: Arc<Config>,
+    pool_config: PyPoolConfig,
+    azure_credential: Option<Arc<PyAzureCredential>>,
+    py: Python<'p>,
+    frame: &Bound<'p, PyAny>,
+    first: BulkLoadRows,
+    chunk_size: usize,
+    trace: BatchTrace,
+) -> PyResult<Bound<'p, PyAny>> {
+    let row_count = frame.len()?;
+    let table_name = first.table_name.clone();
+    let frame = frame.clone().unbind();
+
+    future_into_py(py, async move {
+        load_dataframe_chunks(
+            pool,
+            config,
+            pool_config,
+            azure_credential,
+            &table_name,
+            frame,
+            row_count,
+            first,
+            chunk_size,
+            &trace,
+        )
+        .await
+        .map_err(|e| trace.tag(e))
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn load_dataframe_chunks(
+    pool: Arc<RwLock<Option<ConnectionPool>>>,
+    config: Arc<Config>,
+    pool_config: PyPoolConfig,
+    azure_credential: Option<Arc<PyAzureCredential>>,
+    table_name: &str,
+    frame: Py<PyAny>,
+    row_count: usize,
+    first: BulkLoadRows,
+    chunk_size: usize,
+    trace: &BatchTrace,
+) -> PyResult<u64> {
+    let pool_ref =
+        ensure_pool_initialized_with_auth(pool, config, &pool_config, azure_credential).await?;
+    let mut conn = pool_ref.checkout().await?;
+    conn.request(async |client| trace.set_context_info(client).await)
+        .await?;
+    let mut total: u64 = 0;
+    let mut next = Some(first);
+    let mut start = 0;
+    while let Some(rows) = next.take() {
+        if rows.rows.is_empty() {
+            break;
+        }
+        let mut interrupted = false;
+        let result = conn
+            .request(async |client| bulk_load_on_connection(client, rows, &mut interrupted).await)
+            .await;
+        if interrupted {
+            conn.mark_broken();
+        }
+        let loaded = result?;
+        start += chunk_size;
+        next = Python::attach(|py| -> PyResult<_> {
+            total += loaded.extract::<u64>(py)?;
+            (start < row_count)
+                .then(|| dataframe_chunk(table_name, frame.bind(py), start, chunk_size))
+                .transpose()
+        })?;
+    }
+    Ok(total)
+}
+
+pub fn bulk_load<'p>(
+    pool: Arc<RwLock<Option<ConnectionPool>>>,
+    config: Arc<Config>,
+    pool_config: PyPoolConfig,
+    azure_credential: Option<Arc<PyAzureCredential>>,
+    py: Python<'p>,
+    rows: BulkLoadRows,
+    trace: BatchTrace,
+) -> PyResult<Bound<'p, PyAny>> {
+    future_into_py(py, async move {
+        async {
+            let pool_ref =
+                ensure_pool_initialized_with_auth(pool, config, &pool_config, azure_credential)
+                    .await?;
+            let mut conn = pool_ref.checkout().await?;
+            let mut interrupted = false;
+            let result = conn
+                .request(async |client| {
+                    trace.set_context_info(client).await?;
+                    bulk_load_on_connection(client, rows, &mut interrupted).await
+                })
+                .await;
+            if interrupted {
+                conn.mark_broken();
+            }
+            result
+        }
+        .await
+        .map_err(|e| trace.tag(e))
+    })
+}
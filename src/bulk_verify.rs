@@ -0,0 +1,125 @@
+//! Post-load verification for `Connection.bulk_insert(verify_key=...)`.
+//!
+//! The key range of the loaded rows is taken from the input before the load, and
+//! once it finishes the same connection counts and checksums the rows of the table
+//! in that range, so a pipeline can check the load without writing the SQL itself.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+
+use crate::batch::quote_identifier;
+use crate::parameter_conversion::{FastParameter, python_to_fast_parameter};
+use crate::tls_info::ObservedStream;
+use crate::types::create_sql_error;
+
+/// Key range of the rows about to be loaded; `None` when there are no rows.
+pub struct BulkVerify {
+    key_column: String,
+    range: Option<KeyRange>,
+}
+
+struct KeyRange {
+    low: Py<PyAny>,
+    high: Py<PyAny>,
+    low_param: FastParameter,
+    high_param: FastParameter,
+}
+
+impl BulkVerify {
+    /// Find `key_column` among `columns` and the smallest and largest of its values
+    /// in `data_rows`.  Keys are compared by Python, so they must be orderable.
+    pub fn new(key_column: &str, columns: &[String], data_rows: &Bound<PyList>) -> PyResult<Self> {
+        let index = columns
+            .iter()
+            .position(|c| c.eq_ignore_ascii_case(key_column))
+            .ok_or_else(|| {
+                PyValueError::new_err(format!(
+                    "verify_key '{}' is not one of the inserted columns",
+                    key_column
+                ))
+            })?;
+        let py = data_rows.py();
+        let keys = PyList::empty(py);
+        for row in data_rows.iter() {
+            let key = row.get_item(index)?;
+            if key.is_none() {
+                return Err(PyValueError::new_err(format!(
+                    "verify_key column '{}' has a NULL value",
+                    key_column
+                )));
+            }
+            keys.append(key)?;
+        }
+        let range = if keys.is_empty() {
+            None
+        } else {
+            let builtins = py.import("builtins")?;
+            let low = builtins.call_method1("min", (&keys,))?;
+            let high = builtins.call_method1("max", (&keys,))?;
+            Some(KeyRange {
+                low_param: python_to_fast_parameter(&low)?,
+                high_param: python_to_fast_parameter(&high)?,
+                low: low.unbind(),
+                high: high.unbind(),
+            })
+        };
+        Ok(BulkVerify {
+            key_column: key_column.to_string(),
+            range,
+        })
+    }
+
+    /// Count and checksum the rows of `table` in the loaded key range and report them
+    /// next to `rows_inserted`.  The checksum is `CHECKSUM_AGG(CHECKSUM(columns))`,
+    /// comparable with the same expression run against the source.
+    pub async fn run(
+        self,
+        conn: &mut tiberius::Client<tokio_util::compat::Compat<ObservedStream>>,
+        table: &str,
+        columns: &[String],
+        rows_inserted: u64,
+    ) -> PyResult<Py<PyAny>> {
+        let (row_count, checksum) = match &self.range {
+            Some(range) => {
+                let columns_sql = columns
+                    .iter()
+                    .map(|c| quote_identifier(c))
+                    .collect::<PyResult<Vec<_>>>()?
+                    .join(", ");
+                let key = quote_identifier(&self.key_column)?;
+                let sql = format!(
+                    "SELECT COUNT_BIG(*), CHECKSUM_AGG(CHECKSUM({})) FROM {} \
+                     WHERE {} >= @P1 AND {} <= @P2",
+                    columns_sql,
+                    quote_identifier(table)?,
+                    key,
+                    key
+                );
+                let params: [&dyn tiberius::ToSql; 2] = [&range.low_param, &range.high_param];
+                let row = conn
+                    .query(sql, &params)
+                    .await
+                    .map_err(|e| create_sql_error(e, "Bulk load verification failed"))?
+                    .into_row()
+                    .await
+                    .map_err(|e| create_sql_error(e, "Bulk load verification failed"))?;
+                let row_count = row.as_ref().and_then(|r| r.get::<i64, _>(0)).unwrap_or(0);
+                let checksum = row.as_ref().and_then(|r| r.get::<i32, _>(1));
+                (row_count as u64, checksum)
+            }
+            None => (0, None),
+        };
+
+        Python::attach(|py| {
+            let report = PyDict::new(py);
+            report.set_item("rows_inserted", rows_inserted)?;
+            report.set_item("row_count", row_count)?;
+            report.set_item("checksum", checksum)?;
+            report.set_item("key_min", self.range.as_ref().map(|r| r.low.clone_ref(py)))?;
+            report.set_item("key_max", self.range.as_ref().map(|r| r.high.clone_ref(py)))?;
+            report.set_item("verified", row_count == rows_inserted)?;
+            Ok(report.into_any().unbind())
+        })
+    }
+}
@@ -0,0 +1,119 @@
+//! Warning when a server presents a certificate that is about to expire.
+//!
+//! `fastmssql.set_certificate_expiry_warning(30)` makes every new connection check the
+//! certificate seen during its TLS handshake ([`tls_info`](crate::tls_info)); one that
+//! expires within 30 days raises a `CertificateExpiryWarning` through Python's
+//! `warnings` module, or is passed to the callback instead.  Each certificate is
+//! reported once per process, not once per pooled connection.  Certificates are only
+//! visible under TLS 1.2.
+
+use chrono::{DateTime, Utc};
+use pyo3::exceptions::PyTypeError;
+use pyo3::prelude::*;
+use std::collections::HashSet;
+use std::sync::{LazyLock, Mutex};
+
+use crate::tls_info::CertificateInfo;
+use crate::types::CertificateExpiryWarning;
+
+struct Settings {
+    days: i64,
+    callback: Option<Py<PyAny>>,
+}
+
+/// A certificate, by subject and expiry date.
+type CertificateKey = (String, DateTime<Utc>);
+
+static SETTINGS: Mutex<Option<Settings>> = Mutex::new(None);
+static REPORTED: LazyLock<Mutex<HashSet<CertificateKey>>> = LazyLock::new(Default::default);
+
+/// Warn (or call the callback) if `cert` expires within the configured window.
+pub fn check(cert: &CertificateInfo) {
+    let Some(not_after) = cert.not_after else {
+        return;
+    };
+    let Some(days) = SETTINGS
+        .lock()
+        .ok()
+        .and_then(|settings| settings.as_ref().map(|s| s.days))
+    else {
+        return;
+    };
+    let days_left = (not_after - Utc::now()).num_days();
+    if days_left >= days {
+        return;
+    }
+    if !REPORTED
+        .lock()
+        .is_ok_and(|mut reported| reported.insert((cert.subject.clone(), not_after)))
+    {
+        return;
+    }
+
+    Python::attach(|py| {
+        // Not holding the lock while Python code runs.
+        let callback = SETTINGS.lock().ok().and_then(|settings| {
+            settings
+                .as_ref()
+                .and_then(|s| s.callback.as_ref().map(|c| c.clone_ref(py)))
+        });
+        let result = match callback {
+            Some(callback) => callback
+                .call1(py, (cert.subject.as_str(), not_after, days_left))
+                .map(drop),
+            None => {
+                let message = if days_left < 0 {
+                    format!(
+                        "Server certificate {} expired on {}",
+                        cert.subject,
+                        not_after.format("%Y-%m-%d")
+                    )
+                } else {
+                    format!(
+                        "Server certificate {} expires in {} days, on {}",
+                        cert.subject,
+                        days_left,
+                        not_after.format("%Y-%m-%d")
+                    )
+                };
+                let category = py.get_type::<CertificateExpiryWarning>();
+                match std::ffi::CString::new(message) {
+                    Ok(message) => PyErr::warn(py, &category, &message, 1),
+                    Err(_) => Ok(()),
+                }
+            }
+        };
+        if let Err(e) = result {
+            e.write_unraisable(py, None);
+        }
+    });
+}
+
+/// Report server certificates that expire within `days` days: as a
+/// `CertificateExpiryWarning`, or by calling `callback(subject, not_after, days_left)`.
+/// `days=None` turns the check off.
+#[pyfunction]
+#[pyo3(signature = (days = 30, callback = None))]
+pub fn set_certificate_expiry_warning(
+    py: Python<'_>,
+    days: Option<u32>,
+    callback: Option<Py<PyAny>>,
+) -> PyResult<()> {
+    if let Some(callback) = &callback
+        && !callback.bind(py).is_callable()
+    {
+        return Err(PyTypeError::new_err(
+            "callback must be a callable taking (subject, not_after, days_left)",
+        ));
+    }
+    if let Ok(mut settings) = SETTINGS.lock() {
+        *settings = days.map(|days| Settings {
+            days: days as i64,
+            callback,
+        });
+    }
+    if let Ok(mut reported) = REPORTED.lock() {
+        reported.clear();
+    }
+    Ok(())
+}
@@ -0,0 +1,116 @@
+//! Client assertions for service principals that sign in with a certificate
+//! (`AzureCredential.service_principal_certificate()`).
+//!
+//! Instead of a client secret, the token request carries a short-lived JWT signed
+//! with the certificate's private key (RS256), identifying the certificate by its
+//! SHA-1 and SHA-256 thumbprints (`x5t`, `x5t#S256`) so Microsoft Entra ID can check
+//! it against the ones uploaded to the app registration.  The certificate file is a
+//! PEM file holding the certificate and its unencrypted RSA private key, PKCS#8 or
+//! PKCS#1, as `openssl pkcs12 -nodes` writes it.
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use ring::digest;
+use ring::rand::{SecureRandom, SystemRandom};
+use ring::signature::{RSA_PKCS1_SHA256, RsaKeyPair};
+use rustls_pki_types::pem::PemObject;
+use rustls_pki_types::{CertificateDer, PrivateKeyDer};
+use serde_json::json;
+
+use crate::types::create_auth_error;
+
+/// Lifetime of an assertion; it is used once, right after it is made.
+const ASSERTION_LIFETIME_SECS: i64 = 600;
+
+/// The certificate and private key read from a PEM file.
+pub struct ClientCertificate {
+    der: Vec<u8>,
+    key: RsaKeyPair,
+}
+
+impl ClientCertificate {
+    /// Parse `pem`, which must hold a certificate and its RSA private key.  The first
+    /// certificate is the one signing in; others (the chain) are ignored.
+    pub fn from_pem(pem: &[u8]) -> PyResult<Self> {
+        let der = CertificateDer::pem_slice_iter(pem)
+            .next()
+            .and_then(Result::ok)
+            .ok_or_else(|| PyValueError::new_err("No CERTIFICATE found in the PEM file"))?;
+        let key = PrivateKeyDer::from_pem_slice(pem).map_err(|_| {
+            PyValueError::new_err(
+                "No unencrypted PRIVATE KEY or RSA PRIVATE KEY found in the PEM file",
+            )
+        })?;
+        let key = match &key {
+            PrivateKeyDer::Pkcs8(key) => RsaKeyPair::from_pkcs8(key.secret_pkcs8_der()),
+            PrivateKeyDer::Pkcs1(key) => RsaKeyPair::from_der(key.secret_pkcs1_der()),
+            _ => {
+                return Err(PyValueError::new_err(
+                    "The certificate's private key must be an RSA key",
+                ));
+            }
+        }
+        .map_err(|e| PyValueError::new_err(format!("Unusable RSA private key: {}", e)))?;
+        Ok(ClientCertificate {
+            der: der.to_vec(),
+            key,
+        })
+    }
+
+    /// SHA-1 thumbprint of the certificate, as hex, the way the Azure portal shows it.
+    pub fn thumbprint(&self) -> String {
+        digest::digest(&digest::SHA1_FOR_LEGACY_USE_ONLY, &self.der)
+            .as_ref()
+            .iter()
+            .map(|b| format!("{:02X}", b))
+            .collect()
+    }
+
+    /// A signed assertion that `client_id` is requesting a token from `token_url`.
+    pub fn assertion(&self, client_id: &str, token_url: &str) -> PyResult<String> {
+        let rng = SystemRandom::new();
+        let mut jti = [0u8; 16];
+        rng.fill(&mut jti)
+            .map_err(|_| create_auth_error("Failed to generate a client assertion ID"))?;
+        let now = chrono::Utc::now().timestamp();
+
+        let header = json!({
+            "alg": "RS256",
+            "typ": "JWT",
+            "x5t": URL_SAFE_NO_PAD
+                .encode(digest::digest(&digest::SHA1_FOR_LEGACY_USE_ONLY, &self.der)),
+            "x5t#S256": URL_SAFE_NO_PAD.encode(digest::digest(&digest::SHA256, &self.der)),
+        });
+        let claims = json!({
+            "aud": token_url,
+            "iss": client_id,
+            "sub": client_id,
+            "jti": URL_SAFE_NO_PAD.encode(jti),
+            "nbf": now,
+            "iat": now,
+            "exp": now + ASSERTION_LIFETIME_SECS,
+        });
+        let signing_input = format!(
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(header.to_string()),
+            URL_SAFE_NO_PAD.encode(claims.to_string())
+        );
+
+        let mut signature = vec![0u8; self.key.public().modulus_len()];
+        self.key
+            .sign(
+                &RSA_PKCS1_SHA256,
+                &rng,
+                signing_input.as_bytes(),
+                &mut signature,
+            )
+            .map_err(|_| create_auth_error("Failed to sign the client assertion"))?;
+        Ok(format!(
+            "{}.{}",
+            signing_input,
+            URL_SAFE_NO_PAD.encode(signature)
+        ))
+    }
+}
@@ -0,0 +1,108 @@
+//! Parsing of sparse `COLUMN_SET` values.
+//!
+//! SQL Server returns a column set as untyped XML with one element per non-NULL
+//! sparse column, e.g. `<Color>red</Color><Size>10</Size>`.  Element names use
+//! the `_xHHHH_` escaping of `FOR XML` for characters that are not valid in XML
+//! names, and text content uses the standard entity escapes.
+
+/// Decode `&lt;`-style and numeric character references.
+fn unescape_text(text: &str) -> Result<String, String> {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        let after = &rest[amp + 1..];
+        let semi = after
+            .find(';')
+            .ok_or_else(|| "unterminated entity reference in column set".to_string())?;
+        let entity = &after[..semi];
+        let decoded = match entity {
+            "lt" => '<',
+            "gt" => '>',
+            "amp" => '&',
+            "quot" => '"',
+            "apos" => '\'',
+            _ => {
+                let code = if let Some(hex) = entity.strip_prefix("#x") {
+                    u32::from_str_radix(hex, 16).ok()
+                } else if let Some(dec) = entity.strip_prefix('#') {
+                    dec.parse::<u32>().ok()
+                } else {
+                    None
+                };
+                code.and_then(char::from_u32)
+                    .ok_or_else(|| format!("unknown entity '&{};' in column set", entity))?
+            }
+        };
+        out.push(decoded);
+        rest = &after[semi + 1..];
+    }
+
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Decode `_xHHHH_` escapes in an element name back to the column name.
+fn unescape_name(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    let mut rest = name;
+
+    while let Some(start) = rest.find("_x") {
+        let candidate = &rest[start + 2..];
+        let decoded = candidate
+            .get(..5)
+            .filter(|s| s.ends_with('_'))
+            .and_then(|s| u32::from_str_radix(&s[..4], 16).ok())
+            .and_then(char::from_u32);
+        match decoded {
+            Some(c) => {
+                out.push_str(&rest[..start]);
+                out.push(c);
+                rest = &candidate[5..];
+            }
+            None => {
+                out.push_str(&rest[..start + 2]);
+                rest = candidate;
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// Split a column set XML value into `(column name, text value)` pairs in
+/// document order.  Self-closing elements yield an empty string.
+pub fn parse_column_set(xml: &str) -> Result<Vec<(String, String)>, String> {
+    let mut columns = Vec::new();
+    let mut rest = xml.trim();
+
+    while !rest.is_empty() {
+        let open = rest
+            .strip_prefix('<')
+            .ok_or_else(|| "column set value is not a sequence of XML elements".to_string())?;
+        let tag_end = open
+            .find('>')
+            .ok_or_else(|| "unterminated element in column set".to_string())?;
+        let tag = &open[..tag_end];
+
+        if let Some(name) = tag.strip_suffix('/') {
+            columns.push((unescape_name(name.trim()), String::new()));
+            rest = open[tag_end + 1..].trim_start();
+            continue;
+        }
+
+        let name = tag.split_whitespace().next().unwrap_or("");
+        let body = &open[tag_end + 1..];
+        let close = format!("</{}>", name);
+        let close_at = body
+            .find(&close)
+            .ok_or_else(|| format!("missing closing tag for '{}' in column set", name))?;
+
+        columns.push((unescape_name(name), unescape_text(&body[..close_at])?));
+        rest = body[close_at + close.len()..].trim_start();
+    }
+
+    Ok(columns)
+}
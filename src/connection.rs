@@ -1,29 +1,113 @@
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
-use pyo3::types::PyList;
+use pyo3::types::{PyDict, PyList};
 use pyo3_async_runtimes::tokio::future_into_py;
-use std::sync::Arc;
+use smallvec::SmallVec;
+use std::sync::{Arc, RwLock as StdRwLock};
+use std::time::Duration;
 use tiberius::{AuthMethod, Config, Row};
 use tokio::sync::RwLock;
 
+use crate::arrow_conversion::ArrowColumns;
 use crate::azure_auth::PyAzureCredential;
-use crate::batch::{bulk_insert, execute_batch, query_batch};
-use crate::helpers::wrap_query_stream;
-use crate::parameter_conversion::{FastParameter, convert_parameters_to_fast, params_as_sql_refs};
+use crate::batch::{
+    PyBatch, apply_batch, bulk_insert, execute_batch, parse_batch_items, prepare_bulk_insert,
+    query_batch,
+};
+use crate::bulk_copy::{
+    bulk_load, bulk_load_dataframe, prepare_arrow_bulk_load, prepare_bulk_load,
+    prepare_dataframe_load,
+};
+use crate::dml_progress::{self, ProgressWatch, progress_watch};
+use crate::dynamic_sql::{build_exec_sql, parameter_name, split_meta_row};
+use crate::execution_id::{BatchTrace, context_info_sql, next_execution_id, tag_error};
+use crate::helpers::{
+    FirstResult, first_column_to_list, into_first_result_with_metadata, wrap_traced_query_stream,
+};
+use crate::info_messages;
+use crate::json_columns::parse_json_option;
+use crate::xml_columns::parse_xml_option;
+use crate::metadata::{
+    DESCRIBE_SQL, TABLE_COLUMNS_SQL, describe_rows_to_py, table_columns_to_py,
+};
+use crate::migrations::{self, Script};
+use crate::page_token::{
+    PagePosition, decode_token, next_token, page_keys, page_statement, token_scope,
+};
+use crate::parameter_conversion::{
+    FastParameter, convert_parameters_to_fast, params_as_sql_refs, python_to_fast_parameter,
+    rewrite_json_lists,
+};
 use crate::pool_config::PyPoolConfig;
-use crate::pool_manager::{ConnectionPool, ensure_pool_initialized_with_auth};
+use crate::pool_manager::{ConnectionPool, ensure_pool_initialized_with_auth, replace_pool};
+use crate::pinned::PyPinnedConnection;
+use crate::pool_registry::{self, PoolKey};
+use crate::query_label::{LabelStats, label_comment};
+use crate::query_log::{LoggedParams, QueryLog};
+use crate::result_limits::{ResultLimits, is_limit_error};
+use crate::result_sets::{PendingResultSets, PyResultSetStream};
+use crate::retry_policy::PyRetryPolicy;
+use crate::row_stream::{PendingRows, PyChunkStream, PyRowStream};
+use crate::sql_placeholders::{ParamStyle, translate_placeholders};
 use crate::ssl_config::PySslConfig;
-use crate::types::{create_connection_error, create_sql_error};
+use crate::statement_policy::{PyStatementPolicy, statement_policy_option};
+use crate::touched_tables::{SHOWPLAN_OFF, SHOWPLAN_ON, touched_tables_to_py};
+use crate::transaction::Transaction;
+use crate::type_mapping::DecodeOptions;
+use crate::types::{DatabaseError, PyQueryStream, QueryTimeoutError, create_sql_error};
 
-struct ConnectionHandles {
+/// Session properties reported by `server_info()`; CONNECTIONPROPERTY needs no
+/// server-level permission, unlike `sys.dm_exec_connections`.
+const CONNECTION_PROPERTIES_SQL: &str = "SELECT \
+    CONVERT(BIGINT, CONNECTIONPROPERTY('protocol_version')), \
+    CONVERT(NVARCHAR(40), CONNECTIONPROPERTY('encrypt_option')), \
+    CONVERT(NVARCHAR(40), CONNECTIONPROPERTY('auth_scheme')), \
+    CONVERT(NVARCHAR(40), CONNECTIONPROPERTY('net_transport'))";
+
+/// `CONNECTIONPROPERTY('protocol_version')` as a TDS version, e.g. 0x74000004 -> "7.4".
+fn tds_version_name(version: i64) -> String {
+    match version >> 24 {
+        0x70 => "7.0".to_string(),
+        0x71 => "7.1".to_string(),
+        0x72 => "7.2".to_string(),
+        0x73 => "7.3".to_string(),
+        0x74 => "7.4".to_string(),
+        0x08 => "8.0".to_string(),
+        _ => format!("0x{:08X}", version),
+    }
+}
+
+/// Validate a `timeout=` argument, in seconds.
+fn statement_timeout(timeout: Option<f64>) -> PyResult<Option<Duration>> {
+    match timeout {
+        Some(secs) if !(secs.is_finite() && secs > 0.0) => Err(PyValueError::new_err(
+            "timeout must be a positive number of seconds",
+        )),
+        secs => Ok(secs.map(Duration::from_secs_f64)),
+    }
+}
+
+/// Appended to the statement run by `execute_returning()`; its single-row result set
+/// is always the last one in the batch.
+const ROW_COUNT_SUFFIX: &str = "\n;SELECT ROWCOUNT_BIG() AS rows_affected";
+
+pub(crate) struct ConnectionHandles {
     pool: Arc<RwLock<Option<ConnectionPool>>>,
     config: Arc<Config>,
     pool_config: PyPoolConfig,
     azure_credential: Option<Arc<PyAzureCredential>>,
+    pub(crate) decode_options: DecodeOptions,
+    trace_context_info: bool,
+    query_log: Arc<QueryLog>,
+    on_message: Option<Arc<Py<PyAny>>>,
+    retry_policy: Option<PyRetryPolicy>,
+    statement_policy: Option<Arc<Py<PyStatementPolicy>>>,
 }
 
 impl ConnectionHandles {
-    fn ensure_connected(&self) -> impl std::future::Future<Output = PyResult<ConnectionPool>> + '_ {
+    pub(crate) fn ensure_connected(
+        &self,
+    ) -> impl std::future::Future<Output = PyResult<ConnectionPool>> + '_ {
         ensure_pool_initialized_with_auth(
             self.pool.clone(),
             self.config.clone(),
@@ -31,110 +115,476 @@ impl ConnectionHandles {
             self.azure_credential.clone(),
         )
     }
+
+    /// Raise `PolicyViolation` if the connection's statement policy rejects `sql`.
+    pub(crate) fn check_statement(&self, py: Python<'_>, sql: &str) -> PyResult<()> {
+        match &self.statement_policy {
+            Some(policy) => policy.get().enforce(py, sql),
+            None => Ok(()),
+        }
+    }
+
+    /// Allocate an execution ID for `sql` and log the call, then prefix the statement
+    /// with `SET CONTEXT_INFO` when the connection traces to the server.
+    pub(crate) fn trace(
+        &self,
+        py: Python<'_>,
+        sql: String,
+        params: LoggedParams<'_>,
+    ) -> (String, String) {
+        let execution_id = next_execution_id();
+        self.query_log.log(py, &execution_id, &sql, params);
+        let sql = self.with_context_info(&execution_id, sql);
+        (execution_id, sql)
+    }
+
+    /// Allocate one execution ID for a call that sends several statements and log
+    /// each of them under it.  CONTEXT_INFO is set once, ahead of the first.
+    pub(crate) fn trace_statements<'a>(
+        &self,
+        py: Python<'_>,
+        statements: impl IntoIterator<Item = (&'a str, LoggedParams<'a>)>,
+    ) -> BatchTrace {
+        let execution_id = next_execution_id();
+        for (sql, params) in statements {
+            self.query_log.log(py, &execution_id, sql, params);
+        }
+        let context_info = self
+            .trace_context_info
+            .then(|| context_info_sql(&execution_id));
+        BatchTrace {
+            execution_id,
+            context_info,
+        }
+    }
+
+    /// Run `statement`, collecting the server's info messages and passing each to the
+    /// `on_message` callback.
+    pub(crate) async fn with_messages<F: std::future::Future>(
+        &self,
+        statement: F,
+    ) -> (F::Output, Vec<String>) {
+        info_messages::collect(self.on_message.clone(), statement).await
+    }
+
+    /// Run `attempt` under the connection's retry policy, or once when there is no
+    /// policy or the statement is not idempotent.
+    async fn with_retries<T, F, Fut>(&self, idempotent: bool, mut attempt: F) -> PyResult<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = PyResult<T>>,
+    {
+        match &self.retry_policy {
+            Some(policy) if idempotent => policy.run(attempt).await,
+            _ => attempt().await,
+        }
+    }
+
+    fn with_context_info(&self, execution_id: &str, sql: String) -> String {
+        if self.trace_context_info {
+            context_info_sql(execution_id) + &sql
+        } else {
+            sql
+        }
+    }
 }
 
-#[pyclass(name = "Connection")]
-pub struct PyConnection {
-    pool: Arc<RwLock<Option<ConnectionPool>>>,
+/// Convert `parameters` and rewrite `query`'s placeholders under `paramstyle`, and
+/// its `IN (@Pn)` predicates for JSON lists; see `PyConnection::bind`.
+pub(crate) fn bind_parameters(
+    py: Python<'_>,
+    paramstyle: ParamStyle,
+    query: String,
+    parameters: Option<&Bound<PyAny>>,
+) -> PyResult<(String, SmallVec<[FastParameter; 16]>)> {
+    let fast_parameters = convert_parameters_to_fast(parameters, py)?;
+    if parameters.is_none() {
+        return Ok((query, fast_parameters));
+    }
+    let query = translate_placeholders(&query, paramstyle, fast_parameters.len())
+        .map_err(PyValueError::new_err)?;
+    Ok((rewrite_json_lists(query, &fast_parameters), fast_parameters))
+}
+
+/// What new physical connections are opened with.  Shared by a connection and every
+/// `with_options()` copy of it, so `reload_config()` through one reaches them all.
+#[derive(Clone)]
+struct ConnectSettings {
     config: Arc<Config>,
+    /// `config` before `ssl_config` was applied, for `reload_config(new_ssl_config=...)`;
+    /// None when the connection string carries the TLS settings.
+    config_without_ssl: Option<Arc<Config>>,
     pool_config: PyPoolConfig,
     _ssl_config: Option<PySslConfig>,
     azure_credential: Option<Arc<PyAzureCredential>>,
+    pool_key: Arc<PoolKey>,
+}
+
+#[pyclass(name = "Connection")]
+pub struct PyConnection {
+    pool: Arc<RwLock<Option<ConnectionPool>>>,
+    settings: Arc<StdRwLock<ConnectSettings>>,
+    decode_options: DecodeOptions,
+    trace_context_info: bool,
+    query_log: Arc<QueryLog>,
+    on_message: Option<Arc<Py<PyAny>>>,
+    retry_policy: Option<PyRetryPolicy>,
+    statement_policy: Option<Arc<Py<PyStatementPolicy>>>,
+    paramstyle: ParamStyle,
+    label_stats: Arc<LabelStats>,
 }
 
 impl PyConnection {
+    /// The current connect settings; a snapshot, so a later `reload_config()` does not
+    /// change what a statement already under way connects with.
+    fn settings(&self) -> ConnectSettings {
+        match self.settings.read() {
+            Ok(settings) => settings.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    /// Server config and credential new physical connections are opened with.
+    pub fn connect_target(&self) -> (Config, Option<Arc<PyAzureCredential>>) {
+        let settings = self.settings();
+        (settings.config.as_ref().clone(), settings.azure_credential)
+    }
+
     fn clone_handles(&self) -> ConnectionHandles {
+        let settings = self.settings();
         ConnectionHandles {
             pool: Arc::clone(&self.pool),
-            config: Arc::clone(&self.config),
-            pool_config: self.pool_config.clone(),
-            azure_credential: self.azure_credential.clone(),
+            config: settings.config,
+            pool_config: settings.pool_config,
+            azure_credential: settings.azure_credential,
+            decode_options: self.decode_options,
+            trace_context_info: self.trace_context_info,
+            query_log: Arc::clone(&self.query_log),
+            on_message: self.on_message.clone(),
+            retry_policy: self.retry_policy.clone(),
+            statement_policy: self.statement_policy.clone(),
+        }
+    }
+
+    /// Convert `parameters` and rewrite `query`'s `?` or `%s` placeholders to `@Pn`
+    /// under the connection's paramstyle.  Statements run without parameters are left
+    /// alone, as pyodbc and pymssql leave them.
+    fn bind(
+        &self,
+        py: Python<'_>,
+        query: String,
+        parameters: Option<&Bound<PyAny>>,
+    ) -> PyResult<(String, SmallVec<[FastParameter; 16]>)> {
+        if let Some(policy) = &self.statement_policy {
+            policy.get().enforce(py, &query)?;
         }
+        bind_parameters(py, self.paramstyle, query, parameters)
+    }
+
+    /// Check the SQL of each `(sql, parameters)` item against the statement policy.
+    fn check_batch_items(&self, py: Python<'_>, items: &Bound<'_, PyList>) -> PyResult<()> {
+        match &self.statement_policy {
+            Some(policy) => policy.get().enforce_batch(py, items),
+            None => Ok(()),
+        }
+    }
+
+    /// Run `query` and pass its first result set, collected into Arrow column buffers,
+    /// to `finish` to build the Python object returned.
+    fn collect_arrow<'p>(
+        &self,
+        py: Python<'p>,
+        query: String,
+        parameters: Option<&Bound<PyAny>>,
+        finish: fn(ArrowColumns, Python<'_>) -> PyResult<Py<PyAny>>,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        let (query, fast_parameters) = self.bind(py, query, parameters)?;
+        let handles = self.clone_handles();
+        let (execution_id, query) =
+            handles.trace(py, query, LoggedParams::Positional(&fast_parameters));
+
+        future_into_py(py, async move {
+            let pool_ref = handles.ensure_connected().await?;
+            let (columns, _) = handles
+                .with_messages(Self::query_arrow_async_gil_free(
+                    &pool_ref,
+                    &query,
+                    &fast_parameters,
+                    handles.decode_options,
+                ))
+                .await;
+            let columns = columns.map_err(|e| tag_error(e, &execution_id))?;
+            Python::attach(|py| finish(columns, py))
+        })
     }
 
     async fn get_pool_connection(
         pool: &ConnectionPool,
     ) -> PyResult<bb8::PooledConnection<'_, crate::pool_manager::AzureConnectionManager>> {
-        pool.get().await.map_err(|e| match e {
-            bb8::RunError::TimedOut => create_connection_error(
-                "Connection pool timeout - all connections are busy. \
-                     Try reducing concurrent requests or increasing pool size.",
-            ),
-            bb8::RunError::User(e) => {
-                create_connection_error(format!("Failed to get connection from pool: {}", e))
-            }
-        })
+        pool.checkout().await
     }
 
+    /// Run `query` and return its first result set along with that set's metadata.
     #[inline]
     async fn execute_query_async_gil_free(
         pool: &ConnectionPool,
         query: &str,
         parameters: &[FastParameter],
-    ) -> PyResult<Vec<Row>> {
+        limits: ResultLimits,
+    ) -> PyResult<FirstResult> {
         let mut conn = Self::get_pool_connection(pool).await?;
         let tiberius_params = params_as_sql_refs(parameters);
 
-        let stream = conn
-            .query(query, &tiberius_params)
-            .await
-            .map_err(|e| create_sql_error(e, "Query execution failed"))?;
+        let result = conn
+            .request(async |client| {
+                let stream = client
+                    .query(query, &tiberius_params)
+                    .await
+                    .map_err(|e| create_sql_error(e, "Query execution failed"))?;
+                into_first_result_with_metadata(stream, limits).await
+            })
+            .await;
+        if result.as_ref().is_err_and(is_limit_error) {
+            conn.mark_broken();
+        }
+        result
+    }
 
-        let result = stream
-            .into_first_result()
-            .await
-            .map_err(|e| create_sql_error(e, "Failed to get results"))?;
+    /// Run `query` and collect its first result set into Arrow column buffers.
+    async fn query_arrow_async_gil_free(
+        pool: &ConnectionPool,
+        query: &str,
+        parameters: &[FastParameter],
+        options: DecodeOptions,
+    ) -> PyResult<ArrowColumns> {
+        let mut conn = Self::get_pool_connection(pool).await?;
+        let tiberius_params = params_as_sql_refs(parameters);
 
-        drop(conn);
-        Ok(result)
+        let columns = conn
+            .request(async |client| {
+                let stream = client
+                    .query(query, &tiberius_params)
+                    .await
+                    .map_err(|e| create_sql_error(e, "Query execution failed"))?;
+                ArrowColumns::from_stream(stream, options).await
+            })
+            .await;
+        if columns.as_ref().is_err_and(is_limit_error) {
+            conn.mark_broken();
+        }
+        columns
+    }
+
+    /// Apply `scripts` on one pooled connection.  A failed run leaves its session lock
+    /// and transaction behind, so the connection is discarded rather than reused.
+    async fn apply_migrations_async_gil_free(
+        pool: &ConnectionPool,
+        trace: &BatchTrace,
+        scripts: &[Script],
+        table: &str,
+        dry_run: bool,
+    ) -> PyResult<Vec<migrations::Outcome>> {
+        let mut conn = Self::get_pool_connection(pool).await?;
+        let outcomes = conn
+            .request(async |client| {
+                trace.set_context_info(client).await?;
+                migrations::apply(client, scripts, table, dry_run).await
+            })
+            .await;
+        if outcomes.is_err() {
+            conn.mark_broken();
+        }
+        outcomes
     }
 
     #[inline]
     async fn execute_simple_query_async_gil_free(
         pool: &ConnectionPool,
         query: &str,
-    ) -> PyResult<Vec<Row>> {
+        limits: ResultLimits,
+    ) -> PyResult<FirstResult> {
         let mut conn = Self::get_pool_connection(pool).await?;
 
-        let stream = conn
-            .simple_query(query)
-            .await
-            .map_err(|e| create_sql_error(e, "Query execution failed"))?;
+        let result = conn
+            .request(async |client| {
+                let stream = client
+                    .simple_query(query)
+                    .await
+                    .map_err(|e| create_sql_error(e, "Query execution failed"))?;
+                into_first_result_with_metadata(stream, limits).await
+            })
+            .await;
+        if result.as_ref().is_err_and(is_limit_error) {
+            conn.mark_broken();
+        }
+        result
+    }
+
+    /// Run `query` followed by [`ROW_COUNT_SUFFIX`] and return the first result set
+    /// produced by `query` (empty if it produced none) with the affected row count.
+    async fn execute_returning_async_gil_free(
+        pool: &ConnectionPool,
+        query: &str,
+        parameters: &[FastParameter],
+    ) -> PyResult<(Vec<Row>, u64)> {
+        let mut conn = Self::get_pool_connection(pool).await?;
+        let tiberius_params = params_as_sql_refs(parameters);
+        let sql = format!("{}{}", query, ROW_COUNT_SUFFIX);
+
+        let mut result_sets = conn
+            .request(async |client| {
+                client
+                    .query(sql.as_str(), &tiberius_params)
+                    .await
+                    .map_err(|e| create_sql_error(e, "Command execution failed"))?
+                    .into_results()
+                    .await
+                    .map_err(|e| create_sql_error(e, "Failed to get results"))
+            })
+            .await?;
+        drop(conn);
+
+        let rows_affected = result_sets
+            .pop()
+            .and_then(|rows| rows.into_iter().next())
+            .and_then(|row| row.get::<i64, _>(0))
+            .ok_or_else(|| DatabaseError::new_err("Row count missing from result"))?;
+        let rows = result_sets.into_iter().next().unwrap_or_default();
+        Ok((rows, rows_affected.max(0) as u64))
+    }
 
-        let result = stream
-            .into_first_result()
-            .await
-            .map_err(|e| create_sql_error(e, "Failed to get results"))?;
+    /// Run an `exec_sql()` batch and split off its trailing count/OUTPUT row; the
+    /// remaining result sets are those produced by the dynamic SQL.
+    async fn exec_sql_async_gil_free(
+        pool: &ConnectionPool,
+        sql: &str,
+        parameters: &[FastParameter],
+    ) -> PyResult<(Vec<Vec<Row>>, Option<Row>)> {
+        let mut conn = Self::get_pool_connection(pool).await?;
+        let tiberius_params = params_as_sql_refs(parameters);
 
+        let mut result_sets = conn
+            .request(async |client| {
+                client
+                    .query(sql, &tiberius_params)
+                    .await
+                    .map_err(|e| create_sql_error(e, "Dynamic SQL execution failed"))?
+                    .into_results()
+                    .await
+                    .map_err(|e| create_sql_error(e, "Failed to get results"))
+            })
+            .await?;
         drop(conn);
-        Ok(result)
+
+        let meta = result_sets.pop().and_then(|rows| rows.into_iter().next());
+        Ok((result_sets, meta))
+    }
+
+    /// Compile `query` with `SHOWPLAN_XML` on and return its plan documents, one per
+    /// statement. The setting is switched off again before the connection goes back
+    /// to the pool; if that fails the connection is discarded instead.
+    async fn showplan_async_gil_free(
+        pool: &ConnectionPool,
+        query: &str,
+        parameters: &[FastParameter],
+    ) -> PyResult<Vec<String>> {
+        let mut conn = Self::get_pool_connection(pool).await?;
+        let tiberius_params = params_as_sql_refs(parameters);
+        let (plans, reset) = conn
+            .request(async |client| {
+                client
+                    .simple_query(SHOWPLAN_ON)
+                    .await
+                    .map_err(|e| create_sql_error(e, "Failed to enable SHOWPLAN_XML"))?
+                    .into_results()
+                    .await
+                    .map_err(|e| create_sql_error(e, "Failed to enable SHOWPLAN_XML"))?;
+
+                let plans = match client.query(query, &tiberius_params).await {
+                    Ok(stream) => stream.into_results().await,
+                    Err(e) => Err(e),
+                };
+                let reset = match client.simple_query(SHOWPLAN_OFF).await {
+                    Ok(stream) => stream.into_results().await.map(|_| ()),
+                    Err(e) => Err(e),
+                };
+                Ok::<_, PyErr>((plans, reset))
+            })
+            .await?;
+        if reset.is_err() {
+            conn.mark_broken();
+        }
+
+        let plans = plans.map_err(|e| create_sql_error(e, "Failed to compile statement"))?;
+        Ok(plans
+            .iter()
+            .flatten()
+            .filter_map(|row| row.get::<&str, _>(0).map(str::to_string))
+            .collect())
     }
 
+    /// Run `query` and return the row count of each statement. With a `timeout`, a
+    /// statement still running when it expires is cancelled and the connection
+    /// discarded, or with `recover_on_timeout` checked in the background and kept if
+    /// it is still healthy. With `progress`, its progress is polled from another
+    /// connection while it runs.
     #[inline]
     async fn execute_command_async_gil_free(
         pool: &ConnectionPool,
         query: &str,
         parameters: &[FastParameter],
-    ) -> PyResult<u64> {
-        let mut conn = Self::get_pool_connection(pool).await?;
+        timeout: Option<Duration>,
+        recover_on_timeout: bool,
+        progress: Option<&ProgressWatch>,
+    ) -> PyResult<Vec<u64>> {
+        let mut conn = pool.checkout_owned().await?;
         let tiberius_params = params_as_sql_refs(parameters);
 
-        let result = conn
-            .execute(query, &tiberius_params)
-            .await
-            .map_err(|e| create_sql_error(e, "Command execution failed"))?;
+        let _poller = match progress {
+            Some(watch) => {
+                let session_id = conn
+                    .request(async |client| dml_progress::session_id(client).await)
+                    .await
+                    .map_err(|e| create_sql_error(e, "Failed to read the session ID"))?;
+                Some(watch.spawn(pool.clone(), session_id))
+            }
+            None => None,
+        };
+
+        let execute = conn.request(async |client| client.execute(query, &tiberius_params).await);
+        let result = match timeout {
+            Some(limit) => match tokio::time::timeout(limit, execute).await {
+                Ok(result) => result,
+                Err(_) => {
+                    // The expired statement is abandoned in flight, so the connection
+                    // is cancelled and discarded when it goes back to the pool, unless
+                    // recovery gets it back in step first.
+                    if recover_on_timeout {
+                        tokio::spawn(async move {
+                            conn.recover().await;
+                        });
+                    }
+                    return Err(QueryTimeoutError::new_err(format!(
+                        "Statement cancelled after exceeding its {}s timeout",
+                        limit.as_secs_f64()
+                    )));
+                }
+            },
+            None => execute.await,
+        }
+        .map_err(|e| create_sql_error(e, "Command execution failed"))?;
 
-        let total_affected = result.rows_affected().iter().sum::<u64>();
+        let per_statement = result.rows_affected().to_vec();
 
         drop(conn);
-        Ok(total_affected)
+        Ok(per_statement)
     }
 }
 
 #[pymethods]
 impl PyConnection {
     #[new]
-    #[pyo3(signature = (connection_string = None, pool_config = None, ssl_config = None, azure_credential = None, server = None, database = None, username = None, password = None, application_intent = None, port = None, instance_name = None, application_name = None))]
+    #[pyo3(signature = (connection_string = None, pool_config = None, ssl_config = None, azure_credential = None, server = None, database = None, username = None, password = None, application_intent = None, port = None, instance_name = None, application_name = None, rstrip_char = false, time_as_timedelta = false, parse_json = None, parse_xml = None, trace_context_info = false, log_queries = false, log_parameters = false, redact_parameters = None, on_message = None, retry_policy = None, paramstyle = None, statement_policy = None, max_result_columns = None, max_row_bytes = None))]
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         connection_string: Option<String>,
@@ -149,10 +599,40 @@ impl PyConnection {
         port: Option<u16>,
         instance_name: Option<String>,
         application_name: Option<String>,
+        rstrip_char: bool,
+        time_as_timedelta: bool,
+        parse_json: Option<&Bound<PyAny>>,
+        parse_xml: Option<&Bound<PyAny>>,
+        trace_context_info: bool,
+        log_queries: bool,
+        log_parameters: bool,
+        redact_parameters: Option<&Bound<PyAny>>,
+        on_message: Option<Py<PyAny>>,
+        retry_policy: Option<PyRetryPolicy>,
+        paramstyle: Option<&str>,
+        statement_policy: Option<&Bound<PyAny>>,
+        max_result_columns: Option<usize>,
+        max_row_bytes: Option<usize>,
     ) -> PyResult<Self> {
-        let config = if let Some(conn_str) = connection_string {
-            Config::from_ado_string(&conn_str)
-                .map_err(|e| PyValueError::new_err(format!("Invalid connection string: {}", e)))?
+        let query_log = QueryLog::new(log_queries, log_parameters, redact_parameters)?;
+        let paramstyle = paramstyle
+            .map(ParamStyle::parse)
+            .transpose()
+            .map_err(PyValueError::new_err)?
+            .unwrap_or_default();
+        let pool_key = match &connection_string {
+            Some(conn_str) => PoolKey::from_connection_string(conn_str, azure_credential.as_ref()),
+            None => PoolKey::new(
+                server.as_deref(),
+                database.as_deref(),
+                username.as_deref(),
+                azure_credential.as_ref(),
+            ),
+        };
+        let (config, config_without_ssl) = if let Some(conn_str) = connection_string {
+            let config = Config::from_ado_string(&conn_str)
+                .map_err(|e| PyValueError::new_err(format!("Invalid connection string: {}", e)))?;
+            (config, None)
         } else if let Some(ref srv) = server {
             let mut config = Config::new();
             config.host(srv);
@@ -191,10 +671,11 @@ impl PyConnection {
                     }
                 }
             }
+            let config_without_ssl = config.clone();
             if let Some(ref ssl_cfg) = ssl_config {
                 ssl_cfg.apply_to_config(&mut config);
             }
-            config
+            (config, Some(Arc::new(config_without_ssl)))
         } else {
             return Err(PyValueError::new_err(
                 "Either connection_string or server must be provided",
@@ -207,12 +688,34 @@ impl PyConnection {
             ));
         }
 
+        let pool = Arc::new(RwLock::new(None));
+        let pool_config = pool_config.unwrap_or_default();
+        pool_registry::register(pool_key.clone(), &pool, pool_config.max_size);
+
         Ok(PyConnection {
-            pool: Arc::new(RwLock::new(None)),
-            config: Arc::new(config),
-            pool_config: pool_config.unwrap_or_else(PyPoolConfig::default),
-            _ssl_config: ssl_config,
-            azure_credential: azure_credential.map(Arc::new),
+            pool,
+            settings: Arc::new(StdRwLock::new(ConnectSettings {
+                config: Arc::new(config),
+                config_without_ssl,
+                pool_config,
+                _ssl_config: ssl_config,
+                azure_credential: azure_credential.map(Arc::new),
+                pool_key: Arc::new(pool_key),
+            })),
+            decode_options: DecodeOptions {
+                rstrip_char,
+                time_as_timedelta,
+                json: parse_json_option(parse_json)?,
+                xml: parse_xml_option(parse_xml)?,
+                limits: ResultLimits::new(max_result_columns, max_row_bytes)?,
+            },
+            trace_context_info,
+            query_log: Arc::new(query_log),
+            on_message: on_message.map(Arc::new),
+            retry_policy,
+            statement_policy: statement_policy_option(statement_policy)?,
+            paramstyle,
+            label_stats: Arc::default(),
         })
     }
 
@@ -223,44 +726,614 @@ impl PyConnection {
         query: String,
         parameters: Option<&Bound<PyAny>>,
     ) -> PyResult<Bound<'p, PyAny>> {
-        let fast_parameters = convert_parameters_to_fast(parameters, py)?;
+        let (query, fast_parameters) = self.bind(py, query, parameters)?;
+        let handles = self.clone_handles();
+        let (execution_id, query) =
+            handles.trace(py, query, LoggedParams::Positional(&fast_parameters));
+
+        future_into_py(py, async move {
+            let pool_ref = handles.ensure_connected().await?;
+            let (execution_result, messages) = handles
+                .with_messages(Self::execute_query_async_gil_free(
+                    &pool_ref,
+                    &query,
+                    &fast_parameters,
+                    handles.decode_options.limits,
+                ))
+                .await;
+            let execution_result = execution_result.map_err(|e| tag_error(e, &execution_id))?;
+            wrap_traced_query_stream(
+                execution_result,
+                handles.decode_options,
+                execution_id,
+                messages,
+            )
+        })
+    }
+
+    /// Run a query and return the values of its first column as a plain list,
+    /// skipping `FastRow` construction (e.g. for lists of IDs).
+    #[pyo3(signature = (query, parameters=None))]
+    pub fn fetch_column<'p>(
+        &self,
+        py: Python<'p>,
+        query: String,
+        parameters: Option<&Bound<PyAny>>,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        let (query, fast_parameters) = self.bind(py, query, parameters)?;
         let handles = self.clone_handles();
+        let (execution_id, query) =
+            handles.trace(py, query, LoggedParams::Positional(&fast_parameters));
 
         future_into_py(py, async move {
             let pool_ref = handles.ensure_connected().await?;
-            let execution_result =
-                Self::execute_query_async_gil_free(&pool_ref, &query, &fast_parameters).await?;
-            wrap_query_stream(execution_result)
+            let (rows, _) = handles
+                .with_messages(Self::execute_query_async_gil_free(
+                    &pool_ref,
+                    &query,
+                    &fast_parameters,
+                    handles.decode_options.limits,
+                ))
+                .await;
+            let (rows, _) = rows.map_err(|e| tag_error(e, &execution_id))?;
+            first_column_to_list(rows, handles.decode_options)
+        })
+    }
+
+    /// Run a query and return its first result set as a `pyarrow.Table`, built from
+    /// the row stream column by column without creating Python objects per value.
+    #[pyo3(signature = (query, parameters=None))]
+    pub fn query_arrow<'p>(
+        &self,
+        py: Python<'p>,
+        query: String,
+        parameters: Option<&Bound<PyAny>>,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        self.collect_arrow(py, query, parameters, ArrowColumns::into_table)
+    }
+
+    /// Run a query and return its first result set as a `polars.DataFrame`, handed
+    /// over from the Arrow buffers `query_arrow()` builds without copying them.
+    #[pyo3(signature = (query, parameters=None))]
+    pub fn query_polars<'p>(
+        &self,
+        py: Python<'p>,
+        query: String,
+        parameters: Option<&Bound<PyAny>>,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        self.collect_arrow(py, query, parameters, ArrowColumns::into_polars)
+    }
+
+    /// Run a query and return its first result set as a dict of column name to 1-D
+    /// `numpy.ndarray`, filled from the same column buffers as `query_arrow()`.
+    #[pyo3(signature = (query, parameters=None))]
+    pub fn query_numpy<'p>(
+        &self,
+        py: Python<'p>,
+        query: String,
+        parameters: Option<&Bound<PyAny>>,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        self.collect_arrow(py, query, parameters, ArrowColumns::into_numpy)
+    }
+
+    /// Apply the `*.sql` scripts in `directory` that are not yet recorded in `table`,
+    /// in file name order, and return what happened to each script.  Scripts are split
+    /// on `GO` lines; each runs in a transaction with the insert of its version row
+    /// unless it holds a statement that cannot run in one.
+    #[pyo3(signature = (directory, *, table = migrations::DEFAULT_TABLE.to_string(), dry_run = false))]
+    pub fn apply_migrations<'p>(
+        &self,
+        py: Python<'p>,
+        directory: std::path::PathBuf,
+        table: String,
+        dry_run: bool,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        let scripts = migrations::load_scripts(&directory)?;
+        let handles = self.clone_handles();
+        for (sql, _) in scripts.iter().flat_map(|script| &script.batches) {
+            handles.check_statement(py, sql)?;
+        }
+        // One ID for the run; each batch is logged under it, and CONTEXT_INFO is set
+        // once for the session rather than ahead of batches that must come first.
+        let trace = handles.trace_statements(
+            py,
+            scripts
+                .iter()
+                .flat_map(|script| &script.batches)
+                .filter(|_| !dry_run)
+                .map(|(sql, _)| (sql.as_str(), LoggedParams::None)),
+        );
+
+        future_into_py(py, async move {
+            let pool_ref = handles.ensure_connected().await?;
+            let (outcomes, _) = handles
+                .with_messages(Self::apply_migrations_async_gil_free(
+                    &pool_ref, &trace, &scripts, &table, dry_run,
+                ))
+                .await;
+            let outcomes = outcomes.map_err(|e| trace.tag(e))?;
+            Python::attach(|py| migrations::outcomes_to_py(py, outcomes))
+        })
+    }
+
+    /// Iterate over the result sets of a multi-statement batch or stored procedure
+    /// (`async for rs in conn.stream_results(sql)`), each as its own `QueryStream`.
+    /// Result sets are fetched as the iterator advances rather than all up front.
+    #[pyo3(signature = (query, parameters=None))]
+    pub fn stream_results(
+        &self,
+        py: Python<'_>,
+        query: String,
+        parameters: Option<&Bound<PyAny>>,
+    ) -> PyResult<PyResultSetStream> {
+        let (query, fast_parameters) = self.bind(py, query, parameters)?;
+        let handles = self.clone_handles();
+        let (execution_id, query) =
+            handles.trace(py, query, LoggedParams::Positional(&fast_parameters));
+        let pending = PendingResultSets {
+            pool: handles.pool,
+            config: handles.config,
+            pool_config: handles.pool_config,
+            azure_credential: handles.azure_credential,
+            sql: query,
+            parameters: fast_parameters.into_vec(),
+            limits: handles.decode_options.limits,
+        };
+        Ok(PyResultSetStream::new(
+            pending,
+            handles.decode_options,
+            execution_id,
+        ))
+    }
+
+    /// Iterate over the rows of the first result set (`async for row in
+    /// conn.query_stream(sql)`), reading them from the server as the iterator advances
+    /// instead of buffering the whole result. The pooled connection stays checked out
+    /// until the rows are exhausted or `aclose()` is awaited.
+    #[pyo3(signature = (query, parameters=None))]
+    pub fn query_stream(
+        &self,
+        py: Python<'_>,
+        query: String,
+        parameters: Option<&Bound<PyAny>>,
+    ) -> PyResult<PyRowStream> {
+        let (query, fast_parameters) = self.bind(py, query, parameters)?;
+        let handles = self.clone_handles();
+        let (execution_id, query) =
+            handles.trace(py, query, LoggedParams::Positional(&fast_parameters));
+        let pending = PendingRows {
+            pool: handles.pool,
+            config: handles.config,
+            pool_config: handles.pool_config,
+            azure_credential: handles.azure_credential,
+            sql: query,
+            parameters: fast_parameters.into_vec(),
+            limits: handles.decode_options.limits,
+        };
+        Ok(PyRowStream::new(
+            pending,
+            handles.decode_options,
+            execution_id,
+        ))
+    }
+
+    /// Iterate over the first result set in lists of up to `chunk_size` rows
+    /// (`async for chunk in conn.fetch_chunks(sql)`). The next chunk is read from the
+    /// server while the current one is processed; the pooled connection stays checked
+    /// out until the rows are exhausted or `aclose()` is awaited.
+    #[pyo3(signature = (query, parameters=None, chunk_size=10_000))]
+    pub fn fetch_chunks(
+        &self,
+        py: Python<'_>,
+        query: String,
+        parameters: Option<&Bound<PyAny>>,
+        chunk_size: usize,
+    ) -> PyResult<PyChunkStream> {
+        let (query, fast_parameters) = self.bind(py, query, parameters)?;
+        let handles = self.clone_handles();
+        let (execution_id, query) =
+            handles.trace(py, query, LoggedParams::Positional(&fast_parameters));
+        let pending = PendingRows {
+            pool: handles.pool,
+            config: handles.config,
+            pool_config: handles.pool_config,
+            azure_credential: handles.azure_credential,
+            sql: query,
+            parameters: fast_parameters.into_vec(),
+            limits: handles.decode_options.limits,
+        };
+        PyChunkStream::new(pending, handles.decode_options, execution_id, chunk_size)
+    }
+
+    /// Fetch one page of `query`'s rows, at most `page_size`.  The result's
+    /// `next_token` (None on the last page) is passed back as `token=` to fetch the
+    /// page after it.  With `key` (a column name or list of names, unique together)
+    /// pages follow the key in ascending order; otherwise `query` must end with an
+    /// ORDER BY, and pages are read with OFFSET/FETCH.
+    #[pyo3(signature = (query, parameters=None, *, page_size, key=None, token=None))]
+    pub fn fetch_page<'p>(
+        &self,
+        py: Python<'p>,
+        query: String,
+        parameters: Option<&Bound<PyAny>>,
+        page_size: usize,
+        key: Option<&Bound<PyAny>>,
+        token: Option<&str>,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        if page_size == 0 {
+            return Err(PyValueError::new_err("page_size must be at least 1"));
+        }
+        let keys = page_keys(key)?;
+        let scope = token_scope(&query, &keys);
+        let position = match token {
+            Some(token) => decode_token(token, &scope)?,
+            None => PagePosition::Start,
+        };
+        let (query, mut fast_parameters) = self.bind(py, query, parameters)?;
+        let query = page_statement(&query, &keys, page_size, &position, &mut fast_parameters)?;
+        let handles = self.clone_handles();
+        let (execution_id, query) =
+            handles.trace(py, query, LoggedParams::Positional(&fast_parameters));
+
+        future_into_py(py, async move {
+            let pool_ref = handles.ensure_connected().await?;
+            let (result, messages) = handles
+                .with_messages(Self::execute_query_async_gil_free(
+                    &pool_ref,
+                    &query,
+                    &fast_parameters,
+                    handles.decode_options.limits,
+                ))
+                .await;
+            let (mut rows, metadata) = result.map_err(|e| tag_error(e, &execution_id))?;
+            let options = handles.decode_options;
+            Python::attach(|py| -> PyResult<Py<PyAny>> {
+                let next = if rows.len() > page_size {
+                    rows.truncate(page_size);
+                    let last = &rows[page_size - 1];
+                    Some(next_token(&scope, &position, &keys, page_size, last, options, py)?)
+                } else {
+                    None
+                };
+                let mut stream = PyQueryStream::from_first_result(rows, metadata, options, py)?
+                    .with_execution_id(execution_id)
+                    .with_messages(messages);
+                if let Some(next) = next {
+                    stream = stream.with_next_token(next);
+                }
+                Ok(Py::new(py, stream)?.into_any())
+            })
         })
     }
 
     #[pyo3(signature = (query))]
     pub fn simple_query<'p>(&self, py: Python<'p>, query: String) -> PyResult<Bound<'p, PyAny>> {
         let handles = self.clone_handles();
+        handles.check_statement(py, &query)?;
+        let (execution_id, query) = handles.trace(py, query, LoggedParams::None);
 
         future_into_py(py, async move {
             let pool_ref = handles.ensure_connected().await?;
-            let execution_result =
-                Self::execute_simple_query_async_gil_free(&pool_ref, &query).await?;
-            wrap_query_stream(execution_result)
+            let (execution_result, messages) = handles
+                .with_messages(Self::execute_simple_query_async_gil_free(
+                    &pool_ref,
+                    &query,
+                    handles.decode_options.limits,
+                ))
+                .await;
+            let execution_result = execution_result.map_err(|e| tag_error(e, &execution_id))?;
+            wrap_traced_query_stream(
+                execution_result,
+                handles.decode_options,
+                execution_id,
+                messages,
+            )
         })
     }
 
-    #[pyo3(signature = (query, parameters=None))]
+    /// Execute a statement and return the number of rows affected. With a
+    /// `retry_policy`, statements passed with `idempotent=True` are re-run when they
+    /// fail with a transient error; others run once, since a multi-statement batch may
+    /// have committed part of its work before failing. A statement still running after
+    /// `timeout` seconds is cancelled and raises `QueryTimeoutError`; with
+    /// `recover_on_timeout=True` its connection is kept if it answers a health check
+    /// after the cancel. A `label`
+    /// is appended to the statement as a comment, for finding it in Query Store, and
+    /// counted in `pool_stats()["labels"]`. With `on_progress`, the number of rows the
+    /// running statement has affected so far is passed to it every
+    /// `progress_interval` seconds (default 5) while the count changes; it is read
+    /// from `sys.dm_exec_query_profiles` on a second pooled connection, which needs
+    /// `VIEW SERVER STATE`. Cancelling the returned future stops the statement: with a
+    /// TDS attention signal when the connection is unencrypted after login, otherwise
+    /// (the default, encrypted) by closing the connection.
+    #[pyo3(signature = (query, parameters=None, *, idempotent=false, timeout=None, recover_on_timeout=false, label=None, on_progress=None, progress_interval=None))]
+    #[allow(clippy::too_many_arguments)]
     pub fn execute<'p>(
         &self,
         py: Python<'p>,
         query: String,
         parameters: Option<&Bound<PyAny>>,
+        idempotent: bool,
+        timeout: Option<f64>,
+        recover_on_timeout: bool,
+        label: Option<String>,
+        on_progress: Option<Py<PyAny>>,
+        progress_interval: Option<f64>,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        let timeout = statement_timeout(timeout)?;
+        let progress = progress_watch(py, on_progress, progress_interval)?;
+        let comment = label.as_deref().map(label_comment).transpose()?;
+        let (mut query, fast_parameters) = self.bind(py, query, parameters)?;
+        if let Some(comment) = &comment {
+            query.push_str(comment);
+        }
+        let handles = self.clone_handles();
+        let (execution_id, query) =
+            handles.trace(py, query, LoggedParams::Positional(&fast_parameters));
+        let label_stats = Arc::clone(&self.label_stats);
+
+        future_into_py(py, async move {
+            let (query, fast_parameters) = (&query, &fast_parameters);
+            let started = std::time::Instant::now();
+            let result = handles
+                .with_retries(idempotent, || async {
+                    let pool_ref = handles.ensure_connected().await?;
+                    let (per_statement, _) = handles
+                        .with_messages(Self::execute_command_async_gil_free(
+                            &pool_ref,
+                            query,
+                            fast_parameters,
+                            timeout,
+                            recover_on_timeout,
+                            progress.as_ref(),
+                        ))
+                        .await;
+                    per_statement
+                })
+                .await
+                .map(|per_statement| per_statement.iter().sum::<u64>());
+            if let Some(label) = &label {
+                label_stats.record(label, started.elapsed(), result.as_ref().ok().copied());
+            }
+            result.map_err(|e| tag_error(e, &execution_id))
+        })
+    }
+
+    /// Like `execute()`, but return the row count of each statement in `query` as a
+    /// list instead of their sum, e.g. `[3, 1]` for an `UPDATE` followed by a `DELETE`.
+    #[pyo3(signature = (query, parameters=None))]
+    pub fn execute_counts<'p>(
+        &self,
+        py: Python<'p>,
+        query: String,
+        parameters: Option<&Bound<PyAny>>,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        let (query, fast_parameters) = self.bind(py, query, parameters)?;
+        let handles = self.clone_handles();
+        let (execution_id, query) =
+            handles.trace(py, query, LoggedParams::Positional(&fast_parameters));
+
+        future_into_py(py, async move {
+            let pool_ref = handles.ensure_connected().await?;
+            let (per_statement, _) = handles
+                .with_messages(Self::execute_command_async_gil_free(
+                    &pool_ref,
+                    &query,
+                    &fast_parameters,
+                    None,
+                    false,
+                    None,
+                ))
+                .await;
+            per_statement.map_err(|e| tag_error(e, &execution_id))
+        })
+    }
+
+    /// Run a data-modifying statement and return both the rows it produced (e.g. from an
+    /// `OUTPUT` clause) and the number of rows it affected, as a `QueryStream` with
+    /// `rows_affected` set. The count is that of the last statement in `query`; the
+    /// per-statement counts of a batch or procedure are not available alongside rows,
+    /// since tiberius drops the DONE tokens carrying them while streaming rows.
+    #[pyo3(signature = (query, parameters=None))]
+    pub fn execute_returning<'p>(
+        &self,
+        py: Python<'p>,
+        query: String,
+        parameters: Option<&Bound<PyAny>>,
     ) -> PyResult<Bound<'p, PyAny>> {
-        let fast_parameters = convert_parameters_to_fast(parameters, py)?;
+        let (query, fast_parameters) = self.bind(py, query, parameters)?;
         let handles = self.clone_handles();
+        let (execution_id, query) =
+            handles.trace(py, query, LoggedParams::Positional(&fast_parameters));
 
         future_into_py(py, async move {
             let pool_ref = handles.ensure_connected().await?;
-            let affected_count =
-                Self::execute_command_async_gil_free(&pool_ref, &query, &fast_parameters).await?;
-            Ok(affected_count)
+            let (result, messages) = handles
+                .with_messages(Self::execute_returning_async_gil_free(
+                    &pool_ref,
+                    &query,
+                    &fast_parameters,
+                ))
+                .await;
+            let (rows, rows_affected) = result.map_err(|e| tag_error(e, &execution_id))?;
+            Python::attach(|py| -> PyResult<Py<PyAny>> {
+                let stream = PyQueryStream::from_tiberius_rows(rows, handles.decode_options, py)?
+                    .with_execution_id(execution_id)
+                    .with_rows_affected(rows_affected)
+                    .with_messages(messages);
+                Ok(Py::new(py, stream)?.into_any())
+            })
+        })
+    }
+
+    /// Run dynamic SQL through `sp_executesql`, binding `params` (a dict) by name so the
+    /// server reuses one plan per statement text. `outputs` maps OUTPUT parameter names
+    /// to their SQL types; a name also in `params` is input/output.
+    ///
+    /// Returns a `QueryStream` over the first result set with `rows_affected` and
+    /// `output_params` set. `returns_rows=True` raises if the statement produced no
+    /// result set; `returns_rows=False` returns the affected row count instead.
+    #[pyo3(signature = (sql, params=None, returns_rows=None, *, outputs=None))]
+    pub fn exec_sql<'p>(
+        &self,
+        py: Python<'p>,
+        sql: String,
+        params: Option<&Bound<PyDict>>,
+        returns_rows: Option<bool>,
+        outputs: Option<&Bound<PyDict>>,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        let mut inputs = Vec::new();
+        if let Some(params) = params {
+            for (name, value) in params.iter() {
+                let name = parameter_name(name.extract::<&str>()?)?;
+                inputs.push((name, python_to_fast_parameter(&value)?));
+            }
+        }
+        let mut output_types = Vec::new();
+        if let Some(outputs) = outputs {
+            if returns_rows == Some(false) && !outputs.is_empty() {
+                return Err(PyValueError::new_err(
+                    "OUTPUT parameters are returned on the QueryStream; \
+                     use returns_rows=None or True to read them",
+                ));
+            }
+            for (name, sql_type) in outputs.iter() {
+                let name = parameter_name(name.extract::<&str>()?)?;
+                output_types.push((name, sql_type.extract::<String>()?));
+            }
+        }
+        let handles = self.clone_handles();
+        handles.check_statement(py, &sql)?;
+        // Logged as written, before it is wrapped in the sp_executesql batch.
+        let execution_id = next_execution_id();
+        handles
+            .query_log
+            .log(py, &execution_id, &sql, LoggedParams::Named(&inputs));
+        let call = build_exec_sql(sql, inputs, output_types)?;
+        let batch = handles.with_context_info(&execution_id, call.sql);
+
+        future_into_py(py, async move {
+            let pool_ref = handles.ensure_connected().await?;
+            let (result, messages) = handles
+                .with_messages(Self::exec_sql_async_gil_free(
+                    &pool_ref,
+                    &batch,
+                    &call.parameters,
+                ))
+                .await;
+            let (result_sets, meta) = result.map_err(|e| tag_error(e, &execution_id))?;
+            if returns_rows == Some(true) && result_sets.is_empty() {
+                return Err(tag_error(
+                    PyValueError::new_err(
+                        "exec_sql() expected rows but the statement returned no result set",
+                    ),
+                    &execution_id,
+                ));
+            }
+
+            Python::attach(|py| -> PyResult<Py<PyAny>> {
+                let (rows_affected, output_params) =
+                    split_meta_row(meta, &call.output_names, handles.decode_options, py)?;
+                if returns_rows == Some(false) {
+                    return Ok(rows_affected.into_pyobject(py)?.into_any().unbind());
+                }
+                let rows = result_sets.into_iter().next().unwrap_or_default();
+                let stream = PyQueryStream::from_tiberius_rows(rows, handles.decode_options, py)?
+                    .with_execution_id(execution_id)
+                    .with_rows_affected(rows_affected)
+                    .with_output_params(output_params)
+                    .with_messages(messages);
+                Ok(Py::new(py, stream)?.into_any())
+            })
+        })
+    }
+
+    /// Report the tables `sql` would read and write, as `{"read": [...], "written": [...]}`
+    /// of `database.schema.table` names, from its estimated plan. The statement is
+    /// compiled but not run.
+    #[pyo3(signature = (sql, parameters=None))]
+    pub fn touched_tables<'p>(
+        &self,
+        py: Python<'p>,
+        sql: String,
+        parameters: Option<&Bound<PyAny>>,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        let (sql, fast_parameters) = self.bind(py, sql, parameters)?;
+        let handles = self.clone_handles();
+        // Logged but not prefixed with SET CONTEXT_INFO: the statement is only compiled,
+        // and the prefix would add a plan of its own.
+        let execution_id = next_execution_id();
+        handles.query_log.log(
+            py,
+            &execution_id,
+            &sql,
+            LoggedParams::Positional(&fast_parameters),
+        );
+
+        future_into_py(py, async move {
+            let pool_ref = handles.ensure_connected().await?;
+            let (plans, _) = handles
+                .with_messages(Self::showplan_async_gil_free(
+                    &pool_ref,
+                    &sql,
+                    &fast_parameters,
+                ))
+                .await;
+            let plans = plans.map_err(|e| tag_error(e, &execution_id))?;
+            Python::attach(|py| touched_tables_to_py(&plans, py))
+        })
+    }
+
+    /// Describe the columns of the first result set `sql` would return, without running it.
+    /// Reports declared types and lengths, which row metadata on the wire does not carry.
+    pub fn describe<'p>(&self, py: Python<'p>, sql: String) -> PyResult<Bound<'p, PyAny>> {
+        let handles = self.clone_handles();
+        let parameters = [FastParameter::String(sql)];
+        let (execution_id, query) = handles.trace(
+            py,
+            DESCRIBE_SQL.to_string(),
+            LoggedParams::Positional(&parameters),
+        );
+
+        future_into_py(py, async move {
+            let pool_ref = handles.ensure_connected().await?;
+            let (rows, _) = handles
+                .with_messages(Self::execute_query_async_gil_free(
+                    &pool_ref,
+                    &query,
+                    &parameters,
+                    ResultLimits::default(),
+                ))
+                .await;
+            let (rows, _) = rows.map_err(|e| tag_error(e, &execution_id))?;
+            Python::attach(|py| describe_rows_to_py(&rows, py))
+        })
+    }
+
+    /// Describe the columns of a table from the catalog, flagging identity, computed,
+    /// rowversion, GENERATED ALWAYS and defaulted columns. Raises ValueError if the
+    /// table does not exist.
+    pub fn table_columns<'p>(&self, py: Python<'p>, table: String) -> PyResult<Bound<'p, PyAny>> {
+        let handles = self.clone_handles();
+        let parameters = [FastParameter::String(table.clone())];
+        let (execution_id, query) = handles.trace(
+            py,
+            TABLE_COLUMNS_SQL.to_string(),
+            LoggedParams::Positional(&parameters),
+        );
+
+        future_into_py(py, async move {
+            let pool_ref = handles.ensure_connected().await?;
+            let (rows, _) = handles
+                .with_messages(Self::execute_query_async_gil_free(
+                    &pool_ref,
+                    &query,
+                    &parameters,
+                    ResultLimits::default(),
+                ))
+                .await;
+            let (rows, _) = rows.map_err(|e| tag_error(e, &execution_id))?;
+            Python::attach(|py| table_columns_to_py(&table, &rows, py))
         })
     }
 
@@ -272,10 +1345,56 @@ impl PyConnection {
         })
     }
 
-    pub fn pool_stats<'p>(&self, py: Python<'p>) -> PyResult<Bound<'p, PyAny>> {
+    /// Security details of a pooled connection: TDS and TLS versions, cipher suite, the
+    /// server certificate's subject, issuer and validity, encryption and auth scheme.
+    pub fn server_info<'p>(&self, py: Python<'p>) -> PyResult<Bound<'p, PyAny>> {
+        let handles = self.clone_handles();
+        future_into_py(py, async move {
+            let pool_ref = handles.ensure_connected().await?;
+            let mut conn = pool_ref.checkout().await?;
+            let handshake = conn.handshake().clone();
+            let row = conn
+                .request(async |client| {
+                    match client.simple_query(CONNECTION_PROPERTIES_SQL).await {
+                        Ok(stream) => stream.into_row().await,
+                        Err(e) => Err(e),
+                    }
+                })
+                .await
+                .map_err(|e| create_sql_error(e, "Reading connection properties failed"))?
+                .ok_or_else(|| DatabaseError::new_err("Connection properties missing"))?;
+            drop(conn);
+
+            let protocol_version = row.get::<i64, _>(0);
+            let encrypted = row
+                .get::<&str, _>(1)
+                .map(|v| v.eq_ignore_ascii_case("TRUE"));
+            let auth_scheme = row.get::<&str, _>(2).map(str::to_string);
+            let net_transport = row.get::<&str, _>(3).map(str::to_string);
+            Python::attach(|py| {
+                let dict = PyDict::new(py);
+                dict.set_item("tds_version", protocol_version.map(tds_version_name))?;
+                dict.set_item("encrypted", encrypted)?;
+                dict.set_item("auth_scheme", auth_scheme)?;
+                dict.set_item("net_transport", net_transport)?;
+                handshake.fill_dict(&dict)?;
+                Ok(dict.unbind())
+            })
+        })
+    }
+
+    /// Statistics for this connection's pool, including the server/database/user `key`
+    /// it is registered under. With `by_key=True`, `by_key` also lists the totals for
+    /// every connected pool in the process, one entry per key. `labels` holds the
+    /// counters of statements run with `execute(..., label=...)`.
+    #[pyo3(signature = (by_key=false))]
+    pub fn pool_stats<'p>(&self, py: Python<'p>, by_key: bool) -> PyResult<Bound<'p, PyAny>> {
         let pool = self.pool.clone();
-        let max_size = self.pool_config.max_size;
-        let min_idle = self.pool_config.min_idle;
+        let settings = self.settings();
+        let max_size = settings.pool_config.max_size;
+        let min_idle = settings.pool_config.min_idle;
+        let pool_key = settings.pool_key;
+        let label_stats = Arc::clone(&self.label_stats);
 
         future_into_py(py, async move {
             let (is_connected, connections, idle_connections) = {
@@ -287,6 +1406,11 @@ impl PyConnection {
                     (false, 0u32, 0u32)
                 }
             };
+            let per_key = if by_key {
+                Some(pool_registry::stats_by_key().await?)
+            } else {
+                None
+            };
 
             Python::try_attach(|py| {
                 let dict = pyo3::types::PyDict::new(py);
@@ -299,6 +1423,11 @@ impl PyConnection {
                 )?;
                 dict.set_item("max_size", max_size)?;
                 dict.set_item("min_idle", min_idle)?;
+                dict.set_item("key", pool_registry::key_to_py(&pool_key, py)?)?;
+                dict.set_item("labels", label_stats.to_py(py)?)?;
+                if let Some(per_key) = per_key {
+                    dict.set_item("by_key", per_key)?;
+                }
                 Ok(dict.unbind())
             })
             .ok_or_else(|| {
@@ -307,6 +1436,93 @@ impl PyConnection {
         })
     }
 
+    /// A summary of this connection's health for a `/healthz` handler: `status`
+    /// ("ok", "degraded" or "down"), the round-trip time of a `SELECT 1` ping through
+    /// the pool, pool saturation, circuit state and the latest connection error.
+    /// Every value is JSON-serialisable. The ping gives up after `timeout` seconds.
+    #[pyo3(signature = (timeout=5.0))]
+    pub fn health_report<'p>(&self, py: Python<'p>, timeout: f64) -> PyResult<Bound<'p, PyAny>> {
+        let limit = statement_timeout(Some(timeout))?.unwrap_or(Duration::from_secs(5));
+        let handles = self.clone_handles();
+        let max_size = handles.pool_config.max_size;
+
+        future_into_py(py, async move {
+            let started = std::time::Instant::now();
+            let ping = tokio::time::timeout(limit, async {
+                let pool_ref = handles.ensure_connected().await?;
+                let mut conn = pool_ref.checkout().await?;
+                conn.request(async |client| match client.simple_query("SELECT 1").await {
+                    Ok(stream) => stream.into_row().await,
+                    Err(e) => Err(e),
+                })
+                .await
+                .map_err(|e| create_sql_error(e, "Health check ping failed"))?;
+                Ok::<_, PyErr>(())
+            })
+            .await;
+            let ping_ms = started.elapsed().as_secs_f64() * 1000.0;
+            let checked_at = chrono::Utc::now();
+
+            let pool = handles.pool.read().await.clone();
+            let (connections, idle_connections) = pool
+                .as_ref()
+                .map(|p| {
+                    let state = p.state();
+                    (state.connections, state.idle_connections)
+                })
+                .unwrap_or((0, 0));
+            let active_connections = connections.saturating_sub(idle_connections);
+            let saturation = active_connections as f64 / max_size as f64;
+            // No breaker rejects calls: the circuit is "open" while the pool's latest
+            // attempt to open a connection has failed.
+            let circuit_open = pool
+                .as_ref()
+                .is_some_and(|p| p.last_connect_error().is_some());
+            let last_failure = pool.as_ref().and_then(|p| p.last_failure());
+
+            Python::attach(|py| {
+                let ping_error = match ping {
+                    Ok(Ok(())) => None,
+                    Ok(Err(e)) => Some(e.value(py).str()?.to_string()),
+                    Err(_) => Some(format!("Ping timed out after {}s", limit.as_secs_f64())),
+                };
+                let (last_error, last_error_at) = match (last_failure, &ping_error) {
+                    (Some((error, at)), _) => (Some(error), Some(at)),
+                    // The pool could not be created, so no attempt was recorded.
+                    (None, Some(error)) if pool.is_none() => {
+                        (Some(error.clone()), Some(checked_at))
+                    }
+                    (None, _) => (None, None),
+                };
+                let status = if ping_error.is_some() {
+                    "down"
+                } else if circuit_open || saturation >= 1.0 {
+                    "degraded"
+                } else {
+                    "ok"
+                };
+                let iso = |at: chrono::DateTime<chrono::Utc>| {
+                    at.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+                };
+
+                let dict = PyDict::new(py);
+                dict.set_item("status", status)?;
+                dict.set_item("checked_at", iso(checked_at))?;
+                dict.set_item("ping_ms", ping_error.is_none().then_some(ping_ms))?;
+                dict.set_item("ping_error", ping_error)?;
+                dict.set_item("connections", connections)?;
+                dict.set_item("idle_connections", idle_connections)?;
+                dict.set_item("active_connections", active_connections)?;
+                dict.set_item("max_size", max_size)?;
+                dict.set_item("saturation", saturation)?;
+                dict.set_item("circuit", if circuit_open { "open" } else { "closed" })?;
+                dict.set_item("last_error", last_error)?;
+                dict.set_item("last_error_at", last_error_at.map(iso))?;
+                Ok(dict.unbind())
+            })
+        })
+    }
+
     pub fn __aenter__<'p>(slf: Bound<'p, Self>, py: Python<'p>) -> PyResult<Bound<'p, PyAny>> {
         let handles = slf.borrow().clone_handles();
         let slf_clone = slf.clone().unbind();
@@ -357,34 +1573,299 @@ impl PyConnection {
         py: Python<'p>,
         queries: &Bound<'p, PyList>,
     ) -> PyResult<Bound<'p, PyAny>> {
+        self.check_batch_items(py, queries)?;
         let handles = self.clone_handles();
+        let batch_queries = parse_batch_items(queries, py)?;
+        let trace = handles.trace_statements(
+            py,
+            batch_queries
+                .iter()
+                .map(|(sql, params)| (sql.as_str(), LoggedParams::Positional(params))),
+        );
         query_batch(
             handles.pool,
             handles.config,
             handles.pool_config,
             handles.azure_credential,
+            handles.decode_options,
             py,
-            queries,
+            batch_queries,
+            trace,
         )
     }
 
+    /// Insert rows into `table_name`. By default rows are sent as chunked multi-row
+    /// INSERT statements; `bulk_copy=True` streams them with the TDS bulk load
+    /// protocol instead, which is much faster for large loads but gives unlisted
+    /// columns NULL rather than their defaults.  With `verify_key` the rows of the
+    /// table in the loaded key range are counted and checksummed afterwards, and a
+    /// report dict is returned instead of the count.
+    #[pyo3(signature = (table_name, columns, data_rows, *, bulk_copy=false, verify_key=None))]
     pub fn bulk_insert<'p>(
         &self,
         py: Python<'p>,
         table_name: String,
         columns: Vec<String>,
         data_rows: &Bound<'p, PyList>,
+        bulk_copy: bool,
+        verify_key: Option<String>,
     ) -> PyResult<Bound<'p, PyAny>> {
         let handles = self.clone_handles();
+        if bulk_copy {
+            let rows = prepare_bulk_load(table_name, columns, data_rows, verify_key)?;
+            let trace = handles.trace_statements(py, [(&*rows.logged_sql()?, LoggedParams::None)]);
+            return bulk_load(
+                handles.pool,
+                handles.config,
+                handles.pool_config,
+                handles.azure_credential,
+                py,
+                rows,
+                trace,
+            );
+        }
+        let rows = prepare_bulk_insert(table_name, columns, data_rows, verify_key)?;
+        let trace = handles.trace_statements(py, [(&*rows.logged_sql()?, LoggedParams::None)]);
         bulk_insert(
             handles.pool,
             handles.config,
             handles.pool_config,
             handles.azure_credential,
             py,
-            table_name,
-            columns,
-            data_rows,
+            rows,
+            trace,
+        )
+    }
+
+    /// Bulk load a `pyarrow.Table` (or `RecordBatch`) into `table_name` with the TDS
+    /// bulk load protocol.  Columns are matched to the table's by name and each Arrow
+    /// value is encoded as its column's SQL type; like `bulk_copy=True`, unlisted
+    /// columns get NULL rather than their defaults.  Returns the number of rows loaded.
+    pub fn bulk_insert_arrow<'p>(
+        &self,
+        py: Python<'p>,
+        table_name: String,
+        arrow_table: &Bound<'p, PyAny>,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        let rows = prepare_arrow_bulk_load(table_name, arrow_table)?;
+        let handles = self.clone_handles();
+        let trace = handles.trace_statements(py, [(&*rows.logged_sql()?, LoggedParams::None)]);
+        bulk_load(
+            handles.pool,
+            handles.config,
+            handles.pool_config,
+            handles.azure_credential,
+            py,
+            rows,
+            trace,
+        )
+    }
+
+    /// Bulk load a `pandas.DataFrame` into `table_name`, `chunk_size` rows per bulk load,
+    /// all on one connection.  The frame goes through `pyarrow.Table.from_pandas()`,
+    /// so dtypes map as they do there and NaN, NaT and `pd.NA` become NULL; the index
+    /// is not loaded.  Chunks already loaded stay when a later one fails.  Returns the
+    /// number of rows loaded.
+    #[pyo3(signature = (table_name, frame, *, chunk_size=100_000))]
+    pub fn bulk_insert_dataframe<'p>(
+        &self,
+        py: Python<'p>,
+        table_name: String,
+        frame: &Bound<'p, PyAny>,
+        chunk_size: usize,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        let first = prepare_dataframe_load(&table_name, frame, chunk_size)?;
+        let handles = self.clone_handles();
+        let trace = handles.trace_statements(py, [(&*first.logged_sql()?, LoggedParams::None)]);
+        bulk_load_dataframe(
+            handles.pool,
+            handles.config,
+            handles.pool_config,
+            handles.azure_credential,
+            py,
+            frame,
+            first,
+            chunk_size,
+            trace,
+        )
+    }
+
+    /// Send all statements queued in a `Batch` as one transaction in a single round trip.
+    /// Returns the total number of affected rows; nothing is committed if any statement fails.
+    pub fn apply<'p>(
+        &self,
+        py: Python<'p>,
+        batch: PyRef<'p, PyBatch>,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        let handles = self.clone_handles();
+        for sql in batch.statements() {
+            handles.check_statement(py, sql)?;
+        }
+        let trace = handles.trace_statements(py, batch.logged_statements());
+        apply_batch(
+            handles.pool,
+            handles.config,
+            handles.pool_config,
+            handles.azure_credential,
+            py,
+            &batch,
+            trace,
+        )
+    }
+
+    /// Create another `Connection` backed by the same pool (and credentials) as this one,
+    /// with different per-connection defaults. Options left as None are inherited.
+    #[pyo3(signature = (*, rstrip_char = None, time_as_timedelta = None, parse_json = None, parse_xml = None, trace_context_info = None, log_queries = None, log_parameters = None, redact_parameters = None, on_message = None, retry_policy = None, paramstyle = None, statement_policy = None, max_result_columns = None, max_row_bytes = None))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn with_options(
+        &self,
+        rstrip_char: Option<bool>,
+        time_as_timedelta: Option<bool>,
+        parse_json: Option<&Bound<PyAny>>,
+        parse_xml: Option<&Bound<PyAny>>,
+        trace_context_info: Option<bool>,
+        log_queries: Option<bool>,
+        log_parameters: Option<bool>,
+        redact_parameters: Option<&Bound<PyAny>>,
+        on_message: Option<Py<PyAny>>,
+        retry_policy: Option<PyRetryPolicy>,
+        paramstyle: Option<&str>,
+        statement_policy: Option<&Bound<PyAny>>,
+        max_result_columns: Option<usize>,
+        max_row_bytes: Option<usize>,
+    ) -> PyResult<PyConnection> {
+        let query_log =
+            self.query_log
+                .with_overrides(log_queries, log_parameters, redact_parameters)?;
+        let mut decode_options = self.decode_options;
+        if let Some(rstrip_char) = rstrip_char {
+            decode_options.rstrip_char = rstrip_char;
+        }
+        if let Some(time_as_timedelta) = time_as_timedelta {
+            decode_options.time_as_timedelta = time_as_timedelta;
+        }
+        if parse_json.is_some() {
+            decode_options.json = parse_json_option(parse_json)?;
+        }
+        if parse_xml.is_some() {
+            decode_options.xml = parse_xml_option(parse_xml)?;
+        }
+        let limits = ResultLimits::new(max_result_columns, max_row_bytes)?;
+        if let Some(max_columns) = limits.max_columns {
+            decode_options.limits.max_columns = Some(max_columns);
+        }
+        if let Some(max_row_bytes) = limits.max_row_bytes {
+            decode_options.limits.max_row_bytes = Some(max_row_bytes);
+        }
+        let paramstyle = match paramstyle {
+            Some(name) => ParamStyle::parse(name).map_err(PyValueError::new_err)?,
+            None => self.paramstyle,
+        };
+        Ok(PyConnection {
+            pool: Arc::clone(&self.pool),
+            settings: Arc::clone(&self.settings),
+            decode_options,
+            trace_context_info: trace_context_info.unwrap_or(self.trace_context_info),
+            query_log: Arc::new(query_log),
+            on_message: on_message.map(Arc::new).or_else(|| self.on_message.clone()),
+            retry_policy: retry_policy.or_else(|| self.retry_policy.clone()),
+            statement_policy: statement_policy_option(statement_policy)?
+                .or_else(|| self.statement_policy.clone()),
+            paramstyle,
+            label_stats: Arc::clone(&self.label_stats),
+        })
+    }
+
+    /// Apply new pool sizing, TLS settings or credential without restarting: a pool
+    /// is built from the new settings and swapped in, and the old pool closes once
+    /// the requests running on it have finished.  Options left as None are kept.  A
+    /// connection that has not connected yet just takes the new settings.  The
+    /// settings are shared with `with_options()` copies, which see the change too.
+    #[pyo3(signature = (*, new_pool_config = None, new_ssl_config = None, new_azure_credential = None))]
+    pub fn reload_config<'p>(
+        &self,
+        py: Python<'p>,
+        new_pool_config: Option<PyPoolConfig>,
+        new_ssl_config: Option<PySslConfig>,
+        new_azure_credential: Option<PyAzureCredential>,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        let current = self.settings();
+        let config = match &new_ssl_config {
+            Some(ssl_config) => {
+                let Some(base) = &current.config_without_ssl else {
+                    return Err(PyValueError::new_err(
+                        "new_ssl_config requires a Connection created with server=...; \
+                         a connection string's Encrypt and TrustServerCertificate \
+                         settings cannot be replaced",
+                    ));
+                };
+                let mut config = base.as_ref().clone();
+                ssl_config.apply_to_config(&mut config);
+                Arc::new(config)
+            }
+            None => Arc::clone(&current.config),
+        };
+        let pool_config = new_pool_config.unwrap_or_else(|| current.pool_config.clone());
+        let pool_key = match &new_azure_credential {
+            Some(credential) => Arc::new(PoolKey::new(
+                Some(&current.pool_key.server),
+                current.pool_key.database.as_deref(),
+                None,
+                Some(credential),
+            )),
+            None => Arc::clone(&current.pool_key),
+        };
+        let azure_credential = match new_azure_credential {
+            Some(credential) => Some(Arc::new(credential)),
+            None => current.azure_credential.clone(),
+        };
+        let pool = Arc::clone(&self.pool);
+        let settings = Arc::clone(&self.settings);
+
+        future_into_py(py, async move {
+            let connected = pool.read().await.is_some();
+            if connected {
+                replace_pool(
+                    Arc::clone(&pool),
+                    Arc::clone(&config),
+                    &pool_config,
+                    azure_credential.clone(),
+                )
+                .await?;
+            }
+            pool_registry::update(&pool, &pool_key, pool_config.max_size);
+            let mut settings = match settings.write() {
+                Ok(settings) => settings,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            settings.config = config;
+            if new_ssl_config.is_some() {
+                settings._ssl_config = new_ssl_config;
+            }
+            settings.pool_config = pool_config;
+            settings.azure_credential = azure_credential;
+            settings.pool_key = pool_key;
+            Ok(())
+        })
+    }
+
+    /// Hold one pooled connection for several statements
+    /// (`async with conn.acquire() as pinned:`), so temp tables, SET options and other
+    /// session state carry from one statement to the next.  The connection goes back to
+    /// the pool when the block exits.
+    pub fn acquire(&self) -> PyPinnedConnection {
+        PyPinnedConnection::new(self.clone_handles(), self.paramstyle)
+    }
+
+    /// Create a dedicated (non-pooled) `Transaction` that targets the same server with
+    /// the same credentials as this connection. The connection is opened lazily on `begin()`.
+    pub fn transaction(&self) -> Transaction {
+        let settings = self.settings();
+        Transaction::from_parts(
+            settings.config,
+            settings.azure_credential.as_deref().cloned(),
+            self.decode_options,
+            self.statement_policy.clone(),
         )
     }
 
@@ -393,7 +1874,21 @@ impl PyConnection {
         py: Python<'p>,
         commands: &Bound<'p, PyList>,
     ) -> PyResult<Bound<'p, PyAny>> {
+        self.check_batch_items(py, commands)?;
         let handles = self.clone_handles();
-        execute_batch(handles.config, handles.azure_credential, py, commands)
+        let batch_commands = parse_batch_items(commands, py)?;
+        let trace = handles.trace_statements(
+            py,
+            batch_commands
+                .iter()
+                .map(|(sql, params)| (sql.as_str(), LoggedParams::Positional(params))),
+        );
+        execute_batch(
+            handles.config,
+            handles.azure_credential,
+            py,
+            batch_commands,
+            trace,
+        )
     }
 }
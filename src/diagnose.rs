@@ -0,0 +1,335 @@
+//! Step-by-step connectivity check for `fastmssql.diagnose()`.
+//!
+//! Opening a pool reports a single "connection failed" for what may be a DNS typo, a
+//! firewall, an encryption mismatch or a bad password.  The check runs each stage of
+//! a connection on its own (name resolution, TCP connect, Azure token when one is
+//! configured, TLS handshake, login, `SELECT 1`) and stops at the first failure, so
+//! the report says which one it was.  Connects go through the server's
+//! [`proxy`](crate::proxy) route when it has one, as pooled connections do.
+//!
+//! tiberius performs the TLS handshake and the login in one call; a TLS error is
+//! reported against the handshake and a server error (e.g. 18456) against the login,
+//! which by then has completed the handshake.
+
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tiberius::Config;
+use tiberius::error::Error as TError;
+use tokio::net::TcpStream;
+use tokio_util::compat::TokioAsyncWriteCompatExt;
+
+use crate::azure_auth::PyAzureCredential;
+use crate::connection::PyConnection;
+use crate::{host_resolver, proxy, tcp_connect};
+
+struct Step {
+    name: &'static str,
+    ok: bool,
+    elapsed_ms: f64,
+    detail: String,
+    code: Option<u32>,
+}
+
+#[derive(Default)]
+struct Report {
+    address: String,
+    proxy: Option<String>,
+    steps: Vec<Step>,
+}
+
+impl Report {
+    fn pass(&mut self, name: &'static str, started: Instant, detail: impl Into<String>) {
+        self.push(name, true, started, detail.into(), None);
+    }
+
+    fn fail(&mut self, name: &'static str, started: Instant, detail: impl Into<String>) {
+        self.push(name, false, started, detail.into(), None);
+    }
+
+    fn push(
+        &mut self,
+        name: &'static str,
+        ok: bool,
+        started: Instant,
+        detail: String,
+        code: Option<u32>,
+    ) {
+        self.steps.push(Step {
+            name,
+            ok,
+            elapsed_ms: started.elapsed().as_secs_f64() * 1000.0,
+            detail,
+            code,
+        });
+    }
+
+    fn to_py(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        let failed = self.steps.iter().find(|step| !step.ok);
+        let steps = PyList::empty(py);
+        for step in &self.steps {
+            let dict = PyDict::new(py);
+            dict.set_item("step", step.name)?;
+            dict.set_item("ok", step.ok)?;
+            dict.set_item("elapsed_ms", step.elapsed_ms)?;
+            dict.set_item("detail", &step.detail)?;
+            dict.set_item("code", step.code)?;
+            steps.append(dict)?;
+        }
+        let report = PyDict::new(py);
+        report.set_item("ok", failed.is_none())?;
+        report.set_item("failed_step", failed.map(|step| step.name))?;
+        report.set_item("address", &self.address)?;
+        report.set_item("proxy", &self.proxy)?;
+        report.set_item("steps", steps)?;
+        Ok(report.into_any().unbind())
+    }
+}
+
+fn timed_out(limit: Duration) -> String {
+    format!("timed out after {:.1}s", limit.as_secs_f64())
+}
+
+/// Resolve `address` and connect to the first address that accepts, recording the
+/// DNS and TCP steps. A server with a proxy route is connected to through the proxy,
+/// which resolves its name, so there is no DNS step. None once a step has failed.
+async fn resolve_and_connect(
+    report: &mut Report,
+    address: &str,
+    limit: Duration,
+) -> Option<TcpStream> {
+    let (host, _) = host_resolver::split_host_port(address);
+    if let Some(proxy) = proxy::describe_route(host) {
+        report.proxy = Some(proxy.clone());
+        let started = Instant::now();
+        return match tokio::time::timeout(limit, tcp_connect::connect(address)).await {
+            Ok(Ok(tcp)) => {
+                let _ = tcp.set_nodelay(true);
+                report.pass(
+                    "tcp",
+                    started,
+                    format!("connected to {} via {}", address, proxy),
+                );
+                Some(tcp)
+            }
+            Ok(Err(e)) => {
+                report.fail("tcp", started, format!("{}: {}", address, e));
+                None
+            }
+            Err(_) => {
+                report.fail(
+                    "tcp",
+                    started,
+                    format!("{} via {}: {}", address, proxy, timed_out(limit)),
+                );
+                None
+            }
+        };
+    }
+
+    let started = Instant::now();
+    let resolved: Vec<SocketAddr> =
+        match tokio::time::timeout(limit, host_resolver::lookup(address)).await {
+            Ok(Ok(addrs)) => addrs,
+            Ok(Err(e)) => {
+                report.fail(
+                    "dns",
+                    started,
+                    format!("could not resolve {}: {}", address, e),
+                );
+                return None;
+            }
+            Err(_) => {
+                report.fail("dns", started, timed_out(limit));
+                return None;
+            }
+        };
+    if resolved.is_empty() {
+        report.fail(
+            "dns",
+            started,
+            format!("{} resolved to no addresses", address),
+        );
+        return None;
+    }
+    let listed: Vec<String> = resolved.iter().map(SocketAddr::to_string).collect();
+    report.pass("dns", started, listed.join(", "));
+
+    let started = Instant::now();
+    let mut errors = Vec::new();
+    for addr in &resolved {
+        match tokio::time::timeout(limit, TcpStream::connect(addr)).await {
+            Ok(Ok(tcp)) => {
+                let _ = tcp.set_nodelay(true);
+                report.pass("tcp", started, format!("connected to {}", addr));
+                return Some(tcp);
+            }
+            Ok(Err(e)) => errors.push(format!("{}: {}", addr, e)),
+            Err(_) => errors.push(format!("{}: {}", addr, timed_out(limit))),
+        }
+    }
+    report.fail("tcp", started, errors.join("; "));
+    None
+}
+
+async fn run(
+    mut config: Config,
+    azure_credential: Option<Arc<PyAzureCredential>>,
+    limit: Duration,
+) -> Report {
+    let mut report = Report {
+        address: config.get_addr(),
+        ..Report::default()
+    };
+    let address = report.address.clone();
+    let Some(mut tcp) = resolve_and_connect(&mut report, &address, limit).await else {
+        return report;
+    };
+
+    if let Some(credential) = azure_credential {
+        let started = Instant::now();
+        match tokio::time::timeout(limit, credential.to_auth_method()).await {
+            Ok(Ok(auth)) => {
+                config.authentication(auth);
+                report.pass(
+                    "token",
+                    started,
+                    format!("{:?} token acquired", credential.credential_type),
+                );
+            }
+            Ok(Err(e)) => {
+                report.fail("token", started, e.to_string());
+                return report;
+            }
+            Err(_) => {
+                report.fail("token", started, timed_out(limit));
+                return report;
+            }
+        }
+    }
+
+    let mut redirected = None;
+    let started = Instant::now();
+    let client = loop {
+        let connect = tiberius::Client::connect(config.clone(), tcp.compat_write());
+        match tokio::time::timeout(limit, connect).await {
+            Ok(Ok(client)) => break client,
+            // Azure SQL gateways hand the session to another node; follow it once.
+            Ok(Err(TError::Routing { host, port })) if redirected.is_none() => {
+                let target = format!("{}:{}", host, port);
+                config.host(&host);
+                config.port(port);
+                match tokio::time::timeout(limit, tcp_connect::connect(&target)).await {
+                    Ok(Ok(next)) => {
+                        let _ = next.set_nodelay(true);
+                        tcp = next;
+                        redirected = Some(match proxy::describe_route(&host) {
+                            Some(proxy) => format!("{} via {}", target, proxy),
+                            None => target,
+                        });
+                    }
+                    Ok(Err(e)) => {
+                        report.fail("tcp", started, format!("redirect to {}: {}", target, e));
+                        return report;
+                    }
+                    Err(_) => {
+                        report.fail(
+                            "tcp",
+                            started,
+                            format!("redirect to {}: {}", target, timed_out(limit)),
+                        );
+                        return report;
+                    }
+                }
+            }
+            Ok(Err(TError::Server(e))) => {
+                report.pass("tls", started, "handshake completed");
+                report.push(
+                    "login",
+                    false,
+                    started,
+                    format!("{} (error {}, state {})", e.message(), e.code(), e.state()),
+                    Some(e.code()),
+                );
+                return report;
+            }
+            Ok(Err(TError::Tls(msg))) => {
+                report.fail("tls", started, msg);
+                return report;
+            }
+            Ok(Err(e)) => {
+                report.fail(
+                    "tls",
+                    started,
+                    format!("connection failed during TLS handshake or login: {}", e),
+                );
+                return report;
+            }
+            Err(_) => {
+                report.fail("tls", started, timed_out(limit));
+                return report;
+            }
+        }
+    };
+    report.pass("tls", started, "handshake completed");
+    let detail = match redirected {
+        Some(target) => format!("logged in via {}", target),
+        None => "logged in".to_string(),
+    };
+    report.pass("login", started, detail);
+
+    let mut client = client;
+    let started = Instant::now();
+    let query = async { client.simple_query("SELECT 1").await?.into_row().await };
+    match tokio::time::timeout(limit, query).await {
+        Ok(Ok(Some(_))) => report.pass("query", started, "SELECT 1 returned a row"),
+        Ok(Ok(None)) => report.fail("query", started, "SELECT 1 returned no rows"),
+        Ok(Err(e)) => report.fail("query", started, e.to_string()),
+        Err(_) => report.fail("query", started, timed_out(limit)),
+    }
+    report
+}
+
+/// Run the checks for `config` and return the report as a dict.
+fn diagnose_config<'p>(
+    py: Python<'p>,
+    config: Config,
+    azure_credential: Option<Arc<PyAzureCredential>>,
+    timeout: f64,
+) -> PyResult<Bound<'p, PyAny>> {
+    if !(timeout.is_finite() && timeout > 0.0) {
+        return Err(PyValueError::new_err(
+            "timeout must be a positive number of seconds",
+        ));
+    }
+    let limit = Duration::from_secs_f64(timeout);
+    pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let report = run(config, azure_credential, limit).await;
+        Python::attach(|py| report.to_py(py))
+    })
+}
+
+/// Check connectivity step by step for a connection string or `Connection`, returning
+/// a report of each step and the first one that failed. Never raises for connection
+/// problems; those are what the report describes.
+#[pyfunction]
+#[pyo3(signature = (target, timeout = 10.0))]
+pub fn diagnose<'p>(
+    py: Python<'p>,
+    target: &Bound<'p, PyAny>,
+    timeout: f64,
+) -> PyResult<Bound<'p, PyAny>> {
+    if let Ok(conn) = target.cast::<PyConnection>() {
+        let (config, azure_credential) = conn.borrow().connect_target();
+        return diagnose_config(py, config, azure_credential, timeout);
+    }
+    let connection_string: String = target.extract().map_err(|_| {
+        PyTypeError::new_err("diagnose() expects a connection string or a Connection")
+    })?;
+    let config = Config::from_ado_string(&connection_string)
+        .map_err(|e| PyValueError::new_err(format!("Invalid connection string: {}", e)))?;
+    diagnose_config(py, config, None, timeout)
+}
@@ -0,0 +1,159 @@
+//! Progress of a long-running data-modifying statement (`execute(on_progress=...)`).
+//!
+//! tiberius reports row counts only once a statement completes, and `@@ROWCOUNT` is
+//! private to the session running it, so progress is read from the server instead:
+//! while the statement runs, a second pooled connection polls
+//! `sys.dm_exec_query_profiles` for the rows that have gone through the topmost
+//! insert, update, delete or merge operator of the statement's plan.  That view is
+//! filled by lightweight query profiling (on by default from SQL Server 2019 and in
+//! Azure SQL) and needs `VIEW SERVER STATE` (`VIEW DATABASE STATE` on Azure SQL
+//! Database).  If polling fails, a `RuntimeWarning` is issued once and the statement
+//! carries on unobserved.
+
+use pyo3::exceptions::{PyRuntimeWarning, PyValueError};
+use pyo3::prelude::*;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+use crate::pool_manager::{ConnectionPool, TiberiusClient};
+
+/// Seconds between polls when `progress_interval` is not given.
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Rows through the topmost DML operator of the statement `@P1` is running, summed
+/// over the threads of a parallel plan; NULL between statements or before the plan
+/// reaches it.
+const PROGRESS_SQL: &str = "SELECT SUM(row_count) FROM sys.dm_exec_query_profiles \
+    WHERE session_id = @P1 AND node_id = (\
+        SELECT MIN(node_id) FROM sys.dm_exec_query_profiles \
+        WHERE session_id = @P1 AND (physical_operator_name LIKE N'%Insert' \
+            OR physical_operator_name LIKE N'%Update' \
+            OR physical_operator_name LIKE N'%Delete' \
+            OR physical_operator_name LIKE N'%Merge'))";
+
+/// The `on_progress` callback of an `execute()` call and how often to call it.
+#[derive(Clone)]
+pub struct ProgressWatch {
+    callback: Arc<Py<PyAny>>,
+    interval: Duration,
+}
+
+/// Validate the `on_progress=` and `progress_interval=` arguments.
+pub fn progress_watch(
+    py: Python<'_>,
+    on_progress: Option<Py<PyAny>>,
+    progress_interval: Option<f64>,
+) -> PyResult<Option<ProgressWatch>> {
+    let interval = match progress_interval {
+        Some(secs) if !(secs.is_finite() && secs > 0.0) => {
+            return Err(PyValueError::new_err(
+                "progress_interval must be a positive number of seconds",
+            ));
+        }
+        Some(secs) => Duration::from_secs_f64(secs),
+        None => DEFAULT_INTERVAL,
+    };
+    let Some(callback) = on_progress else {
+        if progress_interval.is_some() {
+            return Err(PyValueError::new_err(
+                "progress_interval requires on_progress",
+            ));
+        }
+        return Ok(None);
+    };
+    if !callback.bind(py).is_callable() {
+        return Err(PyValueError::new_err(
+            "on_progress must be a callable taking the number of rows affected so far",
+        ));
+    }
+    Ok(Some(ProgressWatch {
+        callback: Arc::new(callback),
+        interval,
+    }))
+}
+
+/// The session ID of `client`, to find its running statement from another connection.
+pub async fn session_id(client: &mut TiberiusClient) -> tiberius::Result<i16> {
+    let row = client
+        .simple_query("SELECT @@SPID")
+        .await?
+        .into_row()
+        .await?;
+    Ok(row.and_then(|row| row.get::<i16, _>(0)).unwrap_or_default())
+}
+
+/// Polls progress until dropped.
+pub struct ProgressPoller(JoinHandle<()>);
+
+impl Drop for ProgressPoller {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+impl ProgressWatch {
+    /// Start polling the progress of `session_id` on a connection from `pool`, calling
+    /// the callback whenever the row count has changed since the last call.
+    pub fn spawn(&self, pool: ConnectionPool, session_id: i16) -> ProgressPoller {
+        let watch = self.clone();
+        ProgressPoller(tokio::spawn(async move {
+            let mut reported = None;
+            loop {
+                tokio::time::sleep(watch.interval).await;
+                let rows = match poll(&pool, session_id).await {
+                    Ok(rows) => rows,
+                    Err(message) => {
+                        warn(&message);
+                        return;
+                    }
+                };
+                if rows.is_none() || rows == reported {
+                    continue;
+                }
+                reported = rows;
+                Python::attach(|py| {
+                    if let Err(e) = watch.callback.call1(py, (rows,)) {
+                        e.write_unraisable(py, Some(watch.callback.bind(py)));
+                    }
+                });
+            }
+        }))
+    }
+}
+
+async fn poll(pool: &ConnectionPool, session_id: i16) -> Result<Option<i64>, String> {
+    let mut conn = pool
+        .checkout()
+        .await
+        .map_err(|e| Python::attach(|py| e.value(py).to_string()))?;
+    let row = conn
+        .request(async |client| {
+            client
+                .query(PROGRESS_SQL, &[&session_id])
+                .await?
+                .into_row()
+                .await
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.and_then(|row| row.get::<i64, _>(0)))
+}
+
+fn warn(cause: &str) {
+    let message = format!(
+        "on_progress stopped: could not read statement progress from \
+         sys.dm_exec_query_profiles: {}",
+        cause
+    );
+    Python::attach(|py| {
+        let category = py.get_type::<PyRuntimeWarning>();
+        let result = match std::ffi::CString::new(message) {
+            Ok(message) => PyErr::warn(py, &category, &message, 1),
+            Err(_) => Ok(()),
+        };
+        if let Err(e) = result {
+            e.write_unraisable(py, None);
+        }
+    });
+}
@@ -0,0 +1,102 @@
+//! Process-wide cache of server name resolutions.
+//!
+//! Every new physical connection resolves the server name, and a pool warming up or
+//! replacing connections after a failover can resolve the same name dozens of times
+//! in a burst.  Resolutions are kept for a short TTL (30 s by default, set with
+//! `fastmssql.set_dns_cache_ttl()`).  An entry is dropped as soon as none of its
+//! addresses accepts a connection, so a failover that moves the name to a new address
+//! is picked up on the next attempt rather than when the TTL runs out.
+//! `fastmssql.diagnose()` always resolves afresh.  Host aliases and the resolver
+//! callable ([`host_resolver`](crate::host_resolver)) apply before caching.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::collections::HashMap;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::host_resolver;
+
+const DEFAULT_TTL_MS: u64 = 30_000;
+
+static TTL_MS: AtomicU64 = AtomicU64::new(DEFAULT_TTL_MS);
+static CACHE: LazyLock<Mutex<HashMap<String, Entry>>> = LazyLock::new(Default::default);
+
+struct Entry {
+    addrs: Vec<SocketAddr>,
+    expires: Instant,
+}
+
+/// Resolve `address` (`host:port`), serving a cached result while it is fresh.
+pub async fn resolve(address: &str) -> io::Result<Vec<SocketAddr>> {
+    let ttl = Duration::from_millis(TTL_MS.load(Ordering::Relaxed));
+    if !ttl.is_zero()
+        && let Ok(cache) = CACHE.lock()
+        && let Some(entry) = cache.get(address)
+        && entry.expires > Instant::now()
+    {
+        return Ok(entry.addrs.clone());
+    }
+
+    let addrs = host_resolver::lookup(address).await?;
+    if !ttl.is_zero()
+        && !addrs.is_empty()
+        && let Ok(mut cache) = CACHE.lock()
+    {
+        cache.insert(
+            address.to_string(),
+            Entry {
+                addrs: addrs.clone(),
+                expires: Instant::now() + ttl,
+            },
+        );
+    }
+    Ok(addrs)
+}
+
+/// Forget the resolution of `address`, e.g. after none of its addresses answered.
+pub fn invalidate(address: &str) {
+    if let Ok(mut cache) = CACHE.lock() {
+        cache.remove(address);
+    }
+}
+
+/// Set how long server name resolutions are reused, in seconds. 0 disables the cache
+/// (and empties it).
+#[pyfunction]
+pub fn set_dns_cache_ttl(seconds: f64) -> PyResult<()> {
+    if !(seconds.is_finite() && seconds >= 0.0) {
+        return Err(PyValueError::new_err(
+            "DNS cache TTL must be a non-negative number of seconds",
+        ));
+    }
+    TTL_MS.store((seconds * 1000.0) as u64, Ordering::Relaxed);
+    if seconds == 0.0 {
+        flush_dns_cache(None);
+    }
+    Ok(())
+}
+
+/// Drop cached resolutions, for `host` only or for every server, and return how many
+/// entries were removed.
+#[pyfunction]
+#[pyo3(signature = (host = None))]
+pub fn flush_dns_cache(host: Option<&str>) -> usize {
+    let Ok(mut cache) = CACHE.lock() else {
+        return 0;
+    };
+    let before = cache.len();
+    match host {
+        Some(host) => cache.retain(|address, _| {
+            let cached_host = address
+                .rsplit_once(':')
+                .map_or(address.as_str(), |(h, _)| h);
+            !cached_host.eq_ignore_ascii_case(host)
+        }),
+        None => cache.clear(),
+    }
+    before - cache.len()
+}
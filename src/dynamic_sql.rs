@@ -0,0 +1,226 @@
+//! Statement building for `Connection.exec_sql()`.
+//!
+//! The dynamic SQL is passed to `sp_executesql` along with a declaration list derived
+//! from the wire type of each bound value, so the server caches one plan per
+//! statement text.  OUTPUT parameters are bound to local variables of the outer
+//! batch and read back from a trailing single-row result set that also carries the
+//! affected row count.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::borrow::Cow;
+use tiberius::Row;
+
+use crate::batch::quote_identifier;
+use crate::parameter_conversion::{FastParameter, TypedNull};
+use crate::type_mapping::{DecodeOptions, sql_to_python};
+use crate::types::DatabaseError;
+
+/// `sp_executesql` takes the statement and declaration list ahead of the values.
+const MAX_EXEC_SQL_PARAMETERS: usize = 2100 - 2;
+
+/// The outer batch and its parameters: `@P1` is the statement, `@P2` the declaration
+/// list and `@P3..` the input values in order.
+pub struct ExecSqlCall {
+    pub sql: String,
+    pub parameters: Vec<FastParameter>,
+    pub output_names: Vec<String>,
+}
+
+/// Declared type matching what `FastParameter` puts on the wire.
+fn declared_type(param: &FastParameter) -> Cow<'static, str> {
+    Cow::Borrowed(match param {
+        FastParameter::Null(null) => match null {
+            TypedNull::U8 => "tinyint",
+            TypedNull::I16 => "smallint",
+            TypedNull::I32 => "int",
+            TypedNull::I64 => "bigint",
+            TypedNull::F32 => "real",
+            TypedNull::F64 => "float",
+            TypedNull::Bit => "bit",
+            TypedNull::String => "nvarchar(max)",
+            TypedNull::Guid => "uniqueidentifier",
+            TypedNull::Binary => "varbinary(max)",
+            TypedNull::Numeric => "decimal(38, 10)",
+            TypedNull::Xml => "xml",
+            TypedNull::DateTime => "datetime",
+            TypedNull::SmallDateTime => "smalldatetime",
+            TypedNull::Time => "time",
+            TypedNull::Date => "date",
+            TypedNull::DateTime2 => "datetime2",
+            TypedNull::DateTimeOffset => "datetimeoffset",
+        },
+        FastParameter::Bool(_) => "bit",
+        FastParameter::I64(_) => "bigint",
+        FastParameter::F64(_) => "float",
+        // Declared at full precision so values of different sizes share a plan.
+        FastParameter::Decimal(n) => return format!("decimal(38, {})", n.scale()).into(),
+        FastParameter::Guid(_) => "uniqueidentifier",
+        FastParameter::String(_) | FastParameter::JsonList(_) => "nvarchar(max)",
+        FastParameter::Xml(_) => "xml",
+        FastParameter::Bytes(_) | FastParameter::Buffer(_) => "varbinary(max)",
+        FastParameter::Date(_) => "date",
+        FastParameter::DateTime(_) => "datetime2",
+        FastParameter::SmallDateTime(_) => "smalldatetime",
+        FastParameter::Time(_) => "time",
+        FastParameter::DateTimeOffset(_) => "datetimeoffset",
+    })
+}
+
+/// Strip an optional leading `@` and check the rest is a regular identifier, since
+/// the name is written into the batch unquoted.
+pub fn parameter_name(raw: &str) -> PyResult<String> {
+    let name = raw.strip_prefix('@').unwrap_or(raw);
+    let mut chars = name.chars();
+    let valid_start = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_');
+    if !valid_start
+        || name.len() > 127
+        || !chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '@' | '#' | '$'))
+    {
+        return Err(PyValueError::new_err(format!(
+            "Invalid parameter name '{}': expected an identifier such as 'customer_id'",
+            raw
+        )));
+    }
+    Ok(name.to_string())
+}
+
+/// OUTPUT types are written into the batch as given, so only type-name characters
+/// are accepted (e.g. `int`, `nvarchar(100)`, `decimal(18, 2)`).
+fn output_type(name: &str, sql_type: &str) -> PyResult<String> {
+    let sql_type = sql_type.trim();
+    let valid = sql_type
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic())
+        && sql_type
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '(' | ')' | ',' | ' '));
+    if !valid {
+        return Err(PyValueError::new_err(format!(
+            "Invalid SQL type '{}' for OUTPUT parameter '{}'",
+            sql_type, name
+        )));
+    }
+    Ok(sql_type.to_string())
+}
+
+/// Build the batch that runs `statement` through `sp_executesql`.
+///
+/// A name present in both `inputs` and `outputs` is an input/output parameter: its
+/// value initialises the local variable bound to it.
+pub fn build_exec_sql(
+    statement: String,
+    inputs: Vec<(String, FastParameter)>,
+    outputs: Vec<(String, String)>,
+) -> PyResult<ExecSqlCall> {
+    if inputs.len() > MAX_EXEC_SQL_PARAMETERS {
+        return Err(PyValueError::new_err(format!(
+            "Too many parameters: {} provided, but sp_executesql supports at most {}",
+            inputs.len(),
+            MAX_EXEC_SQL_PARAMETERS
+        )));
+    }
+
+    let mut seen: Vec<String> = Vec::with_capacity(inputs.len() + outputs.len());
+    for (name, _) in &inputs {
+        let key = name.to_ascii_lowercase();
+        if seen.contains(&key) {
+            return Err(PyValueError::new_err(format!(
+                "Duplicate parameter name '{}'",
+                name
+            )));
+        }
+        seen.push(key);
+    }
+    let mut output_keys: Vec<String> = Vec::with_capacity(outputs.len());
+    for (name, _) in &outputs {
+        let key = name.to_ascii_lowercase();
+        if output_keys.contains(&key) {
+            return Err(PyValueError::new_err(format!(
+                "Duplicate OUTPUT parameter name '{}'",
+                name
+            )));
+        }
+        output_keys.push(key);
+    }
+
+    let mut declarations = Vec::with_capacity(inputs.len() + outputs.len());
+    let mut locals = String::new();
+    let mut arguments = Vec::with_capacity(inputs.len() + outputs.len());
+    let mut parameters = Vec::with_capacity(inputs.len() + 2);
+    parameters.push(FastParameter::String(statement));
+    parameters.push(FastParameter::String(String::new()));
+
+    // Input/output values are bound first so each local can be initialised from its
+    // placeholder; the remaining inputs follow.
+    let mut plain_inputs = Vec::with_capacity(inputs.len());
+    let mut initial_values: Vec<Option<FastParameter>> = vec![None; outputs.len()];
+    for (name, value) in inputs {
+        let key = name.to_ascii_lowercase();
+        match output_keys.iter().position(|k| *k == key) {
+            Some(index) => initial_values[index] = Some(value),
+            None => plain_inputs.push((name, value)),
+        }
+    }
+
+    for (index, ((name, sql_type), initial)) in outputs.iter().zip(initial_values).enumerate() {
+        let sql_type = output_type(name, sql_type)?;
+        locals.push_str(&format!("DECLARE @__out{} {}", index, sql_type));
+        if let Some(value) = initial {
+            parameters.push(value);
+            locals.push_str(&format!(" = @P{}", parameters.len()));
+        }
+        locals.push_str(";\n");
+        declarations.push(format!("@{} {} OUTPUT", name, sql_type));
+        arguments.push(format!("@{} = @__out{} OUTPUT", name, index));
+    }
+
+    for (name, value) in plain_inputs {
+        declarations.push(format!("@{} {}", name, declared_type(&value)));
+        parameters.push(value);
+        arguments.push(format!("@{} = @P{}", name, parameters.len()));
+    }
+
+    let mut select = String::from("SELECT ROWCOUNT_BIG() AS rows_affected");
+    for (index, (name, _)) in outputs.iter().enumerate() {
+        select.push_str(&format!(", @__out{} AS {}", index, quote_identifier(name)?));
+    }
+
+    parameters[1] = FastParameter::String(declarations.join(", "));
+    let mut sql = locals;
+    sql.push_str("EXEC sp_executesql @P1, @P2");
+    for argument in &arguments {
+        sql.push_str(", ");
+        sql.push_str(argument);
+    }
+    sql.push_str(";\n");
+    sql.push_str(&select);
+
+    Ok(ExecSqlCall {
+        sql,
+        parameters,
+        output_names: outputs.into_iter().map(|(name, _)| name).collect(),
+    })
+}
+
+/// Read the affected row count and OUTPUT values from the trailing result set.
+pub fn split_meta_row(
+    meta: Option<Row>,
+    output_names: &[String],
+    options: DecodeOptions,
+    py: Python<'_>,
+) -> PyResult<(u64, Py<PyAny>)> {
+    let row = meta.ok_or_else(|| DatabaseError::new_err("Row count missing from result"))?;
+    let rows_affected = row.get::<i64, _>(0).unwrap_or(0).max(0) as u64;
+
+    let values = PyDict::new(py);
+    for (index, name) in output_names.iter().enumerate() {
+        let col_type = row.columns()[index + 1].column_type();
+        values.set_item(name, sql_to_python(&row, index + 1, col_type, options, py)?)?;
+    }
+    Ok((rows_affected, values.into_any().unbind()))
+}
@@ -0,0 +1,91 @@
+//! Per-execution IDs for correlating application logs, driver errors and server-side
+//! traces.
+//!
+//! Each `Connection.query()` / `execute()` / `simple_query()` call gets an ID of the
+//! form `<process tag>-<sequence>` (e.g. `3f9a61c2-0000002a`); batch and bulk load
+//! calls get one shared by all of their statements.  It is returned on the
+//! `QueryStream`, attached to exceptions raised by the call, and — when the
+//! connection was created with `trace_context_info=True` — written to the session's
+//! `CONTEXT_INFO` ahead of the statement so Extended Events / `sys.dm_exec_sessions`
+//! can be joined against it.
+
+use crate::tls_info::ObservedStream;
+use crate::types::create_sql_error;
+use pyo3::prelude::*;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static PROCESS_TAG: OnceLock<u32> = OnceLock::new();
+static SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+/// Distinguishes IDs from different processes (and restarts of the same one).
+fn process_tag() -> u32 {
+    *PROCESS_TAG.get_or_init(|| {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+        let mixed = (nanos ^ (std::process::id() as u64).rotate_left(32))
+            .wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        (mixed >> 32) as u32
+    })
+}
+
+pub fn next_execution_id() -> String {
+    let sequence = SEQUENCE.fetch_add(1, Ordering::Relaxed) + 1;
+    format!("{:08x}-{:08x}", process_tag(), sequence)
+}
+
+/// `SET CONTEXT_INFO` statement to run ahead of the traced statement.  The ID is
+/// ASCII, so its bytes are written as-is and read back with
+/// `CAST(CONTEXT_INFO() AS VARCHAR(128))` (trailing zero bytes aside).
+pub fn context_info_sql(execution_id: &str) -> String {
+    let mut sql = String::with_capacity(20 + execution_id.len() * 2);
+    sql.push_str("SET CONTEXT_INFO 0x");
+    for byte in execution_id.bytes() {
+        sql.push_str(&format!("{:02x}", byte));
+    }
+    sql.push_str(";\n");
+    sql
+}
+
+/// Attach the ID to an exception as `execution_id` and as a note in its traceback.
+pub fn tag_error(err: PyErr, execution_id: &str) -> PyErr {
+    Python::attach(|py| {
+        let value = err.value(py);
+        let _ = value.setattr("execution_id", execution_id);
+        let _ = value.call_method1("add_note", (format!("execution_id: {}", execution_id),));
+    });
+    err
+}
+
+/// The ID of a call that sends several statements — `query_batch()`, `apply()`, a
+/// bulk load — and the `SET CONTEXT_INFO` statement to run once ahead of them.
+#[derive(Clone, Debug)]
+pub struct BatchTrace {
+    pub execution_id: String,
+    pub context_info: Option<String>,
+}
+
+impl BatchTrace {
+    /// Write the ID to the session's `CONTEXT_INFO`, if the connection traces to the
+    /// server.
+    pub async fn set_context_info(
+        &self,
+        conn: &mut tiberius::Client<tokio_util::compat::Compat<ObservedStream>>,
+    ) -> PyResult<()> {
+        if let Some(context_info) = &self.context_info {
+            conn.simple_query(context_info.as_str())
+                .await
+                .map_err(|e| create_sql_error(e, "Failed to set CONTEXT_INFO"))?
+                .into_results()
+                .await
+                .map_err(|e| create_sql_error(e, "Failed to set CONTEXT_INFO"))?;
+        }
+        Ok(())
+    }
+
+    pub fn tag(&self, err: PyErr) -> PyErr {
+        tag_error(err, &self.execution_id)
+    }
+}
@@ -0,0 +1,77 @@
+//! SQL builders for the FILESTREAM helpers on `Transaction`.
+//!
+//! The Win32 `OpenSqlFilestream` API needs SMB access to the server and an ODBC
+//! stack, so the helpers move FILESTREAM data through TDS instead: the column is
+//! an ordinary `varbinary(max)` to T-SQL, readable with `SUBSTRING` and writable
+//! with `UPDATE ... SET col = ...` or `col.WRITE(...)`.  The path/transaction
+//! context pair is still exposed for callers that do have Win32 access.
+
+use pyo3::prelude::*;
+
+use crate::batch::quote_identifier;
+
+/// A FILESTREAM column addressed by table and a single-column key (`@P1`).
+pub struct FilestreamTarget {
+    table: String,
+    column: String,
+    key_column: String,
+}
+
+impl FilestreamTarget {
+    /// Quote the identifiers up front; errors for names containing a null byte.
+    pub fn new(table: &str, column: &str, key_column: &str) -> PyResult<Self> {
+        Ok(FilestreamTarget {
+            table: quote_identifier(table)?,
+            column: quote_identifier(column)?,
+            key_column: quote_identifier(key_column)?,
+        })
+    }
+
+    /// `PathName()` and the transaction context; the latter is NULL outside a transaction.
+    pub fn context_sql(&self) -> String {
+        format!(
+            "SELECT {col}.PathName() AS path, GET_FILESTREAM_TRANSACTION_CONTEXT() AS transaction_context \
+             FROM {table} WHERE {key} = @P1",
+            col = self.column,
+            table = self.table,
+            key = self.key_column
+        )
+    }
+
+    /// Whole value, or the bytes from 1-based `@P2` (length `@P3` when `with_length`).
+    pub fn read_sql(&self, ranged: bool, with_length: bool) -> String {
+        let expr = match (ranged, with_length) {
+            (false, _) => self.column.clone(),
+            (true, true) => format!("SUBSTRING({}, @P2, @P3)", self.column),
+            (true, false) => format!(
+                "SUBSTRING({col}, @P2, DATALENGTH({col}))",
+                col = self.column
+            ),
+        };
+        format!(
+            "SELECT {expr} AS data FROM {table} WHERE {key} = @P1",
+            table = self.table,
+            key = self.key_column
+        )
+    }
+
+    /// Replace the whole value with `@P2`, or overwrite `DATALENGTH(@P2)` bytes at
+    /// 0-based offset `@P3` when `at_offset`.
+    pub fn write_sql(&self, at_offset: bool) -> String {
+        if at_offset {
+            format!(
+                "UPDATE {table} SET {col}.WRITE(@P2, @P3, DATALENGTH(@P2)) WHERE {key} = @P1",
+                table = self.table,
+                col = self.column,
+                key = self.key_column
+            )
+        } else {
+            format!(
+                "UPDATE {table} SET {col} = @P2 WHERE {key} = @P1",
+                table = self.table,
+                col = self.column,
+                key = self.key_column
+            )
+        }
+    }
+}
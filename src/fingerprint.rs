@@ -0,0 +1,45 @@
+//! Stable fingerprints for application-level result caching and query telemetry.
+//!
+//! Fingerprints are FNV-1a (64-bit) hashes rendered as 16 hex digits.  std's
+//! `DefaultHasher` is not used because its output may change between Rust releases,
+//! and fingerprints are meant to be stored in caches and metrics that outlive a build.
+
+use pyo3::prelude::*;
+use tiberius::ColumnType;
+
+use crate::sql_placeholders::normalize_statement;
+use crate::type_mapping::column_type_name;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+fn fnv1a_64(data: &[u8]) -> u64 {
+    data.iter().fold(FNV_OFFSET_BASIS, |hash, &b| {
+        (hash ^ u64::from(b)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+/// FNV-1a hash of `data` as 16 hex digits.
+pub fn to_hex(data: &[u8]) -> String {
+    format!("{:016x}", fnv1a_64(data))
+}
+
+/// Hash of the column names and SQL type names, in order.
+pub fn schema_fingerprint(names: &[String], column_types: &[ColumnType]) -> String {
+    let mut canonical = String::new();
+    for (name, col_type) in names.iter().zip(column_types) {
+        canonical.push_str(name);
+        canonical.push(':');
+        canonical.push_str(column_type_name(*col_type));
+        canonical.push('\n');
+    }
+    to_hex(canonical.as_bytes())
+}
+
+/// Hash of `sql` after normalisation: comments, layout, keyword case, literal values
+/// and the length of IN / VALUES lists do not affect the result, so statements that
+/// differ only in those share a fingerprint.
+#[pyfunction]
+pub fn statement_fingerprint(sql: &str) -> String {
+    to_hex(normalize_statement(sql).as_bytes())
+}
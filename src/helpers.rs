@@ -1,12 +1,101 @@
+use futures_util::TryStreamExt;
 use pyo3::prelude::*;
-use tiberius::Row;
+use pyo3::types::PyList;
+use tiberius::{QueryItem, QueryStream, ResultMetadata, Row};
+
+use crate::result_limits::ResultLimits;
+use crate::type_mapping::{DecodeOptions, sql_to_python};
+use crate::types::create_sql_error;
 
 /// Wrap `Vec<Row>` into a `Py<PyAny>` via `PyQueryStream`.
 /// Shared between connection.rs and transaction.rs.
-pub fn wrap_query_stream(rows: Vec<Row>) -> PyResult<Py<PyAny>> {
+pub fn wrap_query_stream(rows: Vec<Row>, options: DecodeOptions) -> PyResult<Py<PyAny>> {
     Python::attach(|py| -> PyResult<Py<PyAny>> {
-        let query_stream = crate::types::PyQueryStream::from_tiberius_rows(rows, py)?;
+        let query_stream = crate::types::PyQueryStream::from_tiberius_rows(rows, options, py)?;
         let py_result = Py::new(py, query_stream)?;
         Ok(py_result.into_any())
     })
 }
+
+/// Rows of the first result set together with its metadata.
+pub type FirstResult = (Vec<Row>, Option<ResultMetadata>);
+
+/// Like `QueryStream::into_first_result`, but also keeping the first result set's
+/// metadata so an empty result can still report its columns.  Later result sets
+/// are read and dropped.  The first result set is checked against `limits` as it
+/// arrives.
+pub async fn into_first_result_with_metadata(
+    mut stream: QueryStream<'_>,
+    limits: ResultLimits,
+) -> PyResult<FirstResult> {
+    let mut rows = Vec::new();
+    let mut metadata = None;
+    let mut in_first = true;
+    while let Some(item) = stream
+        .try_next()
+        .await
+        .map_err(|e| create_sql_error(e, "Failed to get results"))?
+    {
+        match item {
+            QueryItem::Metadata(meta) if metadata.is_none() => {
+                limits.check_columns(meta.columns())?;
+                metadata = Some(meta);
+            }
+            QueryItem::Metadata(_) => in_first = false,
+            QueryItem::Row(row) if in_first => {
+                limits.check_row(&row, rows.len())?;
+                rows.push(row);
+            }
+            QueryItem::Row(_) => {}
+        }
+    }
+    Ok((rows, metadata))
+}
+
+/// Wrap the first result set of a query, keeping its columns when it has no rows.
+pub fn wrap_first_result(result: FirstResult, options: DecodeOptions) -> PyResult<Py<PyAny>> {
+    Python::attach(|py| -> PyResult<Py<PyAny>> {
+        let (rows, metadata) = result;
+        let query_stream =
+            crate::types::PyQueryStream::from_first_result(rows, metadata, options, py)?;
+        Ok(Py::new(py, query_stream)?.into_any())
+    })
+}
+
+/// Like [`wrap_first_result`], recording the ID of the call that produced the rows.
+pub fn wrap_traced_query_stream(
+    result: FirstResult,
+    options: DecodeOptions,
+    execution_id: String,
+    messages: Vec<String>,
+) -> PyResult<Py<PyAny>> {
+    Python::attach(|py| -> PyResult<Py<PyAny>> {
+        let (rows, metadata) = result;
+        let query_stream =
+            crate::types::PyQueryStream::from_first_result(rows, metadata, options, py)?
+                .with_execution_id(execution_id)
+                .with_messages(messages);
+        let py_result = Py::new(py, query_stream)?;
+        Ok(py_result.into_any())
+    })
+}
+
+/// Convert the first column of `rows` straight into a Python list, without building
+/// `FastRow` objects. An empty result gives an empty list.
+pub fn first_column_to_list(rows: Vec<Row>, options: DecodeOptions) -> PyResult<Py<PyAny>> {
+    Python::attach(|py| -> PyResult<Py<PyAny>> {
+        let Some(col_type) = rows
+            .first()
+            .and_then(|row| row.columns().first())
+            .map(|col| col.column_type())
+        else {
+            return Ok(PyList::empty(py).into_any().unbind());
+        };
+
+        let values = rows
+            .iter()
+            .map(|row| sql_to_python(row, 0, col_type, options, py))
+            .collect::<PyResult<Vec<_>>>()?;
+        Ok(PyList::new(py, values)?.into_any().unbind())
+    })
+}
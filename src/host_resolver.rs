@@ -0,0 +1,137 @@
+//! Server name resolution overrides: a host alias table and a resolver callable.
+//!
+//! With split-horizon DNS or an SSH tunnel the name in the connection string is not
+//! the address to dial.  `fastmssql.set_host_aliases({"db.internal": "127.0.0.1:14330"})`
+//! maps names to addresses, and `fastmssql.set_resolver(fn)` lets Python code decide
+//! for names without an alias.  Only the TCP target changes: the TLS certificate is
+//! still checked against the name in the connection string.  Lookups go through the
+//! [`dns_cache`](crate::dns_cache), which is flushed whenever the overrides change.
+
+use pyo3::exceptions::PyTypeError;
+use pyo3::prelude::*;
+use pyo3::types::PyString;
+use std::collections::HashMap;
+use std::io;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{LazyLock, Mutex};
+
+use crate::dns_cache::flush_dns_cache;
+
+static ALIASES: LazyLock<Mutex<HashMap<String, String>>> = LazyLock::new(Default::default);
+static RESOLVER: Mutex<Option<Py<PyAny>>> = Mutex::new(None);
+
+/// Resolve `address` (`host:port`) to socket addresses, applying the host aliases and
+/// the resolver callable before falling back to the system resolver.
+pub async fn lookup(address: &str) -> io::Result<Vec<SocketAddr>> {
+    let (host, port) = split_host_port(address);
+
+    let alias = ALIASES
+        .lock()
+        .ok()
+        .and_then(|aliases| aliases.get(&host.to_ascii_lowercase()).cloned());
+    let targets = match alias {
+        Some(target) => vec![target],
+        None => match call_resolver(host, port)? {
+            Some(targets) => targets,
+            None => return Ok(tokio::net::lookup_host(address).await?.collect()),
+        },
+    };
+
+    let mut resolved = Vec::new();
+    for target in targets {
+        let (target_host, target_port) = parse_target(&target, port);
+        resolved.extend(tokio::net::lookup_host((target_host.as_str(), target_port)).await?);
+    }
+    Ok(resolved)
+}
+
+/// Ask the Python resolver for `host`. None when there is no resolver or it declined.
+fn call_resolver(host: &str, port: u16) -> io::Result<Option<Vec<String>>> {
+    if !RESOLVER.lock().is_ok_and(|resolver| resolver.is_some()) {
+        return Ok(None);
+    }
+    Python::attach(|py| -> PyResult<Option<Vec<String>>> {
+        let resolver = match RESOLVER.lock() {
+            Ok(resolver) => resolver.as_ref().map(|r| r.clone_ref(py)),
+            Err(_) => None,
+        };
+        let Some(resolver) = resolver else {
+            return Ok(None);
+        };
+        let answer = resolver.call1(py, (host, port))?;
+        let answer = answer.bind(py);
+        if answer.is_none() {
+            Ok(None)
+        } else if answer.is_instance_of::<PyString>() {
+            Ok(Some(vec![answer.extract()?]))
+        } else {
+            Ok(Some(answer.extract()?))
+        }
+    })
+    .map_err(|e| io::Error::other(format!("resolver failed for {}: {}", host, e)))
+}
+
+/// Split `host:port` (the form `Config::get_addr()` produces).
+pub fn split_host_port(address: &str) -> (&str, u16) {
+    match address.rsplit_once(':') {
+        Some((host, port)) => match port.parse() {
+            Ok(port) => (host.trim_start_matches('[').trim_end_matches(']'), port),
+            Err(_) => (address, 1433),
+        },
+        None => (address, 1433),
+    }
+}
+
+/// Parse an alias or resolver answer: an IP address, `host`, or either with a port.
+/// `default_port` is used when the target has none.
+fn parse_target(target: &str, default_port: u16) -> (String, u16) {
+    if let Ok(addr) = target.parse::<SocketAddr>() {
+        return (addr.ip().to_string(), addr.port());
+    }
+    if let Ok(ip) = target.trim_matches(['[', ']']).parse::<IpAddr>() {
+        return (ip.to_string(), default_port);
+    }
+    match target.rsplit_once(':') {
+        Some((host, port)) => match port.parse() {
+            Ok(port) => (host.to_string(), port),
+            Err(_) => (target.to_string(), default_port),
+        },
+        None => (target.to_string(), default_port),
+    }
+}
+
+/// Replace the host alias table, e.g. `{"db.internal": "127.0.0.1:14330"}`. Names
+/// match case-insensitively; a target without a port keeps the connection's port.
+/// None or an empty dict removes all aliases.
+#[pyfunction]
+#[pyo3(signature = (aliases = None))]
+pub fn set_host_aliases(aliases: Option<HashMap<String, String>>) {
+    if let Ok(mut table) = ALIASES.lock() {
+        *table = aliases
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(host, target)| (host.to_ascii_lowercase(), target))
+            .collect();
+    }
+    flush_dns_cache(None);
+}
+
+/// Set a callable `resolver(host, port)` consulted for names without an alias. It
+/// returns an address (`"10.0.0.5"`, `"10.0.0.5:1433"`, another host name), a list
+/// of them, or None to use normal DNS. None removes the resolver.
+#[pyfunction]
+#[pyo3(signature = (resolver = None))]
+pub fn set_resolver(py: Python<'_>, resolver: Option<Py<PyAny>>) -> PyResult<()> {
+    if let Some(resolver) = &resolver
+        && !resolver.bind(py).is_callable()
+    {
+        return Err(PyTypeError::new_err(
+            "resolver must be a callable taking (host, port)",
+        ));
+    }
+    if let Ok(mut current) = RESOLVER.lock() {
+        *current = resolver;
+    }
+    flush_dns_cache(None);
+    Ok(())
+}
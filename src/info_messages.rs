@@ -0,0 +1,124 @@
+//! Informational messages from the server (`PRINT`, `RAISERROR` with severity 10 or
+//! lower, DBCC output).
+//!
+//! tiberius reads these INFO tokens but does not return them; it only reports each
+//! one as a `tracing` event.  A minimal subscriber installed at module load picks up
+//! exactly that event and hands the text to whichever statement is running in the
+//! current task: [`collect`] scopes a task-local buffer around a statement, and the
+//! messages end up on `QueryStream.messages` and are passed as they arrive to the
+//! `Connection(on_message=...)` callback.  Events outside a [`collect`] scope (e.g.
+//! "Changed database context" during login) are ignored.
+
+use pyo3::prelude::*;
+use std::cell::RefCell;
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+use tracing::field::{Field, Visit};
+use tracing::subscriber::Interest;
+use tracing::{Event, Metadata, span};
+
+/// Module and line of the `event!` tiberius 0.12.3 emits for each INFO token
+/// (`TokenStream::get_info`).  Its environment-change and login-ack events share the
+/// module and level, so the line is what identifies it.
+const TIBERIUS_INFO_TARGET: &str = "tiberius::tds::stream::token";
+const TIBERIUS_INFO_LINE: u32 = 194;
+
+struct Collector {
+    messages: Vec<String>,
+    on_message: Option<Arc<Py<PyAny>>>,
+}
+
+tokio::task_local! {
+    static COLLECTOR: RefCell<Collector>;
+}
+
+/// Run `statement`, returning its output together with the info messages the server
+/// sent while it ran. `on_message` is called with each message as it arrives.
+pub async fn collect<F: Future>(
+    on_message: Option<Arc<Py<PyAny>>>,
+    statement: F,
+) -> (F::Output, Vec<String>) {
+    let collector = RefCell::new(Collector {
+        messages: Vec::new(),
+        on_message,
+    });
+    COLLECTOR
+        .scope(collector, async {
+            let output = statement.await;
+            let messages = COLLECTOR.with(|c| std::mem::take(&mut c.borrow_mut().messages));
+            (output, messages)
+        })
+        .await
+}
+
+fn deliver(message: String) {
+    let _ = COLLECTOR.try_with(|collector| {
+        let callback = collector.borrow().on_message.clone();
+        if let Some(callback) = callback {
+            Python::attach(|py| {
+                if let Err(e) = callback.call1(py, (message.as_str(),)) {
+                    e.write_unraisable(py, Some(callback.bind(py)));
+                }
+            });
+        }
+        collector.borrow_mut().messages.push(message);
+    });
+}
+
+#[derive(Default)]
+struct MessageText(String);
+
+impl Visit for MessageText {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.0 = format!("{:?}", value);
+        }
+    }
+}
+
+/// Subscriber interested in the tiberius INFO-token event only.
+struct InfoMessageSubscriber;
+
+fn is_info_token(metadata: &Metadata<'_>) -> bool {
+    metadata.is_event()
+        && metadata.target() == TIBERIUS_INFO_TARGET
+        && metadata.line() == Some(TIBERIUS_INFO_LINE)
+}
+
+impl tracing::Subscriber for InfoMessageSubscriber {
+    fn register_callsite(&self, metadata: &'static Metadata<'static>) -> Interest {
+        if is_info_token(metadata) {
+            Interest::always()
+        } else {
+            Interest::never()
+        }
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        is_info_token(metadata)
+    }
+
+    fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+        span::Id::from_u64(1)
+    }
+
+    fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+    fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut text = MessageText::default();
+        event.record(&mut text);
+        deliver(text.0);
+    }
+
+    fn enter(&self, _: &span::Id) {}
+
+    fn exit(&self, _: &span::Id) {}
+}
+
+/// Install the subscriber; a no-op if one is already set.
+pub fn install() {
+    let _ = tracing::subscriber::set_global_default(InfoMessageSubscriber);
+}
@@ -0,0 +1,58 @@
+//! BIT and integer column values, decoded in one place.
+//!
+//! Every BIT, TINYINT, SMALLINT, INT and BIGINT column (fixed-width or nullable)
+//! is converted by [`integer_to_python`] from the decoded TDS value itself, so the
+//! result depends only on what arrived on the wire:
+//!
+//! | Wire value                    | Python                                   |
+//! |-------------------------------|------------------------------------------|
+//! | BIT / nullable BIT 0, 1       | `False`, `True`                          |
+//! | TINYINT 0 to 255              | `int` 0 to 255 (unsigned, never negative) |
+//! | SMALLINT, INT, BIGINT         | `int`                                    |
+//! | NULL of any of these widths   | `None`                                   |
+//!
+//! Nullable integer columns carry their width per value, and tiberius tags a NULL
+//! by whatever width the column declared (a NULL SMALLINT is an `I16(None)`), so
+//! NULL is recognised on every variant rather than only the INT/BIGINT ones.
+//!
+//! Computed expressions keep the type SQL Server gives them: `CAST(x AS BIT)` and
+//! `x & y` over BIT columns are BIT and return bools, while
+//! `CASE WHEN ... THEN 1 ELSE 0 END` and `IIF(c, 1, 0)` are INT and return ints.
+//! The typed getters bridge the two: `FastRow.get_bool()` accepts bools and the
+//! integers 0 and 1 ([`as_bool`]), and `FastRow.get_int()` returns a BIT as 0 or 1.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyInt};
+use pyo3::{IntoPyObjectExt, Py, PyAny};
+use tiberius::{ColumnData, Row};
+
+/// Value of the BIT or integer column `index` of `row`.
+pub fn integer_to_python(row: &Row, index: usize, py: Python) -> PyResult<Py<PyAny>> {
+    match row.cells().nth(index).map(|(_, data)| data) {
+        Some(ColumnData::Bit(value)) => value.into_py_any(py),
+        Some(ColumnData::U8(value)) => value.into_py_any(py),
+        Some(ColumnData::I16(value)) => value.into_py_any(py),
+        Some(ColumnData::I32(value)) => value.into_py_any(py),
+        Some(ColumnData::I64(value)) => value.into_py_any(py),
+        _ => Err(PyValueError::new_err(format!(
+            "Failed to convert column {} to integer",
+            index
+        ))),
+    }
+}
+
+/// `value` as a bool if it is one, or an integer 0 or 1 (a computed flag).
+pub fn as_bool(value: &Bound<'_, PyAny>) -> PyResult<Option<bool>> {
+    if value.is_instance_of::<PyBool>() {
+        return Ok(Some(value.extract()?));
+    }
+    if value.is_instance_of::<PyInt>() {
+        return Ok(match value.extract::<i64>() {
+            Ok(0) => Some(false),
+            Ok(1) => Some(true),
+            _ => None,
+        });
+    }
+    Ok(None)
+}
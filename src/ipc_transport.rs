@@ -0,0 +1,178 @@
+//! Arrow IPC serialization of results (`QueryStream.to_ipc_bytes()` and
+//! `QueryStream.from_ipc_bytes()`), to ship a result to another process or cache it
+//! (e.g. in Redis) without pickling `FastRow` objects.
+//!
+//! The rows are written as one Arrow IPC stream, built from the raw rows like
+//! `query_arrow()` builds its table, with the record batch buffers optionally
+//! compressed with zstd or lz4.  The wire type of each column is kept in the schema
+//! metadata, so `column_metadata()` and `schema_fingerprint()` of the restored result
+//! match the original.  Values come back the way pyarrow converts them to Python,
+//! which for every type `query_arrow()` produces is the type a query returns.
+
+use pyo3::exceptions::{PyImportError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict};
+use tiberius::{Column, ColumnType, Row};
+
+use crate::arrow_conversion::ArrowColumns;
+use crate::type_mapping::DecodeOptions;
+
+/// Schema metadata key holding the comma-separated column wire types.
+const COLUMN_TYPES_KEY: &str = "fastmssql.column_types";
+
+/// Columns of a restored result and the Python values of each of its rows.
+pub type RestoredRows = (Vec<Column>, Vec<Vec<Py<PyAny>>>);
+
+const ALL_COLUMN_TYPES: [ColumnType; 35] = [
+    ColumnType::Null,
+    ColumnType::Bit,
+    ColumnType::Int1,
+    ColumnType::Int2,
+    ColumnType::Int4,
+    ColumnType::Int8,
+    ColumnType::Datetime4,
+    ColumnType::Float4,
+    ColumnType::Float8,
+    ColumnType::Money,
+    ColumnType::Datetime,
+    ColumnType::Money4,
+    ColumnType::Guid,
+    ColumnType::Intn,
+    ColumnType::Bitn,
+    ColumnType::Decimaln,
+    ColumnType::Numericn,
+    ColumnType::Floatn,
+    ColumnType::Datetimen,
+    ColumnType::Daten,
+    ColumnType::Timen,
+    ColumnType::Datetime2,
+    ColumnType::DatetimeOffsetn,
+    ColumnType::BigVarBin,
+    ColumnType::BigVarChar,
+    ColumnType::BigBinary,
+    ColumnType::BigChar,
+    ColumnType::NVarchar,
+    ColumnType::NChar,
+    ColumnType::Xml,
+    ColumnType::Udt,
+    ColumnType::Text,
+    ColumnType::Image,
+    ColumnType::NText,
+    ColumnType::SSVariant,
+];
+
+fn type_tag(col_type: ColumnType) -> String {
+    format!("{:?}", col_type)
+}
+
+fn parse_type_tag(tag: &str) -> ColumnType {
+    ALL_COLUMN_TYPES
+        .into_iter()
+        .find(|t| type_tag(*t) == tag)
+        .unwrap_or(ColumnType::Null)
+}
+
+fn pyarrow<'py>(py: Python<'py>, method: &str) -> PyResult<Bound<'py, PyModule>> {
+    py.import("pyarrow").map_err(|_| {
+        PyImportError::new_err(format!(
+            "{}() requires pyarrow (pip install fastmssql[arrow])",
+            method
+        ))
+    })
+}
+
+/// Serialize `rows` (with `columns`) as an Arrow IPC stream.
+pub fn to_ipc_bytes<'a>(
+    py: Python<'_>,
+    columns: &[Column],
+    rows: impl Iterator<Item = &'a Row>,
+    options: DecodeOptions,
+    compression: Option<&str>,
+) -> PyResult<Py<PyBytes>> {
+    if let Some(codec) = compression
+        && !matches!(codec, "zstd" | "lz4")
+    {
+        return Err(PyValueError::new_err(format!(
+            "Unsupported compression '{}'; expected None, 'zstd' or 'lz4'",
+            codec
+        )));
+    }
+    let pa = pyarrow(py, "to_ipc_bytes")?;
+    let table = ArrowColumns::from_rows(columns, rows, options)?.into_table(py)?;
+
+    let tags: Vec<String> = columns.iter().map(|c| type_tag(c.column_type())).collect();
+    let metadata = PyDict::new(py);
+    metadata.set_item(COLUMN_TYPES_KEY, tags.join(","))?;
+    let table = table
+        .bind(py)
+        .call_method1("replace_schema_metadata", (metadata,))?;
+
+    let write_options = PyDict::new(py);
+    write_options.set_item("compression", compression)?;
+    let write_options = pa
+        .getattr("ipc")?
+        .getattr("IpcWriteOptions")?
+        .call((), Some(&write_options))?;
+    let sink = pa.call_method0("BufferOutputStream")?;
+    let kwargs = PyDict::new(py);
+    kwargs.set_item("options", write_options)?;
+    let writer = pa.getattr("ipc")?.call_method(
+        "new_stream",
+        (&sink, table.getattr("schema")?),
+        Some(&kwargs),
+    )?;
+    writer.call_method1("write_table", (&table,))?;
+    writer.call_method0("close")?;
+    Ok(sink
+        .call_method0("getvalue")?
+        .call_method0("to_pybytes")?
+        .cast_into::<PyBytes>()?
+        .unbind())
+}
+
+/// Columns and row values of an Arrow IPC stream written by [`to_ipc_bytes`].
+pub fn read_ipc_bytes(py: Python<'_>, data: &Bound<'_, PyAny>) -> PyResult<RestoredRows> {
+    let pa = pyarrow(py, "from_ipc_bytes")?;
+    let buffer = pa.call_method1("py_buffer", (data,))?;
+    let table = pa
+        .getattr("ipc")?
+        .call_method1("open_stream", (buffer,))
+        .and_then(|reader| reader.call_method0("read_all"))
+        .map_err(|e| PyValueError::new_err(format!("Not an Arrow IPC stream: {}", e.value(py))))?;
+
+    let names: Vec<String> = table.getattr("column_names")?.extract()?;
+    let metadata = table.getattr("schema")?.getattr("metadata")?;
+    let tags: Vec<String> = if metadata.is_none() {
+        Vec::new()
+    } else {
+        match metadata.get_item(COLUMN_TYPES_KEY.as_bytes()) {
+            Ok(tags) => String::from_utf8_lossy(tags.cast::<PyBytes>()?.as_bytes())
+                .split(',')
+                .map(str::to_string)
+                .collect(),
+            Err(_) => Vec::new(),
+        }
+    };
+    let columns: Vec<Column> = names
+        .iter()
+        .enumerate()
+        .map(|(index, name)| {
+            let tag = tags.get(index).map_or("", String::as_str);
+            Column::new(name.clone(), parse_type_tag(tag))
+        })
+        .collect();
+
+    let num_rows: usize = table.getattr("num_rows")?.extract()?;
+    let mut rows: Vec<Vec<Py<PyAny>>> = (0..num_rows)
+        .map(|_| Vec::with_capacity(columns.len()))
+        .collect();
+    for index in 0..columns.len() {
+        let values = table
+            .call_method1("column", (index,))?
+            .call_method0("to_pylist")?;
+        for (row, value) in rows.iter_mut().zip(values.try_iter()?) {
+            row.push(value?.unbind());
+        }
+    }
+    Ok((columns, rows))
+}
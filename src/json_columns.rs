@@ -0,0 +1,184 @@
+//! JSON decoding for `Connection(parse_json=...)`.
+//!
+//! With `parse_json=True`, a `FOR JSON` result (which SQL Server returns as one
+//! NVARCHAR column split across several rows) is joined back together and returned as
+//! a single row holding the parsed dict or list.  A list of column names also parses
+//! those NVARCHAR/VARCHAR columns of every row.  Values are built straight from the
+//! parser, so objects keep their key order and no intermediate tree is allocated.
+
+use pyo3::IntoPyObjectExt;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyDict, PyList, PyString};
+use serde::de::{self, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+use std::fmt;
+use std::sync::Mutex;
+use tiberius::ColumnType;
+
+use crate::types::DataError;
+
+/// Name SQL Server gives the single column of a `FOR JSON` result.
+pub const FOR_JSON_COLUMN: &str = "JSON_F52E2B61-18A1-11d1-B105-00805F49916B";
+
+/// Which result columns are decoded as JSON.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum JsonMode {
+    #[default]
+    Off,
+    /// Only `FOR JSON` results.
+    ForJson,
+    /// `FOR JSON` results and the named columns (matched case-insensitively).
+    Columns(&'static [Box<str>]),
+}
+
+impl JsonMode {
+    pub fn parses_for_json(self) -> bool {
+        self != JsonMode::Off
+    }
+
+    /// Whether values of this column are parsed row by row.
+    pub fn parses_column(self, name: &str, col_type: ColumnType) -> bool {
+        let JsonMode::Columns(columns) = self else {
+            return false;
+        };
+        matches!(
+            col_type,
+            ColumnType::NVarchar
+                | ColumnType::NChar
+                | ColumnType::BigVarChar
+                | ColumnType::BigChar
+                | ColumnType::NText
+                | ColumnType::Text
+        ) && columns.iter().any(|c| c.eq_ignore_ascii_case(name))
+    }
+}
+
+/// Column lists seen so far.  `DecodeOptions` is `Copy`, so each distinct list is
+/// leaked once and shared by every connection that asks for it.
+static COLUMN_LISTS: Mutex<Vec<&'static [Box<str>]>> = Mutex::new(Vec::new());
+
+fn intern_columns(columns: Vec<Box<str>>) -> &'static [Box<str>] {
+    let mut lists = COLUMN_LISTS.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(list) = lists.iter().find(|list| ***list == *columns) {
+        return list;
+    }
+    let list: &'static [Box<str>] = Box::leak(columns.into_boxed_slice());
+    lists.push(list);
+    list
+}
+
+/// Read the `parse_json` argument: a bool, or an iterable of column names.
+pub fn parse_json_option(value: Option<&Bound<'_, PyAny>>) -> PyResult<JsonMode> {
+    let Some(value) = value else {
+        return Ok(JsonMode::Off);
+    };
+    if value.is_none() {
+        return Ok(JsonMode::Off);
+    }
+    if value.is_instance_of::<PyBool>() {
+        return Ok(if value.is_truthy()? {
+            JsonMode::ForJson
+        } else {
+            JsonMode::Off
+        });
+    }
+    if value.is_instance_of::<PyString>() {
+        return Err(PyValueError::new_err(
+            "parse_json expects True/False or a list of column names, not a single string",
+        ));
+    }
+    let mut columns: Vec<Box<str>> = Vec::new();
+    for item in value.try_iter()? {
+        let name = item?
+            .extract::<String>()
+            .map_err(|_| PyValueError::new_err("parse_json column names must be strings"))?;
+        if !columns.iter().any(|c| c.eq_ignore_ascii_case(&name)) {
+            columns.push(name.into_boxed_str());
+        }
+    }
+    Ok(JsonMode::Columns(intern_columns(columns)))
+}
+
+/// Parse `text` into Python objects.  `column` names the source in the error raised
+/// for invalid JSON.
+pub fn json_to_python(py: Python<'_>, text: &str, column: &str) -> PyResult<Py<PyAny>> {
+    let mut deserializer = serde_json::Deserializer::from_str(text);
+    PyValueSeed(py)
+        .deserialize(&mut deserializer)
+        .and_then(|value| deserializer.end().map(|()| value))
+        .map_err(|e| {
+            DataError::new_err(format!(
+                "Column '{}' does not contain valid JSON: {}",
+                column, e
+            ))
+        })
+}
+
+/// Builds the Python value of one JSON value as it is parsed.
+#[derive(Clone, Copy)]
+struct PyValueSeed<'py>(Python<'py>);
+
+fn to_object<'py, T, E>(py: Python<'py>, value: T) -> Result<Py<PyAny>, E>
+where
+    T: IntoPyObject<'py>,
+    E: de::Error,
+{
+    value.into_py_any(py).map_err(E::custom)
+}
+
+impl<'de> DeserializeSeed<'de> for PyValueSeed<'_> {
+    type Value = Py<PyAny>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for PyValueSeed<'_> {
+    type Value = Py<PyAny>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a JSON value")
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(self.0.None())
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<Self::Value, E> {
+        to_object(self.0, v)
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+        to_object(self.0, v)
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        to_object(self.0, v)
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Self::Value, E> {
+        to_object(self.0, v)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        to_object(self.0, v)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let list = PyList::empty(self.0);
+        while let Some(item) = seq.next_element_seed(self)? {
+            list.append(item).map_err(de::Error::custom)?;
+        }
+        Ok(list.into_any().unbind())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let dict = PyDict::new(self.0);
+        while let Some(key) = map.next_key::<String>()? {
+            let value = map.next_value_seed(self)?;
+            dict.set_item(key, value).map_err(de::Error::custom)?;
+        }
+        Ok(dict.into_any().unbind())
+    }
+}
@@ -1,30 +1,85 @@
 #![allow(non_local_definitions)]
 
+#[cfg(feature = "mimalloc")]
 #[global_allocator]
 static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 
 use pyo3::prelude::*;
 
+mod arrow_conversion;
+mod arrow_import;
+mod attention;
 mod azure_auth;
 mod batch;
+mod bulk_copy;
+mod bulk_verify;
+mod cert_expiry;
+mod client_assertion;
+mod column_set;
 mod connection;
+mod diagnose;
+mod dml_progress;
+mod dns_cache;
+mod dynamic_sql;
+mod execution_id;
+mod filestream;
+mod fingerprint;
 mod helpers;
+mod host_resolver;
+mod info_messages;
+mod integer_columns;
+mod ipc_transport;
+mod json_columns;
+mod metadata;
+mod migrations;
+mod page_token;
 mod parameter_conversion;
+mod pinned;
 mod pool_config;
 mod pool_manager;
+mod pool_registry;
+mod proxy;
 mod py_parameters;
+mod query_label;
+mod query_log;
+mod result_limits;
+mod result_sets;
+mod retry_policy;
+mod row_hash;
+mod row_stream;
+mod sql_compose;
+mod sql_placeholders;
 mod ssl_config;
+mod statement_policy;
+mod tcp_connect;
+mod tls_info;
+mod touched_tables;
 mod transaction;
 mod type_mapping;
 mod types;
+mod wire_debug;
+mod xml_columns;
 
 pub use azure_auth::{AzureCredentialType, PyAzureCredential};
+pub use batch::PyBatch;
 pub use connection::PyConnection;
+pub use pinned::PyPinnedConnection;
 pub use pool_config::PyPoolConfig;
 pub use py_parameters::{Parameter, Parameters};
+pub use result_sets::PyResultSetStream;
+pub use retry_policy::PyRetryPolicy;
+pub use row_stream::{PyChunkStream, PyRowStream};
+pub use sql_compose::{PyComposed, PyIdentifier, PyParam, PySql};
 pub use ssl_config::{EncryptionLevel, PySslConfig};
+pub use statement_policy::PyStatementPolicy;
 pub use transaction::Transaction;
-pub use types::{PyFastRow, PyQueryStream, SqlError, SqlConnectionError, TlsError, ProtocolError, ConversionError};
+pub use types::{
+    concat, database_unavailable_error, invalid_credentials_error, login_disabled_error,
+    AuthenticationError, ConversionError, DataError, DatabaseError, FastMssqlError,
+    IntegrityError, InterfaceError, OperationalError, PoolTimeoutError, ProgrammingError,
+    ProtocolError, PyFastRow, PyQueryStream, QueryTimeoutError, SqlConnectionError, SqlError,
+    TlsError,
+};
 
 use crate::parameter_conversion::TypedNull;
 
@@ -45,7 +100,7 @@ fn fastmssql(m: &Bound<'_, PyModule>) -> PyResult<()> {
         .enable_all()
         // Async I/O workload: 1× CPU workers is optimal. More workers increase work-stealing
         // contention without improving throughput for DB-latency-bound operations.
-        .worker_threads(cpu_count.max(4).min(16))
+        .worker_threads(cpu_count.clamp(4, 16))
         // No spawn_blocking is used anywhere in this codebase — all DB I/O is async.
         // A small ceiling gives a safety margin for any future sync work without
         // ballooning virtual memory (2 MB stack × N threads).
@@ -60,15 +115,27 @@ fn fastmssql(m: &Bound<'_, PyModule>) -> PyResult<()> {
         .event_interval(61); // Tokio default — batches I/O event polling per scheduler tick
 
     pyo3_async_runtimes::tokio::init(builder);
+    info_messages::install();
 
     m.add_class::<PyConnection>()?;
+    m.add_class::<PyPinnedConnection>()?;
     m.add_class::<Transaction>()?;
+    m.add_class::<PyBatch>()?;
     m.add_class::<PyFastRow>()?;
     m.add_class::<PyQueryStream>()?;
+    m.add_class::<PyResultSetStream>()?;
+    m.add_class::<PyRowStream>()?;
+    m.add_class::<PyChunkStream>()?;
     m.add_class::<Parameter>()?;
     m.add_class::<Parameters>()?;
+    m.add_class::<PySql>()?;
+    m.add_class::<PyIdentifier>()?;
+    m.add_class::<PyParam>()?;
+    m.add_class::<PyComposed>()?;
     m.add_class::<PyPoolConfig>()?;
+    m.add_class::<PyRetryPolicy>()?;
     m.add_class::<PySslConfig>()?;
+    m.add_class::<PyStatementPolicy>()?;
     m.add_class::<EncryptionLevel>()?;
     m.add_class::<PyAzureCredential>()?;
     m.add_class::<AzureCredentialType>()?;
@@ -76,14 +143,43 @@ fn fastmssql(m: &Bound<'_, PyModule>) -> PyResult<()> {
     
     {
         let py = m.py();
+        m.add("FastMssqlError", py.get_type::<FastMssqlError>())?;
+        m.add("InterfaceError", py.get_type::<InterfaceError>())?;
+        m.add("PolicyViolation", py.get_type::<types::PolicyViolation>())?;
+        m.add("ResultLimitError", py.get_type::<types::ResultLimitError>())?;
+        m.add("DatabaseError", py.get_type::<DatabaseError>())?;
         m.add("SqlError", py.get_type::<SqlError>())?;
+        m.add("IntegrityError", py.get_type::<IntegrityError>())?;
+        m.add("ProgrammingError", py.get_type::<ProgrammingError>())?;
+        m.add("OperationalError", py.get_type::<OperationalError>())?;
+        m.add("PoolTimeoutError", py.get_type::<PoolTimeoutError>())?;
+        m.add("AuthenticationError", py.get_type::<AuthenticationError>())?;
+        m.add("InvalidCredentialsError", invalid_credentials_error(py))?;
+        m.add("LoginDisabledError", login_disabled_error(py))?;
+        m.add("DatabaseUnavailableError", database_unavailable_error(py))?;
+        m.add("QueryTimeoutError", py.get_type::<QueryTimeoutError>())?;
         m.add("SqlConnectionError", py.get_type::<SqlConnectionError>())?;
         m.add("TlsError", py.get_type::<TlsError>())?;
         m.add("ProtocolError", py.get_type::<ProtocolError>())?;
         m.add("ConversionError", py.get_type::<ConversionError>())?;
+        m.add("DataError", py.get_type::<DataError>())?;
+        m.add(
+            "CertificateExpiryWarning",
+            py.get_type::<types::CertificateExpiryWarning>(),
+        )?;
     }
 
     m.add_function(wrap_pyfunction!(version, m)?)?;
+    m.add_function(wrap_pyfunction!(concat, m)?)?;
+    m.add_function(wrap_pyfunction!(fingerprint::statement_fingerprint, m)?)?;
+    m.add_function(wrap_pyfunction!(diagnose::diagnose, m)?)?;
+    m.add_function(wrap_pyfunction!(dns_cache::set_dns_cache_ttl, m)?)?;
+    m.add_function(wrap_pyfunction!(dns_cache::flush_dns_cache, m)?)?;
+    m.add_function(wrap_pyfunction!(host_resolver::set_host_aliases, m)?)?;
+    m.add_function(wrap_pyfunction!(host_resolver::set_resolver, m)?)?;
+    m.add_function(wrap_pyfunction!(proxy::set_proxies, m)?)?;
+    m.add_function(wrap_pyfunction!(cert_expiry::set_certificate_expiry_warning, m)?)?;
+    m.add_function(wrap_pyfunction!(wire_debug::enable_wire_debug, m)?)?;
 
     Ok(())
 }
@@ -0,0 +1,155 @@
+//! Declared result-set and table metadata.
+//!
+//! Row metadata on the wire only carries the TDS type of each column, not its
+//! declared length, so `Connection.describe()` asks the server to describe the
+//! statement instead of executing it (`sp_describe_first_result_set`), and
+//! `Connection.table_columns()` reads the catalog views for a table.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use tiberius::Row;
+
+/// Describes the first result set of the statement bound as `@P1` without running it.
+pub const DESCRIBE_SQL: &str = "EXEC sp_describe_first_result_set @tsql = @P1";
+
+/// Columns of the table named by `@P1`, with the flags needed to decide whether a
+/// value can be supplied on INSERT. `GeneratedAlwaysType` (temporal period columns)
+/// is read through COLUMNPROPERTY so the query still runs on pre-2016 servers,
+/// where it is NULL.
+pub const TABLE_COLUMNS_SQL: &str = "\
+SELECT c.name, \
+       TYPE_NAME(c.system_type_id) AS type, \
+       c.is_nullable, \
+       c.is_identity, \
+       c.is_computed, \
+       CAST(ISNULL(cc.is_persisted, 0) AS bit) AS is_persisted, \
+       CAST(CASE WHEN c.default_object_id <> 0 THEN 1 ELSE 0 END AS bit) AS has_default, \
+       CAST(CASE WHEN c.system_type_id = 189 THEN 1 ELSE 0 END AS bit) AS is_rowversion, \
+       CAST(CASE WHEN ISNULL(COLUMNPROPERTY(c.object_id, c.name, 'GeneratedAlwaysType'), 0) <> 0 \
+            THEN 1 ELSE 0 END AS bit) AS is_generated_always \
+FROM sys.columns c \
+LEFT JOIN sys.computed_columns cc ON cc.object_id = c.object_id AND cc.column_id = c.column_id \
+WHERE c.object_id = OBJECT_ID(@P1) \
+ORDER BY c.column_id";
+
+fn get_column<'a, R>(row: &'a Row, column: &str) -> PyResult<Option<R>>
+where
+    R: tiberius::FromSql<'a>,
+{
+    row.try_get::<R, &str>(column).map_err(|e| {
+        PyValueError::new_err(format!(
+            "Unexpected sp_describe_first_result_set output for '{}': {}",
+            column, e
+        ))
+    })
+}
+
+/// Convert `sp_describe_first_result_set` rows into a list of column dicts.
+///
+/// `max_length` is the declared size in bytes (-1 for MAX types) as reported by
+/// the server; `char_length` is the declared size in characters for character
+/// types (half the byte length for NCHAR/NVARCHAR) and `None` otherwise.
+pub fn describe_rows_to_py(rows: &[Row], py: Python<'_>) -> PyResult<Py<PyAny>> {
+    let list = PyList::empty(py);
+
+    for row in rows {
+        // Hidden columns are only added for browse-mode metadata (FOR BROWSE).
+        if get_column::<bool>(row, "is_hidden")?.unwrap_or(false) {
+            continue;
+        }
+
+        let declared_type = get_column::<&str>(row, "system_type_name")?.unwrap_or("");
+        let base_type = declared_type
+            .split('(')
+            .next()
+            .unwrap_or(declared_type)
+            .trim()
+            .to_ascii_lowercase();
+        let max_length = get_column::<i16>(row, "max_length")?.unwrap_or(0);
+        let is_unicode = matches!(base_type.as_str(), "nchar" | "nvarchar" | "ntext");
+        let char_length = match base_type.as_str() {
+            "text" | "ntext" => Some(-1),
+            _ if max_length == -1 && matches!(base_type.as_str(), "varchar" | "nvarchar") => {
+                Some(-1)
+            }
+            "nchar" | "nvarchar" => Some(max_length / 2),
+            "char" | "varchar" => Some(max_length),
+            _ => None,
+        };
+
+        let dict = PyDict::new(py);
+        dict.set_item("name", get_column::<&str>(row, "name")?)?;
+        dict.set_item("type", base_type.as_str())?;
+        dict.set_item("declared_type", declared_type)?;
+        dict.set_item(
+            "nullable",
+            get_column::<bool>(row, "is_nullable")?.unwrap_or(true),
+        )?;
+        dict.set_item("is_unicode", is_unicode)?;
+        dict.set_item("max_length", max_length)?;
+        dict.set_item("char_length", char_length)?;
+        dict.set_item(
+            "precision",
+            get_column::<u8>(row, "precision")?.unwrap_or(0),
+        )?;
+        dict.set_item("scale", get_column::<u8>(row, "scale")?.unwrap_or(0))?;
+        dict.set_item(
+            "is_identity",
+            get_column::<bool>(row, "is_identity_column")?.unwrap_or(false),
+        )?;
+        dict.set_item(
+            "is_computed",
+            get_column::<bool>(row, "is_computed_column")?.unwrap_or(false),
+        )?;
+        list.append(dict)?;
+    }
+
+    Ok(list.into())
+}
+
+fn get_flag(row: &Row, column: &str) -> PyResult<bool> {
+    Ok(get_column::<bool>(row, column)?.unwrap_or(false))
+}
+
+/// Convert `TABLE_COLUMNS_SQL` rows into a list of column dicts.
+///
+/// `is_insertable` is false for identity, computed, rowversion and GENERATED ALWAYS
+/// columns, which SQL Server rejects (or, for identity, rejects without
+/// `IDENTITY_INSERT`) in an INSERT column list.
+pub fn table_columns_to_py(table: &str, rows: &[Row], py: Python<'_>) -> PyResult<Py<PyAny>> {
+    // Every table has at least one column, so no rows means OBJECT_ID() was NULL.
+    if rows.is_empty() {
+        return Err(PyValueError::new_err(format!(
+            "Table '{}' not found",
+            table
+        )));
+    }
+
+    let list = PyList::empty(py);
+
+    for row in rows {
+        let is_identity = get_flag(row, "is_identity")?;
+        let is_computed = get_flag(row, "is_computed")?;
+        let is_rowversion = get_flag(row, "is_rowversion")?;
+        let is_generated_always = get_flag(row, "is_generated_always")?;
+
+        let dict = PyDict::new(py);
+        dict.set_item("name", get_column::<&str>(row, "name")?)?;
+        dict.set_item("type", get_column::<&str>(row, "type")?)?;
+        dict.set_item("nullable", get_flag(row, "is_nullable")?)?;
+        dict.set_item("is_identity", is_identity)?;
+        dict.set_item("is_computed", is_computed)?;
+        dict.set_item("is_persisted", get_flag(row, "is_persisted")?)?;
+        dict.set_item("has_default", get_flag(row, "has_default")?)?;
+        dict.set_item("is_rowversion", is_rowversion)?;
+        dict.set_item("is_generated_always", is_generated_always)?;
+        dict.set_item(
+            "is_insertable",
+            !(is_identity || is_computed || is_rowversion || is_generated_always),
+        )?;
+        list.append(dict)?;
+    }
+
+    Ok(list.into())
+}
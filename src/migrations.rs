@@ -0,0 +1,336 @@
+//! Versioned SQL scripts for `Connection.apply_migrations(directory)`.
+//!
+//! The `*.sql` files of a directory are applied in file name order, each once: a
+//! version table records the name and checksum of every applied script, and a
+//! session application lock keeps two processes from applying the same scripts at
+//! the same time.  Scripts are split on `GO` lines like sqlcmd does, and each runs
+//! in its own transaction together with the insert of its version row, unless it
+//! holds a statement SQL Server refuses inside a user transaction.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tiberius::Client;
+use tokio_util::compat::Compat;
+
+use crate::batch::quote_identifier;
+use crate::fingerprint;
+use crate::sql_placeholders::{code_words, split_go_batches};
+use crate::tls_info::ObservedStream;
+use crate::types::{InterfaceError, create_sql_error};
+
+pub const DEFAULT_TABLE: &str = "dbo.__fastmssql_migrations";
+
+/// Comment that makes a script run outside a transaction.
+const NO_TRANSACTION_MARKER: &str = "-- fastmssql:no-transaction";
+
+/// How long to wait for another process applying migrations to finish.
+const LOCK_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// One `.sql` file of the migrations directory.
+pub struct Script {
+    pub name: String,
+    checksum: String,
+    pub batches: Vec<(String, u32)>,
+    transactional: bool,
+}
+
+/// Read the `*.sql` files of `directory`, sorted by name.  Checksums are taken with
+/// `\r\n` line endings normalised, so a checkout on another platform still matches.
+pub fn load_scripts(directory: &Path) -> PyResult<Vec<Script>> {
+    let entries = std::fs::read_dir(directory).map_err(|e| {
+        PyValueError::new_err(format!(
+            "Cannot read migrations directory '{}': {}",
+            directory.display(),
+            e
+        ))
+    })?;
+    let mut paths = Vec::new();
+    for entry in entries {
+        let path = entry
+            .map_err(|e| PyValueError::new_err(e.to_string()))?
+            .path();
+        let is_sql = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("sql"));
+        if is_sql && path.is_file() {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    let mut scripts = Vec::with_capacity(paths.len());
+    for path in paths {
+        let name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .ok_or_else(|| {
+                PyValueError::new_err(format!("Invalid migration file name: {}", path.display()))
+            })?
+            .to_string();
+        let text = std::fs::read_to_string(&path).map_err(|e| {
+            PyValueError::new_err(format!("Cannot read migration '{}': {}", name, e))
+        })?;
+        let text = text.replace("\r\n", "\n");
+        let batches = split_go_batches(&text)
+            .into_iter()
+            .map(|(sql, count)| (sql.to_string(), count))
+            .collect();
+        scripts.push(Script {
+            checksum: fingerprint::to_hex(text.as_bytes()),
+            transactional: !text.contains(NO_TRANSACTION_MARKER) && !needs_autocommit(&text),
+            name,
+            batches,
+        });
+    }
+    Ok(scripts)
+}
+
+/// Whether `sql` holds a statement that cannot run inside a user transaction.
+fn needs_autocommit(sql: &str) -> bool {
+    let words: Vec<String> = code_words(sql)
+        .into_iter()
+        .map(str::to_ascii_lowercase)
+        .collect();
+    words.iter().enumerate().any(|(i, word)| {
+        let next = words.get(i + 1).map_or("", String::as_str);
+        match word.as_str() {
+            "backup" | "restore" | "reconfigure" => true,
+            "create" | "alter" | "drop" => matches!(next, "database" | "fulltext"),
+            _ => false,
+        }
+    })
+}
+
+/// What happened to one script.
+pub struct Outcome {
+    name: String,
+    status: &'static str,
+    batches: usize,
+    duration: Option<Duration>,
+    transactional: bool,
+}
+
+impl Outcome {
+    fn new(script: &Script, status: &'static str, duration: Option<Duration>) -> Self {
+        Outcome {
+            name: script.name.clone(),
+            status,
+            batches: script.batches.len(),
+            duration,
+            transactional: script.transactional,
+        }
+    }
+}
+
+/// `[{"name", "status", "batches", "duration_ms", "transactional"}]`
+pub fn outcomes_to_py(py: Python<'_>, outcomes: Vec<Outcome>) -> PyResult<Py<PyAny>> {
+    let list = PyList::empty(py);
+    for outcome in outcomes {
+        let entry = PyDict::new(py);
+        entry.set_item("name", outcome.name)?;
+        entry.set_item("status", outcome.status)?;
+        entry.set_item("batches", outcome.batches)?;
+        entry.set_item(
+            "duration_ms",
+            outcome.duration.map(|d| d.as_secs_f64() * 1000.0),
+        )?;
+        entry.set_item("transactional", outcome.transactional)?;
+        list.append(entry)?;
+    }
+    Ok(list.into_any().unbind())
+}
+
+/// Apply the scripts not yet recorded in `table`, or with `dry_run` only report
+/// which those are.  Raises if an applied script has changed since it was applied.
+///
+/// The session lock, `XACT_ABORT` and any open transaction are left behind when a
+/// script fails, so the caller must discard the connection on error.
+pub async fn apply(
+    client: &mut Client<Compat<ObservedStream>>,
+    scripts: &[Script],
+    table: &str,
+    dry_run: bool,
+) -> PyResult<Vec<Outcome>> {
+    let quoted = quote_identifier(table)?;
+
+    if !dry_run {
+        let create = format!(
+            "IF OBJECT_ID(@P1, 'U') IS NULL CREATE TABLE {} (\
+             name NVARCHAR(255) NOT NULL PRIMARY KEY, \
+             checksum CHAR(16) NOT NULL, \
+             applied_at DATETIME2 NOT NULL DEFAULT SYSUTCDATETIME(), \
+             duration_ms BIGINT NOT NULL)",
+            quoted
+        );
+        run(
+            client,
+            &create,
+            &[&quoted.as_str()],
+            "Failed to create the migrations table",
+        )
+        .await?;
+        let lock = format!(
+            "DECLARE @result INT; EXEC @result = sp_getapplock @Resource = @P1, \
+             @LockMode = 'Exclusive', @LockOwner = 'Session', @LockTimeout = {}; \
+             SELECT @result",
+            LOCK_TIMEOUT.as_millis()
+        );
+        let resource = format!("fastmssql:migrations:{}", table);
+        let row = client
+            .query(lock, &[&resource.as_str()])
+            .await
+            .map_err(|e| create_sql_error(e, "Failed to lock the migrations table"))?
+            .into_row()
+            .await
+            .map_err(|e| create_sql_error(e, "Failed to lock the migrations table"))?;
+        let result = row.and_then(|r| r.try_get::<i32, _>(0).ok().flatten());
+        if result.unwrap_or(-1) < 0 {
+            return Err(InterfaceError::new_err(format!(
+                "Timed out waiting for another process applying migrations to {}",
+                table
+            )));
+        }
+    }
+
+    // Deferred name resolution lets this compile when the table does not exist yet.
+    let select = format!(
+        "IF OBJECT_ID(@P1, 'U') IS NOT NULL SELECT name, checksum FROM {}",
+        quoted
+    );
+    let applied: Vec<(String, String)> = client
+        .query(select, &[&quoted.as_str()])
+        .await
+        .map_err(|e| create_sql_error(e, "Failed to read the migrations table"))?
+        .into_first_result()
+        .await
+        .map_err(|e| create_sql_error(e, "Failed to read the migrations table"))?
+        .iter()
+        .map(|row| {
+            let name = row.get::<&str, _>(0).unwrap_or_default().to_string();
+            let checksum = row.get::<&str, _>(1).unwrap_or_default().trim().to_string();
+            (name, checksum)
+        })
+        .collect();
+
+    for script in scripts {
+        if let Some((_, checksum)) = applied.iter().find(|(name, _)| *name == script.name)
+            && *checksum != script.checksum
+        {
+            return Err(InterfaceError::new_err(format!(
+                "Migration '{}' was changed after it was applied \
+                 (checksum {} recorded, {} on disk)",
+                script.name, checksum, script.checksum
+            )));
+        }
+    }
+
+    let mut outcomes = Vec::with_capacity(scripts.len());
+    for script in scripts {
+        if applied.iter().any(|(name, _)| *name == script.name) {
+            outcomes.push(Outcome::new(script, "skipped", None));
+        } else if dry_run {
+            outcomes.push(Outcome::new(script, "pending", None));
+        } else {
+            let elapsed = apply_script(client, script, &quoted).await?;
+            outcomes.push(Outcome::new(script, "applied", Some(elapsed)));
+        }
+    }
+
+    if !dry_run {
+        let release = "SET XACT_ABORT OFF; \
+                       EXEC sp_releaseapplock @Resource = @P1, @LockOwner = 'Session'";
+        let resource = format!("fastmssql:migrations:{}", table);
+        run(
+            client,
+            release,
+            &[&resource.as_str()],
+            "Failed to unlock the migrations table",
+        )
+        .await?;
+    }
+    Ok(outcomes)
+}
+
+/// Run every batch of `script` and record it in the version table, inside one
+/// transaction when the script allows it.
+async fn apply_script(
+    client: &mut Client<Compat<ObservedStream>>,
+    script: &Script,
+    quoted_table: &str,
+) -> PyResult<Duration> {
+    let started = Instant::now();
+    if script.transactional {
+        run(
+            client,
+            "SET XACT_ABORT ON; BEGIN TRANSACTION",
+            &[],
+            "Failed to start transaction",
+        )
+        .await?;
+    }
+    let total = script.batches.len();
+    for (index, (sql, count)) in script.batches.iter().enumerate() {
+        for _ in 0..*count {
+            let result = match client.simple_query(sql.as_str()).await {
+                Ok(stream) => stream.into_results().await.map(|_| ()),
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                let err = create_sql_error(e, "Migration failed");
+                Python::attach(|py| {
+                    let note = format!(
+                        "migration: {}, batch {} of {}",
+                        script.name,
+                        index + 1,
+                        total
+                    );
+                    let _ = err.value(py).call_method1("add_note", (note,));
+                });
+                return Err(err);
+            }
+        }
+    }
+    let elapsed = started.elapsed();
+    let record = format!(
+        "INSERT INTO {} (name, checksum, duration_ms) VALUES (@P1, @P2, @P3)",
+        quoted_table
+    );
+    let duration_ms = elapsed.as_millis() as i64;
+    run(
+        client,
+        &record,
+        &[
+            &script.name.as_str(),
+            &script.checksum.as_str(),
+            &duration_ms,
+        ],
+        "Failed to record the migration",
+    )
+    .await?;
+    if script.transactional {
+        run(
+            client,
+            "COMMIT TRANSACTION",
+            &[],
+            "Failed to commit the migration",
+        )
+        .await?;
+    }
+    Ok(elapsed)
+}
+
+async fn run(
+    client: &mut Client<Compat<ObservedStream>>,
+    sql: &str,
+    params: &[&dyn tiberius::ToSql],
+    context: &'static str,
+) -> PyResult<()> {
+    client
+        .execute(sql, params)
+        .await
+        .map(|_| ())
+        .map_err(|e| create_sql_error(e, context))
+}
@@ -0,0 +1,251 @@
+//! Paged reads for `Connection.fetch_page()`.
+//!
+//! A page is the user's query wrapped so the server returns at most `page_size + 1`
+//! rows; the extra row only tells whether another page follows.  With `key` columns
+//! the wrapper filters on the last key seen (keyset paging, stable under concurrent
+//! inserts and cheap on an index), otherwise it appends `OFFSET ... FETCH` to the
+//! query's own `ORDER BY`.
+//!
+//! Where the next page starts is handed back as an opaque token: URL-safe base64 of a
+//! small JSON document holding the offset or the typed key values, plus a hash of the
+//! query and keys so a token cannot be replayed against a different query.  The
+//! values are bound as parameters when the token comes back, never spliced into SQL.
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use serde_json::{Value, json};
+use smallvec::SmallVec;
+use tiberius::Row;
+use tiberius::Uuid;
+use tiberius::numeric::Numeric;
+
+use crate::batch::quote_identifier_part;
+use crate::fingerprint::to_hex;
+use crate::parameter_conversion::{FastParameter, buffer_bytes, python_to_fast_parameter};
+use crate::type_mapping::{DecodeOptions, numeric_to_decimal_string, sql_to_python};
+
+const DATETIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.f";
+
+/// Where a page starts.
+pub enum PagePosition {
+    Start,
+    Offset(u64),
+    /// Values of the key columns in the last row of the previous page.
+    After(Vec<FastParameter>),
+}
+
+/// The `key=` argument: a column name or a list of them.
+pub fn page_keys(key: Option<&Bound<PyAny>>) -> PyResult<Vec<String>> {
+    let keys = match key {
+        None => Vec::new(),
+        Some(key) => match key.extract::<String>() {
+            Ok(name) => vec![name],
+            Err(_) => key.extract::<Vec<String>>().map_err(|_| {
+                PyValueError::new_err("key must be a column name or a list of column names")
+            })?,
+        },
+    };
+    if key.is_some() && keys.is_empty() {
+        return Err(PyValueError::new_err("key must name at least one column"));
+    }
+    Ok(keys)
+}
+
+/// Hash tying tokens to the query text and key columns they were issued for.
+pub fn token_scope(query: &str, keys: &[String]) -> String {
+    to_hex(format!("{}\0{}", query, keys.join("\0")).as_bytes())
+}
+
+/// Wrap `query`, whose own parameters are `parameters`, to return the page at
+/// `position`, appending the paging parameters.
+pub fn page_statement(
+    query: &str,
+    keys: &[String],
+    page_size: usize,
+    position: &PagePosition,
+    parameters: &mut SmallVec<[FastParameter; 16]>,
+) -> PyResult<String> {
+    let query = query.trim_end().trim_end_matches(';');
+    let fetch = format!("@P{}", parameters.len() + 1);
+    parameters.push(FastParameter::I64(page_size as i64 + 1));
+
+    if keys.is_empty() {
+        let offset = match position {
+            PagePosition::Offset(offset) => *offset,
+            _ => 0,
+        };
+        let skip = format!("@P{}", parameters.len() + 1);
+        parameters.push(FastParameter::I64(offset as i64));
+        return Ok(format!(
+            "{}\nOFFSET {} ROWS FETCH NEXT {} ROWS ONLY",
+            query, skip, fetch
+        ));
+    }
+
+    let columns = keys
+        .iter()
+        .map(|key| quote_identifier_part(key))
+        .collect::<PyResult<Vec<_>>>()?;
+    let mut sql = format!("SELECT TOP ({}) * FROM (\n{}\n) AS [page]", fetch, query);
+    if let PagePosition::After(values) = position {
+        // (k1 > v1) OR (k1 = v1 AND k2 > v2) OR ...
+        let mut placeholders = Vec::with_capacity(values.len());
+        for value in values {
+            placeholders.push(format!("@P{}", parameters.len() + 1));
+            parameters.push(value.clone());
+        }
+        let disjuncts: Vec<String> = (0..columns.len())
+            .map(|i| {
+                let mut terms: Vec<String> = (0..i)
+                    .map(|j| format!("{} = {}", columns[j], placeholders[j]))
+                    .collect();
+                terms.push(format!("{} > {}", columns[i], placeholders[i]));
+                format!("({})", terms.join(" AND "))
+            })
+            .collect();
+        sql.push_str(" WHERE ");
+        sql.push_str(&disjuncts.join(" OR "));
+    }
+    sql.push_str(" ORDER BY ");
+    sql.push_str(&columns.join(", "));
+    Ok(sql)
+}
+
+/// The token for the page after one that started at `position` and ended with `last`.
+pub fn next_token(
+    scope: &str,
+    position: &PagePosition,
+    keys: &[String],
+    page_size: usize,
+    last: &Row,
+    options: DecodeOptions,
+    py: Python<'_>,
+) -> PyResult<String> {
+    let document = if keys.is_empty() {
+        let offset = match position {
+            PagePosition::Offset(offset) => *offset,
+            _ => 0,
+        };
+        json!({ "s": scope, "o": offset + page_size as u64 })
+    } else {
+        let values = keys
+            .iter()
+            .map(|key| {
+                let index = last
+                    .columns()
+                    .iter()
+                    .position(|c| c.name() == key)
+                    .or_else(|| {
+                        last.columns()
+                            .iter()
+                            .position(|c| c.name().eq_ignore_ascii_case(key))
+                    })
+                    .ok_or_else(|| {
+                        PyValueError::new_err(format!(
+                            "fetch_page() key column '{}' is not in the result",
+                            key
+                        ))
+                    })?;
+                let col_type = last.columns()[index].column_type();
+                let value = sql_to_python(last, index, col_type, options, py)?;
+                encode_value(&python_to_fast_parameter(value.bind(py))?).ok_or_else(|| {
+                    PyValueError::new_err(format!(
+                        "fetch_page() key column '{}' is NULL in the last row; key columns \
+                         must not be NULL",
+                        key
+                    ))
+                })
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        json!({ "s": scope, "k": values })
+    };
+    Ok(URL_SAFE_NO_PAD.encode(document.to_string()))
+}
+
+/// Read back a token issued by [`next_token`] for the same `scope`.
+pub fn decode_token(token: &str, scope: &str) -> PyResult<PagePosition> {
+    let invalid = || PyValueError::new_err("Invalid page token");
+    let bytes = URL_SAFE_NO_PAD.decode(token.trim()).map_err(|_| invalid())?;
+    let document: Value = serde_json::from_slice(&bytes).map_err(|_| invalid())?;
+    if document.get("s").and_then(Value::as_str) != Some(scope) {
+        return Err(PyValueError::new_err(
+            "Page token was issued for a different query or key",
+        ));
+    }
+    if let Some(offset) = document.get("o") {
+        return offset.as_u64().map(PagePosition::Offset).ok_or_else(invalid);
+    }
+    let values = document
+        .get("k")
+        .and_then(Value::as_array)
+        .ok_or_else(invalid)?
+        .iter()
+        .map(|value| decode_value(value).ok_or_else(invalid))
+        .collect::<PyResult<Vec<_>>>()?;
+    Ok(PagePosition::After(values))
+}
+
+/// A key value as `[tag, value]`, keeping the type its parameter binds with; `None`
+/// for NULL, which no key can be compared with.
+fn encode_value(value: &FastParameter) -> Option<Value> {
+    Some(match value {
+        FastParameter::Null(_) => return None,
+        FastParameter::Bool(b) => json!(["b", b]),
+        FastParameter::I64(i) => json!(["i", i]),
+        FastParameter::F64(f) => json!(["f", f]),
+        FastParameter::Decimal(n) => json!(["d", numeric_to_decimal_string(*n)]),
+        FastParameter::Guid(g) => json!(["u", g.to_string()]),
+        FastParameter::String(s) | FastParameter::JsonList(s) | FastParameter::Xml(s) => {
+            json!(["s", s])
+        }
+        FastParameter::Bytes(b) => json!(["x", URL_SAFE_NO_PAD.encode(b)]),
+        FastParameter::Buffer(b) => json!(["x", URL_SAFE_NO_PAD.encode(buffer_bytes(b))]),
+        FastParameter::Date(d) => json!(["D", d.to_string()]),
+        FastParameter::DateTime(dt) | FastParameter::SmallDateTime(dt) => {
+            json!(["T", dt.format(DATETIME_FORMAT).to_string()])
+        }
+        FastParameter::Time(t) => json!(["t", t.to_string()]),
+        FastParameter::DateTimeOffset(dt) => json!(["z", dt.to_rfc3339()]),
+    })
+}
+
+fn decode_value(value: &Value) -> Option<FastParameter> {
+    let parts = value.as_array()?;
+    let tag = parts.first()?.as_str()?;
+    let value = parts.get(1)?;
+    let text = || value.as_str();
+    Some(match tag {
+        "b" => FastParameter::Bool(value.as_bool()?),
+        "i" => FastParameter::I64(value.as_i64()?),
+        "f" => FastParameter::F64(value.as_f64()?),
+        "d" => FastParameter::Decimal(parse_decimal(text()?)?),
+        "u" => FastParameter::Guid(Uuid::parse_str(text()?).ok()?),
+        "s" => FastParameter::String(text()?.to_string()),
+        "x" => FastParameter::Bytes(URL_SAFE_NO_PAD.decode(text()?).ok()?),
+        "D" => FastParameter::Date(NaiveDate::parse_from_str(text()?, "%Y-%m-%d").ok()?),
+        "T" => FastParameter::DateTime(
+            NaiveDateTime::parse_from_str(text()?, DATETIME_FORMAT).ok()?,
+        ),
+        "t" => FastParameter::Time(NaiveTime::parse_from_str(text()?, "%H:%M:%S%.f").ok()?),
+        "z" => FastParameter::DateTimeOffset(DateTime::parse_from_rfc3339(text()?).ok()?),
+        _ => return None,
+    })
+}
+
+/// Parse the output of `numeric_to_decimal_string` back into a `Numeric`.
+fn parse_decimal(text: &str) -> Option<Numeric> {
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text),
+    };
+    let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    let scale = u8::try_from(fraction.len()).ok().filter(|s| *s < 38)?;
+    let value: i128 = format!("{}{}", whole, fraction).parse().ok()?;
+    Some(Numeric::new_with_scale(
+        if negative { -value } else { value },
+        scale,
+    ))
+}
@@ -1,10 +1,20 @@
-use crate::py_parameters::Parameters;
+use crate::py_parameters::{Parameter, Parameters};
+use crate::sql_placeholders;
 use crate::type_mapping;
-use chrono::{NaiveDate, NaiveDateTime};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, Timelike};
+use pyo3::buffer::PyBuffer;
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
-use pyo3::types::{PyBool, PyBytes, PyFloat, PyInt, PyList, PyString};
+use pyo3::types::{
+    PyBool, PyByteArray, PyBytes, PyDateTime, PyDict, PyFloat, PyInt, PyList, PyMemoryView,
+    PyString,
+};
 use smallvec::SmallVec;
+use std::borrow::Cow;
+use std::sync::Arc;
+use tiberius::Uuid;
+use tiberius::numeric::Numeric;
+use tiberius::xml::XmlData;
 
 #[derive(Debug, Clone)]
 pub enum FastParameter {
@@ -12,10 +22,27 @@ pub enum FastParameter {
     Bool(bool),
     I64(i64),
     F64(f64),
+    /// `decimal.Decimal`, bound as NUMERIC with the value's own precision and scale.
+    Decimal(Numeric),
+    Guid(Uuid),
     String(String),
     Bytes(Vec<u8>),
+    /// A contiguous `bytearray` or `memoryview`, bound as VARBINARY straight from the
+    /// Python buffer instead of a copy.
+    Buffer(Arc<PyBuffer<u8>>),
     Date(NaiveDate),
-    DateTime(NaiveDateTime)
+    DateTime(NaiveDateTime),
+    /// Already rounded to the minute and range-checked by `to_smalldatetime`.
+    SmallDateTime(NaiveDateTime),
+    Time(NaiveTime),
+    /// A timezone-aware `datetime`, bound as DATETIMEOFFSET with its UTC offset.
+    DateTimeOffset(DateTime<FixedOffset>),
+    /// `Parameter.json_list()`: the values as one NVARCHAR(MAX) JSON array, read back
+    /// with OPENJSON by the `IN (@Pn)` predicate it is bound to.
+    JsonList(String),
+    /// An `xml.etree.ElementTree.Element`, or a string wrapped in
+    /// `Parameter(value, "XML")`, bound as XML rather than NVARCHAR.
+    Xml(String),
 }
 
 impl tiberius::ToSql for FastParameter {
@@ -25,10 +52,27 @@ impl tiberius::ToSql for FastParameter {
             FastParameter::Bool(b) => b.to_sql(),
             FastParameter::I64(i) => i.to_sql(),
             FastParameter::F64(f) => f.to_sql(),
-            FastParameter::String(s) => s.to_sql(),
+            FastParameter::Decimal(n) => n.to_sql(),
+            FastParameter::Guid(g) => g.to_sql(),
+            FastParameter::String(s) | FastParameter::JsonList(s) => s.to_sql(),
+            FastParameter::Xml(s) => {
+                tiberius::ColumnData::Xml(Some(Cow::Owned(XmlData::new(s.as_str()))))
+            }
             FastParameter::Bytes(b) => b.to_sql(),
+            FastParameter::Buffer(b) => {
+                tiberius::ColumnData::Binary(Some(Cow::Borrowed(buffer_bytes(b))))
+            }
             FastParameter::Date(d) => d.to_sql(),
-            FastParameter::DateTime(dt) => dt.to_sql()
+            FastParameter::DateTime(dt) => dt.to_sql(),
+            FastParameter::SmallDateTime(dt) => {
+                let days = (dt.date() - smalldatetime_epoch()).num_days() as u16;
+                let minutes = (dt.hour() * 60 + dt.minute()) as u16;
+                tiberius::ColumnData::SmallDateTime(Some(tiberius::time::SmallDateTime::new(
+                    days, minutes,
+                )))
+            }
+            FastParameter::Time(t) => t.to_sql(),
+            FastParameter::DateTimeOffset(dt) => dt.to_sql(),
         }
     }
 }
@@ -63,11 +107,47 @@ pub fn python_to_fast_parameter(obj: &Bound<PyAny>) -> PyResult<FastParameter> {
     if let Ok(py_by) = obj.cast::<PyBytes>() {
         return Ok(FastParameter::Bytes(py_by.as_bytes().to_vec()));
     }
+    if obj.is_instance_of::<PyByteArray>() || obj.is_instance_of::<PyMemoryView>() {
+        return buffer_to_fast_parameter(obj);
+    }
+    if let Ok(param) = obj.cast::<Parameter>() {
+        let param = param.borrow();
+        let value = param.value.bind(obj.py());
+        if param.is_json_list {
+            return Ok(FastParameter::JsonList(value.extract()?));
+        }
+        return match param.sql_type.as_deref() {
+            Some(sql_type) => python_to_typed_fast_parameter(value, sql_type),
+            None => python_to_fast_parameter(value),
+        };
+    }
+    if obj.is_instance(type_mapping::decimal_class(obj.py())?)? {
+        return decimal_to_numeric(obj).map(FastParameter::Decimal);
+    }
+    if obj.is_instance(type_mapping::uuid_class(obj.py())?)? {
+        let bytes: [u8; 16] = obj.getattr("bytes")?.extract()?;
+        return Ok(FastParameter::Guid(Uuid::from_bytes(bytes)));
+    }
+    // datetime is a subclass of date, so it has to be checked first.
+    if let Ok(py_dt) = obj.cast::<PyDateTime>() {
+        return datetime_to_fast_parameter(py_dt);
+    }
     if let Ok(py_date) = obj.extract::<NaiveDate>() {
         return Ok(FastParameter::Date(py_date));
     }
-    if let Ok(py_dt) = obj.extract::<NaiveDateTime>() {
-        return Ok(FastParameter::DateTime(py_dt));
+    if let Ok(py_time) = obj.extract::<NaiveTime>() {
+        return Ok(FastParameter::Time(py_time));
+    }
+
+    let element_tree = type_mapping::element_tree(obj.py())?;
+    if obj.is_instance(&element_tree.getattr("Element")?)? {
+        return element_to_xml(element_tree, obj);
+    }
+
+    if let Some(numpy) = type_mapping::numpy_scalar_types(obj.py())
+        && obj.is_instance(numpy.generic.bind(obj.py()))?
+    {
+        return numpy_scalar_to_fast_parameter(obj, numpy);
     }
 
     // Fallback for custom types
@@ -81,6 +161,325 @@ pub fn python_to_fast_parameter(obj: &Bound<PyAny>) -> PyResult<FastParameter> {
     }
 }
 
+/// Serialize an `Element` (and its subtree) for an XML parameter.
+fn element_to_xml(element_tree: &Bound<PyAny>, element: &Bound<PyAny>) -> PyResult<FastParameter> {
+    let kwargs = PyDict::new(element.py());
+    kwargs.set_item("encoding", "unicode")?;
+    let text = element_tree.call_method("tostring", (element,), Some(&kwargs))?;
+    Ok(FastParameter::Xml(text.extract()?))
+}
+
+/// numpy booleans, integers and floats bind like their Python counterparts.  float64
+/// already subclasses `float` (and `str_`/`bytes_` subclass `str`/`bytes`), so only the
+/// other sizes reach this point.
+fn numpy_scalar_to_fast_parameter(
+    obj: &Bound<PyAny>,
+    numpy: &type_mapping::NumpyScalarTypes,
+) -> PyResult<FastParameter> {
+    let py = obj.py();
+    if obj.is_instance(numpy.bool_.bind(py))? {
+        return Ok(FastParameter::Bool(obj.is_truthy()?));
+    }
+    if obj.is_instance(numpy.integer.bind(py))? {
+        return obj
+            .extract::<i64>()
+            .map(FastParameter::I64)
+            .map_err(|_| PyValueError::new_err("Int too large"));
+    }
+    if obj.is_instance(numpy.floating.bind(py))? {
+        return obj.extract::<f64>().map(FastParameter::F64);
+    }
+    Err(PyValueError::new_err(format!(
+        "Unsupported type: numpy.{}",
+        obj.get_type().name()?
+    )))
+}
+
+/// Keep a contiguous view of `bytes` exported for the lifetime of the parameter rather
+/// than copying it.  Mutable buffers (a `bytearray`, or a view of one) are copied: the
+/// statement reads the parameter after the GIL is released, when Python code could be
+/// writing to it.  Strided views and views of other item types are copied too.
+fn buffer_to_fast_parameter(obj: &Bound<PyAny>) -> PyResult<FastParameter> {
+    match PyBuffer::<u8>::get(obj) {
+        Ok(buffer)
+            if buffer.is_c_contiguous() && buffer.readonly() && views_immutable_bytes(obj)? =>
+        {
+            Ok(FastParameter::Buffer(Arc::new(buffer)))
+        }
+        _ => {
+            let bytes = obj.py().get_type::<PyBytes>().call1((obj,))?;
+            Ok(FastParameter::Bytes(
+                bytes.cast::<PyBytes>()?.as_bytes().to_vec(),
+            ))
+        }
+    }
+}
+
+/// Whether `obj` is a memoryview of a `bytes` object.  A read-only view does not make
+/// its memory immutable (`memoryview(bytearray()).toreadonly()`), so the exporter is
+/// checked instead.
+fn views_immutable_bytes(obj: &Bound<PyAny>) -> PyResult<bool> {
+    Ok(obj.is_instance_of::<PyMemoryView>()
+        && obj.getattr("obj")?.is_exact_instance_of::<PyBytes>())
+}
+
+/// The bytes of a buffer wrapped by `buffer_to_fast_parameter`.  An exported buffer
+/// cannot be resized or freed, so the memory stays valid while `buffer` lives.
+pub fn buffer_bytes(buffer: &PyBuffer<u8>) -> &[u8] {
+    if buffer.len_bytes() == 0 {
+        return &[];
+    }
+    // SAFETY: only C-contiguous views of `bytes` with `u8` items are wrapped, so the
+    // memory is `len_bytes()` consecutive bytes starting at `buf_ptr()`, and nothing
+    // can write to it while the buffer is exported.
+    unsafe { std::slice::from_raw_parts(buffer.buf_ptr() as *const u8, buffer.len_bytes()) }
+}
+
+/// Naive datetimes bind as DATETIME2; aware ones as DATETIMEOFFSET, keeping the offset
+/// `utcoffset()` reports so any `tzinfo` (including `zoneinfo`) works.
+fn datetime_to_fast_parameter(dt: &Bound<PyDateTime>) -> PyResult<FastParameter> {
+    let kwargs = PyDict::new(dt.py());
+    kwargs.set_item("tzinfo", dt.py().None())?;
+    let local = dt
+        .call_method("replace", (), Some(&kwargs))?
+        .extract::<NaiveDateTime>()?;
+    let offset = dt.call_method0("utcoffset")?;
+    if offset.is_none() {
+        return Ok(FastParameter::DateTime(local));
+    }
+
+    let delta = offset.extract::<TimeDelta>()?;
+    let seconds = delta.num_seconds();
+    let whole_minutes = delta.subsec_nanos() == 0 && seconds % 60 == 0;
+    // SQL Server accepts offsets of whole minutes between -14:00 and +14:00.
+    let fixed = Some(seconds)
+        .filter(|s| whole_minutes && s.abs() <= 14 * 3600)
+        .and_then(|s| FixedOffset::east_opt(s as i32))
+        .ok_or_else(|| {
+            PyValueError::new_err(format!(
+                "DATETIMEOFFSET parameter {} has UTC offset {}; it must be whole minutes \
+                 between -14:00 and +14:00",
+                dt, offset
+            ))
+        })?;
+    let aware = local
+        .and_local_timezone(fixed)
+        .single()
+        .expect("a fixed offset maps every local time to one instant");
+    Ok(FastParameter::DateTimeOffset(aware))
+}
+
+/// Convert a `decimal.Decimal` exactly, keeping its scale (`Decimal("1.50")` binds as
+/// NUMERIC(3, 2)).  Values that do not fit NUMERIC(38) or have more than 37 decimal
+/// places are rejected rather than rounded.
+fn decimal_to_numeric(obj: &Bound<PyAny>) -> PyResult<Numeric> {
+    let (sign, digits, exponent): (u8, Vec<u8>, Bound<PyAny>) =
+        obj.call_method0("as_tuple")?.extract()?;
+    let exponent = exponent.extract::<i64>().map_err(|_| {
+        PyValueError::new_err(format!("Decimal parameter must be finite, got {}", obj))
+    })?;
+    let out_of_range = || {
+        PyValueError::new_err(format!(
+            "Decimal parameter {} does not fit in NUMERIC(38)",
+            obj
+        ))
+    };
+
+    let mut value: i128 = 0;
+    for digit in digits {
+        value = value
+            .checked_mul(10)
+            .and_then(|v| v.checked_add(i128::from(digit)))
+            .ok_or_else(out_of_range)?;
+    }
+    let scale = if exponent < 0 {
+        u8::try_from(-exponent)
+            .ok()
+            .filter(|scale| *scale < 38)
+            .ok_or_else(|| {
+                PyValueError::new_err(format!(
+                    "Decimal parameter {} has more than 37 decimal places",
+                    obj
+                ))
+            })?
+    } else {
+        let factor = u32::try_from(exponent)
+            .ok()
+            .and_then(|e| 10i128.checked_pow(e))
+            .ok_or_else(out_of_range)?;
+        value = value.checked_mul(factor).ok_or_else(out_of_range)?;
+        0
+    };
+    if value >= 10i128.pow(38) {
+        return Err(out_of_range());
+    }
+    Ok(Numeric::new_with_scale(if sign == 1 { -value } else { value }, scale))
+}
+
+/// Convert a value wrapped in `Parameter(value, sql_type)`.
+///
+/// Only SQL types whose wire encoding differs from the default mapping are handled
+/// here; any other `sql_type` is informational and the value converts as usual.
+fn python_to_typed_fast_parameter(value: &Bound<PyAny>, sql_type: &str) -> PyResult<FastParameter> {
+    if value.is_none() {
+        return Ok(FastParameter::Null(
+            typed_null_for_sql_type(sql_type).unwrap_or(TypedNull::U8),
+        ));
+    }
+    if sql_type.eq_ignore_ascii_case("SMALLDATETIME") {
+        let dt = match value.extract::<NaiveDateTime>() {
+            Ok(dt) => dt,
+            Err(_) => value
+                .extract::<NaiveDate>()
+                .map_err(|_| {
+                    PyValueError::new_err("SMALLDATETIME parameter must be a datetime or date")
+                })?
+                .and_hms_opt(0, 0, 0)
+                .expect("midnight is a valid time"),
+        };
+        return to_smalldatetime(dt).map(FastParameter::SmallDateTime);
+    }
+    if sql_type.eq_ignore_ascii_case("XML") {
+        if let Ok(text) = value.cast::<PyString>() {
+            return Ok(FastParameter::Xml(text.to_str()?.to_owned()));
+        }
+        return match python_to_fast_parameter(value)? {
+            xml @ FastParameter::Xml(_) => Ok(xml),
+            _ => Err(PyValueError::new_err(
+                "XML parameter must be a str or an xml.etree.ElementTree.Element",
+            )),
+        };
+    }
+    python_to_fast_parameter(value)
+}
+
+/// `None` wrapped in a typed `Parameter` becomes a NULL of that type where one exists.
+/// Length, precision and scale (`VARBINARY(MAX)`, `DECIMAL(18, 2)`) are ignored, since
+/// a NULL carries none.
+pub fn typed_null_for_sql_type(sql_type: &str) -> Option<TypedNull> {
+    let name = sql_type.split('(').next().unwrap_or_default().trim();
+    let null = match name.to_ascii_uppercase().as_str() {
+        "TINYINT" => TypedNull::U8,
+        "SMALLINT" => TypedNull::I16,
+        "INT" | "INTEGER" => TypedNull::I32,
+        "BIGINT" => TypedNull::I64,
+        "REAL" => TypedNull::F32,
+        "FLOAT" => TypedNull::F64,
+        "BIT" => TypedNull::Bit,
+        "CHAR" | "VARCHAR" | "NCHAR" | "NVARCHAR" | "TEXT" | "NTEXT" | "SYSNAME" => {
+            TypedNull::String
+        }
+        "UNIQUEIDENTIFIER" => TypedNull::Guid,
+        "BINARY" | "VARBINARY" | "IMAGE" => TypedNull::Binary,
+        "DECIMAL" | "NUMERIC" | "MONEY" | "SMALLMONEY" => TypedNull::Numeric,
+        "XML" => TypedNull::Xml,
+        "DATETIME" => TypedNull::DateTime,
+        "SMALLDATETIME" => TypedNull::SmallDateTime,
+        "TIME" => TypedNull::Time,
+        "DATE" => TypedNull::Date,
+        "DATETIME2" => TypedNull::DateTime2,
+        "DATETIMEOFFSET" => TypedNull::DateTimeOffset,
+        _ => return None,
+    };
+    Some(null)
+}
+
+/// Serialize the values of `Parameter.json_list()` to a JSON array.  Each value is
+/// converted as a parameter would be; numbers stay JSON numbers and everything else
+/// becomes the string SQL Server converts back when comparing (ISO 8601 for dates).
+pub fn json_list_text(values: &Bound<PyAny>) -> PyResult<String> {
+    if !type_mapping::is_expandable_iterable(values)? {
+        return Err(PyValueError::new_err(format!(
+            "json_list() takes an iterable of values, got {}",
+            values.get_type().name()?
+        )));
+    }
+    let mut items = Vec::new();
+    for value in values.try_iter()? {
+        let value = value?;
+        // `bool` subclasses `int`, which binds first as a parameter.
+        if let Ok(flag) = value.cast::<PyBool>() {
+            items.push(flag.is_true().into());
+            continue;
+        }
+        let item = match python_to_fast_parameter(&value)? {
+            FastParameter::Null(_) => serde_json::Value::Null,
+            FastParameter::Bool(b) => b.into(),
+            FastParameter::I64(i) => i.into(),
+            FastParameter::F64(f) => serde_json::Number::from_f64(f)
+                .map(serde_json::Value::Number)
+                .ok_or_else(|| {
+                    PyValueError::new_err(format!("json_list() value {} is not finite", f))
+                })?,
+            FastParameter::Decimal(n) => type_mapping::numeric_to_decimal_string(n).into(),
+            FastParameter::Guid(g) => g.to_string().into(),
+            FastParameter::String(s) => s.into(),
+            FastParameter::Date(d) => d.to_string().into(),
+            FastParameter::DateTime(dt) | FastParameter::SmallDateTime(dt) => {
+                dt.format("%Y-%m-%dT%H:%M:%S%.f").to_string().into()
+            }
+            FastParameter::Time(t) => t.to_string().into(),
+            FastParameter::DateTimeOffset(dt) => dt.to_rfc3339().into(),
+            FastParameter::Bytes(_)
+            | FastParameter::Buffer(_)
+            | FastParameter::JsonList(_)
+            | FastParameter::Xml(_) => {
+                return Err(PyValueError::new_err(
+                    "json_list() values must be numbers, strings, dates, UUIDs or None",
+                ));
+            }
+        };
+        items.push(item);
+    }
+    Ok(serde_json::Value::Array(items).to_string())
+}
+
+/// Rewrite `IN (@Pn)` to `IN (SELECT value FROM OPENJSON(@Pn))` for every placeholder
+/// bound to a `Parameter.json_list()`.  Statements without one are returned as is.
+pub fn rewrite_json_lists(sql: String, params: &[FastParameter]) -> String {
+    let lists: SmallVec<[usize; 4]> = params
+        .iter()
+        .enumerate()
+        .filter(|(_, p)| matches!(p, FastParameter::JsonList(_)))
+        .map(|(i, _)| i + 1)
+        .collect();
+    if lists.is_empty() {
+        return sql;
+    }
+    sql_placeholders::rewrite_json_in_lists(&sql, &lists)
+}
+
+#[inline]
+pub fn smalldatetime_epoch() -> NaiveDate {
+    NaiveDate::from_ymd_opt(1900, 1, 1).expect("valid date")
+}
+
+/// Round to SMALLDATETIME's one-minute precision the way SQL Server does
+/// (29.998 s and below round down, 29.999 s and above round up) and check the
+/// 1900-01-01 .. 2079-06-06 23:59 range.
+pub fn to_smalldatetime(dt: NaiveDateTime) -> PyResult<NaiveDateTime> {
+    let millis_into_minute = dt.second() * 1000 + dt.nanosecond() / 1_000_000;
+    let truncated = dt
+        .with_second(0)
+        .and_then(|d| d.with_nanosecond(0))
+        .expect("zero seconds is always valid");
+    let rounded = if millis_into_minute >= 29_999 {
+        truncated + chrono::Duration::minutes(1)
+    } else {
+        truncated
+    };
+
+    let days = (rounded.date() - smalldatetime_epoch()).num_days();
+    // Day 65535 after the epoch is 2079-06-06, the last SMALLDATETIME date.
+    if !(0..=u16::MAX as i64).contains(&days) {
+        return Err(PyValueError::new_err(format!(
+            "SMALLDATETIME value {} is outside the supported range 1900-01-01 to 2079-06-06 23:59",
+            dt
+        )));
+    }
+    Ok(rounded)
+}
+
 /// Convert a `&[FastParameter]` into a `SmallVec` of `&dyn tiberius::ToSql` fat-pointer
 /// references for passing directly to tiberius `query`/`execute` methods.
 ///
@@ -104,8 +503,8 @@ pub fn convert_parameters_to_fast(
 ) -> PyResult<SmallVec<[FastParameter; 16]>> {
     if let Some(params) = parameters {
         if let Ok(params_obj) = params.extract::<Py<Parameters>>() {
-            let list = params_obj.bind(py).call_method0("to_list")?;
-            python_params_to_fast_parameters(list.cast::<PyList>()?)
+            let list = params_obj.borrow(py).to_typed_list(py)?;
+            python_params_to_fast_parameters(list.bind(py))
         } else if let Ok(list) = params.cast::<PyList>() {
             python_params_to_fast_parameters(list)
         } else {
@@ -0,0 +1,256 @@
+//! `Connection.acquire()`: one pooled connection held for a sequence of statements.
+//!
+//! Each `Connection` call checks a connection out of the pool and returns it, so
+//! session state such as temp tables or SET options does not carry from one call to
+//! the next.  A `PinnedConnection` checks one connection out on `__aenter__` and runs
+//! every statement on it until `__aexit__` returns it to the pool.  An open
+//! transaction left behind is rolled back first; if the block raised, or that
+//! rollback fails, the connection is discarded instead, since its state is unknown.
+
+use pyo3::prelude::*;
+use pyo3::types::PyList;
+use pyo3_async_runtimes::tokio::future_into_py;
+use std::sync::Arc;
+use tokio::sync::Mutex as AsyncMutex;
+
+use crate::batch::BulkRequest;
+use crate::connection::{ConnectionHandles, bind_parameters};
+use crate::execution_id::tag_error;
+use crate::helpers::{into_first_result_with_metadata, wrap_traced_query_stream};
+use crate::parameter_conversion::params_as_sql_refs;
+use crate::pool_manager::AzureConnectionManager;
+use crate::query_log::LoggedParams;
+use crate::sql_placeholders::ParamStyle;
+use crate::types::{InterfaceError, create_sql_error};
+
+type PinnedSession = bb8::PooledConnection<'static, AzureConnectionManager>;
+
+const ROLLBACK_OPEN_TRANSACTION: &str = "IF @@TRANCOUNT > 0 ROLLBACK TRANSACTION";
+
+#[pyclass(name = "PinnedConnection")]
+pub struct PyPinnedConnection {
+    handles: Arc<ConnectionHandles>,
+    paramstyle: ParamStyle,
+    session: Arc<AsyncMutex<Option<PinnedSession>>>,
+}
+
+impl PyPinnedConnection {
+    pub(crate) fn new(handles: ConnectionHandles, paramstyle: ParamStyle) -> Self {
+        PyPinnedConnection {
+            handles: Arc::new(handles),
+            paramstyle,
+            session: Arc::new(AsyncMutex::new(None)),
+        }
+    }
+}
+
+fn not_acquired() -> PyErr {
+    InterfaceError::new_err(
+        "PinnedConnection is not acquired; use `async with conn.acquire() as pinned:`",
+    )
+}
+
+/// Return the pinned connection to the pool, rolling back a transaction left open.
+/// `discard` (the block raised) or a failed rollback drops it from the pool instead.
+async fn release(session: &AsyncMutex<Option<PinnedSession>>, discard: bool) {
+    let Some(mut conn) = session.lock().await.take() else {
+        return;
+    };
+    if discard {
+        conn.mark_broken();
+        return;
+    }
+    let rolled_back = conn
+        .request(
+            async |client| match client.simple_query(ROLLBACK_OPEN_TRANSACTION).await {
+                Ok(stream) => stream.into_results().await.is_ok(),
+                Err(_) => false,
+            },
+        )
+        .await;
+    if !rolled_back {
+        conn.mark_broken();
+    }
+}
+
+#[pymethods]
+impl PyPinnedConnection {
+    pub fn __aenter__<'p>(slf: Bound<'p, Self>, py: Python<'p>) -> PyResult<Bound<'p, PyAny>> {
+        let (handles, session) = {
+            let this = slf.borrow();
+            (Arc::clone(&this.handles), Arc::clone(&this.session))
+        };
+        let slf_clone = slf.clone().unbind();
+
+        future_into_py(py, async move {
+            let mut guard = session.lock().await;
+            if guard.is_some() {
+                return Err(InterfaceError::new_err(
+                    "PinnedConnection is already acquired",
+                ));
+            }
+            let pool = handles.ensure_connected().await?;
+            *guard = Some(pool.checkout_owned().await?);
+            drop(guard);
+            Python::try_attach(|py| Ok(slf_clone.clone_ref(py))).ok_or_else(|| {
+                pyo3::exceptions::PyRuntimeError::new_err("Failed to attach Python runtime thread")
+            })?
+        })
+    }
+
+    pub fn __aexit__<'p>(
+        &self,
+        py: Python<'p>,
+        exc_type: Option<Bound<PyAny>>,
+        _exc_value: Option<Bound<PyAny>>,
+        _traceback: Option<Bound<PyAny>>,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        let session = Arc::clone(&self.session);
+        let discard = exc_type.is_some_and(|t| !t.is_none());
+        future_into_py(py, async move {
+            release(&session, discard).await;
+            Ok(false)
+        })
+    }
+
+    /// Return the connection to the pool without leaving an `async with` block.
+    pub fn release<'p>(&self, py: Python<'p>) -> PyResult<Bound<'p, PyAny>> {
+        let session = Arc::clone(&self.session);
+        future_into_py(py, async move {
+            release(&session, false).await;
+            Ok(())
+        })
+    }
+
+    /// Whether a pooled connection is currently held.
+    pub fn is_acquired(&self) -> bool {
+        match self.session.try_lock() {
+            Ok(guard) => guard.is_some(),
+            Err(_) => true,
+        }
+    }
+
+    /// Run a query on the pinned connection and return its first result set.
+    #[pyo3(signature = (query, parameters=None))]
+    pub fn query<'p>(
+        &self,
+        py: Python<'p>,
+        query: String,
+        parameters: Option<&Bound<PyAny>>,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        self.handles.check_statement(py, &query)?;
+        let (query, fast_parameters) = bind_parameters(py, self.paramstyle, query, parameters)?;
+        let handles = Arc::clone(&self.handles);
+        let session = Arc::clone(&self.session);
+        let (execution_id, query) =
+            handles.trace(py, query, LoggedParams::Positional(&fast_parameters));
+
+        future_into_py(py, async move {
+            let mut guard = session.lock().await;
+            let conn = guard.as_mut().ok_or_else(not_acquired)?;
+            let tiberius_params = params_as_sql_refs(&fast_parameters);
+            let (result, messages) = handles
+                .with_messages(conn.request(async |client| {
+                    let stream = client
+                        .query(query.as_str(), &tiberius_params)
+                        .await
+                        .map_err(|e| create_sql_error(e, "Query execution failed"))?;
+                    into_first_result_with_metadata(stream, handles.decode_options.limits).await
+                }))
+                .await;
+            drop(guard);
+            let result = result.map_err(|e| tag_error(e, &execution_id))?;
+            wrap_traced_query_stream(result, handles.decode_options, execution_id, messages)
+        })
+    }
+
+    /// Run a statement on the pinned connection and return the number of rows affected.
+    #[pyo3(signature = (query, parameters=None))]
+    pub fn execute<'p>(
+        &self,
+        py: Python<'p>,
+        query: String,
+        parameters: Option<&Bound<PyAny>>,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        self.handles.check_statement(py, &query)?;
+        let (query, fast_parameters) = bind_parameters(py, self.paramstyle, query, parameters)?;
+        let handles = Arc::clone(&self.handles);
+        let session = Arc::clone(&self.session);
+        let (execution_id, query) =
+            handles.trace(py, query, LoggedParams::Positional(&fast_parameters));
+
+        future_into_py(py, async move {
+            let mut guard = session.lock().await;
+            let conn = guard.as_mut().ok_or_else(not_acquired)?;
+            let tiberius_params = params_as_sql_refs(&fast_parameters);
+            let (result, _) = handles
+                .with_messages(conn.request(async |client| {
+                    client
+                        .execute(query.as_str(), &tiberius_params)
+                        .await
+                        .map_err(|e| create_sql_error(e, "Command execution failed"))
+                }))
+                .await;
+            drop(guard);
+            Ok(result.map_err(|e| tag_error(e, &execution_id))?.total())
+        })
+    }
+
+    /// Run a raw (unparameterised) batch on the pinned connection and return its
+    /// first result set.
+    #[pyo3(signature = (query))]
+    pub fn simple_query<'p>(&self, py: Python<'p>, query: String) -> PyResult<Bound<'p, PyAny>> {
+        let handles = Arc::clone(&self.handles);
+        let session = Arc::clone(&self.session);
+        handles.check_statement(py, &query)?;
+        let (execution_id, query) = handles.trace(py, query, LoggedParams::None);
+
+        future_into_py(py, async move {
+            let mut guard = session.lock().await;
+            let conn = guard.as_mut().ok_or_else(not_acquired)?;
+            let (result, messages) = handles
+                .with_messages(conn.request(async |client| {
+                    let stream = client
+                        .simple_query(query.as_str())
+                        .await
+                        .map_err(|e| create_sql_error(e, "Query execution failed"))?;
+                    into_first_result_with_metadata(stream, handles.decode_options.limits).await
+                }))
+                .await;
+            drop(guard);
+            let result = result.map_err(|e| tag_error(e, &execution_id))?;
+            wrap_traced_query_stream(result, handles.decode_options, execution_id, messages)
+        })
+    }
+
+    /// Insert rows into `table_name` on the pinned connection, as
+    /// `Connection.bulk_insert()` does.  The table may be a #temp table created
+    /// earlier in the block, so rows can be staged and merged into their target in
+    /// the same transaction.
+    #[pyo3(signature = (table_name, columns, data_rows, *, bulk_copy=false, verify_key=None))]
+    pub fn bulk_insert<'p>(
+        &self,
+        py: Python<'p>,
+        table_name: String,
+        columns: Vec<String>,
+        data_rows: &Bound<'p, PyList>,
+        bulk_copy: bool,
+        verify_key: Option<String>,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        let request = BulkRequest::prepare(table_name, columns, data_rows, bulk_copy, verify_key)?;
+        let session = Arc::clone(&self.session);
+
+        future_into_py(py, async move {
+            let mut guard = session.lock().await;
+            let conn = guard.as_mut().ok_or_else(not_acquired)?;
+            let mut interrupted = false;
+            let result = conn
+                .request(async |client| request.run(client, &mut interrupted).await)
+                .await;
+            if interrupted {
+                conn.mark_broken();
+            }
+            result
+        })
+    }
+}
@@ -12,12 +12,18 @@ pub struct PyPoolConfig {
     pub connection_timeout: Option<std::time::Duration>,
     pub test_on_check_out: Option<bool>,
     pub retry_connection: Option<bool>,
+    pub health_check_interval: Option<std::time::Duration>,
+    pub login_timeout: Option<std::time::Duration>,
+    pub max_concurrent_logins: Option<u32>,
+    pub login_interval: Option<std::time::Duration>,
+    pub login_jitter: Option<std::time::Duration>,
 }
 
 #[pymethods]
 impl PyPoolConfig {
     #[new]
-    #[pyo3(signature = (max_size = 20, min_idle = Some(2), max_lifetime_secs = None, idle_timeout_secs = None, connection_timeout_secs = Some(30), test_on_check_out = None, retry_connection = None))]
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (max_size = 20, min_idle = Some(2), max_lifetime_secs = None, idle_timeout_secs = None, connection_timeout_secs = Some(30), test_on_check_out = None, retry_connection = None, health_check_interval_secs = None, login_timeout_secs = None, max_concurrent_logins = None, login_interval_secs = None, login_jitter_secs = None))]
     pub fn new(
         max_size: u32,
         min_idle: Option<u32>,
@@ -26,6 +32,11 @@ impl PyPoolConfig {
         connection_timeout_secs: Option<u64>,
         test_on_check_out: Option<bool>,
         retry_connection: Option<bool>,
+        health_check_interval_secs: Option<u64>,
+        login_timeout_secs: Option<u64>,
+        max_concurrent_logins: Option<u32>,
+        login_interval_secs: Option<f64>,
+        login_jitter_secs: Option<f64>,
     ) -> PyResult<Self> {
         // Validate max_size >= 1
         if max_size < 1 {
@@ -33,36 +44,61 @@ impl PyPoolConfig {
         }
 
         // Validate min_idle <= max_size
-        if let Some(min) = min_idle {
-            if min > max_size {
-                return Err(PyValueError::new_err(
-                    format!("min_idle ({}) cannot be greater than max_size ({})", min, max_size),
-                ));
-            }
+        if let Some(min) = min_idle
+            && min > max_size
+        {
+            return Err(PyValueError::new_err(
+                format!("min_idle ({}) cannot be greater than max_size ({})", min, max_size),
+            ));
         }
 
         // Validate duration values are not negative (zero is allowed)
         // max_lifetime_secs
-        if let Some(lt_secs) = max_lifetime_secs {
-            if lt_secs == 0 {
-                return Err(PyValueError::new_err("max_lifetime_secs must be > 0 if specified"));
-            }
+        if let Some(lt_secs) = max_lifetime_secs
+            && lt_secs == 0
+        {
+            return Err(PyValueError::new_err("max_lifetime_secs must be > 0 if specified"));
         }
 
         // idle_timeout_secs
-        if let Some(it_secs) = idle_timeout_secs {
-            if it_secs == 0 {
-                return Err(PyValueError::new_err("idle_timeout_secs must be > 0 if specified"));
-            }
+        if let Some(it_secs) = idle_timeout_secs
+            && it_secs == 0
+        {
+            return Err(PyValueError::new_err("idle_timeout_secs must be > 0 if specified"));
         }
 
         // connection_timeout_secs >= 1 second
-        if let Some(ct_secs) = connection_timeout_secs {
-            if ct_secs < 1 {
-                return Err(PyValueError::new_err("connection_timeout_secs must be >= 1"));
-            }
+        if let Some(ct_secs) = connection_timeout_secs
+            && ct_secs < 1
+        {
+            return Err(PyValueError::new_err("connection_timeout_secs must be >= 1"));
         }
 
+        // health_check_interval_secs >= 1 second
+        if let Some(hc_secs) = health_check_interval_secs
+            && hc_secs < 1
+        {
+            return Err(PyValueError::new_err("health_check_interval_secs must be >= 1"));
+        }
+
+        // login_timeout_secs >= 1 second
+        if let Some(lt_secs) = login_timeout_secs
+            && lt_secs < 1
+        {
+            return Err(PyValueError::new_err("login_timeout_secs must be >= 1"));
+        }
+
+        // max_concurrent_logins >= 1
+        if let Some(limit) = max_concurrent_logins
+            && limit < 1
+        {
+            return Err(PyValueError::new_err("max_concurrent_logins must be >= 1"));
+        }
+
+        // login_interval_secs / login_jitter_secs >= 0 seconds
+        let login_interval = seconds_to_duration("login_interval_secs", login_interval_secs)?;
+        let login_jitter = seconds_to_duration("login_jitter_secs", login_jitter_secs)?;
+
         Ok(PyPoolConfig {
             max_size,
             min_idle,
@@ -71,6 +107,11 @@ impl PyPoolConfig {
             connection_timeout: connection_timeout_secs.map(std::time::Duration::from_secs),
             test_on_check_out,
             retry_connection,
+            health_check_interval: health_check_interval_secs.map(std::time::Duration::from_secs),
+            login_timeout: login_timeout_secs.map(std::time::Duration::from_secs),
+            max_concurrent_logins,
+            login_interval,
+            login_jitter,
         })
     }
 
@@ -86,12 +127,12 @@ impl PyPoolConfig {
         if value == 0 {
             return Err(PyValueError::new_err("max_size must be greater than 0"));
         }
-        if let Some(min) = self.min_idle {
-            if min > value {
-                return Err(PyValueError::new_err(
-                    "max_size cannot be less than min_idle",
-                ));
-            }
+        if let Some(min) = self.min_idle
+            && min > value
+        {
+            return Err(PyValueError::new_err(
+                "max_size cannot be less than min_idle",
+            ));
         }
         self.max_size = value;
         Ok(())
@@ -106,12 +147,12 @@ impl PyPoolConfig {
     /// Set the minimum number of idle connections
     #[setter]
     pub fn set_min_idle(&mut self, value: Option<u32>) -> PyResult<()> {
-        if let Some(min) = value {
-            if min > self.max_size {
-                return Err(PyValueError::new_err(
-                    "min_idle cannot be greater than max_size",
-                ));
-            }
+        if let Some(min) = value
+            && min > self.max_size
+        {
+            return Err(PyValueError::new_err(
+                "min_idle cannot be greater than max_size",
+            ));
         }
         self.min_idle = value;
         Ok(())
@@ -126,10 +167,10 @@ impl PyPoolConfig {
     /// Set the maximum lifetime of connections in seconds
     #[setter]
     pub fn set_max_lifetime_secs(&mut self, value: Option<u64>) -> PyResult<()> {
-        if let Some(secs) = value {
-            if secs == 0 {
-                return Err(PyValueError::new_err("max_lifetime_secs must be > 0 if specified"));
-            }
+        if let Some(secs) = value
+            && secs == 0
+        {
+            return Err(PyValueError::new_err("max_lifetime_secs must be > 0 if specified"));
         }
         self.max_lifetime = value.map(std::time::Duration::from_secs);
         Ok(())
@@ -144,10 +185,10 @@ impl PyPoolConfig {
     /// Set the idle timeout in seconds
     #[setter]
     pub fn set_idle_timeout_secs(&mut self, value: Option<u64>) -> PyResult<()> {
-        if let Some(secs) = value {
-            if secs == 0 {
-                return Err(PyValueError::new_err("idle_timeout_secs must be > 0 if specified"));
-            }
+        if let Some(secs) = value
+            && secs == 0
+        {
+            return Err(PyValueError::new_err("idle_timeout_secs must be > 0 if specified"));
         }
         self.idle_timeout = value.map(std::time::Duration::from_secs);
         Ok(())
@@ -162,10 +203,10 @@ impl PyPoolConfig {
     /// Set the connection timeout in seconds
     #[setter]
     pub fn set_connection_timeout_secs(&mut self, value: Option<u64>) -> PyResult<()> {
-        if let Some(secs) = value {
-            if secs < 1 {
-                return Err(PyValueError::new_err("connection_timeout_secs must be >= 1"));
-            }
+        if let Some(secs) = value
+            && secs < 1
+        {
+            return Err(PyValueError::new_err("connection_timeout_secs must be >= 1"));
         }
         self.connection_timeout = value.map(std::time::Duration::from_secs);
         Ok(())
@@ -183,6 +224,86 @@ impl PyPoolConfig {
         self.retry_connection
     }
 
+    /// Get the idle connection maintenance interval in seconds
+    #[getter]
+    pub fn health_check_interval_secs(&self) -> Option<u64> {
+        self.health_check_interval.map(|d| d.as_secs())
+    }
+
+    /// Set the idle connection maintenance interval in seconds
+    #[setter]
+    pub fn set_health_check_interval_secs(&mut self, value: Option<u64>) -> PyResult<()> {
+        if let Some(secs) = value
+            && secs < 1
+        {
+            return Err(PyValueError::new_err("health_check_interval_secs must be >= 1"));
+        }
+        self.health_check_interval = value.map(std::time::Duration::from_secs);
+        Ok(())
+    }
+
+    /// Get the limit on opening one physical connection (DNS, TCP, TLS and login) in seconds
+    #[getter]
+    pub fn login_timeout_secs(&self) -> Option<u64> {
+        self.login_timeout.map(|d| d.as_secs())
+    }
+
+    /// Set the limit on opening one physical connection in seconds
+    #[setter]
+    pub fn set_login_timeout_secs(&mut self, value: Option<u64>) -> PyResult<()> {
+        if let Some(secs) = value
+            && secs < 1
+        {
+            return Err(PyValueError::new_err("login_timeout_secs must be >= 1"));
+        }
+        self.login_timeout = value.map(std::time::Duration::from_secs);
+        Ok(())
+    }
+
+    /// Get the limit on connections being opened at the same time
+    #[getter]
+    pub fn max_concurrent_logins(&self) -> Option<u32> {
+        self.max_concurrent_logins
+    }
+
+    /// Set the limit on connections being opened at the same time
+    #[setter]
+    pub fn set_max_concurrent_logins(&mut self, value: Option<u32>) -> PyResult<()> {
+        if let Some(limit) = value
+            && limit < 1
+        {
+            return Err(PyValueError::new_err("max_concurrent_logins must be >= 1"));
+        }
+        self.max_concurrent_logins = value;
+        Ok(())
+    }
+
+    /// Get the minimum spacing between the starts of new logins in seconds
+    #[getter]
+    pub fn login_interval_secs(&self) -> Option<f64> {
+        self.login_interval.map(|d| d.as_secs_f64())
+    }
+
+    /// Set the minimum spacing between the starts of new logins in seconds
+    #[setter]
+    pub fn set_login_interval_secs(&mut self, value: Option<f64>) -> PyResult<()> {
+        self.login_interval = seconds_to_duration("login_interval_secs", value)?;
+        Ok(())
+    }
+
+    /// Get the random delay added to the login spacing in seconds
+    #[getter]
+    pub fn login_jitter_secs(&self) -> Option<f64> {
+        self.login_jitter.map(|d| d.as_secs_f64())
+    }
+
+    /// Set the random delay added to the login spacing in seconds
+    #[setter]
+    pub fn set_login_jitter_secs(&mut self, value: Option<f64>) -> PyResult<()> {
+        self.login_jitter = seconds_to_duration("login_jitter_secs", value)?;
+        Ok(())
+    }
+
     /// Create a default configuration for high-throughput scenarios
     /// Optimized for 15-25 concurrent workers without pool contention
     #[staticmethod]
@@ -195,6 +316,11 @@ impl PyPoolConfig {
             connection_timeout: Some(std::time::Duration::from_secs(30)),
             test_on_check_out: None,
             retry_connection: None,
+            health_check_interval: None,
+            login_timeout: None,
+            max_concurrent_logins: None,
+            login_interval: None,
+            login_jitter: None,
         }
     }
 
@@ -209,6 +335,11 @@ impl PyPoolConfig {
             connection_timeout: Some(std::time::Duration::from_secs(30)),
             test_on_check_out: None,
             retry_connection: None,
+            health_check_interval: None,
+            login_timeout: None,
+            max_concurrent_logins: None,
+            login_interval: None,
+            login_jitter: None,
         }
     }
 
@@ -223,6 +354,11 @@ impl PyPoolConfig {
             connection_timeout: Some(std::time::Duration::from_secs(15)),
             test_on_check_out: None,
             retry_connection: None,
+            health_check_interval: None,
+            login_timeout: None,
+            max_concurrent_logins: None,
+            login_interval: None,
+            login_jitter: None,
         }
     }
 
@@ -237,6 +373,11 @@ impl PyPoolConfig {
             connection_timeout: Some(std::time::Duration::from_secs(10)),
             test_on_check_out: None,
             retry_connection: None,
+            health_check_interval: None,
+            login_timeout: None,
+            max_concurrent_logins: None,
+            login_interval: None,
+            login_jitter: None,
         }
     }
 
@@ -252,6 +393,11 @@ impl PyPoolConfig {
             connection_timeout: Some(std::time::Duration::from_secs(10)),
             test_on_check_out: None,
             retry_connection: None,
+            health_check_interval: None,
+            login_timeout: None,
+            max_concurrent_logins: None,
+            login_interval: None,
+            login_jitter: None,
         }
     }
 
@@ -270,23 +416,45 @@ impl PyPoolConfig {
             connection_timeout: Some(std::time::Duration::from_secs(30)),
             test_on_check_out: None,
             retry_connection: None,
+            health_check_interval: None,
+            login_timeout: None,
+            max_concurrent_logins: None,
+            login_interval: None,
+            login_jitter: None,
         }
     }
 
     fn __repr__(&self) -> String {
         format!(
-            "PoolConfig(max_size={}, min_idle={:?}, max_lifetime_secs={:?}, idle_timeout_secs={:?}, connection_timeout_secs={:?}, test_on_check_out={:?}, retry_connection={:?})",
+            "PoolConfig(max_size={}, min_idle={:?}, max_lifetime_secs={:?}, idle_timeout_secs={:?}, connection_timeout_secs={:?}, test_on_check_out={:?}, retry_connection={:?}, health_check_interval_secs={:?}, login_timeout_secs={:?}, max_concurrent_logins={:?}, login_interval_secs={:?}, login_jitter_secs={:?})",
             self.max_size,
             self.min_idle,
             self.max_lifetime_secs(),
             self.idle_timeout_secs(),
             self.connection_timeout_secs(),
             self.test_on_check_out,
-            self.retry_connection
+            self.retry_connection,
+            self.health_check_interval_secs(),
+            self.login_timeout_secs(),
+            self.max_concurrent_logins,
+            self.login_interval_secs(),
+            self.login_jitter_secs()
         )
     }
 }
 
+/// Validate a non-negative number of seconds given for `name`.
+fn seconds_to_duration(
+    name: &str,
+    secs: Option<f64>,
+) -> PyResult<Option<std::time::Duration>> {
+    secs.map(|secs| {
+        std::time::Duration::try_from_secs_f64(secs)
+            .map_err(|_| PyValueError::new_err(format!("{} must be >= 0", name)))
+    })
+    .transpose()
+}
+
 impl Default for PyPoolConfig {
     fn default() -> Self {
         PyPoolConfig {
@@ -297,6 +465,11 @@ impl Default for PyPoolConfig {
             connection_timeout: Some(std::time::Duration::from_secs(30)),
             test_on_check_out: None,
             retry_connection: None,
+            health_check_interval: None,
+            login_timeout: None,
+            max_concurrent_logins: None,
+            login_interval: None,
+            login_jitter: None,
         }
     }
 }
@@ -1,19 +1,137 @@
+use crate::attention::Canceller;
 use crate::azure_auth::PyAzureCredential;
 use crate::pool_config::PyPoolConfig;
-use crate::types::{create_connection_error, create_sql_error};
-use bb8::Pool;
+use crate::retry_policy;
+use crate::tls_info::{HandshakeInfo, ObservedStream};
+use crate::types::{
+    PoolTimeoutError, create_auth_error, create_connection_error, create_sql_error,
+};
+use bb8::{Pool, PooledConnection};
+use chrono::{DateTime, Utc};
 use pyo3::prelude::*;
 use std::fmt;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
 use tiberius::Config;
-use tokio::sync::RwLock;
+use tokio::sync::{RwLock, Semaphore};
 use tokio_util::compat::TokioAsyncWriteCompatExt;
 
 // ──────────────────────────────────────────────────────────────────────────────
 // Custom connection manager
 // ──────────────────────────────────────────────────────────────────────────────
 
-type TiberiusClient = tiberius::Client<tokio_util::compat::Compat<tokio::net::TcpStream>>;
+/// Tries at opening a connection whose login keeps failing with a transient error.
+const LOGIN_RETRY_ATTEMPTS: u32 = 4;
+/// Delay before the first login retry; it doubles for each further retry.
+const LOGIN_RETRY_BACKOFF: Duration = Duration::from_secs(1);
+const LOGIN_RETRY_MAX_BACKOFF: Duration = Duration::from_secs(8);
+
+pub type TiberiusClient = tiberius::Client<tokio_util::compat::Compat<ObservedStream>>;
+
+/// How long [`PooledClient::recover`] waits for the server to stop an abandoned
+/// request and answer its probe.
+const RECOVERY_WAIT: Duration = Duration::from_secs(5);
+
+/// Values echoed by recovery probes, so each answer can be told apart from the
+/// leftovers of the abandoned request.
+static PROBE_NONCE: AtomicI64 = AtomicI64::new(1);
+
+/// A pooled `tiberius::Client` plus a flag the idle-maintenance task sets when a
+/// ping fails, so bb8 discards the connection instead of handing it out again, and
+/// one marking a request in flight (see [`PooledClient::request`]).
+pub struct PooledClient {
+    client: TiberiusClient,
+    broken: bool,
+    handshake: HandshakeInfo,
+    canceller: Canceller,
+    in_request: bool,
+}
+
+impl PooledClient {
+    /// What the server revealed while this connection was opened (TLS version, cipher
+    /// suite, certificate).
+    pub fn handshake(&self) -> &HandshakeInfo {
+        &self.handshake
+    }
+
+    /// Have bb8 discard the connection on return, e.g. after a request was abandoned
+    /// part-way through and the session state is unknown.
+    pub fn mark_broken(&mut self) {
+        self.broken = true;
+    }
+
+    /// Run `request` on the client.  If the future is dropped before the request
+    /// completes (the caller was cancelled or timed out), the connection is left
+    /// flagged, and on its return bb8 sends the server an attention signal (see
+    /// [`crate::attention`]) and discards it instead of reusing it mid-response.
+    pub async fn request<T>(&mut self, request: impl AsyncFnOnce(&mut TiberiusClient) -> T) -> T {
+        self.in_request = true;
+        let output = request(&mut self.client).await;
+        self.in_request = false;
+        output
+    }
+
+    /// After a request was abandoned in flight, stop it with an attention signal and
+    /// check that the session answers a probe in step.  Returns whether the connection
+    /// can go back into the pool; otherwise it is marked broken and bb8 discards it
+    /// (and opens a replacement in the background, up to `min_idle`).  A connection
+    /// that cannot carry an attention signal (see [`crate::attention`]) is always
+    /// discarded.
+    pub async fn recover(&mut self) -> bool {
+        if !self.in_request {
+            return !self.broken;
+        }
+        let healthy = self.canceller.send_attention()
+            && tokio::time::timeout(RECOVERY_WAIT, self.probe_in_step())
+                .await
+                .unwrap_or(false);
+        if healthy {
+            self.in_request = false;
+        } else {
+            self.broken = true;
+        }
+        healthy
+    }
+
+    /// Send probes echoing a fresh value until one comes back in its own answer.  The
+    /// first probe may read the end of the cancelled response instead, so a second one
+    /// gets the chance to confirm the stream is back in step.  Each probe also rolls
+    /// back a transaction the cancelled request left open, and the session only
+    /// counts as recovered once it reports none.
+    async fn probe_in_step(&mut self) -> bool {
+        for _ in 0..2 {
+            let nonce = PROBE_NONCE.fetch_add(1, Ordering::Relaxed);
+            let answer = match self
+                .client
+                .simple_query(format!(
+                    "IF @@TRANCOUNT > 0 ROLLBACK TRANSACTION; \
+                     SELECT CAST({nonce} AS BIGINT), @@TRANCOUNT"
+                ))
+                .await
+            {
+                Ok(stream) => stream.into_row().await,
+                Err(e) => Err(e),
+            };
+            match answer {
+                Ok(Some(row)) if row.get::<i64, _>(0) == Some(nonce) => {
+                    return row.get::<i32, _>(1) == Some(0);
+                }
+                Ok(_) => continue,
+                Err(_) => return false,
+            }
+        }
+        false
+    }
+}
+
+impl std::ops::Deref for PooledClient {
+    type Target = TiberiusClient;
+
+    fn deref(&self) -> &TiberiusClient {
+        &self.client
+    }
+}
 
 /// Error type for `AzureConnectionManager`.
 #[derive(Debug)]
@@ -21,6 +139,11 @@ pub enum PoolConnectionError {
     Io(std::io::Error),
     Tiberius(tiberius::error::Error),
     Auth(String),
+    /// `PoolConfig.login_timeout_secs` ran out while opening a connection.
+    LoginTimeout {
+        stage: &'static str,
+        after: Duration,
+    },
 }
 
 impl fmt::Display for PoolConnectionError {
@@ -29,6 +152,13 @@ impl fmt::Display for PoolConnectionError {
             PoolConnectionError::Io(e) => write!(f, "I/O error: {e}"),
             PoolConnectionError::Tiberius(e) => write!(f, "SQL error: {e}"),
             PoolConnectionError::Auth(e) => write!(f, "Auth error: {e}"),
+            PoolConnectionError::LoginTimeout { stage, after } => {
+                write!(
+                    f,
+                    "login timed out after {}s during {stage}",
+                    after.as_secs()
+                )
+            }
         }
     }
 }
@@ -47,6 +177,52 @@ impl From<tiberius::error::Error> for PoolConnectionError {
     }
 }
 
+impl PoolConnectionError {
+    /// Whether the server refused the login only for now (throttling, failover).
+    fn is_transient_login_error(&self) -> bool {
+        match self {
+            PoolConnectionError::Tiberius(tiberius::error::Error::Server(e)) => {
+                retry_policy::is_transient_login_error(e.code(), e.state())
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether the server refused the login itself (error 18456), which for an Azure
+    /// credential means it did not accept the access token.
+    fn is_login_refused(&self) -> bool {
+        matches!(
+            self,
+            PoolConnectionError::Tiberius(tiberius::error::Error::Server(e)) if e.code() == 18456
+        )
+    }
+
+    /// The server's refusal of the login or the failed TLS handshake, which opening
+    /// more connections will not get past.
+    fn refusal(&self) -> Option<tiberius::error::Error> {
+        match self {
+            PoolConnectionError::Tiberius(e @ tiberius::error::Error::Tls(_)) => Some(e.clone()),
+            PoolConnectionError::Tiberius(e @ tiberius::error::Error::Server(_))
+                if !self.is_transient_login_error() =>
+            {
+                Some(e.clone())
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Why the most recent attempt to open a connection failed.
+#[derive(Clone)]
+struct ConnectFailure {
+    /// Description for the `PoolTimeoutError` message.
+    description: String,
+    /// See [`PoolConnectionError::refusal`].
+    refusal: Option<tiberius::error::Error>,
+    /// When the attempt failed.
+    at: DateTime<Utc>,
+}
+
 /// Convert a [`PoolConnectionError`] into a typed Python exception,
 /// preserving the structured context of the underlying [`tiberius::error::Error`]
 /// (SQL error code/state, TLS details, routing info, etc.) rather than
@@ -57,7 +233,10 @@ impl From<PoolConnectionError> for pyo3::PyErr {
             PoolConnectionError::Tiberius(terr) => create_sql_error(terr, "Connection error"),
             PoolConnectionError::Io(err) => create_connection_error(format!("I/O error: {err}")),
             PoolConnectionError::Auth(msg) => {
-                create_connection_error(format!("Authentication error: {msg}"))
+                create_auth_error(format!("Authentication error: {msg}"))
+            }
+            timeout @ PoolConnectionError::LoginTimeout { .. } => {
+                create_connection_error(timeout.to_string())
             }
         }
     }
@@ -71,6 +250,8 @@ impl From<PoolConnectionError> for pyo3::PyErr {
 /// connection (on pool warm-up, `max_lifetime` rotation, idle-timeout eviction, or
 /// reconnect after error).  This fixes the bug where a static token baked into
 /// `bb8_tiberius::ConnectionManager`'s config would silently go stale after ~1 hour.
+/// A token the server refuses at login is dropped from the cache, so the next attempt
+/// fetches a new one rather than presenting it again until it expires locally.
 ///
 /// For SQL Server / Windows auth (`azure_credential = None`) the base config already
 /// carries the credentials and the manager behaves identically to `bb8_tiberius`.
@@ -80,52 +261,232 @@ pub struct AzureConnectionManager {
     base_config: Config,
     /// Azure credential, or `None` for non-Azure auth.
     azure_credential: Option<Arc<PyAzureCredential>>,
+    /// Limit on opening one connection, from `PoolConfig.login_timeout_secs`.
+    login_timeout: Option<Duration>,
+    /// Permits for connections being opened at once, from
+    /// `PoolConfig.max_concurrent_logins`, so a cold pool doesn't log in all at once.
+    login_slots: Option<Arc<Semaphore>>,
+    /// Spacing between logins, from `PoolConfig.login_interval_secs` and
+    /// `login_jitter_secs`.
+    login_pacing: Option<LoginPacing>,
+    /// Why the most recent `connect()` failed; cleared when one succeeds.  Shared
+    /// with the [`ConnectionPool`] so a checkout timeout can report it.
+    last_connect_error: Arc<Mutex<Option<ConnectFailure>>>,
+    /// The most recent failed `connect()`, kept after later ones succeed.
+    last_failure: Arc<Mutex<Option<ConnectFailure>>>,
 }
 
 impl AzureConnectionManager {
-    pub fn new(base_config: Config, azure_credential: Option<Arc<PyAzureCredential>>) -> Self {
+    pub fn new(
+        base_config: Config,
+        azure_credential: Option<Arc<PyAzureCredential>>,
+        login_timeout: Option<Duration>,
+        max_concurrent_logins: Option<u32>,
+        login_pacing: Option<LoginPacing>,
+    ) -> Self {
         Self {
             base_config,
             azure_credential,
+            login_timeout,
+            login_slots: max_concurrent_logins
+                .map(|limit| Arc::new(Semaphore::new(limit as usize))),
+            login_pacing,
+            last_connect_error: Arc::new(Mutex::new(None)),
+            last_failure: Arc::new(Mutex::new(None)),
         }
     }
-}
 
-impl bb8::ManageConnection for AzureConnectionManager {
-    type Connection = TiberiusClient;
-    type Error = PoolConnectionError;
+    /// Run one stage of `connect()` within the login deadline, if there is one.
+    async fn stage<T, E>(
+        &self,
+        deadline: Option<tokio::time::Instant>,
+        stage: &'static str,
+        work: impl Future<Output = Result<T, E>>,
+    ) -> Result<T, PoolConnectionError>
+    where
+        E: Into<PoolConnectionError>,
+    {
+        let result = match deadline {
+            Some(deadline) => tokio::time::timeout_at(deadline, work).await.map_err(|_| {
+                PoolConnectionError::LoginTimeout {
+                    stage,
+                    after: self.login_timeout.unwrap_or_default(),
+                }
+            })?,
+            None => work.await,
+        };
+        result.map_err(Into::into)
+    }
 
-    async fn connect(&self) -> Result<Self::Connection, Self::Error> {
+    async fn open(&self) -> Result<PooledClient, PoolConnectionError> {
+        let deadline = self
+            .login_timeout
+            .map(|limit| tokio::time::Instant::now() + limit);
         let mut config = self.base_config.clone();
 
         // Refresh (or serve from cache) the Azure access token for every new connection.
         // `to_auth_method()` is cheap when a valid cached token exists; it only hits the
         // network when the token has expired.
         if let Some(cred) = &self.azure_credential {
-            let auth_method = cred
-                .to_auth_method()
-                .await
-                .map_err(|e| PoolConnectionError::Auth(e.to_string()))?;
+            let auth_method = self
+                .stage(deadline, "Azure token acquisition", async {
+                    cred.to_auth_method()
+                        .await
+                        .map_err(|e| PoolConnectionError::Auth(e.to_string()))
+                })
+                .await?;
             config.authentication(auth_method);
         }
 
-        let tcp = tokio::net::TcpStream::connect(config.get_addr()).await?;
+        let tcp = self
+            .stage(
+                deadline,
+                "DNS lookup and TCP connect",
+                crate::tcp_connect::connect(&config.get_addr()),
+            )
+            .await?;
         tcp.set_nodelay(true)?;
 
-        let client = match tiberius::Client::connect(config.clone(), tcp.compat_write()).await {
+        let tcp = ObservedStream::new(tcp);
+        let mut handshake = tcp.handshake();
+        let mut canceller = tcp.canceller();
+        let login = tiberius::Client::connect(config.clone(), tcp.compat_write());
+        let client = match self.stage(deadline, "TLS handshake and login", login).await {
             Ok(c) => c,
             // Server redirect: reconnect to the forwarded address.
-            Err(tiberius::error::Error::Routing { host, port }) => {
+            Err(PoolConnectionError::Tiberius(tiberius::error::Error::Routing { host, port })) => {
                 config.host(&host);
                 config.port(port);
-                let tcp = tokio::net::TcpStream::connect(config.get_addr()).await?;
+                let tcp = self
+                    .stage(
+                        deadline,
+                        "TCP connect to redirected server",
+                        crate::tcp_connect::connect(&config.get_addr()),
+                    )
+                    .await?;
                 tcp.set_nodelay(true)?;
-                tiberius::Client::connect(config, tcp.compat_write()).await?
+                let tcp = ObservedStream::new(tcp);
+                handshake = tcp.handshake();
+                canceller = tcp.canceller();
+                let login = tiberius::Client::connect(config, tcp.compat_write());
+                self.stage(
+                    deadline,
+                    "TLS handshake and login to redirected server",
+                    login,
+                )
+                .await?
+            }
+            Err(e) => return Err(e),
+        };
+
+        let handshake = handshake.lock().map(|h| h.clone()).unwrap_or_default();
+        Ok(PooledClient {
+            client,
+            broken: false,
+            handshake,
+            canceller,
+            in_request: false,
+        })
+    }
+}
+
+/// Keeps the logins of one pool `interval` (plus up to `jitter`) apart.
+pub struct LoginPacing {
+    interval: Duration,
+    jitter: Duration,
+    /// Earliest start of the next login.
+    next: Mutex<Option<tokio::time::Instant>>,
+}
+
+impl LoginPacing {
+    pub fn new(interval: Option<Duration>, jitter: Option<Duration>) -> Option<Self> {
+        let (interval, jitter) = (interval.unwrap_or_default(), jitter.unwrap_or_default());
+        (!interval.is_zero() || !jitter.is_zero()).then(|| Self {
+            interval,
+            jitter,
+            next: Mutex::new(None),
+        })
+    }
+
+    /// Reserve the next start time and sleep until it; logins queue up in the order
+    /// they asked rather than waking together.
+    async fn wait_turn(&self) {
+        let now = tokio::time::Instant::now();
+        let start = match self.next.lock() {
+            Ok(mut next) => {
+                let start = next.map_or(now, |at| at.max(now));
+                *next = Some(start + self.interval + retry_policy::jitter(self.jitter));
+                start
             }
-            Err(e) => return Err(e.into()),
+            Err(_) => now,
         };
+        tokio::time::sleep_until(start).await;
+    }
+}
+
+impl bb8::ManageConnection for AzureConnectionManager {
+    type Connection = PooledClient;
+    type Error = PoolConnectionError;
 
-        Ok(client)
+    /// Open a connection, retrying logins that Azure SQL refused while throttling or
+    /// failing over.  Those are retried here with jittered backoff rather than left to
+    /// bb8, which retries every failure at the same fixed intervals in every pool.
+    async fn connect(&self) -> Result<Self::Connection, Self::Error> {
+        let mut attempt = 1;
+        loop {
+            // Held for one attempt only, so a backoff sleep doesn't hold up other
+            // logins.  The semaphore is never closed, so acquiring only waits.
+            let permit = match &self.login_slots {
+                Some(slots) => slots.acquire().await.ok(),
+                None => None,
+            };
+            if let Some(pacing) = &self.login_pacing {
+                pacing.wait_turn().await;
+            }
+            let result = self.open().await;
+            drop(permit);
+            if let (Err(e), Some(cred)) = (&result, &self.azure_credential)
+                && e.is_login_refused()
+            {
+                cred.discard_cached_token().await;
+            }
+            let failure = result.as_ref().err().map(|e| ConnectFailure {
+                description: {
+                    let mut description = match attempt {
+                        1 => e.to_string(),
+                        n => format!("{} (after {} attempts)", e, n),
+                    };
+                    if e.is_transient_login_error() {
+                        description.push_str(
+                            "; the server is refusing logins for now, e.g. throttling a \
+                             burst of them (PoolConfig.max_concurrent_logins and \
+                             login_interval_secs spread logins out)",
+                        );
+                    }
+                    description
+                },
+                refusal: e.refusal(),
+                at: Utc::now(),
+            });
+            if let (Some(failure), Ok(mut last)) = (&failure, self.last_failure.lock()) {
+                *last = Some(failure.clone());
+            }
+            if let Ok(mut last) = self.last_connect_error.lock() {
+                *last = failure;
+            }
+            match result {
+                Err(e) if attempt < LOGIN_RETRY_ATTEMPTS && e.is_transient_login_error() => {
+                    let delay = retry_policy::backoff(
+                        LOGIN_RETRY_BACKOFF,
+                        LOGIN_RETRY_MAX_BACKOFF,
+                        attempt,
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
     }
 
     async fn is_valid(&self, conn: &mut Self::Connection) -> Result<(), Self::Error> {
@@ -134,25 +495,124 @@ impl bb8::ManageConnection for AzureConnectionManager {
         // the connection is still alive — combined into a single round-trip.
         // This runs only when test_on_check_out = true or on periodic lifetime /
         // idle-timeout health checks — never on every routine checkout.
-        conn.simple_query("IF @@TRANCOUNT > 0 ROLLBACK TRANSACTION; SELECT 1")
+        conn.client
+            .simple_query("IF @@TRANCOUNT > 0 ROLLBACK TRANSACTION; SELECT 1")
             .await?;
         Ok(())
     }
 
-    /// Returns `false` unconditionally.
+    /// Returns `true` for connections the idle-maintenance task marked broken and for
+    /// those returned with a request still in flight, after sending that request an
+    /// attention signal where the connection allows it.
     ///
     /// bb8 calls this synchronously on every connection return.  `tiberius::Client`
     /// wraps an async TCP stream and exposes no synchronous liveness check, so
-    /// there is nothing meaningful to inspect here.  All real health-checking is
-    /// handled by [`is_valid`](AzureConnectionManager::is_valid), which runs a
-    /// real server round-trip on periodic lifetime / idle-timeout checks and,
-    /// optionally, on every checkout when `test_on_check_out = true`.
-    fn has_broken(&self, _conn: &mut Self::Connection) -> bool {
-        false
+    /// the flag set by a failed background ping is the only thing to inspect here.
+    /// Other health-checking is handled by [`is_valid`](AzureConnectionManager::is_valid),
+    /// which runs a real server round-trip on every checkout when
+    /// `test_on_check_out = true`.
+    fn has_broken(&self, conn: &mut Self::Connection) -> bool {
+        if conn.in_request {
+            // Abandoned mid-request: stop the statement on the server.
+            conn.canceller.send_attention();
+            return true;
+        }
+        conn.broken
+    }
+}
+
+/// The bb8 pool plus the reason its last connection attempt failed.
+///
+/// bb8 opens connections in the background and retries failures until
+/// `connection_timeout`, so a checkout that gives up only sees "timed out".  The
+/// recorded error lets callers say why (e.g. which login stage was slow).
+#[derive(Clone)]
+pub struct ConnectionPool {
+    pool: Pool<AzureConnectionManager>,
+    last_connect_error: Arc<Mutex<Option<ConnectFailure>>>,
+    last_failure: Arc<Mutex<Option<ConnectFailure>>>,
+    /// Tells this pool apart from one that replaces it in the same slot
+    /// (`reload_config()`), so the old pool's background tasks stop.
+    id: u64,
+}
+
+static NEXT_POOL_ID: AtomicU64 = AtomicU64::new(0);
+
+impl ConnectionPool {
+    fn last_connect_failure(&self) -> Option<ConnectFailure> {
+        self.last_connect_error.lock().ok().and_then(|e| e.clone())
+    }
+
+    /// Why the most recent attempt to open a connection failed, if it did.
+    pub fn last_connect_error(&self) -> Option<String> {
+        self.last_connect_failure().map(|e| e.description)
+    }
+
+    /// The latest failed attempt to open a connection and when it happened, even if
+    /// connections have opened since.
+    pub fn last_failure(&self) -> Option<(String, DateTime<Utc>)> {
+        self.last_failure
+            .lock()
+            .ok()
+            .and_then(|e| e.as_ref().map(|f| (f.description.clone(), f.at)))
+    }
+
+    /// Check out a connection, raising `PoolTimeoutError` when none became available
+    /// within `connection_timeout` and the typed connect error otherwise.  A timeout
+    /// spent retrying a refused login raises the refusal (e.g. `InvalidCredentialsError`)
+    /// instead, as waiting longer would not have helped.
+    pub async fn checkout(&self) -> PyResult<PooledConnection<'_, AzureConnectionManager>> {
+        self.pool.get().await.map_err(|e| self.checkout_error(e))
+    }
+
+    /// Like [`ConnectionPool::checkout`], for a connection held beyond the pool borrow
+    /// (`Connection.acquire()`).
+    pub async fn checkout_owned(
+        &self,
+    ) -> PyResult<PooledConnection<'static, AzureConnectionManager>> {
+        self.pool
+            .get_owned()
+            .await
+            .map_err(|e| self.checkout_error(e))
+    }
+
+    fn checkout_error(&self, e: bb8::RunError<PoolConnectionError>) -> PyErr {
+        match e {
+            bb8::RunError::TimedOut => pool_timeout_error(self.last_connect_failure()),
+            bb8::RunError::User(e) => e.into(),
+        }
+    }
+}
+
+/// The error for running out of `connection_timeout`: the refusal when the server kept
+/// refusing the login, else `PoolTimeoutError` with why the last connect failed.
+fn pool_timeout_error(failure: Option<ConnectFailure>) -> PyErr {
+    if let Some(refusal) = failure.as_ref().and_then(|f| f.refusal.clone()) {
+        return create_sql_error(refusal, "Connection error");
     }
+    let message = match failure.map(|f| f.description) {
+        Some(cause) => format!(
+            "Connection pool timeout - could not open a connection: {}",
+            cause
+        ),
+        None => "Connection pool timeout - all connections are busy. \
+                 Try reducing concurrent requests or increasing pool size."
+            .to_string(),
+    };
+    Python::attach(|py| {
+        let exc = PoolTimeoutError::new_err(message.clone());
+        let _ = exc.value(py).setattr("message", message.as_str());
+        exc
+    })
 }
 
-pub type ConnectionPool = Pool<AzureConnectionManager>;
+impl std::ops::Deref for ConnectionPool {
+    type Target = Pool<AzureConnectionManager>;
+
+    fn deref(&self) -> &Pool<AzureConnectionManager> {
+        &self.pool
+    }
+}
 
 // ──────────────────────────────────────────────────────────────────────────────
 // Pool helpers
@@ -163,7 +623,15 @@ pub async fn establish_pool(
     azure_credential: Option<Arc<PyAzureCredential>>,
     pool_config: &PyPoolConfig,
 ) -> PyResult<ConnectionPool> {
-    let manager = AzureConnectionManager::new(base_config.clone(), azure_credential);
+    let manager = AzureConnectionManager::new(
+        base_config.clone(),
+        azure_credential,
+        pool_config.login_timeout,
+        pool_config.max_concurrent_logins,
+        LoginPacing::new(pool_config.login_interval, pool_config.login_jitter),
+    );
+    let last_connect_error = Arc::clone(&manager.last_connect_error);
+    let last_failure = Arc::clone(&manager.last_failure);
     let mut builder = Pool::builder().max_size(pool_config.max_size);
 
     if let Some(min) = pool_config.min_idle {
@@ -184,8 +652,28 @@ pub async fn establish_pool(
     if let Some(retry) = pool_config.retry_connection {
         builder = builder.retry_connection(retry);
     }
+    if let Some(interval) = pool_config.health_check_interval {
+        // bb8's reaper retires connections past max_lifetime / idle_timeout; run it
+        // on the same cadence as the idle maintenance task.
+        builder = builder.reaper_rate(interval);
+    }
 
-    let pool = builder.build(manager).await.map_err(pyo3::PyErr::from)?;
+    let retrying = pool_config.retry_connection != Some(false);
+    let built = builder.build(manager).await.map_err(|e| {
+        if retrying {
+            // bb8 retried the min_idle connects until connection_timeout, so this is
+            // the timeout a checkout would have hit.
+            pool_timeout_error(last_connect_error.lock().ok().and_then(|f| f.clone()))
+        } else {
+            pyo3::PyErr::from(e)
+        }
+    })?;
+    let pool = ConnectionPool {
+        pool: built,
+        last_connect_error,
+        last_failure,
+        id: NEXT_POOL_ID.fetch_add(1, Ordering::Relaxed),
+    };
 
     // Warmup pool if min_idle is configured to eliminate cold-start latency.
     if let Some(min_idle) = pool_config.min_idle {
@@ -221,11 +709,187 @@ pub async fn ensure_pool_initialized_with_auth(
     // Pass the base config and credential to establish_pool.
     // AzureConnectionManager will call to_auth_method() on every new connection,
     // so tokens are always fresh regardless of when bb8 decides to open them.
-    let new_pool = establish_pool(&config, azure_credential, pool_config).await?;
+    let new_pool = establish_pool(&config, azure_credential.clone(), pool_config).await?;
     *write_guard = Some(new_pool.clone());
+    spawn_background_tasks(&pool, &new_pool, pool_config, azure_credential);
+
     Ok(new_pool)
 }
 
+/// Build a pool from new settings and swap it into `pool` (`reload_config()`).
+///
+/// The old pool leaves the slot at once, so every later checkout uses the new one.
+/// Connections already checked out of the old pool finish their work, and the old
+/// pool closes its connections once the last of them is returned.  If the new pool
+/// cannot be built (e.g. its warm-up fails), the old one stays in place.
+pub async fn replace_pool(
+    pool: Arc<RwLock<Option<ConnectionPool>>>,
+    config: Arc<Config>,
+    pool_config: &PyPoolConfig,
+    azure_credential: Option<Arc<PyAzureCredential>>,
+) -> PyResult<()> {
+    let new_pool = establish_pool(&config, azure_credential.clone(), pool_config).await?;
+    let old_pool = pool.write().await.replace(new_pool.clone());
+    spawn_background_tasks(&pool, &new_pool, pool_config, azure_credential);
+    drop(old_pool);
+    Ok(())
+}
+
+/// Start the token refresher and idle maintenance of `current`, the pool in `pool`.
+fn spawn_background_tasks(
+    pool: &Arc<RwLock<Option<ConnectionPool>>>,
+    current: &ConnectionPool,
+    pool_config: &PyPoolConfig,
+    azure_credential: Option<Arc<PyAzureCredential>>,
+) {
+    if let Some(credential) = azure_credential {
+        tokio::spawn(keep_token_warm(
+            Arc::downgrade(pool),
+            current.id,
+            credential,
+        ));
+    }
+    if let Some(interval) = pool_config.health_check_interval {
+        tokio::spawn(maintain_idle_connections(
+            Arc::downgrade(pool),
+            current.id,
+            pool_config.clone(),
+            interval,
+        ));
+    }
+}
+
+/// How long before the cached token's (already buffered) expiry the refresher
+/// fetches a new one.
+const TOKEN_REFRESH_LEAD: Duration = Duration::from_secs(300);
+
+/// Wait before retrying after a failed background refresh.
+const TOKEN_REFRESH_RETRY: Duration = Duration::from_secs(30);
+
+/// How often a sleeping refresher checks whether its pool is still alive, so a
+/// dropped `Connection` does not keep the credential in memory until the next refresh.
+const TOKEN_REFRESH_POLL: Duration = Duration::from_secs(60);
+
+/// Whether the slot still holds the pool with `id`.
+fn pool_is_alive(pool: &Weak<RwLock<Option<ConnectionPool>>>, id: u64) -> bool {
+    pool.upgrade().is_some_and(|slot| {
+        slot.try_read().map_or(true, |guard| {
+            guard.as_ref().is_some_and(|current| current.id == id)
+        })
+    })
+}
+
+/// Background task that keeps an Azure pool's token fresh.
+///
+/// Without it, the first connection bb8 opens after the token's lifetime (idle
+/// replenishment, `max_lifetime` rotation or a burst of checkouts) pays for the
+/// token round trip — for `DefaultAzure` an `az` subprocess — on top of the login
+/// itself.  Refreshing ahead of expiry keeps that work off the request path.
+///
+/// The task holds only a weak reference to the pool slot and exits once the owning
+/// `Connection` objects are gone, the pool has been replaced or closed, or the
+/// credential is a static access token.
+/// Failures are retried; the request path still falls back to fetching a token
+/// itself if the cache has expired.
+async fn keep_token_warm(
+    pool: Weak<RwLock<Option<ConnectionPool>>>,
+    id: u64,
+    credential: Arc<PyAzureCredential>,
+) {
+    while pool_is_alive(&pool, id) {
+        let wait = match credential.refresh_ahead_of_expiry(TOKEN_REFRESH_LEAD).await {
+            Ok(Some(wait)) => wait,
+            Ok(None) => return,
+            Err(_) => TOKEN_REFRESH_RETRY,
+        };
+
+        let deadline = tokio::time::Instant::now() + wait;
+        while tokio::time::Instant::now() < deadline {
+            tokio::time::sleep_until(
+                deadline.min(tokio::time::Instant::now() + TOKEN_REFRESH_POLL),
+            )
+            .await;
+            if !pool_is_alive(&pool, id) {
+                return;
+            }
+        }
+    }
+}
+
+/// Background task enabled by `PoolConfig.health_check_interval_secs`.
+///
+/// Every `interval` it checks out the pool's idle connections, pings each one and
+/// marks failures broken so bb8 discards them, then opens connections until
+/// `min_idle` are idle again.  Returning the checked-out connections also retires
+/// any past `max_lifetime`.  Dead sockets and expired connections are therefore
+/// dealt with during quiet periods instead of on the first checkout after one.
+///
+/// Like [`keep_token_warm`], the task exits once the pool slot is dropped or holds
+/// another pool.
+async fn maintain_idle_connections(
+    pool: Weak<RwLock<Option<ConnectionPool>>>,
+    id: u64,
+    pool_config: PyPoolConfig,
+    interval: Duration,
+) {
+    let ping_timeout = pool_config
+        .connection_timeout
+        .unwrap_or(Duration::from_secs(30));
+    let mut ticker = tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        ticker.tick().await;
+
+        let Some(slot) = pool.upgrade() else {
+            return;
+        };
+        let Some(current) = slot.read().await.clone().filter(|current| current.id == id) else {
+            return;
+        };
+        drop(slot);
+
+        run_idle_maintenance(&current, &pool_config, ping_timeout).await;
+    }
+}
+
+async fn run_idle_maintenance(
+    pool: &ConnectionPool,
+    pool_config: &PyPoolConfig,
+    ping_timeout: Duration,
+) {
+    // Hold every connection checked out during this pass so each `get()` yields a
+    // different one; they all go back to the pool when `held` is dropped.
+    let mut held = Vec::new();
+
+    let idle = pool.state().idle_connections;
+    for _ in 0..idle {
+        // Stop if the application has taken the remaining idle connections, rather
+        // than making `get()` open a new one or wait.
+        if pool.state().idle_connections == 0 {
+            break;
+        }
+        let Ok(Ok(mut conn)) = tokio::time::timeout(ping_timeout, pool.get()).await else {
+            break;
+        };
+        let ping = async { conn.client.simple_query("SELECT 1").await?.into_row().await };
+        if !matches!(tokio::time::timeout(ping_timeout, ping).await, Ok(Ok(_))) {
+            conn.broken = true;
+        }
+        held.push(conn);
+    }
+
+    let min_idle = pool_config.min_idle.unwrap_or(0);
+    let mut ready = held.iter().filter(|conn| !conn.broken).count() as u32;
+    while ready < min_idle && pool.state().connections < pool_config.max_size {
+        match tokio::time::timeout(ping_timeout, pool.get()).await {
+            Ok(Ok(conn)) => held.push(conn),
+            _ => break,
+        }
+        ready += 1;
+    }
+}
+
 /// Warms up the connection pool by pre-establishing `target_connections` connections.
 /// This eliminates cold-start latency on first queries.
 ///
@@ -265,8 +929,9 @@ pub async fn warmup_pool(
         match tokio::time::timeout_at(deadline, set.join_next()).await {
             // Task completed successfully.
             Ok(Some(Ok(Ok(())))) => {}
-            // Task returned a bb8/connection error – collect it and continue.
-            Ok(Some(Ok(Err(e)))) => errors.push(e.to_string()),
+            // Task returned a bb8/connection error – collect it (with why the connect
+            // failed, for a timeout) and continue.
+            Ok(Some(Ok(Err(e)))) => errors.push(pool.checkout_error(e).to_string()),
             // Task panicked or was cancelled – record the join error and continue.
             Ok(Some(Err(join_err))) => errors.push(format!("task panicked: {join_err}")),
             // All tasks finished.
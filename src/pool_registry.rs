@@ -0,0 +1,179 @@
+//! Process-wide registry of connection pools, for per-tenant statistics.
+//!
+//! Every `Connection` owns a pool (shared with its `with_options()` copies), so a
+//! multi-tenant application ends up with one pool per database or login.  Each pool
+//! is registered under a key of server, database and login when its `Connection` is
+//! created; `pool_stats(by_key=True)` sums the live pools under each key so a noisy
+//! tenant shows up as one line rather than being lost in a process-wide total.
+
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use std::sync::{Arc, Mutex, Weak};
+use tokio::sync::RwLock;
+
+use crate::azure_auth::PyAzureCredential;
+use crate::pool_manager::ConnectionPool;
+
+type PoolSlot = RwLock<Option<ConnectionPool>>;
+
+/// What a pool is connected as. `user` is the SQL login, or `azure:<credential
+/// type>[:<client id>]` for Azure AD credentials; None for integrated auth.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PoolKey {
+    pub server: String,
+    pub database: Option<String>,
+    pub user: Option<String>,
+}
+
+impl PoolKey {
+    pub fn new(
+        server: Option<&str>,
+        database: Option<&str>,
+        username: Option<&str>,
+        azure_credential: Option<&PyAzureCredential>,
+    ) -> Self {
+        let user = match azure_credential {
+            Some(credential) => {
+                let mut user = format!("azure:{:?}", credential.credential_type);
+                if let Some(client_id) = credential.config.get("client_id") {
+                    user.push(':');
+                    user.push_str(client_id);
+                }
+                Some(user)
+            }
+            None => username.map(str::to_string),
+        };
+        PoolKey {
+            server: server.unwrap_or_default().to_string(),
+            database: database.map(str::to_string),
+            user,
+        }
+    }
+
+    /// Key for an ADO-style connection string; keywords are matched the way
+    /// `Config::from_ado_string` accepts them.
+    pub fn from_connection_string(
+        connection_string: &str,
+        azure_credential: Option<&PyAzureCredential>,
+    ) -> Self {
+        let mut server = None;
+        let mut database = None;
+        let mut username = None;
+        for pair in connection_string.split(';') {
+            let Some((key, value)) = pair.split_once('=') else {
+                continue;
+            };
+            let value = value
+                .trim()
+                .trim_matches(|c| c == '{' || c == '}' || c == '\'' || c == '"');
+            match key.trim().to_ascii_lowercase().as_str() {
+                "server" | "data source" | "address" | "addr" | "network address" => {
+                    server = Some(value)
+                }
+                "database" | "initial catalog" => database = Some(value),
+                "user id" | "user" | "uid" => username = Some(value),
+                _ => {}
+            }
+        }
+        Self::new(server, database, username, azure_credential)
+    }
+}
+
+struct Entry {
+    key: PoolKey,
+    pool: Weak<PoolSlot>,
+    max_size: u32,
+}
+
+static REGISTRY: Mutex<Vec<Entry>> = Mutex::new(Vec::new());
+
+/// Record a `Connection`'s pool; entries go away once every `Connection` sharing the
+/// pool has been dropped.
+pub fn register(key: PoolKey, pool: &Arc<PoolSlot>, max_size: u32) {
+    let mut registry = REGISTRY.lock().unwrap_or_else(|e| e.into_inner());
+    registry.retain(|entry| entry.pool.strong_count() > 0);
+    registry.push(Entry {
+        key,
+        pool: Arc::downgrade(pool),
+        max_size,
+    });
+}
+
+/// Re-key a registered pool after `reload_config()` changed its credential or size.
+pub fn update(pool: &Arc<PoolSlot>, key: &PoolKey, max_size: u32) {
+    let mut registry = REGISTRY.lock().unwrap_or_else(|e| e.into_inner());
+    for entry in registry
+        .iter_mut()
+        .filter(|entry| std::ptr::eq(entry.pool.as_ptr(), Arc::as_ptr(pool)))
+    {
+        entry.key = key.clone();
+        entry.max_size = max_size;
+    }
+}
+
+#[derive(Default)]
+struct KeyTotals {
+    pools: u32,
+    connections: u32,
+    idle_connections: u32,
+    max_size: u32,
+}
+
+/// Per-key totals over the pools that are currently connected, as a list of dicts.
+pub async fn stats_by_key() -> PyResult<Py<PyAny>> {
+    let live: Vec<(PoolKey, Arc<PoolSlot>, u32)> = {
+        let registry = REGISTRY.lock().unwrap_or_else(|e| e.into_inner());
+        registry
+            .iter()
+            .filter_map(|entry| Some((entry.key.clone(), entry.pool.upgrade()?, entry.max_size)))
+            .collect()
+    };
+
+    let mut totals: Vec<(PoolKey, KeyTotals)> = Vec::new();
+    for (key, slot, max_size) in live {
+        let Some(state) = slot.read().await.as_ref().map(|pool| pool.state()) else {
+            continue;
+        };
+        let index = match totals.iter().position(|(k, _)| *k == key) {
+            Some(index) => index,
+            None => {
+                totals.push((key, KeyTotals::default()));
+                totals.len() - 1
+            }
+        };
+        let entry = &mut totals[index].1;
+        entry.pools += 1;
+        entry.connections += state.connections;
+        entry.idle_connections += state.idle_connections;
+        entry.max_size += max_size;
+    }
+
+    Python::attach(|py| {
+        let list = PyList::empty(py);
+        for (key, entry) in totals {
+            let dict = PyDict::new(py);
+            dict.set_item("server", key.server)?;
+            dict.set_item("database", key.database)?;
+            dict.set_item("user", key.user)?;
+            dict.set_item("pools", entry.pools)?;
+            dict.set_item("connections", entry.connections)?;
+            dict.set_item("idle_connections", entry.idle_connections)?;
+            dict.set_item(
+                "active_connections",
+                entry.connections.saturating_sub(entry.idle_connections),
+            )?;
+            dict.set_item("max_size", entry.max_size)?;
+            list.append(dict)?;
+        }
+        Ok(list.into_any().unbind())
+    })
+}
+
+/// The key as a dict, for `pool_stats()`.
+pub fn key_to_py(key: &PoolKey, py: Python<'_>) -> PyResult<Py<PyAny>> {
+    let dict = PyDict::new(py);
+    dict.set_item("server", &key.server)?;
+    dict.set_item("database", &key.database)?;
+    dict.set_item("user", &key.user)?;
+    Ok(dict.into_any().unbind())
+}
@@ -0,0 +1,301 @@
+//! Reaching servers through a SOCKS5 proxy or an HTTP `CONNECT` proxy.
+//!
+//! `fastmssql.set_proxies({"*.internal": "socks5://bastion.example.com:1080"})` routes
+//! connections to matching servers through the given proxy; keys are host names,
+//! `*.suffix` patterns or `*` for every server.  The proxy hands back a stream to the
+//! server that tiberius uses like a direct TCP connection, so TLS and login are
+//! unchanged.  Server names are resolved on the proxy side, so host aliases and the
+//! resolver callable do not apply to proxied servers.
+//!
+//! There is no SSH transport: a bastion reached over SSH can serve as a SOCKS5 proxy
+//! with `ssh -D`.
+
+use base64::Engine;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::collections::HashMap;
+use std::io;
+use std::net::IpAddr;
+use std::sync::{LazyLock, Mutex};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use url::Url;
+
+use crate::tcp_connect;
+
+static ROUTES: LazyLock<Mutex<HashMap<String, Proxy>>> = LazyLock::new(Default::default);
+
+#[derive(Clone, Debug)]
+enum Proxy {
+    Socks5 {
+        address: String,
+        credentials: Option<(String, String)>,
+    },
+    Http {
+        address: String,
+        credentials: Option<(String, String)>,
+    },
+}
+
+impl Proxy {
+    /// Parse `socks5://[user:pass@]host[:1080]` or `http://[user:pass@]host[:8080]`.
+    fn parse(spec: &str) -> Result<Self, String> {
+        let url = Url::parse(spec).map_err(|e| format!("invalid proxy URL {:?}: {}", spec, e))?;
+        let host = match url.host_str() {
+            Some(host) if !host.is_empty() => host.to_string(),
+            _ => return Err(format!("proxy URL {:?} has no host", spec)),
+        };
+        let credentials = match url.username() {
+            "" => None,
+            user => Some((decode(user), decode(url.password().unwrap_or_default()))),
+        };
+        let address =
+            |default_port: u16| format!("{}:{}", host, url.port().unwrap_or(default_port));
+
+        match url.scheme() {
+            "socks5" | "socks5h" => Ok(Proxy::Socks5 {
+                address: address(1080),
+                credentials,
+            }),
+            "http" => Ok(Proxy::Http {
+                address: address(8080),
+                credentials,
+            }),
+            other => Err(format!(
+                "unsupported proxy scheme {:?}; use socks5:// or http://",
+                other
+            )),
+        }
+    }
+
+    /// The proxy's kind and address, without its credentials.
+    fn describe(&self) -> String {
+        match self {
+            Proxy::Socks5 { address, .. } => format!("SOCKS5 proxy {}", address),
+            Proxy::Http { address, .. } => format!("HTTP proxy {}", address),
+        }
+    }
+
+    async fn connect(&self, host: &str, port: u16) -> io::Result<TcpStream> {
+        match self {
+            Proxy::Socks5 {
+                address,
+                credentials,
+            } => {
+                let mut stream = tcp_connect::connect_direct(address).await?;
+                socks5_handshake(&mut stream, host, port, credentials.as_ref()).await?;
+                Ok(stream)
+            }
+            Proxy::Http {
+                address,
+                credentials,
+            } => {
+                let stream = tcp_connect::connect_direct(address).await?;
+                http_connect(stream, host, port, credentials.as_ref()).await
+            }
+        }
+    }
+}
+
+fn decode(component: &str) -> String {
+    percent_encoding::percent_decode_str(component)
+        .decode_utf8_lossy()
+        .into_owned()
+}
+
+/// Connect to `host:port` through the proxy configured for `host`, or None when the
+/// server is reached directly.
+pub async fn connect(host: &str, port: u16) -> Option<io::Result<TcpStream>> {
+    let proxy = route_for(host)?;
+    Some(
+        proxy
+            .connect(host, port)
+            .await
+            .map_err(|e| io::Error::new(e.kind(), format!("via proxy: {}", e))),
+    )
+}
+
+/// The proxy `host` is reached through, e.g. `SOCKS5 proxy bastion:1080`, or None
+/// when it is reached directly.
+pub fn describe_route(host: &str) -> Option<String> {
+    route_for(host).map(|proxy| proxy.describe())
+}
+
+/// The proxy for `host`: an exact entry, else the longest matching `*.suffix`, else `*`.
+fn route_for(host: &str) -> Option<Proxy> {
+    let routes = ROUTES.lock().ok()?;
+    if routes.is_empty() {
+        return None;
+    }
+    let host = host.to_ascii_lowercase();
+    if let Some(proxy) = routes.get(&host) {
+        return Some(proxy.clone());
+    }
+    routes
+        .iter()
+        .filter_map(|(pattern, proxy)| {
+            let suffix = pattern.strip_prefix("*.")?;
+            host.ends_with(&format!(".{}", suffix))
+                .then_some((suffix.len(), proxy))
+        })
+        .max_by_key(|(len, _)| *len)
+        .map(|(_, proxy)| proxy.clone())
+        .or_else(|| routes.get("*").cloned())
+}
+
+fn proxy_error(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::ConnectionRefused, message.into())
+}
+
+/// RFC 1928 CONNECT, with RFC 1929 username/password authentication when configured.
+/// Host names are sent unresolved so the proxy resolves them.
+async fn socks5_handshake(
+    stream: &mut TcpStream,
+    host: &str,
+    port: u16,
+    credentials: Option<&(String, String)>,
+) -> io::Result<()> {
+    let method = if credentials.is_some() { 0x02 } else { 0x00 };
+    stream.write_all(&[0x05, 0x01, method]).await?;
+    let mut choice = [0u8; 2];
+    stream.read_exact(&mut choice).await?;
+    if choice[0] != 0x05 || choice[1] != method {
+        return Err(proxy_error(
+            "SOCKS5 proxy rejected the authentication method",
+        ));
+    }
+
+    if let Some((user, password)) = credentials {
+        let (user, password) = (user.as_bytes(), password.as_bytes());
+        if user.len() > 255 || password.len() > 255 {
+            return Err(proxy_error(
+                "SOCKS5 username and password are limited to 255 bytes",
+            ));
+        }
+        let mut request = vec![0x01, user.len() as u8];
+        request.extend_from_slice(user);
+        request.push(password.len() as u8);
+        request.extend_from_slice(password);
+        stream.write_all(&request).await?;
+        let mut status = [0u8; 2];
+        stream.read_exact(&mut status).await?;
+        if status[1] != 0x00 {
+            return Err(proxy_error(
+                "SOCKS5 proxy rejected the username or password",
+            ));
+        }
+    }
+
+    let mut request = vec![0x05, 0x01, 0x00];
+    match host.trim_matches(['[', ']']).parse::<IpAddr>() {
+        Ok(IpAddr::V4(ip)) => {
+            request.push(0x01);
+            request.extend_from_slice(&ip.octets());
+        }
+        Ok(IpAddr::V6(ip)) => {
+            request.push(0x04);
+            request.extend_from_slice(&ip.octets());
+        }
+        Err(_) => {
+            if host.len() > 255 {
+                return Err(proxy_error("host name too long for SOCKS5"));
+            }
+            request.push(0x03);
+            request.push(host.len() as u8);
+            request.extend_from_slice(host.as_bytes());
+        }
+    }
+    request.extend_from_slice(&port.to_be_bytes());
+    stream.write_all(&request).await?;
+
+    let mut reply = [0u8; 4];
+    stream.read_exact(&mut reply).await?;
+    if reply[1] != 0x00 {
+        let reason = match reply[1] {
+            0x02 => "connection not allowed by ruleset",
+            0x03 => "network unreachable",
+            0x04 => "host unreachable",
+            0x05 => "connection refused",
+            0x06 => "TTL expired",
+            _ => "general failure",
+        };
+        return Err(proxy_error(format!(
+            "SOCKS5 proxy could not connect: {}",
+            reason
+        )));
+    }
+    // Skip the bound address and port.
+    let skip = match reply[3] {
+        0x01 => 4 + 2,
+        0x04 => 16 + 2,
+        0x03 => stream.read_u8().await? as usize + 2,
+        _ => return Err(proxy_error("malformed SOCKS5 reply")),
+    };
+    let mut bound = vec![0u8; skip];
+    stream.read_exact(&mut bound).await?;
+    Ok(())
+}
+
+/// HTTP `CONNECT`, with Basic proxy authentication when configured.
+async fn http_connect(
+    stream: TcpStream,
+    host: &str,
+    port: u16,
+    credentials: Option<&(String, String)>,
+) -> io::Result<TcpStream> {
+    let target = match host.parse::<IpAddr>() {
+        Ok(IpAddr::V6(_)) => format!("[{}]:{}", host, port),
+        _ => format!("{}:{}", host, port),
+    };
+    let mut request = format!("CONNECT {target} HTTP/1.1\r\nHost: {target}\r\n");
+    if let Some((user, password)) = credentials {
+        let token = base64::engine::general_purpose::STANDARD.encode(format!("{user}:{password}"));
+        request.push_str(&format!("Proxy-Authorization: Basic {token}\r\n"));
+    }
+    request.push_str("\r\n");
+
+    let mut reader = BufReader::new(stream);
+    reader.get_mut().write_all(request.as_bytes()).await?;
+    let mut status = String::new();
+    reader.read_line(&mut status).await?;
+    let code = status.split_whitespace().nth(1).unwrap_or_default();
+    if code != "200" {
+        return Err(proxy_error(format!(
+            "HTTP proxy refused CONNECT: {}",
+            status.trim_end()
+        )));
+    }
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).await? == 0 {
+            return Err(proxy_error("HTTP proxy closed the connection"));
+        }
+        if header == "\r\n" || header == "\n" {
+            break;
+        }
+    }
+    // The server speaks first only after the client's prelogin, so nothing past the
+    // response headers can be buffered.
+    Ok(reader.into_inner())
+}
+
+/// Replace the proxy routes, e.g. `{"*.internal": "socks5://bastion:1080"}`. Keys are
+/// server names, `*.suffix` patterns or `*`; values are `socks5://` or `http://` URLs.
+/// None or an empty dict connects to every server directly.
+#[pyfunction]
+#[pyo3(signature = (routes = None))]
+pub fn set_proxies(routes: Option<HashMap<String, String>>) -> PyResult<()> {
+    let parsed = routes
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(pattern, spec)| {
+            Proxy::parse(&spec)
+                .map(|proxy| (pattern.to_ascii_lowercase(), proxy))
+                .map_err(PyValueError::new_err)
+        })
+        .collect::<PyResult<HashMap<_, _>>>()?;
+    if let Ok(mut table) = ROUTES.lock() {
+        *table = parsed;
+    }
+    Ok(())
+}
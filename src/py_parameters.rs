@@ -1,4 +1,6 @@
+use crate::parameter_conversion::{json_list_text, typed_null_for_sql_type};
 use crate::type_mapping;
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use pyo3::types::{PyDict, PyList, PyTuple};
 
@@ -10,6 +12,8 @@ pub struct Parameter {
     pub sql_type: Option<String>,
     #[pyo3(get)]
     pub is_expanded: bool,
+    #[pyo3(get)]
+    pub is_json_list: bool,
 }
 
 #[pymethods]
@@ -19,16 +23,51 @@ impl Parameter {
     pub fn new(value: Py<PyAny>, sql_type: Option<String>) -> Self {
         let is_expanded = Python::attach(|py| {
             let value_bound = value.bind(py);
-            type_mapping::is_expandable_iterable(&value_bound).unwrap_or(false)
+            type_mapping::is_expandable_iterable(value_bound).unwrap_or(false)
         });
 
         Parameter {
             value,
             sql_type,
             is_expanded,
+            is_json_list: false,
         }
     }
 
+    /// A NULL bound with the given SQL type, for statements and procedures that
+    /// need to know the type of a NULL argument (`Parameter.null("VARBINARY(MAX)")`).
+    #[staticmethod]
+    #[pyo3(signature = (sql_type))]
+    pub fn null(py: Python, sql_type: String) -> PyResult<Self> {
+        if typed_null_for_sql_type(&sql_type).is_none() {
+            return Err(PyValueError::new_err(format!(
+                "Unsupported SQL type for a NULL parameter: '{}'",
+                sql_type
+            )));
+        }
+        Ok(Parameter {
+            value: py.None(),
+            sql_type: Some(sql_type),
+            is_expanded: false,
+            is_json_list: false,
+        })
+    }
+
+    /// A list of any length sent as one NVARCHAR(MAX) JSON array.  Bound to
+    /// `IN (@Pn)`, the predicate is rewritten to `IN (SELECT value FROM OPENJSON(@Pn))`,
+    /// so large ID lists need neither 2,100 parameters nor a placeholder each.
+    #[staticmethod]
+    #[pyo3(signature = (values))]
+    pub fn json_list(py: Python, values: &Bound<PyAny>) -> PyResult<Self> {
+        let text = json_list_text(values)?;
+        Ok(Parameter {
+            value: text.into_pyobject(py)?.into_any().unbind(),
+            sql_type: None,
+            is_expanded: false,
+            is_json_list: true,
+        })
+    }
+
     fn __repr__(&self, py: Python) -> String {
         let value_bound = self.value.bind(py);
         let value_repr = match value_bound.repr() {
@@ -36,6 +75,10 @@ impl Parameter {
             Err(_) => "<error>".to_string(),
         };
 
+        if self.is_json_list {
+            return format!("Parameter(json_list={})", value_repr);
+        }
+
         // Check if this is an expanded parameter (iterable)
         if self.is_expanded {
             match &self.sql_type {
@@ -138,21 +181,7 @@ impl Parameters {
     }
 
     pub fn to_list(&self, py: Python) -> PyResult<Py<PyList>> {
-        let named_len = self.named.bind(py).len();
-        if named_len > 0 {
-            return Err(pyo3::exceptions::PyValueError::new_err(format!(
-                "Named parameters are not supported by the SQL Server wire protocol. \
-                 Use positional parameters (Parameters(value1, value2, ...)) instead. \
-                 Found {} named parameter(s): {:?}",
-                named_len,
-                self.named
-                    .bind(py)
-                    .keys()
-                    .iter()
-                    .map(|k| k.str().map(|s| s.to_string()).unwrap_or_default())
-                    .collect::<Vec<_>>()
-            )));
-        }
+        self.ensure_no_named(py)?;
         let mut values = Vec::new();
         for param_py in &self.positional {
             let param = param_py.borrow(py);
@@ -201,3 +230,40 @@ impl Parameters {
         Ok(new_dict.into())
     }
 }
+
+impl Parameters {
+    fn ensure_no_named(&self, py: Python) -> PyResult<()> {
+        let named_len = self.named.bind(py).len();
+        if named_len > 0 {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Named parameters are not supported by the SQL Server wire protocol. \
+                 Use positional parameters (Parameters(value1, value2, ...)) instead. \
+                 Found {} named parameter(s): {:?}",
+                named_len,
+                self.named
+                    .bind(py)
+                    .keys()
+                    .iter()
+                    .map(|k| k.str().map(|s| s.to_string()).unwrap_or_default())
+                    .collect::<Vec<_>>()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Like `to_list`, but keeps the `Parameter` wrapper around values that carry a
+    /// `sql_type` or a JSON list, so parameter conversion can honour them.
+    pub fn to_typed_list(&self, py: Python) -> PyResult<Py<PyList>> {
+        self.ensure_no_named(py)?;
+        let mut values = Vec::with_capacity(self.positional.len());
+        for param_py in &self.positional {
+            let param = param_py.borrow(py);
+            if (param.sql_type.is_some() && !param.is_expanded) || param.is_json_list {
+                values.push(param_py.clone_ref(py).into_any());
+            } else {
+                values.push(param.value.clone_ref(py));
+            }
+        }
+        Ok(PyList::new(py, values)?.into())
+    }
+}
@@ -0,0 +1,86 @@
+//! Statement labels for `Connection.execute(..., label=...)`.
+//!
+//! A label is appended to the statement as a fixed-format comment
+//! (`/* fastmssql:label=nightly-refresh */`), so every run of a labelled statement
+//! carries the same text and can be found in Query Store or `sys.dm_exec_query_stats`
+//! with `LIKE '%fastmssql:label=nightly-refresh%'`.  Each label also gets counters in
+//! `pool_stats()["labels"]`, shared by the `with_options()` copies of a connection.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+const MAX_LABEL_LEN: usize = 128;
+
+/// Check `label` and return the comment appended to labelled statements.  Only
+/// characters that cannot end the comment or change its meaning are accepted.
+pub fn label_comment(label: &str) -> PyResult<String> {
+    let valid = !label.is_empty()
+        && label.len() <= MAX_LABEL_LEN
+        && label
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':'));
+    if !valid {
+        return Err(PyValueError::new_err(format!(
+            "Invalid label '{}': expected 1-{} letters, digits, '-', '_', '.' or ':'",
+            label, MAX_LABEL_LEN
+        )));
+    }
+    // On its own line so a trailing `--` comment in the statement cannot swallow it.
+    Ok(format!("\n/* fastmssql:label={} */", label))
+}
+
+#[derive(Default)]
+struct LabelTotals {
+    executions: u64,
+    errors: u64,
+    rows_affected: u64,
+    total: Duration,
+    max: Duration,
+}
+
+/// Per-label counters of one pool.
+#[derive(Default)]
+pub struct LabelStats {
+    labels: Mutex<BTreeMap<String, LabelTotals>>,
+}
+
+impl LabelStats {
+    /// Count one run of `label` that took `elapsed` and affected `rows_affected` rows,
+    /// or failed when it is None.
+    pub fn record(&self, label: &str, elapsed: Duration, rows_affected: Option<u64>) {
+        let mut labels = self.labels.lock().unwrap_or_else(|e| e.into_inner());
+        let totals = match labels.get_mut(label) {
+            Some(totals) => totals,
+            None => labels.entry(label.to_string()).or_default(),
+        };
+        totals.executions += 1;
+        match rows_affected {
+            Some(rows) => totals.rows_affected += rows,
+            None => totals.errors += 1,
+        }
+        totals.total += elapsed;
+        totals.max = totals.max.max(elapsed);
+    }
+
+    /// `{label: {"executions", "errors", "rows_affected", "total_ms", "avg_ms", "max_ms"}}`
+    pub fn to_py<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let labels = self.labels.lock().unwrap_or_else(|e| e.into_inner());
+        let result = PyDict::new(py);
+        for (label, totals) in labels.iter() {
+            let total_ms = totals.total.as_secs_f64() * 1000.0;
+            let entry = PyDict::new(py);
+            entry.set_item("executions", totals.executions)?;
+            entry.set_item("errors", totals.errors)?;
+            entry.set_item("rows_affected", totals.rows_affected)?;
+            entry.set_item("total_ms", total_ms)?;
+            entry.set_item("avg_ms", total_ms / totals.executions as f64)?;
+            entry.set_item("max_ms", totals.max.as_secs_f64() * 1000.0)?;
+            result.set_item(label, entry)?;
+        }
+        Ok(result)
+    }
+}
@@ -0,0 +1,225 @@
+//! Statement logging for `Connection(log_queries=True)`.
+//!
+//! Each `query()` / `execute()` / `simple_query()` / `execute_returning()` /
+//! `exec_sql()` call is written at DEBUG level to the `fastmssql.query` logger of
+//! Python's `logging` module, tagged with its execution ID.  Parameter values are
+//! only included with `log_parameters=True`, and then any parameter matched by a
+//! redaction rule (a 1-based position, or a name pattern with `*` / `?` wildcards) is
+//! written as `***` so secrets never reach the log.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyInt, PyString};
+use std::sync::OnceLock;
+
+use crate::parameter_conversion::FastParameter;
+use crate::type_mapping::numeric_to_decimal_string;
+
+static LOGGER: OnceLock<Option<Py<PyAny>>> = OnceLock::new();
+
+/// String values longer than this are cut short in the log.
+const MAX_LOGGED_STRING: usize = 200;
+const REDACTED: &str = "***";
+
+#[derive(Clone, Debug)]
+enum RedactRule {
+    Position(usize),
+    Pattern(String),
+}
+
+/// Parameters of a logged call: positional (`@P1`, `@P2`, ...) or named (`exec_sql()`).
+pub enum LoggedParams<'a> {
+    None,
+    Positional(&'a [FastParameter]),
+    Named(&'a [(String, FastParameter)]),
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct QueryLog {
+    pub enabled: bool,
+    pub include_parameters: bool,
+    rules: Vec<RedactRule>,
+}
+
+impl QueryLog {
+    pub fn new(
+        enabled: bool,
+        include_parameters: bool,
+        redact: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<Self> {
+        Ok(QueryLog {
+            enabled,
+            include_parameters,
+            rules: match redact {
+                Some(redact) => parse_rules(redact)?,
+                None => Vec::new(),
+            },
+        })
+    }
+
+    /// Copy with the given settings replaced, for `with_options()`.
+    pub fn with_overrides(
+        &self,
+        enabled: Option<bool>,
+        include_parameters: Option<bool>,
+        redact: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<Self> {
+        Ok(QueryLog {
+            enabled: enabled.unwrap_or(self.enabled),
+            include_parameters: include_parameters.unwrap_or(self.include_parameters),
+            rules: match redact {
+                Some(redact) => parse_rules(redact)?,
+                None => self.rules.clone(),
+            },
+        })
+    }
+
+    fn redacts(&self, position: usize, name: &str) -> bool {
+        self.rules.iter().any(|rule| match rule {
+            RedactRule::Position(p) => *p == position,
+            RedactRule::Pattern(pattern) => glob_match(pattern.as_bytes(), name.as_bytes()),
+        })
+    }
+
+    /// Write the statement (and parameters, if enabled) to the `fastmssql.query` logger.
+    /// Logging failures are ignored so they never fail the call being logged.
+    pub fn log(&self, py: Python<'_>, execution_id: &str, sql: &str, params: LoggedParams<'_>) {
+        if !self.enabled {
+            return;
+        }
+        let Some(logger) = logger(py) else {
+            return;
+        };
+        if !logger
+            .call_method1("isEnabledFor", (10,))
+            .and_then(|enabled| enabled.is_truthy())
+            .unwrap_or(false)
+        {
+            return;
+        }
+
+        let rendered = if self.include_parameters {
+            self.render_parameters(params)
+        } else {
+            String::new()
+        };
+        let _ = if rendered.is_empty() {
+            logger.call_method1("debug", ("[%s] %s", execution_id, sql))
+        } else {
+            logger.call_method1("debug", ("[%s] %s | %s", execution_id, sql, rendered))
+        };
+    }
+
+    fn render_parameters(&self, params: LoggedParams<'_>) -> String {
+        let mut entries = Vec::new();
+        match params {
+            LoggedParams::None => {}
+            LoggedParams::Positional(values) => {
+                for (index, value) in values.iter().enumerate() {
+                    let name = format!("P{}", index + 1);
+                    entries.push(self.render_entry(index + 1, &name, value));
+                }
+            }
+            LoggedParams::Named(values) => {
+                for (index, (name, value)) in values.iter().enumerate() {
+                    entries.push(self.render_entry(index + 1, name, value));
+                }
+            }
+        }
+        entries.join(", ")
+    }
+
+    fn render_entry(&self, position: usize, name: &str, value: &FastParameter) -> String {
+        if self.redacts(position, name) {
+            format!("@{}={}", name, REDACTED)
+        } else {
+            format!("@{}={}", name, render_value(value))
+        }
+    }
+}
+
+fn logger(py: Python<'_>) -> Option<&Bound<'_, PyAny>> {
+    LOGGER
+        .get_or_init(|| {
+            py.import("logging")
+                .and_then(|m| m.call_method1("getLogger", ("fastmssql.query",)))
+                .map(|l| l.unbind())
+                .ok()
+        })
+        .as_ref()
+        .map(|l| l.bind(py))
+}
+
+fn parse_rules(redact: &Bound<'_, PyAny>) -> PyResult<Vec<RedactRule>> {
+    let mut rules = Vec::new();
+    for item in redact.try_iter()? {
+        let item = item?;
+        if item.is_instance_of::<PyInt>() {
+            let position: usize = item.extract()?;
+            if position == 0 {
+                return Err(PyValueError::new_err(
+                    "redact_parameters positions are 1-based (1 is @P1)",
+                ));
+            }
+            rules.push(RedactRule::Position(position));
+        } else if item.is_instance_of::<PyString>() {
+            let pattern = item.extract::<String>()?;
+            let pattern = pattern.strip_prefix('@').unwrap_or(&pattern);
+            rules.push(RedactRule::Pattern(pattern.to_ascii_lowercase()));
+        } else {
+            return Err(PyValueError::new_err(
+                "redact_parameters entries must be positions (int) or name patterns (str)",
+            ));
+        }
+    }
+    Ok(rules)
+}
+
+/// Case-insensitive match of `name` against a pattern using `*` (any run) and `?`
+/// (any one character). The pattern is already lower-cased.
+fn glob_match(pattern: &[u8], name: &[u8]) -> bool {
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while n < name.len() {
+        if p < pattern.len() && pattern[p] == b'*' {
+            backtrack = Some((p, n));
+            p += 1;
+        } else if p < pattern.len()
+            && (pattern[p] == b'?' || pattern[p] == name[n].to_ascii_lowercase())
+        {
+            p += 1;
+            n += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            n = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&b| b == b'*')
+}
+
+fn render_value(value: &FastParameter) -> String {
+    match value {
+        FastParameter::Null(_) => "NULL".to_string(),
+        FastParameter::Bool(b) => if *b { "1" } else { "0" }.to_string(),
+        FastParameter::I64(i) => i.to_string(),
+        FastParameter::F64(f) => f.to_string(),
+        FastParameter::Decimal(n) => numeric_to_decimal_string(*n),
+        FastParameter::Guid(g) => format!("'{}'", g),
+        FastParameter::String(s) | FastParameter::JsonList(s) | FastParameter::Xml(s) => {
+            let mut text: String = s.chars().take(MAX_LOGGED_STRING).collect();
+            if text.len() < s.len() {
+                text.push_str("...");
+            }
+            format!("'{}'", text.replace('\'', "''"))
+        }
+        FastParameter::Bytes(b) => format!("<{} bytes>", b.len()),
+        FastParameter::Buffer(b) => format!("<{} bytes>", b.len_bytes()),
+        FastParameter::Date(d) => format!("'{}'", d),
+        FastParameter::DateTime(dt) | FastParameter::SmallDateTime(dt) => format!("'{}'", dt),
+        FastParameter::Time(t) => format!("'{}'", t),
+        FastParameter::DateTimeOffset(dt) => format!("'{}'", dt),
+    }
+}
@@ -0,0 +1,117 @@
+//! Guards on the shape of results (`Connection(max_result_columns=...,
+//! max_row_bytes=...)`).
+//!
+//! An accidental `SELECT *` from a very wide table can exhaust a memory-constrained
+//! service long before the rows are turned into Python objects.  The limits are
+//! checked as the result is read from the server: the column count as soon as the
+//! column metadata arrives, and the size of each row as it arrives, so the call fails
+//! with `ResultLimitError` after at most one oversized row.  A pooled connection is
+//! then discarded, so the rest of the result is never read; a pinned connection or
+//! transaction skips the rest (without keeping it) before its next statement.
+//!
+//! A row's size is that of its values as received: the UTF-8 length of strings, the
+//! length of binary and XML values, and the storage size of fixed-width types.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use tiberius::{Column, ColumnData, Row};
+
+use crate::types::ResultLimitError;
+
+/// The limits of a connection; `None` means unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResultLimits {
+    pub max_columns: Option<usize>,
+    pub max_row_bytes: Option<usize>,
+}
+
+impl ResultLimits {
+    /// Validate the `max_result_columns=` and `max_row_bytes=` arguments.
+    pub fn new(max_columns: Option<usize>, max_row_bytes: Option<usize>) -> PyResult<Self> {
+        if max_columns == Some(0) {
+            return Err(PyValueError::new_err(
+                "max_result_columns must be at least 1",
+            ));
+        }
+        if max_row_bytes == Some(0) {
+            return Err(PyValueError::new_err("max_row_bytes must be at least 1"));
+        }
+        Ok(ResultLimits {
+            max_columns,
+            max_row_bytes,
+        })
+    }
+
+    /// Raise `ResultLimitError` if a result has more than `max_columns` columns.
+    pub fn check_columns(&self, columns: &[Column]) -> PyResult<()> {
+        match self.max_columns {
+            Some(limit) if columns.len() > limit => {
+                let names: Vec<&str> = columns.iter().take(5).map(Column::name).collect();
+                Err(ResultLimitError::new_err(format!(
+                    "Result has {} columns ({}{}), over the connection's \
+                     max_result_columns={}; select only the columns you need",
+                    columns.len(),
+                    names.join(", "),
+                    if columns.len() > names.len() {
+                        ", ..."
+                    } else {
+                        ""
+                    },
+                    limit
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Raise `ResultLimitError` if `row` is larger than `max_row_bytes`.  `index` is
+    /// the row's position in its result, from 0.
+    pub fn check_row(&self, row: &Row, index: usize) -> PyResult<()> {
+        let Some(limit) = self.max_row_bytes else {
+            return Ok(());
+        };
+        let size: usize = row.cells().map(|(_, data)| value_bytes(data)).sum();
+        if size <= limit {
+            return Ok(());
+        }
+        let widest = row
+            .cells()
+            .max_by_key(|(_, data)| value_bytes(data))
+            .map(|(column, data)| {
+                format!(
+                    "; widest column '{}' has {} bytes",
+                    column.name(),
+                    value_bytes(data)
+                )
+            })
+            .unwrap_or_default();
+        Err(ResultLimitError::new_err(format!(
+            "Row {} is {} bytes, over the connection's max_row_bytes={}{}",
+            index, size, limit, widest
+        )))
+    }
+}
+
+/// Whether `e` is a `ResultLimitError`, after which a pooled connection is discarded
+/// rather than left to read the rest of the result.
+pub fn is_limit_error(e: &PyErr) -> bool {
+    Python::attach(|py| e.is_instance_of::<ResultLimitError>(py))
+}
+
+/// The size of `data` as received.
+fn value_bytes(data: &ColumnData<'_>) -> usize {
+    match data {
+        ColumnData::String(Some(s)) => s.len(),
+        ColumnData::Binary(Some(b)) => b.len(),
+        ColumnData::Xml(Some(xml)) => xml.as_ref().as_ref().len(),
+        ColumnData::U8(Some(_)) | ColumnData::Bit(Some(_)) => 1,
+        ColumnData::I16(Some(_)) => 2,
+        ColumnData::I32(Some(_)) | ColumnData::F32(Some(_)) | ColumnData::Date(Some(_)) => 4,
+        ColumnData::SmallDateTime(Some(_)) => 4,
+        ColumnData::I64(Some(_)) | ColumnData::F64(Some(_)) | ColumnData::DateTime(Some(_)) => 8,
+        ColumnData::Time(Some(_)) | ColumnData::DateTime2(Some(_)) => 8,
+        ColumnData::DateTimeOffset(Some(_)) => 10,
+        ColumnData::Guid(Some(_)) | ColumnData::Numeric(Some(_)) => 16,
+        _ => 0,
+    }
+}
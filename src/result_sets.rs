@@ -0,0 +1,206 @@
+//! Incremental consumption of multi-statement batches (`Connection.stream_results`).
+//!
+//! The statement runs on a pooled connection owned by a background task, which
+//! collects one result set at a time and hands it over a bounded channel.  The
+//! task only reads ahead by a single result set, so a long script is consumed as
+//! the caller iterates instead of being buffered whole.  Dropping the iterator
+//! before the last result set aborts the task; the batch is then cancelled on the
+//! server and its connection discarded (see [`crate::attention`]).
+
+use futures_util::TryStreamExt;
+use pyo3::exceptions::PyStopAsyncIteration;
+use pyo3::prelude::*;
+use pyo3_async_runtimes::tokio::future_into_py;
+use std::sync::{Arc, Mutex as StdMutex};
+use tiberius::{Config, QueryItem, Row};
+use tokio::sync::{Mutex, RwLock, mpsc};
+use tokio::task::AbortHandle;
+
+use crate::azure_auth::PyAzureCredential;
+use crate::execution_id::tag_error;
+use crate::helpers::wrap_query_stream;
+use crate::parameter_conversion::{FastParameter, params_as_sql_refs};
+use crate::pool_config::PyPoolConfig;
+use crate::pool_manager::{ConnectionPool, ensure_pool_initialized_with_auth};
+use crate::result_limits::{ResultLimits, is_limit_error};
+use crate::type_mapping::DecodeOptions;
+use crate::types::create_sql_error;
+
+/// Everything the producer task needs; taken on the first `__anext__`.
+pub struct PendingResultSets {
+    pub pool: Arc<RwLock<Option<ConnectionPool>>>,
+    pub config: Arc<Config>,
+    pub pool_config: PyPoolConfig,
+    pub azure_credential: Option<Arc<PyAzureCredential>>,
+    pub sql: String,
+    pub parameters: Vec<FastParameter>,
+    pub limits: ResultLimits,
+}
+
+enum StreamState {
+    NotStarted(Box<PendingResultSets>),
+    Running(mpsc::Receiver<PyResult<Vec<Row>>>),
+    Finished,
+}
+
+/// Async iterator over the result sets of a batch; each item is a `QueryStream`.
+#[pyclass(name = "ResultSetStream")]
+pub struct PyResultSetStream {
+    state: Arc<Mutex<StreamState>>,
+    task: Arc<StdMutex<Option<AbortHandle>>>,
+    decode_options: DecodeOptions,
+    execution_id: String,
+}
+
+impl PyResultSetStream {
+    pub fn new(
+        pending: PendingResultSets,
+        decode_options: DecodeOptions,
+        execution_id: String,
+    ) -> Self {
+        PyResultSetStream {
+            state: Arc::new(Mutex::new(StreamState::NotStarted(Box::new(pending)))),
+            task: Arc::default(),
+            decode_options,
+            execution_id,
+        }
+    }
+}
+
+impl Drop for PyResultSetStream {
+    fn drop(&mut self) {
+        if let Ok(mut task) = self.task.lock()
+            && let Some(task) = task.take()
+        {
+            task.abort();
+        }
+    }
+}
+
+/// Run the batch and send each result set as soon as it is complete.  If the
+/// iterator goes away first, the connection is discarded rather than returned with
+/// the rest of the batch unread.
+async fn produce(
+    pending: PendingResultSets,
+    sender: &mpsc::Sender<PyResult<Vec<Row>>>,
+) -> PyResult<()> {
+    let pool = ensure_pool_initialized_with_auth(
+        pending.pool,
+        pending.config,
+        &pending.pool_config,
+        pending.azure_credential,
+    )
+    .await?;
+    let mut conn = pool.checkout().await?;
+
+    let tiberius_params = params_as_sql_refs(&pending.parameters);
+    let limits = pending.limits;
+    let produced = conn
+        .request(async |client| {
+            let mut stream = client
+                .query(pending.sql.as_str(), &tiberius_params)
+                .await
+                .map_err(|e| create_sql_error(e, "Query execution failed"))?;
+
+            let mut current: Option<Vec<Row>> = None;
+            while let Some(item) = stream
+                .try_next()
+                .await
+                .map_err(|e| create_sql_error(e, "Failed to get results"))?
+            {
+                match item {
+                    QueryItem::Metadata(meta) => {
+                        limits.check_columns(meta.columns())?;
+                        if let Some(rows) = current.replace(Vec::new())
+                            && sender.send(Ok(rows)).await.is_err()
+                        {
+                            // The iterator was dropped.
+                            return Ok(false);
+                        }
+                    }
+                    QueryItem::Row(row) => {
+                        let rows = current.get_or_insert_with(Vec::new);
+                        limits.check_row(&row, rows.len())?;
+                        rows.push(row);
+                    }
+                }
+            }
+
+            if let Some(rows) = current {
+                let _ = sender.send(Ok(rows)).await;
+            }
+            Ok(true)
+        })
+        .await;
+    match produced {
+        Ok(true) => Ok(()),
+        Ok(false) => {
+            conn.mark_broken();
+            Ok(())
+        }
+        Err(e) => {
+            if is_limit_error(&e) {
+                conn.mark_broken();
+            }
+            Err(e)
+        }
+    }
+}
+
+#[pymethods]
+impl PyResultSetStream {
+    pub fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    pub fn __anext__<'p>(&self, py: Python<'p>) -> PyResult<Bound<'p, PyAny>> {
+        let state = self.state.clone();
+        let task = self.task.clone();
+        let decode_options = self.decode_options;
+        let execution_id = self.execution_id.clone();
+
+        future_into_py(py, async move {
+            let mut state = state.lock().await;
+
+            if matches!(*state, StreamState::NotStarted(_)) {
+                let StreamState::NotStarted(pending) =
+                    std::mem::replace(&mut *state, StreamState::Finished)
+                else {
+                    unreachable!()
+                };
+                let (sender, receiver) = mpsc::channel(1);
+                let handle = tokio::spawn(async move {
+                    if let Err(e) = produce(*pending, &sender).await {
+                        let _ = sender.send(Err(e)).await;
+                    }
+                });
+                if let Ok(mut task) = task.lock() {
+                    *task = Some(handle.abort_handle());
+                }
+                *state = StreamState::Running(receiver);
+            }
+
+            let next = match &mut *state {
+                StreamState::Running(receiver) => receiver.recv().await,
+                _ => None,
+            };
+            match next {
+                Some(Ok(rows)) => wrap_query_stream(rows, decode_options),
+                Some(Err(e)) => {
+                    *state = StreamState::Finished;
+                    Err(tag_error(e, &execution_id))
+                }
+                None => {
+                    *state = StreamState::Finished;
+                    Err(PyStopAsyncIteration::new_err(()))
+                }
+            }
+        })
+    }
+
+    /// ID of the `stream_results()` call, as attached to exceptions it raises
+    #[getter]
+    pub fn execution_id(&self) -> &str {
+        &self.execution_id
+    }
+}
@@ -0,0 +1,155 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::collections::hash_map::RandomState;
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+use crate::types::SqlError;
+
+/// Server errors after which re-running a statement is safe: it was rolled back or
+/// never started.
+const DEFAULT_RETRY_ON: &[u32] = &[
+    1205,  // chosen as deadlock victim
+    40197, // Azure SQL: error processing the request (failover, upgrade)
+    40501, // Azure SQL: service is busy
+    40613, // Azure SQL: database not currently available
+    49918, // Azure SQL: not enough resources to process the request
+    49919, // Azure SQL: too many create/update operations in progress
+    49920, // Azure SQL: service is busy processing multiple requests
+    10928, // resource limit reached
+    10929, // minimum resource guarantee not met
+];
+
+/// Whether a login error means Azure SQL is throttling or moving the database rather
+/// than that the login is wrong; the pool retries these when opening connections.
+pub fn is_transient_login_error(code: u32, state: u8) -> bool {
+    match code {
+        40197 | 40501 | 40613 | 49918 | 49919 | 49920 | 10928 | 10929 => true,
+        // Login failed because the database is not available (yet), e.g. mid-failover.
+        18456 => state == 38,
+        _ => false,
+    }
+}
+
+/// Exponential backoff with jitter (50-100% of the nominal delay), so clients that
+/// failed together don't retry in lockstep. `retry` counts from 1.
+pub fn backoff(base: Duration, max: Duration, retry: u32) -> Duration {
+    let nominal = base
+        .saturating_mul(1 << (retry.max(1) - 1).min(16))
+        .min(max);
+    let jitter = RandomState::new().build_hasher().finish() % 1000;
+    nominal.mul_f64(0.5 + jitter as f64 / 2000.0)
+}
+
+/// A random delay between zero and `max`.
+pub fn jitter(max: Duration) -> Duration {
+    let fraction = RandomState::new().build_hasher().finish() % 1000;
+    max.mul_f64(fraction as f64 / 1000.0)
+}
+
+/// How `Connection.execute()` retries statements that failed with a transient error.
+#[pyclass(name = "RetryPolicy", from_py_object)]
+#[derive(Clone)]
+pub struct PyRetryPolicy {
+    pub max_attempts: u32,
+    pub backoff: Duration,
+    pub max_backoff: Duration,
+    pub retry_on: Vec<u32>,
+}
+
+#[pymethods]
+impl PyRetryPolicy {
+    #[new]
+    #[pyo3(signature = (max_attempts = 3, backoff_secs = 0.1, max_backoff_secs = 5.0, retry_on = None))]
+    pub fn new(
+        max_attempts: u32,
+        backoff_secs: f64,
+        max_backoff_secs: f64,
+        retry_on: Option<Vec<u32>>,
+    ) -> PyResult<Self> {
+        if max_attempts < 1 {
+            return Err(PyValueError::new_err("max_attempts must be >= 1"));
+        }
+        if !(backoff_secs.is_finite() && backoff_secs >= 0.0) {
+            return Err(PyValueError::new_err("backoff_secs must be >= 0"));
+        }
+        if !(max_backoff_secs.is_finite() && max_backoff_secs >= backoff_secs) {
+            return Err(PyValueError::new_err(
+                "max_backoff_secs must be >= backoff_secs",
+            ));
+        }
+        Ok(PyRetryPolicy {
+            max_attempts,
+            backoff: Duration::from_secs_f64(backoff_secs),
+            max_backoff: Duration::from_secs_f64(max_backoff_secs),
+            retry_on: retry_on.unwrap_or_else(|| DEFAULT_RETRY_ON.to_vec()),
+        })
+    }
+
+    /// Total number of tries, including the first one
+    #[getter]
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Delay before the first retry, in seconds; it doubles for each further retry
+    #[getter]
+    pub fn backoff_secs(&self) -> f64 {
+        self.backoff.as_secs_f64()
+    }
+
+    /// Upper bound for the delay between retries, in seconds
+    #[getter]
+    pub fn max_backoff_secs(&self) -> f64 {
+        self.max_backoff.as_secs_f64()
+    }
+
+    /// SQL Server error numbers that are retried
+    #[getter]
+    pub fn retry_on(&self) -> Vec<u32> {
+        self.retry_on.clone()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "RetryPolicy(max_attempts={}, backoff_secs={}, max_backoff_secs={}, retry_on={:?})",
+            self.max_attempts,
+            self.backoff_secs(),
+            self.max_backoff_secs(),
+            self.retry_on
+        )
+    }
+}
+
+impl PyRetryPolicy {
+    /// Run `attempt` until it succeeds, fails with an error the policy does not cover,
+    /// or `max_attempts` is reached.
+    pub async fn run<T, F, Fut>(&self, mut attempt: F) -> PyResult<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = PyResult<T>>,
+    {
+        let mut tries = 1;
+        loop {
+            match attempt().await {
+                Err(e) if tries < self.max_attempts && self.is_retryable(&e) => {
+                    tokio::time::sleep(backoff(self.backoff, self.max_backoff, tries)).await;
+                    tries += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    fn is_retryable(&self, err: &PyErr) -> bool {
+        Python::attach(|py| {
+            err.is_instance_of::<SqlError>(py)
+                && err
+                    .value(py)
+                    .getattr("code")
+                    .and_then(|code| code.extract::<u32>())
+                    .is_ok_and(|code| self.retry_on.contains(&code))
+        })
+    }
+}
@@ -0,0 +1,149 @@
+//! Per-row hashes for comparing result sets across environments.
+//!
+//! Each value is written to a canonical byte form and the row is hashed with the same
+//! FNV-1a function as the fingerprints.  The canonical form depends on the value, not on
+//! the exact column type, so the same data read from differently declared columns hashes
+//! the same:
+//!
+//! - integers, BIT and DECIMAL/NUMERIC as a decimal with trailing zeros dropped, so
+//!   `INT 5`, `BIGINT 5` and `DECIMAL(10,2) 5.00` are equal;
+//! - REAL and FLOAT as a 64-bit float;
+//! - CHAR, VARCHAR, NCHAR, NVARCHAR and XML as UTF-8 text;
+//! - DATETIME, SMALLDATETIME and DATETIME2 as the date and time of day, and
+//!   DATETIMEOFFSET as the UTC instant.
+//!
+//! Values are length-prefixed, so `('ab', 'c')` and `('a', 'bc')` differ.
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use tiberius::{ColumnData, ColumnType, FromSql, Row};
+
+use crate::fingerprint::to_hex;
+use crate::type_mapping::DecodeOptions;
+
+const NULL: u8 = 0;
+const NUMBER: u8 = 1;
+const FLOAT: u8 = 2;
+const TEXT: u8 = 3;
+const BINARY: u8 = 4;
+const GUID: u8 = 5;
+const DATE: u8 = 6;
+const TIME: u8 = 7;
+const DATETIME: u8 = 8;
+const DATETIMEOFFSET: u8 = 9;
+
+fn write_len_prefixed(buf: &mut Vec<u8>, tag: u8, data: &[u8]) {
+    buf.push(tag);
+    buf.extend_from_slice(&(data.len() as u64).to_le_bytes());
+    buf.extend_from_slice(data);
+}
+
+fn write_number(buf: &mut Vec<u8>, mut value: i128, mut scale: u8) {
+    while scale > 0 && value % 10 == 0 {
+        value /= 10;
+        scale -= 1;
+    }
+    buf.push(NUMBER);
+    buf.extend_from_slice(&value.to_le_bytes());
+    buf.push(scale);
+}
+
+fn write_date(buf: &mut Vec<u8>, date: NaiveDate) {
+    buf.extend_from_slice(&date.num_days_from_ce().to_le_bytes());
+}
+
+fn write_time(buf: &mut Vec<u8>, time: NaiveTime) {
+    let nanos = u64::from(time.num_seconds_from_midnight()) * 1_000_000_000
+        + u64::from(time.nanosecond());
+    buf.extend_from_slice(&nanos.to_le_bytes());
+}
+
+fn write_datetime(buf: &mut Vec<u8>, tag: u8, value: NaiveDateTime) {
+    buf.push(tag);
+    write_date(buf, value.date());
+    write_time(buf, value.time());
+}
+
+fn write_float(buf: &mut Vec<u8>, value: f64) {
+    // -0.0 == 0.0, so both hash the same.
+    let value = if value == 0.0 { 0.0 } else { value };
+    buf.push(FLOAT);
+    buf.extend_from_slice(&value.to_bits().to_le_bytes());
+}
+
+/// Append the canonical form of one value; `rstrip` drops the padding of CHAR/NCHAR
+/// values, as decoding with `rstrip_char` does.
+fn write_value(buf: &mut Vec<u8>, data: &ColumnData<'static>, rstrip: bool) -> PyResult<()> {
+    let convert_err = |e: tiberius::error::Error| PyValueError::new_err(e.to_string());
+    match data {
+        ColumnData::U8(Some(v)) => write_number(buf, i128::from(*v), 0),
+        ColumnData::I16(Some(v)) => write_number(buf, i128::from(*v), 0),
+        ColumnData::I32(Some(v)) => write_number(buf, i128::from(*v), 0),
+        ColumnData::I64(Some(v)) => write_number(buf, i128::from(*v), 0),
+        ColumnData::Bit(Some(v)) => write_number(buf, i128::from(*v), 0),
+        ColumnData::Numeric(Some(n)) => write_number(buf, n.value(), n.scale()),
+        ColumnData::F32(Some(v)) => write_float(buf, f64::from(*v)),
+        ColumnData::F64(Some(v)) => write_float(buf, *v),
+        ColumnData::String(Some(s)) => {
+            let s = if rstrip { s.trim_end_matches(' ') } else { s };
+            write_len_prefixed(buf, TEXT, s.as_bytes())
+        }
+        ColumnData::Xml(Some(xml)) => {
+            let text: &str = (**xml).as_ref();
+            write_len_prefixed(buf, TEXT, text.as_bytes())
+        }
+        ColumnData::Binary(Some(b)) => write_len_prefixed(buf, BINARY, b),
+        ColumnData::Guid(Some(g)) => {
+            buf.push(GUID);
+            buf.extend_from_slice(g.as_bytes());
+        }
+        ColumnData::Date(Some(_)) => {
+            if let Some(date) = NaiveDate::from_sql(data).map_err(convert_err)? {
+                buf.push(DATE);
+                write_date(buf, date);
+            }
+        }
+        ColumnData::Time(Some(_)) => {
+            if let Some(time) = NaiveTime::from_sql(data).map_err(convert_err)? {
+                buf.push(TIME);
+                write_time(buf, time);
+            }
+        }
+        ColumnData::DateTime(Some(_))
+        | ColumnData::SmallDateTime(Some(_))
+        | ColumnData::DateTime2(Some(_)) => {
+            if let Some(value) = NaiveDateTime::from_sql(data).map_err(convert_err)? {
+                write_datetime(buf, DATETIME, value);
+            }
+        }
+        ColumnData::DateTimeOffset(Some(_)) => {
+            if let Some(value) =
+                chrono::DateTime::<chrono::Utc>::from_sql(data).map_err(convert_err)?
+            {
+                write_datetime(buf, DATETIMEOFFSET, value.naive_utc());
+            }
+        }
+        _ => buf.push(NULL),
+    }
+    Ok(())
+}
+
+/// Hash of the values at `indices` of `row`, in that order, as 16 hex digits.
+/// `buf` is scratch space reused across rows.
+pub fn row_hash(
+    row: &Row,
+    indices: &[usize],
+    column_types: &[ColumnType],
+    options: DecodeOptions,
+    buf: &mut Vec<u8>,
+) -> PyResult<String> {
+    buf.clear();
+    let cells: Vec<&ColumnData<'static>> = row.cells().map(|(_, data)| data).collect();
+    for &index in indices {
+        let rstrip = options.rstrip_char
+            && matches!(column_types[index], ColumnType::BigChar | ColumnType::NChar);
+        write_value(buf, cells[index], rstrip)?;
+    }
+    Ok(to_hex(buf))
+}
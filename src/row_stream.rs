@@ -0,0 +1,439 @@
+//! Incremental consumption of large results (`Connection.query_stream` and
+//! `Connection.fetch_chunks`).
+//!
+//! `query()` collects the whole first result set before returning.  Here the
+//! statement runs on a pooled connection owned by a background task that reads the
+//! TDS stream in batches and hands them over a bounded channel, so at most a couple
+//! of batches are held in memory however many rows the query returns.  The pooled
+//! connection stays checked out until the stream is exhausted or closed.
+//! `query_stream()` yields the rows one by one from small batches; `fetch_chunks()`
+//! yields each batch as a list, so the caller pays the async overhead once per chunk.
+//! `cancel()` stops the producer task mid-read; the statement is then cancelled on
+//! the server and its connection discarded (see [`crate::attention`]).  `aclose()`
+//! and dropping the iterator do the same when rows are left unread, so a connection
+//! never goes back to the pool with the rest of a result still on the wire.
+
+use futures_util::TryStreamExt;
+use pyo3::exceptions::{PyStopAsyncIteration, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyList;
+use pyo3_async_runtimes::tokio::future_into_py;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex as StdMutex};
+use tiberius::{Config, QueryItem, Row};
+use tokio::sync::{Mutex, RwLock, mpsc};
+use tokio::task::AbortHandle;
+
+use crate::azure_auth::PyAzureCredential;
+use crate::execution_id::tag_error;
+use crate::parameter_conversion::{FastParameter, params_as_sql_refs};
+use crate::pool_config::PyPoolConfig;
+use crate::pool_manager::{ConnectionPool, ensure_pool_initialized_with_auth};
+use crate::result_limits::{ResultLimits, is_limit_error};
+use crate::type_mapping::DecodeOptions;
+use crate::types::{ColumnInfo, PyFastRow, build_column_info, create_sql_error};
+
+/// Rows read from the server per channel message.
+const ROW_BATCH_SIZE: usize = 256;
+
+/// Everything the producer task needs; taken on the first `__anext__`.
+pub struct PendingRows {
+    pub pool: Arc<RwLock<Option<ConnectionPool>>>,
+    pub config: Arc<Config>,
+    pub pool_config: PyPoolConfig,
+    pub azure_credential: Option<Arc<PyAzureCredential>>,
+    pub sql: String,
+    pub parameters: Vec<FastParameter>,
+    pub limits: ResultLimits,
+}
+
+enum StreamState {
+    NotStarted(Box<PendingRows>),
+    Running(mpsc::Receiver<PyResult<Vec<Row>>>),
+    Finished,
+}
+
+struct RowStreamInner {
+    state: StreamState,
+    buffered: VecDeque<Row>,
+    column_info: Option<Arc<ColumnInfo>>,
+}
+
+/// The producer task, kept outside [`RowStreamInner`] so `cancel()` can reach it while
+/// an `__anext__` holds the iterator lock.
+#[derive(Default)]
+struct Producer {
+    task: Option<AbortHandle>,
+    cancelled: bool,
+}
+
+impl Producer {
+    fn cancel(&mut self) {
+        self.cancelled = true;
+        if let Some(task) = self.task.take() {
+            task.abort();
+        }
+    }
+}
+
+/// Async iterator over the rows of the first result set; each item is a `FastRow`.
+#[pyclass(name = "RowStream")]
+pub struct PyRowStream {
+    inner: Arc<Mutex<RowStreamInner>>,
+    producer: Arc<StdMutex<Producer>>,
+    decode_options: DecodeOptions,
+    execution_id: String,
+}
+
+/// Async iterator over the first result set in chunks; each item is a list of
+/// `FastRow`.
+#[pyclass(name = "ChunkStream")]
+pub struct PyChunkStream {
+    inner: Arc<Mutex<RowStreamInner>>,
+    producer: Arc<StdMutex<Producer>>,
+    decode_options: DecodeOptions,
+    execution_id: String,
+    chunk_size: usize,
+}
+
+fn new_inner(pending: PendingRows) -> Arc<Mutex<RowStreamInner>> {
+    Arc::new(Mutex::new(RowStreamInner {
+        state: StreamState::NotStarted(Box::new(pending)),
+        buffered: VecDeque::new(),
+        column_info: None,
+    }))
+}
+
+impl PyRowStream {
+    pub fn new(pending: PendingRows, decode_options: DecodeOptions, execution_id: String) -> Self {
+        PyRowStream {
+            inner: new_inner(pending),
+            producer: Arc::default(),
+            decode_options,
+            execution_id,
+        }
+    }
+}
+
+impl PyChunkStream {
+    pub fn new(
+        pending: PendingRows,
+        decode_options: DecodeOptions,
+        execution_id: String,
+        chunk_size: usize,
+    ) -> PyResult<Self> {
+        if chunk_size == 0 {
+            return Err(PyValueError::new_err("chunk_size must be at least 1"));
+        }
+        Ok(PyChunkStream {
+            inner: new_inner(pending),
+            producer: Arc::default(),
+            decode_options,
+            execution_id,
+            chunk_size,
+        })
+    }
+}
+
+/// Run the statement and send the rows of its first result set in batches of
+/// `batch_size`.  If the iterator goes away before the result is read, the
+/// connection is discarded rather than returned with the rest of it unread.
+async fn produce(
+    pending: PendingRows,
+    batch_size: usize,
+    sender: &mpsc::Sender<PyResult<Vec<Row>>>,
+) -> PyResult<()> {
+    let pool = ensure_pool_initialized_with_auth(
+        pending.pool,
+        pending.config,
+        &pending.pool_config,
+        pending.azure_credential,
+    )
+    .await?;
+    let mut conn = pool.checkout().await?;
+
+    let tiberius_params = params_as_sql_refs(&pending.parameters);
+    let limits = pending.limits;
+    let produced = conn
+        .request(async |client| {
+            let mut stream = client
+                .query(pending.sql.as_str(), &tiberius_params)
+                .await
+                .map_err(|e| create_sql_error(e, "Query execution failed"))?;
+
+            let mut result_sets = 0;
+            let mut rows_read = 0;
+            let mut batch = Vec::with_capacity(batch_size);
+            while let Some(item) = stream
+                .try_next()
+                .await
+                .map_err(|e| create_sql_error(e, "Failed to get results"))?
+            {
+                match item {
+                    QueryItem::Metadata(meta) => {
+                        result_sets += 1;
+                        if result_sets == 1 {
+                            limits.check_columns(meta.columns())?;
+                        } else if !batch.is_empty() {
+                            // Hand over the end of the first result set; later ones
+                            // are read to the end and dropped.
+                            let last = std::mem::take(&mut batch);
+                            if sender.send(Ok(last)).await.is_err() {
+                                return Ok(false);
+                            }
+                        }
+                    }
+                    QueryItem::Row(_) if result_sets > 1 => {}
+                    QueryItem::Row(row) => {
+                        limits.check_row(&row, rows_read)?;
+                        rows_read += 1;
+                        batch.push(row);
+                        if batch.len() == batch_size {
+                            let full =
+                                std::mem::replace(&mut batch, Vec::with_capacity(batch_size));
+                            if sender.send(Ok(full)).await.is_err() {
+                                // The iterator was dropped or closed.
+                                return Ok(false);
+                            }
+                        }
+                    }
+                }
+            }
+
+            if !batch.is_empty() {
+                let _ = sender.send(Ok(batch)).await;
+            }
+            Ok(true)
+        })
+        .await;
+    match produced {
+        Ok(true) => Ok(()),
+        Ok(false) => {
+            conn.mark_broken();
+            Ok(())
+        }
+        Err(e) => {
+            if is_limit_error(&e) {
+                conn.mark_broken();
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Receive the next batch, starting the producer on first use. None once the result
+/// is exhausted; errors finish the stream.
+async fn next_batch(
+    inner: &mut RowStreamInner,
+    producer: &StdMutex<Producer>,
+    batch_size: usize,
+) -> Option<PyResult<Vec<Row>>> {
+    if matches!(inner.state, StreamState::NotStarted(_)) {
+        let StreamState::NotStarted(pending) =
+            std::mem::replace(&mut inner.state, StreamState::Finished)
+        else {
+            unreachable!()
+        };
+        let Ok(mut producer) = producer.lock() else {
+            return None;
+        };
+        if producer.cancelled {
+            return None;
+        }
+        // One batch in the channel plus one being read keeps the server busy without
+        // buffering more than that.
+        let (sender, receiver) = mpsc::channel(1);
+        let task = tokio::spawn(async move {
+            if let Err(e) = produce(*pending, batch_size, &sender).await {
+                let _ = sender.send(Err(e)).await;
+            }
+        });
+        producer.task = Some(task.abort_handle());
+        inner.state = StreamState::Running(receiver);
+    }
+
+    let next = match &mut inner.state {
+        StreamState::Running(receiver) => receiver.recv().await,
+        _ => None,
+    };
+    if !matches!(next, Some(Ok(_))) {
+        inner.state = StreamState::Finished;
+    }
+    next
+}
+
+#[pymethods]
+impl PyRowStream {
+    pub fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    pub fn __anext__<'p>(&self, py: Python<'p>) -> PyResult<Bound<'p, PyAny>> {
+        let inner = self.inner.clone();
+        let producer = self.producer.clone();
+        let decode_options = self.decode_options;
+        let execution_id = self.execution_id.clone();
+
+        future_into_py(py, async move {
+            let mut inner = inner.lock().await;
+
+            while inner.buffered.is_empty() {
+                match next_batch(&mut inner, &producer, ROW_BATCH_SIZE).await {
+                    Some(Ok(rows)) => inner.buffered.extend(rows),
+                    Some(Err(e)) => return Err(tag_error(e, &execution_id)),
+                    None => return Err(PyStopAsyncIteration::new_err(())),
+                }
+            }
+
+            let row = inner.buffered.pop_front().expect("buffer is not empty");
+            let column_info = inner
+                .column_info
+                .get_or_insert_with(|| build_column_info(&row, decode_options))
+                .clone();
+            Python::attach(|py| -> PyResult<Py<PyAny>> {
+                let fast_row = PyFastRow::from_tiberius_row(row, py, column_info)?;
+                Ok(Py::new(py, fast_row)?.into_any())
+            })
+        })
+    }
+
+    /// Stop reading and release the connection; rows not yet fetched are discarded.
+    /// Use when leaving an `async for` loop early.  If rows were left unread the
+    /// statement is cancelled and the connection discarded, as with `cancel()`.
+    pub fn aclose<'p>(&self, py: Python<'p>) -> PyResult<Bound<'p, PyAny>> {
+        close(&self.inner, &self.producer, py)
+    }
+
+    /// Cancel the statement on the server, e.g. when nobody wants the rest of a long
+    /// scan. The connection is discarded rather than drained; iteration then ends.
+    pub fn cancel(&self) {
+        cancel(&self.producer);
+    }
+
+    /// ID of the `query_stream()` call, as attached to exceptions it raises
+    #[getter]
+    pub fn execution_id(&self) -> &str {
+        &self.execution_id
+    }
+}
+
+/// Discard unread rows and release the producer's pooled connection; see [`finish`].
+fn close<'p>(
+    inner: &Arc<Mutex<RowStreamInner>>,
+    producer: &Arc<StdMutex<Producer>>,
+    py: Python<'p>,
+) -> PyResult<Bound<'p, PyAny>> {
+    let inner = inner.clone();
+    let producer = producer.clone();
+    future_into_py(py, async move {
+        finish(inner, producer).await;
+        Ok(())
+    })
+}
+
+/// End the stream.  If rows of the result are left unread the producer is aborted,
+/// which cancels the statement and discards the connection.  Otherwise the producer
+/// is left to read the rest of the response, so the connection goes back to the
+/// pool; with nothing buffered that means waiting for its next message, as only the
+/// producer knows whether more rows are coming.
+async fn finish(inner: Arc<Mutex<RowStreamInner>>, producer: Arc<StdMutex<Producer>>) {
+    let mut inner = inner.lock().await;
+    let state = std::mem::replace(&mut inner.state, StreamState::Finished);
+    let unread = !inner.buffered.is_empty()
+        || match state {
+            StreamState::Running(mut receiver) => matches!(receiver.recv().await, Some(Ok(_))),
+            _ => false,
+        };
+    inner.buffered.clear();
+    if unread {
+        cancel(&producer);
+    }
+}
+
+/// Abort the producer task; dropping it mid-request cancels the statement.
+fn cancel(producer: &StdMutex<Producer>) {
+    if let Ok(mut producer) = producer.lock() {
+        producer.cancel();
+    }
+}
+
+/// [`finish`] a dropped stream in the background, if its producer is still running.
+fn finish_dropped(inner: &Arc<Mutex<RowStreamInner>>, producer: &Arc<StdMutex<Producer>>) {
+    let running = producer.lock().is_ok_and(|producer| {
+        producer
+            .task
+            .as_ref()
+            .is_some_and(|task| !task.is_finished())
+    });
+    if running {
+        pyo3_async_runtimes::tokio::get_runtime().spawn(finish(inner.clone(), producer.clone()));
+    }
+}
+
+impl Drop for PyRowStream {
+    fn drop(&mut self) {
+        finish_dropped(&self.inner, &self.producer);
+    }
+}
+
+impl Drop for PyChunkStream {
+    fn drop(&mut self) {
+        finish_dropped(&self.inner, &self.producer);
+    }
+}
+
+#[pymethods]
+impl PyChunkStream {
+    pub fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    pub fn __anext__<'p>(&self, py: Python<'p>) -> PyResult<Bound<'p, PyAny>> {
+        let inner = self.inner.clone();
+        let producer = self.producer.clone();
+        let decode_options = self.decode_options;
+        let execution_id = self.execution_id.clone();
+        let chunk_size = self.chunk_size;
+
+        future_into_py(py, async move {
+            let mut inner = inner.lock().await;
+
+            let rows = match next_batch(&mut inner, &producer, chunk_size).await {
+                Some(Ok(rows)) => rows,
+                Some(Err(e)) => return Err(tag_error(e, &execution_id)),
+                None => return Err(PyStopAsyncIteration::new_err(())),
+            };
+            let column_info = inner
+                .column_info
+                .get_or_insert_with(|| build_column_info(&rows[0], decode_options))
+                .clone();
+            Python::attach(|py| -> PyResult<Py<PyAny>> {
+                let chunk = PyList::empty(py);
+                for row in rows {
+                    let fast_row = PyFastRow::from_tiberius_row(row, py, column_info.clone())?;
+                    chunk.append(Py::new(py, fast_row)?)?;
+                }
+                Ok(chunk.into_any().unbind())
+            })
+        })
+    }
+
+    /// Stop reading and release the connection; chunks not yet fetched are discarded.
+    /// Use when leaving an `async for` loop early.  If rows were left unread the
+    /// statement is cancelled and the connection discarded, as with `cancel()`.
+    pub fn aclose<'p>(&self, py: Python<'p>) -> PyResult<Bound<'p, PyAny>> {
+        close(&self.inner, &self.producer, py)
+    }
+
+    /// Cancel the statement on the server, e.g. when nobody wants the rest of a long
+    /// scan. The connection is discarded rather than drained; iteration then ends.
+    pub fn cancel(&self) {
+        cancel(&self.producer);
+    }
+
+    /// ID of the `fetch_chunks()` call, as attached to exceptions it raises
+    #[getter]
+    pub fn execution_id(&self) -> &str {
+        &self.execution_id
+    }
+}
@@ -0,0 +1,394 @@
+//! Safe statement composition: `SQL`, `Identifier` and `Param`.
+//!
+//! `SQL("SELECT * FROM {table} WHERE id = {id}").format(table=Identifier("dbo.Users"),
+//! id=Param(5))` gives a `Composed` statement whose names are bracket-quoted and whose
+//! values are bound as `@P1..@Pn` parameters, never spliced into the text.  Only
+//! `SQL`, `Identifier`, `Param`/`Parameter` and other `Composed` objects can fill a
+//! placeholder, so a plain string cannot end up in a statement by accident.
+//! `Composed` unpacks into `(sql, params)` for `conn.query(*statement)`.
+
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyIterator, PyList, PyTuple};
+
+use crate::batch::{quote_identifier, quote_identifier_part};
+use crate::py_parameters::Parameter;
+
+#[derive(Clone, Debug)]
+enum Part {
+    Text(String),
+    /// Index into the statement's parameters.
+    Param(usize),
+}
+
+/// Parts and parameters of a statement being composed.
+#[derive(Default)]
+struct Builder {
+    parts: Vec<Part>,
+    params: Vec<Py<PyAny>>,
+}
+
+impl Builder {
+    fn push_text(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        match self.parts.last_mut() {
+            Some(Part::Text(last)) => last.push_str(text),
+            _ => self.parts.push(Part::Text(text.to_string())),
+        }
+    }
+
+    fn push_param(&mut self, value: Py<PyAny>) -> usize {
+        self.params.push(value);
+        let index = self.params.len() - 1;
+        self.parts.push(Part::Param(index));
+        index
+    }
+
+    /// Append a composable object; returns the parameter index when it was a value.
+    fn push(&mut self, value: &Bound<'_, PyAny>) -> PyResult<Option<usize>> {
+        if let Ok(sql) = value.cast::<PySql>() {
+            self.push_text(&sql.get().template);
+        } else if let Ok(identifier) = value.cast::<PyIdentifier>() {
+            self.push_text(&identifier.get().quoted);
+        } else if let Ok(param) = value.cast::<PyParam>() {
+            return Ok(Some(self.push_param(param.get().bound_value(value.py())?)));
+        } else if value.is_instance_of::<Parameter>() {
+            return Ok(Some(self.push_param(value.clone().unbind())));
+        } else if let Ok(composed) = value.cast::<PyComposed>() {
+            let composed = composed.get();
+            let offset = self.params.len();
+            self.params
+                .extend(composed.params.iter().map(|p| p.clone_ref(value.py())));
+            for part in &composed.parts {
+                match part {
+                    Part::Text(text) => self.push_text(text),
+                    Part::Param(index) => self.parts.push(Part::Param(offset + index)),
+                }
+            }
+        } else {
+            return Err(PyTypeError::new_err(format!(
+                "Cannot compose a '{}' into SQL: wrap values in Param() and names in Identifier()",
+                value.get_type().name()?
+            )));
+        }
+        Ok(None)
+    }
+
+    fn finish(self) -> PyComposed {
+        PyComposed {
+            parts: self.parts,
+            params: self.params,
+        }
+    }
+}
+
+/// A placeholder of a template: `{}`, `{0}` or `{name}`.
+enum Field<'a> {
+    Auto,
+    Index(usize),
+    Name(&'a str),
+}
+
+/// Split `template` into literal text and placeholders, undoubling `{{` and `}}`.
+fn parse_template(template: &str) -> PyResult<Vec<Result<String, Field<'_>>>> {
+    let mut pieces = Vec::new();
+    let mut text = String::new();
+    let mut rest = template;
+    while let Some(at) = rest.find(['{', '}']) {
+        text.push_str(&rest[..at]);
+        let brace = rest.as_bytes()[at];
+        if rest.as_bytes().get(at + 1) == Some(&brace) {
+            text.push(brace as char);
+            rest = &rest[at + 2..];
+            continue;
+        }
+        if brace == b'}' {
+            return Err(PyValueError::new_err(
+                "Single '}' in SQL template; write '}}' for a literal brace",
+            ));
+        }
+        let Some(len) = rest[at + 1..].find('}') else {
+            return Err(PyValueError::new_err(
+                "Unclosed '{' in SQL template; write '{{' for a literal brace",
+            ));
+        };
+        let name = &rest[at + 1..at + 1 + len];
+        let field = if name.is_empty() {
+            Field::Auto
+        } else if let Ok(index) = name.parse::<usize>() {
+            Field::Index(index)
+        } else if name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+            Field::Name(name)
+        } else {
+            return Err(PyValueError::new_err(format!(
+                "Invalid placeholder '{{{}}}' in SQL template: format specs and conversions are not supported",
+                name
+            )));
+        };
+        pieces.push(Ok(std::mem::take(&mut text)));
+        pieces.push(Err(field));
+        rest = &rest[at + 1 + len + 1..];
+    }
+    text.push_str(rest);
+    pieces.push(Ok(text));
+    Ok(pieces)
+}
+
+/// A trusted SQL fragment, and the template `format()` fills in.
+#[pyclass(name = "SQL", frozen)]
+pub struct PySql {
+    template: String,
+}
+
+#[pymethods]
+impl PySql {
+    #[new]
+    pub fn new(template: String) -> Self {
+        PySql { template }
+    }
+
+    /// Fill the template's `{}`, `{0}` and `{name}` placeholders.  The same
+    /// placeholder used twice binds its `Param` once.
+    #[pyo3(signature = (*args, **kwargs))]
+    pub fn format(
+        &self,
+        args: &Bound<'_, PyTuple>,
+        kwargs: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<PyComposed> {
+        let mut builder = Builder::default();
+        let mut next_auto = 0;
+        let mut numbering: Option<bool> = None;
+        let mut bound: Vec<(String, usize)> = Vec::new();
+
+        for piece in parse_template(&self.template)? {
+            let field = match piece {
+                Ok(text) => {
+                    builder.push_text(&text);
+                    continue;
+                }
+                Err(field) => field,
+            };
+            let (key, value) = match field {
+                Field::Auto | Field::Index(_) => {
+                    let auto = matches!(field, Field::Auto);
+                    if *numbering.get_or_insert(auto) != auto {
+                        return Err(PyValueError::new_err(
+                            "Cannot mix '{}' and '{0}' placeholders in one SQL template",
+                        ));
+                    }
+                    let index = match field {
+                        Field::Index(index) => index,
+                        _ => {
+                            next_auto += 1;
+                            next_auto - 1
+                        }
+                    };
+                    let value = args.get_item(index).map_err(|_| {
+                        PyValueError::new_err(format!(
+                            "SQL template has a placeholder {} but only {} positional arguments were given",
+                            index,
+                            args.len()
+                        ))
+                    })?;
+                    (index.to_string(), value)
+                }
+                Field::Name(name) => {
+                    let value = kwargs
+                        .and_then(|kwargs| kwargs.get_item(name).transpose())
+                        .transpose()?
+                        .ok_or_else(|| {
+                            PyValueError::new_err(format!(
+                                "No value given for placeholder '{{{}}}'",
+                                name
+                            ))
+                        })?;
+                    (name.to_string(), value)
+                }
+            };
+            if let Some((_, index)) = bound.iter().find(|(k, _)| *k == key) {
+                builder.parts.push(Part::Param(*index));
+            } else if let Some(index) = builder.push(&value)? {
+                bound.push((key, index));
+            }
+        }
+        Ok(builder.finish())
+    }
+
+    /// Join `items` with this fragment as the separator, e.g.
+    /// `SQL(", ").join([Identifier("id"), Identifier("name")])`.
+    pub fn join(&self, items: &Bound<'_, PyAny>) -> PyResult<PyComposed> {
+        let mut builder = Builder::default();
+        for (i, item) in items.try_iter()?.enumerate() {
+            if i > 0 {
+                builder.push_text(&self.template);
+            }
+            builder.push(&item?)?;
+        }
+        Ok(builder.finish())
+    }
+
+    /// The fragment as written
+    #[getter]
+    pub fn string(&self) -> &str {
+        &self.template
+    }
+
+    fn __add__(slf: &Bound<'_, Self>, other: &Bound<'_, PyAny>) -> PyResult<PyComposed> {
+        concat(slf.as_any(), other)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("SQL({:?})", self.template)
+    }
+}
+
+/// A table, column or other name, bracket-quoted.  One argument is split on dots
+/// (`Identifier("dbo.Users")` is `[dbo].[Users]`); several arguments are the parts
+/// of a multipart name, taken literally (`Identifier("dbo", "odd.name")`).
+#[pyclass(name = "Identifier", frozen)]
+pub struct PyIdentifier {
+    quoted: String,
+}
+
+#[pymethods]
+impl PyIdentifier {
+    #[new]
+    #[pyo3(signature = (*parts))]
+    pub fn new(parts: Vec<String>) -> PyResult<Self> {
+        if parts.is_empty() || parts.iter().any(|part| part.is_empty()) {
+            return Err(PyValueError::new_err(
+                "Identifier needs at least one non-empty name",
+            ));
+        }
+        let quoted = if let [name] = parts.as_slice() {
+            quote_identifier(name)?
+        } else {
+            parts
+                .iter()
+                .map(|part| quote_identifier_part(part))
+                .collect::<PyResult<Vec<_>>>()?
+                .join(".")
+        };
+        Ok(PyIdentifier { quoted })
+    }
+
+    /// The quoted name, e.g. `[dbo].[Users]`
+    #[getter]
+    pub fn string(&self) -> &str {
+        &self.quoted
+    }
+
+    fn __add__(slf: &Bound<'_, Self>, other: &Bound<'_, PyAny>) -> PyResult<PyComposed> {
+        concat(slf.as_any(), other)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Identifier({:?})", self.quoted)
+    }
+}
+
+/// A value bound as a statement parameter, optionally with an explicit SQL type as
+/// `Parameter` takes it.
+#[pyclass(name = "Param", frozen)]
+pub struct PyParam {
+    #[pyo3(get)]
+    value: Py<PyAny>,
+    #[pyo3(get)]
+    sql_type: Option<String>,
+}
+
+impl PyParam {
+    fn bound_value(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        match &self.sql_type {
+            None => Ok(self.value.clone_ref(py)),
+            Some(sql_type) => Ok(Py::new(
+                py,
+                Parameter::new(self.value.clone_ref(py), Some(sql_type.clone())),
+            )?
+            .into_any()),
+        }
+    }
+}
+
+#[pymethods]
+impl PyParam {
+    #[new]
+    #[pyo3(signature = (value, sql_type=None))]
+    pub fn new(value: Py<PyAny>, sql_type: Option<String>) -> Self {
+        PyParam { value, sql_type }
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        let value = self.value.bind(py).repr()?;
+        Ok(match &self.sql_type {
+            Some(sql_type) => format!("Param({}, sql_type={:?})", value, sql_type),
+            None => format!("Param({})", value),
+        })
+    }
+}
+
+/// A composed statement: `sql` with `@P1..@Pn` placeholders and the `params` they bind.
+#[pyclass(name = "Composed", frozen)]
+pub struct PyComposed {
+    parts: Vec<Part>,
+    params: Vec<Py<PyAny>>,
+}
+
+#[pymethods]
+impl PyComposed {
+    /// Statement text with `@P1..@Pn` placeholders
+    #[getter]
+    pub fn sql(&self) -> String {
+        let mut sql = String::new();
+        for part in &self.parts {
+            match part {
+                Part::Text(text) => sql.push_str(text),
+                Part::Param(index) => sql.push_str(&format!("@P{}", index + 1)),
+            }
+        }
+        sql
+    }
+
+    /// Values for the placeholders, in order
+    #[getter]
+    pub fn params<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyList>> {
+        PyList::new(py, self.params.iter().map(|p| p.bind(py)))
+    }
+
+    /// `sql, params = composed`, or `conn.query(*composed)`.
+    fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyIterator>> {
+        PyTuple::new(
+            py,
+            [
+                self.sql().into_pyobject(py)?.into_any(),
+                self.params(py)?.into_any(),
+            ],
+        )?
+        .try_iter()
+    }
+
+    fn __add__(slf: &Bound<'_, Self>, other: &Bound<'_, PyAny>) -> PyResult<PyComposed> {
+        concat(slf.as_any(), other)
+    }
+
+    fn __str__(&self) -> String {
+        self.sql()
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        Ok(format!(
+            "Composed(sql={:?}, params={})",
+            self.sql(),
+            self.params(py)?.repr()?
+        ))
+    }
+}
+
+fn concat(left: &Bound<'_, PyAny>, right: &Bound<'_, PyAny>) -> PyResult<PyComposed> {
+    let mut builder = Builder::default();
+    builder.push(left)?;
+    builder.push(right)?;
+    Ok(builder.finish())
+}
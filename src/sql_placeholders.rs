@@ -0,0 +1,608 @@
+//! Placeholder-aware scanning of T-SQL text.
+//!
+//! Several features splice user statements together or rewrite their `@Pn`
+//! placeholders.  Doing that with a plain string replace would also touch text
+//! inside string literals, quoted identifiers and comments, so this module walks
+//! the SQL with a small lexer that only reports placeholders found in code.  The
+//! same lexer translates pyodbc-style `?` and pymssql-style `%s` placeholders.
+
+/// Length of the non-code region (string literal, quoted identifier or comment)
+/// starting at `bytes[i]`, or `0` if `bytes[i]` starts ordinary code.
+///
+/// Unterminated regions extend to the end of the input; SQL Server will reject
+/// the statement anyway, and treating the tail as opaque is the conservative
+/// choice for a rewriter.
+fn skip_non_code(bytes: &[u8], i: usize) -> usize {
+    let rest = &bytes[i..];
+    match rest[0] {
+        // '...' and N'...' literals, "..." and [...] identifiers. Doubling the
+        // closing character escapes it.
+        open @ (b'\'' | b'"' | b'[') => {
+            let close = if open == b'[' { b']' } else { open };
+            let mut j = 1;
+            while j < rest.len() {
+                if rest[j] == close {
+                    if rest.get(j + 1) == Some(&close) {
+                        j += 2;
+                        continue;
+                    }
+                    return j + 1;
+                }
+                j += 1;
+            }
+            rest.len()
+        }
+        b'-' if rest.get(1) == Some(&b'-') => rest
+            .iter()
+            .position(|&b| b == b'\n')
+            .map_or(rest.len(), |p| p + 1),
+        // T-SQL block comments nest.
+        b'/' if rest.get(1) == Some(&b'*') => {
+            let mut depth = 0usize;
+            let mut j = 0;
+            while j + 1 < rest.len() {
+                match (rest[j], rest[j + 1]) {
+                    (b'/', b'*') => {
+                        depth += 1;
+                        j += 2;
+                    }
+                    (b'*', b'/') => {
+                        depth -= 1;
+                        j += 2;
+                        if depth == 0 {
+                            return j;
+                        }
+                    }
+                    _ => j += 1,
+                }
+            }
+            rest.len()
+        }
+        _ => 0,
+    }
+}
+
+#[inline]
+fn is_identifier_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || matches!(b, b'_' | b'@' | b'#' | b'$') || b >= 0x80
+}
+
+/// Rewrites every `@Pn` placeholder in `sql` to `@P{n + offset}`.
+///
+/// Placeholders inside literals, quoted identifiers and comments are left alone, as
+/// are longer variable names that merely start with `@P<digits>` (e.g. `@P1x`).
+/// Returns an error naming the placeholder if `n` is outside `1..=param_count`,
+/// because after renumbering an out-of-range reference would silently bind to a
+/// neighbouring statement's parameter instead of failing.
+pub fn renumber_placeholders(
+    sql: &str,
+    offset: usize,
+    param_count: usize,
+) -> Result<String, String> {
+    let bytes = sql.as_bytes();
+    let mut out = String::with_capacity(sql.len() + 8);
+    let mut copied = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let skip = skip_non_code(bytes, i);
+        if skip > 0 {
+            i += skip;
+            continue;
+        }
+
+        let b = bytes[i];
+        let at_boundary = i == 0 || !is_identifier_byte(bytes[i - 1]);
+        if b == b'@'
+            && at_boundary
+            && matches!(bytes.get(i + 1), Some(b'P' | b'p'))
+            && bytes.get(i + 2).is_some_and(u8::is_ascii_digit)
+        {
+            let digits_start = i + 2;
+            let mut end = digits_start;
+            while end < bytes.len() && bytes[end].is_ascii_digit() {
+                end += 1;
+            }
+            if end < bytes.len() && is_identifier_byte(bytes[end]) {
+                // A longer name such as @P1x or @P1_total, not a placeholder.
+                i = end;
+                continue;
+            }
+
+            let placeholder = &sql[i..end];
+            let n: usize = sql[digits_start..end].parse().unwrap_or(0);
+            if n == 0 || n > param_count {
+                return Err(format!(
+                    "placeholder {} does not match any of the {} parameter(s) supplied",
+                    placeholder, param_count
+                ));
+            }
+
+            out.push_str(&sql[copied..i]);
+            out.push_str("@P");
+            out.push_str(&(n + offset).to_string());
+            copied = end;
+            i = end;
+            continue;
+        }
+
+        i += 1;
+    }
+
+    out.push_str(&sql[copied..]);
+    Ok(out)
+}
+
+/// Placeholder syntax of the statements a `Connection` runs, named as in DB-API
+/// (PEP 249): `@P1, @P2, ...` natively, `?` as in pyodbc, or `%s` as in pymssql.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ParamStyle {
+    #[default]
+    Native,
+    Qmark,
+    Format,
+}
+
+impl ParamStyle {
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "native" => Ok(ParamStyle::Native),
+            "qmark" => Ok(ParamStyle::Qmark),
+            "format" => Ok(ParamStyle::Format),
+            other => Err(format!(
+                "Invalid paramstyle '{}'; expected 'native', 'qmark' or 'format'",
+                other
+            )),
+        }
+    }
+}
+
+/// Rewrites `?` (qmark) or `%s`/`%d` (format) placeholders in `sql` to `@P1..@Pn`,
+/// numbered in order of appearance.  In format style `%%` stands for a literal `%`,
+/// in code and in string literals alike, as it does with pymssql.
+///
+/// Placeholders inside literals, quoted identifiers and comments are left alone.
+/// Returns an error if the number of placeholders differs from `param_count`.
+pub fn translate_placeholders(
+    sql: &str,
+    style: ParamStyle,
+    param_count: usize,
+) -> Result<String, String> {
+    if style == ParamStyle::Native {
+        return Ok(sql.to_string());
+    }
+    let bytes = sql.as_bytes();
+    let mut out = String::with_capacity(sql.len() + param_count * 2);
+    let mut placeholders = 0;
+    let mut copied = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let skip = skip_non_code(bytes, i);
+        if skip > 0 {
+            if style == ParamStyle::Format && matches!(bytes[i], b'\'' | b'"' | b'[') {
+                out.push_str(&sql[copied..i]);
+                out.push_str(&sql[i..i + skip].replace("%%", "%"));
+                copied = i + skip;
+            }
+            i += skip;
+            continue;
+        }
+
+        let len = match (style, bytes[i], bytes.get(i + 1)) {
+            (ParamStyle::Qmark, b'?', _) => 1,
+            (ParamStyle::Format, b'%', Some(b's' | b'd')) => 2,
+            (ParamStyle::Format, b'%', Some(b'%')) => {
+                out.push_str(&sql[copied..=i]);
+                copied = i + 2;
+                i += 2;
+                continue;
+            }
+            _ => {
+                i += 1;
+                continue;
+            }
+        };
+        placeholders += 1;
+        out.push_str(&sql[copied..i]);
+        out.push_str("@P");
+        out.push_str(&placeholders.to_string());
+        copied = i + len;
+        i += len;
+    }
+
+    if placeholders != param_count {
+        let placeholder = if style == ParamStyle::Qmark {
+            "?"
+        } else {
+            "%s"
+        };
+        return Err(format!(
+            "statement has {} '{}' placeholder(s) but {} parameter(s) were supplied",
+            placeholders, placeholder, param_count
+        ));
+    }
+    out.push_str(&sql[copied..]);
+    Ok(out)
+}
+
+/// Rewrites `IN (@Pn)` to `IN (SELECT value FROM OPENJSON(@Pn))` for each `n` in
+/// `lists`, the placeholders bound to a JSON array, so the predicate matches the
+/// array's elements rather than its text.  `NOT IN` is rewritten the same way.
+///
+/// Only an `IN` whose parentheses hold the lone placeholder is rewritten; other uses
+/// of the placeholder (an explicit `OPENJSON(@Pn) WITH (...)`, say) are left alone.
+pub fn rewrite_json_in_lists(sql: &str, lists: &[usize]) -> String {
+    let bytes = sql.as_bytes();
+    let skip_space = |mut j: usize| {
+        while j < bytes.len() && bytes[j].is_ascii_whitespace() {
+            j += 1;
+        }
+        j
+    };
+    let mut out = String::with_capacity(sql.len() + lists.len() * 32);
+    let mut copied = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let skip = skip_non_code(bytes, i);
+        if skip > 0 {
+            i += skip;
+            continue;
+        }
+
+        let at_boundary = i == 0 || !is_identifier_byte(bytes[i - 1]);
+        let is_in = at_boundary
+            && bytes[i..].len() >= 2
+            && bytes[i..i + 2].eq_ignore_ascii_case(b"in")
+            && !bytes.get(i + 2).is_some_and(|&b| is_identifier_byte(b));
+        if !is_in {
+            i += 1;
+            continue;
+        }
+
+        let open = skip_space(i + 2);
+        let start = skip_space(open + 1);
+        let mut end = start + 2;
+        while end < bytes.len() && bytes[end].is_ascii_digit() {
+            end += 1;
+        }
+        let close = skip_space(end);
+        let rewritten = bytes.get(open) == Some(&b'(')
+            && bytes.get(start) == Some(&b'@')
+            && matches!(bytes.get(start + 1), Some(b'P' | b'p'))
+            && bytes.get(close) == Some(&b')')
+            && sql[start + 2..end]
+                .parse::<usize>()
+                .is_ok_and(|n| lists.contains(&n));
+        if rewritten {
+            out.push_str(&sql[copied..open]);
+            out.push_str("(SELECT value FROM OPENJSON(");
+            out.push_str(&sql[start..end]);
+            out.push_str("))");
+            copied = close + 1;
+            i = close + 1;
+        } else {
+            i += 2;
+        }
+    }
+
+    out.push_str(&sql[copied..]);
+    out
+}
+
+#[inline]
+fn is_placeholder(word: &str) -> bool {
+    let bytes = word.as_bytes();
+    bytes.len() > 2
+        && bytes[0] == b'@'
+        && matches!(bytes[1], b'P' | b'p')
+        && bytes[2..].iter().all(u8::is_ascii_digit)
+}
+
+/// Length of the numeric literal (decimal, exponent or `0x` hex) at `bytes[i]`.
+fn numeric_literal_len(bytes: &[u8], i: usize) -> usize {
+    let mut end = i;
+    if bytes[i] == b'0' && matches!(bytes.get(i + 1), Some(b'x' | b'X')) {
+        end += 2;
+        while end < bytes.len() && bytes[end].is_ascii_hexdigit() {
+            end += 1;
+        }
+        return end - i;
+    }
+    while end < bytes.len() && (bytes[end].is_ascii_digit() || bytes[end] == b'.') {
+        end += 1;
+    }
+    if end < bytes.len() && matches!(bytes[end], b'e' | b'E') {
+        end += 1;
+        if matches!(bytes.get(end), Some(b'+' | b'-')) {
+            end += 1;
+        }
+        while end < bytes.len() && bytes[end].is_ascii_digit() {
+            end += 1;
+        }
+    }
+    end - i
+}
+
+/// Canonical text of a statement for grouping: comments dropped, literals and `@Pn`
+/// placeholders replaced by `?`, comma-separated runs of `?` (IN lists, VALUES rows)
+/// collapsed to one, words and quoted identifiers lower-cased, and tokens separated
+/// by single spaces.  Statements that differ only in literal values, list length,
+/// case or layout normalise to the same text.
+pub fn normalize_statement(sql: &str) -> String {
+    let bytes = sql.as_bytes();
+    let mut tokens: Vec<String> = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let b = bytes[i];
+        let at_boundary = i == 0 || !is_identifier_byte(bytes[i - 1]);
+
+        // The N of an N'...' literal belongs to the literal.
+        if matches!(b, b'N' | b'n') && at_boundary && bytes.get(i + 1) == Some(&b'\'') {
+            i += 1;
+            continue;
+        }
+
+        let skip = skip_non_code(bytes, i);
+        if skip > 0 {
+            match b {
+                b'\'' => tokens.push("?".to_string()),
+                b'"' | b'[' => {
+                    let close = if b == b'[' { b']' } else { b'"' };
+                    let end = i + skip;
+                    let inner_end = if skip > 1 && bytes[end - 1] == close {
+                        end - 1
+                    } else {
+                        end
+                    };
+                    tokens.push(format!("[{}]", sql[i + 1..inner_end].to_lowercase()));
+                }
+                _ => {}
+            }
+            i += skip;
+            continue;
+        }
+
+        if b.is_ascii_whitespace() {
+            i += 1;
+        } else if at_boundary
+            && (b.is_ascii_digit()
+                || (b == b'.' && bytes.get(i + 1).is_some_and(u8::is_ascii_digit)))
+        {
+            tokens.push("?".to_string());
+            i += numeric_literal_len(bytes, i);
+        } else if is_identifier_byte(b) {
+            let mut end = i;
+            while end < bytes.len() && is_identifier_byte(bytes[end]) {
+                end += 1;
+            }
+            let word = &sql[i..end];
+            tokens.push(if is_placeholder(word) {
+                "?".to_string()
+            } else {
+                word.to_lowercase()
+            });
+            i = end;
+        } else {
+            tokens.push((b as char).to_string());
+            i += 1;
+        }
+    }
+
+    let mut out: Vec<String> = Vec::with_capacity(tokens.len());
+    for token in tokens {
+        let n = out.len();
+        if token == "?" && n >= 2 && out[n - 1] == "," && out[n - 2] == "?" {
+            out.pop();
+            continue;
+        }
+        out.push(token);
+    }
+    while out.last().is_some_and(|t| t == ";") {
+        out.pop();
+    }
+    out.join(" ")
+}
+
+/// Words that start a new statement, ending the search for the WHERE clause of the
+/// DELETE or UPDATE before them.
+/// SET, ELSE and END are left out as they also appear inside UPDATE and CASE.
+const STATEMENT_STARTS: &[&str] = &[
+    "select", "insert", "update", "delete", "merge", "exec", "execute", "create", "alter", "drop",
+    "truncate", "declare", "if", "while", "begin", "commit", "rollback", "return", "print",
+    "throw", "go",
+];
+
+/// Words after which DELETE and UPDATE name an action rather than start a statement:
+/// `ON DELETE CASCADE`, `AFTER UPDATE`, `FOR DELETE`, `INSTEAD OF UPDATE`,
+/// `GRANT DELETE`, `WHEN MATCHED THEN DELETE` and permission lists.
+const ACTION_PREFIXES: &[&str] = &[
+    "on", "after", "for", "of", "grant", "deny", "revoke", "then", ",",
+];
+
+/// The first DELETE or UPDATE statement in `sql` that has no WHERE clause, as
+/// `"DELETE"` or `"UPDATE"`.
+///
+/// This is a lexical check: the WHERE must appear at the same parenthesis depth
+/// before the next statement starts (`;` or a word such as SELECT or DECLARE), so a
+/// WHERE inside a subquery does not count.  Literals, quoted identifiers and comments
+/// are skipped.  `UPDATE(column)` in triggers and `UPDATE STATISTICS` are not
+/// treated as statements.
+pub fn unguarded_modification(sql: &str) -> Option<&'static str> {
+    let bytes = sql.as_bytes();
+    let mut pending: Option<(&'static str, usize)> = None;
+    let mut previous = String::new();
+    let mut depth = 0usize;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let b = bytes[i];
+        let skip = skip_non_code(bytes, i);
+        if skip > 0 {
+            if !matches!(b, b'-' | b'/') {
+                previous.clear();
+            }
+            i += skip;
+            continue;
+        }
+        if b.is_ascii_whitespace() {
+            i += 1;
+            continue;
+        }
+        if !is_identifier_byte(b) {
+            match b {
+                b'(' => depth += 1,
+                b')' => depth = depth.saturating_sub(1),
+                b';' if pending.is_some_and(|(_, d)| d >= depth) => {
+                    return pending.map(|(verb, _)| verb);
+                }
+                _ => {}
+            }
+            previous = (b as char).to_string();
+            i += 1;
+            continue;
+        }
+
+        let mut end = i;
+        while end < bytes.len() && is_identifier_byte(bytes[end]) {
+            end += 1;
+        }
+        let word = sql[i..end].to_ascii_lowercase();
+        if let Some((verb, at)) = pending
+            && depth == at
+        {
+            if word == "where" {
+                pending = None;
+            } else if STATEMENT_STARTS.contains(&word.as_str()) {
+                return Some(verb);
+            }
+        }
+        if pending.is_none() && matches!(word.as_str(), "delete" | "update") {
+            let next = next_code_word(sql, end);
+            let is_statement = !ACTION_PREFIXES.contains(&previous.as_str())
+                && !next.starts_with('(')
+                && !next.eq_ignore_ascii_case("statistics");
+            if is_statement {
+                pending = Some((if word == "delete" { "DELETE" } else { "UPDATE" }, depth));
+            }
+        }
+        previous = word;
+        i = end;
+    }
+    pending.map(|(verb, _)| verb)
+}
+
+/// The word (or single punctuation character) of code that follows `sql[from..]`.
+fn next_code_word(sql: &str, from: usize) -> &str {
+    let bytes = sql.as_bytes();
+    let mut i = from;
+    while i < bytes.len() {
+        let skip = skip_non_code(bytes, i);
+        if skip > 0 {
+            i += skip;
+        } else if bytes[i].is_ascii_whitespace() {
+            i += 1;
+        } else {
+            break;
+        }
+    }
+    let mut end = i;
+    while end < bytes.len() && is_identifier_byte(bytes[end]) {
+        end += 1;
+    }
+    if end == i && i < bytes.len() {
+        end = i + 1;
+    }
+    &sql[i..end]
+}
+
+/// Splits a script into the batches separated by `GO` lines, as sqlcmd and SSMS do,
+/// each with its repeat count.
+///
+/// A separator is a line holding only `GO` (in any case), optionally followed by a
+/// count (`GO 5`) and a `--` comment.  `GO` inside literals, quoted identifiers and
+/// block comments does not separate batches.  Batches holding nothing but whitespace
+/// and comments are dropped.
+pub fn split_go_batches(script: &str) -> Vec<(&str, u32)> {
+    let bytes = script.as_bytes();
+    let mut batches = Vec::new();
+    let mut start = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if i == 0 || bytes[i - 1] == b'\n' {
+            let line_end = bytes[i..]
+                .iter()
+                .position(|&b| b == b'\n')
+                .map_or(bytes.len(), |p| i + p + 1);
+            if let Some(count) = go_separator(&script[i..line_end]) {
+                push_batch(&mut batches, &script[start..i], count);
+                start = line_end;
+                i = line_end;
+                continue;
+            }
+        }
+        i += skip_non_code(bytes, i).max(1);
+    }
+    push_batch(&mut batches, &script[start..], 1);
+    batches
+}
+
+/// The repeat count of a `GO` separator line, or `None` if `line` is not one.
+fn go_separator(line: &str) -> Option<u32> {
+    let line = line.trim();
+    let line = line
+        .split_once("--")
+        .map_or(line, |(code, _)| code.trim_end());
+    let rest = line
+        .get(..2)
+        .filter(|word| word.eq_ignore_ascii_case("go"))
+        .map(|_| &line[2..])?;
+    if rest.is_empty() {
+        return Some(1);
+    }
+    if !rest.starts_with(|c: char| c.is_ascii_whitespace()) {
+        return None;
+    }
+    rest.trim_start().parse::<u32>().ok().filter(|&n| n > 0)
+}
+
+fn push_batch<'a>(batches: &mut Vec<(&'a str, u32)>, sql: &'a str, count: u32) {
+    let bytes = sql.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i].is_ascii_whitespace() {
+            i += 1;
+        } else if bytes[i..].starts_with(b"--") || bytes[i..].starts_with(b"/*") {
+            i += skip_non_code(bytes, i);
+        } else {
+            batches.push((sql, count));
+            return;
+        }
+    }
+}
+
+/// The words of `sql` outside literals, quoted identifiers and comments, in order.
+pub fn code_words(sql: &str) -> Vec<&str> {
+    let bytes = sql.as_bytes();
+    let mut words = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let skip = skip_non_code(bytes, i);
+        if skip > 0 {
+            i += skip;
+        } else if is_identifier_byte(bytes[i]) {
+            let mut end = i;
+            while end < bytes.len() && is_identifier_byte(bytes[end]) {
+                end += 1;
+            }
+            words.push(&sql[i..end]);
+            i = end;
+        } else {
+            i += 1;
+        }
+    }
+    words
+}
@@ -0,0 +1,219 @@
+//! Guardrails for `Connection(statement_policy=...)`.
+//!
+//! Every statement a `Connection` (or a `PinnedConnection` or `Transaction` acquired
+//! from it) is asked to run is checked before it is sent: it must match no `deny` pattern, must match an
+//! `allow` pattern when any are given, and with `require_where` every DELETE and
+//! UPDATE needs a WHERE clause.  A `check` callable has the last word.  A rejected
+//! statement raises `PolicyViolation` and never reaches the server.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyList, PyString};
+use std::sync::Arc;
+
+use crate::sql_placeholders::unguarded_modification;
+use crate::types::PolicyViolation;
+
+/// A compiled `re` pattern and the text it was written as.
+struct Rule {
+    pattern: Py<PyAny>,
+    source: String,
+}
+
+impl Rule {
+    fn matches(&self, py: Python<'_>, sql: &str) -> PyResult<bool> {
+        Ok(!self.pattern.call_method1(py, "search", (sql,))?.is_none(py))
+    }
+}
+
+/// Strings are compiled case-insensitively; compiled patterns are used as given.
+fn parse_rules(
+    py: Python<'_>,
+    rules: Option<&Bound<'_, PyAny>>,
+    name: &str,
+) -> PyResult<Vec<Rule>> {
+    let Some(rules) = rules else {
+        return Ok(Vec::new());
+    };
+    if rules.is_instance_of::<PyString>() {
+        return Err(PyValueError::new_err(format!(
+            "{} expects a list of patterns, not a single string",
+            name
+        )));
+    }
+    let re = py.import("re")?;
+    let mut parsed = Vec::new();
+    for item in rules.try_iter()? {
+        let item = item?;
+        let (pattern, source) = if item.is_instance_of::<PyString>() {
+            let source = item.extract::<String>()?;
+            let flags = re.getattr("IGNORECASE")?;
+            (re.call_method1("compile", (&item, flags))?, source)
+        } else if item.hasattr("search")? && item.hasattr("pattern")? {
+            let source = item.getattr("pattern")?.str()?.to_string();
+            (item, source)
+        } else {
+            return Err(PyValueError::new_err(format!(
+                "{} entries must be regular expressions (str or re.Pattern)",
+                name
+            )));
+        };
+        parsed.push(Rule {
+            pattern: pattern.unbind(),
+            source,
+        });
+    }
+    Ok(parsed)
+}
+
+/// Rules checked before a connection runs a statement.
+#[pyclass(name = "StatementPolicy", frozen)]
+pub struct PyStatementPolicy {
+    deny: Vec<Rule>,
+    allow: Vec<Rule>,
+    require_where: bool,
+    check: Option<Py<PyAny>>,
+}
+
+impl PyStatementPolicy {
+    /// Raise `PolicyViolation` unless `sql` passes every rule.
+    pub fn enforce(&self, py: Python<'_>, sql: &str) -> PyResult<()> {
+        for rule in &self.deny {
+            if rule.matches(py, sql)? {
+                return Err(PolicyViolation::new_err(format!(
+                    "Statement blocked by deny rule '{}'",
+                    rule.source
+                )));
+            }
+        }
+        if !self.allow.is_empty() && !self.allow_matches(py, sql)? {
+            return Err(PolicyViolation::new_err(
+                "Statement matches none of the allow rules",
+            ));
+        }
+        if self.require_where
+            && let Some(verb) = unguarded_modification(sql)
+        {
+            return Err(PolicyViolation::new_err(format!(
+                "{} without a WHERE clause blocked by the statement policy",
+                verb
+            )));
+        }
+        if let Some(check) = &self.check {
+            let verdict = check.bind(py).call1((sql,))?;
+            if verdict.is_instance_of::<PyString>() {
+                return Err(PolicyViolation::new_err(verdict.extract::<String>()?));
+            }
+            if verdict.is_instance_of::<PyBool>() && !verdict.is_truthy()? {
+                return Err(PolicyViolation::new_err(
+                    "Statement rejected by the statement policy check",
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check the SQL of each `(sql, parameters)` item of a batch.
+    pub fn enforce_batch(&self, py: Python<'_>, items: &Bound<'_, PyList>) -> PyResult<()> {
+        for item in items.iter() {
+            if let Ok(sql) = item.get_item(0).and_then(|sql| sql.extract::<String>()) {
+                self.enforce(py, &sql)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn allow_matches(&self, py: Python<'_>, sql: &str) -> PyResult<bool> {
+        for rule in &self.allow {
+            if rule.matches(py, sql)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
+
+#[pymethods]
+impl PyStatementPolicy {
+    #[new]
+    #[pyo3(signature = (deny = None, allow = None, *, require_where = false, check = None))]
+    pub fn new(
+        py: Python<'_>,
+        deny: Option<&Bound<'_, PyAny>>,
+        allow: Option<&Bound<'_, PyAny>>,
+        require_where: bool,
+        check: Option<Bound<'_, PyAny>>,
+    ) -> PyResult<Self> {
+        if let Some(check) = &check
+            && !check.is_callable()
+        {
+            return Err(PyValueError::new_err("check must be callable"));
+        }
+        Ok(PyStatementPolicy {
+            deny: parse_rules(py, deny, "deny")?,
+            allow: parse_rules(py, allow, "allow")?,
+            require_where,
+            check: check.map(Bound::unbind),
+        })
+    }
+
+    /// Patterns that block a statement they match
+    #[getter]
+    pub fn deny<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyList>> {
+        PyList::new(py, self.deny.iter().map(|rule| rule.source.as_str()))
+    }
+
+    /// Patterns of which a statement must match one, when any are given
+    #[getter]
+    pub fn allow<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyList>> {
+        PyList::new(py, self.allow.iter().map(|rule| rule.source.as_str()))
+    }
+
+    /// Whether DELETE and UPDATE statements need a WHERE clause
+    #[getter]
+    pub fn require_where(&self) -> bool {
+        self.require_where
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "StatementPolicy(deny={:?}, allow={:?}, require_where={}, check={})",
+            self.deny.iter().map(|r| &r.source).collect::<Vec<_>>(),
+            self.allow.iter().map(|r| &r.source).collect::<Vec<_>>(),
+            self.require_where,
+            if self.check.is_some() {
+                "<callable>"
+            } else {
+                "None"
+            }
+        )
+    }
+}
+
+/// Read the `statement_policy` argument: a `StatementPolicy`, or a bare callable used
+/// as its `check`.
+pub fn statement_policy_option(
+    value: Option<&Bound<'_, PyAny>>,
+) -> PyResult<Option<Arc<Py<PyStatementPolicy>>>> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    if value.is_none() {
+        return Ok(None);
+    }
+    if let Ok(policy) = value.cast::<PyStatementPolicy>() {
+        return Ok(Some(Arc::new(policy.clone().unbind())));
+    }
+    if value.is_callable() {
+        let policy = PyStatementPolicy {
+            deny: Vec::new(),
+            allow: Vec::new(),
+            require_where: false,
+            check: Some(value.clone().unbind()),
+        };
+        return Ok(Some(Arc::new(Py::new(value.py(), policy)?)));
+    }
+    Err(PyValueError::new_err(
+        "statement_policy must be a StatementPolicy or a callable",
+    ))
+}
@@ -0,0 +1,110 @@
+//! Dual-stack TCP connects ("happy eyeballs", RFC 8305).
+//!
+//! `TcpStream::connect(host:port)` tries the resolved addresses one after another, so
+//! when a host has an AAAA record but IPv6 is not actually routed, every connection
+//! waits for the IPv6 attempt to time out before IPv4 is tried.  Here the addresses
+//! are interleaved by family and a new attempt starts every [`ATTEMPT_DELAY`] (or as
+//! soon as the previous one fails) while earlier ones are still pending; the first to
+//! connect wins and the rest are dropped.  Names are resolved through
+//! [`dns_cache`](crate::dns_cache).  Servers with a [`proxy`](crate::proxy) route are
+//! reached through the proxy instead.
+
+use std::io;
+use std::net::SocketAddr;
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio::task::JoinSet;
+
+use crate::{dns_cache, host_resolver, proxy};
+
+/// Head start each attempt gets before the next address is tried in parallel.
+const ATTEMPT_DELAY: Duration = Duration::from_millis(250);
+
+/// Connect to `address` (`host:port`), through its proxy if one is configured.
+pub async fn connect(address: &str) -> io::Result<TcpStream> {
+    let (host, port) = host_resolver::split_host_port(address);
+    match proxy::connect(host, port).await {
+        Some(proxied) => proxied,
+        None => connect_direct(address).await,
+    }
+}
+
+/// Resolve `address` (`host:port`) and connect to whichever address answers first.
+pub async fn connect_direct(address: &str) -> io::Result<TcpStream> {
+    let resolved = dns_cache::resolve(address).await?;
+    let mut pending = interleave_families(resolved).into_iter();
+    let mut attempts = JoinSet::new();
+    let mut last_error = None;
+
+    loop {
+        if attempts.is_empty() {
+            match pending.next() {
+                Some(addr) => {
+                    attempts.spawn(TcpStream::connect(addr));
+                }
+                None => {
+                    // Every address failed; resolve afresh next time in case the
+                    // name has moved (e.g. after a failover).
+                    dns_cache::invalidate(address);
+                    return Err(last_error.unwrap_or_else(|| {
+                        io::Error::new(
+                            io::ErrorKind::NotFound,
+                            format!("{} resolved to no addresses", address),
+                        )
+                    }));
+                }
+            }
+        }
+
+        let finished = if pending.len() > 0 {
+            match tokio::time::timeout(ATTEMPT_DELAY, attempts.join_next()).await {
+                Ok(finished) => finished,
+                Err(_) => {
+                    // Still waiting: start the next address alongside.
+                    if let Some(addr) = pending.next() {
+                        attempts.spawn(TcpStream::connect(addr));
+                    }
+                    continue;
+                }
+            }
+        } else {
+            attempts.join_next().await
+        };
+
+        match finished {
+            // Dropping `attempts` aborts the connects still in flight.
+            Some(Ok(Ok(stream))) => return Ok(stream),
+            Some(Ok(Err(e))) => {
+                last_error = Some(e);
+                if let Some(addr) = pending.next() {
+                    attempts.spawn(TcpStream::connect(addr));
+                }
+            }
+            Some(Err(e)) => last_error = Some(io::Error::other(e)),
+            None => {}
+        }
+    }
+}
+
+/// Order addresses alternately by family, starting with the family the resolver
+/// listed first.
+fn interleave_families(resolved: Vec<SocketAddr>) -> Vec<SocketAddr> {
+    let Some(first) = resolved.first() else {
+        return resolved;
+    };
+    let prefer_v6 = first.is_ipv6();
+    let (mut preferred, mut other): (Vec<_>, Vec<_>) = resolved
+        .into_iter()
+        .partition(|addr| addr.is_ipv6() == prefer_v6);
+    let mut ordered = Vec::with_capacity(preferred.len() + other.len());
+    preferred.reverse();
+    other.reverse();
+    while let Some(addr) = preferred.pop() {
+        ordered.push(addr);
+        if let Some(addr) = other.pop() {
+            ordered.push(addr);
+        }
+    }
+    ordered.extend(other.into_iter().rev());
+    ordered
+}
@@ -0,0 +1,468 @@
+//! Negotiated TLS parameters of a connection, observed on the wire.
+//!
+//! tiberius runs the TLS handshake internally and does not expose the session.  The
+//! handshake itself is visible though: in TDS 7.x it travels inside PRELOGIN packets,
+//! and the server's PRELOGIN response, its ServerHello (protocol version, cipher
+//! suite) and, under TLS 1.2, its Certificate message are sent in the clear.
+//! [`ObservedStream`] wraps the TCP stream and reads those messages as they pass; once
+//! the handshake is over it is a plain pass-through.  Under TLS 1.3 the certificate is
+//! encrypted, so only the version and cipher suite are known.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::io;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, ready};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::net::TcpStream;
+
+use crate::attention::{Canceller, OutgoingFrames};
+use crate::cert_expiry;
+use crate::wire_debug::WireTrace;
+
+/// Give up on a handshake that has not finished within this many bytes.
+const MAX_OBSERVED_BYTES: usize = 256 * 1024;
+
+/// What the server revealed during PRELOGIN and the TLS handshake.
+#[derive(Clone, Debug, Default)]
+pub struct HandshakeInfo {
+    /// Product version from the PRELOGIN response, e.g. "16.0.4135".
+    pub server_version: Option<String>,
+    pub tls_version: Option<&'static str>,
+    pub cipher_suite: Option<u16>,
+    pub certificate: Option<CertificateInfo>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CertificateInfo {
+    pub subject: String,
+    pub issuer: String,
+    pub not_before: Option<DateTime<Utc>>,
+    pub not_after: Option<DateTime<Utc>>,
+}
+
+impl HandshakeInfo {
+    /// Add the handshake fields to `dict`.
+    pub fn fill_dict(&self, dict: &Bound<'_, PyDict>) -> PyResult<()> {
+        let cert = self.certificate.as_ref();
+        dict.set_item("server_version", self.server_version.as_deref())?;
+        dict.set_item("tls_version", self.tls_version)?;
+        dict.set_item("cipher_suite", self.cipher_suite.map(cipher_suite_name))?;
+        dict.set_item("certificate_subject", cert.map(|c| c.subject.as_str()))?;
+        dict.set_item("certificate_issuer", cert.map(|c| c.issuer.as_str()))?;
+        dict.set_item("certificate_not_before", cert.and_then(|c| c.not_before))?;
+        dict.set_item("certificate_not_after", cert.and_then(|c| c.not_after))?;
+        Ok(())
+    }
+}
+
+/// A TCP stream that records the server's side of the handshake.
+///
+/// The socket is shared with the connection's [`Canceller`], which may write an
+/// attention packet after tiberius abandoned a request; reads and writes therefore go
+/// through `&TcpStream` readiness rather than an exclusively owned stream.
+pub struct ObservedStream {
+    inner: Arc<TcpStream>,
+    observer: Option<Box<Observer>>,
+    info: Arc<Mutex<HandshakeInfo>>,
+    outgoing: Arc<Mutex<OutgoingFrames>>,
+    trace: Option<Box<WireTrace>>,
+}
+
+impl ObservedStream {
+    pub fn new(inner: TcpStream) -> Self {
+        ObservedStream {
+            trace: WireTrace::start(inner.peer_addr().ok()),
+            inner: Arc::new(inner),
+            observer: Some(Box::default()),
+            info: Arc::default(),
+            outgoing: Arc::default(),
+        }
+    }
+
+    /// Handle to the observations, filled in as the handshake proceeds.
+    pub fn handshake(&self) -> Arc<Mutex<HandshakeInfo>> {
+        Arc::clone(&self.info)
+    }
+
+    /// Handle for sending an attention signal on this stream's socket.
+    pub fn canceller(&self) -> Canceller {
+        Canceller::new(Arc::clone(&self.inner), Arc::clone(&self.outgoing))
+    }
+}
+
+impl AsyncRead for ObservedStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        let read = loop {
+            ready!(this.inner.poll_read_ready(cx))?;
+            match this.inner.try_read(buf.initialize_unfilled()) {
+                Ok(n) => break n,
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => continue,
+                Err(e) => return Poll::Ready(Err(e)),
+            }
+        };
+        let before = buf.filled().len();
+        buf.advance(read);
+        if let Some(trace) = this.trace.as_mut() {
+            trace.received(&buf.filled()[before..]);
+        }
+        if let Some(observer) = this.observer.as_mut() {
+            let done = observer.feed(&buf.filled()[before..]);
+            if let Ok(mut info) = this.info.lock() {
+                *info = observer.info.clone();
+            }
+            if done {
+                if let Some(cert) = &observer.info.certificate {
+                    cert_expiry::check(cert);
+                }
+                this.observer = None;
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncWrite for ObservedStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        let written = loop {
+            ready!(this.inner.poll_write_ready(cx))?;
+            match this.inner.try_write(buf) {
+                Ok(n) => break n,
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => continue,
+                Err(e) => return Poll::Ready(Err(e)),
+            }
+        };
+        if let Ok(mut outgoing) = this.outgoing.lock() {
+            outgoing.feed(&buf[..written]);
+        }
+        if let Some(trace) = this.trace.as_mut() {
+            trace.sent(&buf[..written]);
+        }
+        Poll::Ready(Ok(written))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        // Writes go straight to the socket.
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        // The socket is closed when the last handle to it is dropped.
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Incremental parser of the server's bytes: TDS packets, then the TLS records they
+/// carry, then the handshake messages in those records.
+#[derive(Default)]
+struct Observer {
+    seen: usize,
+    tds: Vec<u8>,
+    prelogin_done: bool,
+    tls: Vec<u8>,
+    handshake: Vec<u8>,
+    info: HandshakeInfo,
+}
+
+impl Observer {
+    /// Consume newly read bytes; true once there is nothing more to learn.
+    fn feed(&mut self, bytes: &[u8]) -> bool {
+        self.seen += bytes.len();
+        self.tds.extend_from_slice(bytes);
+        self.seen > MAX_OBSERVED_BYTES || self.parse_packets().is_none()
+    }
+
+    /// None when observation is over (handshake finished, no TLS, or unexpected data).
+    fn parse_packets(&mut self) -> Option<()> {
+        while self.tds.len() >= 8 {
+            let length = u16::from_be_bytes([self.tds[2], self.tds[3]]) as usize;
+            if length < 8 {
+                return None;
+            }
+            if self.tds.len() < length {
+                break;
+            }
+            let payload: Vec<u8> = self.tds[8..length].to_vec();
+            let end_of_message = self.tds[1] & 0x01 != 0;
+            self.tds.drain(..length);
+
+            if !self.prelogin_done {
+                // The PRELOGIN response fits in one packet.
+                self.prelogin_done = end_of_message;
+                if !self.parse_prelogin(&payload)? {
+                    return None;
+                }
+            } else {
+                self.tls.extend_from_slice(&payload);
+                self.parse_records()?;
+            }
+        }
+        Some(())
+    }
+
+    /// Record the server version; Some(false) when the server does not support
+    /// encryption.
+    fn parse_prelogin(&mut self, payload: &[u8]) -> Option<bool> {
+        let mut encryption_supported = true;
+        let mut options = payload;
+        while let [token, rest @ ..] = options {
+            if *token == 0xFF {
+                break;
+            }
+            let [o1, o2, l1, l2, rest @ ..] = rest else {
+                return None;
+            };
+            let offset = u16::from_be_bytes([*o1, *o2]) as usize;
+            let len = u16::from_be_bytes([*l1, *l2]) as usize;
+            let value = payload.get(offset..offset + len)?;
+            match (token, value) {
+                (0x00, [major, minor, b1, b2, ..]) => {
+                    let build = u16::from_be_bytes([*b1, *b2]);
+                    self.info.server_version = Some(format!("{}.{}.{}", major, minor, build));
+                }
+                // ENCRYPT_NOT_SUP
+                (0x01, [0x02]) => encryption_supported = false,
+                _ => {}
+            }
+            options = rest;
+        }
+        Some(encryption_supported)
+    }
+
+    fn parse_records(&mut self) -> Option<()> {
+        while self.tls.len() >= 5 {
+            // Anything but a handshake record means the handshake part is over (or
+            // there was no TLS at all and this is the login response).
+            if self.tls[0] != 22 || self.tls[1] != 0x03 {
+                return None;
+            }
+            let length = u16::from_be_bytes([self.tls[3], self.tls[4]]) as usize;
+            if self.tls.len() < 5 + length {
+                break;
+            }
+            self.handshake.extend_from_slice(&self.tls[5..5 + length]);
+            self.tls.drain(..5 + length);
+            self.parse_handshake()?;
+        }
+        Some(())
+    }
+
+    fn parse_handshake(&mut self) -> Option<()> {
+        while self.handshake.len() >= 4 {
+            let length =
+                u32::from_be_bytes([0, self.handshake[1], self.handshake[2], self.handshake[3]])
+                    as usize;
+            if self.handshake.len() < 4 + length {
+                break;
+            }
+            let kind = self.handshake[0];
+            let body: Vec<u8> = self.handshake[4..4 + length].to_vec();
+            self.handshake.drain(..4 + length);
+            match kind {
+                // ServerHello; under TLS 1.3 everything after it is encrypted.
+                2 => {
+                    parse_server_hello(&body, &mut self.info)?;
+                    if self.info.tls_version == Some("TLSv1.3") {
+                        return None;
+                    }
+                }
+                // Certificate (TLS 1.2): the first entry is the server's own.
+                11 => {
+                    let [l1, l2, l3, ..] = *body.get(3..)? else {
+                        return None;
+                    };
+                    let cert_len = u32::from_be_bytes([0, l1, l2, l3]) as usize;
+                    let der = body.get(6..6 + cert_len)?;
+                    self.info.certificate = parse_certificate(der);
+                }
+                // ServerHelloDone
+                14 => return None,
+                _ => {}
+            }
+        }
+        Some(())
+    }
+}
+
+fn parse_server_hello(body: &[u8], info: &mut HandshakeInfo) -> Option<()> {
+    let mut version = u16::from_be_bytes([*body.first()?, *body.get(1)?]);
+    let session_id_len = *body.get(34)? as usize;
+    let rest = body.get(35 + session_id_len..)?;
+    info.cipher_suite = Some(u16::from_be_bytes([*rest.first()?, *rest.get(1)?]));
+
+    // supported_versions carries the real version for TLS 1.3.
+    let mut extensions = rest.get(5..)?;
+    while let [t1, t2, l1, l2, tail @ ..] = extensions {
+        let len = u16::from_be_bytes([*l1, *l2]) as usize;
+        let data = tail.get(..len)?;
+        if [*t1, *t2] == [0x00, 0x2b] && len == 2 {
+            version = u16::from_be_bytes([data[0], data[1]]);
+        }
+        extensions = &tail[len..];
+    }
+    info.tls_version = Some(match version {
+        0x0301 => "TLSv1.0",
+        0x0302 => "TLSv1.1",
+        0x0303 => "TLSv1.2",
+        0x0304 => "TLSv1.3",
+        _ => "unknown",
+    });
+    Some(())
+}
+
+/// IANA name of a cipher suite rustls can negotiate, or its hex code.
+fn cipher_suite_name(suite: u16) -> String {
+    let name = match suite {
+        0x1301 => "TLS_AES_128_GCM_SHA256",
+        0x1302 => "TLS_AES_256_GCM_SHA384",
+        0x1303 => "TLS_CHACHA20_POLY1305_SHA256",
+        0xC02B => "TLS_ECDHE_ECDSA_WITH_AES_128_GCM_SHA256",
+        0xC02C => "TLS_ECDHE_ECDSA_WITH_AES_256_GCM_SHA384",
+        0xC02F => "TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256",
+        0xC030 => "TLS_ECDHE_RSA_WITH_AES_256_GCM_SHA384",
+        0xCCA8 => "TLS_ECDHE_RSA_WITH_CHACHA20_POLY1305_SHA256",
+        0xCCA9 => "TLS_ECDHE_ECDSA_WITH_CHACHA20_POLY1305_SHA256",
+        _ => return format!("0x{:04X}", suite),
+    };
+    name.to_string()
+}
+
+// ── Minimal X.509 (DER) reading: subject, issuer and validity ────────────────────
+
+/// Split one DER element off `data`: (tag, contents, rest).
+fn der_element(data: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let (&tag, rest) = data.split_first()?;
+    let (&first, rest) = rest.split_first()?;
+    let (len, rest) = if first < 0x80 {
+        (first as usize, rest)
+    } else {
+        let count = (first & 0x7F) as usize;
+        if count == 0 || count > 4 || rest.len() < count {
+            return None;
+        }
+        let len = rest[..count]
+            .iter()
+            .fold(0usize, |acc, b| (acc << 8) | *b as usize);
+        (len, &rest[count..])
+    };
+    if rest.len() < len {
+        return None;
+    }
+    Some((tag, &rest[..len], &rest[len..]))
+}
+
+fn parse_certificate(der: &[u8]) -> Option<CertificateInfo> {
+    let (_, certificate, _) = der_element(der)?;
+    let (_, tbs, _) = der_element(certificate)?;
+    let mut fields = tbs;
+    // Optional explicit version [0].
+    if fields.first() == Some(&0xA0) {
+        fields = der_element(fields)?.2;
+    }
+    let (_, _serial, fields) = der_element(fields)?;
+    let (_, _signature, fields) = der_element(fields)?;
+    let (_, issuer, fields) = der_element(fields)?;
+    let (_, validity, fields) = der_element(fields)?;
+    let (_, subject, _) = der_element(fields)?;
+
+    let (not_before_tag, not_before, rest) = der_element(validity)?;
+    let (not_after_tag, not_after, _) = der_element(rest)?;
+    Some(CertificateInfo {
+        subject: format_name(subject),
+        issuer: format_name(issuer),
+        not_before: parse_time(not_before_tag, not_before),
+        not_after: parse_time(not_after_tag, not_after),
+    })
+}
+
+/// `CN=host, O=Org` from an X.509 Name, attributes in certificate order.
+fn format_name(mut rdns: &[u8]) -> String {
+    let mut parts = Vec::new();
+    while let Some((_, set, rest)) = der_element(rdns) {
+        let mut attributes = set;
+        while let Some((_, attribute, next)) = der_element(attributes) {
+            if let Some((_, oid, value)) = der_element(attribute)
+                && let Some((tag, text, _)) = der_element(value)
+            {
+                let text = match tag {
+                    // BMPString
+                    0x1E => String::from_utf16_lossy(
+                        &text
+                            .chunks_exact(2)
+                            .map(|c| u16::from_be_bytes([c[0], c[1]]))
+                            .collect::<Vec<_>>(),
+                    ),
+                    _ => String::from_utf8_lossy(text).into_owned(),
+                };
+                parts.push(format!("{}={}", attribute_name(oid), text));
+            }
+            attributes = next;
+        }
+        rdns = rest;
+    }
+    parts.join(", ")
+}
+
+fn attribute_name(oid: &[u8]) -> String {
+    match oid {
+        [0x55, 0x04, 0x03] => "CN".into(),
+        [0x55, 0x04, 0x06] => "C".into(),
+        [0x55, 0x04, 0x07] => "L".into(),
+        [0x55, 0x04, 0x08] => "ST".into(),
+        [0x55, 0x04, 0x0A] => "O".into(),
+        [0x55, 0x04, 0x0B] => "OU".into(),
+        [0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x09, 0x01] => "emailAddress".into(),
+        _ => {
+            // Dotted notation for anything else.
+            let mut ids = Vec::new();
+            let mut value = 0u64;
+            for byte in oid {
+                value = (value << 7) | (byte & 0x7F) as u64;
+                if byte & 0x80 == 0 {
+                    ids.push(value);
+                    value = 0;
+                }
+            }
+            let mut arcs = Vec::with_capacity(ids.len() + 1);
+            if let Some((&first, rest)) = ids.split_first() {
+                let top = (first / 40).min(2);
+                arcs.push(top);
+                arcs.push(first - top * 40);
+                arcs.extend_from_slice(rest);
+            }
+            arcs.iter()
+                .map(u64::to_string)
+                .collect::<Vec<_>>()
+                .join(".")
+        }
+    }
+}
+
+/// UTCTime (`YYMMDDHHMMSSZ`) or GeneralizedTime (`YYYYMMDDHHMMSSZ`).
+fn parse_time(tag: u8, value: &[u8]) -> Option<DateTime<Utc>> {
+    let text = std::str::from_utf8(value).ok()?.strip_suffix('Z')?;
+    let (year, rest) = match tag {
+        0x17 => {
+            let yy: i32 = text.get(..2)?.parse().ok()?;
+            (if yy < 50 { 2000 + yy } else { 1900 + yy }, text.get(2..)?)
+        }
+        0x18 => (text.get(..4)?.parse().ok()?, text.get(4..)?),
+        _ => return None,
+    };
+    let field = |i: usize| -> Option<u32> { rest.get(i..i + 2)?.parse().ok() };
+    NaiveDate::from_ymd_opt(year, field(0)?, field(2)?)?
+        .and_hms_opt(field(4)?, field(6)?, field(8)?)
+        .map(|t| t.and_utc())
+}
@@ -0,0 +1,89 @@
+//! Tables a statement reads and writes, for `Connection.touched_tables()`.
+//!
+//! DONE tokens only carry row counts and `sp_describe_first_result_set` only covers
+//! the columns of a result set, so neither sees the target of an UPDATE or DELETE.
+//! The estimated plan does: with `SHOWPLAN_XML` on, the server compiles the statement
+//! without running it and returns one plan document per statement, in which every
+//! table access is an `<Object>` element under the operator (`<RelOp>`) that uses it.
+//! Objects under an Insert/Update/Delete/Merge operator are writes; the rest are reads.
+
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use std::collections::BTreeSet;
+
+pub const SHOWPLAN_ON: &str = "SET SHOWPLAN_XML ON";
+pub const SHOWPLAN_OFF: &str = "SET SHOWPLAN_XML OFF";
+
+/// Value of `name="..."` inside the start tag `tag`.
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let needle = format!(" {}=\"", name);
+    let start = tag.find(&needle)? + needle.len();
+    let len = tag[start..].find('"')?;
+    Some(&tag[start..start + len])
+}
+
+fn unbracket(part: &str) -> String {
+    part.strip_prefix('[')
+        .and_then(|p| p.strip_suffix(']'))
+        .unwrap_or(part)
+        .replace("]]", "]")
+}
+
+/// `database.schema.table` with whichever parts the plan gives (table variables have
+/// no database or schema).
+fn object_name(tag: &str) -> Option<String> {
+    let table = attribute(tag, "Table")?;
+    let parts: Vec<String> = [attribute(tag, "Database"), attribute(tag, "Schema")]
+        .into_iter()
+        .flatten()
+        .chain(std::iter::once(table))
+        .map(unbracket)
+        .collect();
+    Some(parts.join("."))
+}
+
+fn is_write_operator(physical_op: &str) -> bool {
+    ["Insert", "Update", "Delete", "Merge"]
+        .iter()
+        .any(|op| physical_op.contains(op))
+}
+
+/// Split the objects referenced by the plans into (read, written) table names.
+pub fn tables_from_showplan(plans: &[String]) -> (BTreeSet<String>, BTreeSet<String>) {
+    let mut read = BTreeSet::new();
+    let mut written = BTreeSet::new();
+
+    for plan in plans {
+        let mut current_op = "";
+        let mut rest = plan.as_str();
+        while let Some(open) = rest.find('<') {
+            let after = &rest[open + 1..];
+            let Some(close) = after.find('>') else {
+                break;
+            };
+            let tag = &after[..close];
+            if tag.starts_with("RelOp ") {
+                current_op = attribute(tag, "PhysicalOp").unwrap_or("");
+            } else if tag.starts_with("Object ")
+                && let Some(name) = object_name(tag)
+            {
+                if is_write_operator(current_op) {
+                    written.insert(name);
+                } else {
+                    read.insert(name);
+                }
+            }
+            rest = &after[close + 1..];
+        }
+    }
+
+    (read, written)
+}
+
+pub fn touched_tables_to_py(plans: &[String], py: Python<'_>) -> PyResult<Py<PyAny>> {
+    let (read, written) = tables_from_showplan(plans);
+    let dict = PyDict::new(py);
+    dict.set_item("read", PyList::new(py, read)?)?;
+    dict.set_item("written", PyList::new(py, written)?)?;
+    Ok(dict.into_any().unbind())
+}
@@ -1,34 +1,93 @@
-use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
-use pyo3::types::PyList;
+use pyo3::types::{PyDict, PyList};
 use pyo3_async_runtimes::tokio::future_into_py;
 use std::sync::Arc;
-use tiberius::{AuthMethod, Client, Config};
-use tokio::net::TcpStream;
+use tiberius::{AuthMethod, Client, Config, Row};
 use tokio::sync::Mutex as AsyncMutex;
 use tokio_util::compat::TokioAsyncReadCompatExt;
 
 use crate::azure_auth::PyAzureCredential;
-use crate::batch::{execute_batch_on_connection, parse_batch_items, query_batch_on_connection};
-use crate::helpers::wrap_query_stream;
-use crate::parameter_conversion::{convert_parameters_to_fast, params_as_sql_refs};
+use crate::batch::{
+    BulkRequest, execute_batch_on_connection, parse_batch_items, query_batch_on_connection,
+};
+use crate::filestream::FilestreamTarget;
+use crate::helpers::{into_first_result_with_metadata, wrap_first_result, wrap_query_stream};
+use crate::parameter_conversion::{
+    FastParameter, convert_parameters_to_fast, params_as_sql_refs, python_to_fast_parameter,
+    rewrite_json_lists,
+};
 use crate::ssl_config::PySslConfig;
-use crate::types::{create_connection_error, create_sql_error};
+use crate::statement_policy::PyStatementPolicy;
+use crate::tls_info::ObservedStream;
+use crate::type_mapping::DecodeOptions;
+use crate::types::{InterfaceError, create_connection_error, create_sql_error};
 
 /// Type for a single direct connection (not pooled)
-type SingleConnectionType = Client<tokio_util::compat::Compat<TcpStream>>;
+type SingleConnectionType = Client<tokio_util::compat::Compat<ObservedStream>>;
 
 /// Bundles the three cloned handles needed for async transaction operations.
 struct TransactionHandles {
     conn: Arc<AsyncMutex<Option<SingleConnectionType>>>,
     config: Arc<Config>,
     azure_credential: Option<PyAzureCredential>,
+    decode_options: DecodeOptions,
 }
 
 impl TransactionHandles {
     async fn ensure_connected(&self) -> PyResult<()> {
         Transaction::ensure_connected_inner(&self.conn, &self.config, self.azure_credential.as_ref()).await
     }
+
+    /// Run a parameterised query on the dedicated connection and return the first result set.
+    async fn query_rows(&self, sql: &str, parameters: &[FastParameter]) -> PyResult<Vec<Row>> {
+        self.ensure_connected().await?;
+        let tiberius_params = params_as_sql_refs(parameters);
+
+        let mut conn_guard = self.conn.lock().await;
+        let conn_ref = conn_guard
+            .as_mut()
+            .ok_or_else(|| InterfaceError::new_err("Connection is not established"))?;
+
+        conn_ref
+            .query(sql, &tiberius_params)
+            .await
+            .map_err(|e| create_sql_error(e, "Query execution failed"))?
+            .into_first_result()
+            .await
+            .map_err(|e| create_sql_error(e, "Failed to get results"))
+    }
+
+    /// Run a parameterised command on the dedicated connection and return the rows affected.
+    async fn execute_command(&self, sql: &str, parameters: &[FastParameter]) -> PyResult<u64> {
+        self.ensure_connected().await?;
+        let tiberius_params = params_as_sql_refs(parameters);
+
+        let mut conn_guard = self.conn.lock().await;
+        let conn_ref = conn_guard
+            .as_mut()
+            .ok_or_else(|| InterfaceError::new_err("Connection is not established"))?;
+
+        let result = conn_ref
+            .execute(sql, &tiberius_params)
+            .await
+            .map_err(|e| create_sql_error(e, "Command execution failed"))?;
+        Ok(result.total())
+    }
+}
+
+/// Savepoint names are bound as a variable (`SAVE TRANSACTION @P1`) rather than
+/// spliced into the SQL. SQL Server rejects names longer than 32 characters; check
+/// that here so the error names the problem.
+fn savepoint_parameter(name: String) -> PyResult<FastParameter> {
+    let len = name.chars().count();
+    if len == 0 || len > 32 {
+        return Err(PyValueError::new_err(format!(
+            "Savepoint name must be 1 to 32 characters, got {}",
+            len
+        )));
+    }
+    Ok(FastParameter::String(name))
 }
 
 /// A single dedicated connection (not pooled) for transaction support.
@@ -40,12 +99,16 @@ pub struct Transaction {
     config: Arc<Config>,
     _ssl_config: Option<PySslConfig>,
     azure_credential: Option<PyAzureCredential>,
+    decode_options: DecodeOptions,
+    /// The policy of the `Connection` this transaction was created from, if any.
+    statement_policy: Option<Arc<Py<PyStatementPolicy>>>,
 }
 
 #[pymethods]
 impl Transaction {
     #[new]
-    #[pyo3(signature = (connection_string = None, ssl_config = None, azure_credential = None, server = None, database = None, username = None, password = None, application_intent = None, port = None, instance_name = None, application_name = None))]
+    #[pyo3(signature = (connection_string = None, ssl_config = None, azure_credential = None, server = None, database = None, username = None, password = None, application_intent = None, port = None, instance_name = None, application_name = None, rstrip_char = false, time_as_timedelta = false))]
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         connection_string: Option<String>,
         ssl_config: Option<PySslConfig>,
@@ -58,6 +121,8 @@ impl Transaction {
         port: Option<u16>,
         instance_name: Option<String>,
         application_name: Option<String>,
+        rstrip_char: bool,
+        time_as_timedelta: bool,
     ) -> PyResult<Self> {
         // Store the original server parameter for validation before it gets reassigned
         let server_param = server.clone();
@@ -128,6 +193,12 @@ impl Transaction {
             config: Arc::new(config),
             _ssl_config: ssl_config,
             azure_credential,
+            decode_options: DecodeOptions {
+                rstrip_char,
+                time_as_timedelta,
+                ..DecodeOptions::default()
+            },
+            statement_policy: None,
         })
     }
 
@@ -140,7 +211,9 @@ impl Transaction {
         query: String,
         parameters: Option<&Bound<'p, PyAny>>,
     ) -> PyResult<Bound<'p, PyAny>> {
+        self.check_statement(py, &query)?;
         let fast_parameters = convert_parameters_to_fast(parameters, py)?;
+        let query = rewrite_json_lists(query, &fast_parameters);
         let handles = self.clone_handles();
 
         future_into_py(py, async move {
@@ -152,21 +225,20 @@ impl Transaction {
                 let mut conn_guard = handles.conn.lock().await;
                 let conn_ref = conn_guard
                     .as_mut()
-                    .ok_or_else(|| PyRuntimeError::new_err("Connection is not established"))?;
+                    .ok_or_else(|| InterfaceError::new_err("Connection is not established"))?;
 
                 let result = conn_ref
                     .query(&query, &tiberius_params)
                     .await
-                    .map_err(|e| create_sql_error(e, "Query execution failed"))?
-                    .into_first_result()
-                    .await
-                    .map_err(|e| create_sql_error(e, "Failed to get results"))?;
+                    .map_err(|e| create_sql_error(e, "Query execution failed"))?;
+                let result =
+                    into_first_result_with_metadata(result, handles.decode_options.limits).await?;
 
                 drop(conn_guard);
                 result
             };
 
-            wrap_query_stream(execution_result)
+            wrap_first_result(execution_result, handles.decode_options)
         })
     }
 
@@ -178,6 +250,7 @@ impl Transaction {
         py: Python<'p>,
         query: String,
     ) -> PyResult<Bound<'p, PyAny>> {
+        self.check_statement(py, &query)?;
         let handles = self.clone_handles();
 
         future_into_py(py, async move {
@@ -187,21 +260,20 @@ impl Transaction {
                 let mut conn_guard = handles.conn.lock().await;
                 let conn_ref = conn_guard
                     .as_mut()
-                    .ok_or_else(|| PyRuntimeError::new_err("Connection is not established"))?;
+                    .ok_or_else(|| InterfaceError::new_err("Connection is not established"))?;
 
                 let result = conn_ref
                     .simple_query(&query)
                     .await
-                    .map_err(|e| create_sql_error(e, "Query execution failed"))?
-                    .into_first_result()
-                    .await
-                    .map_err(|e| create_sql_error(e, "Failed to get results"))?;
+                    .map_err(|e| create_sql_error(e, "Query execution failed"))?;
+                let result =
+                    into_first_result_with_metadata(result, handles.decode_options.limits).await?;
 
                 drop(conn_guard);
                 result
             };
 
-            wrap_query_stream(execution_result)
+            wrap_first_result(execution_result, handles.decode_options)
         })
     }
 
@@ -214,7 +286,9 @@ impl Transaction {
         command: String,
         parameters: Option<&Bound<'p, PyAny>>,
     ) -> PyResult<Bound<'p, PyAny>> {
+        self.check_statement(py, &command)?;
         let fast_parameters = convert_parameters_to_fast(parameters, py)?;
+        let command = rewrite_json_lists(command, &fast_parameters);
         let handles = self.clone_handles();
 
         future_into_py(py, async move {
@@ -226,7 +300,7 @@ impl Transaction {
                 let mut conn_guard = handles.conn.lock().await;
                 let conn_ref = conn_guard
                     .as_mut()
-                    .ok_or_else(|| PyRuntimeError::new_err("Connection is not established"))?;
+                    .ok_or_else(|| InterfaceError::new_err("Connection is not established"))?;
 
                 let result = conn_ref
                     .execute(&command, &tiberius_params)
@@ -250,6 +324,7 @@ impl Transaction {
         py: Python<'p>,
         commands: &Bound<'p, PyList>,
     ) -> PyResult<Bound<'p, PyAny>> {
+        self.check_batch_items(py, commands)?;
         let batch_commands = parse_batch_items(commands, py)?;
         let handles = self.clone_handles();
 
@@ -260,7 +335,7 @@ impl Transaction {
                 let mut conn_guard = handles.conn.lock().await;
                 let conn_ref = conn_guard
                     .as_mut()
-                    .ok_or_else(|| PyRuntimeError::new_err("Connection is not established"))?;
+                    .ok_or_else(|| InterfaceError::new_err("Connection is not established"))?;
 
                 execute_batch_on_connection(conn_ref, batch_commands).await?
             };
@@ -280,6 +355,7 @@ impl Transaction {
         py: Python<'p>,
         queries: &Bound<'p, PyList>,
     ) -> PyResult<Bound<'p, PyAny>> {
+        self.check_batch_items(py, queries)?;
         let batch_queries = parse_batch_items(queries, py)?;
         let handles = self.clone_handles();
 
@@ -290,7 +366,7 @@ impl Transaction {
                 let mut conn_guard = handles.conn.lock().await;
                 let conn_ref = conn_guard
                     .as_mut()
-                    .ok_or_else(|| PyRuntimeError::new_err("Connection is not established"))?;
+                    .ok_or_else(|| InterfaceError::new_err("Connection is not established"))?;
 
                 query_batch_on_connection(conn_ref, batch_queries).await?
             };
@@ -298,7 +374,7 @@ impl Transaction {
             Python::attach(|py| -> PyResult<Py<PyAny>> {
                 let mut py_results = Vec::with_capacity(all_results.len());
                 for result in all_results {
-                    let py_result = wrap_query_stream(result)?;
+                    let py_result = wrap_query_stream(result, handles.decode_options)?;
                     py_results.push(py_result.into_any());
                 }
                 let py_list = PyList::new(py, py_results)?;
@@ -307,6 +383,37 @@ impl Transaction {
         })
     }
 
+    /// Insert rows into `table_name` on the transaction connection, as
+    /// `Connection.bulk_insert()` does.  The table may be a #temp table created earlier
+    /// on this connection, so rows can be staged and merged into their target before
+    /// the transaction commits.
+    #[pyo3(signature = (table_name, columns, data_rows, *, bulk_copy=false, verify_key=None))]
+    pub fn bulk_insert<'p>(
+        &self,
+        py: Python<'p>,
+        table_name: String,
+        columns: Vec<String>,
+        data_rows: &Bound<'p, PyList>,
+        bulk_copy: bool,
+        verify_key: Option<String>,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        let request = BulkRequest::prepare(table_name, columns, data_rows, bulk_copy, verify_key)?;
+        let handles = self.clone_handles();
+
+        future_into_py(py, async move {
+            handles.ensure_connected().await?;
+
+            let mut conn_guard = handles.conn.lock().await;
+            let conn_ref = conn_guard
+                .as_mut()
+                .ok_or_else(|| InterfaceError::new_err("Connection is not established"))?;
+
+            // An interrupted bulk load surfaces as the error; the caller rolls back.
+            let mut interrupted = false;
+            request.run(conn_ref, &mut interrupted).await
+        })
+    }
+
     /// Begin a transaction
     pub fn begin<'p>(&self, py: Python<'p>) -> PyResult<Bound<'p, PyAny>> {
         let handles = self.clone_handles();
@@ -318,7 +425,7 @@ impl Transaction {
                 let mut conn_guard = handles.conn.lock().await;
                 let conn_ref = conn_guard
                     .as_mut()
-                    .ok_or_else(|| PyRuntimeError::new_err("Connection is not established"))?;
+                    .ok_or_else(|| InterfaceError::new_err("Connection is not established"))?;
 
                 conn_ref
                     .simple_query("BEGIN TRANSACTION")
@@ -332,6 +439,129 @@ impl Transaction {
         })
     }
 
+    /// Return `{"path": ..., "transaction_context": ...}` for the FILESTREAM value in the
+    /// row where `key_column = key`, or None if no row matches. The transaction context
+    /// is only non-NULL inside an open transaction.
+    #[pyo3(signature = (table, column, key_column, key))]
+    pub fn filestream_context<'p>(
+        &self,
+        py: Python<'p>,
+        table: String,
+        column: String,
+        key_column: String,
+        key: &Bound<'p, PyAny>,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        let sql = FilestreamTarget::new(&table, &column, &key_column)?.context_sql();
+        let parameters = [python_to_fast_parameter(key)?];
+        let handles = self.clone_handles();
+
+        future_into_py(py, async move {
+            let rows = handles.query_rows(&sql, &parameters).await?;
+            Python::attach(|py| -> PyResult<Py<PyAny>> {
+                let Some(row) = rows.first() else {
+                    return Ok(py.None());
+                };
+                let dict = PyDict::new(py);
+                dict.set_item("path", row.try_get::<&str, usize>(0).ok().flatten())?;
+                dict.set_item(
+                    "transaction_context",
+                    row.try_get::<&[u8], usize>(1).ok().flatten(),
+                )?;
+                Ok(dict.into_any().unbind())
+            })
+        })
+    }
+
+    /// Read FILESTREAM data for the row where `key_column = key`: the whole value, or
+    /// `length` bytes (default: to the end) starting at 0-based `offset`.
+    /// Returns None if no row matches or the value is NULL.
+    #[pyo3(signature = (table, column, key_column, key, offset=0, length=None))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn filestream_read<'p>(
+        &self,
+        py: Python<'p>,
+        table: String,
+        column: String,
+        key_column: String,
+        key: &Bound<'p, PyAny>,
+        offset: u32,
+        length: Option<u32>,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        let ranged = offset > 0 || length.is_some();
+        let sql = FilestreamTarget::new(&table, &column, &key_column)?
+            .read_sql(ranged, length.is_some());
+        let mut parameters = vec![python_to_fast_parameter(key)?];
+        if ranged {
+            // SUBSTRING is 1-based.
+            parameters.push(FastParameter::I64(offset as i64 + 1));
+        }
+        if let Some(length) = length {
+            parameters.push(FastParameter::I64(length as i64));
+        }
+        let handles = self.clone_handles();
+
+        future_into_py(py, async move {
+            let rows = handles.query_rows(&sql, &parameters).await?;
+            Python::attach(|py| -> PyResult<Py<PyAny>> {
+                match rows.first().and_then(|row| row.try_get::<&[u8], usize>(0).ok().flatten()) {
+                    Some(data) => Ok(data.into_pyobject(py)?.into_any().unbind()),
+                    None => Ok(py.None()),
+                }
+            })
+        })
+    }
+
+    /// Write FILESTREAM data for the row where `key_column = key`. Replaces the whole
+    /// value, or overwrites `len(data)` bytes at 0-based `offset` (extending the value
+    /// when the write runs past its end). Returns the number of rows updated.
+    #[pyo3(signature = (table, column, key_column, key, data, offset=None))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn filestream_write<'p>(
+        &self,
+        py: Python<'p>,
+        table: String,
+        column: String,
+        key_column: String,
+        key: &Bound<'p, PyAny>,
+        data: Vec<u8>,
+        offset: Option<u32>,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        let sql = FilestreamTarget::new(&table, &column, &key_column)?.write_sql(offset.is_some());
+        let mut parameters = vec![python_to_fast_parameter(key)?, FastParameter::Bytes(data)];
+        if let Some(offset) = offset {
+            parameters.push(FastParameter::I64(offset as i64));
+        }
+        let handles = self.clone_handles();
+
+        future_into_py(py, async move { handles.execute_command(&sql, &parameters).await })
+    }
+
+    /// Set a savepoint inside the current transaction (`SAVE TRANSACTION`).
+    pub fn savepoint<'p>(&self, py: Python<'p>, name: String) -> PyResult<Bound<'p, PyAny>> {
+        let parameters = [savepoint_parameter(name)?];
+        let handles = self.clone_handles();
+
+        future_into_py(py, async move {
+            handles
+                .execute_command("SAVE TRANSACTION @P1", &parameters)
+                .await?;
+            Ok(())
+        })
+    }
+
+    /// Undo the work done since savepoint `name`; the transaction stays open.
+    pub fn rollback_to<'p>(&self, py: Python<'p>, name: String) -> PyResult<Bound<'p, PyAny>> {
+        let parameters = [savepoint_parameter(name)?];
+        let handles = self.clone_handles();
+
+        future_into_py(py, async move {
+            handles
+                .execute_command("ROLLBACK TRANSACTION @P1", &parameters)
+                .await?;
+            Ok(())
+        })
+    }
+
     /// Commit the current transaction
     pub fn commit<'p>(&self, py: Python<'p>) -> PyResult<Bound<'p, PyAny>> {
         let conn = Arc::clone(&self.conn);
@@ -379,12 +609,48 @@ impl Transaction {
 }
 
 impl Transaction {
+    /// Build a not-yet-connected transaction from an already validated config.
+    /// Used by `Connection.transaction()` so the dedicated connection inherits the
+    /// pooled connection's target, SSL settings and credentials.
+    pub fn from_parts(
+        config: Arc<Config>,
+        azure_credential: Option<PyAzureCredential>,
+        decode_options: DecodeOptions,
+        statement_policy: Option<Arc<Py<PyStatementPolicy>>>,
+    ) -> Self {
+        Transaction {
+            conn: Arc::new(AsyncMutex::new(None)),
+            config,
+            _ssl_config: None,
+            azure_credential,
+            decode_options,
+            statement_policy,
+        }
+    }
+
+    /// Raise `PolicyViolation` if the statement policy rejects `sql`.
+    fn check_statement(&self, py: Python<'_>, sql: &str) -> PyResult<()> {
+        match &self.statement_policy {
+            Some(policy) => policy.get().enforce(py, sql),
+            None => Ok(()),
+        }
+    }
+
+    /// Check the SQL of each `(sql, parameters)` item against the statement policy.
+    fn check_batch_items(&self, py: Python<'_>, items: &Bound<'_, PyList>) -> PyResult<()> {
+        match &self.statement_policy {
+            Some(policy) => policy.get().enforce_batch(py, items),
+            None => Ok(()),
+        }
+    }
+
     /// Clone the three fields needed for async transaction operations into a single struct.
     fn clone_handles(&self) -> TransactionHandles {
         TransactionHandles {
             conn: Arc::clone(&self.conn),
             config: Arc::clone(&self.config),
             azure_credential: self.azure_credential.clone(),
+            decode_options: self.decode_options,
         }
     }
 
@@ -397,7 +663,7 @@ impl Transaction {
         let mut conn_guard = conn.lock().await;
         let conn_ref = conn_guard
             .as_mut()
-            .ok_or_else(|| PyRuntimeError::new_err("Connection is not established"))?;
+            .ok_or_else(|| InterfaceError::new_err("Connection is not established"))?;
 
         conn_ref
             .simple_query(sql)
@@ -417,7 +683,7 @@ impl Transaction {
     ) -> PyResult<()> {
         let mut conn_guard = conn.lock().await;
         if conn_guard.is_none() {
-            let tcp_stream = TcpStream::connect(config.get_addr()).await.map_err(|e| {
+            let tcp_stream = crate::tcp_connect::connect(&config.get_addr()).await.map_err(|e| {
                         create_connection_error(format!("Failed to connect to server: {}", e))
                     })?;
 
@@ -428,7 +694,7 @@ impl Transaction {
                 create_connection_error(format!("Failed to set TCP_NODELAY: {}", e))
             })?;
 
-            let compat_stream = tcp_stream.compat();
+            let compat_stream = ObservedStream::new(tcp_stream).compat();
 
             // Configure authentication
             let mut auth_config = (**config).clone();
@@ -437,9 +703,16 @@ impl Transaction {
                 auth_config.authentication(auth_method);
             }
 
-            let new_conn: SingleConnectionType = Client::connect(auth_config, compat_stream)
-                .await
-                .map_err(|e| create_sql_error(e, "Failed to connect to database"))?;
+            let new_conn: SingleConnectionType =
+                match Client::connect(auth_config, compat_stream).await {
+                    Ok(new_conn) => new_conn,
+                    Err(e) => {
+                        if let Some(azure_cred) = azure_credential {
+                            azure_cred.discard_refused_token(&e).await;
+                        }
+                        return Err(create_sql_error(e, "Failed to connect to database"));
+                    }
+                };
             *conn_guard = Some(new_conn);
         }
 
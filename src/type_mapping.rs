@@ -1,10 +1,17 @@
 use std::sync::OnceLock;
 
 use pyo3::exceptions::PyValueError;
-use pyo3::types::{PyBytes, PyFrozenSet, PyList, PySet, PyString, PyTuple};
+use pyo3::types::{
+    PyByteArray, PyBytes, PyFrozenSet, PyList, PyMemoryView, PySet, PyString, PyTuple,
+};
 use pyo3::{IntoPyObjectExt, Py, PyAny, prelude::*};
 use tiberius::{ColumnType, Row};
 
+use crate::integer_columns::integer_to_python;
+use crate::json_columns::JsonMode;
+use crate::result_limits::ResultLimits;
+use crate::xml_columns::{XmlMode, xml_to_python};
+
 /// Cached handle to `decimal.Decimal` — imported once, reused for every row.
 /// Stored as `Option` to allow initialization via `get_or_init()` with fallible closure.
 static DECIMAL_CLASS: OnceLock<Option<Py<PyAny>>> = OnceLock::new();
@@ -12,7 +19,7 @@ static DECIMAL_CLASS: OnceLock<Option<Py<PyAny>>> = OnceLock::new();
 /// Return a `Bound` reference to `decimal.Decimal`, initializing the cache on
 /// the very first call and simply re-binding on every subsequent call.
 #[inline]
-fn get_decimal_class(py: Python<'_>) -> PyResult<&Bound<'_, PyAny>> {
+pub fn decimal_class(py: Python<'_>) -> PyResult<&Bound<'_, PyAny>> {
     let cls = DECIMAL_CLASS
         .get_or_init(|| {
             py.import("decimal")
@@ -25,6 +32,92 @@ fn get_decimal_class(py: Python<'_>) -> PyResult<&Bound<'_, PyAny>> {
     Ok(cls.bind(py))
 }
 
+/// Cached handle to `uuid.UUID`, for recognising UUID parameters.
+static UUID_CLASS: OnceLock<Option<Py<PyAny>>> = OnceLock::new();
+
+/// Return a `Bound` reference to `uuid.UUID`, importing it on first use.
+#[inline]
+pub fn uuid_class(py: Python<'_>) -> PyResult<&Bound<'_, PyAny>> {
+    let cls = UUID_CLASS
+        .get_or_init(|| {
+            py.import("uuid")
+                .and_then(|m| m.getattr("UUID"))
+                .map(|u| u.unbind())
+                .ok()
+        })
+        .as_ref()
+        .ok_or_else(|| PyValueError::new_err("Failed to initialize uuid.UUID"))?;
+    Ok(cls.bind(py))
+}
+
+/// Cached handle to the `xml.etree.ElementTree` module, for XML parameters.
+static ELEMENT_TREE: OnceLock<Option<Py<PyAny>>> = OnceLock::new();
+
+/// Return a `Bound` reference to `xml.etree.ElementTree`, importing it on first use.
+#[inline]
+pub fn element_tree(py: Python<'_>) -> PyResult<&Bound<'_, PyAny>> {
+    let module = ELEMENT_TREE
+        .get_or_init(|| {
+            py.import("xml.etree.ElementTree")
+                .map(|m| m.into_any().unbind())
+                .ok()
+        })
+        .as_ref()
+        .ok_or_else(|| PyValueError::new_err("Failed to initialize xml.etree.ElementTree"))?;
+    Ok(module.bind(py))
+}
+
+/// numpy's scalar base classes, for recognising numpy scalar parameters.
+pub struct NumpyScalarTypes {
+    pub generic: Py<PyAny>,
+    pub bool_: Py<PyAny>,
+    pub integer: Py<PyAny>,
+    pub floating: Py<PyAny>,
+}
+
+static NUMPY_SCALAR_TYPES: OnceLock<Option<NumpyScalarTypes>> = OnceLock::new();
+
+/// numpy's scalar classes, or `None` while numpy has not been imported.  numpy is
+/// never imported here: without it in `sys.modules` no numpy scalar can exist, so
+/// the lookup is retried until the application imports it.
+pub fn numpy_scalar_types(py: Python<'_>) -> Option<&NumpyScalarTypes> {
+    if let Some(types) = NUMPY_SCALAR_TYPES.get() {
+        return types.as_ref();
+    }
+    let numpy = py
+        .import("sys")
+        .and_then(|sys| sys.getattr("modules"))
+        .and_then(|modules| modules.get_item("numpy"))
+        .ok()?;
+    let class = |name: &str| numpy.getattr(name).map(|c| c.unbind());
+    let types = (|| {
+        Ok::<_, PyErr>(NumpyScalarTypes {
+            generic: class("generic")?,
+            bool_: class("bool_")?,
+            integer: class("integer")?,
+            floating: class("floating")?,
+        })
+    })()
+    .ok();
+    NUMPY_SCALAR_TYPES.get_or_init(|| types).as_ref()
+}
+
+/// Connection-level options that change how column values are decoded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DecodeOptions {
+    /// Strip trailing spaces from fixed-width CHAR/NCHAR values.
+    pub rstrip_char: bool,
+    /// Return TIME values as `datetime.timedelta` since midnight instead of
+    /// `datetime.time`, so they can be added to datetimes and subtracted.
+    pub time_as_timedelta: bool,
+    /// Which result columns are returned as parsed JSON (`parse_json`).
+    pub json: JsonMode,
+    /// Whether XML columns are returned as parsed elements (`parse_xml`).
+    pub xml: XmlMode,
+    /// Column count and row size guards (`max_result_columns`, `max_row_bytes`).
+    pub limits: ResultLimits,
+}
+
 /// Macro to eliminate boilerplate for identical scalar type conversions.
 macro_rules! impl_handle_scalar {
     ($name:ident, $t:ty, $lbl:expr) => {
@@ -33,19 +126,15 @@ macro_rules! impl_handle_scalar {
             match row.try_get::<$t, usize>(index) {
                 Ok(Some(val)) => Ok(val.into_pyobject(py)?.into_any().unbind()),
                 Ok(None) => Ok(py.None()),
-                Err(_) => Err(PyValueError::new_err(concat!(
-                    "Failed to convert column {} to ",
-                    $lbl
+                Err(_) => Err(PyValueError::new_err(format!(
+                    "Failed to convert column {} to {}",
+                    index, $lbl
                 ))),
             }
         }
     };
 }
 
-impl_handle_scalar!(handle_int1, u8, "INT1");
-impl_handle_scalar!(handle_int2, i16, "INT2");
-impl_handle_scalar!(handle_int4, i32, "INT4");
-impl_handle_scalar!(handle_int8, i64, "INT8");
 impl_handle_scalar!(handle_float4, f32, "FLOAT4");
 impl_handle_scalar!(handle_float8, f64, "FLOAT8");
 
@@ -73,14 +162,24 @@ fn handle_varchar(row: &Row, index: usize, py: Python) -> PyResult<Py<PyAny>> {
     }
 }
 
+/// Fixed-width CHAR/NCHAR values arrive space-padded to the declared length.
 #[inline(always)]
-fn handle_bit(row: &Row, index: usize, py: Python) -> PyResult<Py<PyAny>> {
-    match row.try_get::<bool, usize>(index) {
-        Ok(Some(val)) => val.into_py_any(py),
+fn handle_char(
+    row: &Row,
+    index: usize,
+    rstrip: bool,
+    label: &'static str,
+    py: Python,
+) -> PyResult<Py<PyAny>> {
+    match row.try_get::<&str, usize>(index) {
+        Ok(Some(val)) => {
+            let val = if rstrip { val.trim_end_matches(' ') } else { val };
+            Ok(val.into_pyobject(py)?.into_any().unbind())
+        }
         Ok(None) => Ok(py.None()),
         Err(_) => Err(PyValueError::new_err(format!(
-            "Failed to convert column {} to BIT",
-            index
+            "Failed to convert column {} to {}",
+            index, label
         ))),
     }
 }
@@ -97,11 +196,43 @@ fn handle_binary(row: &Row, index: usize, py: Python) -> PyResult<Py<PyAny>> {
     }
 }
 
+/// TEXT/NTEXT values are read in full: the login packet sets TEXTSIZE to
+/// unlimited, and tiberius decodes the text pointer and payload into one string
+/// (TEXT through its column collation, NTEXT as UTF-16).
+#[inline(always)]
+fn handle_legacy_text(
+    row: &Row,
+    index: usize,
+    label: &'static str,
+    py: Python,
+) -> PyResult<Py<PyAny>> {
+    match row.try_get::<&str, usize>(index) {
+        Ok(Some(val)) => Ok(val.into_pyobject(py)?.into_any().unbind()),
+        Ok(None) => Ok(py.None()),
+        Err(e) => Err(PyValueError::new_err(format!(
+            "Failed to convert column {} to {}: {}",
+            index, label, e
+        ))),
+    }
+}
+
+#[inline(always)]
+fn handle_image(row: &Row, index: usize, py: Python) -> PyResult<Py<PyAny>> {
+    match row.try_get::<&[u8], usize>(index) {
+        Ok(Some(val)) => Ok(val.into_pyobject(py)?.into_any().unbind()),
+        Ok(None) => Ok(py.None()),
+        Err(e) => Err(PyValueError::new_err(format!(
+            "Failed to convert column {} to IMAGE: {}",
+            index, e
+        ))),
+    }
+}
+
 #[inline(always)]
 fn handle_money(row: &Row, index: usize, py: Python) -> PyResult<Py<PyAny>> {
     match row.try_get::<f64, usize>(index) {
         Ok(Some(val)) => {
-            let decimal_class = get_decimal_class(py)?;
+            let decimal_class = decimal_class(py)?;
             // Avoids floating-point math traps by formatting via string conversion directly
             let s = format!("{:.4}", val);
             Ok(decimal_class.call1((s,))?.unbind())
@@ -118,7 +249,7 @@ fn handle_money(row: &Row, index: usize, py: Python) -> PyResult<Py<PyAny>> {
 fn handle_money4(row: &Row, index: usize, py: Python) -> PyResult<Py<PyAny>> {
     match row.try_get::<f64, usize>(index) {
         Ok(Some(val)) => {
-            let decimal_class = get_decimal_class(py)?;
+            let decimal_class = decimal_class(py)?;
             let s = format!("{:.4}", val);
             Ok(decimal_class.call1((s,))?.unbind())
         }
@@ -142,7 +273,7 @@ fn handle_money4(row: &Row, index: usize, py: Python) -> PyResult<Py<PyAny>> {
 /// This function builds the string directly from the raw `value` (i128) and
 /// `scale` (u8), correctly handling all sign/magnitude combinations.
 #[inline]
-fn numeric_to_decimal_string(numeric: tiberius::numeric::Numeric) -> String {
+pub fn numeric_to_decimal_string(numeric: tiberius::numeric::Numeric) -> String {
     let value = numeric.value();
     let scale = numeric.scale() as usize;
 
@@ -164,7 +295,7 @@ fn numeric_to_decimal_string(numeric: tiberius::numeric::Numeric) -> String {
 fn handle_decimal(row: &Row, index: usize, py: Python) -> PyResult<Py<PyAny>> {
     match row.try_get::<tiberius::numeric::Numeric, usize>(index) {
         Ok(Some(numeric)) => {
-            let decimal_class = get_decimal_class(py)?;
+            let decimal_class = decimal_class(py)?;
             let s = numeric_to_decimal_string(numeric);
             Ok(decimal_class.call1((s,))?.unbind())
         }
@@ -188,6 +319,20 @@ fn handle_datetime(row: &Row, index: usize, py: Python) -> PyResult<Py<PyAny>> {
     }
 }
 
+/// SMALLDATETIME has one-minute precision, so the decoded value always has zero
+/// seconds and microseconds.
+#[inline(always)]
+fn handle_smalldatetime(row: &Row, index: usize, py: Python) -> PyResult<Py<PyAny>> {
+    match row.try_get::<chrono::NaiveDateTime, usize>(index) {
+        Ok(Some(val)) => val.into_py_any(py),
+        Ok(None) => Ok(py.None()),
+        Err(_) => Err(PyValueError::new_err(format!(
+            "Failed to convert column {} to SMALLDATETIME",
+            index
+        ))),
+    }
+}
+
 #[inline(always)]
 fn handle_date(row: &Row, index: usize, py: Python) -> PyResult<Py<PyAny>> {
     match row.try_get::<chrono::NaiveDate, usize>(index) {
@@ -201,8 +346,9 @@ fn handle_date(row: &Row, index: usize, py: Python) -> PyResult<Py<PyAny>> {
 }
 
 #[inline(always)]
-fn handle_time(row: &Row, index: usize, py: Python) -> PyResult<Py<PyAny>> {
+fn handle_time(row: &Row, index: usize, as_timedelta: bool, py: Python) -> PyResult<Py<PyAny>> {
     match row.try_get::<chrono::NaiveTime, usize>(index) {
+        Ok(Some(val)) if as_timedelta => (val - chrono::NaiveTime::MIN).into_py_any(py),
         Ok(Some(val)) => Ok(val.into_py_any(py)?),
         Ok(None) => Ok(py.None()),
         Err(_) => Err(PyValueError::new_err(format!(
@@ -241,12 +387,9 @@ fn handle_uuid(row: &Row, index: usize, py: Python) -> PyResult<Py<PyAny>> {
 }
 
 #[inline(always)]
-fn handle_xml(row: &Row, index: usize, py: Python) -> PyResult<Py<PyAny>> {
+fn handle_xml(row: &Row, index: usize, mode: XmlMode, py: Python) -> PyResult<Py<PyAny>> {
     match row.try_get::<&tiberius::xml::XmlData, usize>(index) {
-        Ok(Some(xml_data)) => {
-            let xml_str = xml_data.to_string();
-            Ok(xml_str.into_pyobject(py)?.into_any().unbind())
-        }
+        Ok(Some(xml_data)) => xml_to_python(py, xml_data.as_ref(), mode, index),
         Ok(None) => Ok(py.None()),
         Err(_) => Err(PyValueError::new_err(format!(
             "Failed to convert column {} to XML",
@@ -255,52 +398,6 @@ fn handle_xml(row: &Row, index: usize, py: Python) -> PyResult<Py<PyAny>> {
     }
 }
 
-#[inline(always)]
-fn handle_nchar(row: &Row, index: usize, py: Python) -> PyResult<Py<PyAny>> {
-    match row.try_get::<&str, usize>(index) {
-        Ok(Some(val)) => Ok(val.into_pyobject(py)?.into_any().unbind()),
-        Ok(None) => Ok(py.None()),
-        Err(_) => Err(PyValueError::new_err(format!(
-            "Failed to convert column {} to NCHAR",
-            index
-        ))),
-    }
-}
-
-/// Handle SQL Server's variable-length nullable integer type (`Intn`).
-/// Ordered by demographic likelihood (INT/INT4 and BIGINT/INT8 are statistically primary).
-#[inline(always)]
-fn handle_intn(row: &Row, index: usize, py: Python) -> PyResult<Py<PyAny>> {
-    // 4-byte: INT (Statistically most common database target)
-    if let Ok(Some(val)) = row.try_get::<i32, usize>(index) {
-        return Ok((val as i64).into_pyobject(py)?.into_any().unbind());
-    }
-    // 8-byte: BIGINT
-    if let Ok(Some(val)) = row.try_get::<i64, usize>(index) {
-        return Ok(val.into_pyobject(py)?.into_any().unbind());
-    }
-    // 2-byte: SMALLINT
-    if let Ok(Some(val)) = row.try_get::<i16, usize>(index) {
-        return Ok((val as i64).into_pyobject(py)?.into_any().unbind());
-    }
-    // 1-byte: TINYINT
-    if let Ok(Some(val)) = row.try_get::<u8, usize>(index) {
-        return Ok((val as i64).into_pyobject(py)?.into_any().unbind());
-    }
-
-    // Check for explicit SQL NULL execution across any variant match
-    if row.try_get::<i32, usize>(index).map(|v| v.is_none()).unwrap_or(false)
-        || row.try_get::<i64, usize>(index).map(|v| v.is_none()).unwrap_or(false)
-    {
-        return Ok(py.None());
-    }
-
-    Err(PyValueError::new_err(format!(
-        "Failed to convert column {} to integer",
-        index
-    )))
-}
-
 #[inline(always)]
 fn handle_floatn(row: &Row, index: usize, py: Python) -> PyResult<Py<PyAny>> {
     // 8-byte: FLOAT
@@ -334,42 +431,97 @@ fn handle_fallback(row: &Row, index: usize, py: Python) -> PyResult<Py<PyAny>> {
     }
 }
 
+/// SQL Server type name for a wire column type.
+///
+/// The TDS metadata only carries the wire type, so nullable integer/float columns
+/// whose width is not known map to the generic `int`/`float`, and `datetimen` to
+/// `datetime`.
+pub fn column_type_name(col_type: ColumnType) -> &'static str {
+    match col_type {
+        ColumnType::Null => "null",
+        ColumnType::Bit | ColumnType::Bitn => "bit",
+        ColumnType::Int1 => "tinyint",
+        ColumnType::Int2 => "smallint",
+        ColumnType::Int4 | ColumnType::Intn => "int",
+        ColumnType::Int8 => "bigint",
+        ColumnType::Float4 => "real",
+        ColumnType::Float8 | ColumnType::Floatn => "float",
+        ColumnType::Money => "money",
+        ColumnType::Money4 => "smallmoney",
+        ColumnType::Datetime | ColumnType::Datetimen => "datetime",
+        ColumnType::Datetime4 => "smalldatetime",
+        ColumnType::Datetime2 => "datetime2",
+        ColumnType::Daten => "date",
+        ColumnType::Timen => "time",
+        ColumnType::DatetimeOffsetn => "datetimeoffset",
+        ColumnType::Decimaln => "decimal",
+        ColumnType::Numericn => "numeric",
+        ColumnType::Guid => "uniqueidentifier",
+        ColumnType::BigVarBin => "varbinary",
+        ColumnType::BigBinary => "binary",
+        ColumnType::BigVarChar => "varchar",
+        ColumnType::BigChar => "char",
+        ColumnType::NVarchar => "nvarchar",
+        ColumnType::NChar => "nchar",
+        ColumnType::Xml => "xml",
+        ColumnType::Udt => "udt",
+        ColumnType::Text => "text",
+        ColumnType::NText => "ntext",
+        ColumnType::Image => "image",
+        ColumnType::SSVariant => "sql_variant",
+    }
+}
+
+/// True for the UTF-16 string types (NCHAR, NVARCHAR, NTEXT).
+pub fn is_unicode_type(col_type: ColumnType) -> bool {
+    matches!(
+        col_type,
+        ColumnType::NChar | ColumnType::NVarchar | ColumnType::NText
+    )
+}
+
 pub fn sql_to_python(
     row: &Row,
     index: usize,
     col_type: ColumnType,
+    options: DecodeOptions,
     py: Python,
 ) -> PyResult<Py<PyAny>> {
     match col_type {
-        ColumnType::Int4 => handle_int4(row, index, py),
-        ColumnType::Int8 => handle_int8(row, index, py),
-        ColumnType::Int1 => handle_int1(row, index, py),
-        ColumnType::Int2 => handle_int2(row, index, py),
-        ColumnType::Intn => handle_intn(row, index, py),
+        // BIT and every integer width share one conversion; see integer_columns.
+        ColumnType::Int4
+        | ColumnType::Int8
+        | ColumnType::Int1
+        | ColumnType::Int2
+        | ColumnType::Intn
+        | ColumnType::Bit
+        | ColumnType::Bitn => integer_to_python(row, index, py),
         ColumnType::Float8 => handle_float8(row, index, py),
         ColumnType::Float4 => handle_float4(row, index, py),
         ColumnType::Floatn => handle_floatn(row, index, py),
         ColumnType::NVarchar => handle_nvarchar(row, index, py),
-        ColumnType::NChar => handle_nchar(row, index, py),
-        ColumnType::BigVarChar | ColumnType::BigChar => handle_varchar(row, index, py),
-        ColumnType::Text => handle_varchar(row, index, py),
-        ColumnType::NText => handle_nvarchar(row, index, py),
-        ColumnType::Image => handle_binary(row, index, py),
-        ColumnType::Bit | ColumnType::Bitn => handle_bit(row, index, py),
+        ColumnType::NChar => handle_char(row, index, options.rstrip_char, "NCHAR", py),
+        ColumnType::BigChar => handle_char(row, index, options.rstrip_char, "CHAR", py),
+        ColumnType::BigVarChar => handle_varchar(row, index, py),
+        ColumnType::Text => handle_legacy_text(row, index, "TEXT", py),
+        ColumnType::NText => handle_legacy_text(row, index, "NTEXT", py),
+        ColumnType::Image => handle_image(row, index, py),
         ColumnType::Money => handle_money(row, index, py),
         ColumnType::Money4 => handle_money4(row, index, py),
         ColumnType::Decimaln | ColumnType::Numericn => handle_decimal(row, index, py),
         ColumnType::Datetime | ColumnType::Datetimen | ColumnType::Datetime2 => {
             handle_datetime(row, index, py)
         }
-        ColumnType::Datetime4 => handle_datetime(row, index, py),
+        ColumnType::Datetime4 => handle_smalldatetime(row, index, py),
         ColumnType::Daten => handle_date(row, index, py),
-        ColumnType::Timen => handle_time(row, index, py),
+        ColumnType::Timen => handle_time(row, index, options.time_as_timedelta, py),
         ColumnType::DatetimeOffsetn => handle_datetimeoffset(row, index, py),
         ColumnType::Guid => handle_uuid(row, index, py),
-        ColumnType::Xml => handle_xml(row, index, py),
+        ColumnType::Xml => handle_xml(row, index, options.xml, py),
         ColumnType::SSVariant => handle_fallback(row, index, py),
         ColumnType::BigVarBin => handle_binary(row, index, py),
+        // TIMESTAMP/ROWVERSION arrives as BINARY(8) (tiberius drops the TDS user type),
+        // so it is returned as 8 big-endian bytes; FastRow.rowversion() decodes it to int.
         ColumnType::BigBinary => handle_binary(row, index, py),
         ColumnType::Udt => handle_fallback(row, index, py),
         ColumnType::Null => Ok(py.None()),
@@ -378,7 +530,11 @@ pub fn sql_to_python(
 
 pub fn is_expandable_iterable(obj: &Bound<PyAny>) -> PyResult<bool> {
     // Fast path: scalar types
-    if obj.is_instance_of::<PyString>() || obj.is_instance_of::<PyBytes>() {
+    if obj.is_instance_of::<PyString>()
+        || obj.is_instance_of::<PyBytes>()
+        || obj.is_instance_of::<PyByteArray>()
+        || obj.is_instance_of::<PyMemoryView>()
+    {
         return Ok(false);
     }
 
@@ -392,5 +548,5 @@ pub fn is_expandable_iterable(obj: &Bound<PyAny>) -> PyResult<bool> {
     }
 
     // Dynamic fallback with string lookup tracking optimization
-    Ok(obj.hasattr(pyo3::intern!(obj.py(), "__iter__"))?)
+    obj.hasattr(pyo3::intern!(obj.py(), "__iter__"))
 }
\ No newline at end of file
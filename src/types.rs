@@ -1,17 +1,211 @@
-use crate::type_mapping;
+use crate::column_set::parse_column_set;
+use crate::integer_columns;
+use crate::json_columns::{FOR_JSON_COLUMN, json_to_python};
+use crate::type_mapping::{self, DecodeOptions};
 use ahash::AHashMap as HashMap;
-use pyo3::exceptions::{PyException, PyRuntimeError};
+use pyo3::exceptions::{PyException, PyUserWarning};
 use pyo3::prelude::*;
-use pyo3::types::PyDict;
+use pyo3::sync::PyOnceLock;
+use pyo3::types::{
+    PyBool, PyBytes, PyDate, PyDateTime, PyDelta, PyDict, PyFloat, PyInt, PyString, PyTime,
+    PyTuple, PyType,
+};
 use pyo3::{create_exception, exceptions::PyValueError};
-use std::sync::Arc;
-use tiberius::{ColumnType, Row, error::Error as TError};
+use std::sync::{Arc, Mutex, OnceLock};
+use tiberius::{Column, ColumnType, ResultMetadata, Row, error::Error as TError};
+
+// FastMssqlError
+// ├── InterfaceError          misuse of the API (e.g. Transaction used before begin())
+// │   ├── PolicyViolation     statement rejected by the connection's statement_policy
+// │   └── ResultLimitError    result wider than max_result_columns / max_row_bytes
+// └── DatabaseError
+//     ├── SqlError            error reported by the server (.code, .state, .message)
+//     │   ├── IntegrityError  constraint violations (2627, 2601, 547, 515)
+//     │   └── ProgrammingError  invalid SQL or object names (102, 207, 208, ...)
+//     ├── OperationalError
+//     │   ├── SqlConnectionError  network, routing and pool problems
+//     │   │   ├── TlsError
+//     │   │   └── PoolTimeoutError
+//     │   ├── AuthenticationError  login failures and Azure token errors
+//     │   │   ├── InvalidCredentialsError  unknown login or wrong password (also a SqlError)
+//     │   │   ├── LoginDisabledError  disabled, locked out or expired login (also a SqlError)
+//     │   │   └── DatabaseUnavailableError  database not accessible (also a SqlError)
+//     │   └── QueryTimeoutError  statement cancelled after its `timeout`
+//     ├── ProtocolError
+//     └── ConversionError
+//         └── DataError
+create_exception!(crate::fastmssql, FastMssqlError, PyException);
+create_exception!(crate::fastmssql, InterfaceError, FastMssqlError);
+create_exception!(crate::fastmssql, PolicyViolation, InterfaceError);
+create_exception!(crate::fastmssql, ResultLimitError, InterfaceError);
+create_exception!(crate::fastmssql, DatabaseError, FastMssqlError);
+create_exception!(crate::fastmssql, SqlError, DatabaseError);
+create_exception!(crate::fastmssql, IntegrityError, SqlError);
+create_exception!(crate::fastmssql, ProgrammingError, SqlError);
+create_exception!(crate::fastmssql, OperationalError, DatabaseError);
+create_exception!(crate::fastmssql, SqlConnectionError, OperationalError);
+create_exception!(crate::fastmssql, TlsError, SqlConnectionError);
+create_exception!(crate::fastmssql, PoolTimeoutError, SqlConnectionError);
+create_exception!(crate::fastmssql, AuthenticationError, OperationalError);
+create_exception!(crate::fastmssql, QueryTimeoutError, OperationalError);
+create_exception!(crate::fastmssql, ProtocolError, DatabaseError);
+create_exception!(crate::fastmssql, ConversionError, DatabaseError);
+create_exception!(crate::fastmssql, DataError, ConversionError);
+
+// Warning category for server certificates close to expiry (see cert_expiry.rs).
+create_exception!(crate::fastmssql, CertificateExpiryWarning, PyUserWarning);
+
+/// A login error class deriving from both `AuthenticationError` and `SqlError`: these
+/// errors are reported by the server like any other, and `except SqlError` caught them
+/// before they had classes of their own.  `create_exception!` takes a single base, so
+/// the class is built with `type()`.
+fn login_error_type<'py>(
+    py: Python<'py>,
+    cell: &'static PyOnceLock<Py<PyType>>,
+    name: &str,
+) -> Bound<'py, PyType> {
+    cell.get_or_init(py, || {
+        let authentication = py.get_type::<AuthenticationError>();
+        let namespace = PyDict::new(py);
+        authentication
+            .getattr("__module__")
+            .and_then(|module| namespace.set_item("__module__", module))
+            .and_then(|()| {
+                let bases = PyTuple::new(py, [authentication, py.get_type::<SqlError>()])?;
+                py.get_type::<PyType>().call1((name, bases, namespace))
+            })
+            .and_then(|class| Ok(class.cast_into::<PyType>()?.unbind()))
+            .expect("Failed to initialize new exception type.")
+    })
+    .bind(py)
+    .clone()
+}
+
+/// Unknown login or wrong password.
+pub fn invalid_credentials_error(py: Python<'_>) -> Bound<'_, PyType> {
+    static CLASS: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+    login_error_type(py, &CLASS, "InvalidCredentialsError")
+}
+
+/// Disabled, locked out or expired login.
+pub fn login_disabled_error(py: Python<'_>) -> Bound<'_, PyType> {
+    static CLASS: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+    login_error_type(py, &CLASS, "LoginDisabledError")
+}
+
+/// Database missing, offline or not accessible to the login.
+pub fn database_unavailable_error(py: Python<'_>) -> Bound<'_, PyType> {
+    static CLASS: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+    login_error_type(py, &CLASS, "DatabaseUnavailableError")
+}
+
+/// Exception class for a server error number: constraint violations, invalid SQL and
+/// login failures get their own subclasses, everything else is a plain `SqlError`.
+fn server_error_type(py: Python<'_>, code: u32, state: u8) -> Bound<'_, pyo3::types::PyType> {
+    match code {
+        // Duplicate key (unique constraint / unique index), FK or CHECK conflict, NULL
+        // into a NOT NULL column.
+        2627 | 2601 | 547 | 515 => py.get_type::<IntegrityError>(),
+        // Syntax errors, unknown column / object / procedure, undeclared variable,
+        // missing or surplus procedure arguments.
+        102 | 105 | 156 | 207 | 208 | 137 | 201 | 2812 | 8144 | 8145 => {
+            py.get_type::<ProgrammingError>()
+        }
+        // Login failed; the state says why, but on-premises servers always report 1.
+        18456 => match state {
+            2 | 5 | 6 | 8 | 9 => invalid_credentials_error(py),
+            7 | 18 => login_disabled_error(py),
+            38 | 40 => database_unavailable_error(py),
+            _ => py.get_type::<AuthenticationError>(),
+        },
+        // Account disabled, locked out, password expired or to be changed.
+        18470 | 18486 | 18487 | 18488 => login_disabled_error(py),
+        // Cannot open the database requested by the login.
+        4060 => database_unavailable_error(py),
+        // Untrusted domain.
+        18452 => py.get_type::<AuthenticationError>(),
+        _ => py.get_type::<SqlError>(),
+    }
+}
+
+/// What to check when the server refuses a login, for the errors a wrong connection
+/// string or account setup runs into.
+fn login_error_hint(code: u32, state: u8) -> Option<&'static str> {
+    Some(match (code, state) {
+        (18456, 2 | 5) => "the login does not exist on this server; check User Id",
+        (18456, 6) => {
+            "a Windows account name was used for SQL Server authentication; \
+             use a SQL login or Windows authentication"
+        }
+        (18456, 8 | 9) => "the password is wrong; check Password",
+        (18456, 7) => "the login is disabled; an administrator can run ALTER LOGIN ... ENABLE",
+        (18456, 18) | (18488, _) => "the login's password must be changed before it can be used",
+        (18456, 11 | 12) => {
+            "the login is valid but may not connect to the server; \
+             it needs the CONNECT SQL permission"
+        }
+        (18456, 38) | (4060, _) => {
+            "check Database: the database may not exist, be offline, or have no user \
+             for this login"
+        }
+        (18456, 40) => "the login's default database is unavailable; name one with Database",
+        (18456, 58) => {
+            "the server only accepts Windows authentication; enable SQL Server and \
+             Windows authentication mode on the server"
+        }
+        (18456, _) => "check User Id and Password; the server's error log gives the exact reason",
+        (18452, _) => {
+            "the login is from an untrusted domain; use SQL Server authentication or a \
+             client in a trusted domain"
+        }
+        (18470, _) => "the login is disabled; an administrator can run ALTER LOGIN ... ENABLE",
+        (18486, _) => {
+            "the login is locked out after too many failed attempts; an administrator \
+             can run ALTER LOGIN ... WITH PASSWORD = '...' UNLOCK"
+        }
+        (18487, _) => "the login's password has expired and must be changed",
+        _ => return None,
+    })
+}
+
+/// What to check when the TLS handshake with the server fails.
+fn tls_error_hint(message: &str) -> &'static str {
+    let message = message.to_ascii_lowercase();
+    if message.contains("unknownissuer") || message.contains("unknown issuer") {
+        "the server certificate is not signed by a trusted authority; pass its CA with \
+         SslConfig.with_ca_certificate(), or TrustServerCertificate=true in development"
+    } else if message.contains("notvalidforname") || message.contains("not valid for name") {
+        "the server certificate does not name the host connected to; connect with the \
+         host name on the certificate"
+    } else if message.contains("expired") {
+        "the server certificate has expired and must be renewed on the server"
+    } else {
+        "check Encrypt: the server may not support encryption or a TLS version the \
+         client accepts (1.2 or later)"
+    }
+}
 
-create_exception!(crate::fastmssql, SqlError, PyException);
-create_exception!(crate::fastmssql, SqlConnectionError, PyException);
-create_exception!(crate::fastmssql, TlsError, PyException);
-create_exception!(crate::fastmssql, ProtocolError, PyException);
-create_exception!(crate::fastmssql, ConversionError, PyException);
+/// Exception of type `exc_type` with `.message` and `.hint` set; the hint is appended
+/// to `text` so it shows up in tracebacks.
+fn with_hint<'py>(
+    exc_type: Bound<'py, pyo3::types::PyType>,
+    text: String,
+    message: &str,
+    hint: Option<&str>,
+) -> PyErr {
+    let py = exc_type.py();
+    let text = match hint {
+        Some(hint) => format!("{text} (hint: {hint})"),
+        None => text,
+    };
+    let exc = PyErr::from_type(exc_type, text);
+    {
+        let value = exc.value(py);
+        let _ = value.setattr("message", message);
+        let _ = value.setattr("hint", hint);
+    }
+    exc
+}
 
 pub fn create_sql_error(err: TError, base: &'static str) -> PyErr {
     match err {
@@ -19,13 +213,24 @@ pub fn create_sql_error(err: TError, base: &'static str) -> PyErr {
             let code = s.code();
             let message = s.message().to_string();
             let state = s.state();
+            // Empty when the error was not raised inside a stored procedure.
+            let procedure = Some(s.procedure()).filter(|p| !p.is_empty());
             Python::attach(|py| {
-                let exc = SqlError::new_err(message.clone());
+                let exc = with_hint(
+                    server_error_type(py, code, state),
+                    message.clone(),
+                    &message,
+                    login_error_hint(code, state),
+                );
                 {
                     let value = exc.value(py);
                     let _ = value.setattr("code", code);
-                    let _ = value.setattr("message", message.as_str());
+                    let _ = value.setattr("number", code);
                     let _ = value.setattr("state", state);
+                    let _ = value.setattr("class_", s.class());
+                    let _ = value.setattr("server", s.server());
+                    let _ = value.setattr("procedure", procedure);
+                    let _ = value.setattr("line_number", s.line());
                 }
                 exc
             })
@@ -36,9 +241,12 @@ pub fn create_sql_error(err: TError, base: &'static str) -> PyErr {
             exc
         }),
         TError::Tls(msg) => Python::attach(|py| {
-            let exc = TlsError::new_err(format!("{base}: {msg}"));
-            let _ = exc.value(py).setattr("message", msg.as_str());
-            exc
+            with_hint(
+                py.get_type::<TlsError>(),
+                format!("{base}: {msg}"),
+                &msg,
+                Some(tls_error_hint(&msg)),
+            )
         }),
         TError::Routing { host, port } => {
             let message = format!("server redirected to {host}:{port}");
@@ -77,10 +285,21 @@ pub fn create_sql_error(err: TError, base: &'static str) -> PyErr {
                 exc
             })
         }
-        _ => PyRuntimeError::new_err(format!("{base}: {err}")),
+        _ => DatabaseError::new_err(format!("{base}: {err}")),
     }
 }
 
+/// Creates an `AuthenticationError` with the `.message` attribute set, for failures to
+/// obtain credentials (e.g. an Azure token) before the server is contacted.
+pub fn create_auth_error(message: impl Into<String>) -> PyErr {
+    let message = message.into();
+    Python::attach(|py| {
+        let exc = AuthenticationError::new_err(message.clone());
+        let _ = exc.value(py).setattr("message", message.as_str());
+        exc
+    })
+}
+
 /// Creates a `SqlConnectionError` with the `.message` attribute set to the provided message.
 pub fn create_connection_error(message: impl Into<String>) -> PyErr {
     let message = message.into();
@@ -91,6 +310,29 @@ pub fn create_connection_error(message: impl Into<String>) -> PyErr {
     })
 }
 
+/// Creates a `DataError` for a typed row getter, with `.column` and `.message` set.
+fn data_error(column: &Bound<PyAny>, value: &Bound<PyAny>, expected: &str) -> PyErr {
+    let py = column.py();
+    let type_name = value
+        .get_type()
+        .name()
+        .map(|n| n.to_string())
+        .unwrap_or_else(|_| "unknown".to_string());
+    let message = format!(
+        "Column {} holds a {} value, expected {}",
+        column.repr().map(|r| r.to_string()).unwrap_or_default(),
+        type_name,
+        expected
+    );
+    let exc = DataError::new_err(message.clone());
+    {
+        let value = exc.value(py);
+        let _ = value.setattr("column", column);
+        let _ = value.setattr("message", message.as_str());
+    }
+    exc
+}
+
 /// Memory-optimized to share column metadata across all rows in a result set.
 /// Holds shared column information for a result set to reduce memory usage.
 /// This is shared across all `PyFastRow` instances in a result set.
@@ -102,13 +344,37 @@ pub struct ColumnInfo {
     pub map: HashMap<String, usize>,
     /// Cached column types (one per column) to avoid repeated lookups during value conversion
     pub column_types: Vec<ColumnType>,
+    /// Decoding options of the connection that produced the result set
+    pub options: DecodeOptions,
+    /// Columns whose values are parsed as JSON (one flag per column)
+    pub json_columns: Vec<bool>,
+    /// Column names as interned Python strings, created on first use and shared by
+    /// every row's `to_dict()`, `keys()` and `columns()`
+    py_names: OnceLock<Vec<Py<PyString>>>,
+}
+
+impl ColumnInfo {
+    /// Column names as Python strings, interned once per result set.
+    pub fn py_names(&self, py: Python<'_>) -> &[Py<PyString>] {
+        self.py_names.get_or_init(|| {
+            self.names
+                .iter()
+                .map(|name| PyString::intern(py, name).unbind())
+                .collect()
+        })
+    }
+
+    fn py_names_list<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, pyo3::types::PyList>> {
+        pyo3::types::PyList::new(py, self.py_names(py))
+    }
 }
 
 /// Memory-optimized to share column metadata across all rows in a result set.
 #[pyclass(name = "FastRow", from_py_object)]
 pub struct PyFastRow {
-    // Row values stored in column order for cache-friendly access
-    values: Vec<Py<PyAny>>,
+    // Row values stored in column order for cache-friendly access; a boxed slice
+    // rather than a Vec saves the capacity word on every row
+    values: Box<[Py<PyAny>]>,
     // Shared pointer to column metadata for the entire result set
     column_info: Arc<ColumnInfo>,
 }
@@ -122,7 +388,22 @@ impl Clone for PyFastRow {
     }
 }
 
+/// True if `value` is a `decimal.Decimal`.
+fn is_decimal(value: &Bound<PyAny>) -> PyResult<bool> {
+    value.is_instance(type_mapping::decimal_class(value.py())?)
+}
+
 impl PyFastRow {
+    /// Column value by name or index, or None if it is NULL.
+    fn non_null<'py>(
+        &self,
+        py: Python<'py>,
+        column: &Bound<'py, PyAny>,
+    ) -> PyResult<Option<Bound<'py, PyAny>>> {
+        let value = self.__getitem__(py, column.clone())?.into_bound(py);
+        Ok(if value.is_none() { None } else { Some(value) })
+    }
+
     /// Create a new PyFastRow from a Tiberius row and shared column info
     pub fn from_tiberius_row(row: Row, py: Python, column_info: Arc<ColumnInfo>) -> PyResult<Self> {
         // Pre-allocate vector with exact capacity and cache num_columns to avoid repeated lookups
@@ -136,12 +417,19 @@ impl PyFastRow {
                 .get(i)
                 .copied()
                 .ok_or_else(|| PyValueError::new_err("Column type not found"))?;
-            let value = Self::extract_value_direct(&row, i, col_type, py)?;
+            let value = if column_info.json_columns[i] {
+                match row.try_get::<&str, usize>(i) {
+                    Ok(Some(text)) => json_to_python(py, text, &column_info.names[i])?,
+                    _ => py.None(),
+                }
+            } else {
+                Self::extract_value_direct(&row, i, col_type, column_info.options, py)?
+            };
             values.push(value);
         }
 
         Ok(PyFastRow {
-            values,
+            values: values.into_boxed_slice(),
             column_info,
         })
     }
@@ -153,9 +441,10 @@ impl PyFastRow {
         row: &Row,
         index: usize,
         col_type: ColumnType,
+        options: DecodeOptions,
         py: Python,
     ) -> PyResult<Py<PyAny>> {
-        type_mapping::sql_to_python(row, index, col_type, py)
+        type_mapping::sql_to_python(row, index, col_type, options, py)
     }
 }
 
@@ -191,9 +480,14 @@ impl PyFastRow {
         }
     }
 
-    /// Get all column names from shared column info - returns slice to avoid cloning
-    pub fn columns(&self) -> &[String] {
-        &self.column_info.names
+    /// Get all column names; the strings are shared by every row of the result
+    pub fn columns<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, pyo3::types::PyList>> {
+        self.column_info.py_names_list(py)
+    }
+
+    /// Column names, as for a mapping; same as `columns()`
+    pub fn keys<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, pyo3::types::PyList>> {
+        self.column_info.py_names_list(py)
     }
 
     /// Get number of columns
@@ -211,16 +505,207 @@ impl PyFastRow {
         self.__getitem__(py, index.into_pyobject(py)?.into_any())
     }
 
+    /// Decode a TIMESTAMP/ROWVERSION column (8 big-endian bytes) as an unsigned int.
+    /// Returns None for NULL; raises ValueError if the value is not 8 bytes of binary.
+    pub fn rowversion(&self, py: Python, column: Bound<PyAny>) -> PyResult<Option<u64>> {
+        let value = self.__getitem__(py, column)?;
+        let value = value.bind(py);
+        if value.is_none() {
+            return Ok(None);
+        }
+        let bytes = value
+            .cast::<PyBytes>()
+            .map_err(|_| PyValueError::new_err("rowversion column must contain binary data"))?
+            .as_bytes();
+        let raw: [u8; 8] = bytes.try_into().map_err(|_| {
+            PyValueError::new_err(format!(
+                "rowversion value must be 8 bytes, got {}",
+                bytes.len()
+            ))
+        })?;
+        Ok(Some(u64::from_be_bytes(raw)))
+    }
+
+    /// Integer value of a column (INT/BIGINT/SMALLINT/TINYINT/BIT or an integral
+    /// DECIMAL). Returns None for NULL; raises DataError for any other value.
+    pub fn get_int(&self, py: Python, column: Bound<PyAny>) -> PyResult<Option<i64>> {
+        let Some(value) = self.non_null(py, &column)? else {
+            return Ok(None);
+        };
+        let is_integral = value.is_instance_of::<PyInt>()
+            || (is_decimal(&value)? && value.eq(value.call_method0("to_integral_value")?)?);
+        if !is_integral {
+            return Err(data_error(&column, &value, "an integer"));
+        }
+        value
+            .call_method0("__int__")
+            .and_then(|int| int.extract::<i64>())
+            .map(Some)
+            .map_err(|_| data_error(&column, &value, "an integer that fits in 64 bits"))
+    }
+
+    /// Float value of a numeric column. Returns None for NULL; raises DataError for
+    /// non-numeric values.
+    pub fn get_float(&self, py: Python, column: Bound<PyAny>) -> PyResult<Option<f64>> {
+        let Some(value) = self.non_null(py, &column)? else {
+            return Ok(None);
+        };
+        if value.is_instance_of::<PyFloat>()
+            || value.is_instance_of::<PyInt>()
+            || is_decimal(&value)?
+        {
+            return Ok(Some(value.extract::<f64>()?));
+        }
+        Err(data_error(&column, &value, "a number"))
+    }
+
+    /// `decimal.Decimal` value of a numeric column; integers and floats are converted
+    /// (floats via their shortest repr). Returns None for NULL; raises DataError otherwise.
+    pub fn get_decimal(&self, py: Python, column: Bound<PyAny>) -> PyResult<Option<Py<PyAny>>> {
+        let Some(value) = self.non_null(py, &column)? else {
+            return Ok(None);
+        };
+        if is_decimal(&value)? {
+            return Ok(Some(value.unbind()));
+        }
+        let decimal = type_mapping::decimal_class(py)?;
+        if value.is_instance_of::<PyInt>() && !value.is_instance_of::<PyBool>() {
+            return Ok(Some(decimal.call1((value,))?.unbind()));
+        }
+        if value.is_instance_of::<PyFloat>() {
+            return Ok(Some(decimal.call1((value.repr()?,))?.unbind()));
+        }
+        Err(data_error(&column, &value, "a decimal"))
+    }
+
+    /// String value of a column. Returns None for NULL; raises DataError otherwise.
+    pub fn get_str(&self, py: Python, column: Bound<PyAny>) -> PyResult<Option<String>> {
+        let Some(value) = self.non_null(py, &column)? else {
+            return Ok(None);
+        };
+        if !value.is_instance_of::<PyString>() {
+            return Err(data_error(&column, &value, "a string"));
+        }
+        Ok(Some(value.extract()?))
+    }
+
+    /// Boolean value of a BIT column, or of an integer 0/1 such as a computed
+    /// `CASE WHEN ... THEN 1 ELSE 0 END` flag. Returns None for NULL; raises DataError
+    /// otherwise.
+    pub fn get_bool(&self, py: Python, column: Bound<PyAny>) -> PyResult<Option<bool>> {
+        let Some(value) = self.non_null(py, &column)? else {
+            return Ok(None);
+        };
+        match integer_columns::as_bool(&value)? {
+            Some(flag) => Ok(Some(flag)),
+            None => Err(data_error(&column, &value, "a bool or an integer 0 or 1")),
+        }
+    }
+
+    /// Bytes value of a binary column. Returns None for NULL; raises DataError otherwise.
+    pub fn get_bytes(&self, py: Python, column: Bound<PyAny>) -> PyResult<Option<Py<PyAny>>> {
+        let Some(value) = self.non_null(py, &column)? else {
+            return Ok(None);
+        };
+        if !value.is_instance_of::<PyBytes>() {
+            return Err(data_error(&column, &value, "bytes"));
+        }
+        Ok(Some(value.unbind()))
+    }
+
+    /// `datetime.datetime` value (naive, or aware for DATETIMEOFFSET). Returns None for
+    /// NULL; raises DataError otherwise.
+    pub fn get_datetime(&self, py: Python, column: Bound<PyAny>) -> PyResult<Option<Py<PyAny>>> {
+        let Some(value) = self.non_null(py, &column)? else {
+            return Ok(None);
+        };
+        if !value.is_instance_of::<PyDateTime>() {
+            return Err(data_error(&column, &value, "a datetime"));
+        }
+        Ok(Some(value.unbind()))
+    }
+
+    /// `datetime.date` value; datetimes are truncated to their date. Returns None for
+    /// NULL; raises DataError otherwise.
+    pub fn get_date(&self, py: Python, column: Bound<PyAny>) -> PyResult<Option<Py<PyAny>>> {
+        let Some(value) = self.non_null(py, &column)? else {
+            return Ok(None);
+        };
+        if value.is_instance_of::<PyDateTime>() {
+            return Ok(Some(value.call_method0("date")?.unbind()));
+        }
+        if !value.is_instance_of::<PyDate>() {
+            return Err(data_error(&column, &value, "a date"));
+        }
+        Ok(Some(value.unbind()))
+    }
+
+    /// `datetime.time` value of a TIME column, also when the connection returns TIME as
+    /// `timedelta`. Returns None for NULL; raises DataError otherwise.
+    pub fn get_time(&self, py: Python, column: Bound<PyAny>) -> PyResult<Option<Py<PyAny>>> {
+        let Some(value) = self.non_null(py, &column)? else {
+            return Ok(None);
+        };
+        if value.is_instance_of::<PyDelta>()
+            && let Ok(since_midnight) = value.extract::<chrono::TimeDelta>()
+            && since_midnight >= chrono::TimeDelta::zero()
+            && since_midnight < chrono::TimeDelta::days(1)
+        {
+            let time = chrono::NaiveTime::MIN + since_midnight;
+            return Ok(Some(time.into_pyobject(py)?.into_any().unbind()));
+        }
+        if !value.is_instance_of::<PyTime>() {
+            return Err(data_error(&column, &value, "a time"));
+        }
+        Ok(Some(value.unbind()))
+    }
+
+    /// `uuid.UUID` value of a UNIQUEIDENTIFIER column (returned as a string by default).
+    /// Returns None for NULL; raises DataError if the value is not a UUID.
+    pub fn get_uuid(&self, py: Python, column: Bound<PyAny>) -> PyResult<Option<Py<PyAny>>> {
+        let Some(value) = self.non_null(py, &column)? else {
+            return Ok(None);
+        };
+        if value.is_instance_of::<PyString>() {
+            let uuid_class = py.import("uuid")?.getattr("UUID")?;
+            if let Ok(uuid) = uuid_class.call1((&value,)) {
+                return Ok(Some(uuid.unbind()));
+            }
+        }
+        Err(data_error(&column, &value, "a UUID"))
+    }
+
+    /// Decode a sparse COLUMN_SET value into a dict of the non-NULL sparse columns
+    /// (values are their XML text). Returns None for NULL; raises ValueError if the
+    /// column does not hold column-set XML.
+    pub fn column_set(&self, py: Python, column: Bound<PyAny>) -> PyResult<Option<Py<PyDict>>> {
+        let value = self.__getitem__(py, column)?;
+        let value = value.bind(py);
+        if value.is_none() {
+            return Ok(None);
+        }
+        let xml = value
+            .extract::<&str>()
+            .map_err(|_| PyValueError::new_err("column set column must contain XML"))?;
+        let columns = parse_column_set(xml).map_err(PyValueError::new_err)?;
+
+        let dict = PyDict::new(py);
+        for (name, text) in columns {
+            dict.set_item(name, text)?;
+        }
+        Ok(Some(dict.unbind()))
+    }
+
     /// Get all values as a list - optimized to minimize cloning
     pub fn values(&self, py: Python) -> PyResult<Py<pyo3::types::PyList>> {
         Ok(pyo3::types::PyList::new(py, &self.values)?.into())
     }
 
-    /// Convert to dictionary - optimized with zip iterator
+    /// Convert to dictionary, keyed by the result's interned column names
     pub fn to_dict(&self, py: Python) -> PyResult<Py<PyAny>> {
         let dict = PyDict::new(py);
 
-        for (name, value) in self.column_info.names.iter().zip(self.values.iter()) {
+        for (name, value) in self.column_info.py_names(py).iter().zip(self.values.iter()) {
             dict.set_item(name, value)?;
         }
 
@@ -240,13 +725,20 @@ impl PyFastRow {
 
 /// Helper to build column info from the first row
 /// Caches both column names and types for efficient value conversion
-fn build_column_info(first_row: &Row) -> Arc<ColumnInfo> {
-    let mut names = Vec::with_capacity(first_row.columns().len());
-    let mut column_types = Vec::with_capacity(first_row.columns().len());
-    let mut map = HashMap::with_capacity(first_row.columns().len());
+pub fn build_column_info(first_row: &Row, options: DecodeOptions) -> Arc<ColumnInfo> {
+    column_info_from_columns(first_row.columns(), options)
+}
+
+fn column_info_from_columns(columns: &[Column], options: DecodeOptions) -> Arc<ColumnInfo> {
+    let mut names = Vec::with_capacity(columns.len());
+    let mut column_types = Vec::with_capacity(columns.len());
+    let mut map = HashMap::with_capacity(columns.len());
 
-    for col in first_row.columns().iter() {
+    let mut json_columns = Vec::with_capacity(columns.len());
+
+    for col in columns.iter() {
         let name = col.name().to_string();
+        json_columns.push(options.json.parses_column(&name, col.column_type()));
         names.push(name);
         column_types.push(col.column_type());
     }
@@ -260,9 +752,42 @@ fn build_column_info(first_row: &Row) -> Arc<ColumnInfo> {
         names,
         map,
         column_types,
+        options,
+        json_columns,
+        py_names: OnceLock::new(),
     })
 }
 
+/// Schemas of recent empty results.  An endpoint that keeps getting no rows back
+/// reuses one `ColumnInfo` (and its interned names) instead of building it per call.
+static EMPTY_RESULT_SCHEMAS: Mutex<Vec<Arc<ColumnInfo>>> = Mutex::new(Vec::new());
+const MAX_EMPTY_RESULT_SCHEMAS: usize = 64;
+
+/// Column info for a result set with no rows, shared with earlier empty results of
+/// the same schema.
+fn empty_result_column_info(columns: &[Column], options: DecodeOptions) -> Arc<ColumnInfo> {
+    let same_schema = |info: &ColumnInfo| {
+        info.options == options
+            && info.names.len() == columns.len()
+            && columns
+                .iter()
+                .zip(info.names.iter().zip(&info.column_types))
+                .all(|(col, (name, col_type))| col.name() == name && col.column_type() == *col_type)
+    };
+    let mut schemas = EMPTY_RESULT_SCHEMAS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    if let Some(info) = schemas.iter().find(|info| same_schema(info)) {
+        return Arc::clone(info);
+    }
+    let info = column_info_from_columns(columns, options);
+    if schemas.len() == MAX_EMPTY_RESULT_SCHEMAS {
+        schemas.remove(0);
+    }
+    schemas.push(Arc::clone(&info));
+    info
+}
+
 /// A streaming wrapper around a Tiberius QueryStream
 /// Implements async iteration to fetch rows one at a time
 /// Lazy conversion: stores raw rows, converts to Python on-demand, caches for reset()
@@ -270,11 +795,17 @@ fn build_column_info(first_row: &Row) -> Arc<ColumnInfo> {
 pub struct PyQueryStream {
     // Store raw Tiberius rows in Option (Row doesn't impl Clone, so we take() on first access)
     tiberius_rows: Vec<Option<Row>>,
-    // Cache of converted rows (parallel to tiberius_rows, None = not yet converted)
-    converted_cache: Vec<Option<PyFastRow>>,
+    // Cache of converted rows (parallel to tiberius_rows, None = not yet converted);
+    // every access returns the same FastRow object rather than a copy
+    converted_cache: Vec<Option<Py<PyFastRow>>>,
     column_info: Option<Arc<ColumnInfo>>,
     position: usize,
     is_complete: bool,
+    execution_id: Option<String>,
+    rows_affected: Option<u64>,
+    output_params: Option<Py<PyAny>>,
+    messages: Vec<String>,
+    next_token: Option<String>,
 }
 
 #[pymethods]
@@ -290,7 +821,7 @@ impl PyQueryStream {
         if self.position < self.tiberius_rows.len() {
             let fast_row = self.get_or_convert_row(py, self.position)?;
             self.position += 1;
-            Py::new(py, fast_row).map(|p| p.into_any())
+            Ok(fast_row.into_any())
         } else {
             // All rows have been iterated
             self.is_complete = true;
@@ -319,8 +850,7 @@ impl PyQueryStream {
 
             let mut row_list = Vec::with_capacity(stop - start);
             for i in start..stop {
-                let fast_row = self.get_or_convert_row(py, i)?;
-                row_list.push(Py::new(py, fast_row)?.into_any());
+                row_list.push(self.get_or_convert_row(py, i)?.into_any());
             }
 
             let py_list = pyo3::types::PyList::new(py, row_list)?;
@@ -347,9 +877,7 @@ impl PyQueryStream {
                 ));
             }
 
-            let fast_row = self.get_or_convert_row(py, actual_index)?;
-
-            return Py::new(py, fast_row).map(|p| p.into_any());
+            return Ok(self.get_or_convert_row(py, actual_index)?.into_any());
         }
 
         Err(PyValueError::new_err("Index must be an integer or slice"))
@@ -366,9 +894,7 @@ impl PyQueryStream {
         }
 
         for i in self.position..self.tiberius_rows.len() {
-            let fast_row = self.get_or_convert_row(py, i)?;
-            let py_row = Py::new(py, fast_row)?;
-            row_list.push(py_row.into_any());
+            row_list.push(self.get_or_convert_row(py, i)?.into_any());
         }
 
         self.position = self.tiberius_rows.len();
@@ -387,9 +913,7 @@ impl PyQueryStream {
         }
 
         for i in self.position..end {
-            let fast_row = self.get_or_convert_row(py, i)?;
-            let py_row = Py::new(py, fast_row)?;
-            row_list.push(py_row.into_any());
+            row_list.push(self.get_or_convert_row(py, i)?.into_any());
         }
 
         self.position = end;
@@ -398,13 +922,148 @@ impl PyQueryStream {
     }
 
     /// Get column names
-    pub fn columns(&self) -> PyResult<Vec<String>> {
+    pub fn columns<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, pyo3::types::PyList>> {
         match &self.column_info {
-            Some(info) => Ok(info.names.clone()),
+            Some(info) => info.py_names_list(py),
             None => Err(PyValueError::new_err("No column information available")),
         }
     }
 
+    /// Get per-column type metadata as a list of dicts with keys `name`, `type`
+    /// (SQL Server type name) and `is_unicode`
+    pub fn column_metadata(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        let info = self
+            .column_info
+            .as_ref()
+            .ok_or_else(|| PyValueError::new_err("No column information available"))?;
+        let list = pyo3::types::PyList::empty(py);
+        for (name, col_type) in info.names.iter().zip(info.column_types.iter()) {
+            let dict = PyDict::new(py);
+            dict.set_item("name", name)?;
+            dict.set_item("type", type_mapping::column_type_name(*col_type))?;
+            dict.set_item("is_unicode", type_mapping::is_unicode_type(*col_type))?;
+            list.append(dict)?;
+        }
+        Ok(list.into())
+    }
+
+    /// Stable hash of the column names and types, for keying cached results; None
+    /// when the result carries no column information (e.g. a DML statement)
+    pub fn schema_fingerprint(&self) -> Option<String> {
+        self.column_info
+            .as_ref()
+            .map(|info| crate::fingerprint::schema_fingerprint(&info.names, &info.column_types))
+    }
+
+    /// Stable hash of each row's values, as 16 hex digits, for comparing result sets
+    /// without converting them to Python.  `columns` picks and orders the columns to
+    /// hash; all of them by default.  Rows already read as FastRow objects can no longer
+    /// be hashed, so call this before reading rows.
+    #[pyo3(signature = (columns=None))]
+    pub fn row_hashes(&self, columns: Option<Vec<String>>) -> PyResult<Vec<String>> {
+        let Some(info) = &self.column_info else {
+            return Ok(Vec::new());
+        };
+        let indices = match columns {
+            Some(names) => names
+                .iter()
+                .map(|name| {
+                    info.map.get(name).copied().ok_or_else(|| {
+                        PyValueError::new_err(format!("Column '{}' not found", name))
+                    })
+                })
+                .collect::<PyResult<Vec<usize>>>()?,
+            None => (0..info.names.len()).collect(),
+        };
+        let mut buf = Vec::new();
+        self.tiberius_rows
+            .iter()
+            .map(|row| {
+                let row = row.as_ref().ok_or_else(|| {
+                    PyValueError::new_err(
+                        "Cannot hash rows that were already read; call row_hashes() first",
+                    )
+                })?;
+                crate::row_hash::row_hash(row, &indices, &info.column_types, info.options, &mut buf)
+            })
+            .collect()
+    }
+
+    /// Serialize the rows as an Arrow IPC stream, optionally compressed with `"zstd"` or
+    /// `"lz4"`, for `QueryStream.from_ipc_bytes()` in another process.  Like
+    /// `row_hashes()`, this needs rows not yet read as FastRow objects.
+    #[pyo3(signature = (compression=None))]
+    pub fn to_ipc_bytes(&self, py: Python<'_>, compression: Option<&str>) -> PyResult<Py<PyBytes>> {
+        let (columns, options) = match &self.column_info {
+            Some(info) => (
+                info.names
+                    .iter()
+                    .zip(&info.column_types)
+                    .map(|(name, col_type)| Column::new(name.clone(), *col_type))
+                    .collect(),
+                info.options,
+            ),
+            None => (Vec::new(), DecodeOptions::default()),
+        };
+        let rows = self
+            .tiberius_rows
+            .iter()
+            .map(|row| {
+                row.as_ref().ok_or_else(|| {
+                    PyValueError::new_err(
+                        "Cannot serialize rows that were already read; call to_ipc_bytes() first",
+                    )
+                })
+            })
+            .collect::<PyResult<Vec<&Row>>>()?;
+        crate::ipc_transport::to_ipc_bytes(py, &columns, rows.into_iter(), options, compression)
+    }
+
+    /// Rebuild a result from the bytes of `to_ipc_bytes()`; the rows are converted
+    /// up front.
+    #[staticmethod]
+    pub fn from_ipc_bytes(py: Python<'_>, data: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let (columns, rows) = crate::ipc_transport::read_ipc_bytes(py, data)?;
+        let column_info = column_info_from_columns(&columns, DecodeOptions::default());
+        let converted_cache = rows
+            .into_iter()
+            .map(|values| {
+                Py::new(
+                    py,
+                    PyFastRow {
+                        values: values.into_boxed_slice(),
+                        column_info: Arc::clone(&column_info),
+                    },
+                )
+                .map(Some)
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        let mut stream = PyQueryStream::empty();
+        stream.tiberius_rows = std::iter::repeat_with(|| None)
+            .take(converted_cache.len())
+            .collect();
+        stream.converted_cache = converted_cache;
+        stream.column_info = Some(column_info);
+        Ok(stream)
+    }
+
+    /// Append the rows of `other`, which must have the same column names and types.
+    /// Rows are moved rather than copied or converted, so `other` is left empty.
+    pub fn extend(slf: &Bound<'_, Self>, other: &Bound<'_, Self>) -> PyResult<()> {
+        if slf.is(other) {
+            return Err(PyValueError::new_err(
+                "Cannot extend a QueryStream with itself",
+            ));
+        }
+        let mut this = slf.borrow_mut();
+        let mut other = other.borrow_mut();
+        if let (Some(a), Some(b)) = (&this.column_info, &other.column_info) {
+            check_same_columns(a, b)?;
+        }
+        this.append_from(&mut other);
+        Ok(())
+    }
+
     /// Reset iteration to the beginning
     pub fn reset(&mut self) {
         self.position = 0;
@@ -430,6 +1089,43 @@ impl PyQueryStream {
         self.tiberius_rows.is_empty()
     }
 
+    /// ID of the call that produced this result, for matching it against driver
+    /// errors and server-side traces; None for results not produced by a traced call
+    #[getter]
+    pub fn execution_id(&self) -> Option<&str> {
+        self.execution_id.as_deref()
+    }
+
+    /// Rows affected by the statement, for results from `Connection.execute_returning()`
+    /// and `Connection.exec_sql()`; None otherwise
+    #[getter]
+    pub fn rows_affected(&self) -> Option<u64> {
+        self.rows_affected
+    }
+
+    /// Informational messages (`PRINT`, low-severity `RAISERROR`) the server sent while
+    /// the statement ran, in order
+    #[getter]
+    pub fn messages(&self) -> Vec<String> {
+        self.messages.clone()
+    }
+
+    /// Values of the OUTPUT parameters by name, for results from `Connection.exec_sql()`;
+    /// None otherwise
+    #[getter]
+    pub fn output_params(&self, py: Python<'_>) -> Option<Py<PyAny>> {
+        self.output_params
+            .as_ref()
+            .map(|values| values.clone_ref(py))
+    }
+
+    /// Token for the next page, to pass back as `token=`, for results from
+    /// `Connection.fetch_page()`; None on the last page and for other results
+    #[getter]
+    pub fn next_token(&self) -> Option<&str> {
+        self.next_token.as_deref()
+    }
+
     /// Backwards compatibility: check if stream has rows
     pub fn has_rows(&self) -> bool {
         !self.tiberius_rows.is_empty()
@@ -447,7 +1143,7 @@ impl PyQueryStream {
         if self.position < self.tiberius_rows.len() {
             let fast_row = self.get_or_convert_row(py, self.position)?;
             self.position += 1;
-            Ok(Some(Py::new(py, fast_row)?))
+            Ok(Some(fast_row))
         } else {
             Ok(None)
         }
@@ -464,11 +1160,110 @@ impl PyQueryStream {
     }
 }
 
+/// Raise ValueError unless two result sets have identical column names and wire types.
+fn check_same_columns(a: &ColumnInfo, b: &ColumnInfo) -> PyResult<()> {
+    if a.names != b.names {
+        return Err(PyValueError::new_err(format!(
+            "Cannot combine result sets with different columns: {:?} vs {:?}",
+            a.names, b.names
+        )));
+    }
+    for (name, (ta, tb)) in a
+        .names
+        .iter()
+        .zip(a.column_types.iter().zip(b.column_types.iter()))
+    {
+        if ta != tb {
+            return Err(PyValueError::new_err(format!(
+                "Cannot combine result sets: column '{}' is {} in one and {} in the other",
+                name,
+                type_mapping::column_type_name(*ta),
+                type_mapping::column_type_name(*tb)
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Concatenate result sets with matching schemas into a new `QueryStream`.
+///
+/// Rows are moved out of the inputs, which are left empty. The schemas are checked
+/// before any rows are moved, so nothing is lost if they do not match.
+#[pyfunction]
+pub fn concat(results: Vec<Bound<'_, PyQueryStream>>) -> PyResult<PyQueryStream> {
+    let mut reference: Option<Arc<ColumnInfo>> = None;
+    for result in &results {
+        if let Some(info) = &result.borrow().column_info {
+            match &reference {
+                Some(first) => check_same_columns(first, info)?,
+                None => reference = Some(Arc::clone(info)),
+            }
+        }
+    }
+
+    let mut combined = PyQueryStream::empty();
+    for result in &results {
+        combined.append_from(&mut result.borrow_mut());
+    }
+    Ok(combined)
+}
+
 impl PyQueryStream {
+    fn empty() -> Self {
+        PyQueryStream {
+            tiberius_rows: Vec::new(),
+            converted_cache: Vec::new(),
+            column_info: None,
+            position: 0,
+            is_complete: false,
+            execution_id: None,
+            rows_affected: None,
+            output_params: None,
+            messages: Vec::new(),
+            next_token: None,
+        }
+    }
+
+    pub fn with_execution_id(mut self, execution_id: String) -> Self {
+        self.execution_id = Some(execution_id);
+        self
+    }
+
+    pub fn with_rows_affected(mut self, rows_affected: u64) -> Self {
+        self.rows_affected = Some(rows_affected);
+        self
+    }
+
+    pub fn with_output_params(mut self, output_params: Py<PyAny>) -> Self {
+        self.output_params = Some(output_params);
+        self
+    }
+
+    pub fn with_messages(mut self, messages: Vec<String>) -> Self {
+        self.messages = messages;
+        self
+    }
+
+    pub fn with_next_token(mut self, next_token: String) -> Self {
+        self.next_token = Some(next_token);
+        self
+    }
+
+    /// Move all rows of `other` to the end of `self`; schemas must already be checked.
+    fn append_from(&mut self, other: &mut PyQueryStream) {
+        if self.column_info.is_none() {
+            self.column_info = other.column_info.clone();
+        }
+        self.tiberius_rows.append(&mut other.tiberius_rows);
+        self.converted_cache.append(&mut other.converted_cache);
+        other.position = 0;
+        self.is_complete = false;
+    }
+
     /// Private helper: check cache → convert from tiberius row → cache result
-    fn get_or_convert_row(&mut self, py: Python<'_>, index: usize) -> PyResult<PyFastRow> {
+    fn get_or_convert_row(&mut self, py: Python<'_>, index: usize) -> PyResult<Py<PyFastRow>> {
         if let Some(cached) = &self.converted_cache[index] {
-            Ok(cached.clone())
+            Ok(cached.clone_ref(py))
         } else {
             let row = self.tiberius_rows[index]
                 .take()
@@ -477,32 +1272,57 @@ impl PyQueryStream {
                 .column_info
                 .as_ref()
                 .ok_or_else(|| PyValueError::new_err("No column info"))?;
-            let fast_row = PyFastRow::from_tiberius_row(row, py, Arc::clone(column_info))?;
-            self.converted_cache[index] = Some(fast_row.clone());
+            let fast_row = Py::new(
+                py,
+                PyFastRow::from_tiberius_row(row, py, Arc::clone(column_info))?,
+            )?;
+            self.converted_cache[index] = Some(fast_row.clone_ref(py));
             Ok(fast_row)
         }
     }
 
+    /// Like [`PyQueryStream::from_tiberius_rows`], for a first result set read along
+    /// with its metadata: a result with no rows skips the row pipeline and still
+    /// reports its columns.
+    pub fn from_first_result(
+        tiberius_rows: Vec<tiberius::Row>,
+        metadata: Option<ResultMetadata>,
+        options: DecodeOptions,
+        py: Python,
+    ) -> PyResult<Self> {
+        if !tiberius_rows.is_empty() {
+            return Self::from_tiberius_rows(tiberius_rows, options, py);
+        }
+        let mut stream = PyQueryStream::empty();
+        stream.column_info = metadata.map(|m| empty_result_column_info(m.columns(), options));
+        Ok(stream)
+    }
+
     /// Create a new QueryStream from Tiberius rows
     /// LAZY: stores raw rows, NO Python conversion (minimal GIL hold)
     /// Rows converted on-demand during iteration and cached for reset()
-    pub fn from_tiberius_rows(tiberius_rows: Vec<tiberius::Row>, _py: Python) -> PyResult<Self> {
+    pub fn from_tiberius_rows(
+        tiberius_rows: Vec<tiberius::Row>,
+        options: DecodeOptions,
+        py: Python,
+    ) -> PyResult<Self> {
         if tiberius_rows.is_empty() {
-            return Ok(PyQueryStream {
-                tiberius_rows: Vec::new(),
-                converted_cache: Vec::new(),
-                column_info: None,
-                position: 0,
-                is_complete: false,
-            });
+            return Ok(PyQueryStream::empty());
         }
 
         let first_row = &tiberius_rows[0];
-        let column_info = build_column_info(first_row);
+        let column_info = build_column_info(first_row, options);
+        if options.json.parses_for_json()
+            && column_info.names.len() == 1
+            && column_info.names[0] == FOR_JSON_COLUMN
+        {
+            return Self::from_for_json_rows(tiberius_rows, column_info, py);
+        }
 
         let row_count = tiberius_rows.len();
 
-        let converted_cache: Vec<Option<PyFastRow>> = vec![None; row_count];
+        let converted_cache: Vec<Option<Py<PyFastRow>>> =
+            std::iter::repeat_with(|| None).take(row_count).collect();
 
         let wrapped_rows: Vec<Option<Row>> = tiberius_rows.into_iter().map(Some).collect();
 
@@ -512,6 +1332,49 @@ impl PyQueryStream {
             column_info: Some(column_info),
             position: 0,
             is_complete: false,
+            execution_id: None,
+            rows_affected: None,
+            output_params: None,
+            messages: Vec::new(),
+            next_token: None,
+        })
+    }
+    /// A `FOR JSON` result split across rows, joined and parsed into one row.
+    fn from_for_json_rows(
+        tiberius_rows: Vec<tiberius::Row>,
+        column_info: Arc<ColumnInfo>,
+        py: Python,
+    ) -> PyResult<Self> {
+        let mut text = String::new();
+        for row in &tiberius_rows {
+            if let Ok(Some(chunk)) = row.try_get::<&str, usize>(0) {
+                text.push_str(chunk);
+            }
+        }
+        let value = if text.is_empty() {
+            py.None()
+        } else {
+            json_to_python(py, &text, FOR_JSON_COLUMN)?
+        };
+        let row = Py::new(
+            py,
+            PyFastRow {
+                values: vec![value].into_boxed_slice(),
+                column_info: Arc::clone(&column_info),
+            },
+        )?;
+
+        Ok(PyQueryStream {
+            tiberius_rows: vec![None],
+            converted_cache: vec![Some(row)],
+            column_info: Some(column_info),
+            position: 0,
+            is_complete: false,
+            execution_id: None,
+            rows_affected: None,
+            output_params: None,
+            messages: Vec::new(),
+            next_token: None,
         })
     }
 }
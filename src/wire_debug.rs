@@ -0,0 +1,475 @@
+//! TDS wire tracing for diagnosing protocol problems (`fastmssql.enable_wire_debug`).
+//!
+//! Every connection opened while tracing is on logs a line per TDS packet it sends
+//! and receives (type, status, length, packet ID) to the trace file, and a summary of
+//! the tokens of each server response: DONE counts and flags, ENVCHANGE types, INFO
+//! and ERROR numbers, LOGINACK versions.  Tokens are followed up to the first
+//! COLMETADATA of a response; rows are not decoded.
+//!
+//! Payload values (message texts, packet bytes) are left out unless tracing was
+//! enabled with `include_values=True`, and LOGIN7 packets are never dumped.  With
+//! `Encrypt=true` everything after the login travels inside TLS records, which are
+//! logged by size only.
+
+use std::fmt::Write as _;
+use std::fs::{File, OpenOptions};
+use std::io::Write as _;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use pyo3::prelude::*;
+
+/// Bytes of each packet shown with `include_values=True`.
+const MAX_DUMPED_BYTES: usize = 256;
+/// Give up following the tokens of a response that buffers more than this.
+const MAX_PENDING_TOKEN_BYTES: usize = 1024 * 1024;
+
+struct TraceFile {
+    file: Mutex<File>,
+    include_values: bool,
+}
+
+impl TraceFile {
+    fn line(&self, connection: u64, direction: &str, text: &str) {
+        let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S%.6fZ");
+        if let Ok(mut file) = self.file.lock() {
+            let _ = writeln!(file, "{} conn {} {} {}", now, connection, direction, text);
+        }
+    }
+}
+
+static TRACE_FILE: Mutex<Option<Arc<TraceFile>>> = Mutex::new(None);
+static NEXT_CONNECTION: AtomicU64 = AtomicU64::new(1);
+
+/// Log the TDS packets and token summaries of connections opened from now on to
+/// `path` (appended to). `include_values=True` adds message texts and the first bytes
+/// of each packet; `path=None` turns tracing off.
+#[pyfunction]
+#[pyo3(signature = (path, *, include_values = false))]
+pub fn enable_wire_debug(path: Option<PathBuf>, include_values: bool) -> PyResult<()> {
+    let trace = match path {
+        Some(path) => Some(Arc::new(TraceFile {
+            file: Mutex::new(OpenOptions::new().create(true).append(true).open(path)?),
+            include_values,
+        })),
+        None => None,
+    };
+    if let Ok(mut current) = TRACE_FILE.lock() {
+        *current = trace;
+    }
+    Ok(())
+}
+
+/// Traces one connection opened while tracing is on.
+pub struct WireTrace {
+    file: Arc<TraceFile>,
+    connection: u64,
+    sent: Frames,
+    received: Frames,
+}
+
+impl WireTrace {
+    pub fn start(peer: Option<SocketAddr>) -> Option<Box<Self>> {
+        let file = TRACE_FILE.lock().ok()?.clone()?;
+        let connection = NEXT_CONNECTION.fetch_add(1, Ordering::Relaxed);
+        let peer = peer.map_or_else(|| "unknown address".to_string(), |a| a.to_string());
+        file.line(connection, "--", &format!("connected to {}", peer));
+        Some(Box::new(Self {
+            file,
+            connection,
+            sent: Frames::default(),
+            received: Frames::default(),
+        }))
+    }
+
+    /// Account for `data`, just written to the socket.
+    pub fn sent(&mut self, data: &[u8]) {
+        let (file, connection) = (&self.file, self.connection);
+        self.sent.feed(data, file.include_values, |text| {
+            file.line(connection, "->", text)
+        });
+    }
+
+    /// Account for `data`, just read from the socket.
+    pub fn received(&mut self, data: &[u8]) {
+        let (file, connection) = (&self.file, self.connection);
+        self.received.feed(data, file.include_values, |text| {
+            file.line(connection, "<-", text)
+        });
+    }
+}
+
+/// Splits one direction of the byte stream into TDS packets or TLS records.
+#[derive(Default)]
+struct Frames {
+    frame: Vec<u8>,
+    tokens: Tokens,
+    lost: bool,
+}
+
+impl Frames {
+    fn feed(&mut self, mut data: &[u8], include_values: bool, mut log: impl FnMut(&str)) {
+        while !data.is_empty() && !self.lost {
+            let wanted = match (frame_length(&self.frame), self.frame.first()) {
+                (Some(total), _) => total,
+                (None, None) => 1,
+                (None, Some(&kind)) if is_tls(kind) => 5,
+                (None, Some(_)) => 8,
+            };
+            let n = wanted.saturating_sub(self.frame.len()).min(data.len());
+            self.frame.extend_from_slice(&data[..n]);
+            data = &data[n..];
+
+            let kind = self.frame[0];
+            if !is_tls(kind) && !is_tds(kind) {
+                log(&format!(
+                    "unrecognised packet type 0x{:02X}; tracing stops",
+                    kind
+                ));
+                self.lost = true;
+                continue;
+            }
+            match frame_length(&self.frame) {
+                Some(total) if total < if is_tls(kind) { 5 } else { 8 } => {
+                    log(&format!(
+                        "lost packet framing (length {}); tracing stops",
+                        total
+                    ));
+                    self.lost = true;
+                }
+                Some(total) if self.frame.len() >= total => {
+                    let frame = std::mem::take(&mut self.frame);
+                    self.packet(&frame, include_values, &mut log);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn packet(&mut self, frame: &[u8], include_values: bool, log: &mut impl FnMut(&str)) {
+        let kind = frame[0];
+        if is_tls(kind) {
+            log(&format!("TLS {}, {} bytes", tls_content(kind), frame.len()));
+            return;
+        }
+        let status = frame[1];
+        let mut text = format!(
+            "{} packet, {} bytes, status 0x{:02X}{}, SPID {}, packet {}",
+            packet_name(kind),
+            frame.len(),
+            status,
+            if status & 0x01 != 0 { " (EOM)" } else { "" },
+            u16::from_be_bytes([frame[4], frame[5]]),
+            frame[6]
+        );
+        let payload = &frame[8..];
+        if include_values && kind != 0x10 && !payload.is_empty() {
+            text.push_str(", payload ");
+            for byte in payload.iter().take(MAX_DUMPED_BYTES) {
+                let _ = write!(text, "{:02x}", byte);
+            }
+            if payload.len() > MAX_DUMPED_BYTES {
+                text.push_str("...");
+            }
+        }
+        log(&text);
+
+        if kind == 0x04 {
+            self.tokens.feed(payload, include_values);
+            if status & 0x01 != 0 {
+                log(&format!("tokens: {}", self.tokens.finish()));
+            }
+        }
+    }
+}
+
+fn is_tls(kind: u8) -> bool {
+    (20..=23).contains(&kind)
+}
+
+fn is_tds(kind: u8) -> bool {
+    matches!(kind, 0x01..=0x04 | 0x06..=0x08 | 0x0E | 0x10..=0x12)
+}
+
+/// Total length of the frame starting with `header`, once enough of it is known.
+fn frame_length(header: &[u8]) -> Option<usize> {
+    let kind = *header.first()?;
+    if is_tls(kind) {
+        let bytes = header.get(3..5)?;
+        Some(5 + u16::from_be_bytes([bytes[0], bytes[1]]) as usize)
+    } else if is_tds(kind) {
+        let bytes = header.get(2..4)?;
+        Some(u16::from_be_bytes([bytes[0], bytes[1]]) as usize)
+    } else {
+        None
+    }
+}
+
+fn packet_name(kind: u8) -> &'static str {
+    match kind {
+        0x01 => "SQL batch",
+        0x02 => "pre-TDS7 login",
+        0x03 => "RPC",
+        0x04 => "tabular result",
+        0x06 => "attention",
+        0x07 => "bulk load",
+        0x08 => "federated auth token",
+        0x0E => "transaction manager",
+        0x10 => "LOGIN7",
+        0x11 => "SSPI",
+        0x12 => "PRELOGIN",
+        _ => "unknown",
+    }
+}
+
+fn tls_content(kind: u8) -> &'static str {
+    match kind {
+        20 => "change cipher spec",
+        21 => "alert",
+        22 => "handshake",
+        _ => "application data",
+    }
+}
+
+/// Summaries of the tokens of one server response, read as its packets arrive.
+#[derive(Default)]
+struct Tokens {
+    pending: Vec<u8>,
+    seen: Vec<String>,
+    /// Set once a token that cannot be skipped without decoding rows is reached.
+    stopped: bool,
+}
+
+impl Tokens {
+    fn feed(&mut self, payload: &[u8], include_values: bool) {
+        if self.stopped {
+            return;
+        }
+        self.pending.extend_from_slice(payload);
+        let mut pos = 0;
+        while pos < self.pending.len() {
+            match token(&self.pending[pos..], include_values) {
+                Token::Complete { length, summary } => {
+                    self.seen.push(summary);
+                    pos += length;
+                }
+                Token::Incomplete => break,
+                Token::Stop(summary) => {
+                    self.seen.push(summary);
+                    self.stopped = true;
+                    break;
+                }
+            }
+        }
+        self.pending.drain(..pos);
+        if self.pending.len() > MAX_PENDING_TOKEN_BYTES {
+            self.seen.push("(token too large to follow)".into());
+            self.stopped = true;
+        }
+        if self.stopped {
+            self.pending = Vec::new();
+        }
+    }
+
+    /// The summary of the response just completed.
+    fn finish(&mut self) -> String {
+        if !self.stopped && !self.pending.is_empty() {
+            self.seen.push(format!(
+                "({} bytes of truncated token data)",
+                self.pending.len()
+            ));
+        }
+        let summary = if self.seen.is_empty() {
+            "none".to_string()
+        } else {
+            self.seen.join(", ")
+        };
+        *self = Self::default();
+        summary
+    }
+}
+
+enum Token {
+    Complete { length: usize, summary: String },
+    Incomplete,
+    Stop(String),
+}
+
+fn u16_at(data: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(data.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn u32_at(data: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(data.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn u64_at(data: &[u8], at: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(data.get(at..at + 8)?.try_into().ok()?))
+}
+
+/// The token at the start of `data`.
+fn token(data: &[u8], include_values: bool) -> Token {
+    let kind = data[0];
+    let complete = |length: usize, summary: String| {
+        if data.len() < length {
+            Token::Incomplete
+        } else {
+            Token::Complete { length, summary }
+        }
+    };
+    // Tokens with a 2-byte length after the token byte.
+    let body = || -> Option<(usize, &[u8])> {
+        let length = 3 + u16_at(data, 1)? as usize;
+        Some((length, data.get(3..length)?))
+    };
+    match kind {
+        0xFD..=0xFF => {
+            let (Some(status), Some(count)) = (u16_at(data, 1), u64_at(data, 5)) else {
+                return Token::Incomplete;
+            };
+            complete(13, done_summary(kind, status, count))
+        }
+        0x79 => complete(5, "RETURNSTATUS".into()),
+        0xAA | 0xAB | 0xAD | 0xE3 | 0xA4 | 0xA5 | 0xA9 | 0xED => {
+            let Some((length, body)) = body() else {
+                return Token::Incomplete;
+            };
+            let summary = match kind {
+                0xAA => message_summary("ERROR", body, include_values),
+                0xAB => message_summary("INFO", body, include_values),
+                0xAD => loginack_summary(body),
+                0xE3 => format!("ENVCHANGE({})", envchange_name(body.first().copied())),
+                0xA4 => "TABNAME".into(),
+                0xA5 => "COLINFO".into(),
+                0xA9 => "ORDER".into(),
+                _ => "SSPI".into(),
+            };
+            complete(length, summary)
+        }
+        0xE4 | 0xEE => {
+            let Some(length) = u32_at(data, 1) else {
+                return Token::Incomplete;
+            };
+            let name = if kind == 0xE4 {
+                "SESSIONSTATE"
+            } else {
+                "FEDAUTHINFO"
+            };
+            complete(5 + length as usize, name.into())
+        }
+        0xAE => {
+            // FEATUREEXTACK: (feature ID, 4-byte length, data)... up to 0xFF.
+            let mut at = 1;
+            loop {
+                match data.get(at) {
+                    None => return Token::Incomplete,
+                    Some(0xFF) => return complete(at + 1, "FEATUREEXTACK".into()),
+                    Some(_) => match u32_at(data, at + 1) {
+                        Some(length) => at += 5 + length as usize,
+                        None => return Token::Incomplete,
+                    },
+                }
+            }
+        }
+        0x81 => match u16_at(data, 1) {
+            Some(0xFFFF) => complete(3, "COLMETADATA (no metadata)".into()),
+            Some(columns) => Token::Stop(format!(
+                "COLMETADATA ({} columns; rows and later tokens not decoded)",
+                columns
+            )),
+            None => Token::Incomplete,
+        },
+        0xAC => Token::Stop("RETURNVALUE (later tokens not decoded)".into()),
+        _ => Token::Stop(format!("token 0x{:02X} (later tokens not decoded)", kind)),
+    }
+}
+
+fn done_summary(kind: u8, status: u16, count: u64) -> String {
+    let name = match kind {
+        0xFD => "DONE",
+        0xFE => "DONEPROC",
+        _ => "DONEINPROC",
+    };
+    let mut details = Vec::new();
+    if status & 0x10 != 0 {
+        details.push(format!("count {}", count));
+    }
+    for (bit, flag) in [(0x01, "more"), (0x02, "error"), (0x20, "attention")] {
+        if status & bit != 0 {
+            details.push(flag.to_string());
+        }
+    }
+    if details.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}({})", name, details.join(", "))
+    }
+}
+
+/// ERROR/INFO: number, state, class, then the message as a 2-byte-length UTF-16 string.
+fn message_summary(name: &str, body: &[u8], include_values: bool) -> String {
+    let (Some(number), Some(&state), Some(&class)) = (u32_at(body, 0), body.get(4), body.get(5))
+    else {
+        return name.to_string();
+    };
+    let mut summary = format!("{} {} (class {}, state {})", name, number, class, state);
+    if include_values
+        && let Some(chars) = u16_at(body, 6)
+        && let Some(text) = body.get(8..8 + chars as usize * 2)
+    {
+        let units: Vec<u16> = text
+            .chunks_exact(2)
+            .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+            .collect();
+        let _ = write!(summary, " {:?}", String::from_utf16_lossy(&units));
+    }
+    summary
+}
+
+/// LOGINACK: interface, TDS version, program name, program version.
+fn loginack_summary(body: &[u8]) -> String {
+    let Some(tds) = body.get(1..5) else {
+        return "LOGINACK".into();
+    };
+    let name_chars = body.get(5).copied().unwrap_or(0) as usize;
+    let version = body
+        .get(6 + name_chars * 2..10 + name_chars * 2)
+        .map(|v| {
+            format!(
+                ", server {}.{}.{}",
+                v[0],
+                v[1],
+                u16::from_be_bytes([v[2], v[3]])
+            )
+        })
+        .unwrap_or_default();
+    format!(
+        "LOGINACK (TDS 0x{:02X}{:02X}{:02X}{:02X}{})",
+        tds[0], tds[1], tds[2], tds[3], version
+    )
+}
+
+fn envchange_name(kind: Option<u8>) -> &'static str {
+    match kind {
+        Some(1) => "database",
+        Some(2) => "language",
+        Some(3) => "character set",
+        Some(4) => "packet size",
+        Some(7) => "collation",
+        Some(8) => "begin transaction",
+        Some(9) => "commit transaction",
+        Some(10) => "rollback transaction",
+        Some(11) => "enlist DTC transaction",
+        Some(12) => "defect transaction",
+        Some(13) => "mirroring partner",
+        Some(15) => "promote transaction",
+        Some(16) => "transaction manager address",
+        Some(17) => "transaction ended",
+        Some(18) => "reset connection",
+        Some(19) => "user instance",
+        Some(20) => "routing",
+        _ => "unknown",
+    }
+}
@@ -0,0 +1,90 @@
+//! XML decoding for `Connection(parse_xml=...)`.
+//!
+//! With `parse_xml=True` XML columns come back as `xml.etree.ElementTree.Element`
+//! objects, and with `parse_xml="lxml"` as `lxml.etree` elements, instead of strings.
+//!
+//! SQL Server stores XML as UTF-16, and a value that was converted from text can
+//! still lead with a byte order mark or an `<?xml ... encoding="UTF-16"?>`
+//! declaration.  The value reaches Python already decoded, so both are stripped
+//! first: ElementTree rejects a leading BOM as not well-formed, and lxml refuses
+//! `str` input that declares an encoding.
+
+use pyo3::exceptions::{PyImportError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyString};
+
+use crate::type_mapping::element_tree;
+use crate::types::DataError;
+
+/// How XML column values are returned.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum XmlMode {
+    /// As the serialized string.
+    #[default]
+    Off,
+    ElementTree,
+    Lxml,
+}
+
+/// Read the `parse_xml` argument: a bool, or `"etree"` / `"lxml"`.
+pub fn parse_xml_option(value: Option<&Bound<'_, PyAny>>) -> PyResult<XmlMode> {
+    let Some(value) = value.filter(|v| !v.is_none()) else {
+        return Ok(XmlMode::Off);
+    };
+    if value.is_instance_of::<PyBool>() {
+        return Ok(if value.is_truthy()? {
+            XmlMode::ElementTree
+        } else {
+            XmlMode::Off
+        });
+    }
+    let invalid = || PyValueError::new_err("parse_xml expects True/False, 'etree' or 'lxml'");
+    let name = value.cast::<PyString>().map_err(|_| invalid())?.to_str()?;
+    match name {
+        "etree" => Ok(XmlMode::ElementTree),
+        "lxml" => {
+            // Fail when the connection is created rather than on the first XML value.
+            value.py().import("lxml.etree").map_err(|_| {
+                PyImportError::new_err("parse_xml='lxml' requires lxml (pip install lxml)")
+            })?;
+            Ok(XmlMode::Lxml)
+        }
+        _ => Err(invalid()),
+    }
+}
+
+/// `text` without a leading byte order mark or XML declaration.
+fn document_text(text: &str) -> &str {
+    let text = text.trim_start_matches('\u{feff}').trim_start();
+    match text.strip_prefix("<?xml") {
+        Some(rest) if rest.starts_with(|c: char| c.is_whitespace()) => rest
+            .find("?>")
+            .map_or(text, |end| rest[end + 2..].trim_start()),
+        _ => text,
+    }
+}
+
+/// The XML value of column `index` as `mode` asks for it.
+pub fn xml_to_python(
+    py: Python<'_>,
+    text: &str,
+    mode: XmlMode,
+    index: usize,
+) -> PyResult<Py<PyAny>> {
+    let parsed = match mode {
+        XmlMode::Off => return Ok(PyString::new(py, text).into_any().unbind()),
+        XmlMode::ElementTree => {
+            element_tree(py)?.call_method1("fromstring", (document_text(text),))
+        }
+        XmlMode::Lxml => py
+            .import("lxml.etree")?
+            .call_method1("fromstring", (document_text(text),)),
+    };
+    parsed.map(Bound::unbind).map_err(|e| {
+        DataError::new_err(format!(
+            "Column {} does not contain a well-formed XML document: {}",
+            index,
+            e.value(py)
+        ))
+    })
+}
@@ -0,0 +1,68 @@
+"""A local stand-in for the Azure token endpoints: managed identity (GET) and
+Microsoft Entra ID client credentials (POST)."""
+
+import json
+import threading
+from http.server import BaseHTTPRequestHandler, HTTPServer
+from urllib.parse import parse_qs
+
+
+class IdentityEndpoint:
+    """Hands out tokens; with ``challenge`` set it first answers 401 like Azure Arc.
+
+    ``requests`` records ``(path, headers)`` of token requests, and ``forms`` the
+    decoded body of each POST.
+    """
+
+    def __init__(self, challenge=None):
+        self.challenge = challenge
+        self.requests = []
+        self.forms = []
+        endpoint = self
+
+        class Handler(BaseHTTPRequestHandler):
+            def do_GET(self):
+                headers = {name.lower(): value for name, value in self.headers.items()}
+                endpoint.requests.append((self.path, headers))
+                if endpoint.challenge and "Authorization" not in self.headers:
+                    self.send_response(401)
+                    self.send_header("WWW-Authenticate", f"Basic realm={endpoint.challenge}")
+                    self.end_headers()
+                    return
+                self._send_token()
+
+            def do_POST(self):
+                headers = {name.lower(): value for name, value in self.headers.items()}
+                endpoint.requests.append((self.path, headers))
+                length = int(self.headers.get("Content-Length", 0))
+                endpoint.forms.append(parse_qs(self.rfile.read(length).decode()))
+                self._send_token()
+
+            def _send_token(self):
+                body = json.dumps({"access_token": "token", "expires_in": "3600"}).encode()
+                self.send_response(200)
+                self.send_header("Content-Type", "application/json")
+                self.send_header("Content-Length", str(len(body)))
+                self.end_headers()
+                self.wfile.write(body)
+
+            def log_message(self, *args):
+                pass
+
+        self.http = HTTPServer(("127.0.0.1", 0), Handler)
+
+    def __enter__(self):
+        threading.Thread(target=self.http.serve_forever, daemon=True).start()
+        return self
+
+    def __exit__(self, *exc):
+        self.http.shutdown()
+        self.http.server_close()
+
+    @property
+    def authority(self):
+        return f"http://127.0.0.1:{self.http.server_port}"
+
+    @property
+    def url(self):
+        return f"http://127.0.0.1:{self.http.server_port}/metadata/identity/oauth2/token"
@@ -0,0 +1,380 @@
+"""A scripted TDS server for exercising connection handling without SQL Server.
+
+The server answers PRELOGIN without encryption, so clients must connect with
+``Encrypt=DANGER_PLAINTEXT``; with ``tls=True`` it requires encryption instead and
+runs the session over TLS with a self-signed certificate. It either rejects every LOGIN7 with a chosen error or
+accepts it; accepted sessions answer SQL batches and RPCs with an empty DONE, except
+requests containing ``WAITFOR``, which never get a reply (a statement blocked on the
+server), and requests containing a key of ``results``, which get that result set.
+"""
+
+import asyncio
+import datetime
+import functools
+import ssl
+import struct
+import tempfile
+import time
+from pathlib import Path
+
+from fastmssql import Connection, PoolConfig
+
+SQL_BATCH = 0x01
+RPC = 0x03
+TABULAR_RESULT = 0x04
+ATTENTION = 0x06
+LOGIN7 = 0x10
+PRELOGIN = 0x12
+
+
+def packet(kind, payload, eom=True):
+    """One TDS packet, by default with the EOM status bit set."""
+    return struct.pack(">BBHHBB", kind, int(eom), 8 + len(payload), 0, 1, 0) + payload
+
+
+def b_varchar(text):
+    return struct.pack("<B", len(text)) + text.encode("utf-16-le")
+
+
+def us_varchar(text):
+    return struct.pack("<H", len(text)) + text.encode("utf-16-le")
+
+
+def done(status=0x0000):
+    return struct.pack("<BHHQ", 0xFD, status, 0, 0)
+
+
+def prelogin_response(encryption=0x02):
+    # VERSION at offset 11 (6 bytes), ENCRYPTION at offset 17 (1 byte: NOT_SUP, or
+    # ON to require TLS).
+    options = struct.pack(">BHH", 0x00, 11, 6) + struct.pack(">BHH", 0x01, 17, 1) + b"\xff"
+    return packet(
+        TABULAR_RESULT, options + struct.pack(">IH", 0x10000FA0, 0) + bytes([encryption])
+    )
+
+
+def login_ack():
+    body = struct.pack("<B", 1) + struct.pack(">I", 0x74000004) + b_varchar("fake")
+    body += struct.pack("<I", 0x10000FA0)
+    return packet(TABULAR_RESULT, struct.pack("<BH", 0xAD, len(body)) + body + done())
+
+
+def error_response(number, state, message, server="fake"):
+    body = (
+        struct.pack("<iBB", number, state, 14)
+        + us_varchar(message)
+        + b_varchar(server)
+        + b_varchar("")
+        + struct.pack("<i", 1)
+    )
+    return packet(TABULAR_RESULT, struct.pack("<BH", 0xAA, len(body)) + body + done(0x0002))
+
+
+_EPOCH = datetime.date(1, 1, 1)
+
+
+def _ticks(t):
+    """100 ns ticks since midnight, as TIME(7) and DATETIME2(7) store them."""
+    seconds = t.hour * 3600 + t.minute * 60 + t.second
+    return seconds * 10_000_000 + t.microsecond * 10
+
+
+def _date_bytes(d):
+    return (d - _EPOCH).days.to_bytes(3, "little")
+
+
+def _plp(data):
+    """A PLP value sent as one chunk: total length, chunk length, data, terminator."""
+    return struct.pack("<QI", len(data), len(data)) + data + b"\x00\x00\x00\x00"
+
+
+# TYPE_INFO and value encoder per column type.
+COLUMN_TYPES = {
+    "int": (b"\x26\x08", lambda v: b"\x08" + struct.pack("<q", v)),
+    "float": (b"\x6d\x08", lambda v: b"\x08" + struct.pack("<d", v)),
+    "bit": (b"\x68\x01", lambda v: b"\x01" + bytes([bool(v)])),
+    "bit_not_null": (b"\x32", lambda v: bytes([bool(v)])),
+    "tinyint": (b"\x26\x01", lambda v: b"\x01" + struct.pack("<B", v)),
+    "tinyint_not_null": (b"\x30", lambda v: struct.pack("<B", v)),
+    "smallint": (b"\x26\x02", lambda v: b"\x02" + struct.pack("<h", v)),
+    "int32": (b"\x26\x04", lambda v: b"\x04" + struct.pack("<i", v)),
+    "nvarchar": (
+        b"\xe7" + struct.pack("<H", 8000) + b"\x09\x04\xd0\x00\x34",
+        lambda v: struct.pack("<H", len(v) * 2) + v.encode("utf-16-le"),
+    ),
+    "varbinary": (b"\xa5" + struct.pack("<H", 8000), lambda v: struct.pack("<H", len(v)) + v),
+    "date": (b"\x28", lambda v: b"\x03" + _date_bytes(v)),
+    "time": (b"\x29\x07", lambda v: b"\x05" + _ticks(v).to_bytes(5, "little")),
+    "datetime2": (
+        b"\x2a\x07",
+        lambda v: b"\x08" + _ticks(v.time()).to_bytes(5, "little") + _date_bytes(v.date()),
+    ),
+    "xml": (b"\xf1\x00", lambda v: _plp(v.encode("utf-16-le"))),
+}
+
+_NULLS = {"nvarchar": b"\xff\xff", "varbinary": b"\xff\xff", "xml": b"\xff" * 8}
+
+
+def result_set(columns, rows, more=False):
+    """COLMETADATA, ROW and DONE tokens for ``columns`` (``(name, type)`` pairs with
+    types from ``COLUMN_TYPES``) and ``rows`` (tuples of Python values). With
+    ``more`` the DONE token announces another result set."""
+    body = struct.pack("<BH", 0x81, len(columns))
+    for name, kind in columns:
+        body += struct.pack("<IH", 0, 0x0001) + COLUMN_TYPES[kind][0] + b_varchar(name)
+    for row in rows:
+        body += b"\xd1"
+        for (_, kind), value in zip(columns, row):
+            if value is None:
+                body += _NULLS.get(kind, b"\x00")
+            else:
+                body += COLUMN_TYPES[kind][1](value)
+    status = 0x0011 if more else 0x0010
+    return body + struct.pack("<BHHQ", 0xFD, status, 0xC1, len(rows))
+
+
+async def read_packet(reader):
+    header = await reader.readexactly(8)
+    kind, _, length = struct.unpack(">BBH", header[:4])
+    return kind, await reader.readexactly(length - 8)
+
+
+@functools.cache
+def tls_context():
+    """Server context with a self-signed certificate; needs ``cryptography``."""
+    from cryptography import x509
+    from cryptography.hazmat.primitives import hashes, serialization
+    from cryptography.hazmat.primitives.asymmetric import ec
+    from cryptography.x509.oid import NameOID
+
+    key = ec.generate_private_key(ec.SECP256R1())
+    name = x509.Name([x509.NameAttribute(NameOID.COMMON_NAME, "fake")])
+    now = datetime.datetime.now(datetime.timezone.utc)
+    cert = (
+        x509.CertificateBuilder()
+        .subject_name(name)
+        .issuer_name(name)
+        .public_key(key.public_key())
+        .serial_number(x509.random_serial_number())
+        .not_valid_before(now - datetime.timedelta(days=1))
+        .not_valid_after(now + datetime.timedelta(days=1))
+        .sign(key, hashes.SHA256())
+    )
+    directory = Path(tempfile.mkdtemp())
+    (directory / "cert.pem").write_bytes(cert.public_bytes(serialization.Encoding.PEM))
+    (directory / "key.pem").write_bytes(
+        key.private_bytes(
+            serialization.Encoding.PEM,
+            serialization.PrivateFormat.PKCS8,
+            serialization.NoEncryption(),
+        )
+    )
+    context = ssl.SSLContext(ssl.PROTOCOL_TLS_SERVER)
+    # TLS 1.2 keeps the handshake a strict back-and-forth inside PRELOGIN packets.
+    context.maximum_version = ssl.TLSVersion.TLSv1_2
+    context.load_cert_chain(directory / "cert.pem", directory / "key.pem")
+    return context
+
+
+class _Plain:
+    """TDS packets straight on the socket."""
+
+    def __init__(self, reader, writer):
+        self.reader = reader
+        self.writer = writer
+
+    async def read_packet(self):
+        return await read_packet(self.reader)
+
+    def write(self, data):
+        self.writer.write(data)
+
+
+class _Tls:
+    """TDS packets inside TLS, after a handshake carried in PRELOGIN packets."""
+
+    def __init__(self, reader, writer):
+        self.reader = reader
+        self.writer = writer
+        self.incoming = ssl.MemoryBIO()
+        self.outgoing = ssl.MemoryBIO()
+        self.tls = tls_context().wrap_bio(self.incoming, self.outgoing, server_side=True)
+        self.buffer = b""
+
+    async def handshake(self):
+        while True:
+            try:
+                self.tls.do_handshake()
+                break
+            except ssl.SSLWantReadError:
+                pass
+            if flight := self.outgoing.read():
+                self.writer.write(packet(PRELOGIN, flight))
+                await self.writer.drain()
+            _, payload = await read_packet(self.reader)
+            self.incoming.write(payload)
+        if flight := self.outgoing.read():
+            self.writer.write(packet(PRELOGIN, flight))
+
+    async def read_packet(self):
+        while True:
+            if len(self.buffer) >= 8:
+                kind, _, length = struct.unpack(">BBH", self.buffer[:4])
+                if len(self.buffer) >= length:
+                    payload, self.buffer = self.buffer[8:length], self.buffer[length:]
+                    return kind, payload
+            try:
+                self.buffer += self.tls.read(65536)
+                continue
+            except ssl.SSLWantReadError:
+                pass
+            except ssl.SSLZeroReturnError:
+                raise ConnectionResetError("client closed the TLS session")
+            data = await self.reader.read(65536)
+            if not data:
+                raise asyncio.IncompleteReadError(b"", None)
+            self.incoming.write(data)
+
+    def write(self, data):
+        self.tls.write(data)
+        self.writer.write(self.outgoing.read())
+
+
+class FakeServer:
+    """Rejects logins with error ``login_error`` (``None`` accepts them).
+
+    ``results`` maps text to look for in a request to the ``(columns, rows)`` result
+    set to answer it with, or to a list of them for a batch with several result sets;
+    see ``result_set()``.
+
+    ``login_delay`` seconds pass before each login is answered, and ``done_delay``
+    seconds before the DONE token that ends a response with a result set.
+
+    ``tls`` requires encryption; connect with ``connection_string``, which trusts the
+    self-signed certificate.
+
+    ``received`` records ``(packet type, time)`` for every packet from clients, and
+    ``closed`` counts sessions the client disconnected.
+    """
+
+    def __init__(
+        self,
+        login_error=None,
+        state=1,
+        message="Login failed",
+        results=None,
+        login_delay=0,
+        done_delay=0,
+        tls=False,
+    ):
+        self.login_error = login_error
+        self.tls = tls
+        self.login_delay = login_delay
+        self.done_delay = done_delay
+        self.state = state
+        self.message = message
+        self.results = results or {}
+        self.received = []
+        self.closed = 0
+        self.server = None
+
+    async def __aenter__(self):
+        self.server = await asyncio.start_server(self._handle, "127.0.0.1", 0)
+        return self
+
+    async def __aexit__(self, *exc):
+        self.server.close()
+
+    @property
+    def connection_string(self):
+        port = self.server.sockets[0].getsockname()[1]
+        if self.tls:
+            encrypt = "Encrypt=true;TrustServerCertificate=true"
+        else:
+            encrypt = "Encrypt=DANGER_PLAINTEXT"
+        return f"Server=127.0.0.1,{port};Database=master;User Id=sa;Password=x;{encrypt}"
+
+    @property
+    def logins(self):
+        """Arrival times of LOGIN7 packets."""
+        return [at for kind, at in self.received if kind == LOGIN7]
+
+    def count(self, kind):
+        return sum(1 for k, _ in self.received if k == kind)
+
+    async def wait_for(self, kind, count=1, timeout=5.0):
+        """Wait until ``count`` packets of ``kind`` have arrived."""
+        deadline = time.monotonic() + timeout
+        while self.count(kind) < count:
+            if time.monotonic() > deadline:
+                raise TimeoutError(f"no packet of type {kind:#x} from the client")
+            await asyncio.sleep(0.02)
+
+    def _respond(self, payload):
+        for marker, result in self.results.items():
+            if marker.encode("utf-16-le") in payload:
+                sets = result if isinstance(result, list) else [result]
+                return b"".join(
+                    result_set(columns, rows, more=i < len(sets) - 1)
+                    for i, (columns, rows) in enumerate(sets)
+                )
+        return done()
+
+    async def _handle(self, reader, writer):
+        channel = _Plain(reader, writer)
+        try:
+            while True:
+                kind, payload = await channel.read_packet()
+                self.received.append((kind, time.monotonic()))
+                if kind == LOGIN7 and self.login_delay:
+                    await asyncio.sleep(self.login_delay)
+                if kind == PRELOGIN and self.tls:
+                    writer.write(prelogin_response(encryption=0x01))
+                    channel = _Tls(reader, writer)
+                    await channel.handshake()
+                elif kind == PRELOGIN:
+                    writer.write(prelogin_response())
+                elif kind == LOGIN7 and self.login_error is not None:
+                    channel.write(error_response(self.login_error, self.state, self.message))
+                elif kind == LOGIN7:
+                    channel.write(login_ack())
+                elif kind in (SQL_BATCH, RPC) and "WAITFOR".encode("utf-16-le") not in payload:
+                    response = self._respond(payload)
+                    if self.done_delay and len(response) > len(done()):
+                        # The rows go first, in a packet of their own.
+                        channel.write(packet(TABULAR_RESULT, response[: -len(done())], eom=False))
+                        await writer.drain()
+                        await asyncio.sleep(self.done_delay)
+                        response = response[-len(done()) :]
+                    channel.write(packet(TABULAR_RESULT, response))
+                elif kind == ATTENTION:
+                    channel.write(packet(TABULAR_RESULT, done(0x0020)))
+                await writer.drain()
+        except (asyncio.IncompleteReadError, ConnectionError):
+            self.closed += 1
+        except asyncio.CancelledError:
+            pass
+        finally:
+            writer.close()
+
+
+def connect(server, pool=None, **options):
+    """A ``Connection`` to ``server`` on one pooled connection, opened on first use.
+
+    The pool gives up after 4 seconds without retrying and skips the check-out health
+    check; ``pool`` overrides any of those ``PoolConfig`` settings. ``options`` are
+    passed to ``Connection``. With an ``azure_credential`` the SQL login is left out of
+    the connection string.
+    """
+    connection_string = server.connection_string
+    if options.get("azure_credential") is not None:
+        connection_string = connection_string.replace("User Id=sa;Password=x;", "")
+    config = {
+        "max_size": 1,
+        "min_idle": 0,
+        "connection_timeout_secs": 4,
+        "retry_connection": False,
+        "test_on_check_out": False,
+        **(pool or {}),
+    }
+    return Connection(connection_string, pool_config=PoolConfig(**config), **options)
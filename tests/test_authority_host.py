@@ -0,0 +1,68 @@
+"""
+Tests for sending client-credential token requests to a sovereign cloud's login host.
+"""
+
+import pytest
+from fake_identity import IdentityEndpoint
+from fake_tds import FakeServer, connect
+
+from fastmssql import AzureCredential
+
+
+@pytest.mark.parametrize(
+    "authority_host, expected",
+    [
+        ("login.microsoftonline.us", "https://login.microsoftonline.us"),
+        ("https://login.partner.microsoftonline.cn/", "https://login.partner.microsoftonline.cn"),
+    ],
+)
+def test_authority_host_is_normalized(authority_host, expected):
+    cred = AzureCredential.service_principal(
+        "client", "secret", "tenant", authority_host=authority_host
+    )
+    assert cred.config["authority_host"] == expected
+    assert AzureCredential.default(authority_host=authority_host).config["authority_host"] == expected
+
+
+@pytest.mark.parametrize("authority_host", ["", "ftp://login.microsoftonline.us", "not a host"])
+def test_invalid_authority_host_is_rejected(authority_host):
+    with pytest.raises(ValueError, match="authority_host"):
+        AzureCredential.service_principal(
+            "client", "secret", "tenant", authority_host=authority_host
+        )
+
+
+async def _connect(server, credential):
+    conn = connect(server, azure_credential=credential)
+    await conn.execute("SET NOCOUNT ON")
+
+
+@pytest.mark.asyncio
+async def test_token_is_requested_from_authority_host():
+    with IdentityEndpoint() as identity:
+        cred = AzureCredential.service_principal(
+            "client",
+            "secret",
+            "my-tenant",
+            authority_host=identity.authority,
+            resource="https://database.usgovcloudapi.net/",
+        )
+        async with FakeServer() as server:
+            await _connect(server, cred)
+
+    path, _ = identity.requests[0]
+    assert path == "/my-tenant/oauth2/v2.0/token"
+    form = identity.forms[0]
+    assert form["grant_type"] == ["client_credentials"]
+    assert form["scope"] == ["https://database.usgovcloudapi.net/.default"]
+
+
+@pytest.mark.asyncio
+async def test_authority_host_from_environment(monkeypatch):
+    with IdentityEndpoint() as identity:
+        monkeypatch.setenv("AZURE_AUTHORITY_HOST", identity.authority)
+        cred = AzureCredential.service_principal("env-client", "secret", "env-tenant")
+        async with FakeServer() as server:
+            await _connect(server, cred)
+
+    assert identity.requests[0][0] == "/env-tenant/oauth2/v2.0/token"
@@ -0,0 +1,124 @@
+"""Tests for the Batch unit-of-work object and Connection.apply()."""
+
+import pytest
+from conftest import Config
+
+from fastmssql import Batch, Connection, SqlError
+
+
+async def _setup_table(conn: Connection, name: str):
+    await conn.execute(f"IF OBJECT_ID('{name}', 'U') IS NOT NULL DROP TABLE {name}")
+    await conn.execute(f"CREATE TABLE {name} (id INT PRIMARY KEY, label NVARCHAR(50))")
+
+
+def test_batch_add_and_clear():
+    batch = Batch()
+    assert len(batch) == 0
+
+    batch.add("SELECT 1")
+    batch.add("SELECT @P1, @P2", [1, "a"])
+    assert len(batch) == 2
+    assert batch.param_count == 2
+
+    batch.clear()
+    assert len(batch) == 0
+    assert batch.param_count == 0
+
+
+def test_batch_rejects_too_many_parameters():
+    batch = Batch()
+    batch.add("SELECT 1", list(range(2000)))
+    with pytest.raises(ValueError):
+        batch.add("SELECT 1", list(range(101)))
+    # The rejected statement is not queued.
+    assert len(batch) == 1
+    assert batch.param_count == 2000
+
+
+@pytest.mark.asyncio
+async def test_apply_empty_batch_returns_zero(test_config: Config):
+    conn = Connection(test_config.connection_string)
+    assert await conn.apply(Batch()) == 0
+
+
+@pytest.mark.asyncio
+async def test_apply_rejects_out_of_range_placeholder(test_config: Config):
+    conn = Connection(test_config.connection_string)
+    batch = Batch()
+    batch.add("SELECT @P1, @P2", [1])
+    with pytest.raises(ValueError):
+        await conn.apply(batch)
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_apply_renumbers_placeholders(test_config: Config):
+    table = "test_batch_apply_renumber"
+    async with Connection(test_config.connection_string) as conn:
+        await _setup_table(conn, table)
+        try:
+            batch = Batch()
+            batch.add(f"INSERT INTO {table} VALUES (@P1, @P2)", [1, "one"])
+            batch.add(f"INSERT INTO {table} VALUES (@P1, @P2);", [2, "two"])
+            # Placeholder-like text inside literals and comments must be left alone.
+            batch.add(
+                f"INSERT INTO {table} VALUES (@P2, '@P1') -- @P1 in a comment",
+                ["ignored", 3],
+            )
+            batch.add(f"UPDATE {table} SET label = label + @P1 WHERE id < @P2", ["!", 3])
+
+            assert await conn.apply(batch) == 5
+
+            result = await conn.query(f"SELECT id, label FROM {table} ORDER BY id")
+            assert [(r["id"], r["label"]) for r in result.rows()] == [
+                (1, "one!"),
+                (2, "two!"),
+                (3, "@P1"),
+            ]
+        finally:
+            await conn.execute(f"DROP TABLE {table}")
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_apply_is_atomic(test_config: Config):
+    table = "test_batch_apply_atomic"
+    async with Connection(test_config.connection_string) as conn:
+        await _setup_table(conn, table)
+        try:
+            batch = Batch()
+            batch.add(f"INSERT INTO {table} VALUES (@P1, @P2)", [1, "first"])
+            batch.add(f"INSERT INTO {table} VALUES (@P1, @P2)", [1, "duplicate key"])
+
+            with pytest.raises(SqlError):
+                await conn.apply(batch)
+
+            result = await conn.query(f"SELECT COUNT(*) AS cnt FROM {table}")
+            assert result.rows()[0]["cnt"] == 0
+
+            # The pooled connection must not be left inside a transaction.
+            result = await conn.query("SELECT @@TRANCOUNT AS cnt")
+            assert result.rows()[0]["cnt"] == 0
+        finally:
+            await conn.execute(f"DROP TABLE {table}")
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_apply_batch_is_reusable(test_config: Config):
+    table = "test_batch_apply_reuse"
+    async with Connection(test_config.connection_string) as conn:
+        await _setup_table(conn, table)
+        try:
+            batch = Batch()
+            batch.add(f"INSERT INTO {table} VALUES (@P1, @P2)", [1, "a"])
+            assert await conn.apply(batch) == 1
+
+            batch.clear()
+            batch.add(f"DELETE FROM {table} WHERE id = @P1", [1])
+            assert await conn.apply(batch) == 1
+
+            result = await conn.query(f"SELECT COUNT(*) AS cnt FROM {table}")
+            assert result.rows()[0]["cnt"] == 0
+        finally:
+            await conn.execute(f"DROP TABLE {table}")
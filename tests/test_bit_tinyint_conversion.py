@@ -0,0 +1,75 @@
+"""
+Tests for BIT and integer column conversion: bools for BIT, unsigned TINYINT, NULL at
+every integer width, and the typed getters over computed flags.
+
+The fake TDS server returns the result sets, so these run without SQL Server.
+"""
+
+import pytest
+from fake_tds import FakeServer, connect
+
+from fastmssql import DataError
+
+RESULTS = {
+    "FROM flags": (
+        [
+            ("bit", "bit"),
+            ("bit_not_null", "bit_not_null"),
+            ("tiny", "tinyint"),
+            ("tiny_not_null", "tinyint_not_null"),
+            ("small", "smallint"),
+            ("regular", "int32"),
+            ("big", "int"),
+        ],
+        [
+            (True, False, 255, 255, -32768, -1, 9_000_000_000),
+            (False, True, 0, 128, 7, 2_147_483_647, -1),
+            (None, True, None, 1, None, None, None),
+        ],
+    ),
+    "FROM computed": (
+        [("is_active", "int32"), ("as_bit", "bit"), ("count", "int32")],
+        [(1, True, 2), (0, False, -1)],
+    ),
+}
+
+
+@pytest.mark.asyncio
+async def test_bit_and_integer_values():
+    async with FakeServer(results=RESULTS) as server:
+        result = await connect(server).query("SELECT * FROM flags")
+
+    first, second, third = result.rows()
+    assert first["bit"] is True and first["bit_not_null"] is False
+    assert second["bit"] is False and second["bit_not_null"] is True
+    # TINYINT is unsigned: 255 and 128 are never read back as negative.
+    assert first["tiny"] == 255 and first["tiny_not_null"] == 255
+    assert second["tiny_not_null"] == 128
+    assert first["small"] == -32768
+    assert first["regular"] == -1
+    assert second["regular"] == 2_147_483_647
+    assert first["big"] == 9_000_000_000
+    for column in ("bit", "tiny", "small", "regular", "big"):
+        assert third[column] is None
+
+
+@pytest.mark.asyncio
+async def test_typed_getters_over_bits_and_flags():
+    async with FakeServer(results=RESULTS) as server:
+        conn = connect(server)
+        flags = (await conn.query("SELECT * FROM flags")).rows()
+        computed = (await conn.query("SELECT * FROM computed")).rows()
+
+    assert flags[0].get_bool("bit") is True
+    assert flags[0].get_int("bit") == 1
+    assert flags[0].get_int("tiny") == 255
+    assert flags[2].get_bool("bit") is None
+    assert flags[2].get_int("small") is None
+
+    # CASE WHEN ... THEN 1 ELSE 0 END is an INT; get_bool reads 0 and 1 as flags.
+    assert computed[0].get_bool("is_active") is True
+    assert computed[1].get_bool("is_active") is False
+    assert computed[1].get_bool("as_bit") is False
+    for row in computed:
+        with pytest.raises(DataError):
+            row.get_bool("count")
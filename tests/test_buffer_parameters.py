@@ -0,0 +1,59 @@
+"""Tests for bytearray and memoryview parameters, bound as VARBINARY."""
+
+import array
+
+import pytest
+from conftest import Config
+from fake_tds import FakeServer, connect
+
+from fastmssql import Connection, Parameter
+
+
+def test_buffers_are_not_expanded_as_in_lists():
+    assert not Parameter(bytearray(b"abc")).is_expanded
+    assert not Parameter(memoryview(b"abc")).is_expanded
+
+
+@pytest.mark.parametrize(
+    "value",
+    [
+        bytearray(b"\x00\x01\x02"),
+        bytearray(),
+        memoryview(b"abcdef"),
+        memoryview(b"abcdef")[::2],
+        memoryview(array.array("i", [1, 2, 3])),
+    ],
+)
+@pytest.mark.asyncio
+async def test_buffers_are_accepted(value):
+    async with FakeServer() as server:
+        conn = connect(server)
+        await conn.execute("UPDATE blobs SET data = @P1", [value])
+
+
+@pytest.mark.asyncio
+async def test_mutable_buffers_are_copied_when_the_call_is_made():
+    async with FakeServer() as server:
+        conn = connect(server)
+        data = bytearray(b"abc")
+        pending = conn.execute("UPDATE blobs SET data = @P1, old = @P2", [data, memoryview(data)])
+        # A bytearray still exported to the pending statement would refuse to resize.
+        data.extend(b"def")
+        await pending
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_buffers_round_trip(test_config: Config):
+    blob = bytearray(range(256)) * 4096  # 1 MiB
+    async with Connection(test_config.connection_string) as conn:
+        for value, expected in [
+            (blob, bytes(blob)),
+            (memoryview(blob)[10:20], bytes(blob[10:20])),
+            (memoryview(b"abcdef")[::2], b"ace"),
+            (bytearray(), b""),
+        ]:
+            result = await conn.query("SELECT @P1 AS v, DATALENGTH(@P1) AS n", [value])
+            row = result.fetchone()
+            assert row["v"] == expected
+            assert row["n"] == len(expected)
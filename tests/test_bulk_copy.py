@@ -0,0 +1,91 @@
+"""Tests for Connection.bulk_insert(..., bulk_copy=True)."""
+
+from datetime import date, datetime
+
+import pytest
+from conftest import Config
+
+from fastmssql import Connection
+
+TABLE = "test_bulk_copy_rows"
+
+
+async def _create_table(conn):
+    await conn.execute(f"IF OBJECT_ID('{TABLE}', 'U') IS NOT NULL DROP TABLE {TABLE}")
+    await conn.execute(
+        f"CREATE TABLE {TABLE} ("
+        "id INT IDENTITY PRIMARY KEY, "
+        "code VARCHAR(10) NOT NULL, "
+        "name NVARCHAR(50) NULL, "
+        "qty SMALLINT NULL, "
+        "price DECIMAL(10, 2) NULL, "
+        "ratio FLOAT NULL, "
+        "active BIT NULL, "
+        "day DATE NULL, "
+        "created DATETIME2 NULL, "
+        "legacy DATETIME NULL, "
+        "doubled AS qty * 2)"
+    )
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_bulk_copy_round_trip(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        await _create_table(conn)
+        try:
+            columns = ["code", "name", "qty", "price", "ratio", "active", "day", "created", "legacy"]
+            rows = [
+                ["A1", "Ärger", 3, "12.345", 0.5, True, date(2024, 1, 2),
+                 datetime(2024, 1, 2, 3, 4, 5, 678000), datetime(2024, 1, 2, 3, 4, 5)],
+                ["B2", None, None, 7, 1, 0, "2024-02-03", "2024-02-03 10:00:00", None],
+            ]
+            assert await conn.bulk_insert(TABLE, columns, rows, bulk_copy=True) == 2
+
+            result = await conn.query(f"SELECT * FROM {TABLE} ORDER BY id")
+            first, second = result.fetchall()
+            assert first["code"] == "A1"
+            assert first["name"] == "Ärger"
+            assert first["doubled"] == 6
+            assert str(first["price"]) == "12.35"
+            assert first["active"] is True
+            assert first["day"] == date(2024, 1, 2)
+            assert first["created"] == datetime(2024, 1, 2, 3, 4, 5, 678000)
+            assert first["legacy"] == datetime(2024, 1, 2, 3, 4, 5)
+            assert second["name"] is None
+            assert second["qty"] is None
+            assert str(second["price"]) == "7.00"
+            assert second["day"] == date(2024, 2, 3)
+        finally:
+            await conn.execute(f"DROP TABLE {TABLE}")
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_bulk_copy_unlisted_columns_are_null(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        await _create_table(conn)
+        try:
+            await conn.bulk_insert(TABLE, ["code"], [["X"], ["Y"]], bulk_copy=True)
+            result = await conn.query(f"SELECT COUNT(*) AS n FROM {TABLE} WHERE name IS NULL")
+            assert result.fetchone()["n"] == 2
+        finally:
+            await conn.execute(f"DROP TABLE {TABLE}")
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_bulk_copy_rejects_bad_values_before_loading(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        await _create_table(conn)
+        try:
+            with pytest.raises(ValueError, match="out of range"):
+                await conn.bulk_insert(TABLE, ["code", "qty"], [["A", 1], ["B", 70000]], bulk_copy=True)
+            with pytest.raises(ValueError, match="not an insertable column"):
+                await conn.bulk_insert(TABLE, ["code", "doubled"], [["A", 1]], bulk_copy=True)
+
+            result = await conn.query(f"SELECT COUNT(*) AS n FROM {TABLE}")
+            assert result.fetchone()["n"] == 0
+        finally:
+            await conn.execute(f"DROP TABLE {TABLE}")
+
@@ -0,0 +1,107 @@
+"""Tests for Connection.bulk_insert_arrow()."""
+
+from datetime import date, datetime, timezone
+from decimal import Decimal
+
+import pytest
+from conftest import Config
+
+from fastmssql import Connection
+
+pa = pytest.importorskip("pyarrow")
+
+TABLE = "test_bulk_insert_arrow_rows"
+
+
+async def _create_table(conn):
+    await conn.execute(f"IF OBJECT_ID('{TABLE}', 'U') IS NOT NULL DROP TABLE {TABLE}")
+    await conn.execute(
+        f"CREATE TABLE {TABLE} ("
+        "id INT IDENTITY PRIMARY KEY, "
+        "code VARCHAR(10) NOT NULL, "
+        "name NVARCHAR(50) NULL, "
+        "qty SMALLINT NULL, "
+        "price DECIMAL(10, 2) NULL, "
+        "ratio FLOAT NULL, "
+        "active BIT NULL, "
+        "day DATE NULL, "
+        "created DATETIME2 NULL, "
+        "payload VARBINARY(20) NULL)"
+    )
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_bulk_insert_arrow_round_trip(test_config: Config):
+    table = pa.table(
+        {
+            "code": pa.array(["A1", "B2", "A1"]).dictionary_encode(),
+            "name": ["Ärger", None, "x"],
+            "qty": pa.array([3, None, 255], pa.uint8()),
+            "price": pa.array([Decimal("12.35"), None, Decimal("0.10")], pa.decimal128(10, 2)),
+            "ratio": pa.array([0.5, None, 2.0], pa.float32()),
+            "active": [True, None, False],
+            "day": pa.array([date(2024, 1, 2), None, date(1999, 12, 31)], pa.date32()),
+            "created": pa.array(
+                [datetime(2024, 1, 2, 3, 4, 5, 678000), None, datetime(2024, 6, 1, 12, 0)],
+                pa.timestamp("ns"),
+            ),
+            "payload": [b"\x00\x01", None, b""],
+        }
+    )
+    async with Connection(test_config.connection_string) as conn:
+        await _create_table(conn)
+        try:
+            assert await conn.bulk_insert_arrow(TABLE, table) == 3
+
+            result = await conn.query(f"SELECT * FROM {TABLE} ORDER BY id")
+            first, second, third = result.fetchall()
+            assert first["code"] == "A1"
+            assert first["name"] == "Ärger"
+            assert first["qty"] == 3
+            assert str(first["price"]) == "12.35"
+            assert first["ratio"] == 0.5
+            assert first["active"] is True
+            assert first["day"] == date(2024, 1, 2)
+            assert first["created"] == datetime(2024, 1, 2, 3, 4, 5, 678000)
+            assert first["payload"] == b"\x00\x01"
+            assert second["code"] == "B2"
+            assert all(second[c] is None for c in ("name", "qty", "price", "day", "payload"))
+            assert third["qty"] == 255
+        finally:
+            await conn.execute(f"DROP TABLE {TABLE}")
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_bulk_insert_arrow_slices_and_aware_timestamps(test_config: Config):
+    table = pa.table(
+        {
+            "code": ["skip", "B", "C"],
+            "created": pa.array(
+                [None, datetime(2024, 1, 1, 12, tzinfo=timezone.utc), None],
+                pa.timestamp("us", tz="UTC"),
+            ),
+        }
+    ).slice(1)
+    async with Connection(test_config.connection_string) as conn:
+        await _create_table(conn)
+        try:
+            assert await conn.bulk_insert_arrow(TABLE, table) == 2
+            result = await conn.query(f"SELECT code, created FROM {TABLE} ORDER BY id")
+            rows = [(r["code"], r["created"]) for r in result.fetchall()]
+            assert rows == [("B", datetime(2024, 1, 1, 12)), ("C", None)]
+        finally:
+            await conn.execute(f"DROP TABLE {TABLE}")
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_bulk_insert_arrow_rejects_unknown_column(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        await _create_table(conn)
+        try:
+            with pytest.raises(ValueError, match="not an insertable column"):
+                await conn.bulk_insert_arrow(TABLE, pa.table({"code": ["A"], "nope": [1]}))
+        finally:
+            await conn.execute(f"DROP TABLE {TABLE}")
@@ -0,0 +1,80 @@
+"""Tests for Connection.bulk_insert_dataframe()."""
+
+from datetime import datetime
+
+import pytest
+from conftest import Config
+
+from fastmssql import Connection
+
+pd = pytest.importorskip("pandas")
+pytest.importorskip("pyarrow")
+
+TABLE = "test_bulk_insert_dataframe_rows"
+
+
+async def _create_table(conn):
+    await conn.execute(f"IF OBJECT_ID('{TABLE}', 'U') IS NOT NULL DROP TABLE {TABLE}")
+    await conn.execute(
+        f"CREATE TABLE {TABLE} ("
+        "id INT NOT NULL PRIMARY KEY, "
+        "name NVARCHAR(50) NULL, "
+        "qty INT NULL, "
+        "ratio FLOAT NULL, "
+        "kind VARCHAR(10) NULL, "
+        "created DATETIME2 NULL)"
+    )
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_bulk_insert_dataframe_maps_nan_and_nat_to_null(test_config: Config):
+    frame = pd.DataFrame(
+        {
+            "id": [1, 2, 3],
+            "name": ["Ada", None, "Grace"],
+            "qty": pd.array([5, None, 7], dtype="Int64"),
+            "ratio": [0.5, float("nan"), 1.5],
+            "kind": pd.Categorical(["a", "b", "a"]),
+            "created": [datetime(2024, 1, 2, 3, 4, 5), pd.NaT, datetime(2024, 6, 1)],
+        },
+        index=[10, 20, 30],
+    )
+    async with Connection(test_config.connection_string) as conn:
+        await _create_table(conn)
+        try:
+            assert await conn.bulk_insert_dataframe(TABLE, frame) == 3
+
+            result = await conn.query(f"SELECT * FROM {TABLE} ORDER BY id")
+            first, second, third = result.fetchall()
+            assert (first["name"], first["qty"], first["ratio"]) == ("Ada", 5, 0.5)
+            assert first["kind"] == "a"
+            assert first["created"] == datetime(2024, 1, 2, 3, 4, 5)
+            assert all(second[c] is None for c in ("name", "qty", "ratio", "created"))
+            assert second["kind"] == "b"
+            assert third["qty"] == 7
+        finally:
+            await conn.execute(f"DROP TABLE {TABLE}")
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_bulk_insert_dataframe_in_chunks(test_config: Config):
+    frame = pd.DataFrame({"id": range(1, 1001), "qty": range(1000)})
+    async with Connection(test_config.connection_string) as conn:
+        await _create_table(conn)
+        try:
+            assert await conn.bulk_insert_dataframe(TABLE, frame, chunk_size=300) == 1000
+            result = await conn.query(f"SELECT COUNT(*) AS n, SUM(qty) AS total FROM {TABLE}")
+            row = result.fetchone()
+            assert row["n"] == 1000
+            assert row["total"] == sum(range(1000))
+        finally:
+            await conn.execute(f"DROP TABLE {TABLE}")
+
+
+@pytest.mark.asyncio
+async def test_bulk_insert_dataframe_rejects_zero_chunk_size(test_config: Config):
+    conn = Connection(test_config.connection_string)
+    with pytest.raises(ValueError, match="chunk_size"):
+        await conn.bulk_insert_dataframe(TABLE, pd.DataFrame({"id": [1]}), chunk_size=0)
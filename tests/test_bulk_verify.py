@@ -0,0 +1,77 @@
+"""Tests for Connection.bulk_insert(..., verify_key=...)."""
+
+import pytest
+from conftest import Config
+
+from fastmssql import Connection
+
+TABLE = "test_bulk_verify_rows"
+
+
+async def _create_table(conn):
+    await conn.execute(f"IF OBJECT_ID('{TABLE}', 'U') IS NOT NULL DROP TABLE {TABLE}")
+    await conn.execute(f"CREATE TABLE {TABLE} (id INT PRIMARY KEY, name NVARCHAR(50) NULL)")
+
+
+@pytest.mark.asyncio
+async def test_verify_key_must_be_an_inserted_column():
+    conn = Connection("Server=localhost;Database=master;User Id=sa;Password=x")
+    with pytest.raises(ValueError, match="verify_key 'missing'"):
+        await conn.bulk_insert(TABLE, ["id", "name"], [[1, "a"]], verify_key="missing")
+
+
+@pytest.mark.asyncio
+async def test_verify_key_must_not_be_null():
+    conn = Connection("Server=localhost;Database=master;User Id=sa;Password=x")
+    with pytest.raises(ValueError, match="NULL"):
+        await conn.bulk_insert(TABLE, ["id", "name"], [[None, "a"]], verify_key="id")
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+@pytest.mark.parametrize("bulk_copy", [False, True])
+async def test_verify_reports_count_and_checksum(test_config: Config, bulk_copy):
+    async with Connection(test_config.connection_string) as conn:
+        await _create_table(conn)
+        try:
+            rows = [[i, f"name {i}"] for i in range(10, 0, -1)]
+            report = await conn.bulk_insert(
+                TABLE, ["id", "name"], rows, bulk_copy=bulk_copy, verify_key="id"
+            )
+            expected = await conn.query(
+                "SELECT CHECKSUM_AGG(CHECKSUM([id], [name])) AS c "
+                f"FROM {TABLE} WHERE id BETWEEN 1 AND 10"
+            )
+            assert report == {
+                "rows_inserted": 10,
+                "row_count": 10,
+                "checksum": expected.fetchone()["c"],
+                "key_min": 1,
+                "key_max": 10,
+                "verified": True,
+            }
+
+            # A row already in the range makes the count disagree.
+            await conn.execute(f"INSERT INTO {TABLE} (id, name) VALUES (25, 'existing')")
+            report = await conn.bulk_insert(
+                TABLE, ["id", "name"], [[20, "x"], [30, "y"]], bulk_copy=bulk_copy, verify_key="id"
+            )
+            assert report["rows_inserted"] == 2
+            assert report["row_count"] == 3
+            assert report["verified"] is False
+        finally:
+            await conn.execute(f"DROP TABLE {TABLE}")
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_verify_with_no_rows(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        await _create_table(conn)
+        try:
+            report = await conn.bulk_insert(TABLE, ["id", "name"], [], verify_key="id")
+            assert report["rows_inserted"] == 0
+            assert report["checksum"] is None
+            assert report["verified"] is True
+        finally:
+            await conn.execute(f"DROP TABLE {TABLE}")
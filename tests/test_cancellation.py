@@ -0,0 +1,153 @@
+"""Tests for cancelling statements: asyncio cancellation and RowStream.cancel(), over
+plain TDS and TLS."""
+
+import asyncio
+
+import pytest
+from conftest import Config
+from fake_tds import ATTENTION, RPC, FakeServer
+
+from fastmssql import Connection, PoolConfig
+
+
+def _pool_config():
+    return PoolConfig(max_size=1, min_idle=1, connection_timeout_secs=5, test_on_check_out=False)
+
+
+async def _wait_closed(server, timeout=5.0):
+    deadline = asyncio.get_running_loop().time() + timeout
+    while not server.closed:
+        assert asyncio.get_running_loop().time() < deadline, "connection was not discarded"
+        await asyncio.sleep(0.02)
+
+
+@pytest.mark.asyncio
+async def test_cancelled_execute_sends_attention_and_discards_connection():
+    async with FakeServer() as server:
+        conn = Connection(server.connection_string, pool_config=_pool_config())
+        pending = conn.execute("WAITFOR DELAY '01:00:00'")
+        await server.wait_for(RPC)
+
+        pending.cancel()
+        with pytest.raises(asyncio.CancelledError):
+            await pending
+        await server.wait_for(ATTENTION)
+        await _wait_closed(server)
+
+
+@pytest.mark.asyncio
+async def test_cancelled_query_batch_does_not_leak_a_busy_connection():
+    async with FakeServer() as server:
+        conn = Connection(server.connection_string, pool_config=_pool_config())
+        pending = asyncio.ensure_future(conn.query_batch([("WAITFOR DELAY '01:00:00'", None)]))
+        await server.wait_for(RPC)
+
+        pending.cancel()
+        with pytest.raises(asyncio.CancelledError):
+            await pending
+        await server.wait_for(ATTENTION)
+        await _wait_closed(server)
+
+        # The next user gets a fresh connection, not the one mid-response.
+        assert await conn.execute("SET NOCOUNT ON") == 0
+        assert len(server.logins) == 2
+
+
+@pytest.mark.asyncio
+async def test_wait_for_timeout_cancels_statement():
+    async with FakeServer() as server:
+        conn = Connection(server.connection_string, pool_config=_pool_config())
+        with pytest.raises(asyncio.TimeoutError):
+            await asyncio.wait_for(conn.execute("WAITFOR DELAY '01:00:00'"), 0.5)
+        await server.wait_for(ATTENTION)
+
+
+@pytest.mark.asyncio
+async def test_completed_statement_keeps_connection():
+    async with FakeServer() as server:
+        conn = Connection(server.connection_string, pool_config=_pool_config())
+        await conn.execute("SET NOCOUNT ON")
+        await conn.execute("SET NOCOUNT ON")
+        assert server.count(ATTENTION) == 0
+        assert server.closed == 0
+
+
+@pytest.mark.asyncio
+async def test_row_stream_cancel_sends_attention():
+    async with FakeServer() as server:
+        conn = Connection(server.connection_string, pool_config=_pool_config())
+        stream = conn.query_stream("WAITFOR DELAY '01:00:00'; SELECT 1")
+        first = asyncio.ensure_future(stream.__anext__())
+        await server.wait_for(RPC)
+
+        stream.cancel()
+        with pytest.raises(StopAsyncIteration):
+            await first
+        await server.wait_for(ATTENTION)
+        await _wait_closed(server)
+
+
+@pytest.mark.asyncio
+async def test_cancelled_execute_on_encrypted_connection_disconnects():
+    pytest.importorskip("cryptography")
+    async with FakeServer(tls=True) as server:
+        conn = Connection(server.connection_string, pool_config=_pool_config())
+        pending = conn.execute("WAITFOR DELAY '01:00:00'")
+        await server.wait_for(RPC)
+
+        pending.cancel()
+        with pytest.raises(asyncio.CancelledError):
+            await pending
+        # No attention signal fits inside the TLS session; the connection is closed.
+        await _wait_closed(server)
+        assert server.count(ATTENTION) == 0
+
+        assert await conn.execute("SET NOCOUNT ON") == 0
+        assert len(server.logins) == 2
+
+
+@pytest.mark.asyncio
+async def test_row_stream_cancel_on_encrypted_connection_disconnects():
+    pytest.importorskip("cryptography")
+    async with FakeServer(tls=True) as server:
+        conn = Connection(server.connection_string, pool_config=_pool_config())
+        stream = conn.query_stream("WAITFOR DELAY '01:00:00'; SELECT 1")
+        first = asyncio.ensure_future(stream.__anext__())
+        await server.wait_for(RPC)
+
+        stream.cancel()
+        with pytest.raises(StopAsyncIteration):
+            await first
+        await _wait_closed(server)
+        assert server.count(ATTENTION) == 0
+
+
+@pytest.mark.asyncio
+async def test_cancel_before_iteration_runs_nothing():
+    conn = Connection("Server=127.0.0.1,1;Database=master;User Id=sa;Password=x")
+    stream = conn.fetch_chunks("SELECT 1", chunk_size=10)
+    stream.cancel()
+    with pytest.raises(StopAsyncIteration):
+        await stream.__anext__()
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_cancelled_scan_stops_on_server(test_config: Config):
+    marker = "fastmssql_cancel_marker"
+    async with Connection(test_config.connection_string) as conn:
+        stream = conn.query_stream(
+            f"SELECT a.object_id /* {marker} */ FROM sys.all_objects a "
+            "CROSS JOIN sys.all_objects b CROSS JOIN sys.all_objects c"
+        )
+        await stream.__anext__()
+        stream.cancel()
+        await asyncio.sleep(1)
+
+        running = await conn.query(
+            "SELECT COUNT(*) AS n FROM sys.dm_exec_requests r "
+            "CROSS APPLY sys.dm_exec_sql_text(r.sql_handle) t "
+            "WHERE t.text LIKE @P1 AND r.session_id <> @@SPID",
+            [f"%{marker} */%"],
+        )
+        assert running.rows()[0]["n"] == 0
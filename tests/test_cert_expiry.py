@@ -0,0 +1,79 @@
+"""Tests for the server certificate expiry warning."""
+
+import warnings
+from datetime import datetime
+
+import pytest
+from conftest import Config
+
+import fastmssql
+from fastmssql import CertificateExpiryWarning, Connection
+
+
+def test_warning_category():
+    assert issubclass(CertificateExpiryWarning, UserWarning)
+
+
+def test_callback_must_be_callable():
+    with pytest.raises(TypeError, match="callable"):
+        fastmssql.set_certificate_expiry_warning(30, callback="not callable")
+    fastmssql.set_certificate_expiry_warning(None)
+
+
+async def _tls12_certificate(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        info = await conn.server_info()
+    if info["tls_version"] != "TLSv1.2":
+        pytest.skip("server certificate is only visible under TLS 1.2")
+    return info
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_callback_receives_expiring_certificate(test_config: Config):
+    info = await _tls12_certificate(test_config)
+    seen = []
+    # Any certificate expires within 100000 days.
+    fastmssql.set_certificate_expiry_warning(100_000, callback=lambda *args: seen.append(args))
+    try:
+        async with Connection(test_config.connection_string) as conn:
+            await conn.query("SELECT 1")
+        async with Connection(test_config.connection_string) as conn:
+            await conn.query("SELECT 1")
+    finally:
+        fastmssql.set_certificate_expiry_warning(None)
+    # Reported once, however many connections present it.
+    assert len(seen) == 1
+    subject, not_after, days_left = seen[0]
+    assert subject == info["certificate_subject"]
+    assert isinstance(not_after, datetime)
+    assert days_left < 100_000
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_warning_is_emitted(test_config: Config):
+    await _tls12_certificate(test_config)
+    fastmssql.set_certificate_expiry_warning(100_000)
+    try:
+        with warnings.catch_warnings(record=True) as caught:
+            warnings.simplefilter("always")
+            async with Connection(test_config.connection_string) as conn:
+                await conn.query("SELECT 1")
+    finally:
+        fastmssql.set_certificate_expiry_warning(None)
+    assert any(issubclass(w.category, CertificateExpiryWarning) for w in caught)
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_distant_expiry_is_not_reported(test_config: Config):
+    await _tls12_certificate(test_config)
+    seen = []
+    fastmssql.set_certificate_expiry_warning(0, callback=lambda *args: seen.append(args))
+    try:
+        async with Connection(test_config.connection_string) as conn:
+            await conn.query("SELECT 1")
+    finally:
+        fastmssql.set_certificate_expiry_warning(None)
+    assert seen == []
@@ -0,0 +1,138 @@
+"""Tests for QueryStream.column_metadata() and Connection.describe()."""
+
+import pytest
+from conftest import Config
+
+from fastmssql import Connection
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_column_metadata_distinguishes_unicode(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        result = await conn.query("""
+            SELECT CAST('a' AS VARCHAR(10)) AS v,
+                   CAST('a' AS CHAR(3)) AS c,
+                   CAST(N'a' AS NVARCHAR(10)) AS nv,
+                   CAST(N'a' AS NCHAR(3)) AS nc,
+                   CAST(1 AS INT) AS i
+        """)
+    meta = {m["name"]: m for m in result.column_metadata()}
+    assert meta["v"]["type"] == "varchar" and meta["v"]["is_unicode"] is False
+    assert meta["c"]["type"] == "char" and meta["c"]["is_unicode"] is False
+    assert meta["nv"]["type"] == "nvarchar" and meta["nv"]["is_unicode"] is True
+    assert meta["nc"]["type"] == "nchar" and meta["nc"]["is_unicode"] is True
+    assert meta["i"]["type"] == "int" and meta["i"]["is_unicode"] is False
+    assert [m["name"] for m in result.column_metadata()] == result.columns()
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_describe_reports_lengths_in_bytes_and_characters(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        cols = await conn.describe("""
+            SELECT CAST('a' AS VARCHAR(10)) AS v,
+                   CAST(N'a' AS NVARCHAR(10)) AS nv,
+                   CAST(N'a' AS NCHAR(4)) AS nc,
+                   CAST(N'a' AS NVARCHAR(MAX)) AS nmax,
+                   CAST(1.5 AS DECIMAL(9, 2)) AS d
+        """)
+    meta = {c["name"]: c for c in cols}
+
+    assert meta["v"]["declared_type"] == "varchar(10)"
+    assert meta["v"]["max_length"] == 10
+    assert meta["v"]["char_length"] == 10
+    assert meta["v"]["is_unicode"] is False
+
+    assert meta["nv"]["type"] == "nvarchar"
+    assert meta["nv"]["max_length"] == 20
+    assert meta["nv"]["char_length"] == 10
+    assert meta["nv"]["is_unicode"] is True
+
+    assert meta["nc"]["char_length"] == 4
+    assert meta["nmax"]["max_length"] == -1
+    assert meta["nmax"]["char_length"] == -1
+
+    assert meta["d"]["char_length"] is None
+    assert (meta["d"]["precision"], meta["d"]["scale"]) == (9, 2)
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_describe_does_not_execute(test_config: Config):
+    table = "test_describe_no_exec"
+    async with Connection(test_config.connection_string) as conn:
+        await conn.execute(f"IF OBJECT_ID('{table}', 'U') IS NOT NULL DROP TABLE {table}")
+        await conn.execute(f"CREATE TABLE {table} (id INT NOT NULL, label NVARCHAR(5) NULL)")
+        try:
+            cols = await conn.describe(f"SELECT id, label FROM {table}")
+            assert [(c["name"], c["nullable"]) for c in cols] == [("id", False), ("label", True)]
+
+            await conn.execute(f"INSERT INTO {table} VALUES (1, N'x')")
+            await conn.describe(f"DELETE FROM {table}; SELECT id FROM {table}")
+            result = await conn.query(f"SELECT COUNT(*) AS cnt FROM {table}")
+            assert result.rows()[0]["cnt"] == 1
+        finally:
+            await conn.execute(f"DROP TABLE {table}")
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_describe_flags_identity_and_computed(test_config: Config):
+    table = "test_describe_identity_computed"
+    async with Connection(test_config.connection_string) as conn:
+        await conn.execute(f"IF OBJECT_ID('{table}', 'U') IS NOT NULL DROP TABLE {table}")
+        await conn.execute(
+            f"CREATE TABLE {table} (id INT IDENTITY PRIMARY KEY, qty INT, doubled AS qty * 2)"
+        )
+        try:
+            cols = await conn.describe(f"SELECT id, qty, doubled FROM {table}")
+            assert [(c["is_identity"], c["is_computed"]) for c in cols] == [
+                (True, False),
+                (False, False),
+                (False, True),
+            ]
+        finally:
+            await conn.execute(f"DROP TABLE {table}")
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_table_columns_flags_non_insertable_columns(test_config: Config):
+    table = "test_table_columns_flags"
+    async with Connection(test_config.connection_string) as conn:
+        await conn.execute(f"IF OBJECT_ID('{table}', 'U') IS NOT NULL DROP TABLE {table}")
+        await conn.execute(
+            f"CREATE TABLE {table} ("
+            "id INT IDENTITY PRIMARY KEY, "
+            "qty INT NOT NULL, "
+            "created DATETIME2 NOT NULL DEFAULT SYSUTCDATETIME(), "
+            "doubled AS qty * 2, "
+            "tripled AS qty * 3 PERSISTED, "
+            "rv ROWVERSION)"
+        )
+        try:
+            cols = {c["name"]: c for c in await conn.table_columns(table)}
+            assert list(cols) == ["id", "qty", "created", "doubled", "tripled", "rv"]
+
+            assert cols["id"]["is_identity"] is True
+            assert cols["qty"]["nullable"] is False
+            assert cols["created"]["has_default"] is True
+            assert cols["doubled"]["is_computed"] is True
+            assert cols["doubled"]["is_persisted"] is False
+            assert cols["tripled"]["is_persisted"] is True
+            assert cols["rv"]["is_rowversion"] is True
+
+            insertable = [name for name, c in cols.items() if c["is_insertable"]]
+            assert insertable == ["qty", "created"]
+            assert await conn.bulk_insert(table, insertable, [[1, "2024-01-01"]]) == 1
+        finally:
+            await conn.execute(f"DROP TABLE {table}")
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_table_columns_unknown_table(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        with pytest.raises(ValueError):
+            await conn.table_columns("test_table_columns_does_not_exist")
@@ -306,3 +306,97 @@ class TestConnectionPoolPresetConfigs:
             async with Connection(test_config.connection_string, preset) as conn:
                 result = await conn.query("SELECT 1 as test")
                 assert result.has_rows()
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_idle_maintenance_restores_min_idle(test_config: Config):
+    """The maintenance task reopens idle connections up to min_idle."""
+    config = PoolConfig(max_size=5, min_idle=3, health_check_interval_secs=1)
+    async with Connection(test_config.connection_string, pool_config=config) as conn:
+        await conn.query("SELECT 1")
+        await asyncio.sleep(2.5)
+        stats = await conn.pool_stats()
+        assert stats["idle_connections"] >= 3
+        assert stats["connections"] <= 5
+        assert (await conn.query("SELECT 1 AS ok")).fetchone()["ok"] == 1
+
+
+@pytest.mark.asyncio
+async def test_pool_stats_reports_key():
+    """The key comes from the connection settings; unconnected pools are not summed."""
+    conn = Connection(server="db1.example.com", database="tenant_a", username="app", password="pw")
+    stats = await conn.pool_stats(by_key=True)
+    assert stats["key"] == {"server": "db1.example.com", "database": "tenant_a", "user": "app"}
+    assert all(entry["database"] != "tenant_a" for entry in stats["by_key"])
+
+    from_string = Connection("Server=db2;Initial Catalog=tenant_b;User Id=svc;Password=pw")
+    assert (await from_string.pool_stats())["key"] == {
+        "server": "db2",
+        "database": "tenant_b",
+        "user": "svc",
+    }
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_pool_stats_by_key_sums_pools(test_config: Config):
+    """Pools connected with the same settings are summed under one key."""
+    async with Connection(test_config.connection_string) as first:
+        async with Connection(test_config.connection_string) as second:
+            await first.query("SELECT 1")
+            await second.query("SELECT 1")
+            stats = await first.pool_stats(by_key=True)
+            matching = [
+                entry
+                for entry in stats["by_key"]
+                if {k: entry[k] for k in ("server", "database", "user")} == stats["key"]
+            ]
+            assert len(matching) == 1
+            assert matching[0]["pools"] >= 2
+            assert matching[0]["connections"] >= 2
+
+
+@pytest.mark.asyncio
+async def test_login_timeout_names_stage():
+    """A server that accepts TCP but never answers the login hits login_timeout_secs."""
+
+    async def silent(reader, writer):
+        await reader.read()
+
+    server = await asyncio.start_server(silent, "127.0.0.1", 0)
+    port = server.sockets[0].getsockname()[1]
+    config = PoolConfig(max_size=1, min_idle=1, connection_timeout_secs=2, login_timeout_secs=1)
+    try:
+        conn = Connection(
+            f"Server=127.0.0.1,{port};Database=master;User Id=sa;Password=x",
+            pool_config=config,
+        )
+        with pytest.raises(
+            Exception, match="login timed out after 1s during TLS handshake and login"
+        ):
+            await conn.query("SELECT 1")
+    finally:
+        server.close()
+
+
+@pytest.mark.asyncio
+async def test_dual_stack_host_reaches_ipv4_listener():
+    """localhost may resolve to ::1 first; the IPv4-only listener must still be reached."""
+
+    async def silent(reader, writer):
+        await reader.read()
+
+    server = await asyncio.start_server(silent, "127.0.0.1", 0)
+    port = server.sockets[0].getsockname()[1]
+    config = PoolConfig(max_size=1, min_idle=1, connection_timeout_secs=2, login_timeout_secs=1)
+    try:
+        conn = Connection(
+            f"Server=localhost,{port};Database=master;User Id=sa;Password=x",
+            pool_config=config,
+        )
+        # Getting as far as the login proves the TCP connect succeeded.
+        with pytest.raises(Exception, match="during TLS handshake and login"):
+            await conn.query("SELECT 1")
+    finally:
+        server.close()
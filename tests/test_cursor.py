@@ -0,0 +1,56 @@
+"""Tests for the DBAPI-style Cursor."""
+
+import pytest
+from conftest import Config
+
+from fastmssql import Connection, Cursor, Transaction
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_cursor_fetch_methods(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        async with conn.cursor() as cur:
+            assert isinstance(cur, Cursor)
+            await cur.execute(
+                "SELECT n, CAST(n AS VARCHAR(10)) AS label FROM (VALUES (1), (2), (3), (4)) AS t(n) ORDER BY n"
+            )
+            assert cur.rowcount == 4
+            assert [col[0] for col in cur.description] == ["n", "label"]
+            assert all(len(col) == 7 for col in cur.description)
+
+            assert cur.fetchone()["n"] == 1
+            assert [r["n"] for r in cur.fetchmany()] == [2]
+            cur.arraysize = 5
+            assert [r["n"] for r in cur.fetchmany()] == [3, 4]
+            assert cur.fetchall() == []
+            assert cur.fetchone() is None
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_cursor_iteration_and_params(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        cur = conn.cursor()
+        await cur.execute("SELECT n FROM (VALUES (1), (2), (3)) AS t(n) WHERE n > @P1 ORDER BY n", [1])
+        assert [row["n"] for row in cur] == [2, 3]
+        cur.close()
+        with pytest.raises(RuntimeError):
+            cur.fetchone()
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_cursor_executemany_rowcount(test_config: Config):
+    tx = Transaction(test_config.connection_string)
+    try:
+        cur = tx.cursor()
+        await cur.execute("CREATE TABLE #cursor_test (id INT)")
+        assert cur.rowcount == -1
+        assert cur.description is None
+        await cur.executemany("INSERT INTO #cursor_test (id) VALUES (@P1)", [[1], [2], [3]])
+        assert cur.rowcount == 3
+        await cur.execute("SELECT COUNT(*) AS c FROM #cursor_test")
+        assert cur.fetchone()["c"] == 3
+    finally:
+        await tx.close()
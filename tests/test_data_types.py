@@ -5,6 +5,7 @@ This module tests all major SQL Server data types to ensure proper conversion
 between Rust/Tiberius and Python types.
 """
 
+from datetime import date, datetime, time
 from decimal import Decimal
 
 import pytest
@@ -147,6 +148,31 @@ async def test_datetime_types(test_config: Config):
     assert row["smalldatetime_val"] is not None
 
 
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_datetime_columns_are_native_datetimes(test_config: Config):
+    """DATETIME and DATETIME2 come back as datetime.datetime with microsecond precision."""
+    sql = """
+        SELECT CAST('2023-12-25 14:30:45.123' AS DATETIME) AS dt,
+               CAST('2023-12-25 14:30:45.1234567' AS DATETIME2) AS dt2,
+               CAST('2023-12-25 14:30:45.5' AS DATETIME2(1)) AS dt2_short
+    """
+    async with Connection(test_config.connection_string) as conn:
+        row = (await conn.query(sql)).rows()[0]
+        streamed = [r async for r in conn.query_stream(sql)][0]
+        column = await conn.fetch_column(sql)
+
+    assert type(row["dt"]) is datetime
+    # DATETIME counts in 1/300 s, so .123 is stored as .12333...
+    assert row["dt"].replace(microsecond=0) == datetime(2023, 12, 25, 14, 30, 45)
+    assert row["dt"].microsecond // 1000 == 123
+    # DATETIME2's 100 ns ticks are truncated to microseconds.
+    assert row["dt2"] == datetime(2023, 12, 25, 14, 30, 45, 123456)
+    assert row["dt2_short"] == datetime(2023, 12, 25, 14, 30, 45, 500000)
+    assert streamed["dt2"] == row["dt2"]
+    assert column == [row["dt"]]
+
+
 @pytest.mark.integration
 @pytest.mark.asyncio
 async def test_binary_types(test_config: Config):
@@ -1526,3 +1552,317 @@ async def test_float53_is_8_byte(test_config: Config):
     val = result.rows()[0].get("val")
     assert isinstance(val, float)
     assert abs(val - 1.5) < 1e-14
+
+
+# ---------------------------------------------------------------------------
+# TIMESTAMP / ROWVERSION: 8 big-endian bytes, decodable via FastRow.rowversion()
+# ---------------------------------------------------------------------------
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_rowversion_column_decodes(test_config: Config):
+    """ROWVERSION columns come back as 8 bytes and decode to an int."""
+    async with Connection(test_config.connection_string) as conn:
+        result = await conn.query("""
+            DECLARE @t TABLE (id INT, rv ROWVERSION);
+            INSERT INTO @t (id) VALUES (1);
+            SELECT id, rv, CAST(rv AS BIGINT) AS rv_int FROM @t;
+        """)
+    row = result.rows()[0]
+    raw = row.get("rv")
+    assert isinstance(raw, bytes)
+    assert len(raw) == 8
+    assert row.rowversion("rv") == row.get("rv_int")
+    assert row.rowversion(1) == int.from_bytes(raw, "big")
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_rowversion_optimistic_update(test_config: Config):
+    """A rowversion read back from a row can be bound as a parameter in a WHERE clause."""
+    table = "test_rowversion_optimistic"
+    async with Connection(test_config.connection_string) as conn:
+        await conn.execute(f"IF OBJECT_ID('{table}', 'U') IS NOT NULL DROP TABLE {table}")
+        await conn.execute(f"CREATE TABLE {table} (id INT PRIMARY KEY, val INT, rv ROWVERSION)")
+        try:
+            await conn.execute(f"INSERT INTO {table} (id, val) VALUES (1, 0)")
+            row = (await conn.query(f"SELECT rv FROM {table} WHERE id = 1")).rows()[0]
+            stale = row.get("rv")
+
+            sql = f"UPDATE {table} SET val = val + 1 WHERE id = 1 AND rv = @P1"
+            assert await conn.execute(sql, [stale]) == 1
+            # The first update bumped the rowversion, so the stale value no longer matches.
+            assert await conn.execute(sql, [stale]) == 0
+        finally:
+            await conn.execute(f"DROP TABLE {table}")
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_rowversion_rejects_non_binary(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        result = await conn.query(
+            "SELECT CAST(NULL AS ROWVERSION) AS rv_null, 'abc' AS txt, CAST(0x01 AS VARBINARY(1)) AS short"
+        )
+    row = result.rows()[0]
+    assert row.rowversion("rv_null") is None
+    with pytest.raises(ValueError):
+        row.rowversion("txt")
+    with pytest.raises(ValueError):
+        row.rowversion("short")
+
+
+# ---------------------------------------------------------------------------
+# SMALLDATETIME: minute precision on read and on parameter binding
+# ---------------------------------------------------------------------------
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_smalldatetime_decodes_with_minute_precision(test_config: Config):
+    from datetime import datetime
+
+    async with Connection(test_config.connection_string) as conn:
+        result = await conn.query(
+            "SELECT CAST('2023-12-25 14:30:29' AS SMALLDATETIME) AS down, "
+            "CAST('2023-12-25 14:30:30' AS SMALLDATETIME) AS up, "
+            "CAST(NULL AS SMALLDATETIME) AS empty"
+        )
+    row = result.rows()[0]
+    assert row["down"] == datetime(2023, 12, 25, 14, 30)
+    assert row["up"] == datetime(2023, 12, 25, 14, 31)
+    assert row["empty"] is None
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_smalldatetime_parameter_binding(test_config: Config):
+    from datetime import date, datetime
+
+    from fastmssql import Parameter, Parameters
+
+    async with Connection(test_config.connection_string) as conn:
+        result = await conn.query(
+            "SELECT SQL_VARIANT_PROPERTY(@P1, 'BaseType') AS base_type, @P1 AS val, "
+            "@P2 AS from_date, @P3 AS rounded_down, @P4 AS null_val",
+            Parameters(
+                Parameter(datetime(2024, 5, 1, 9, 30, 45, 500000), "SMALLDATETIME"),
+                Parameter(date(2024, 5, 1), "smalldatetime"),
+                Parameter(datetime(2024, 5, 1, 9, 30, 29, 998000), "SMALLDATETIME"),
+                Parameter(None, "SMALLDATETIME"),
+            ),
+        )
+    row = result.rows()[0]
+    assert row["base_type"] == "smalldatetime"
+    assert row["val"] == datetime(2024, 5, 1, 9, 31)
+    assert row["from_date"] == datetime(2024, 5, 1)
+    assert row["rounded_down"] == datetime(2024, 5, 1, 9, 30)
+    assert row["null_val"] is None
+
+
+@pytest.mark.asyncio
+async def test_smalldatetime_parameter_out_of_range(test_config: Config):
+    from datetime import datetime
+
+    from fastmssql import Parameter, Parameters
+
+    # Conversion fails before any connection is made.
+    conn = Connection(test_config.connection_string)
+    with pytest.raises(ValueError):
+        await conn.query(
+            "SELECT @P1 AS val",
+            Parameters(Parameter(datetime(2080, 1, 1), "SMALLDATETIME")),
+        )
+
+
+# ---------------------------------------------------------------------------
+# CHAR/NCHAR padding: preserved by default, stripped with rstrip_char=True
+# ---------------------------------------------------------------------------
+
+_CHAR_PADDING_SQL = """
+    SELECT CAST('ab' AS CHAR(5)) AS char_val,
+           CAST(N'ab' AS NCHAR(5)) AS nchar_val,
+           CAST('ab  ' AS VARCHAR(5)) AS varchar_val,
+           CAST(NULL AS CHAR(5)) AS null_val
+"""
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_char_padding_preserved_by_default(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        row = (await conn.query(_CHAR_PADDING_SQL)).rows()[0]
+    assert row["char_val"] == "ab   "
+    assert row["nchar_val"] == "ab   "
+    assert row["varchar_val"] == "ab  "
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_char_padding_rstrip(test_config: Config):
+    async with Connection(test_config.connection_string, rstrip_char=True) as conn:
+        row = (await conn.query(_CHAR_PADDING_SQL)).rows()[0]
+        # Parameters are not modified; SQL Server ignores trailing spaces in '='.
+        match = await conn.query(
+            "SELECT CASE WHEN CAST('ab' AS CHAR(5)) = @P1 THEN 1 ELSE 0 END AS eq", ["ab"]
+        )
+    assert row["char_val"] == "ab"
+    assert row["nchar_val"] == "ab"
+    # VARCHAR keeps its trailing spaces: they are data, not padding.
+    assert row["varchar_val"] == "ab  "
+    assert row["null_val"] is None
+    assert match.rows()[0]["eq"] == 1
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_char_padding_rstrip_applies_to_derived_transaction(test_config: Config):
+    conn = Connection(test_config.connection_string, rstrip_char=True)
+    async with conn.transaction() as tx:
+        row = (await tx.query(_CHAR_PADDING_SQL)).rows()[0]
+    assert row["char_val"] == "ab"
+
+
+# ---------------------------------------------------------------------------
+# Legacy TEXT/NTEXT/IMAGE: large values are read in full, not truncated
+# ---------------------------------------------------------------------------
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_large_text_and_ntext_values(test_config: Config):
+    table = "test_legacy_text_large"
+    text_value = "abc" * 200_000  # 600,000 bytes
+    ntext_value = "é中" * 150_000  # 300,000 characters, 600,000 bytes
+    image_value = bytes(range(256)) * 2_000
+    async with Connection(test_config.connection_string) as conn:
+        await conn.execute(f"IF OBJECT_ID('{table}', 'U') IS NOT NULL DROP TABLE {table}")
+        await conn.execute(f"CREATE TABLE {table} (id INT, t TEXT, nt NTEXT, img IMAGE)")
+        try:
+            await conn.execute(
+                f"INSERT INTO {table} VALUES (1, @P1, @P2, @P3)",
+                [text_value, ntext_value, image_value],
+            )
+            row = (await conn.query(f"SELECT t, nt, img FROM {table}")).rows()[0]
+            assert row["t"] == text_value
+            assert row["nt"] == ntext_value
+            assert row["img"] == image_value
+
+            # Chunked read via SUBSTRING, as documented in the README.
+            parts = []
+            for start in range(1, len(ntext_value) + 1, 4000):
+                result = await conn.query(
+                    f"SELECT SUBSTRING(nt, @P1, @P2) AS part FROM {table}", [start, 4000]
+                )
+                parts.append(result.rows()[0]["part"])
+            assert "".join(parts) == ntext_value
+        finally:
+            await conn.execute(f"DROP TABLE {table}")
+
+
+# ---------------------------------------------------------------------------
+# Sparse columns and COLUMN_SET
+# ---------------------------------------------------------------------------
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_sparse_columns_and_column_set(test_config: Config):
+    table = "test_sparse_column_set"
+    async with Connection(test_config.connection_string) as conn:
+        await conn.execute(f"IF OBJECT_ID('{table}', 'U') IS NOT NULL DROP TABLE {table}")
+        await conn.execute(
+            f"CREATE TABLE {table} ("
+            "id INT PRIMARY KEY, "
+            "color NVARCHAR(20) SPARSE NULL, "
+            "weight DECIMAL(6, 2) SPARSE NULL, "
+            "[note text] VARCHAR(50) SPARSE NULL, "
+            "attrs XML COLUMN_SET FOR ALL_SPARSE_COLUMNS)"
+        )
+        try:
+            await conn.execute(
+                f"INSERT INTO {table} (id, color, weight, [note text]) VALUES (@P1, @P2, @P3, @P4)",
+                [1, "red", 1.5, "a < b & c"],
+            )
+            await conn.execute(f"INSERT INTO {table} (id, color) VALUES (@P1, @P2)", [2, "blue"])
+            await conn.execute(f"INSERT INTO {table} (id) VALUES (@P1)", [3])
+
+            # Individually selected sparse columns decode like ordinary columns.
+            rows = (
+                await conn.query(f"SELECT id, color, weight FROM {table} ORDER BY id")
+            ).rows()
+            assert [(r["color"], r["weight"]) for r in rows] == [
+                ("red", 1.5),
+                ("blue", None),
+                (None, None),
+            ]
+
+            rows = (await conn.query(f"SELECT id, attrs FROM {table} ORDER BY id")).rows()
+            assert rows[0].column_set("attrs") == {
+                "color": "red",
+                "weight": "1.50",
+                "note text": "a < b & c",
+            }
+            assert rows[1].column_set("attrs") == {"color": "blue"}
+            assert rows[2].column_set("attrs") is None
+            with pytest.raises(ValueError):
+                rows[0].column_set("id")
+        finally:
+            await conn.execute(f"DROP TABLE {table}")
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_date_and_time_columns_are_native(test_config: Config):
+    """DATE and TIME come back as date and time, keeping fractional seconds."""
+    async with Connection(test_config.connection_string) as conn:
+        row = (
+            await conn.query(
+                "SELECT CAST('2023-12-25' AS DATE) AS d, "
+                "CAST('14:30:45.1234567' AS TIME) AS t, CAST(NULL AS TIME) AS t_null"
+            )
+        ).rows()[0]
+
+    assert type(row["d"]) is date
+    assert row["d"] == date(2023, 12, 25)
+    assert row["t"] == time(14, 30, 45, 123456)
+    assert row["t_null"] is None
+
+
+
+# ---------------------------------------------------------------------------
+# XML parameters: ElementTree elements and strings typed "XML" bind as xml
+# ---------------------------------------------------------------------------
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_xml_parameter_binding(test_config: Config):
+    import xml.etree.ElementTree as ET
+
+    from fastmssql import Parameter, Parameters
+
+    element = ET.fromstring('<order id="7"><line sku="A1" qty="2"/></order>')
+    async with Connection(test_config.connection_string) as conn:
+        # .value() only exists on the xml type, so these fail if bound as NVARCHAR.
+        result = await conn.query(
+            "SELECT @P1.value('(/order/@id)[1]', 'int') AS from_element, "
+            "@P2.value('(/a/b)[1]', 'nvarchar(10)') AS from_text, "
+            "@P3 AS null_val",
+            Parameters(element, Parameter("<a><b>ü</b></a>", "XML"), Parameter(None, "XML")),
+        )
+    row = result.rows()[0]
+    assert row["from_element"] == 7
+    assert row["from_text"] == "ü"
+    assert row["null_val"] is None
+
+
+@pytest.mark.asyncio
+async def test_xml_parameter_rejects_other_values(test_config: Config):
+    from fastmssql import Parameter, Parameters
+
+    # Conversion fails before any connection is made.
+    conn = Connection(test_config.connection_string)
+    with pytest.raises(ValueError, match="XML parameter"):
+        await conn.query("SELECT @P1 AS val", Parameters(Parameter(42, "XML")))
@@ -0,0 +1,69 @@
+"""Tests for binding datetime, date, time and timezone-aware datetime parameters."""
+
+from datetime import date, datetime, time, timedelta, timezone
+
+import pytest
+from conftest import Config
+from fake_tds import FakeServer, connect
+
+from fastmssql import Connection
+
+
+@pytest.mark.parametrize(
+    "offset",
+    [timedelta(seconds=30), timedelta(hours=15), timedelta(hours=-14, minutes=-1)],
+)
+@pytest.mark.asyncio
+async def test_unsupported_offsets_are_rejected(offset):
+    value = datetime(2024, 5, 1, 9, 30, tzinfo=timezone(offset))
+    async with FakeServer() as server:
+        conn = connect(server)
+        with pytest.raises(ValueError, match="whole minutes"):
+            await conn.query("SELECT @P1 AS v", [value])
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_temporal_parameters_bind_as_native_types(test_config: Config):
+    cases = [
+        (datetime(2024, 5, 1, 9, 30, 45, 123456), "datetime2"),
+        (date(2024, 5, 1), "date"),
+        (time(9, 30, 45, 250000), "time"),
+        (datetime(2024, 5, 1, 9, 30, tzinfo=timezone(timedelta(hours=5, minutes=30))), "datetimeoffset"),
+    ]
+    async with Connection(test_config.connection_string) as conn:
+        for value, type_name in cases:
+            result = await conn.query(
+                "SELECT @P1 AS v, CAST(SQL_VARIANT_PROPERTY(@P1, 'BaseType') AS NVARCHAR(32)) AS t",
+                [value],
+            )
+            row = result.rows()[0]
+            assert row["t"] == type_name
+            assert row["v"] == value
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_datetime_parameter_keeps_time_of_day(test_config: Config):
+    # datetime is a subclass of date; it must not be truncated to a DATE.
+    async with Connection(test_config.connection_string) as conn:
+        result = await conn.query(
+            "SELECT DATEPART(hour, @P1) AS h, DATEPART(minute, @P1) AS m",
+            [datetime(2024, 5, 1, 9, 30)],
+        )
+    row = result.rows()[0]
+    assert (row["h"], row["m"]) == (9, 30)
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_zoneinfo_datetime_keeps_its_offset(test_config: Config):
+    zoneinfo = pytest.importorskip("zoneinfo")
+    try:
+        tz = zoneinfo.ZoneInfo("America/New_York")
+    except zoneinfo.ZoneInfoNotFoundError:
+        pytest.skip("time zone database not available")
+    value = datetime(2024, 7, 1, 12, 0, tzinfo=tz)
+    async with Connection(test_config.connection_string) as conn:
+        result = await conn.query("SELECT DATEPART(tzoffset, @P1) AS offset_minutes", [value])
+    assert result.rows()[0]["offset_minutes"] == -240
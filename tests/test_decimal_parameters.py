@@ -0,0 +1,71 @@
+"""Tests for binding decimal.Decimal parameters as NUMERIC."""
+
+from decimal import Decimal
+
+import pytest
+from conftest import Config
+from fake_tds import FakeServer, connect
+
+from fastmssql import Connection
+
+
+@pytest.mark.parametrize(
+    "value, match",
+    [
+        (Decimal("NaN"), "finite"),
+        (Decimal("Infinity"), "finite"),
+        (Decimal("1" * 39), "NUMERIC\\(38\\)"),
+        (Decimal("1E+38"), "NUMERIC\\(38\\)"),
+        (Decimal("1E-38"), "37 decimal places"),
+    ],
+)
+@pytest.mark.asyncio
+async def test_unrepresentable_decimals_are_rejected(value, match):
+    async with FakeServer() as server:
+        conn = connect(server)
+        with pytest.raises(ValueError, match=match):
+            await conn.query("SELECT @P1 AS v", [value])
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_decimal_round_trips_exactly(test_config: Config):
+    values = [
+        Decimal("1234.56"),
+        Decimal("-0.0001"),
+        Decimal("1.50"),
+        Decimal("12345678901234567890.123456789012345678"),
+        Decimal("-99999999999999999999999999999999999999"),
+        Decimal("1E+3"),
+        Decimal("0"),
+    ]
+    async with Connection(test_config.connection_string) as conn:
+        for value in values:
+            result = await conn.query("SELECT @P1 AS v", [value])
+            returned = result.rows()[0]["v"]
+            assert isinstance(returned, Decimal)
+            assert returned == value
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_decimal_keeps_scale(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        result = await conn.query(
+            "SELECT CAST(SQL_VARIANT_PROPERTY(@P1, 'Precision') AS INT) AS p, "
+            "CAST(SQL_VARIANT_PROPERTY(@P1, 'Scale') AS INT) AS s",
+            [Decimal("1.50")],
+        )
+        row = result.rows()[0]
+    assert (row["p"], row["s"]) == (3, 2)
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_decimal_sums_exactly(test_config: Config):
+    # 0.1 as a float would already be inexact before reaching the server.
+    async with Connection(test_config.connection_string) as conn:
+        result = await conn.query(
+            "SELECT CAST(@P1 AS DECIMAL(19, 4)) + @P1 + @P1 AS t", [Decimal("0.1")]
+        )
+    assert result.rows()[0]["t"] == Decimal("0.3")
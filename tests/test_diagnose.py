@@ -0,0 +1,48 @@
+"""Tests for fastmssql.diagnose() connectivity reports."""
+
+import pytest
+from conftest import Config
+
+from fastmssql import Connection, diagnose
+
+
+@pytest.mark.asyncio
+async def test_diagnose_reports_dns_failure():
+    report = await diagnose(
+        "Server=no-such-host.invalid,1433;Database=master;User Id=sa;Password=x", timeout=5
+    )
+    assert report["ok"] is False
+    assert report["failed_step"] == "dns"
+    assert report["address"] == "no-such-host.invalid:1433"
+    assert report["proxy"] is None
+    assert [step["step"] for step in report["steps"]] == ["dns"]
+
+
+@pytest.mark.asyncio
+async def test_diagnose_rejects_bad_arguments():
+    with pytest.raises(TypeError):
+        await diagnose(42)
+    with pytest.raises(ValueError):
+        await diagnose("Server=localhost;User Id=sa;Password=x", timeout=0)
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_diagnose_success(test_config: Config):
+    report = await diagnose(test_config.connection_string)
+    assert report["ok"] is True
+    assert report["failed_step"] is None
+    assert [step["step"] for step in report["steps"]] == ["dns", "tcp", "tls", "login", "query"]
+    assert all(step["elapsed_ms"] >= 0 for step in report["steps"])
+
+    conn = Connection(test_config.connection_string)
+    assert (await diagnose(conn))["ok"] is True
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_diagnose_login_failure(test_config: Config):
+    conn_str = test_config.connection_string + ";User Id=fastmssql_no_such_login;Password=wrong"
+    report = await diagnose(conn_str)
+    assert report["failed_step"] == "login"
+    assert report["steps"][-1]["code"] == 18456
@@ -0,0 +1,107 @@
+"""
+Tests for fastmssql.diff().
+
+The fake TDS server returns the result sets, so these run without SQL Server.
+"""
+
+import pytest
+from fake_tds import FakeServer, connect
+
+from fastmssql import diff
+
+ORDERS = [("id", "int"), ("region", "nvarchar"), ("total", "float")]
+
+RESULTS = {
+    "FROM old_orders": (
+        ORDERS,
+        [(1, "eu", 10.0), (2, "eu", 20.0), (3, "us", 30.0), (5, "us", 50.0)],
+    ),
+    "FROM new_orders": (
+        ORDERS,
+        [(2, "eu", 20.0), (3, "us", 31.0), (4, "us", 40.0), (5, "us", 50.0), (6, "eu", 60.0)],
+    ),
+    "FROM unordered": (ORDERS, [(2, "eu", 20.0), (1, "eu", 10.0)]),
+    "FROM by_region": (
+        ORDERS,
+        [(1, "eu", 10.0), (2, "eu", 20.0), (1, "us", 30.0)],
+    ),
+    "FROM empty": (ORDERS, []),
+    "FROM big_old": (ORDERS, [(i, "eu", float(i)) for i in range(1000)]),
+    "FROM big_new": (ORDERS, [(i, "eu", float(i) + (i == 1)) for i in range(1000)]),
+}
+
+
+async def _collect(*args, **kwargs):
+    return [item async for item in diff(*args, **kwargs)]
+
+
+@pytest.mark.asyncio
+async def test_diff_reports_added_removed_and_changed():
+    async with FakeServer(results=RESULTS) as server:
+        conn = connect(server, pool={"max_size": 2})
+        changes = await _collect(
+            "SELECT * FROM old_orders", conn, "SELECT * FROM new_orders", conn, "id"
+        )
+
+    assert changes == [
+        ("removed", (1,)),
+        ("changed", (3,)),
+        ("added", (4,)),
+        ("added", (6,)),
+    ]
+
+
+@pytest.mark.asyncio
+async def test_diff_with_composite_key():
+    async with FakeServer(results=RESULTS) as server:
+        conn = connect(server, pool={"max_size": 2})
+        changes = await _collect(
+            "SELECT * FROM by_region", conn, "SELECT * FROM empty", conn, ["region", "id"]
+        )
+
+    assert changes == [
+        ("removed", ("eu", 1)),
+        ("removed", ("eu", 2)),
+        ("removed", ("us", 1)),
+    ]
+
+
+@pytest.mark.asyncio
+async def test_diff_of_identical_results_is_empty():
+    async with FakeServer(results=RESULTS) as server:
+        conn = connect(server, pool={"max_size": 2})
+        changes = await _collect(
+            "SELECT * FROM new_orders", conn, "SELECT * FROM new_orders", conn, "id"
+        )
+
+    assert changes == []
+
+
+@pytest.mark.asyncio
+async def test_diff_rejects_unordered_results():
+    async with FakeServer(results=RESULTS) as server:
+        conn = connect(server, pool={"max_size": 2})
+        with pytest.raises(ValueError, match="ordered by id"):
+            await _collect("SELECT * FROM unordered", conn, "SELECT * FROM empty", conn, "id")
+
+
+@pytest.mark.asyncio
+async def test_diff_requires_key_columns():
+    with pytest.raises(ValueError, match="at least one column"):
+        await _collect("SELECT 1", None, "SELECT 1", None, [])
+
+
+@pytest.mark.asyncio
+async def test_stopping_at_first_difference_leaves_no_rows_behind():
+    async with FakeServer(results=RESULTS) as server:
+        conn = connect(server, pool={"max_size": 2})
+        changes = diff("SELECT * FROM big_old", conn, "SELECT * FROM big_new", conn, "id")
+        assert await changes.__anext__() == ("changed", (1,))
+        await changes.aclose()
+
+        # Both streams had rows unread, so their connections were discarded and the
+        # next query runs on a fresh one instead of reading the leftovers.
+        result = await conn.query("SELECT * FROM old_orders")
+
+        assert [row["id"] for row in result.rows()] == [1, 2, 3, 5]
+        assert len(server.logins) == 3
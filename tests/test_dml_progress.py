@@ -0,0 +1,60 @@
+"""
+Tests for execute(on_progress=...), which reports the rows a long statement has
+affected so far, polled from sys.dm_exec_query_profiles on a second connection.
+
+The fake TDS server never answers a WAITFOR, so the statement stays in flight until
+its timeout while the progress query is answered with a fixed count.
+"""
+
+import asyncio
+
+import pytest
+from fake_tds import FakeServer, connect
+
+from fastmssql import Connection, QueryTimeoutError
+
+RESULTS = {
+    "@@SPID": ([("spid", "smallint")], [(57,)]),
+    "dm_exec_query_profiles": ([("rows", "int")], [(1500,)]),
+}
+
+LONG_UPDATE = "UPDATE big SET flag = 1; WAITFOR DELAY '01:00:00'"
+
+
+@pytest.mark.asyncio
+async def test_on_progress_reports_rows_while_statement_runs():
+    seen = []
+    async with FakeServer(results=RESULTS) as server:
+        with pytest.raises(QueryTimeoutError):
+            await connect(server, pool={"max_size": 2}).execute(
+                LONG_UPDATE,
+                timeout=1.0,
+                on_progress=seen.append,
+                progress_interval=0.1,
+            )
+
+    # Reported once: the count did not change between polls.
+    assert seen == [1500]
+
+
+@pytest.mark.asyncio
+async def test_on_progress_stops_when_statement_finishes():
+    seen = []
+    async with FakeServer(results=RESULTS) as server:
+        conn = connect(server, pool={"max_size": 2})
+        assert await conn.execute(
+            "UPDATE small SET flag = 1", on_progress=seen.append, progress_interval=0.1
+        ) == 0
+        await asyncio.sleep(0.3)
+
+    assert seen == []
+
+
+def test_progress_arguments_are_validated():
+    conn = Connection("Server=localhost;Database=master;User Id=sa;Password=x")
+    with pytest.raises(ValueError, match="callable"):
+        conn.execute("UPDATE t SET x = 1", on_progress=42)
+    with pytest.raises(ValueError, match="positive"):
+        conn.execute("UPDATE t SET x = 1", on_progress=print, progress_interval=0)
+    with pytest.raises(ValueError, match="requires on_progress"):
+        conn.execute("UPDATE t SET x = 1", progress_interval=1.0)
@@ -0,0 +1,32 @@
+"""Tests for the server name resolution cache."""
+
+import pytest
+from conftest import Config
+
+import fastmssql
+from fastmssql import Connection
+
+
+def test_set_dns_cache_ttl_validation():
+    with pytest.raises(ValueError, match="non-negative"):
+        fastmssql.set_dns_cache_ttl(-1)
+    with pytest.raises(ValueError, match="non-negative"):
+        fastmssql.set_dns_cache_ttl(float("nan"))
+    fastmssql.set_dns_cache_ttl(30)
+
+
+def test_flush_dns_cache_when_empty():
+    fastmssql.flush_dns_cache()
+    assert fastmssql.flush_dns_cache() == 0
+    assert fastmssql.flush_dns_cache("no-such-host.example") == 0
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_connecting_populates_dns_cache(test_config: Config):
+    fastmssql.set_dns_cache_ttl(30)
+    fastmssql.flush_dns_cache()
+    async with Connection(test_config.connection_string) as conn:
+        await conn.query("SELECT 1")
+    assert fastmssql.flush_dns_cache() >= 1
+    assert fastmssql.flush_dns_cache() == 0
@@ -0,0 +1,47 @@
+"""
+Tests for results with no rows.
+
+The fake TDS server returns the result sets, so these run without SQL Server.
+"""
+
+import pytest
+from fake_tds import FakeServer, connect
+
+RESULTS = {
+    "FROM empty": ([("id", "int"), ("name", "nvarchar")], []),
+}
+
+
+@pytest.mark.asyncio
+async def test_empty_result_keeps_columns():
+    async with FakeServer(results=RESULTS) as server:
+        result = await connect(server).query("SELECT id, name FROM empty")
+
+    assert not result.has_rows()
+    assert len(result) == 0
+    assert result.fetchall() == []
+    assert result.columns() == ["id", "name"]
+    assert [c["name"] for c in result.column_metadata()] == ["id", "name"]
+
+
+@pytest.mark.asyncio
+async def test_empty_results_share_their_schema():
+    async with FakeServer(results=RESULTS) as server:
+        conn = connect(server)
+        first = await conn.query("SELECT id, name FROM empty")
+        second = await conn.simple_query("SELECT id, name FROM empty")
+
+    assert first is not second
+    assert first.columns() == second.columns()
+    assert all(a is b for a, b in zip(first.columns(), second.columns()))
+    assert first.schema_fingerprint() == second.schema_fingerprint()
+
+
+@pytest.mark.asyncio
+async def test_statement_without_result_set_has_no_columns():
+    async with FakeServer(results=RESULTS) as server:
+        result = await connect(server).query("UPDATE t SET v = 1")
+
+    assert not result.has_rows()
+    with pytest.raises(ValueError, match="No column information"):
+        result.columns()
@@ -0,0 +1,134 @@
+"""Tests for the exception hierarchy."""
+
+import asyncio
+
+import pytest
+from conftest import Config
+
+import fastmssql
+from fastmssql import (
+    AuthenticationError,
+    Connection,
+    ConversionError,
+    DatabaseError,
+    DataError,
+    FastMssqlError,
+    IntegrityError,
+    InterfaceError,
+    OperationalError,
+    PoolConfig,
+    PoolTimeoutError,
+    ProgrammingError,
+    ProtocolError,
+    SqlConnectionError,
+    SqlError,
+    TlsError,
+)
+
+
+@pytest.mark.parametrize(
+    "exc, parent",
+    [
+        (InterfaceError, FastMssqlError),
+        (DatabaseError, FastMssqlError),
+        (SqlError, DatabaseError),
+        (IntegrityError, SqlError),
+        (ProgrammingError, SqlError),
+        (OperationalError, DatabaseError),
+        (SqlConnectionError, OperationalError),
+        (TlsError, SqlConnectionError),
+        (PoolTimeoutError, SqlConnectionError),
+        (AuthenticationError, OperationalError),
+        (ProtocolError, DatabaseError),
+        (ConversionError, DatabaseError),
+        (DataError, ConversionError),
+    ],
+)
+def test_exception_parents(exc, parent):
+    assert issubclass(exc, parent)
+    assert issubclass(exc, FastMssqlError)
+
+
+def test_exceptions_are_exported():
+    for name in ("FastMssqlError", "OperationalError", "IntegrityError", "ProgrammingError", "PoolTimeoutError", "AuthenticationError"):
+        assert name in fastmssql.__all__
+
+
+@pytest.mark.asyncio
+async def test_pool_checkout_timeout_raises_pool_timeout_error():
+    async def silent(reader, writer):
+        await reader.read()
+
+    server = await asyncio.start_server(silent, "127.0.0.1", 0)
+    port = server.sockets[0].getsockname()[1]
+    config = PoolConfig(max_size=1, min_idle=1, connection_timeout_secs=2, login_timeout_secs=1)
+    try:
+        conn = Connection(
+            f"Server=127.0.0.1,{port};Database=master;User Id=sa;Password=x",
+            pool_config=config,
+        )
+        with pytest.raises(PoolTimeoutError, match="during TLS handshake and login"):
+            await conn.query("SELECT 1")
+    finally:
+        server.close()
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_duplicate_key_raises_integrity_error(test_config: Config):
+    sql = "DECLARE @t TABLE (id INT PRIMARY KEY); INSERT INTO @t VALUES (1), (1)"
+    async with Connection(test_config.connection_string) as conn:
+        with pytest.raises(IntegrityError) as excinfo:
+            await conn.execute(sql)
+    assert excinfo.value.code == 2627
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_missing_table_raises_programming_error(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        with pytest.raises(ProgrammingError) as excinfo:
+            await conn.query("SELECT * FROM dbo.fastmssql_no_such_table")
+    assert excinfo.value.code == 208
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_other_server_errors_stay_sql_error(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        with pytest.raises(SqlError) as excinfo:
+            await conn.query("SELECT 1 / 0 AS x")
+    assert type(excinfo.value) is SqlError
+    assert excinfo.value.code == 8134
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_server_error_attributes(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        with pytest.raises(SqlError) as excinfo:
+            await conn.execute("SELECT 1;\nRAISERROR('boom', 16, 3)")
+    e = excinfo.value
+    assert e.number == e.code == 50000
+    assert e.state == 3
+    assert e.class_ == 16
+    assert e.line_number == 2
+    assert e.procedure is None
+    assert isinstance(e.server, str) and e.server
+    assert e.message == "boom"
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_server_error_procedure_name(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        await conn.execute(
+            "CREATE OR ALTER PROCEDURE dbo.fastmssql_raise_error AS\nBEGIN\n    RAISERROR('from proc', 16, 1)\nEND"
+        )
+        try:
+            with pytest.raises(SqlError) as excinfo:
+                await conn.execute("EXEC dbo.fastmssql_raise_error")
+        finally:
+            await conn.execute("DROP PROCEDURE dbo.fastmssql_raise_error")
+    assert excinfo.value.procedure == "fastmssql_raise_error"
+    assert excinfo.value.line_number == 3
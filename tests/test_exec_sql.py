@@ -0,0 +1,57 @@
+"""Tests for Connection.exec_sql()."""
+
+import pytest
+from conftest import Config
+
+from fastmssql import Connection
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_exec_sql_named_parameters(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        result = await conn.exec_sql(
+            "SELECT @a + @b AS total, @name AS name",
+            {"a": 2, "@b": 3, "name": "widget"},
+        )
+    rows = result.fetchall()
+    assert len(rows) == 1
+    assert rows[0]["total"] == 5
+    assert rows[0]["name"] == "widget"
+    assert result.output_params == {}
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_exec_sql_output_parameters(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        result = await conn.exec_sql(
+            "SET @doubled = @value * 2; SET @counter = @counter + 1",
+            {"value": 21, "counter": 9},
+            outputs={"doubled": "int", "counter": "int"},
+        )
+    assert not result.has_rows()
+    assert result.output_params == {"doubled": 42, "counter": 10}
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_exec_sql_returns_rows_flag(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        count = await conn.exec_sql(
+            "DECLARE @t TABLE (id INT); INSERT INTO @t VALUES (1), (2), (@id)",
+            {"id": 3},
+            returns_rows=False,
+        )
+        assert count == 3
+
+        with pytest.raises(ValueError):
+            await conn.exec_sql("DECLARE @x INT = @v", {"v": 1}, returns_rows=True)
+
+
+def test_exec_sql_rejects_invalid_names(test_config: Config):
+    conn = Connection(test_config.connection_string)
+    with pytest.raises(ValueError):
+        conn.exec_sql("SELECT 1", {"bad name": 1})
+    with pytest.raises(ValueError):
+        conn.exec_sql("SELECT 1", outputs={"x": "int; DROP TABLE t"})
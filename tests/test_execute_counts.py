@@ -0,0 +1,29 @@
+"""Tests for Connection.execute_counts()."""
+
+import pytest
+from conftest import Config
+
+from fastmssql import Connection
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_execute_counts_per_statement(test_config: Config):
+    sql = (
+        "DECLARE @t TABLE (id INT, status VARCHAR(10)); "
+        "INSERT INTO @t VALUES (1, 'queued'), (2, 'queued'), (3, 'done'); "
+        "UPDATE @t SET status = 'running' WHERE status = @P1; "
+        "DELETE FROM @t WHERE status = 'done'"
+    )
+    async with Connection(test_config.connection_string) as conn:
+        counts = await conn.execute_counts(sql, ["queued"])
+        total = await conn.execute(sql, ["queued"])
+    assert counts == [3, 2, 1]
+    assert total == sum(counts)
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_execute_counts_single_statement(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        assert await conn.execute_counts("DECLARE @t TABLE (id INT); INSERT INTO @t VALUES (1)") == [1]
@@ -0,0 +1,37 @@
+"""Tests for Connection.execute_returning()."""
+
+import pytest
+from conftest import Config
+
+from fastmssql import Connection
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_execute_returning_output_rows_and_count(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        result = await conn.execute_returning(
+            "DECLARE @t TABLE (id INT, status VARCHAR(10)); "
+            "INSERT INTO @t VALUES (1, 'queued'), (2, 'queued'), (3, 'done'); "
+            "UPDATE @t SET status = 'running' OUTPUT inserted.id, deleted.status AS previous "
+            "WHERE status = @P1",
+            ["queued"],
+        )
+    assert result.rows_affected == 2
+    rows = result.fetchall()
+    assert sorted(r["id"] for r in rows) == [1, 2]
+    assert all(r["previous"] == "queued" for r in rows)
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_execute_returning_without_output(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        result = await conn.execute_returning(
+            "DECLARE @t TABLE (id INT); INSERT INTO @t VALUES (1), (2), (3)"
+        )
+        assert result.rows_affected == 3
+        assert not result.has_rows()
+
+        plain = await conn.query("SELECT 1 AS n")
+        assert plain.rows_affected is None
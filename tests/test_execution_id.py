@@ -0,0 +1,45 @@
+"""Tests for per-execution IDs on Connection calls."""
+
+import pytest
+from conftest import Config
+
+from fastmssql import Connection, SqlError
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_query_results_carry_unique_execution_ids(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        first = await conn.query("SELECT 1 AS n")
+        second = await conn.simple_query("SELECT 2 AS n")
+    assert first.execution_id
+    assert second.execution_id
+    assert first.execution_id != second.execution_id
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_errors_carry_execution_id(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        with pytest.raises(SqlError) as exc_info:
+            await conn.execute("SELECT * FROM table_that_does_not_exist_exec_id")
+    execution_id = exc_info.value.execution_id
+    assert execution_id
+    assert any(execution_id in note for note in exc_info.value.__notes__)
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_trace_context_info_sets_session_context(test_config: Config):
+    async with Connection(test_config.connection_string, trace_context_info=True) as conn:
+        result = await conn.query(
+            "SELECT CAST(CONTEXT_INFO() AS VARCHAR(17)) AS id, @P1 AS echoed", ["x"]
+        )
+        row = result.fetchone()
+        assert row["id"] == result.execution_id
+        assert row["echoed"] == "x"
+        assert await conn.execute("DECLARE @t TABLE (n INT); INSERT INTO @t VALUES (1), (2)") == 2
+
+        untraced = conn.with_options(trace_context_info=False)
+        result = await untraced.query("SELECT CAST(CONTEXT_INFO() AS VARCHAR(17)) AS id")
+        assert result.execution_id
@@ -0,0 +1,35 @@
+"""Tests for Connection.fetch_column()."""
+
+from datetime import date
+
+import pytest
+from conftest import Config
+
+from fastmssql import Connection
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_fetch_column_returns_first_column(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        values = await conn.fetch_column(
+            "SELECT n, 'ignored' AS other FROM (VALUES (3), (1), (2)) AS t(n) ORDER BY n"
+        )
+    assert values == [1, 2, 3]
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_fetch_column_with_parameters_and_nulls(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        values = await conn.fetch_column(
+            "SELECT d FROM (VALUES (CAST(@P1 AS DATE)), (NULL)) AS t(d)", ["2024-02-29"]
+        )
+    assert values == [date(2024, 2, 29), None]
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_fetch_column_empty_result(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        assert await conn.fetch_column("SELECT 1 AS n WHERE 1 = 0") == []
@@ -0,0 +1,92 @@
+"""Tests for Connection.fetch_page() and its continuation tokens."""
+
+import pytest
+from conftest import Config
+from fake_tds import FakeServer, connect
+
+from fastmssql import Connection
+
+COLUMNS = [("id", "int"), ("name", "nvarchar")]
+
+
+@pytest.mark.asyncio
+async def test_keyset_pages_follow_the_last_key():
+    results = {
+        "WHERE ([id] > @P2) ORDER BY [id]": (COLUMNS, [(3, "c")]),
+        "SELECT TOP (@P1) * FROM (": (COLUMNS, [(1, "a"), (2, "b"), (3, "c")]),
+    }
+    async with FakeServer(results=results) as server:
+        conn = connect(server)
+        first = await conn.fetch_page("SELECT id, name FROM users", page_size=2, key="id")
+        assert [row["id"] for row in first.rows()] == [1, 2]
+        assert first.next_token is not None
+
+        second = await conn.fetch_page(
+            "SELECT id, name FROM users", page_size=2, key="id", token=first.next_token
+        )
+        assert [row["id"] for row in second.rows()] == [3]
+        assert second.next_token is None
+
+
+@pytest.mark.asyncio
+async def test_offset_pages_use_offset_fetch():
+    results = {"OFFSET @P2 ROWS FETCH NEXT @P1 ROWS ONLY": (COLUMNS, [(1, "a"), (2, "b")])}
+    async with FakeServer(results=results) as server:
+        conn = connect(server)
+        page = await conn.fetch_page("SELECT id, name FROM users ORDER BY id;", page_size=1)
+        assert len(page) == 1
+        assert page.next_token is not None
+        await conn.fetch_page(
+            "SELECT id, name FROM users ORDER BY id;", page_size=1, token=page.next_token
+        )
+
+
+@pytest.mark.asyncio
+async def test_token_is_tied_to_its_query():
+    results = {"OFFSET": (COLUMNS, [(1, "a"), (2, "b")])}
+    async with FakeServer(results=results) as server:
+        conn = connect(server)
+        page = await conn.fetch_page("SELECT id FROM users ORDER BY id", page_size=1)
+        with pytest.raises(ValueError, match="different query"):
+            await conn.fetch_page(
+                "SELECT id FROM orders ORDER BY id", page_size=1, token=page.next_token
+            )
+        with pytest.raises(ValueError, match="different query"):
+            await conn.fetch_page(
+                "SELECT id FROM users ORDER BY id", page_size=1, key="id", token=page.next_token
+            )
+
+
+@pytest.mark.parametrize("token", ["not a token", "e30", ""])
+def test_invalid_tokens_are_rejected(token):
+    conn = Connection("Server=localhost;User Id=sa;Password=x")
+    with pytest.raises(ValueError, match="(?i)page token"):
+        conn.fetch_page("SELECT 1 AS id", page_size=1, key="id", token=token)
+
+
+@pytest.mark.parametrize("kwargs", [{"page_size": 0}, {"page_size": 1, "key": []}])
+def test_invalid_arguments_are_rejected(kwargs):
+    conn = Connection("Server=localhost;User Id=sa;Password=x")
+    with pytest.raises(ValueError):
+        conn.fetch_page("SELECT 1 AS id", **kwargs)
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_keyset_paging_reads_every_row_once(test_config: Config):
+    query = (
+        "SELECT n % 3 AS bucket, n FROM (SELECT TOP (25) "
+        "ROW_NUMBER() OVER (ORDER BY (SELECT NULL)) AS n FROM sys.all_objects) AS t "
+        "WHERE n > @P1"
+    )
+    async with Connection(test_config.connection_string) as conn:
+        seen, token = [], None
+        while True:
+            page = await conn.fetch_page(
+                query, [0], page_size=7, key=["bucket", "n"], token=token
+            )
+            seen.extend((row["bucket"], row["n"]) for row in page)
+            token = page.next_token
+            if token is None:
+                break
+    assert seen == sorted((n % 3, n) for n in range(1, 26))
@@ -0,0 +1,61 @@
+"""Tests for the FILESTREAM helpers on Transaction.
+
+The integration tests need a database with a FILESTREAM filegroup; they are skipped
+when creating a FILESTREAM table fails.
+"""
+
+import pytest
+from conftest import Config
+
+from fastmssql import SqlError, Transaction
+
+TABLE = "test_filestream_docs"
+
+
+async def _create_filestream_table(tx: Transaction):
+    await tx.execute(f"IF OBJECT_ID('{TABLE}', 'U') IS NOT NULL DROP TABLE {TABLE}")
+    try:
+        await tx.execute(
+            f"CREATE TABLE {TABLE} ("
+            "doc_id UNIQUEIDENTIFIER ROWGUIDCOL NOT NULL UNIQUE DEFAULT NEWID(), "
+            "name NVARCHAR(50) PRIMARY KEY, "
+            "content VARBINARY(MAX) FILESTREAM NULL)"
+        )
+    except SqlError as e:
+        pytest.skip(f"FILESTREAM is not enabled on this database: {e}")
+
+
+@pytest.mark.asyncio
+async def test_filestream_context_requires_transaction(test_config: Config):
+    tx = Transaction(test_config.connection_string)
+    with pytest.raises(RuntimeError):
+        await tx.filestream_context(TABLE, "content", "name", "doc")
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_filestream_read_write(test_config: Config):
+    tx = Transaction(test_config.connection_string)
+    try:
+        await _create_filestream_table(tx)
+        await tx.execute(f"INSERT INTO {TABLE} (name, content) VALUES (@P1, 0x)", ["doc"])
+
+        await tx.begin()
+        assert await tx.filestream_write(TABLE, "content", "name", "doc", b"hello world") == 1
+        assert await tx.filestream_read(TABLE, "content", "name", "doc") == b"hello world"
+        assert await tx.filestream_read(TABLE, "content", "name", "doc", offset=6) == b"world"
+        assert await tx.filestream_read(TABLE, "content", "name", "doc", 0, 5) == b"hello"
+
+        assert await tx.filestream_write(TABLE, "content", "name", "doc", b"W", offset=6) == 1
+        assert await tx.filestream_read(TABLE, "content", "name", "doc") == b"hello World"
+
+        ctx = await tx.filestream_context(TABLE, "content", "name", "doc")
+        assert isinstance(ctx["path"], str)
+        assert isinstance(ctx["transaction_context"], bytes)
+        assert len(ctx["transaction_context"]) > 0
+        await tx.commit()
+
+        assert await tx.filestream_read(TABLE, "content", "name", "missing") is None
+    finally:
+        await tx.execute(f"IF OBJECT_ID('{TABLE}', 'U') IS NOT NULL DROP TABLE {TABLE}")
+        await tx.close()
@@ -0,0 +1,45 @@
+"""Tests for QueryStream.schema_fingerprint() and statement_fingerprint()."""
+
+import pytest
+from conftest import Config
+
+from fastmssql import Connection, statement_fingerprint
+
+
+def test_statement_fingerprint_ignores_literals_case_and_layout():
+    base = statement_fingerprint("SELECT name FROM users WHERE id = 42 AND status = 'active'")
+    assert len(base) == 16
+    assert base == statement_fingerprint(
+        "select  name\n from USERS -- comment\n where id = @P1 and status = N'x';"
+    )
+    assert base != statement_fingerprint("SELECT name FROM users WHERE id = 42")
+
+
+def test_statement_fingerprint_collapses_in_lists():
+    assert statement_fingerprint("SELECT * FROM t WHERE id IN (1, 2, 3)") == statement_fingerprint(
+        "SELECT * FROM t WHERE id IN (@P1)"
+    )
+
+
+def test_statement_fingerprint_keeps_identifiers_distinct():
+    assert statement_fingerprint("SELECT a FROM [Order Details]") != statement_fingerprint(
+        "SELECT a FROM [Orders]"
+    )
+    assert statement_fingerprint("SELECT t1.a FROM t1") != statement_fingerprint(
+        "SELECT t2.a FROM t2"
+    )
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_schema_fingerprint(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        a = await conn.query("SELECT CAST(1 AS INT) AS id, N'x' AS name")
+        b = await conn.query("SELECT CAST(2 AS INT) AS id, N'yy' AS name")
+        c = await conn.query("SELECT CAST(1 AS BIGINT) AS id, N'x' AS name")
+        d = await conn.query("SELECT CAST(1 AS INT) AS id, N'x' AS label")
+
+        assert a.schema_fingerprint() == b.schema_fingerprint()
+        assert len(a.schema_fingerprint()) == 16
+        assert a.schema_fingerprint() != c.schema_fingerprint()
+        assert a.schema_fingerprint() != d.schema_fingerprint()
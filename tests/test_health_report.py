@@ -0,0 +1,49 @@
+"""Tests for Connection.health_report(), run against the fake TDS server."""
+
+import json
+
+import pytest
+from fake_tds import FakeServer
+
+from fastmssql import Connection, PoolConfig
+
+
+def _config(**kwargs):
+    return PoolConfig(max_size=2, min_idle=1, connection_timeout_secs=4, retry_connection=False, **kwargs)
+
+
+@pytest.mark.asyncio
+async def test_healthy_connection_reports_ok():
+    async with FakeServer() as server:
+        conn = Connection(server.connection_string, pool_config=_config())
+        report = await conn.health_report()
+
+    assert report["status"] == "ok"
+    assert report["ping_ms"] >= 0
+    assert report["ping_error"] is None
+    assert report["max_size"] == 2
+    assert 0 <= report["saturation"] <= 1
+    assert report["circuit"] == "closed"
+    assert report["last_error"] is None and report["last_error_at"] is None
+    json.dumps(report)
+
+
+@pytest.mark.asyncio
+async def test_refused_login_reports_down():
+    async with FakeServer(18456, state=8, message="Login failed for user 'sa'.") as server:
+        conn = Connection(server.connection_string, pool_config=_config())
+        report = await conn.health_report(timeout=10.0)
+
+    assert report["status"] == "down"
+    assert report["ping_ms"] is None
+    assert "Login failed" in report["ping_error"]
+    assert report["last_error"] is not None
+    assert report["last_error_at"].endswith("Z")
+    json.dumps(report)
+
+
+@pytest.mark.asyncio
+async def test_timeout_must_be_positive():
+    conn = Connection("Server=127.0.0.1,1;Database=master;User Id=sa;Password=x")
+    with pytest.raises(ValueError, match="timeout"):
+        await conn.health_report(timeout=0)
@@ -0,0 +1,62 @@
+"""Tests for host aliases and the resolver callable."""
+
+import asyncio
+
+import pytest
+
+import fastmssql
+from fastmssql import Connection, PoolConfig
+
+
+async def _silent_server():
+    async def silent(reader, writer):
+        await reader.read()
+
+    server = await asyncio.start_server(silent, "127.0.0.1", 0)
+    return server, server.sockets[0].getsockname()[1]
+
+
+async def _reaches_login(server_name):
+    """True when a connection to server_name got past TCP to the (silent) login."""
+    config = PoolConfig(max_size=1, min_idle=1, connection_timeout_secs=2, login_timeout_secs=1)
+    conn = Connection(
+        f"Server={server_name};Database=master;User Id=sa;Password=x", pool_config=config
+    )
+    with pytest.raises(Exception) as excinfo:
+        await conn.query("SELECT 1")
+    return "during TLS handshake and login" in str(excinfo.value)
+
+
+def test_set_resolver_requires_callable():
+    with pytest.raises(TypeError, match="callable"):
+        fastmssql.set_resolver("10.0.0.1")
+    fastmssql.set_resolver(None)
+
+
+@pytest.mark.asyncio
+async def test_host_alias_redirects_connection():
+    server, port = await _silent_server()
+    try:
+        fastmssql.set_host_aliases({"Aliased-DB.invalid": f"127.0.0.1:{port}"})
+        assert await _reaches_login("aliased-db.invalid")
+    finally:
+        fastmssql.set_host_aliases(None)
+        server.close()
+
+
+@pytest.mark.asyncio
+async def test_resolver_callable_redirects_connection():
+    server, port = await _silent_server()
+    calls = []
+
+    def resolve(host, requested_port):
+        calls.append((host, requested_port))
+        return [f"127.0.0.1:{port}"] if host == "resolved-db.invalid" else None
+
+    try:
+        fastmssql.set_resolver(resolve)
+        assert await _reaches_login("resolved-db.invalid,1500")
+        assert calls[0] == ("resolved-db.invalid", 1500)
+    finally:
+        fastmssql.set_resolver(None)
+        server.close()
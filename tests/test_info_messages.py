@@ -0,0 +1,41 @@
+"""Tests for PRINT / RAISERROR informational messages."""
+
+import pytest
+from conftest import Config
+
+from fastmssql import Connection
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_print_messages_on_result(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        result = await conn.query("PRINT 'first'; RAISERROR('second %d', 0, 1, 2); SELECT 1 AS n")
+        assert result.messages == ["first", "second 2"]
+        assert result.rows()[0]["n"] == 1
+
+        quiet = await conn.query("SELECT 1 AS n")
+        assert quiet.messages == []
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_on_message_callback(test_config: Config):
+    received = []
+    async with Connection(test_config.connection_string, on_message=received.append) as conn:
+        await conn.execute("PRINT 'from execute'")
+        returning = await conn.execute_returning(
+            "DECLARE @t TABLE (id INT); PRINT 'inserting'; INSERT INTO @t VALUES (1)"
+        )
+    assert received == ["from execute", "inserting"]
+    assert returning.messages == ["inserting"]
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_on_message_from_with_options(test_config: Config):
+    received = []
+    async with Connection(test_config.connection_string) as conn:
+        await conn.with_options(on_message=received.append).simple_query("PRINT 'hello'")
+        await conn.simple_query("PRINT 'not delivered'")
+    assert received == ["hello"]
@@ -0,0 +1,71 @@
+"""
+Tests for QueryStream.to_ipc_bytes() / QueryStream.from_ipc_bytes().
+
+The fake TDS server returns the result sets, so these run without SQL Server.
+"""
+
+import datetime
+
+import pytest
+from fake_tds import FakeServer, connect
+
+from fastmssql import QueryStream
+
+pa = pytest.importorskip("pyarrow")
+
+RESULTS = {
+    "FROM orders": (
+        [("id", "int"), ("name", "nvarchar"), ("total", "float"), ("day", "date")],
+        [
+            (1, "first", 9.5, datetime.date(2024, 5, 1)),
+            (2, None, 0.25, datetime.date(2024, 5, 2)),
+        ],
+    ),
+}
+
+
+async def _query():
+    async with FakeServer(results=RESULTS) as server:
+        return await connect(server).query("SELECT * FROM orders")
+
+
+@pytest.mark.asyncio
+@pytest.mark.parametrize("compression", [None, "zstd", "lz4"])
+async def test_round_trip(compression):
+    result = await _query()
+    fingerprint = result.schema_fingerprint()
+    metadata = result.column_metadata()
+
+    data = result.to_ipc_bytes(compression=compression)
+    assert isinstance(data, bytes)
+    restored = QueryStream.from_ipc_bytes(data)
+
+    assert restored.columns() == ["id", "name", "total", "day"]
+    assert restored.schema_fingerprint() == fingerprint
+    assert restored.column_metadata() == metadata
+    assert [tuple(row.values()) for row in restored] == [
+        (1, "first", 9.5, datetime.date(2024, 5, 1)),
+        (2, None, 0.25, datetime.date(2024, 5, 2)),
+    ]
+
+
+@pytest.mark.asyncio
+async def test_bytes_are_an_arrow_stream():
+    result = await _query()
+    table = pa.ipc.open_stream(result.to_ipc_bytes()).read_all()
+    assert table.column_names == ["id", "name", "total", "day"]
+    assert table.num_rows == 2
+
+
+@pytest.mark.asyncio
+async def test_errors():
+    result = await _query()
+    with pytest.raises(ValueError, match="Unsupported compression"):
+        result.to_ipc_bytes(compression="gzip")
+
+    result.fetchone()
+    with pytest.raises(ValueError, match="already read"):
+        result.to_ipc_bytes()
+
+    with pytest.raises(ValueError, match="Not an Arrow IPC stream"):
+        QueryStream.from_ipc_bytes(b"not arrow")
@@ -0,0 +1,90 @@
+"""
+Tests for returning JSON as parsed Python objects (Connection(parse_json=...)).
+
+The fake TDS server returns the result sets, so these run without SQL Server.
+"""
+
+import pytest
+from fake_tds import FakeServer, connect
+
+from fastmssql import Connection, DataError
+
+FOR_JSON_COLUMN = "JSON_F52E2B61-18A1-11d1-B105-00805F49916B"
+
+RESULTS = {
+    "FOR JSON": (
+        [(FOR_JSON_COLUMN, "nvarchar")],
+        [('[{"id": 1, "name": "wid',), ('get", "tags": ["a", "b"]}, {"id": 2, "name": null}]',)],
+    ),
+    "FROM products": (
+        [("id", "int"), ("attributes", "nvarchar"), ("note", "nvarchar")],
+        [
+            (1, '{"color": "red", "size": 3, "ok": true}', '{"not": "parsed"}'),
+            (2, None, None),
+        ],
+    ),
+    "FROM broken": (
+        [("attributes", "nvarchar")],
+        [("{not json",)],
+    ),
+}
+
+
+@pytest.mark.asyncio
+async def test_json_stays_a_string_by_default():
+    async with FakeServer(results=RESULTS) as server:
+        result = await connect(server).query("SELECT * FROM products FOR JSON PATH")
+
+    assert len(result) == 2
+    assert result.rows()[0][0] == '[{"id": 1, "name": "wid'
+
+
+@pytest.mark.asyncio
+async def test_for_json_chunks_are_joined_and_parsed():
+    async with FakeServer(results=RESULTS) as server:
+        result = await connect(server, parse_json=True).query(
+            "SELECT * FROM products FOR JSON PATH"
+        )
+
+    assert len(result) == 1
+    assert result.fetchone()[0] == [
+        {"id": 1, "name": "widget", "tags": ["a", "b"]},
+        {"id": 2, "name": None},
+    ]
+
+
+@pytest.mark.asyncio
+async def test_named_columns_are_parsed():
+    async with FakeServer(results=RESULTS) as server:
+        conn = connect(server, parse_json=["Attributes"])
+        rows = (await conn.query("SELECT * FROM products")).rows()
+
+    attributes = rows[0]["attributes"]
+    assert attributes == {"color": "red", "size": 3, "ok": True}
+    assert list(attributes) == ["color", "size", "ok"]
+    assert rows[0]["note"] == '{"not": "parsed"}'
+    assert rows[1]["attributes"] is None
+
+
+@pytest.mark.asyncio
+async def test_invalid_json_raises_data_error():
+    async with FakeServer(results=RESULTS) as server:
+        conn = connect(server, parse_json=["attributes"])
+        result = await conn.query("SELECT * FROM broken")
+        with pytest.raises(DataError, match="attributes"):
+            result.rows()
+
+
+@pytest.mark.asyncio
+async def test_with_options_overrides_parse_json():
+    async with FakeServer(results=RESULTS) as server:
+        conn = connect(server, parse_json=["attributes"])
+        plain = conn.with_options(parse_json=False)
+        rows = (await plain.query("SELECT * FROM products")).rows()
+
+    assert rows[0]["attributes"] == '{"color": "red", "size": 3, "ok": true}'
+
+
+def test_single_string_is_rejected():
+    with pytest.raises(ValueError, match="parse_json"):
+        Connection("Server=localhost;Database=db;User Id=sa;Password=x", parse_json="attributes")
@@ -0,0 +1,75 @@
+"""Tests for Parameter.json_list() and the OPENJSON rewrite of IN predicates."""
+
+import pytest
+from conftest import Config
+from fake_tds import FakeServer, connect
+
+from fastmssql import Connection, Parameter, Parameters
+
+
+def test_json_list_serializes_values():
+    param = Parameter.json_list([1, "two", None, True, 2.5])
+    assert param.is_json_list
+    assert param.value == '[1,"two",null,true,2.5]'
+
+
+def test_json_list_accepts_generators():
+    assert Parameter.json_list(i for i in range(3)).value == "[0,1,2]"
+
+
+@pytest.mark.parametrize("values", ["123", 42, [b"raw"], [float("nan")]])
+def test_json_list_rejects_unsupported_values(values):
+    with pytest.raises(ValueError, match="json_list"):
+        Parameter.json_list(values)
+
+
+@pytest.mark.parametrize(
+    "sql",
+    [
+        "SELECT n FROM t WHERE n IN (@P1)",
+        "SELECT n FROM t WHERE n not in(  @P1 )",
+        "SELECT n FROM t WHERE n IN (?)",
+    ],
+)
+@pytest.mark.asyncio
+async def test_in_predicate_reads_from_openjson(sql):
+    results = {"OPENJSON(@P1)": ([("n", "int")], [(1,)])}
+    async with FakeServer(results=results) as server:
+        paramstyle = "qmark" if "?" in sql else None
+        conn = connect(server, paramstyle=paramstyle)
+        result = await conn.query(sql, Parameters(Parameter.json_list([1, 2, 3])))
+        assert [row["n"] for row in result.rows()] == [1]
+
+
+@pytest.mark.asyncio
+async def test_other_placeholders_are_not_rewritten():
+    results = {"OPENJSON": ([("n", "int")], [(1,)])}
+    async with FakeServer(results=results) as server:
+        conn = connect(server)
+        result = await conn.query("SELECT n FROM t WHERE n IN (@P1) AND 'IN (@P1)' = ''", [1])
+        assert not result.has_rows()
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_large_id_list(test_config: Config):
+    ids = list(range(0, 10_000, 2))
+    async with Connection(test_config.connection_string) as conn:
+        result = await conn.query(
+            "SELECT COUNT(*) AS matched FROM (SELECT TOP (10000) "
+            "ROW_NUMBER() OVER (ORDER BY (SELECT NULL)) - 1 AS n "
+            "FROM sys.all_objects a CROSS JOIN sys.all_objects b) AS t "
+            "WHERE n IN (@P1) AND n NOT IN (@P2)",
+            Parameters(Parameter.json_list(ids), Parameter.json_list([0, 2])),
+        )
+        assert result.rows()[0]["matched"] == len(ids) - 2
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_empty_list_matches_nothing(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        result = await conn.query(
+            "SELECT 1 AS n WHERE 1 IN (@P1)", Parameters(Parameter.json_list([]))
+        )
+        assert not result.has_rows()
@@ -0,0 +1,87 @@
+"""Tests for the exception types and hints raised for refused logins."""
+
+import pytest
+from fake_tds import FakeServer
+
+from fastmssql import (
+    AuthenticationError,
+    Connection,
+    DatabaseUnavailableError,
+    InvalidCredentialsError,
+    LoginDisabledError,
+    PoolConfig,
+    SqlError,
+    TlsError,
+)
+
+
+def _config(**kwargs):
+    return PoolConfig(max_size=1, min_idle=1, connection_timeout_secs=4, retry_connection=False, **kwargs)
+
+
+@pytest.mark.parametrize(
+    "code, state, exc_type, hint",
+    [
+        (18456, 8, InvalidCredentialsError, "password is wrong"),
+        (18456, 5, InvalidCredentialsError, "does not exist"),
+        (18456, 7, LoginDisabledError, "disabled"),
+        (18486, 1, LoginDisabledError, "locked out"),
+        (18487, 1, LoginDisabledError, "expired"),
+        (18456, 40, DatabaseUnavailableError, "default database"),
+        (4060, 1, DatabaseUnavailableError, "check Database"),
+        (18456, 58, AuthenticationError, "Windows authentication"),
+    ],
+)
+@pytest.mark.asyncio
+async def test_login_error_types_and_hints(code, state, exc_type, hint):
+    async with FakeServer(code, state=state, message="Login failed for user 'sa'.") as server:
+        conn = Connection(server.connection_string, pool_config=_config())
+        with pytest.raises(exc_type) as excinfo:
+            await conn.query("SELECT 1")
+    e = excinfo.value
+    assert isinstance(e, AuthenticationError)
+    assert (e.code, e.state) == (code, state)
+    assert e.message == "Login failed for user 'sa'."
+    assert hint in e.hint
+    assert str(e) == f"{e.message} (hint: {e.hint})"
+
+
+@pytest.mark.asyncio
+async def test_state_1_stays_plain_authentication_error():
+    # On-premises servers hide the reason: every failed login reports state 1.
+    async with FakeServer(18456, state=1, message="Login failed for user 'sa'.") as server:
+        conn = Connection(server.connection_string, pool_config=_config())
+        with pytest.raises(AuthenticationError) as excinfo:
+            await conn.query("SELECT 1")
+    assert type(excinfo.value) is AuthenticationError
+    assert "error log" in excinfo.value.hint
+
+
+@pytest.mark.asyncio
+async def test_refused_login_is_raised_instead_of_pool_timeout():
+    # No warm-up: the checkout waits while bb8 keeps retrying in the background.
+    config = PoolConfig(max_size=1, min_idle=0, connection_timeout_secs=2)
+    async with FakeServer(18456, state=8, message="Login failed for user 'sa'.") as server:
+        conn = Connection(server.connection_string, pool_config=config)
+        with pytest.raises(InvalidCredentialsError):
+            await conn.query("SELECT 1")
+
+
+def test_hierarchy():
+    for exc_type in (InvalidCredentialsError, LoginDisabledError, DatabaseUnavailableError):
+        assert issubclass(exc_type, AuthenticationError)
+        # Still caught by `except SqlError`, as before these classes existed.
+        assert issubclass(exc_type, SqlError)
+        assert exc_type.__module__ == AuthenticationError.__module__
+    assert not issubclass(TlsError, AuthenticationError)
+
+
+@pytest.mark.asyncio
+async def test_unavailable_database_is_still_a_sql_error():
+    async with FakeServer(4060, message="Cannot open database \"shop\".") as server:
+        conn = Connection(server.connection_string, pool_config=_config())
+        with pytest.raises(SqlError) as excinfo:
+            await conn.query("SELECT 1")
+
+    assert isinstance(excinfo.value, DatabaseUnavailableError)
+    assert excinfo.value.code == 4060
@@ -0,0 +1,40 @@
+"""Tests for retrying logins refused with transient Azure SQL errors."""
+
+import pytest
+from fake_tds import FakeServer
+
+from fastmssql import Connection, PoolConfig
+
+
+def _config():
+    # retry_connection=False leaves only the transient-login retries.
+    return PoolConfig(max_size=1, min_idle=1, connection_timeout_secs=4, retry_connection=False)
+
+
+@pytest.mark.asyncio
+async def test_transient_login_error_is_retried_with_backoff():
+    async with FakeServer(40613, message="Database is not currently available") as server:
+        conn = Connection(server.connection_string, pool_config=_config())
+        with pytest.raises(Exception):
+            await conn.query("SELECT 1")
+    assert len(server.logins) >= 2
+    # The first retry waits 0.5-1s (jittered), not bb8's fixed 200ms.
+    assert server.logins[1] - server.logins[0] >= 0.45
+
+
+@pytest.mark.asyncio
+async def test_login_failed_state_38_is_retried():
+    async with FakeServer(18456, state=38, message="Login failed for user 'sa'.") as server:
+        conn = Connection(server.connection_string, pool_config=_config())
+        with pytest.raises(Exception):
+            await conn.query("SELECT 1")
+    assert len(server.logins) >= 2
+
+
+@pytest.mark.asyncio
+async def test_wrong_password_is_not_retried():
+    async with FakeServer(18456, state=1, message="Login failed for user 'sa'.") as server:
+        conn = Connection(server.connection_string, pool_config=_config())
+        with pytest.raises(Exception):
+            await conn.query("SELECT 1")
+    assert len(server.logins) == 1
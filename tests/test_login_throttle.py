@@ -0,0 +1,53 @@
+"""Tests for limiting how hard a pool logs in to the server."""
+
+import pytest
+from fake_tds import LOGIN7, FakeServer
+
+from fastmssql import Connection, PoolConfig, PoolTimeoutError
+
+
+@pytest.mark.asyncio
+async def test_max_concurrent_logins_serialises_logins():
+    async with FakeServer(login_delay=0.2) as server:
+        config = PoolConfig(
+            max_size=4, min_idle=4, connection_timeout_secs=5, max_concurrent_logins=1
+        )
+        conn = Connection(server.connection_string, pool_config=config)
+        await conn.query("SELECT 1")
+        await server.wait_for(LOGIN7, 4)
+    logins = server.logins
+    # Each login starts only after the previous one was answered.
+    assert all(later - earlier >= 0.15 for earlier, later in zip(logins, logins[1:]))
+
+
+@pytest.mark.asyncio
+async def test_logins_are_concurrent_without_limit():
+    async with FakeServer(login_delay=0.2) as server:
+        config = PoolConfig(max_size=4, min_idle=4, connection_timeout_secs=5)
+        conn = Connection(server.connection_string, pool_config=config)
+        await conn.query("SELECT 1")
+        await server.wait_for(LOGIN7, 4)
+    # Serialised, four logins would span at least 0.6s.
+    assert server.logins[-1] - server.logins[0] < 0.45
+
+
+@pytest.mark.asyncio
+async def test_login_interval_spaces_logins():
+    async with FakeServer() as server:
+        config = PoolConfig(
+            max_size=3, min_idle=3, connection_timeout_secs=5, login_interval_secs=0.2
+        )
+        conn = Connection(server.connection_string, pool_config=config)
+        await conn.query("SELECT 1")
+        await server.wait_for(LOGIN7, 3)
+    logins = server.logins
+    assert all(later - earlier >= 0.15 for earlier, later in zip(logins, logins[1:]))
+
+
+@pytest.mark.asyncio
+async def test_throttled_login_timeout_names_throttling():
+    async with FakeServer(40501, message="The service is currently busy.") as server:
+        config = PoolConfig(max_size=1, min_idle=0, connection_timeout_secs=2)
+        conn = Connection(server.connection_string, pool_config=config)
+        with pytest.raises(PoolTimeoutError, match="max_concurrent_logins"):
+            await conn.query("SELECT 1")
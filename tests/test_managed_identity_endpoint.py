@@ -0,0 +1,77 @@
+"""
+Tests for managed identity endpoint overrides and the Azure Arc challenge flow.
+
+A local HTTP server stands in for the metadata service and the fake TDS server
+accepts the token-based login, so these run without Azure or SQL Server.
+"""
+
+from urllib.parse import parse_qs, urlparse
+
+import pytest
+from fake_identity import IdentityEndpoint
+from fake_tds import FakeServer
+
+from fastmssql import AzureCredential, Connection, PoolConfig
+
+
+def _pool_config():
+    return PoolConfig(max_size=1, min_idle=0, connection_timeout_secs=5, test_on_check_out=False)
+
+
+@pytest.mark.parametrize("endpoint", ["not a url", "ftp://example.com/token", ""])
+def test_endpoint_must_be_http_url(endpoint):
+    with pytest.raises(ValueError, match="endpoint"):
+        AzureCredential.managed_identity(endpoint=endpoint)
+
+
+def test_api_version_must_not_be_empty():
+    with pytest.raises(ValueError, match="api_version"):
+        AzureCredential.managed_identity(api_version=" ")
+
+
+def test_overrides_are_visible_in_config():
+    cred = AzureCredential.managed_identity(
+        "client", endpoint="http://localhost:8080/token", api_version="2019-08-01"
+    )
+    assert cred.config["endpoint"] == "http://localhost:8080/token"
+    assert cred.config["api_version"] == "2019-08-01"
+
+
+@pytest.mark.asyncio
+async def test_token_is_requested_from_endpoint_override():
+    with IdentityEndpoint() as identity:
+        cred = AzureCredential.managed_identity(
+            "client", endpoint=identity.url, api_version="2019-08-01"
+        )
+        async with FakeServer() as server:
+            conn = Connection(
+                server.connection_string.replace("User Id=sa;Password=x;", ""),
+                pool_config=_pool_config(),
+                azure_credential=cred,
+            )
+            await conn.execute("SET NOCOUNT ON")
+
+    path, headers = identity.requests[0]
+    query = parse_qs(urlparse(path).query)
+    assert query["api-version"] == ["2019-08-01"]
+    assert query["resource"] == ["https://database.windows.net/"]
+    assert query["client_id"] == ["client"]
+    assert headers["metadata"] == "true"
+
+
+@pytest.mark.asyncio
+async def test_challenge_outside_arc_key_directory_is_refused(tmp_path):
+    secret = tmp_path / "stolen.key"
+    secret.write_text("secret")
+    with IdentityEndpoint(challenge=secret) as identity:
+        cred = AzureCredential.managed_identity(endpoint=identity.url)
+        async with FakeServer() as server:
+            conn = Connection(
+                server.connection_string.replace("User Id=sa;Password=x;", ""),
+                pool_config=_pool_config(),
+                azure_credential=cred,
+            )
+            with pytest.raises(Exception, match="not an Azure Arc key file"):
+                await conn.execute("SET NOCOUNT ON")
+
+    assert all("authorization" not in headers for _, headers in identity.requests)
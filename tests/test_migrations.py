@@ -0,0 +1,112 @@
+"""Tests for Connection.apply_migrations(), run against the fake TDS server."""
+
+import pytest
+from fake_tds import SQL_BATCH, FakeServer, connect
+
+from fastmssql import InterfaceError, PolicyViolation, StatementPolicy
+
+APPLIED = [("name", "nvarchar"), ("checksum", "nvarchar")]
+LOCKED = {"sp_getapplock": ([("result", "int32")], [(0,)])}
+
+
+def _checksum(text):
+    value = 0xCBF29CE484222325
+    for byte in text.encode():
+        value = ((value ^ byte) * 0x100000001B3) & 0xFFFFFFFFFFFFFFFF
+    return f"{value:016x}"
+
+
+def _write(directory, files):
+    for name, text in files.items():
+        (directory / name).write_text(text)
+
+
+@pytest.mark.asyncio
+async def test_dry_run_reports_pending_scripts(tmp_path):
+    first = "CREATE TABLE a (id INT)\n"
+    _write(tmp_path, {"001_a.sql": first, "002_b.sql": "CREATE TABLE b (id INT)\n", "notes.txt": "x"})
+    results = {"SELECT name, checksum": (APPLIED, [("001_a.sql", _checksum(first))])}
+    async with FakeServer(results=results) as server:
+        report = await connect(server).apply_migrations(tmp_path, dry_run=True)
+        assert server.count(SQL_BATCH) == 0
+
+    assert [(r["name"], r["status"]) for r in report] == [
+        ("001_a.sql", "skipped"),
+        ("002_b.sql", "pending"),
+    ]
+    assert all(r["duration_ms"] is None for r in report)
+
+
+@pytest.mark.asyncio
+async def test_scripts_are_split_on_go_lines(tmp_path):
+    script = (
+        "CREATE TABLE t (note NVARCHAR(20))\n"
+        "go\n"
+        "INSERT INTO t VALUES ('\nGO\n')\n"
+        "/* GO */\n"
+        "GO 3 -- three rows\n"
+        "-- only a comment\n"
+        "GO\n"
+    )
+    _write(tmp_path, {"001_t.sql": script})
+    async with FakeServer(results=LOCKED) as server:
+        report = await connect(server).apply_migrations(str(tmp_path))
+        assert server.count(SQL_BATCH) == 4
+
+    assert len(report) == 1
+    assert report[0]["status"] == "applied"
+    assert report[0]["batches"] == 2
+    assert report[0]["transactional"] is True
+    assert report[0]["duration_ms"] >= 0
+
+
+@pytest.mark.asyncio
+async def test_scripts_that_cannot_run_in_a_transaction(tmp_path):
+    _write(
+        tmp_path,
+        {
+            "001_db.sql": "ALTER DATABASE CURRENT SET READ_COMMITTED_SNAPSHOT ON\n",
+            "002_marked.sql": "-- fastmssql:no-transaction\nCREATE INDEX ix ON t (a) WITH (ONLINE = ON)\n",
+            "003_plain.sql": "SELECT 'ALTER DATABASE'\n",
+        },
+    )
+    async with FakeServer(results=LOCKED) as server:
+        report = await connect(server).apply_migrations(tmp_path)
+
+    assert [r["transactional"] for r in report] == [False, False, True]
+
+
+@pytest.mark.asyncio
+async def test_changed_script_is_refused(tmp_path):
+    _write(tmp_path, {"001_a.sql": "CREATE TABLE a (id INT, extra INT)\n"})
+    results = {**LOCKED, "SELECT name, checksum": (APPLIED, [("001_a.sql", "0123456789abcdef")])}
+    async with FakeServer(results=results) as server:
+        with pytest.raises(InterfaceError, match="'001_a.sql' was changed"):
+            await connect(server).apply_migrations(tmp_path)
+        assert server.count(SQL_BATCH) == 0
+
+
+@pytest.mark.asyncio
+async def test_lock_timeout_is_reported(tmp_path):
+    _write(tmp_path, {"001_a.sql": "CREATE TABLE a (id INT)\n"})
+    results = {"sp_getapplock": ([("result", "int32")], [(-1,)])}
+    async with FakeServer(results=results) as server:
+        with pytest.raises(InterfaceError, match="Timed out waiting"):
+            await connect(server).apply_migrations(tmp_path, table="ops.schema_history")
+
+
+@pytest.mark.asyncio
+async def test_statement_policy_checks_every_batch(tmp_path):
+    _write(tmp_path, {"001_a.sql": "CREATE TABLE a (id INT)\nGO\nDROP TABLE b\n"})
+    async with FakeServer(results=LOCKED) as server:
+        conn = connect(server, statement_policy=StatementPolicy(deny=[r"\bDROP\s+TABLE\b"]))
+        with pytest.raises(PolicyViolation):
+            await conn.apply_migrations(tmp_path)
+        assert server.logins == []
+
+
+@pytest.mark.asyncio
+async def test_missing_directory_is_rejected(tmp_path):
+    async with FakeServer() as server:
+        with pytest.raises(ValueError, match="Cannot read migrations directory"):
+            await connect(server).apply_migrations(tmp_path / "missing")
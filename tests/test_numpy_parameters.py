@@ -0,0 +1,48 @@
+"""Tests for binding numpy scalar parameters."""
+
+import pytest
+from conftest import Config
+from fake_tds import FakeServer, connect
+
+from fastmssql import Connection
+
+np = pytest.importorskip("numpy")
+
+
+@pytest.mark.parametrize(
+    "value",
+    [np.int8(-3), np.int64(2**40), np.uint32(7), np.float16(0.5), np.float32(1.25), np.bool_(True)],
+)
+@pytest.mark.asyncio
+async def test_numpy_scalars_are_accepted(value):
+    async with FakeServer() as server:
+        await connect(server).execute("UPDATE t SET v = @P1", [value])
+
+
+@pytest.mark.parametrize("value", [np.uint64(2**64 - 1), np.complex128(1 + 2j)])
+@pytest.mark.asyncio
+async def test_unrepresentable_numpy_scalars_are_rejected(value):
+    async with FakeServer() as server:
+        with pytest.raises(ValueError):
+            await connect(server).execute("UPDATE t SET v = @P1", [value])
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_numpy_scalars_bind_as_native_types(test_config: Config):
+    cases = [
+        (np.int32(42), 42, "bigint"),
+        (np.uint8(255), 255, "bigint"),
+        (np.float32(1.5), 1.5, "float"),
+        (np.float64(2.25), 2.25, "float"),
+        (np.bool_(False), False, "bit"),
+    ]
+    async with Connection(test_config.connection_string) as conn:
+        for value, expected, type_name in cases:
+            result = await conn.query(
+                "SELECT @P1 AS v, CAST(SQL_VARIANT_PROPERTY(@P1, 'BaseType') AS NVARCHAR(32)) AS t",
+                [value],
+            )
+            row = result.fetchone()
+            assert row["v"] == expected
+            assert row["t"] == type_name
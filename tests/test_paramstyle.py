@@ -0,0 +1,86 @@
+"""
+Tests for qmark (``?``) and format (``%s``) placeholder translation.
+
+Statements are checked through the fake TDS server's view of what was sent, or
+against SQL Server for the integration tests.
+"""
+
+import pytest
+from conftest import Config
+
+from fastmssql import Connection
+
+
+@pytest.mark.parametrize("paramstyle", ["pyformat", "numeric", "QMARK", ""])
+def test_unknown_paramstyle_is_rejected(paramstyle):
+    with pytest.raises(ValueError, match="paramstyle"):
+        Connection("Server=localhost;User Id=sa;Password=x", paramstyle=paramstyle)
+
+
+def test_with_options_validates_paramstyle():
+    conn = Connection("Server=localhost;User Id=sa;Password=x")
+    with pytest.raises(ValueError, match="paramstyle"):
+        conn.with_options(paramstyle="named")
+
+
+@pytest.mark.parametrize(
+    "paramstyle, sql, parameters",
+    [
+        ("qmark", "SELECT ?, ?", [1]),
+        ("qmark", "SELECT ?", [1, 2]),
+        ("qmark", "SELECT '?' AS q, @P1", [1]),
+        ("format", "SELECT %s", []),
+        ("format", "SELECT 10 %% 3, %s", [1, 2]),
+    ],
+)
+def test_placeholder_count_must_match_parameters(paramstyle, sql, parameters):
+    conn = Connection("Server=localhost;User Id=sa;Password=x", paramstyle=paramstyle)
+    with pytest.raises(ValueError, match="placeholder"):
+        conn.query(sql, parameters)
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_qmark_placeholders(test_config: Config):
+    async with Connection(test_config.connection_string, paramstyle="qmark") as conn:
+        result = await conn.query(
+            "SELECT ? AS a, ? AS b, '?' AS literal, [?] = 1 /* ? */ -- ?\n", [1, "two"]
+        )
+        row = result.rows()[0]
+        assert row["a"] == 1
+        assert row["b"] == "two"
+        assert row["literal"] == "?"
+        assert row["?"] == 1
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_format_placeholders(test_config: Config):
+    async with Connection(test_config.connection_string, paramstyle="format") as conn:
+        result = await conn.query(
+            "SELECT %s AS a, %d AS b, 7 %% 4 AS modulo, 'x%%' AS literal, '%s' AS kept",
+            ["one", 2],
+        )
+        row = result.rows()[0]
+        assert row["a"] == "one"
+        assert row["b"] == 2
+        assert row["modulo"] == 3
+        assert row["literal"] == "x%"
+        assert row["kept"] == "%s"
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_statements_without_parameters_are_unchanged(test_config: Config):
+    async with Connection(test_config.connection_string, paramstyle="format") as conn:
+        result = await conn.query("SELECT 7 % 4 AS modulo, '?' AS q")
+        assert result.rows()[0]["modulo"] == 3
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_with_options_switches_style(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        qmark = conn.with_options(paramstyle="qmark")
+        assert (await qmark.query("SELECT ? AS v", [5])).rows()[0]["v"] == 5
+        assert (await conn.query("SELECT @P1 AS v", [6])).rows()[0]["v"] == 6
@@ -0,0 +1,80 @@
+"""Tests for Connection.acquire() and PinnedConnection."""
+
+import pytest
+from conftest import Config
+from fake_tds import FakeServer, connect
+
+from fastmssql import Connection, InterfaceError, PoolConfig
+
+RESULTS = {"FROM orders": ([("id", "int")], [(1,), (2,)])}
+
+
+@pytest.mark.asyncio
+async def test_pinned_connection_runs_statements():
+    async with FakeServer(results=RESULTS) as server:
+        conn = connect(server)
+        async with conn.acquire() as pinned:
+            assert pinned.is_acquired()
+            result = await pinned.query("SELECT id FROM orders")
+            assert [row["id"] for row in result] == [1, 2]
+            await pinned.execute("UPDATE orders SET id = id")
+        assert not pinned.is_acquired()
+
+
+@pytest.mark.asyncio
+async def test_pinned_connection_is_returned_to_pool():
+    async with FakeServer(results=RESULTS) as server:
+        conn = connect(server)
+        async with conn.acquire() as pinned:
+            await pinned.query("SELECT id FROM orders")
+        # With max_size=1 this would time out if the connection were still held.
+        result = await conn.query("SELECT id FROM orders")
+        assert len(result) == 2
+        assert len(server.logins) == 1
+
+
+@pytest.mark.asyncio
+async def test_pinned_connection_is_discarded_after_error():
+    async with FakeServer(results=RESULTS) as server:
+        conn = connect(server)
+        with pytest.raises(RuntimeError):
+            async with conn.acquire() as pinned:
+                await pinned.query("SELECT id FROM orders")
+                raise RuntimeError("boom")
+        await conn.query("SELECT id FROM orders")
+        assert len(server.logins) == 2
+
+
+@pytest.mark.asyncio
+async def test_statements_outside_block_raise():
+    async with FakeServer(results=RESULTS) as server:
+        pinned = connect(server).acquire()
+        with pytest.raises(InterfaceError, match="not acquired"):
+            await pinned.query("SELECT id FROM orders")
+
+        async with pinned:
+            await pinned.release()
+            with pytest.raises(InterfaceError, match="not acquired"):
+                await pinned.execute("UPDATE orders SET id = id")
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_temp_table_survives_between_statements(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        async with conn.acquire() as pinned:
+            await pinned.execute("CREATE TABLE #pinned (n INT)")
+            await pinned.execute("INSERT INTO #pinned VALUES (@P1), (@P2)", [1, 2])
+            result = await pinned.query("SELECT SUM(n) AS total FROM #pinned")
+            assert result.fetchone()["total"] == 3
+            await pinned.execute("DROP TABLE #pinned")
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_open_transaction_is_rolled_back_on_exit(test_config: Config):
+    async with Connection(test_config.connection_string, pool_config=PoolConfig(max_size=1)) as conn:
+        async with conn.acquire() as pinned:
+            await pinned.execute("BEGIN TRANSACTION")
+        result = await conn.query("SELECT @@TRANCOUNT AS n")
+        assert result.fetchone()["n"] == 0
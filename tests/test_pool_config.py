@@ -626,3 +626,121 @@ class TestPoolConfigCombinedNewSettings:
         assert config.connection_timeout_secs == 45
         assert config.test_on_check_out is True
         assert config.retry_connection is True
+
+
+@pytest.mark.skipif(PoolConfig is None, reason="fastmssql module not available")
+class TestPoolConfigHealthCheckInterval:
+    """Test PoolConfig health_check_interval_secs configuration."""
+
+    def test_health_check_interval_default(self):
+        """Idle maintenance is disabled unless requested."""
+        assert PoolConfig().health_check_interval_secs is None
+        assert PoolConfig.high_throughput().health_check_interval_secs is None
+
+    def test_health_check_interval_set(self):
+        """Test creating PoolConfig with a maintenance interval."""
+        config = PoolConfig(health_check_interval_secs=60)
+        assert config.health_check_interval_secs == 60
+        assert "health_check_interval_secs=Some(60)" in repr(config)
+
+    def test_health_check_interval_setter(self):
+        """Test updating and clearing the interval."""
+        config = PoolConfig()
+        config.health_check_interval_secs = 15
+        assert config.health_check_interval_secs == 15
+        config.health_check_interval_secs = None
+        assert config.health_check_interval_secs is None
+
+    def test_health_check_interval_zero_rejected(self):
+        """A zero interval is rejected by the constructor and the setter."""
+        with pytest.raises(ValueError, match="health_check_interval_secs"):
+            PoolConfig(health_check_interval_secs=0)
+        config = PoolConfig()
+        with pytest.raises(ValueError, match="health_check_interval_secs"):
+            config.health_check_interval_secs = 0
+
+
+@pytest.mark.skipif(PoolConfig is None, reason="fastmssql module not available")
+class TestPoolConfigLoginTimeout:
+    """Test PoolConfig login_timeout_secs configuration."""
+
+    def test_login_timeout_default(self):
+        """No separate login limit unless requested."""
+        assert PoolConfig().login_timeout_secs is None
+        assert PoolConfig.performance().login_timeout_secs is None
+
+    def test_login_timeout_set(self):
+        config = PoolConfig(login_timeout_secs=5)
+        assert config.login_timeout_secs == 5
+        assert "login_timeout_secs=Some(5)" in repr(config)
+
+    def test_login_timeout_setter(self):
+        config = PoolConfig()
+        config.login_timeout_secs = 3
+        assert config.login_timeout_secs == 3
+        config.login_timeout_secs = None
+        assert config.login_timeout_secs is None
+
+    def test_login_timeout_zero_rejected(self):
+        with pytest.raises(ValueError, match="login_timeout_secs"):
+            PoolConfig(login_timeout_secs=0)
+        config = PoolConfig()
+        with pytest.raises(ValueError, match="login_timeout_secs"):
+            config.login_timeout_secs = 0
+
+
+@pytest.mark.skipif(PoolConfig is None, reason="fastmssql module not available")
+class TestPoolConfigMaxConcurrentLogins:
+    """Test PoolConfig max_concurrent_logins configuration."""
+
+    def test_max_concurrent_logins_default(self):
+        assert PoolConfig().max_concurrent_logins is None
+
+    def test_max_concurrent_logins_set(self):
+        config = PoolConfig(max_size=200, max_concurrent_logins=10)
+        assert config.max_concurrent_logins == 10
+        assert "max_concurrent_logins=Some(10)" in repr(config)
+
+    def test_max_concurrent_logins_setter(self):
+        config = PoolConfig()
+        config.max_concurrent_logins = 4
+        assert config.max_concurrent_logins == 4
+        config.max_concurrent_logins = None
+        assert config.max_concurrent_logins is None
+
+    def test_max_concurrent_logins_zero_rejected(self):
+        with pytest.raises(ValueError, match="max_concurrent_logins"):
+            PoolConfig(max_concurrent_logins=0)
+        config = PoolConfig()
+        with pytest.raises(ValueError, match="max_concurrent_logins"):
+            config.max_concurrent_logins = 0
+
+
+@pytest.mark.skipif(PoolConfig is None, reason="fastmssql module not available")
+class TestPoolConfigLoginPacing:
+    """Test PoolConfig login_interval_secs / login_jitter_secs configuration."""
+
+    def test_login_pacing_default(self):
+        config = PoolConfig()
+        assert config.login_interval_secs is None
+        assert config.login_jitter_secs is None
+
+    def test_login_pacing_set(self):
+        config = PoolConfig(login_interval_secs=0.25, login_jitter_secs=0.1)
+        assert config.login_interval_secs == pytest.approx(0.25)
+        assert config.login_jitter_secs == pytest.approx(0.1)
+        assert "login_interval_secs=Some(0.25)" in repr(config)
+
+    def test_login_pacing_setter(self):
+        config = PoolConfig()
+        config.login_interval_secs = 1
+        assert config.login_interval_secs == 1.0
+        config.login_interval_secs = None
+        assert config.login_interval_secs is None
+
+    def test_login_pacing_negative_rejected(self):
+        with pytest.raises(ValueError, match="login_interval_secs"):
+            PoolConfig(login_interval_secs=-1)
+        config = PoolConfig()
+        with pytest.raises(ValueError, match="login_jitter_secs"):
+            config.login_jitter_secs = float("nan")
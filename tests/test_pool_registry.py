@@ -0,0 +1,60 @@
+"""Tests for the named pool registry (get_pool / close_pool)."""
+
+import threading
+
+import pytest
+from conftest import Config
+
+from fastmssql import Connection, close_pool, get_pool
+
+
+def test_get_pool_unknown_name_without_settings():
+    with pytest.raises(KeyError):
+        get_pool("test_registry_unknown")
+
+
+@pytest.mark.asyncio
+async def test_get_pool_returns_same_connection(test_config: Config):
+    name = "test_registry_same"
+    try:
+        first = get_pool(name, test_config.connection_string)
+        assert isinstance(first, Connection)
+        assert get_pool(name) is first
+        # Settings passed after creation are ignored.
+        assert get_pool(name, test_config.connection_string, rstrip_char=True) is first
+    finally:
+        assert await close_pool(name) is True
+    assert await close_pool(name) is False
+
+
+@pytest.mark.asyncio
+async def test_get_pool_is_atomic_across_threads(test_config: Config):
+    name = "test_registry_threads"
+    results = []
+
+    def worker():
+        results.append(get_pool(name, test_config.connection_string))
+
+    threads = [threading.Thread(target=worker) for _ in range(16)]
+    for t in threads:
+        t.start()
+    for t in threads:
+        t.join()
+
+    try:
+        assert len({id(conn) for conn in results}) == 1
+    finally:
+        await close_pool(name)
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_get_pool_queries(test_config: Config):
+    name = "test_registry_query"
+    try:
+        conn = get_pool(name, test_config.connection_string)
+        result = await get_pool(name).query("SELECT 1 AS ok")
+        assert result.rows()[0]["ok"] == 1
+        assert (await conn.pool_stats())["connected"] is True
+    finally:
+        await close_pool(name)
@@ -0,0 +1,88 @@
+"""Tests for Connection.query_arrow(), run against the fake TDS server."""
+
+import datetime
+
+import pytest
+from fake_tds import FakeServer, connect
+
+pa = pytest.importorskip("pyarrow")
+
+COLUMNS = [
+    ("id", "int"),
+    ("score", "float"),
+    ("active", "bit"),
+    ("name", "nvarchar"),
+    ("payload", "varbinary"),
+    ("born", "date"),
+    ("alarm", "time"),
+    ("seen_at", "datetime2"),
+]
+ROWS = [
+    (1, 2.5, True, "Ada", b"\x00\x01", datetime.date(1815, 12, 10), datetime.time(7, 30), datetime.datetime(2024, 1, 2, 3, 4, 5, 600000)),
+    (2, None, False, None, None, None, None, None),
+    (None, -1.0, None, "Grace", b"", datetime.date(1906, 12, 9), datetime.time(23, 59, 59), datetime.datetime(1970, 1, 1)),
+]
+
+
+@pytest.mark.asyncio
+async def test_query_arrow_builds_typed_columns():
+    async with FakeServer(results={"ARROW_ROWS": (COLUMNS, ROWS)}) as server:
+        conn = connect(server)
+        table = await conn.query_arrow("SELECT * FROM people -- ARROW_ROWS")
+
+    assert table.column_names == [name for name, _ in COLUMNS]
+    assert table.num_rows == 3
+    assert table.schema.field("id").type == pa.int64()
+    assert table.schema.field("score").type == pa.float64()
+    assert table.schema.field("active").type == pa.bool_()
+    assert table.schema.field("name").type == pa.large_string()
+    assert table.schema.field("payload").type == pa.large_binary()
+    assert table.schema.field("born").type == pa.date32()
+    assert table.schema.field("alarm").type == pa.time64("us")
+    assert table.schema.field("seen_at").type == pa.timestamp("us")
+    assert table.to_pylist() == [dict(zip(table.column_names, row)) for row in ROWS]
+
+
+@pytest.mark.asyncio
+async def test_query_arrow_time_as_timedelta():
+    columns = [("alarm", "time")]
+    async with FakeServer(results={"ARROW_TIME": (columns, [(datetime.time(1, 2, 3),)])}) as server:
+        conn = connect(server, time_as_timedelta=True)
+        table = await conn.query_arrow("SELECT alarm -- ARROW_TIME")
+
+    assert table.schema.field("alarm").type == pa.duration("us")
+    assert table.column("alarm").to_pylist() == [datetime.timedelta(hours=1, minutes=2, seconds=3)]
+
+
+@pytest.mark.asyncio
+async def test_query_arrow_empty_result_keeps_columns():
+    async with FakeServer(results={"ARROW_EMPTY": (COLUMNS, [])}) as server:
+        conn = connect(server)
+        table = await conn.query_arrow("SELECT * FROM people WHERE 1 = 0 -- ARROW_EMPTY")
+
+    assert table.num_rows == 0
+    assert table.column_names == [name for name, _ in COLUMNS]
+    assert table.schema.field("name").type == pa.large_string()
+
+
+@pytest.mark.asyncio
+async def test_query_arrow_without_result_set():
+    async with FakeServer() as server:
+        conn = connect(server)
+        table = await conn.query_arrow("UPDATE t SET a = 1 WHERE id = 1")
+
+    assert table.num_rows == 0 and table.num_columns == 0
+
+
+@pytest.mark.asyncio
+async def test_query_polars_returns_dataframe():
+    pl = pytest.importorskip("polars")
+    async with FakeServer(results={"POLARS_ROWS": (COLUMNS, ROWS)}) as server:
+        conn = connect(server)
+        df = await conn.query_polars("SELECT * FROM people -- POLARS_ROWS")
+
+    assert isinstance(df, pl.DataFrame)
+    assert df.columns == [name for name, _ in COLUMNS]
+    assert df.schema["id"] == pl.Int64
+    assert df.schema["name"] == pl.String
+    assert df.rows() == ROWS
@@ -0,0 +1,57 @@
+"""Tests for execute(..., label=...), run against the fake TDS server."""
+
+import logging
+
+import pytest
+from fake_tds import FakeServer, connect
+
+
+@pytest.mark.asyncio
+@pytest.mark.parametrize("label", ["", "x */ DROP TABLE t", "two words", "a" * 129])
+async def test_invalid_labels_are_rejected(label):
+    async with FakeServer() as server:
+        conn = connect(server)
+        with pytest.raises(ValueError, match="Invalid label"):
+            await conn.execute("UPDATE t SET a = 1 WHERE id = 1", label=label)
+        assert (await conn.pool_stats())["labels"] == {}
+
+
+@pytest.mark.asyncio
+async def test_label_is_appended_as_a_comment(caplog):
+    async with FakeServer() as server:
+        conn = connect(server, log_queries=True)
+        with caplog.at_level(logging.DEBUG, logger="fastmssql.query"):
+            await conn.execute("EXEC dbo.refresh -- trailing comment", label="nightly-refresh")
+
+    messages = [r.getMessage() for r in caplog.records if r.name == "fastmssql.query"]
+    assert any(m.endswith("-- trailing comment\n/* fastmssql:label=nightly-refresh */") for m in messages)
+
+
+@pytest.mark.asyncio
+async def test_labelled_runs_are_counted_per_pool():
+    async with FakeServer() as server:
+        conn = connect(server)
+        await conn.execute("UPDATE t SET a = 1 WHERE id = @P1", [1], label="nightly-refresh")
+        await conn.with_options(rstrip_char=True).execute("UPDATE t SET a = 2 WHERE id = 2", label="nightly-refresh")
+        await conn.execute("UPDATE t SET a = 3 WHERE id = 3")
+        stats = (await conn.pool_stats())["labels"]
+
+    assert list(stats) == ["nightly-refresh"]
+    entry = stats["nightly-refresh"]
+    assert entry["executions"] == 2
+    assert entry["errors"] == 0
+    assert entry["rows_affected"] == 0
+    assert entry["max_ms"] >= entry["avg_ms"] >= 0
+    assert entry["total_ms"] == pytest.approx(entry["avg_ms"] * 2)
+
+
+@pytest.mark.asyncio
+async def test_failed_labelled_runs_count_as_errors():
+    async with FakeServer(18456, message="Login failed for user 'sa'.") as server:
+        conn = connect(server)
+        with pytest.raises(Exception):
+            await conn.execute("UPDATE t SET a = 1 WHERE id = 1", label="cleanup")
+        stats = (await conn.pool_stats())["labels"]
+
+    assert stats["cleanup"]["executions"] == 1
+    assert stats["cleanup"]["errors"] == 1
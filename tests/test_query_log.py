@@ -0,0 +1,128 @@
+"""Tests for query logging with parameter redaction."""
+
+import logging
+
+import pytest
+from conftest import Config
+from fake_tds import FakeServer, connect
+
+from fastmssql import Batch, Connection
+
+
+def test_redact_parameters_validation():
+    with pytest.raises(ValueError, match="1-based"):
+        Connection("Server=localhost;Database=test;User Id=sa;Password=x", redact_parameters=[0])
+    with pytest.raises(ValueError, match="positions"):
+        Connection("Server=localhost;Database=test;User Id=sa;Password=x", redact_parameters=[1.5])
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_query_log_redacts_parameters(test_config: Config, caplog):
+    async with Connection(
+        test_config.connection_string,
+        log_queries=True,
+        log_parameters=True,
+        redact_parameters=[2, "*secret*"],
+    ) as conn:
+        with caplog.at_level(logging.DEBUG, logger="fastmssql.query"):
+            result = await conn.query("SELECT @P1 AS a, @P2 AS b", [42, "hunter2"])
+            await conn.exec_sql("SELECT @api_secret AS s", {"api_secret": "s3cr3t"})
+
+    messages = [r.getMessage() for r in caplog.records if r.name == "fastmssql.query"]
+    assert any(result.execution_id in m and "@P1=42" in m and "@P2=***" in m for m in messages)
+    assert any("@api_secret=***" in m for m in messages)
+    assert not any("hunter2" in m or "s3cr3t" in m for m in messages)
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_query_log_omits_parameters_by_default(test_config: Config, caplog):
+    async with Connection(test_config.connection_string, log_queries=True) as conn:
+        with caplog.at_level(logging.DEBUG, logger="fastmssql.query"):
+            await conn.query("SELECT @P1 AS a", ["visible-only-with-log_parameters"])
+            quiet = conn.with_options(log_queries=False)
+            await quiet.query("SELECT 'not logged' AS a")
+
+    messages = [r.getMessage() for r in caplog.records if r.name == "fastmssql.query"]
+    assert any("SELECT @P1 AS a" in m for m in messages)
+    assert not any("visible-only-with-log_parameters" in m for m in messages)
+    assert not any("not logged" in m for m in messages)
+
+
+def _logged(caplog):
+    return [r.getMessage() for r in caplog.records if r.name == "fastmssql.query"]
+
+
+@pytest.mark.asyncio
+async def test_helper_calls_are_logged_with_execution_ids(caplog):
+    results = {"FROM ids": ([("id", "int")], [(1,), (2,)])}
+    async with FakeServer(results=results) as server:
+        conn = connect(server, log_queries=True)
+        with caplog.at_level(logging.DEBUG, logger="fastmssql.query"):
+            assert await conn.fetch_column("SELECT id FROM ids") == [1, 2]
+            await conn.describe("SELECT id FROM described")
+            with pytest.raises(ValueError):
+                await conn.table_columns("dbo.missing")
+            await conn.touched_tables("UPDATE touched SET a = 1")
+            sets = conn.stream_results("SELECT id FROM ids; SELECT id FROM ids")
+            assert [len(rs.rows()) async for rs in sets] == [2]
+
+    messages = _logged(caplog)
+    for statement in (
+        "SELECT id FROM ids",
+        "sp_describe_first_result_set",
+        "sys.columns",
+        "UPDATE touched SET a = 1",
+    ):
+        assert any(statement in m for m in messages), statement
+    assert any(m.startswith(f"[{sets.execution_id}] ") for m in messages)
+    ids = [m[1 : m.index("]")] for m in messages]
+    assert len(ids) == len(set(ids)) == 5
+
+
+@pytest.mark.asyncio
+async def test_migration_batches_are_logged_under_one_execution_id(caplog, tmp_path):
+    (tmp_path / "001_a.sql").write_text("CREATE TABLE a (id INT)\nGO\nCREATE INDEX ix ON a (id)\n")
+    results = {"sp_getapplock": ([("result", "int32")], [(0,)])}
+    async with FakeServer(results=results) as server:
+        conn = connect(server, log_queries=True, trace_context_info=True)
+        with caplog.at_level(logging.DEBUG, logger="fastmssql.query"):
+            await conn.apply_migrations(tmp_path)
+
+    messages = _logged(caplog)
+    assert len(messages) == 2
+    assert "CREATE TABLE a" in messages[0] and "CREATE INDEX ix" in messages[1]
+    assert len({m[: m.index("]")] for m in messages}) == 1
+
+
+@pytest.mark.asyncio
+async def test_batch_calls_are_logged_under_one_execution_id_each(caplog):
+    results = {"FROM ids": ([("id", "int")], [(1,)])}
+    async with FakeServer(results=results) as server:
+        conn = connect(server, log_queries=True, trace_context_info=True)
+        with caplog.at_level(logging.DEBUG, logger="fastmssql.query"):
+            first, second = await conn.query_batch(
+                [("SELECT id FROM ids", None), ("SELECT id FROM ids WHERE id > @P1", [0])]
+            )
+            await conn.execute_batch([("UPDATE a SET x = 1", None), ("UPDATE b SET x = 1", None)])
+            batch = Batch()
+            batch.add("DELETE FROM c")
+            batch.add("DELETE FROM d")
+            await conn.apply(batch)
+            await conn.bulk_insert("loaded", ["id"], [[1], [2]])
+            with pytest.raises(ValueError) as raised:
+                await conn.bulk_insert("missing", ["id"], [[1]], bulk_copy=True)
+
+    assert first.execution_id == second.execution_id
+    by_id = {}
+    for m in _logged(caplog):
+        by_id.setdefault(m[1 : m.index("]")], []).append(m)
+    assert len(by_id) == 5
+    assert len(by_id[first.execution_id]) == 2
+    assert by_id[raised.value.execution_id] == [
+        f"[{raised.value.execution_id}] INSERT BULK [missing] ([id])"
+    ]
+    grouped = sorted(len(statements) for statements in by_id.values())
+    assert grouped == [1, 1, 2, 2, 2]
+    assert any("INSERT INTO [loaded] ([id])" in m for m in _logged(caplog))
@@ -0,0 +1,58 @@
+"""Tests for Connection.query_numpy(), run against the fake TDS server."""
+
+import datetime
+
+import pytest
+from fake_tds import FakeServer, connect
+
+np = pytest.importorskip("numpy")
+
+
+@pytest.mark.asyncio
+async def test_columns_without_nulls_keep_their_dtype():
+    columns = [("id", "int"), ("score", "float"), ("active", "bit"), ("seen_at", "datetime2")]
+    rows = [
+        (1, 2.5, True, datetime.datetime(2024, 1, 2, 3, 4, 5)),
+        (2, -1.0, False, datetime.datetime(1970, 1, 1)),
+    ]
+    async with FakeServer(results={"NUMPY_ROWS": (columns, rows)}) as server:
+        conn = connect(server)
+        arrays = await conn.query_numpy("SELECT * FROM t -- NUMPY_ROWS")
+
+    assert list(arrays) == ["id", "score", "active", "seen_at"]
+    assert arrays["id"].dtype == np.int64
+    assert arrays["id"].tolist() == [1, 2]
+    assert arrays["score"].dtype == np.float64
+    assert arrays["active"].dtype == np.bool_
+    assert arrays["active"].tolist() == [True, False]
+    assert arrays["seen_at"].dtype == np.dtype("datetime64[us]")
+    assert arrays["seen_at"][0] == np.datetime64("2024-01-02T03:04:05")
+
+
+@pytest.mark.asyncio
+async def test_nulls_become_nan_nat_or_none():
+    columns = [("id", "int"), ("active", "bit"), ("name", "nvarchar"), ("born", "date")]
+    rows = [(1, True, "Ada", datetime.date(1815, 12, 10)), (None, None, None, None)]
+    async with FakeServer(results={"NUMPY_NULLS": (columns, rows)}) as server:
+        conn = connect(server)
+        arrays = await conn.query_numpy("SELECT * FROM t -- NUMPY_NULLS")
+
+    assert arrays["id"].dtype == np.float64
+    assert arrays["id"][0] == 1.0 and np.isnan(arrays["id"][1])
+    assert arrays["active"].dtype == object
+    assert arrays["active"].tolist() == [True, None]
+    assert arrays["name"].tolist() == ["Ada", None]
+    assert arrays["born"].dtype == np.dtype("datetime64[D]")
+    assert arrays["born"][0] == np.datetime64("1815-12-10")
+    assert np.isnat(arrays["born"][1])
+
+
+@pytest.mark.asyncio
+async def test_empty_result_keeps_columns():
+    columns = [("id", "int"), ("name", "nvarchar")]
+    async with FakeServer(results={"NUMPY_EMPTY": (columns, [])}) as server:
+        conn = connect(server)
+        arrays = await conn.query_numpy("SELECT * FROM t WHERE 1 = 0 -- NUMPY_EMPTY")
+
+    assert list(arrays) == ["id", "name"]
+    assert len(arrays["id"]) == 0
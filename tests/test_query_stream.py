@@ -0,0 +1,88 @@
+"""Tests for Connection.query_stream() and fetch_chunks() incremental reads."""
+
+import pytest
+from conftest import Config
+
+from fastmssql import Connection, PoolConfig, SqlError
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_query_stream_yields_all_rows(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        # More rows than one internal batch, so several batches are read.
+        sql = (
+            "SELECT TOP (@P1) ROW_NUMBER() OVER (ORDER BY (SELECT NULL)) AS n "
+            "FROM sys.all_objects a CROSS JOIN sys.all_objects b"
+        )
+        seen = [row["n"] async for row in conn.query_stream(sql, [1000])]
+
+    assert seen == list(range(1, 1001))
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_query_stream_reads_first_result_set_only(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        rows = [row.to_dict() async for row in conn.query_stream("SELECT 1 AS a; SELECT 2 AS b")]
+        assert rows == [{"a": 1}]
+
+        empty = [row async for row in conn.query_stream("SELECT 1 AS a WHERE 1 = 0")]
+        assert empty == []
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_query_stream_aclose_returns_connection(test_config: Config):
+    config = PoolConfig(max_size=1, min_idle=0)
+    async with Connection(test_config.connection_string, pool_config=config) as conn:
+        stream = conn.query_stream(
+            "SELECT TOP 5000 a.object_id FROM sys.all_objects a CROSS JOIN sys.all_objects b"
+        )
+        async for _ in stream:
+            break
+        await stream.aclose()
+
+        # The single pooled connection is usable again.
+        result = await conn.query("SELECT 42 AS answer")
+        assert result.fetchone()["answer"] == 42
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_query_stream_error_carries_execution_id(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        stream = conn.query_stream("SELECT * FROM no_such_table_for_query_stream")
+        with pytest.raises(SqlError) as excinfo:
+            async for _ in stream:
+                pass
+        assert excinfo.value.execution_id == stream.execution_id
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_fetch_chunks_sizes(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        sql = (
+            "SELECT TOP (@P1) ROW_NUMBER() OVER (ORDER BY (SELECT NULL)) AS n "
+            "FROM sys.all_objects a CROSS JOIN sys.all_objects b"
+        )
+        chunks = [chunk async for chunk in conn.fetch_chunks(sql, [2500], chunk_size=1000)]
+
+    assert [len(chunk) for chunk in chunks] == [1000, 1000, 500]
+    assert [row["n"] for chunk in chunks for row in chunk] == list(range(1, 2501))
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_fetch_chunks_empty_result(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        chunks = [chunk async for chunk in conn.fetch_chunks("SELECT 1 AS a WHERE 1 = 0")]
+    assert chunks == []
+
+
+@pytest.mark.asyncio
+async def test_fetch_chunks_rejects_zero_chunk_size():
+    conn = Connection("Server=localhost;Database=test;User Id=sa;Password=x")
+    with pytest.raises(ValueError, match="chunk_size"):
+        conn.fetch_chunks("SELECT 1", chunk_size=0)
@@ -0,0 +1,100 @@
+"""Tests for per-statement timeouts on Connection.execute()."""
+
+import time
+
+import pytest
+from conftest import Config
+
+from fastmssql import Connection, OperationalError, PoolConfig, QueryTimeoutError
+
+
+def test_query_timeout_error_is_operational():
+    assert issubclass(QueryTimeoutError, OperationalError)
+
+
+@pytest.mark.asyncio
+@pytest.mark.parametrize("timeout", [0, -1.0, float("nan"), float("inf")])
+async def test_timeout_must_be_positive(timeout):
+    conn = Connection("Server=127.0.0.1,1;Database=master;User Id=sa;Password=x")
+    with pytest.raises(ValueError, match="timeout"):
+        await conn.execute("SELECT 1", timeout=timeout)
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_long_statement_times_out(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        started = time.monotonic()
+        with pytest.raises(QueryTimeoutError, match="timeout"):
+            await conn.execute("WAITFOR DELAY '00:00:30'", timeout=0.5)
+        assert time.monotonic() - started < 10
+
+        # The cancelled connection is discarded; the pool keeps working.
+        assert await conn.execute("SELECT 1", timeout=5.0) >= 0
+
+
+@pytest.mark.asyncio
+async def test_recover_on_timeout_requires_a_valid_timeout():
+    conn = Connection("Server=127.0.0.1,1;Database=master;User Id=sa;Password=x")
+    with pytest.raises(ValueError, match="timeout"):
+        await conn.execute("SELECT 1", timeout=0, recover_on_timeout=True)
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_timed_out_connection_is_recovered_or_replaced(test_config: Config):
+    pool = PoolConfig(max_size=1, min_idle=1)
+    async with Connection(test_config.connection_string, pool_config=pool) as conn:
+        for _ in range(3):
+            with pytest.raises(QueryTimeoutError):
+                await conn.execute("WAITFOR DELAY '00:00:30'", timeout=0.5, recover_on_timeout=True)
+
+        # Whether the connection was kept or replaced, the next statement runs
+        # in step on the single pooled connection.
+        rows = (await conn.query("SELECT 42 AS answer")).rows()
+        assert rows[0]["answer"] == 42
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_recovered_connection_has_no_open_transaction(test_config: Config):
+    pool = PoolConfig(max_size=1, min_idle=1)
+    async with Connection(test_config.connection_string, pool_config=pool) as conn:
+        with pytest.raises(QueryTimeoutError):
+            await conn.execute(
+                "BEGIN TRANSACTION; WAITFOR DELAY '00:00:30'",
+                timeout=0.5,
+                recover_on_timeout=True,
+            )
+
+        rows = (await conn.query("SELECT @@TRANCOUNT AS open_transactions")).rows()
+        assert rows[0]["open_transactions"] == 0
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_statement_blocked_by_lock_is_cancelled(test_config: Config):
+    table = "##fastmssql_query_timeout"
+    async with Connection(test_config.connection_string) as conn:
+        await conn.execute(f"IF OBJECT_ID('tempdb..{table}') IS NOT NULL DROP TABLE {table}")
+        await conn.execute(f"CREATE TABLE {table} (id INT PRIMARY KEY, n INT)")
+        await conn.execute(f"INSERT INTO {table} VALUES (1, 0)")
+        try:
+            async with conn.transaction() as tx:
+                await tx.execute(f"UPDATE {table} SET n = 1 WHERE id = 1")
+                with pytest.raises(QueryTimeoutError):
+                    await conn.execute(f"UPDATE {table} SET n = 2 WHERE id = 1", timeout=1.0)
+                await tx.rollback()
+
+            # The cancelled UPDATE did not run once the lock was released.
+            rows = (await conn.query(f"SELECT n FROM {table} WHERE id = 1")).rows()
+            assert rows[0]["n"] == 0
+        finally:
+            await conn.execute(f"DROP TABLE {table}")
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_fast_statement_within_timeout(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        assert await conn.execute("DECLARE @x INT = 1", timeout=30.0) == 0
@@ -0,0 +1,127 @@
+"""
+Tests for Connection.reload_config().
+
+The fake TDS server accepts logins and answers queries, so these run without SQL Server.
+"""
+
+import asyncio
+
+import pytest
+from fake_identity import IdentityEndpoint
+from fake_tds import FakeServer
+
+from fastmssql import AuthenticationError, AzureCredential, Connection, PoolConfig, SslConfig
+
+RESULTS = {"SELECT 1": ([("one", "int")], [(1,)])}
+
+
+def _pool(**options):
+    options.setdefault("test_on_check_out", False)
+    return PoolConfig(**options)
+
+
+async def _wait_until(condition, timeout=5.0):
+    deadline = asyncio.get_running_loop().time() + timeout
+    while not condition():
+        if asyncio.get_running_loop().time() > deadline:
+            raise TimeoutError("condition not met")
+        await asyncio.sleep(0.02)
+
+
+@pytest.mark.asyncio
+async def test_reload_swaps_in_a_new_pool_and_closes_the_old_one():
+    async with FakeServer(results=RESULTS) as server:
+        conn = Connection(server.connection_string, pool_config=_pool(max_size=1, min_idle=1))
+        await conn.query("SELECT 1")
+        assert len(server.logins) == 1
+
+        await conn.reload_config(new_pool_config=_pool(max_size=3, min_idle=2))
+
+        # The new pool is warmed up before it is swapped in.
+        assert len(server.logins) == 3
+        stats = await conn.pool_stats()
+        assert stats["max_size"] == 3
+        assert stats["connections"] == 2
+        result = await conn.query("SELECT 1")
+        assert result.rows()[0]["one"] == 1
+        await _wait_until(lambda: server.closed == 1)
+
+
+@pytest.mark.asyncio
+async def test_reload_before_connecting_only_changes_settings():
+    async with FakeServer(results=RESULTS) as server:
+        conn = Connection(server.connection_string, pool_config=_pool(max_size=1, min_idle=0))
+        await conn.reload_config(new_pool_config=_pool(max_size=4, min_idle=0))
+        assert server.logins == []
+
+        await conn.query("SELECT 1")
+        assert (await conn.pool_stats())["max_size"] == 4
+
+
+@pytest.mark.asyncio
+async def test_failed_reload_keeps_the_old_pool():
+    async with FakeServer(results=RESULTS) as server:
+        conn = Connection(server.connection_string, pool_config=_pool(max_size=1, min_idle=1))
+        await conn.query("SELECT 1")
+
+        server.login_error = 18456
+        with pytest.raises(AuthenticationError):
+            await conn.reload_config(
+                new_pool_config=_pool(max_size=2, min_idle=1, connection_timeout_secs=1)
+            )
+
+        result = await conn.query("SELECT 1")
+        assert result.rows()[0]["one"] == 1
+        assert (await conn.pool_stats())["max_size"] == 1
+
+
+@pytest.mark.asyncio
+async def test_with_options_copies_see_the_reloaded_settings():
+    async with FakeServer(results=RESULTS) as server:
+        conn = Connection(server.connection_string, pool_config=_pool(max_size=1, min_idle=0))
+        sibling = conn.with_options(rstrip_char=True)
+        await conn.reload_config(new_pool_config=_pool(max_size=4, min_idle=0))
+
+        await sibling.query("SELECT 1")
+        assert (await sibling.pool_stats())["max_size"] == 4
+
+        # A pool created again after disconnect() is built from the new settings too.
+        await sibling.disconnect()
+        await sibling.query("SELECT 1")
+        assert (await sibling.pool_stats())["max_size"] == 4
+        assert (await conn.pool_stats())["max_size"] == 4
+
+
+@pytest.mark.asyncio
+async def test_transactions_and_batches_log_in_with_the_reloaded_credential():
+    with IdentityEndpoint() as old, IdentityEndpoint() as new:
+        async with FakeServer(results=RESULTS) as server:
+            conn = Connection(
+                server.connection_string.replace("User Id=sa;Password=x;", ""),
+                pool_config=_pool(max_size=1, min_idle=0),
+                azure_credential=AzureCredential.service_principal(
+                    "client", "secret", "tenant", authority_host=old.authority
+                ),
+            )
+            sibling = conn.with_options(rstrip_char=True)
+            await conn.reload_config(
+                new_azure_credential=AzureCredential.service_principal(
+                    "client", "secret", "tenant", authority_host=new.authority
+                )
+            )
+
+            transaction = sibling.transaction()
+            await transaction.begin()
+            await transaction.rollback()
+            await transaction.close()
+            await sibling.execute_batch([("SELECT 1", None)])
+
+    assert old.forms == []
+    assert len(new.forms) >= 1
+    assert len(server.logins) == 2
+
+
+def test_ssl_config_cannot_replace_connection_string_settings():
+    conn = Connection("Server=localhost;Database=master;User Id=sa;Password=x")
+    with pytest.raises(ValueError, match="new_ssl_config"):
+        conn.reload_config(new_ssl_config=SslConfig(trust_server_certificate=True))
@@ -0,0 +1,66 @@
+"""Tests for QueryStream.extend() and fastmssql.concat()."""
+
+import pytest
+from conftest import Config
+
+from fastmssql import Connection, concat
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_extend_moves_rows(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        first = await conn.query("SELECT 1 AS id, N'a' AS label")
+        second = await conn.query("SELECT 2 AS id, N'b' AS label UNION ALL SELECT 3, N'c'")
+
+    # Already-converted rows are carried over as well.
+    assert first.rows()[0]["id"] == 1
+    first.extend(second)
+
+    assert len(first) == 3
+    assert len(second) == 0
+    assert [(r["id"], r["label"]) for r in first.rows()] == [(1, "a"), (2, "b"), (3, "c")]
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_extend_rejects_mismatched_columns(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        first = await conn.query("SELECT 1 AS id")
+        renamed = await conn.query("SELECT 2 AS other")
+        retyped = await conn.query("SELECT N'x' AS id")
+
+    with pytest.raises(ValueError):
+        first.extend(renamed)
+    with pytest.raises(ValueError):
+        first.extend(retyped)
+    with pytest.raises(ValueError):
+        first.extend(first)
+    assert len(first) == 1
+    assert len(renamed) == 1
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_concat_partitioned_reads(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        parts = [
+            await conn.query(
+                "SELECT n FROM (VALUES (1), (2), (3), (4)) AS t(n) WHERE n % 2 = @P1 ORDER BY n",
+                [rem],
+            )
+            for rem in (0, 1)
+        ]
+        empty = await conn.query("SELECT 1 AS n WHERE 1 = 0")
+        mismatched = await conn.query("SELECT 'x' AS n")
+
+    with pytest.raises(ValueError):
+        concat([parts[0], mismatched])
+    # A failed concat leaves its inputs untouched.
+    assert len(parts[0]) == 2
+
+    combined = concat([empty, *parts])
+    assert [r["n"] for r in combined] == [2, 4, 1, 3]
+    assert combined.columns() == ["n"]
+    assert all(len(p) == 0 for p in parts)
+    assert len(concat([])) == 0
@@ -0,0 +1,95 @@
+"""
+Tests for Connection(max_result_columns=..., max_row_bytes=...).
+
+The fake TDS server returns the result sets, so these run without SQL Server.
+"""
+
+import pytest
+from fake_tds import FakeServer, connect
+
+from fastmssql import Connection, InterfaceError, ResultLimitError
+
+CONN_STR = "Server=localhost;Database=master;User Id=sa;Password=x"
+
+WIDE = [(f"c{i}", "int") for i in range(8)]
+
+RESULTS = {
+    "FROM wide": (WIDE, [tuple(range(8))]),
+    "FROM notes": (
+        [("id", "int"), ("body", "nvarchar")],
+        [(1, "short"), (2, "x" * 500), (3, "short")],
+    ),
+}
+
+
+@pytest.mark.asyncio
+async def test_too_many_columns_raises():
+    async with FakeServer(results=RESULTS) as server:
+        conn = connect(server, max_result_columns=4)
+        with pytest.raises(
+            ResultLimitError, match=r"8 columns \(c0, c1, c2, c3, c4, \.\.\.\).*max_result_columns=4"
+        ):
+            await conn.query("SELECT * FROM wide")
+
+
+@pytest.mark.asyncio
+async def test_within_limits_passes():
+    async with FakeServer(results=RESULTS) as server:
+        conn = connect(server, max_result_columns=8, max_row_bytes=1000)
+        result = await conn.query("SELECT * FROM notes")
+
+    assert [row["id"] for row in result.rows()] == [1, 2, 3]
+
+
+@pytest.mark.asyncio
+async def test_wide_row_raises_naming_row_and_column():
+    async with FakeServer(results=RESULTS) as server:
+        conn = connect(server, max_row_bytes=100)
+        with pytest.raises(
+            ResultLimitError, match=r"Row 1 is 508 bytes.*widest column 'body' has 500 bytes"
+        ):
+            await conn.simple_query("SELECT * FROM notes")
+
+
+@pytest.mark.asyncio
+async def test_connection_is_discarded_after_limit_error():
+    async with FakeServer(results=RESULTS) as server:
+        conn = connect(server, max_row_bytes=100)
+        with pytest.raises(ResultLimitError):
+            await conn.query("SELECT * FROM notes")
+        result = await conn.query("SELECT * FROM wide")
+
+        assert result.rows()[0]["c7"] == 7
+        assert len(server.logins) == 2
+
+
+@pytest.mark.asyncio
+async def test_query_stream_checks_rows_as_they_arrive():
+    async with FakeServer(results=RESULTS) as server:
+        conn = connect(server, max_row_bytes=100)
+        with pytest.raises(ResultLimitError, match="Row 1"):
+            async for _ in conn.query_stream("SELECT * FROM notes"):
+                pass
+
+
+@pytest.mark.asyncio
+async def test_with_options_overrides_limits():
+    async with FakeServer(results=RESULTS) as server:
+        strict = connect(server, max_result_columns=4)
+        relaxed = strict.with_options(max_result_columns=10)
+        result = await relaxed.query("SELECT * FROM wide")
+        assert len(result.columns()) == 8
+
+        with pytest.raises(ResultLimitError):
+            await strict.with_options(max_row_bytes=10_000).query("SELECT * FROM wide")
+
+
+def test_limits_must_be_positive():
+    with pytest.raises(ValueError, match="max_result_columns"):
+        Connection(CONN_STR, max_result_columns=0)
+    with pytest.raises(ValueError, match="max_row_bytes"):
+        Connection(CONN_STR, max_row_bytes=0)
+
+
+def test_result_limit_error_is_an_interface_error():
+    assert issubclass(ResultLimitError, InterfaceError)
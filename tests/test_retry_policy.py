@@ -0,0 +1,94 @@
+"""Tests for RetryPolicy and Connection.execute() retries."""
+
+import uuid
+
+import pytest
+from conftest import Config
+
+from fastmssql import Connection, RetryPolicy, SqlError
+
+
+def test_defaults():
+    policy = RetryPolicy()
+    assert policy.max_attempts == 3
+    assert policy.backoff_secs == pytest.approx(0.1)
+    assert policy.max_backoff_secs == pytest.approx(5.0)
+    assert {1205, 40501, 40613}.issubset(policy.retry_on)
+
+
+def test_custom_values():
+    policy = RetryPolicy(max_attempts=5, backoff_secs=0.5, max_backoff_secs=2, retry_on=[50000])
+    assert policy.max_attempts == 5
+    assert policy.backoff_secs == pytest.approx(0.5)
+    assert policy.max_backoff_secs == pytest.approx(2.0)
+    assert policy.retry_on == [50000]
+    assert "max_attempts=5" in repr(policy)
+
+
+@pytest.mark.parametrize(
+    "kwargs, match",
+    [
+        ({"max_attempts": 0}, "max_attempts"),
+        ({"backoff_secs": -1}, "backoff_secs"),
+        ({"backoff_secs": 2, "max_backoff_secs": 1}, "max_backoff_secs"),
+    ],
+)
+def test_validation(kwargs, match):
+    with pytest.raises(ValueError, match=match):
+        RetryPolicy(**kwargs)
+
+
+def _flaky_statement(table, failures):
+    """Fails with error 50000 the first `failures` times it runs, then succeeds."""
+    return (
+        f"INSERT INTO {table} DEFAULT VALUES; "
+        f"IF (SELECT COUNT(*) FROM {table}) <= {failures} THROW 50000, 'transient', 1;"
+    )
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_execute_retries_transient_errors(test_config: Config):
+    table = f"##fastmssql_retry_{uuid.uuid4().hex}"
+    policy = RetryPolicy(max_attempts=3, backoff_secs=0.01, retry_on=[50000])
+    async with Connection(test_config.connection_string, retry_policy=policy) as conn:
+        await conn.execute(f"CREATE TABLE {table} (id INT IDENTITY)")
+        try:
+            await conn.execute(_flaky_statement(table, 2), idempotent=True)
+            rows = (await conn.query(f"SELECT COUNT(*) AS n FROM {table}")).rows()
+            assert rows[0]["n"] == 3
+        finally:
+            await conn.execute(f"DROP TABLE {table}")
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_execute_gives_up_after_max_attempts(test_config: Config):
+    table = f"##fastmssql_retry_{uuid.uuid4().hex}"
+    policy = RetryPolicy(max_attempts=2, backoff_secs=0.01, retry_on=[50000])
+    async with Connection(test_config.connection_string, retry_policy=policy) as conn:
+        await conn.execute(f"CREATE TABLE {table} (id INT IDENTITY)")
+        try:
+            with pytest.raises(SqlError) as excinfo:
+                await conn.execute(_flaky_statement(table, 5), idempotent=True)
+            assert excinfo.value.code == 50000
+            rows = (await conn.query(f"SELECT COUNT(*) AS n FROM {table}")).rows()
+            assert rows[0]["n"] == 2
+        finally:
+            await conn.execute(f"DROP TABLE {table}")
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_execute_is_not_retried_by_default(test_config: Config):
+    table = f"##fastmssql_retry_{uuid.uuid4().hex}"
+    policy = RetryPolicy(max_attempts=3, backoff_secs=0.01, retry_on=[50000])
+    async with Connection(test_config.connection_string, retry_policy=policy) as conn:
+        await conn.execute(f"CREATE TABLE {table} (id INT IDENTITY)")
+        try:
+            with pytest.raises(SqlError):
+                await conn.execute(_flaky_statement(table, 2))
+            rows = (await conn.query(f"SELECT COUNT(*) AS n FROM {table}")).rows()
+            assert rows[0]["n"] == 1
+        finally:
+            await conn.execute(f"DROP TABLE {table}")
@@ -0,0 +1,49 @@
+"""
+Tests for the shared column names and cached row objects of a result.
+
+The fake TDS server returns the result sets, so these run without SQL Server.
+"""
+
+import pytest
+from fake_tds import FakeServer, connect
+
+RESULTS = {
+    "FROM orders": (
+        [("id", "int"), ("name", "nvarchar")],
+        [(1, "alpha"), (2, "beta"), (3, "gamma")],
+    ),
+}
+
+
+@pytest.mark.asyncio
+async def test_column_names_are_shared_across_rows():
+    async with FakeServer(results=RESULTS) as server:
+        result = await connect(server).query("SELECT * FROM orders")
+
+    rows = result.fetchall()
+    first, second = rows[0].to_dict(), rows[1].to_dict()
+    assert list(first) == ["id", "name"]
+    for a, b in zip(first, second):
+        assert a is b
+    for a, b in zip(rows[0].keys(), result.columns()):
+        assert a is b
+
+
+@pytest.mark.asyncio
+async def test_keys_matches_columns():
+    async with FakeServer(results=RESULTS) as server:
+        result = await connect(server).query("SELECT * FROM orders")
+
+    row = result.fetchone()
+    assert row.keys() == row.columns() == ["id", "name"]
+    assert dict(zip(row.keys(), (row[k] for k in row.keys()))) == row.to_dict()
+
+
+@pytest.mark.asyncio
+async def test_repeated_access_returns_the_same_row():
+    async with FakeServer(results=RESULTS) as server:
+        result = await connect(server).query("SELECT * FROM orders")
+
+    assert result[0] is result[0]
+    assert result[-1] is result[2]
+    assert result.fetchone() is result[0]
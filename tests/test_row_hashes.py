@@ -0,0 +1,114 @@
+"""
+Tests for QueryStream.row_hashes().
+
+The fake TDS server returns the result sets, so these run without SQL Server.
+"""
+
+from datetime import date, datetime
+
+import pytest
+from conftest import Config
+from fake_tds import FakeServer, connect
+
+from fastmssql import Connection
+
+RESULTS = {
+    "FROM orders": (
+        [("id", "int"), ("name", "nvarchar"), ("placed", "datetime2"), ("total", "float")],
+        [
+            (1, "alpha", datetime(2024, 1, 1, 12, 0), 9.5),
+            (2, "beta", datetime(2024, 1, 2, 8, 30), None),
+            (1, "alpha", datetime(2024, 1, 1, 12, 0), 9.5),
+        ],
+    ),
+    "FROM swapped": (
+        [("name", "nvarchar"), ("id", "int")],
+        [("alpha", 1)],
+    ),
+    "FROM split": (
+        [("a", "nvarchar"), ("b", "nvarchar")],
+        [("ab", "c"), ("a", "bc")],
+    ),
+    "FROM typed": (
+        [("flag", "bit"), ("day", "date"), ("data", "varbinary")],
+        [(True, date(2024, 1, 1), b"\x00\x01"), (None, None, None)],
+    ),
+}
+
+
+@pytest.mark.asyncio
+async def test_row_hashes_are_stable_per_value():
+    async with FakeServer(results=RESULTS) as server:
+        conn = connect(server)
+        first = (await conn.query("SELECT * FROM orders")).row_hashes()
+        again = (await conn.query("SELECT * FROM orders")).row_hashes()
+
+    assert len(first) == 3
+    assert all(len(h) == 16 for h in first)
+    assert first == again
+    assert first[0] == first[2]
+    assert first[0] != first[1]
+
+
+@pytest.mark.asyncio
+async def test_row_hashes_column_selection():
+    async with FakeServer(results=RESULTS) as server:
+        conn = connect(server)
+        orders = await conn.query("SELECT * FROM orders")
+        swapped = await conn.query("SELECT * FROM swapped")
+
+        # Selecting the same values in the same order gives the same hash.
+        assert orders.row_hashes(["id", "name"])[0] == swapped.row_hashes(["id", "name"])[0]
+        assert orders.row_hashes(["id", "name"])[0] != swapped.row_hashes()[0]
+        assert orders.row_hashes(["id"])[0] == orders.row_hashes(["id"])[2]
+
+        with pytest.raises(ValueError, match="missing"):
+            orders.row_hashes(["missing"])
+
+
+@pytest.mark.asyncio
+async def test_row_hashes_keep_value_boundaries():
+    async with FakeServer(results=RESULTS) as server:
+        hashes = (await connect(server).query("SELECT * FROM split")).row_hashes()
+
+    assert hashes[0] != hashes[1]
+
+
+@pytest.mark.asyncio
+async def test_row_hashes_handle_nulls_and_other_types():
+    async with FakeServer(results=RESULTS) as server:
+        hashes = (await connect(server).query("SELECT * FROM typed")).row_hashes()
+
+    assert len(set(hashes)) == 2
+
+
+@pytest.mark.asyncio
+async def test_row_hashes_after_reading_rows_raises():
+    async with FakeServer(results=RESULTS) as server:
+        result = await connect(server).query("SELECT * FROM orders")
+
+    result.fetchone()
+    with pytest.raises(ValueError, match="row_hashes"):
+        result.row_hashes()
+
+
+@pytest.mark.asyncio
+async def test_row_hashes_of_empty_result():
+    async with FakeServer(results=RESULTS) as server:
+        result = await connect(server).query("UPDATE orders SET total = 0")
+
+    assert result.row_hashes() == []
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_row_hashes_ignore_declared_types(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        a = await conn.query("SELECT CAST(5 AS INT) AS n, CAST('abc' AS VARCHAR(10)) AS s")
+        b = await conn.query(
+            "SELECT CAST(5.00 AS DECIMAL(10, 2)) AS n, CAST(N'abc' AS NVARCHAR(20)) AS s"
+        )
+        c = await conn.query("SELECT CAST(5.01 AS DECIMAL(10, 2)) AS n, N'abc' AS s")
+
+    assert a.row_hashes() == b.row_hashes()
+    assert a.row_hashes() != c.row_hashes()
@@ -0,0 +1,130 @@
+"""Tests for Connection.transaction() and Connection.run_in_transaction()."""
+
+import pytest
+from conftest import Config
+
+from fastmssql import Connection, SqlError, Transaction
+
+
+def _sql_error(code: int) -> SqlError:
+    err = SqlError(f"simulated error {code}")
+    err.code = code
+    return err
+
+
+async def _setup_table(conn: Connection, name: str):
+    await conn.execute(f"IF OBJECT_ID('{name}', 'U') IS NOT NULL DROP TABLE {name}")
+    await conn.execute(f"CREATE TABLE {name} (id INT PRIMARY KEY, val INT)")
+
+
+def test_transaction_returns_wrapper(test_config: Config):
+    """Connection.transaction() returns an unconnected Python Transaction wrapper."""
+    conn = Connection(test_config.connection_string)
+    tx = conn.transaction()
+    assert isinstance(tx, Transaction)
+    assert tx.is_connected() is False
+
+
+@pytest.mark.asyncio
+async def test_run_in_transaction_rejects_negative_retries(test_config: Config):
+    conn = Connection(test_config.connection_string)
+    with pytest.raises(ValueError):
+        await conn.run_in_transaction(lambda tx: None, retries=-1)
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_run_in_transaction_commits_and_returns_value(test_config: Config):
+    table = "test_run_in_tx_commit"
+    async with Connection(test_config.connection_string) as conn:
+        await _setup_table(conn, table)
+        try:
+            async def work(tx):
+                result = await tx.query("SELECT @@TRANCOUNT AS cnt")
+                assert result.rows()[0]["cnt"] == 1
+                await tx.execute(f"INSERT INTO {table} VALUES (@P1, @P2)", [1, 10])
+                return "done"
+
+            assert await conn.run_in_transaction(work) == "done"
+
+            result = await conn.query(f"SELECT val FROM {table} WHERE id = 1")
+            assert result.rows()[0]["val"] == 10
+        finally:
+            await conn.execute(f"DROP TABLE {table}")
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_run_in_transaction_rolls_back_on_error(test_config: Config):
+    table = "test_run_in_tx_rollback"
+    async with Connection(test_config.connection_string) as conn:
+        await _setup_table(conn, table)
+        try:
+            async def work(tx):
+                await tx.execute(f"INSERT INTO {table} VALUES (@P1, @P2)", [1, 10])
+                raise ValueError("boom")
+
+            with pytest.raises(ValueError, match="boom"):
+                await conn.run_in_transaction(work)
+
+            result = await conn.query(f"SELECT COUNT(*) AS cnt FROM {table}")
+            assert result.rows()[0]["cnt"] == 0
+        finally:
+            await conn.execute(f"DROP TABLE {table}")
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_run_in_transaction_retries_deadlock_victim(test_config: Config):
+    table = "test_run_in_tx_retry"
+    async with Connection(test_config.connection_string) as conn:
+        await _setup_table(conn, table)
+        try:
+            calls = []
+
+            async def work(tx):
+                calls.append(1)
+                await tx.execute(f"INSERT INTO {table} VALUES (@P1, @P2)", [len(calls), 0])
+                if len(calls) < 3:
+                    raise _sql_error(1205)
+                return len(calls)
+
+            assert await conn.run_in_transaction(work, retries=3) == 3
+
+            # Only the final attempt's insert survives; earlier attempts rolled back.
+            result = await conn.query(f"SELECT id FROM {table}")
+            assert [r["id"] for r in result.rows()] == [3]
+        finally:
+            await conn.execute(f"DROP TABLE {table}")
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_run_in_transaction_gives_up_after_retries(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        calls = []
+
+        async def work(tx):
+            calls.append(1)
+            raise _sql_error(3960)
+
+        with pytest.raises(SqlError) as exc_info:
+            await conn.run_in_transaction(work, retries=2)
+        assert exc_info.value.code == 3960
+        assert len(calls) == 3
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_run_in_transaction_does_not_retry_other_errors(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        calls = []
+
+        async def work(tx):
+            calls.append(1)
+            await tx.execute("SELECT * FROM this_table_does_not_exist_xyz")
+
+        with pytest.raises(SqlError) as exc_info:
+            await conn.run_in_transaction(work, retries=3)
+        assert exc_info.value.code == 208
+        assert len(calls) == 1
@@ -0,0 +1,69 @@
+"""Tests for savepoints on Transaction."""
+
+import pytest
+from conftest import Config
+
+from fastmssql import Transaction
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_savepoint_requires_transaction(test_config: Config):
+    tx = Transaction(test_config.connection_string)
+    try:
+        with pytest.raises(RuntimeError):
+            await tx.savepoint("sp")
+    finally:
+        await tx.close()
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_rollback_to_savepoint_keeps_earlier_work(test_config: Config):
+    tx = Transaction(test_config.connection_string)
+    try:
+        await tx.execute("CREATE TABLE #sp_test (id INT)")
+        await tx.begin()
+        await tx.execute("INSERT INTO #sp_test VALUES (1)")
+        await tx.savepoint("first")
+        await tx.execute("INSERT INTO #sp_test VALUES (2)")
+        await tx.savepoint("second")
+        await tx.execute("INSERT INTO #sp_test VALUES (3)")
+
+        await tx.rollback_to("first")
+        result = await tx.query("SELECT id, @@TRANCOUNT AS c FROM #sp_test ORDER BY id")
+        rows = result.fetchall()
+        assert [r["id"] for r in rows] == [1]
+        assert rows[0]["c"] == 1
+
+        # Savepoints after the target are gone; the target itself survives.
+        with pytest.raises(ValueError):
+            await tx.rollback_to("second")
+        await tx.execute("INSERT INTO #sp_test VALUES (4)")
+        await tx.rollback_to("first")
+
+        await tx.commit()
+        assert (await tx.query("SELECT COUNT(*) AS n FROM #sp_test")).fetchone()["n"] == 1
+    finally:
+        await tx.close()
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_release_and_name_validation(test_config: Config):
+    tx = Transaction(test_config.connection_string)
+    try:
+        await tx.begin()
+        await tx.savepoint("sp")
+        await tx.release("sp")
+        with pytest.raises(ValueError):
+            await tx.rollback_to("sp")
+        with pytest.raises(ValueError):
+            await tx.release("missing")
+        with pytest.raises(ValueError):
+            await tx.savepoint("x" * 33)
+        with pytest.raises(ValueError):
+            await tx.savepoint("")
+        await tx.rollback()
+    finally:
+        await tx.close()
@@ -0,0 +1,50 @@
+"""Tests for Connection.server_info()."""
+
+from datetime import datetime
+
+import pytest
+from conftest import Config
+
+from fastmssql import Connection
+
+KEYS = {
+    "tds_version",
+    "encrypted",
+    "auth_scheme",
+    "net_transport",
+    "server_version",
+    "tls_version",
+    "cipher_suite",
+    "certificate_subject",
+    "certificate_issuer",
+    "certificate_not_before",
+    "certificate_not_after",
+}
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_server_info_reports_session_security(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        info = await conn.server_info()
+        version = (await conn.query("SELECT CAST(SERVERPROPERTY('ProductVersion') AS NVARCHAR(40)) AS v")).rows()[0]["v"]
+    assert set(info) == KEYS
+    assert info["tds_version"].startswith("7.")
+    assert isinstance(info["encrypted"], bool)
+    assert info["auth_scheme"] == "SQL"
+    assert version.startswith(info["server_version"].split(".")[0] + ".")
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_server_info_tls_details_match_encryption(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        info = await conn.server_info()
+    if not info["encrypted"]:
+        pytest.skip("test server does not encrypt the session")
+    assert info["tls_version"] in ("TLSv1.2", "TLSv1.3")
+    assert info["cipher_suite"].startswith("TLS_")
+    if info["tls_version"] == "TLSv1.2":
+        assert info["certificate_subject"]
+        assert isinstance(info["certificate_not_after"], datetime)
+        assert info["certificate_not_before"] < info["certificate_not_after"]
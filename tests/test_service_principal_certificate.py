@@ -0,0 +1,125 @@
+"""
+Tests for AzureCredential.service_principal_certificate(): token requests carry a
+client assertion signed with the certificate's key instead of a client secret.
+"""
+
+import base64
+import datetime
+import hashlib
+import json
+
+import pytest
+from fake_identity import IdentityEndpoint
+from fake_tds import FakeServer, connect
+
+from fastmssql import AzureCredential, AzureCredentialType
+
+pytest.importorskip("cryptography")
+from cryptography import x509  # noqa: E402
+from cryptography.hazmat.primitives import hashes, serialization  # noqa: E402
+from cryptography.hazmat.primitives.asymmetric import ec, padding, rsa  # noqa: E402
+from cryptography.x509.oid import NameOID  # noqa: E402
+
+
+def _certificate(key):
+    name = x509.Name([x509.NameAttribute(NameOID.COMMON_NAME, "fastmssql-test")])
+    now = datetime.datetime.now(datetime.timezone.utc)
+    return (
+        x509.CertificateBuilder()
+        .subject_name(name)
+        .issuer_name(name)
+        .public_key(key.public_key())
+        .serial_number(x509.random_serial_number())
+        .not_valid_before(now)
+        .not_valid_after(now + datetime.timedelta(days=1))
+        .sign(key, hashes.SHA256())
+    )
+
+
+@pytest.fixture
+def rsa_pem(tmp_path):
+    key = rsa.generate_private_key(public_exponent=65537, key_size=2048)
+    cert = _certificate(key)
+    path = tmp_path / "sp.pem"
+    path.write_bytes(
+        cert.public_bytes(serialization.Encoding.PEM)
+        + key.private_bytes(
+            serialization.Encoding.PEM,
+            serialization.PrivateFormat.PKCS8,
+            serialization.NoEncryption(),
+        )
+    )
+    return path, key, cert
+
+
+def _b64decode(segment):
+    return base64.urlsafe_b64decode(segment + "=" * (-len(segment) % 4))
+
+
+def test_credential_reports_certificate_not_key(rsa_pem):
+    path, _, cert = rsa_pem
+    cred = AzureCredential.service_principal_certificate("client", "tenant", str(path))
+
+    assert cred.credential_type == AzureCredentialType.SERVICE_PRINCIPAL
+    assert cred.config["cert_path"] == str(path)
+    assert cred.config["cert_thumbprint"] == cert.fingerprint(hashes.SHA1()).hex().upper()
+    assert "PRIVATE KEY" not in repr(cred.config)
+
+
+@pytest.mark.asyncio
+async def test_token_request_carries_signed_assertion(rsa_pem):
+    path, key, cert = rsa_pem
+    with IdentityEndpoint() as identity:
+        cred = AzureCredential.service_principal_certificate(
+            "my-client", "my-tenant", path, authority_host=identity.authority
+        )
+        async with FakeServer() as server:
+            conn = connect(server, azure_credential=cred)
+            await conn.execute("SET NOCOUNT ON")
+
+    form = identity.forms[0]
+    assert "client_secret" not in form
+    assert form["client_id"] == ["my-client"]
+    assert form["client_assertion_type"] == [
+        "urn:ietf:params:oauth:client-assertion-type:jwt-bearer"
+    ]
+    header, claims, signature = form["client_assertion"][0].split(".")
+    header, claims = json.loads(_b64decode(header)), json.loads(_b64decode(claims))
+    der = cert.public_bytes(serialization.Encoding.DER)
+    assert header["alg"] == "RS256"
+    assert _b64decode(header["x5t#S256"]) == hashlib.sha256(der).digest()
+    assert claims["aud"] == f"{identity.authority}/my-tenant/oauth2/v2.0/token"
+    assert claims["iss"] == claims["sub"] == "my-client"
+    assert claims["exp"] > claims["nbf"]
+    signed = form["client_assertion"][0].rsplit(".", 1)[0].encode()
+    key.public_key().verify(_b64decode(signature), signed, padding.PKCS1v15(), hashes.SHA256())
+
+
+def test_missing_or_invalid_certificate_is_rejected(tmp_path):
+    with pytest.raises(ValueError, match="Cannot read certificate file"):
+        AzureCredential.service_principal_certificate("c", "t", tmp_path / "missing.pem")
+
+    key_only = tmp_path / "key.pem"
+    key = rsa.generate_private_key(public_exponent=65537, key_size=2048)
+    key_only.write_bytes(
+        key.private_bytes(
+            serialization.Encoding.PEM,
+            serialization.PrivateFormat.TraditionalOpenSSL,
+            serialization.NoEncryption(),
+        )
+    )
+    with pytest.raises(ValueError, match="No CERTIFICATE"):
+        AzureCredential.service_principal_certificate("c", "t", key_only)
+
+    ec_key = ec.generate_private_key(ec.SECP256R1())
+    ec_pem = tmp_path / "ec.pem"
+    ec_pem.write_bytes(
+        _certificate(ec_key).public_bytes(serialization.Encoding.PEM)
+        + ec_key.private_bytes(
+            serialization.Encoding.PEM,
+            serialization.PrivateFormat.PKCS8,
+            serialization.NoEncryption(),
+        )
+    )
+    with pytest.raises(ValueError, match="RSA"):
+        AzureCredential.service_principal_certificate("c", "t", ec_pem)
@@ -0,0 +1,38 @@
+"""Tests for Connection.with_options() pool sharing."""
+
+import pytest
+from conftest import Config
+
+from fastmssql import Connection, PoolConfig
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_with_options_shares_pool(test_config: Config):
+    base = Connection(test_config.connection_string, pool_config=PoolConfig(max_size=3, min_idle=1))
+    derived = base.with_options(rstrip_char=True)
+    assert isinstance(derived, Connection)
+
+    await base.query("SELECT 1 AS ok")
+    base_stats = await base.pool_stats()
+    derived_stats = await derived.pool_stats()
+    assert derived_stats["connected"] is True
+    assert derived_stats["max_size"] == base_stats["max_size"] == 3
+
+    await derived.query("SELECT 1 AS ok")
+    assert (await base.pool_stats())["connections"] <= 3
+    await base.disconnect()
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_with_options_overrides_only_given_defaults(test_config: Config):
+    base = Connection(test_config.connection_string)
+    trimmed = base.with_options(rstrip_char=True)
+    inherited = trimmed.with_options()
+    sql = "SELECT CAST('ab' AS CHAR(5)) AS c"
+
+    assert (await base.query(sql)).rows()[0]["c"] == "ab   "
+    assert (await trimmed.query(sql)).rows()[0]["c"] == "ab"
+    assert (await inherited.query(sql)).rows()[0]["c"] == "ab"
+    await base.disconnect()
@@ -0,0 +1,95 @@
+"""Tests for SQL / Identifier / Param statement composition."""
+
+import pytest
+from fake_tds import FakeServer, connect
+
+from fastmssql import SQL, Composed, Identifier, Param, Parameter
+
+
+def test_format_quotes_identifiers_and_binds_values():
+    stmt = SQL("SELECT * FROM {table} WHERE id = {id}").format(table=Identifier("dbo.Users"), id=Param(5))
+    assert isinstance(stmt, Composed)
+    assert stmt.sql == "SELECT * FROM [dbo].[Users] WHERE id = @P1"
+    assert stmt.params == [5]
+    sql, params = stmt
+    assert (sql, params) == (stmt.sql, stmt.params)
+
+
+def test_identifier_escapes_brackets_and_keeps_literal_parts():
+    assert Identifier("we]ird").string == "[we]]ird]"
+    assert Identifier("dbo", "odd.name").string == "[dbo].[odd.name]"
+    with pytest.raises(ValueError):
+        Identifier()
+    with pytest.raises(ValueError):
+        Identifier("")
+
+
+def test_positional_placeholders_and_reuse():
+    stmt = SQL("SELECT {} FROM t WHERE a = {} OR b = {}").format(Identifier("x"), Param(1), Param(2))
+    assert stmt.sql == "SELECT [x] FROM t WHERE a = @P1 OR b = @P2"
+    assert stmt.params == [1, 2]
+
+    stmt = SQL("SELECT {0} FROM t WHERE a = {1} OR b = {1}").format(Identifier("x"), Param("v"))
+    assert stmt.sql == "SELECT [x] FROM t WHERE a = @P1 OR b = @P1"
+    assert stmt.params == ["v"]
+
+
+def test_plain_values_are_refused():
+    with pytest.raises(TypeError, match="Param"):
+        SQL("SELECT * FROM t WHERE id = {id}").format(id=5)
+    with pytest.raises(TypeError):
+        SQL("SELECT * FROM {table}").format(table="users; DROP TABLE users")
+
+
+def test_template_errors():
+    with pytest.raises(ValueError, match="No value"):
+        SQL("SELECT {missing}").format()
+    with pytest.raises(ValueError, match="positional"):
+        SQL("SELECT {}, {}").format(Param(1))
+    with pytest.raises(ValueError, match="mix"):
+        SQL("SELECT {}, {0}").format(Param(1))
+    with pytest.raises(ValueError):
+        SQL("SELECT {id!r}").format(id=Param(1))
+    with pytest.raises(ValueError):
+        SQL("SELECT {").format()
+    assert SQL("SELECT '{{}}'").format().sql == "SELECT '{}'"
+
+
+def test_join_and_nesting_renumber_parameters():
+    columns = SQL(", ").join([Identifier("id"), Identifier("name")])
+    assert columns.sql == "[id], [name]"
+
+    where = SQL("a = {} AND b = {}").format(Param(1), Param(2))
+    stmt = SQL("SELECT {cols} FROM t WHERE c = {c} AND {where}").format(cols=columns, c=Param(0), where=where)
+    assert stmt.sql == "SELECT [id], [name] FROM t WHERE c = @P1 AND a = @P2 AND b = @P3"
+    assert stmt.params == [0, 1, 2]
+
+    combined = where + SQL(" OR ") + where
+    assert combined.sql == "a = @P1 AND b = @P2 OR a = @P3 AND b = @P4"
+    assert combined.params == [1, 2, 1, 2]
+
+
+def test_typed_params_become_parameters():
+    stmt = SQL("SELECT {v}").format(v=Param(None, "VARBINARY(MAX)"))
+    (value,) = stmt.params
+    assert isinstance(value, Parameter)
+    assert value.sql_type == "VARBINARY(MAX)"
+
+    existing = Parameter(7, "BIGINT")
+    assert SQL("SELECT {v}").format(v=existing).params == [existing]
+
+
+@pytest.mark.asyncio
+async def test_composed_statement_runs():
+    columns = [("id", "int"), ("name", "nvarchar")]
+    async with FakeServer(results={"COMPOSED": (columns, [(5, "Ada")])}) as server:
+        conn = connect(server)
+        stmt = SQL("SELECT {cols} FROM {table} WHERE id = {id} -- COMPOSED").format(
+            cols=SQL(", ").join([Identifier("id"), Identifier("name")]),
+            table=Identifier("dbo.Users"),
+            id=Param(5),
+        )
+        result = await conn.query(*stmt)
+
+    rows = result.rows()
+    assert rows[0]["name"] == "Ada"
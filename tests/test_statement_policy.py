@@ -0,0 +1,136 @@
+"""Tests for Connection(statement_policy=...), run against the fake TDS server."""
+
+import re
+
+import pytest
+from fake_tds import RPC, SQL_BATCH, FakeServer, connect
+
+from fastmssql import Connection, InterfaceError, PolicyViolation, StatementPolicy
+
+
+def test_policy_violation_is_an_interface_error():
+    assert issubclass(PolicyViolation, InterfaceError)
+
+
+def test_policy_rejects_bad_arguments():
+    with pytest.raises(ValueError):
+        StatementPolicy(deny=r"\bDROP\b")
+    with pytest.raises(ValueError):
+        StatementPolicy(check="not callable")
+    with pytest.raises(ValueError):
+        Connection("Server=localhost;User Id=sa;Password=x", statement_policy=42)
+
+
+def test_policy_exposes_its_rules():
+    policy = StatementPolicy(deny=[r"\bDROP\b", re.compile("truncate")], require_where=True)
+    assert policy.deny == [r"\bDROP\b", "truncate"]
+    assert policy.allow == []
+    assert policy.require_where is True
+
+
+@pytest.mark.asyncio
+async def test_deny_rule_blocks_statement():
+    policy = StatementPolicy(deny=[r"\bDROP\b", r"\bTRUNCATE\b"])
+    async with FakeServer() as server:
+        conn = connect(server, statement_policy=policy)
+        with pytest.raises(PolicyViolation, match="DROP"):
+            await conn.execute("drop table orders")
+        with pytest.raises(PolicyViolation):
+            await conn.query("TRUNCATE TABLE orders")
+        assert await conn.execute("UPDATE orders SET status = 'done' WHERE id = @P1", [1]) == 0
+
+
+@pytest.mark.asyncio
+async def test_require_where_blocks_unguarded_modifications():
+    policy = StatementPolicy(require_where=True)
+    async with FakeServer() as server:
+        conn = connect(server, statement_policy=policy)
+        with pytest.raises(PolicyViolation, match="DELETE without a WHERE"):
+            await conn.execute("DELETE FROM sessions")
+        with pytest.raises(PolicyViolation, match="UPDATE without a WHERE"):
+            await conn.execute("UPDATE t SET a = (SELECT MAX(b) FROM u WHERE u.id = 1)")
+        await conn.execute("DELETE FROM sessions WHERE id = @P1", [1])
+        await conn.execute("DELETE sessions -- WHERE is required\nWHERE expires_at < SYSUTCDATETIME()")
+        await conn.execute("SELECT 'DELETE FROM x' AS text")
+        await conn.execute(
+            "ALTER TABLE orders ADD CONSTRAINT fk FOREIGN KEY (c) REFERENCES c (id) ON DELETE CASCADE"
+        )
+
+
+@pytest.mark.asyncio
+async def test_allow_list_rejects_everything_else():
+    policy = StatementPolicy(allow=[r"^\s*SELECT\b"])
+    async with FakeServer() as server:
+        conn = connect(server, statement_policy=policy)
+        await conn.query("SELECT 1")
+        with pytest.raises(PolicyViolation, match="allow"):
+            await conn.execute("INSERT INTO t VALUES (1)")
+
+
+@pytest.mark.asyncio
+async def test_check_callable_supplies_the_reason():
+    seen = []
+
+    def check(sql):
+        seen.append(sql)
+        return "writes are disabled during maintenance" if sql.lstrip().upper().startswith("INSERT") else None
+
+    async with FakeServer() as server:
+        conn = connect(server, statement_policy=check)
+        await conn.query("SELECT 1")
+        with pytest.raises(PolicyViolation, match="maintenance"):
+            await conn.execute("INSERT INTO t VALUES (1)")
+    assert seen == ["SELECT 1", "INSERT INTO t VALUES (1)"]
+
+
+@pytest.mark.asyncio
+async def test_batches_are_checked_before_anything_runs():
+    policy = StatementPolicy(deny=[r"\bDROP\b"])
+    async with FakeServer() as server:
+        conn = connect(server, statement_policy=policy)
+        with pytest.raises(PolicyViolation):
+            await conn.execute_batch([("INSERT INTO t VALUES (@P1)", [1]), ("DROP TABLE t", None)])
+    assert server.count(SQL_BATCH) == 0 and server.count(RPC) == 0
+
+
+@pytest.mark.asyncio
+async def test_with_options_inherits_or_replaces_the_policy():
+    policy = StatementPolicy(deny=[r"\bDROP\b"])
+    async with FakeServer() as server:
+        conn = connect(server, statement_policy=policy)
+        with pytest.raises(PolicyViolation):
+            await conn.with_options(rstrip_char=True).execute("DROP TABLE t")
+        strict = conn.with_options(statement_policy=StatementPolicy(require_where=True))
+        with pytest.raises(PolicyViolation):
+            await strict.execute("DELETE FROM t")
+        await strict.execute("DROP TABLE t")
+
+
+@pytest.mark.asyncio
+async def test_pinned_connection_is_checked():
+    policy = StatementPolicy(require_where=True)
+    async with FakeServer() as server:
+        conn = connect(server, statement_policy=policy)
+        async with conn.acquire() as pinned:
+            with pytest.raises(PolicyViolation):
+                await pinned.execute("UPDATE t SET a = 1")
+
+
+@pytest.mark.asyncio
+async def test_transaction_is_checked():
+    policy = StatementPolicy(deny=[r"\bDROP\b"], require_where=True)
+    async with FakeServer() as server:
+        conn = connect(server, statement_policy=policy)
+        transaction = conn.transaction()
+        await transaction.begin()
+        with pytest.raises(PolicyViolation):
+            await transaction.execute("DELETE FROM t")
+        with pytest.raises(PolicyViolation):
+            await transaction.query("DROP TABLE t")
+        with pytest.raises(PolicyViolation):
+            await transaction.execute_batch([("INSERT INTO t VALUES (1)", None), ("DROP TABLE t", None)])
+        await transaction.execute("DELETE FROM t WHERE id = 1")
+        await transaction.rollback()
+        await transaction.close()
+    # Only the guarded DELETE reached the server; BEGIN and ROLLBACK are plain batches.
+    assert server.count(RPC) == 1
@@ -0,0 +1,165 @@
+"""
+Tests for leaving query_stream(), fetch_chunks() and stream_results() early.
+
+The fake TDS server returns the rows, so these run without SQL Server.
+"""
+
+import asyncio
+import gc
+
+import pytest
+from fake_tds import FakeServer, connect
+
+RESULTS = {
+    "FROM events": ([("id", "int")], [(i,) for i in range(1000)]),
+    "FROM users": ([("name", "nvarchar")], [("ada",), ("grace",)]),
+}
+
+
+@pytest.mark.asyncio
+async def test_aclose_discards_connection_with_unread_rows():
+    async with FakeServer(results=RESULTS) as server:
+        conn = connect(server)
+        stream = conn.query_stream("SELECT id FROM events")
+        async for row in stream:
+            assert row["id"] == 0
+            break
+        await stream.aclose()
+
+        result = await conn.query("SELECT name FROM users")
+
+        assert [row["name"] for row in result.rows()] == ["ada", "grace"]
+        assert len(server.logins) == 2
+
+
+@pytest.mark.asyncio
+async def test_dropping_stream_discards_connection_with_unread_rows():
+    async with FakeServer(results=RESULTS) as server:
+        conn = connect(server)
+        stream = conn.query_stream("SELECT id FROM events")
+        async for _ in stream:
+            break
+        del stream
+        gc.collect()
+        await asyncio.sleep(0)
+
+        result = await conn.query("SELECT name FROM users")
+
+        assert [row["name"] for row in result.rows()] == ["ada", "grace"]
+        assert len(server.logins) == 2
+
+
+@pytest.mark.asyncio
+async def test_finished_stream_keeps_connection():
+    async with FakeServer(results=RESULTS) as server:
+        conn = connect(server)
+        ids = [row["id"] async for row in conn.query_stream("SELECT id FROM events")]
+        assert ids == list(range(1000))
+
+        result = await conn.query("SELECT name FROM users")
+
+        assert len(result.rows()) == 2
+        assert len(server.logins) == 1
+
+
+@pytest.mark.asyncio
+async def test_fetch_chunks_aclose_discards_connection_with_unread_rows():
+    async with FakeServer(results=RESULTS) as server:
+        conn = connect(server)
+        chunks = conn.fetch_chunks("SELECT id FROM events", chunk_size=10)
+        async for chunk in chunks:
+            assert [row["id"] for row in chunk] == list(range(10))
+            break
+        await chunks.aclose()
+
+        result = await conn.query("SELECT name FROM users")
+
+        assert [row["name"] for row in result.rows()] == ["ada", "grace"]
+        assert len(server.logins) == 2
+
+
+@pytest.mark.asyncio
+async def test_dropping_fetch_chunks_discards_connection_with_unread_rows():
+    async with FakeServer(results=RESULTS) as server:
+        conn = connect(server)
+        chunks = conn.fetch_chunks("SELECT id FROM events", chunk_size=10)
+        async for _ in chunks:
+            break
+        del chunks
+        gc.collect()
+        await asyncio.sleep(0)
+
+        result = await conn.query("SELECT name FROM users")
+
+        assert len(result.rows()) == 2
+        assert len(server.logins) == 2
+
+
+async def _read_every_chunk(chunks):
+    # Stops at the last row, before the iterator has reported the end.
+    ids = []
+    while len(ids) < 1000:
+        ids.extend(row["id"] for row in await chunks.__anext__())
+    return ids
+
+
+@pytest.mark.asyncio
+async def test_fetch_chunks_aclose_keeps_connection_once_every_row_is_read():
+    # The response is still arriving when the last row has been read.
+    async with FakeServer(results=RESULTS, done_delay=0.5) as server:
+        conn = connect(server)
+        chunks = conn.fetch_chunks("SELECT id FROM events", chunk_size=100)
+        assert await _read_every_chunk(chunks) == list(range(1000))
+        await chunks.aclose()
+
+        await conn.query("SELECT name FROM users")
+
+        assert len(server.logins) == 1
+
+
+@pytest.mark.asyncio
+async def test_dropping_fetch_chunks_keeps_connection_once_every_row_is_read():
+    async with FakeServer(results=RESULTS, done_delay=0.5) as server:
+        conn = connect(server)
+        chunks = conn.fetch_chunks("SELECT id FROM events", chunk_size=100)
+        assert await _read_every_chunk(chunks) == list(range(1000))
+        del chunks
+        gc.collect()
+        await asyncio.sleep(0)
+
+        await conn.query("SELECT name FROM users")
+
+        assert len(server.logins) == 1
+
+
+@pytest.mark.asyncio
+async def test_dropping_stream_results_discards_connection_with_unread_sets():
+    batch = [([("id", "int")], [(i,) for i in range(10)])] * 3
+    async with FakeServer(results={**RESULTS, "FROM batches": batch}) as server:
+        conn = connect(server)
+        sets = conn.stream_results("SELECT id FROM batches")
+        async for result_set in sets:
+            assert len(result_set.rows()) == 10
+            break
+        del sets, result_set
+        gc.collect()
+        await asyncio.sleep(0)
+
+        result = await conn.query("SELECT name FROM users")
+
+        assert [row["name"] for row in result.rows()] == ["ada", "grace"]
+        assert len(server.logins) == 2
+
+
+@pytest.mark.asyncio
+async def test_stream_results_keeps_connection_when_every_set_is_read():
+    batch = [([("id", "int")], [(i,) for i in range(10)])] * 3
+    async with FakeServer(results={**RESULTS, "FROM batches": batch}) as server:
+        conn = connect(server)
+        sets = conn.stream_results("SELECT id FROM batches")
+        assert sets.execution_id
+        assert [len(rs.rows()) async for rs in sets] == [10, 10, 10]
+
+        await conn.query("SELECT name FROM users")
+
+        assert len(server.logins) == 1
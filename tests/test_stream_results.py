@@ -0,0 +1,60 @@
+"""Tests for Connection.stream_results() over multi-statement batches."""
+
+import pytest
+from conftest import Config
+
+from fastmssql import Connection, SqlError
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_stream_results_yields_each_result_set(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        sets = []
+        async for result_set in conn.stream_results(
+            "SELECT 1 AS a UNION ALL SELECT 2; SELECT @P1 AS b, @P2 AS c", ["x", 3]
+        ):
+            sets.append([row.to_dict() for row in result_set.rows()])
+
+    assert sets == [[{"a": 1}, {"a": 2}], [{"b": "x", "c": 3}]]
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_stream_results_mixed_dml_and_select(test_config: Config):
+    table = "test_stream_results_mixed"
+    async with Connection(test_config.connection_string) as conn:
+        await conn.execute(f"IF OBJECT_ID('{table}', 'U') IS NOT NULL DROP TABLE {table}")
+        await conn.execute(f"CREATE TABLE {table} (id INT)")
+        try:
+            script = (
+                f"INSERT INTO {table} VALUES (1), (2);"
+                f"SELECT COUNT(*) AS n FROM {table};"
+                f"DELETE FROM {table} WHERE id = 1;"
+                f"SELECT id FROM {table} WHERE id > 5;"
+                f"SELECT id FROM {table}"
+            )
+            sets = [rs async for rs in conn.stream_results(script)]
+
+            assert len(sets) == 3
+            assert sets[0].rows()[0]["n"] == 2
+            assert len(sets[1]) == 0  # empty result sets are still yielded
+            assert [r["id"] for r in sets[2].rows()] == [2]
+        finally:
+            await conn.execute(f"DROP TABLE {table}")
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_stream_results_error_after_first_set(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        stream = conn.stream_results("SELECT 1 AS a; SELECT 1 / 0 AS b")
+        with pytest.raises(SqlError):
+            async for _ in stream:
+                pass
+
+        # Abandoning a stream early leaves the pooled connection usable.
+        async for _ in conn.stream_results("SELECT 1 AS a; SELECT 2 AS b"):
+            break
+        result = await conn.query("SELECT 42 AS answer")
+        assert result.rows()[0]["answer"] == 42
@@ -0,0 +1,107 @@
+"""Tests for bulk_insert() on a Transaction and a PinnedConnection, loading #temp tables."""
+
+import pytest
+from conftest import Config
+from fake_tds import RPC, FakeServer, connect
+
+from fastmssql import Connection, InterfaceError, Transaction
+
+TABLE = "test_temp_bulk_target"
+ROWS = [["a", 1], ["b", 2], ["c", 3]]
+
+
+@pytest.mark.asyncio
+async def test_pinned_bulk_insert_runs_on_the_held_connection():
+    async with FakeServer() as server:
+        async with connect(server).acquire() as pinned:
+            await pinned.execute("CREATE TABLE #staging (sku NVARCHAR(10), qty INT)")
+            await pinned.bulk_insert("#staging", ["sku", "qty"], ROWS)
+        assert server.count(RPC) == 2
+        assert len(server.logins) == 1
+
+
+@pytest.mark.asyncio
+async def test_pinned_bulk_insert_outside_block_raises():
+    async with FakeServer() as server:
+        pinned = connect(server).acquire()
+        with pytest.raises(InterfaceError, match="not acquired"):
+            await pinned.bulk_insert("#staging", ["sku", "qty"], ROWS)
+
+
+@pytest.mark.asyncio
+async def test_rows_are_checked_before_the_connection_is_used():
+    async with FakeServer() as server:
+        async with connect(server).acquire() as pinned:
+            with pytest.raises(ValueError, match="Row has 1 values but 2 columns"):
+                await pinned.bulk_insert("#staging", ["sku", "qty"], [["a"]])
+        assert server.count(RPC) == 0
+
+
+async def _create_target(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        await conn.execute(f"IF OBJECT_ID('{TABLE}', 'U') IS NOT NULL DROP TABLE {TABLE}")
+        await conn.execute(f"CREATE TABLE {TABLE} (sku NVARCHAR(10) PRIMARY KEY, qty INT)")
+        await conn.execute(f"INSERT INTO {TABLE} VALUES ('a', 0)")
+
+
+async def _target_rows(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        result = await conn.query(f"SELECT sku, qty FROM {TABLE} ORDER BY sku")
+        return [(row["sku"], row["qty"]) for row in result]
+
+
+MERGE = f"""
+MERGE {TABLE} AS t USING #staging AS s ON t.sku = s.sku
+WHEN MATCHED THEN UPDATE SET qty = s.qty
+WHEN NOT MATCHED THEN INSERT (sku, qty) VALUES (s.sku, s.qty);
+"""
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+@pytest.mark.parametrize("bulk_copy", [False, True])
+async def test_load_then_merge_commits_together(test_config: Config, bulk_copy):
+    await _create_target(test_config)
+    tx = Transaction(test_config.connection_string)
+    try:
+        async with tx.transaction():
+            await tx.execute("CREATE TABLE #staging (sku NVARCHAR(10), qty INT)")
+            assert await tx.bulk_insert("#staging", ["sku", "qty"], ROWS, bulk_copy=bulk_copy) == 3
+            await tx.execute(MERGE)
+    finally:
+        await tx.close()
+
+    assert await _target_rows(test_config) == [("a", 1), ("b", 2), ("c", 3)]
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_load_then_merge_rolls_back_together(test_config: Config):
+    await _create_target(test_config)
+    tx = Transaction(test_config.connection_string)
+    try:
+        with pytest.raises(RuntimeError):
+            async with tx.transaction():
+                await tx.execute("CREATE TABLE #staging (sku NVARCHAR(10), qty INT)")
+                await tx.bulk_insert("#staging", ["sku", "qty"], ROWS, bulk_copy=True)
+                await tx.execute(MERGE)
+                raise RuntimeError("abort")
+    finally:
+        await tx.close()
+
+    assert await _target_rows(test_config) == [("a", 0)]
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_pinned_load_with_verify_key(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        async with conn.acquire() as pinned:
+            await pinned.execute("CREATE TABLE #staging (sku NVARCHAR(10), qty INT)")
+            report = await pinned.bulk_insert(
+                "#staging", ["sku", "qty"], ROWS, bulk_copy=True, verify_key="sku"
+            )
+            await pinned.execute("DROP TABLE #staging")
+
+    assert report["rows_inserted"] == 3
+    assert report["verified"] is True
@@ -0,0 +1,61 @@
+"""
+Tests for decoding TIME columns as datetime.timedelta (time_as_timedelta=True).
+
+The fake TDS server returns the result sets, so these run without SQL Server.
+"""
+
+from datetime import date, datetime, time, timedelta
+
+import pytest
+from fake_tds import FakeServer, connect
+
+SHIFTS = {
+    "FROM shifts": (
+        [("day", "date"), ("opens_at", "time"), ("closes_at", "time")],
+        [(date(2024, 1, 1), time(8, 15, 0, 250000), time(17, 45)), (None, None, None)],
+    )
+}
+
+
+@pytest.mark.asyncio
+async def test_time_is_time_by_default():
+    async with FakeServer(results=SHIFTS) as server:
+        rows = (await connect(server).query("SELECT * FROM shifts")).rows()
+
+    assert rows[0]["opens_at"] == time(8, 15, 0, 250000)
+    assert rows[0]["day"] == date(2024, 1, 1)
+
+
+@pytest.mark.asyncio
+async def test_time_as_timedelta_since_midnight():
+    async with FakeServer(results=SHIFTS) as server:
+        conn = connect(server, time_as_timedelta=True)
+        rows = (await conn.query("SELECT * FROM shifts")).rows()
+
+    opens_at, closes_at = rows[0]["opens_at"], rows[0]["closes_at"]
+    assert opens_at == timedelta(hours=8, minutes=15, microseconds=250000)
+    assert closes_at - opens_at == timedelta(hours=9, minutes=29, seconds=59.75)
+    assert datetime(2024, 1, 1) + opens_at == datetime(2024, 1, 1, 8, 15, 0, 250000)
+    assert rows[1]["opens_at"] is None
+
+
+@pytest.mark.asyncio
+async def test_get_time_returns_time_either_way():
+    async with FakeServer(results=SHIFTS) as server:
+        conn = connect(server, time_as_timedelta=True)
+        rows = (await conn.query("SELECT * FROM shifts")).rows()
+
+    assert rows[0].get_time("opens_at") == time(8, 15, 0, 250000)
+    assert rows[1].get_time("opens_at") is None
+
+
+@pytest.mark.asyncio
+async def test_with_options_overrides_time_as_timedelta():
+    async with FakeServer(results=SHIFTS) as server:
+        conn = connect(server, time_as_timedelta=True)
+        plain = conn.with_options(time_as_timedelta=False)
+        rows = (await plain.query("SELECT * FROM shifts")).rows()
+        streamed = [row async for row in conn.query_stream("SELECT * FROM shifts")]
+
+    assert rows[0]["opens_at"] == time(8, 15, 0, 250000)
+    assert streamed[0]["opens_at"] == timedelta(hours=8, minutes=15, microseconds=250000)
@@ -0,0 +1,42 @@
+"""
+Tests for the process-wide Azure token cache.
+
+Credentials for the same identity share one cached token, so separate credential
+objects (and the connections built from them) fetch it from the token endpoint once.
+"""
+
+import pytest
+from fake_identity import IdentityEndpoint
+from fake_tds import FakeServer, connect
+
+from fastmssql import AzureCredential
+
+
+async def _connect(server, credential):
+    conn = connect(server, azure_credential=credential)
+    await conn.execute("SET NOCOUNT ON")
+    return conn
+
+
+@pytest.mark.asyncio
+async def test_credentials_for_same_identity_share_token():
+    with IdentityEndpoint() as identity:
+        async with FakeServer() as server:
+            first = AzureCredential.managed_identity("app", endpoint=identity.url)
+            second = AzureCredential.managed_identity("app", endpoint=identity.url)
+            connections = [await _connect(server, first), await _connect(server, second)]
+
+    assert len(connections) == 2
+    assert len(identity.requests) == 1
+
+
+@pytest.mark.asyncio
+async def test_different_identities_get_their_own_tokens():
+    with IdentityEndpoint() as identity:
+        async with FakeServer() as server:
+            app = AzureCredential.managed_identity("app", endpoint=identity.url)
+            other = AzureCredential.managed_identity("other", endpoint=identity.url)
+            connections = [await _connect(server, app), await _connect(server, other)]
+
+    assert len(connections) == 2
+    assert len(identity.requests) == 2
@@ -0,0 +1,56 @@
+"""
+Tests that pooled and dedicated connections log in with a fresh token after the
+server refuses the cached one.
+"""
+
+import pytest
+from fake_identity import IdentityEndpoint
+from fake_tds import FakeServer, connect
+
+from fastmssql import AzureCredential, Transaction
+
+
+@pytest.mark.asyncio
+async def test_refused_token_is_not_reused():
+    with IdentityEndpoint() as identity:
+        cred = AzureCredential.service_principal(
+            "client", "secret", "tenant", authority_host=identity.authority
+        )
+        async with FakeServer(login_error=18456, message="Token is expired.") as server:
+            conn = connect(server, azure_credential=cred)
+            for _ in range(2):
+                with pytest.raises(Exception, match="Token is expired"):
+                    await conn.execute("SELECT 1")
+
+    # Each login after the refusal asked for a new token.
+    assert len(identity.forms) >= 2
+
+
+@pytest.mark.asyncio
+async def test_transaction_does_not_reuse_a_refused_token():
+    with IdentityEndpoint() as identity:
+        cred = AzureCredential.service_principal(
+            "client", "secret", "tenant", authority_host=identity.authority
+        )
+        async with FakeServer(login_error=18456, message="Token is expired.") as server:
+            conn_str = server.connection_string.replace("User Id=sa;Password=x;", "")
+            for _ in range(2):
+                transaction = Transaction(conn_str, azure_credential=cred)
+                with pytest.raises(Exception, match="Token is expired"):
+                    await transaction.begin()
+
+    assert len(identity.forms) == 2
+
+
+@pytest.mark.asyncio
+async def test_accepted_token_is_reused_across_connections():
+    with IdentityEndpoint() as identity:
+        cred = AzureCredential.service_principal(
+            "client", "secret", "tenant", authority_host=identity.authority
+        )
+        async with FakeServer() as server:
+            for _ in range(2):
+                await connect(server, azure_credential=cred).execute("SET NOCOUNT ON")
+            assert len(server.logins) == 2
+
+    assert len(identity.forms) == 1
@@ -0,0 +1,50 @@
+"""
+Tests for requesting Azure tokens for a resource other than Azure public SQL.
+"""
+
+from urllib.parse import parse_qs, urlparse
+
+import pytest
+from fake_identity import IdentityEndpoint
+from fake_tds import FakeServer, connect
+
+from fastmssql import AzureCredential
+
+
+@pytest.mark.parametrize(
+    "resource",
+    [
+        "https://database.usgovcloudapi.net/",
+        "https://database.usgovcloudapi.net",
+        "https://database.usgovcloudapi.net/.default",
+    ],
+)
+def test_resource_is_normalized(resource):
+    cred = AzureCredential.managed_identity(resource=resource)
+    assert cred.config["resource"] == "https://database.usgovcloudapi.net/"
+
+
+@pytest.mark.parametrize("resource", ["database.usgovcloudapi.net", "http://example.com/", ""])
+def test_resource_must_be_https_url(resource):
+    with pytest.raises(ValueError, match="resource"):
+        AzureCredential.default(resource=resource)
+    with pytest.raises(ValueError, match="resource"):
+        AzureCredential.service_principal("client", "secret", "tenant", resource=resource)
+
+
+def test_default_resource_is_not_in_config():
+    assert "resource" not in AzureCredential.managed_identity().config
+
+
+@pytest.mark.asyncio
+async def test_token_is_requested_for_resource():
+    with IdentityEndpoint() as identity:
+        cred = AzureCredential.managed_identity(
+            endpoint=identity.url, resource="https://database.chinacloudapi.cn/.default"
+        )
+        async with FakeServer() as server:
+            conn = connect(server, azure_credential=cred)
+            await conn.execute("SET NOCOUNT ON")
+
+    path, _ = identity.requests[0]
+    assert parse_qs(urlparse(path).query)["resource"] == ["https://database.chinacloudapi.cn/"]
@@ -0,0 +1,45 @@
+"""Tests for Connection.touched_tables()."""
+
+import pytest
+from conftest import Config
+
+from fastmssql import Connection, PoolConfig
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_touched_tables_reads_and_writes(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        await conn.execute(
+            "IF OBJECT_ID('test_touched_orders', 'U') IS NOT NULL DROP TABLE test_touched_orders; "
+            "IF OBJECT_ID('test_touched_customers', 'U') IS NOT NULL DROP TABLE test_touched_customers; "
+            "CREATE TABLE test_touched_customers (id INT PRIMARY KEY, region VARCHAR(10)); "
+            "CREATE TABLE test_touched_orders (id INT PRIMARY KEY, customer_id INT, status VARCHAR(10))"
+        )
+        try:
+            tables = await conn.touched_tables(
+                "UPDATE test_touched_orders SET status = 'shipped' "
+                "WHERE customer_id IN (SELECT id FROM test_touched_customers WHERE region = @P1)",
+                ["EMEA"],
+            )
+            assert any(name.endswith(".test_touched_orders") for name in tables["written"])
+            assert any(name.endswith(".test_touched_customers") for name in tables["read"])
+            assert not any(name.endswith(".test_touched_customers") for name in tables["written"])
+
+            # Compiled, not run: the table is still empty.
+            await conn.touched_tables("INSERT INTO test_touched_customers VALUES (1, 'EMEA')")
+            result = await conn.query("SELECT COUNT(*) AS n FROM test_touched_customers")
+            assert result.fetchone()["n"] == 0
+        finally:
+            await conn.execute("DROP TABLE test_touched_orders; DROP TABLE test_touched_customers")
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_touched_tables_leaves_connection_usable(test_config: Config):
+    config = PoolConfig(max_size=1, min_idle=0)
+    async with Connection(test_config.connection_string, pool_config=config) as conn:
+        with pytest.raises(Exception):
+            await conn.touched_tables("SELECT * FROM test_touched_does_not_exist")
+        await conn.touched_tables("SELECT 1 AS one")
+        assert (await conn.query("SELECT 1 AS one")).fetchone()["one"] == 1
@@ -822,3 +822,30 @@ def test_connection_string_parsing_azure_sql():
     conn_str = "Server=myserver.database.windows.net,1433;Database=mydb;User Id=user@myserver;Password=test;Encrypt=true"
     Transaction(connection_string=conn_str)
     print("✓ Parsed Azure SQL connection string")
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_transaction_scope_keeps_connection_open(test_config: Config):
+    """transaction() commits or rolls back each unit of work without closing the session."""
+    conn = Transaction(test_config.connection_string)
+    try:
+        await conn.execute("CREATE TABLE #scope_test (id INT)")
+
+        async with conn.transaction() as tx:
+            assert tx is conn
+            await conn.execute("INSERT INTO #scope_test VALUES (1)")
+            assert (await conn.query("SELECT @@TRANCOUNT AS c")).fetchone()["c"] == 1
+
+        with pytest.raises(RuntimeError):
+            async with conn.transaction():
+                await conn.execute("INSERT INTO #scope_test VALUES (2)")
+                raise RuntimeError("abort")
+
+        assert conn.is_connected()
+        result = await conn.query("SELECT COUNT(*) AS n, @@TRANCOUNT AS c FROM #scope_test")
+        row = result.fetchone()
+        assert row["n"] == 1
+        assert row["c"] == 0
+    finally:
+        await conn.close()
@@ -0,0 +1,95 @@
+"""Tests for the typed FastRow getters (get_int, get_str, ...) and DataError."""
+
+import uuid
+from datetime import date, datetime, time
+from decimal import Decimal
+
+import pytest
+from conftest import Config
+
+from fastmssql import Connection, ConversionError, DataError
+
+QUERY = """
+    SELECT CAST(42 AS INT) AS i,
+           CAST(9000000000 AS BIGINT) AS big,
+           CAST(7 AS DECIMAL(10, 0)) AS whole,
+           CAST(1.25 AS DECIMAL(10, 2)) AS dec,
+           CAST(0.5 AS FLOAT) AS f,
+           N'hello' AS s,
+           CAST(1 AS BIT) AS b,
+           CAST(0x0102 AS VARBINARY(2)) AS bin,
+           CAST('2024-05-01T09:30:00' AS DATETIME2) AS dt,
+           CAST('2024-05-01' AS DATE) AS d,
+           CAST('09:30:00' AS TIME) AS t,
+           CAST('6F9619FF-8B86-D011-B42D-00C04FC964FF' AS UNIQUEIDENTIFIER) AS u,
+           CAST(NULL AS INT) AS missing
+"""
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_typed_getters_return_expected_types(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        row = (await conn.query(QUERY)).fetchone()
+
+    assert row.get_int("i") == 42
+    assert row.get_int("big") == 9_000_000_000
+    assert row.get_int("whole") == 7
+    assert row.get_int("b") == 1
+    assert row.get_float("dec") == 1.25
+    assert row.get_float("i") == 42.0
+    assert row.get_decimal("dec") == Decimal("1.25")
+    assert row.get_decimal("i") == Decimal(42)
+    assert row.get_decimal("f") == Decimal("0.5")
+    assert row.get_str("s") == "hello"
+    assert row.get_bool("b") is True
+    assert row.get_bytes("bin") == b"\x01\x02"
+    assert row.get_datetime("dt") == datetime(2024, 5, 1, 9, 30)
+    assert row.get_date("d") == date(2024, 5, 1)
+    assert row.get_date("dt") == date(2024, 5, 1)
+    assert row.get_time("t") == time(9, 30)
+    assert row.get_uuid("u") == uuid.UUID("6f9619ff-8b86-d011-b42d-00c04fc964ff")
+    assert row.get_int(0) == 42
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_typed_getters_return_none_for_null(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        row = (await conn.query(QUERY)).fetchone()
+
+    for getter in (row.get_int, row.get_str, row.get_decimal, row.get_datetime, row.get_uuid):
+        assert getter("missing") is None
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_typed_getters_raise_data_error(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        row = (await conn.query(QUERY)).fetchone()
+
+    with pytest.raises(DataError) as excinfo:
+        row.get_int("s")
+    assert excinfo.value.column == "s"
+    assert "str" in excinfo.value.message
+
+    for getter, column in [
+        (row.get_int, "dec"),
+        (row.get_float, "s"),
+        (row.get_decimal, "b"),
+        (row.get_str, "i"),
+        (row.get_bool, "i"),
+        (row.get_bytes, "s"),
+        (row.get_datetime, "d"),
+        (row.get_date, "t"),
+        (row.get_time, "dt"),
+        (row.get_uuid, "s"),
+    ]:
+        with pytest.raises(DataError):
+            getter(column)
+
+    # DataError is a ConversionError, and unknown columns still raise ValueError.
+    with pytest.raises(ConversionError):
+        row.get_str("i")
+    with pytest.raises(ValueError):
+        row.get_int("no_such_column")
@@ -0,0 +1,53 @@
+"""Tests for Parameter.null() and NULLs bound with a declared SQL type."""
+
+import pytest
+from conftest import Config
+
+from fastmssql import Connection, Parameter, Parameters
+
+
+@pytest.mark.parametrize(
+    "sql_type", ["VARBINARY(MAX)", "datetime", "DECIMAL(18, 2)", " nvarchar (50)", "UNIQUEIDENTIFIER"]
+)
+def test_null_parameter_keeps_type(sql_type):
+    param = Parameter.null(sql_type)
+    assert param.value is None
+    assert param.sql_type == sql_type
+    assert not param.is_expanded
+
+
+@pytest.mark.parametrize("sql_type", ["", "VARBINARYX", "TABLE", "(MAX)"])
+def test_null_parameter_rejects_unknown_type(sql_type):
+    with pytest.raises(ValueError, match="NULL parameter"):
+        Parameter.null(sql_type)
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_typed_nulls_insert_into_typed_columns(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        result = await conn.query(
+            "DECLARE @t TABLE (d DATE, g UNIQUEIDENTIFIER, b VARBINARY(MAX), t TIME); "
+            "INSERT INTO @t VALUES (@P1, @P2, @P3, @P4); "
+            "SELECT d, g, b, t FROM @t",
+            Parameters(
+                Parameter.null("DATE"),
+                Parameter.null("UNIQUEIDENTIFIER"),
+                Parameter.null("VARBINARY(MAX)"),
+                Parameter(None, "TIME"),
+            ),
+        )
+        row = result.fetchone()
+
+    assert [row["d"], row["g"], row["b"], row["t"]] == [None, None, None, None]
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_untyped_null_cannot_bind_as_date(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        with pytest.raises(Exception):
+            await conn.query(
+                "DECLARE @t TABLE (d DATE); INSERT INTO @t VALUES (@P1); SELECT d FROM @t",
+                [None],
+            )
@@ -0,0 +1,39 @@
+"""Tests for binding uuid.UUID parameters as UNIQUEIDENTIFIER."""
+
+import uuid
+
+import pytest
+from conftest import Config
+
+from fastmssql import Connection
+
+VALUE = uuid.UUID("0f8fad5b-d9cb-469f-a165-70867728950e")
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_uuid_binds_as_uniqueidentifier(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        result = await conn.query(
+            "SELECT @P1 AS v, CAST(SQL_VARIANT_PROPERTY(@P1, 'BaseType') AS NVARCHAR(32)) AS t, "
+            "CASE WHEN @P1 = CAST(@P2 AS UNIQUEIDENTIFIER) THEN 1 ELSE 0 END AS same",
+            [VALUE, str(VALUE)],
+        )
+        row = result.fetchone()
+    assert row["t"] == "uniqueidentifier"
+    assert row["v"] == str(VALUE)
+    assert row["same"] == 1
+
+
+@pytest.mark.integration
+@pytest.mark.asyncio
+async def test_uuid_round_trips_through_a_table(test_config: Config):
+    async with Connection(test_config.connection_string) as conn:
+        values = [uuid.uuid4() for _ in range(3)]
+        result = await conn.query(
+            "DECLARE @t TABLE (id UNIQUEIDENTIFIER); "
+            "INSERT INTO @t VALUES (@P1), (@P2), (@P3); "
+            "SELECT id FROM @t WHERE id = @P2",
+            values,
+        )
+    assert [row["id"] for row in result.fetchall()] == [str(values[1])]
@@ -0,0 +1,69 @@
+"""
+Tests for fastmssql.enable_wire_debug() (TDS packet and token tracing to a file).
+
+The fake TDS server answers in plain TDS, so the traced traffic is readable
+without SQL Server.
+"""
+
+import pytest
+from fake_tds import FakeServer, connect
+
+import fastmssql
+
+RESULTS = {"FROM secrets": ([("id", "int"), ("name", "nvarchar")], [(1, "hunter2")])}
+
+
+@pytest.fixture
+def trace_path(tmp_path):
+    path = tmp_path / "wire.log"
+    yield path
+    fastmssql.enable_wire_debug(None)
+
+
+@pytest.mark.asyncio
+async def test_wire_debug_logs_packets_and_tokens(trace_path):
+    fastmssql.enable_wire_debug(trace_path)
+    async with FakeServer(results=RESULTS) as server:
+        conn = connect(server)
+        await conn.execute("UPDATE t SET x = 1")
+        await conn.query("SELECT id, name FROM secrets")
+        await conn.simple_query("SELECT id, name FROM secrets")
+
+    log = trace_path.read_text()
+    assert "-> PRELOGIN packet" in log
+    assert "-> LOGIN7 packet" in log
+    assert "<- tokens: LOGINACK (TDS 0x74000004" in log
+    # execute() and query() send RPC requests; simple_query() sends a SQL batch.
+    assert "-> RPC packet" in log
+    assert "-> SQL batch packet" in log
+    assert "tokens: DONE" in log
+    assert "COLMETADATA (2 columns" in log
+    # No values without include_values.
+    assert "hunter2" not in log
+    assert "payload" not in log
+
+
+@pytest.mark.asyncio
+async def test_wire_debug_include_values_adds_messages(trace_path):
+    fastmssql.enable_wire_debug(trace_path, include_values=True)
+    async with FakeServer(login_error=18456, message="Login failed for user 'sa'") as server:
+        with pytest.raises(Exception):
+            await connect(server).execute("SELECT 1")
+
+    log = trace_path.read_text()
+    assert "ERROR 18456 (class 14, state 1) \"Login failed for user 'sa'\"" in log
+    assert "DONE(error)" in log
+    assert "-> PRELOGIN packet" in log and ", payload " in log
+    # LOGIN7 carries the password and is never dumped.
+    login_lines = [line for line in log.splitlines() if "LOGIN7 packet" in line]
+    assert login_lines and all("payload" not in line for line in login_lines)
+
+
+@pytest.mark.asyncio
+async def test_wire_debug_off_by_default_and_after_disable(trace_path):
+    fastmssql.enable_wire_debug(trace_path)
+    fastmssql.enable_wire_debug(None)
+    async with FakeServer() as server:
+        await connect(server).execute("SELECT 1")
+
+    assert not trace_path.exists() or trace_path.read_text() == ""
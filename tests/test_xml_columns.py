@@ -0,0 +1,87 @@
+"""
+Tests for returning XML columns as parsed elements (Connection(parse_xml=...)).
+
+The fake TDS server returns the result sets, so these run without SQL Server.
+"""
+
+import xml.etree.ElementTree as ET
+
+import pytest
+from fake_tds import FakeServer, connect
+
+from fastmssql import Connection, DataError
+
+DOCUMENT = '<order id="7"><line sku="A1" qty="2"/></order>'
+
+RESULTS = {
+    "FROM orders": (
+        [("id", "int"), ("doc", "xml")],
+        [
+            (1, DOCUMENT),
+            (2, '\ufeff<?xml version="1.0" encoding="UTF-16"?>\n<note>ü</note>'),
+            (3, None),
+        ],
+    ),
+    "FROM broken": (
+        [("doc", "xml")],
+        [("<a/><b/>",)],
+    ),
+}
+
+
+@pytest.mark.asyncio
+async def test_xml_stays_a_string_by_default():
+    async with FakeServer(results=RESULTS) as server:
+        result = await connect(server).query("SELECT * FROM orders")
+
+    assert result.rows()[0]["doc"] == DOCUMENT
+
+
+@pytest.mark.asyncio
+async def test_parse_xml_returns_elements():
+    async with FakeServer(results=RESULTS) as server:
+        result = await connect(server, parse_xml=True).query("SELECT * FROM orders")
+
+    first, second, third = result.rows()
+    assert isinstance(first["doc"], ET.Element)
+    assert first["doc"].get("id") == "7"
+    assert first["doc"].find("line").get("qty") == "2"
+    # The byte order mark and UTF-16 declaration are dropped before parsing.
+    assert second["doc"].tag == "note"
+    assert second["doc"].text == "ü"
+    assert third["doc"] is None
+
+
+@pytest.mark.asyncio
+async def test_parse_xml_with_options():
+    async with FakeServer(results=RESULTS) as server:
+        conn = connect(server).with_options(parse_xml="etree")
+        result = await conn.query("SELECT * FROM orders")
+
+    assert result.rows()[0]["doc"].tag == "order"
+
+
+@pytest.mark.asyncio
+async def test_parse_xml_lxml():
+    etree = pytest.importorskip("lxml.etree")
+    async with FakeServer(results=RESULTS) as server:
+        result = await connect(server, parse_xml="lxml").query("SELECT * FROM orders")
+
+    rows = result.rows()
+    assert isinstance(rows[0]["doc"], etree._Element)
+    assert rows[1]["doc"].text == "ü"
+
+
+@pytest.mark.asyncio
+async def test_xml_fragment_raises_data_error():
+    async with FakeServer(results=RESULTS) as server:
+        result = await connect(server, parse_xml=True).query("SELECT * FROM broken")
+
+    # Rows are decoded when read, so the error surfaces there.
+    with pytest.raises(DataError, match="well-formed XML document"):
+        result.rows()
+
+
+def test_parse_xml_rejects_unknown_value():
+    with pytest.raises(ValueError, match="parse_xml"):
+        Connection("Server=localhost;Database=master;User Id=sa;Password=x", parse_xml="dom")